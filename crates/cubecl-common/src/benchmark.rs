@@ -48,6 +48,56 @@ impl BenchmarkDurations {
             / self.durations.len() as u32;
         var
     }
+
+    /// Returns the variance of the durations in seconds squared.
+    ///
+    /// It isn't taken from the [variance duration](Self::variance_duration), since squared
+    /// durations of a few microseconds are rounded to zero when stored in a [Duration].
+    fn variance_secs(&self, mean: Duration) -> f64 {
+        let mean = mean.as_secs_f64();
+
+        self.durations
+            .iter()
+            .map(|duration| {
+                let tmp = duration.as_secs_f64() - mean;
+                tmp * tmp
+            })
+            .sum::<f64>()
+            / self.durations.len() as f64
+    }
+
+    /// Returns the durations without the outliers, which are the durations further than
+    /// `num_std_dev` standard deviations from the median.
+    ///
+    /// The median is used as the center instead of the mean since it isn't skewed by the outliers.
+    /// Every duration is kept when the threshold isn't positive or when no duration is within it.
+    pub fn without_outliers(&self, num_std_dev: f64) -> Self {
+        if num_std_dev.is_nan() || num_std_dev <= 0.0 {
+            return self.clone();
+        }
+
+        let computed = BenchmarkComputations::new(self);
+        let median = computed.median.as_secs_f64();
+        // Compare the squared distances to avoid computing a square root, which isn't available
+        // without std.
+        let max_distance_squared = self.variance_secs(computed.mean) * num_std_dev * num_std_dev;
+
+        let durations = self
+            .durations
+            .iter()
+            .filter(|duration| {
+                let distance = duration.as_secs_f64() - median;
+                distance * distance <= max_distance_squared
+            })
+            .copied()
+            .collect::<Vec<_>>();
+
+        if durations.is_empty() {
+            return self.clone();
+        }
+
+        Self { durations }
+    }
 }
 
 impl Display for BenchmarkDurations {
@@ -119,15 +169,24 @@ pub trait Benchmark {
     ///
     /// # Notes
     ///
-    /// This should not include warmup, the benchmark will be run at least one time without
-    /// measuring the execution time.
+    /// This should not include warmup, the benchmark will be run [num_warmup](Benchmark::num_warmup)
+    /// times without measuring the execution time.
     fn prepare(&self) -> Self::Args;
     /// Execute the benchmark and returns the time it took to complete.
     fn execute(&self, args: Self::Args);
+    /// Number of executions before measuring the execution time.
+    fn num_warmup(&self) -> usize {
+        1
+    }
     /// Number of samples per run required to have a statistical significance.
     fn num_samples(&self) -> usize {
         10
     }
+    /// Whether the run can stop before collecting all the samples, called after every sample with
+    /// the durations collected so far.
+    fn should_stop(&self, _durations: &[Duration]) -> bool {
+        false
+    }
     /// Name of the benchmark, should be short and it should match the name
     /// defined in the crate Cargo.toml
     fn name(&self) -> String;
@@ -141,40 +200,161 @@ pub trait Benchmark {
     }
     /// Wait for computed to be over
     fn sync(&self);
+    /// The method used to measure the duration of the samples.
+    fn timing_method(&self) -> TimingMethod {
+        TimingMethod::System
+    }
+    /// Wait for computed to be over and returns the time the device spent executing the work
+    /// since the last call, used when timing with [TimingMethod::Device].
+    fn sync_elapsed(&self) -> TimestampsResult {
+        Err(TimestampsError::Unavailable)
+    }
     /// Run the benchmark a number of times.
     fn run(&self) -> BenchmarkDurations {
-        #[cfg(not(feature = "std"))]
-        panic!("Attempting to run benchmark in a no-std environment");
+        match self.timing_method() {
+            TimingMethod::System => {
+                #[cfg(not(feature = "std"))]
+                panic!("Attempting to run benchmark in a no-std environment");
 
-        #[cfg(feature = "std")]
-        {
-            // Warmup
-            let args = self.prepare();
+                #[cfg(feature = "std")]
+                self.run_with(&mut SystemTiming::new(|| self.sync()))
+            }
+            TimingMethod::Device => self.run_with(&mut DeviceTiming::new(|| self.sync_elapsed())),
+        }
+    }
+    /// Run the benchmark a number of times, measuring the samples with the given timing source.
+    fn run_with(&self, timing: &mut dyn TimingSource) -> BenchmarkDurations {
+        // Warmup
+        let args = self.prepare();
 
+        for _ in 0..self.num_warmup() {
             self.execute(args.clone());
             self.sync();
+        }
 
-            let mut durations = Vec::with_capacity(self.num_samples());
+        let mut durations = Vec::with_capacity(self.num_samples());
 
-            for _ in 0..self.num_samples() {
-                // Prepare
-                self.sync();
+        for _ in 0..self.num_samples() {
+            // Execute the benchmark
+            timing.start();
+            self.execute(args.clone());
+            let duration = timing
+                .elapsed()
+                .unwrap_or_else(|err| panic!("Unable to time the benchmark: {err}"));
 
-                // Execute the benchmark
-                let start = Instant::now();
-                self.execute(args.clone());
-                self.sync();
-                let end = Instant::now();
+            // Register the duration
+            durations.push(duration);
 
-                // Register the duration
-                durations.push(end - start);
+            if self.should_stop(&durations) {
+                break;
             }
+        }
+
+        BenchmarkDurations { durations }
+    }
+}
+
+/// How the duration of the benchmark samples is measured.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimingMethod {
+    /// Measure the time on the host from the execution until the synchronization, which includes
+    /// the launch and submission overhead.
+    #[default]
+    System,
+    /// Measure the time spent by the device executing the work, using timestamps.
+    Device,
+}
+
+/// Error that can happen when measuring the time spent on the device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimestampsError {
+    /// The timestamps aren't enabled.
+    Disabled,
+    /// The device doesn't support timestamps.
+    Unavailable,
+    /// Any other error.
+    Unknown(String),
+}
 
-            BenchmarkDurations { durations }
+impl Display for TimestampsError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Disabled => f.write_str("Timestamps are disabled"),
+            Self::Unavailable => f.write_str("Timestamps are unavailable on the device"),
+            Self::Unknown(reason) => write!(f, "Unable to get the timestamps: {reason}"),
         }
     }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for TimestampsError {}
+
+/// Result of a time measurement on the device.
+pub type TimestampsResult = Result<Duration, TimestampsError>;
+
+/// A source of time used to measure the duration of the benchmark samples.
+pub trait TimingSource {
+    /// Start measuring, the work submitted after this call is included in the next
+    /// [elapsed](TimingSource::elapsed) duration.
+    fn start(&mut self);
+    /// Wait for the work submitted since [start](TimingSource::start) to be over and returns its
+    /// duration.
+    fn elapsed(&mut self) -> TimestampsResult;
+}
+
+/// Measure the time on the host, synchronizing with the device before and after the work.
+#[cfg(feature = "std")]
+pub struct SystemTiming<F: FnMut()> {
+    sync: F,
+    start: Option<Instant>,
+}
+
+#[cfg(feature = "std")]
+impl<F: FnMut()> SystemTiming<F> {
+    /// Create a new system timing with the given synchronization function.
+    pub fn new(sync: F) -> Self {
+        Self { sync, start: None }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: FnMut()> TimingSource for SystemTiming<F> {
+    fn start(&mut self) {
+        (self.sync)();
+        self.start = Some(Instant::now());
+    }
+
+    fn elapsed(&mut self) -> TimestampsResult {
+        (self.sync)();
+        let start = self.start.take().expect("The timing should be started.");
+        Ok(Instant::now() - start)
+    }
+}
+
+/// Measure the time on the device with a function returning the time spent executing the work
+/// since its last call.
+pub struct DeviceTiming<F: FnMut() -> TimestampsResult> {
+    sync_elapsed: F,
+}
+
+impl<F: FnMut() -> TimestampsResult> DeviceTiming<F> {
+    /// Create a new device timing with the given function.
+    pub fn new(sync_elapsed: F) -> Self {
+        Self { sync_elapsed }
+    }
+}
+
+impl<F: FnMut() -> TimestampsResult> TimingSource for DeviceTiming<F> {
+    fn start(&mut self) {
+        // Discard the work executed before the start.
+        let _ = (self.sync_elapsed)();
+    }
+
+    fn elapsed(&mut self) -> TimestampsResult {
+        (self.sync_elapsed)()
+    }
+}
+
 /// Result of a benchmark run, with metadata
 #[derive(Default, Clone)]
 pub struct BenchmarkResult {
@@ -304,4 +484,116 @@ mod tests {
         let variance = durations.variance_duration(mean);
         assert_eq!(variance, Duration::from_secs(200));
     }
+
+    #[test]
+    fn test_without_outliers_removes_slow_samples() {
+        let mut durations = vec![Duration::from_millis(10); 9];
+        durations.push(Duration::from_millis(100));
+        let durations = BenchmarkDurations { durations };
+
+        let durations = durations.without_outliers(2.0);
+        assert_eq!(durations.durations, vec![Duration::from_millis(10); 9]);
+    }
+
+    #[test]
+    fn test_without_outliers_keeps_identical_samples() {
+        let durations = BenchmarkDurations {
+            durations: vec![Duration::from_millis(10); 5],
+        };
+
+        let durations = durations.without_outliers(2.0);
+        assert_eq!(durations.durations.len(), 5);
+    }
+
+    #[test]
+    fn test_without_outliers_keeps_every_sample_when_threshold_isnt_positive() {
+        let mut durations = vec![Duration::from_millis(10); 9];
+        durations.push(Duration::from_millis(100));
+        let durations = BenchmarkDurations { durations };
+
+        for threshold in [0.0, -2.0, f64::NAN] {
+            let filtered = durations.without_outliers(threshold);
+            assert_eq!(filtered.durations, durations.durations);
+        }
+    }
+
+    struct MockClock {
+        durations: Vec<Duration>,
+        num_started: usize,
+    }
+
+    impl TimingSource for MockClock {
+        fn start(&mut self) {
+            self.num_started += 1;
+        }
+
+        fn elapsed(&mut self) -> TimestampsResult {
+            Ok(self.durations.remove(0))
+        }
+    }
+
+    struct MockBenchmark {
+        num_executions: core::cell::Cell<usize>,
+    }
+
+    impl Benchmark for MockBenchmark {
+        type Args = ();
+
+        fn prepare(&self) -> Self::Args {}
+
+        fn execute(&self, _args: Self::Args) {
+            self.num_executions.set(self.num_executions.get() + 1);
+        }
+
+        fn num_warmup(&self) -> usize {
+            2
+        }
+
+        fn num_samples(&self) -> usize {
+            3
+        }
+
+        fn name(&self) -> String {
+            "mock".into()
+        }
+
+        fn sync(&self) {}
+    }
+
+    #[test]
+    fn test_run_with_uses_timing_source_for_samples_only() {
+        let benchmark = MockBenchmark {
+            num_executions: core::cell::Cell::new(0),
+        };
+        let mut clock = MockClock {
+            durations: vec![
+                Duration::from_micros(3),
+                Duration::from_micros(1),
+                Duration::from_micros(2),
+            ],
+            num_started: 0,
+        };
+
+        let durations = benchmark.run_with(&mut clock);
+
+        assert_eq!(
+            durations.durations,
+            vec![
+                Duration::from_micros(3),
+                Duration::from_micros(1),
+                Duration::from_micros(2),
+            ]
+        );
+        assert_eq!(clock.num_started, 3);
+        assert_eq!(benchmark.num_executions.get(), 5);
+    }
+
+    #[test]
+    fn test_device_timing_discards_work_before_start() {
+        let mut elapsed = [Duration::from_micros(7), Duration::from_micros(2)].into_iter();
+        let mut timing = DeviceTiming::new(|| Ok(elapsed.next().unwrap()));
+
+        timing.start();
+        assert_eq!(timing.elapsed(), Ok(Duration::from_micros(2)));
+    }
 }
@@ -1,11 +1,20 @@
-use crate::ir::{Elem, KernelDefinition};
-use cubecl_runtime::ExecutionMode;
-use std::fmt::Display;
+use crate::ir::{Elem, KernelDefinition, Optimizations};
+use alloc::sync::Arc;
+use cubecl_runtime::{server::ComputeError, ExecutionMode};
+use std::{any::Any, fmt::Display};
 
 /// Trait for compiled code representation
 pub trait CompilerRepresentation: Display {
     /// Computes and returns the shared memory size
     fn shared_memory_size(&self) -> usize;
+    /// Converts the representation into a value stored on the [compiled kernel](crate::compute::CompiledKernel),
+    /// for runtimes that execute the representation directly instead of its source.
+    fn into_repr(self) -> Option<Arc<dyn Any + Send + Sync>>
+    where
+        Self: Sized,
+    {
+        None
+    }
 }
 
 /// Compiles the representation into its own representation that can be formatted into tokens.
@@ -15,8 +24,22 @@ pub trait Compiler: Sync + Send + 'static + Clone + Default + core::fmt::Debug {
 
     /// Compiles the [kernel definition](KernelDefinition) into the compiler's representation.
     fn compile(kernel: KernelDefinition, mode: ExecutionMode) -> Self::Representation;
+    /// Compiles the [kernel definition](KernelDefinition) into the compiler's representation,
+    /// returning an error when the kernel can't be represented by the compiler.
+    fn try_compile(
+        kernel: KernelDefinition,
+        mode: ExecutionMode,
+    ) -> Result<Self::Representation, ComputeError> {
+        Ok(Self::compile(kernel, mode))
+    }
     /// The size of the given element in bytes.
     fn elem_size(elem: Elem) -> usize;
     /// The maximal size of a shared memory
     fn max_shared_memory_size() -> usize;
+    /// The [optimization passes](Optimizations) executed when processing the scopes of a kernel.
+    ///
+    /// No pass is executed by default.
+    fn optimizations() -> Optimizations {
+        Optimizations::default()
+    }
 }
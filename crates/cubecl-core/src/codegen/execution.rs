@@ -317,9 +317,10 @@ fn create_scalar_handles<R: Runtime, E1: CubeElement, E2: CubeElement, E3: CubeE
     // It is crucial that scalars follow this order: float, int, uint
     let element_priority = |elem: Elem| match elem {
         Elem::Float(_) => 0,
+        Elem::AtomicFloat(_) => 0,
         Elem::Int(_) => 1,
         Elem::AtomicInt(_) => 1,
-        Elem::UInt => 2,
+        Elem::UInt(_) => 2,
         Elem::AtomicUInt => 2,
         Elem::Bool => panic!("Bool scalars are not supported"),
     };
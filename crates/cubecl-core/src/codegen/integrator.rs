@@ -1,8 +1,8 @@
 use super::Compiler;
 use crate::{
     ir::{
-        Binding, CubeDim, Elem, Item, KernelDefinition, Location, ReadingStrategy, Scope, Variable,
-        Vectorization, Visibility,
+        Binding, CubeDim, Elem, Function, Item, KernelDefinition, Location, ReadingStrategy, Scope,
+        UIntKind, Variable, Vectorization, Visibility,
     },
     Runtime,
 };
@@ -22,6 +22,7 @@ pub struct KernelIntegrator {
 pub struct KernelExpansion {
     pub inputs: Vec<InputInfo>,
     pub outputs: Vec<OutputInfo>,
+    pub functions: Vec<Function>,
     pub scope: Scope,
 }
 
@@ -340,7 +341,7 @@ impl KernelIntegrator {
         named.push((
             "info".to_string(),
             Binding {
-                item: Item::new(Elem::UInt),
+                item: Item::new(Elem::UInt(UIntKind::U32)),
                 visibility: Visibility::Read,
                 location: Location::Storage,
                 size: None, // We avoid putting the length here since it will force a new kernel
@@ -357,6 +358,7 @@ impl KernelIntegrator {
             outputs,
             named,
             cube_dim: settings.cube_dim,
+            functions: self.expansion.functions,
             body: self.expansion.scope,
         }
     }
@@ -554,7 +556,7 @@ fn bool_item(ty: Item) -> Item {
 pub fn bool_elem(elem: Elem) -> Elem {
     match elem {
         // U32 are used for bool tensors
-        Elem::Bool => Elem::UInt,
+        Elem::Bool => Elem::UInt(UIntKind::U32),
         _ => elem,
     }
 }
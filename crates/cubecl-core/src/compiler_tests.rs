@@ -0,0 +1,142 @@
+use crate as cubecl;
+use crate::{
+    ir::{Elem, FloatKind, Item, KernelDefinition, UIntKind},
+    prelude::*,
+    Compiler,
+};
+use cubecl_runtime::ExecutionMode;
+use std::path::Path;
+
+#[cube(noinline)]
+pub fn golden_scale(x: F32) -> F32 {
+    x * F32::new(2.0)
+}
+
+#[cube]
+pub fn golden_kernel(input: &Array<F32>, output: &mut Array<F32>) {
+    let mut sum = F32::new(0.0);
+
+    for i in range(0u32, input.len(), Comptime::new(false)) {
+        if input[i] > F32::new(1.0) {
+            sum += golden_scale(input[i]);
+        } else {
+            sum -= F32::new(1e-7);
+        }
+    }
+
+    output[UNIT_POS] = sum;
+}
+
+#[cube]
+pub fn golden_vectorized_kernel(lhs: &Array<F32>, rhs: &Array<F32>, output: &mut Array<F32>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] =
+            F32::erf(lhs[ABSOLUTE_POS]) + F32::powf(lhs[ABSOLUTE_POS], rhs[ABSOLUTE_POS]);
+    }
+}
+
+#[cube]
+pub fn golden_subcube_kernel(output: &mut Array<F32>) {
+    let val = output[UNIT_POS];
+    let sum = subcube_sum::<F32>(val);
+    let max = subcube_max::<F32>(val);
+
+    if UNIT_POS < SUBCUBE_DIM {
+        output[UNIT_POS] = sum + max;
+    }
+}
+
+#[cube]
+pub fn golden_subcube_shuffle_kernel(output: &mut Array<F32>, mask: &mut Array<UInt>) {
+    let val = output[UNIT_POS];
+    let swapped = subcube_shuffle_xor::<F32>(val, UInt::new(1));
+    let next = subcube_shuffle_down::<F32>(val, UInt::new(1));
+    let prefix = subcube_exclusive_sum::<F32>(val);
+
+    output[UNIT_POS] = swapped + next + prefix;
+    mask[UNIT_POS] = subcube_ballot(val > F32::new(0.0));
+}
+
+/// Definition of [golden_kernel], covering loops, branches, function calls and array lengths.
+pub fn kernel() -> KernelDefinition {
+    let mut builder = KernelBuilder::default();
+    let input = builder.input_array(float(FloatKind::F32));
+    let output = builder.output_array(float(FloatKind::F32));
+
+    golden_kernel::__expand(&mut builder.context, input.into(), output.into());
+
+    builder.build(KernelSettings::default())
+}
+
+/// Definition of [golden_vectorized_kernel] with a vectorization factor of 4.
+pub fn vectorized() -> KernelDefinition {
+    let mut builder = KernelBuilder::default();
+    let item = Item::vectorized(Elem::Float(FloatKind::F32), 4);
+    let lhs = builder.input_array(item);
+    let rhs = builder.input_array(item);
+    let output = builder.output_array(item);
+
+    golden_vectorized_kernel::__expand(&mut builder.context, lhs.into(), rhs.into(), output.into());
+
+    builder.build(KernelSettings::default())
+}
+
+/// Definition of [golden_subcube_kernel].
+pub fn subcube() -> KernelDefinition {
+    let mut builder = KernelBuilder::default();
+    let output = builder.output_array(float(FloatKind::F32));
+
+    golden_subcube_kernel::__expand(&mut builder.context, output.into());
+
+    builder.build(KernelSettings::default())
+}
+
+/// Definition of [golden_subcube_shuffle_kernel], with the ballot written to a vectorized mask.
+pub fn subcube_shuffle() -> KernelDefinition {
+    let mut builder = KernelBuilder::default();
+    let output = builder.output_array(float(FloatKind::F32));
+    let mask = builder.output_array(Item::vectorized(Elem::UInt(UIntKind::U32), 4));
+
+    golden_subcube_shuffle_kernel::__expand(&mut builder.context, output.into(), mask.into());
+
+    builder.build(KernelSettings::default())
+}
+
+pub fn float(kind: FloatKind) -> Item {
+    Item::new(Elem::Float(kind))
+}
+
+/// Compiles the kernel in checked mode and returns its source.
+pub fn compile<C: Compiler>(kernel: KernelDefinition) -> String {
+    C::compile(kernel, ExecutionMode::Checked).to_string()
+}
+
+/// Compare the source with the golden file at `path`, the file is overwritten instead when
+/// `CUBECL_GOLDEN=overwrite`.
+pub fn assert_golden(path: &Path, source: String) {
+    if std::env::var("CUBECL_GOLDEN").as_deref() == Ok("overwrite") {
+        std::fs::write(path, &source).unwrap();
+    }
+
+    let expected =
+        std::fs::read_to_string(path).unwrap_or_else(|_| panic!("Missing golden file {path:?}"));
+    assert_eq!(
+        source, expected,
+        "The generated source doesn't match {path:?}, run with CUBECL_GOLDEN=overwrite to update it"
+    );
+}
+
+/// Compare the source with the file of the given name in the `tests/golden` directory of the
+/// calling crate.
+#[macro_export]
+macro_rules! assert_golden {
+    ($file:expr, $source:expr) => {
+        $crate::compiler_tests::assert_golden(
+            &std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("tests")
+                .join("golden")
+                .join($file),
+            $source,
+        )
+    };
+}
@@ -81,7 +81,10 @@ impl KernelBuilder {
 
     /// Build the [kernel definition](KernelDefinition).
     pub fn build(self, settings: KernelSettings) -> KernelDefinition {
+        let functions = self.context.functions.take();
+
         KernelIntegrator::new(KernelExpansion {
+            functions,
             scope: self.context.into_scope(),
             inputs: self.inputs,
             outputs: self.outputs,
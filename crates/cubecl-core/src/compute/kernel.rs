@@ -1,12 +1,17 @@
 use std::{
+    any::Any,
     fmt::{Debug, Display},
     marker::PhantomData,
 };
 
-use crate::{codegen::CompilerRepresentation, ir::CubeDim, Compiler, Kernel, KernelId};
+use crate::{
+    codegen::CompilerRepresentation,
+    ir::{self, CubeDim},
+    Compiler, Kernel, KernelId,
+};
 use alloc::sync::Arc;
 use cubecl_runtime::{
-    server::{Binding, ComputeServer},
+    server::{Binding, ComputeError, ComputeServer},
     ExecutionMode,
 };
 
@@ -15,12 +20,17 @@ pub struct CompiledKernel {
     pub name: Option<&'static str>,
     /// Source code of the kernel
     pub source: String,
+    /// The compiler representation of the kernel, when the compiler provides it.
+    pub repr: Option<Arc<dyn Any + Send + Sync>>,
     /// Size of a cube for the compiled kernel
     pub cube_dim: CubeDim,
     /// The number of bytes used by the share memory
     pub shared_mem_bytes: usize,
     /// Extra debugging information about the compiled kernel.
     pub debug_info: Option<DebugInformation>,
+    /// The textual representation of the IR the kernel was compiled from, only provided when
+    /// debugging information is logged.
+    pub ir: Option<String>,
 }
 
 /// Extra debugging information about the compiled kernel.
@@ -63,6 +73,15 @@ shared_memory: {} bytes",
             ))?;
         }
 
+        if let Some(ir) = &self.ir {
+            f.write_fmt(format_args!(
+                "
+ir:
+```
+{ir}```"
+            ))?;
+        }
+
         f.write_fmt(format_args!(
             "
 source:
@@ -161,6 +180,18 @@ pub trait CubeTask: Send + Sync {
     fn id(&self) -> KernelId;
     /// Compile the kernel into source
     fn compile(&self, mode: ExecutionMode) -> CompiledKernel;
+    /// Compile the kernel into source, returning an error when the kernel can't be compiled.
+    fn try_compile(&self, mode: ExecutionMode) -> Result<CompiledKernel, ComputeError> {
+        Ok(self.compile(mode))
+    }
+    /// The name of the kernel, used for profiling.
+    fn name(&self) -> &'static str {
+        core::any::type_name::<Self>()
+    }
+    /// The textual representation of the IR of the kernel, used for debugging.
+    fn ir(&self) -> Option<String> {
+        None
+    }
 }
 
 /// Wraps a [kernel](Kernel) to create a [cube task](CubeTask).
@@ -172,24 +203,55 @@ pub struct KernelTask<C: Compiler, K: Kernel> {
 
 impl<C: Compiler, K: Kernel> CubeTask for KernelTask<C, K> {
     fn compile(&self, mode: ExecutionMode) -> CompiledKernel {
+        self.try_compile(mode).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    fn try_compile(&self, mode: ExecutionMode) -> Result<CompiledKernel, ComputeError> {
         let gpu_ir = self.kernel_definition.define();
         let cube_dim = gpu_ir.cube_dim;
-        let lower_level_ir = C::compile(gpu_ir, mode);
+
+        if let ExecutionMode::Checked = mode {
+            ir::validate(&gpu_ir).map_err(|errors| {
+                let errors = errors
+                    .iter()
+                    .map(|error| error.to_string())
+                    .collect::<Vec<_>>();
+
+                ComputeError::Compilation(format!(
+                    "Invalid kernel {}:\n{}",
+                    core::any::type_name::<K>(),
+                    errors.join("\n")
+                ))
+            })?;
+        }
+
+        let lower_level_ir = C::try_compile(gpu_ir, mode)?;
         let shared_mem_bytes = lower_level_ir.shared_memory_size();
         let source = lower_level_ir.to_string();
+        let repr = lower_level_ir.into_repr();
 
-        CompiledKernel {
+        Ok(CompiledKernel {
             name: Some(core::any::type_name::<K>()),
             source,
+            repr,
             cube_dim,
             shared_mem_bytes,
             debug_info: None,
-        }
+            ir: None,
+        })
     }
 
     fn id(&self) -> KernelId {
         self.kernel_definition.id().clone()
     }
+
+    fn name(&self) -> &'static str {
+        core::any::type_name::<K>()
+    }
+
+    fn ir(&self) -> Option<String> {
+        Some(self.kernel_definition.define().to_string())
+    }
 }
 
 impl CubeTask for Arc<dyn CubeTask> {
@@ -197,9 +259,21 @@ impl CubeTask for Arc<dyn CubeTask> {
         self.as_ref().compile(mode)
     }
 
+    fn try_compile(&self, mode: ExecutionMode) -> Result<CompiledKernel, ComputeError> {
+        self.as_ref().try_compile(mode)
+    }
+
     fn id(&self) -> KernelId {
         self.as_ref().id()
     }
+
+    fn name(&self) -> &'static str {
+        self.as_ref().name()
+    }
+
+    fn ir(&self) -> Option<String> {
+        self.as_ref().ir()
+    }
 }
 
 impl CubeTask for Box<dyn CubeTask> {
@@ -207,9 +281,21 @@ impl CubeTask for Box<dyn CubeTask> {
         self.as_ref().compile(mode)
     }
 
+    fn try_compile(&self, mode: ExecutionMode) -> Result<CompiledKernel, ComputeError> {
+        self.as_ref().try_compile(mode)
+    }
+
     fn id(&self) -> KernelId {
         self.as_ref().id()
     }
+
+    fn name(&self) -> &'static str {
+        self.as_ref().name()
+    }
+
+    fn ir(&self) -> Option<String> {
+        self.as_ref().ir()
+    }
 }
 
 /// Provides launch information specifying the number of work groups to be used by a compute shader.
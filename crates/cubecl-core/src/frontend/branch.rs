@@ -1,7 +1,11 @@
 use std::ops::Deref;
 
 use crate::frontend::{CubeContext, ExpandElement, UInt};
-use crate::ir::{Branch, Elem, If, IfElse, Item, Loop, RangeLoop, Variable};
+use crate::ir::{
+    BinaryOperator, Branch, Elem, If, IfElse, Item, Loop, Operation, Operator, RangeLoop, Scope,
+    Switch, UIntKind, Variable,
+};
+use crate::unexpanded;
 
 use super::comptime::Comptime;
 use super::ExpandElementTyped;
@@ -72,7 +76,7 @@ where
         }
     } else {
         let mut child = context.child();
-        let index_ty = Item::new(Elem::UInt);
+        let index_ty = Item::new(Elem::UInt(UIntKind::U32));
         let i = child.scope.borrow_mut().create_local_undeclared(index_ty);
         let i = ExpandElement::Plain(i);
 
@@ -128,7 +132,7 @@ pub fn range_stepped_expand<F, S, E, Step>(
         }
     } else {
         let mut child = context.child();
-        let index_ty = Item::new(Elem::UInt);
+        let index_ty = Item::new(Elem::UInt(UIntKind::U32));
         let i = child.scope.borrow_mut().create_local_undeclared(index_ty);
         let i = ExpandElement::Plain(i);
 
@@ -205,6 +209,155 @@ pub fn if_else_expand<IF, EL>(
     }
 }
 
+/// Expand a `match` on a runtime integer into a [switch](Branch::Switch).
+///
+/// Cases are added with [case](SwitchExpand::case) and the statement is registered when calling
+/// [finish](SwitchExpand::finish).
+pub fn switch_expand(value: ExpandElement) -> SwitchExpand {
+    SwitchExpand {
+        value,
+        scope_default: None,
+        cases: Vec::new(),
+    }
+}
+
+/// The value compared against the literal patterns of a `match` on a runtime integer, when the
+/// function isn't expanded.
+pub fn switch_value<T>(_value: T) -> i64 {
+    unexpanded!()
+}
+
+/// A switch statement being expanded.
+pub struct SwitchExpand {
+    value: ExpandElement,
+    scope_default: Option<Scope>,
+    cases: Vec<(Vec<Variable>, Scope)>,
+}
+
+impl SwitchExpand {
+    /// Add a case executing the given block when the value is equal to `value`.
+    pub fn case<B>(self, context: &mut CubeContext, value: i64, block: B) -> Self
+    where
+        B: FnMut(&mut CubeContext),
+    {
+        self.cases(context, &[value], block)
+    }
+
+    /// Add a case executing the given block when the value is equal to any of `values`.
+    pub fn cases<B>(mut self, context: &mut CubeContext, values: &[i64], mut block: B) -> Self
+    where
+        B: FnMut(&mut CubeContext),
+    {
+        let elem = self.value.item().elem();
+        let values = values
+            .iter()
+            .map(|value| elem.constant_from_i64(*value))
+            .collect();
+
+        let mut child = context.child();
+        block(&mut child);
+
+        self.cases.push((values, child.into_scope()));
+        self
+    }
+
+    /// Set the block executed when no case matches the value.
+    pub fn default<B>(mut self, context: &mut CubeContext, mut block: B) -> Self
+    where
+        B: FnMut(&mut CubeContext),
+    {
+        let mut child = context.child();
+        block(&mut child);
+
+        self.scope_default = Some(child.into_scope());
+        self
+    }
+
+    /// Register the switch statement.
+    pub fn finish(self, context: &mut CubeContext) {
+        let scope_default = self
+            .scope_default
+            .unwrap_or_else(|| context.child().into_scope());
+
+        // A break inside a switch case only exits the switch in the generated code, so cases
+        // breaking out of an enclosing loop are lowered to an if else chain instead.
+        let breaks = self.cases.iter().any(|(_, scope)| contains_break(scope));
+
+        if !breaks {
+            context.register(Branch::Switch(Switch {
+                value: *self.value,
+                scope_default,
+                cases: self.cases,
+            }));
+            return;
+        }
+
+        let conds = self
+            .cases
+            .iter()
+            .map(|(values, _)| {
+                let out = context.create_local(Item::new(Elem::Bool));
+                context.register(Operator::Equal(BinaryOperator {
+                    lhs: *self.value,
+                    rhs: values[0],
+                    out: *out,
+                }));
+
+                for value in &values[1..] {
+                    let equal = context.create_local(Item::new(Elem::Bool));
+                    context.register(Operator::Equal(BinaryOperator {
+                        lhs: *self.value,
+                        rhs: *value,
+                        out: *equal,
+                    }));
+                    context.register(Operator::Or(BinaryOperator {
+                        lhs: *out,
+                        rhs: *equal,
+                        out: *out,
+                    }));
+                }
+
+                out
+            })
+            .collect::<Vec<_>>();
+
+        let mut cases = self.cases.into_iter().zip(conds);
+        let ((_, scope_first), cond_first) = cases.next().unwrap();
+
+        let mut scope_else = scope_default;
+        for ((_, scope_if), cond) in cases.rev() {
+            let mut child = context.child();
+            child.register(Branch::IfElse(IfElse {
+                cond: *cond,
+                scope_if,
+                scope_else,
+            }));
+            scope_else = child.into_scope();
+        }
+
+        context.register(Branch::IfElse(IfElse {
+            cond: *cond_first,
+            scope_if: scope_first,
+            scope_else,
+        }));
+    }
+}
+
+fn contains_break(scope: &Scope) -> bool {
+    scope.operations.iter().any(|op| match op {
+        Operation::Branch(Branch::Break) => true,
+        Operation::Branch(Branch::If(op)) => contains_break(&op.scope),
+        Operation::Branch(Branch::IfElse(op)) => {
+            contains_break(&op.scope_if) || contains_break(&op.scope_else)
+        }
+        Operation::Branch(Branch::Switch(op)) => {
+            contains_break(&op.scope_default)
+                || op.cases.iter().any(|(_, scope)| contains_break(scope))
+        }
+        _ => false,
+    })
+}
+
 pub fn break_expand(context: &mut CubeContext) {
     context.register(Branch::Break);
 }
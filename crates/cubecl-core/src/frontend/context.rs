@@ -1,5 +1,5 @@
 use crate::frontend::ExpandElement;
-use crate::ir::{self, Elem, Item, Operation, Scope};
+use crate::ir::{self, Elem, Function, Item, Operation, Scope};
 use alloc::rc::Rc;
 use core::cell::RefCell;
 use std::collections::HashMap;
@@ -51,6 +51,7 @@ pub struct CubeContext {
     pub root: Rc<RefCell<Scope>>,
     pub scope: Rc<RefCell<Scope>>,
     pub pool: VariablePool,
+    pub functions: Rc<RefCell<Vec<Function>>>,
 }
 
 impl CubeContext {
@@ -65,6 +66,7 @@ impl CubeContext {
             pool: Default::default(),
             scope,
             root,
+            functions: Default::default(),
         }
     }
 
@@ -79,9 +81,42 @@ impl CubeContext {
             scope: Rc::new(RefCell::new(scope)),
             root: self.root.clone(),
             pool: self.pool.clone(),
+            functions: self.functions.clone(),
         }
     }
 
+    /// Create a context for the body of a function that isn't inlined.
+    ///
+    /// The function has its own root scope and variables, but shares the functions of the kernel.
+    pub fn function(&mut self) -> CubeContext {
+        let root = Rc::new(RefCell::new(Scope::root()));
+        let scope = root.clone();
+
+        Self {
+            pool: Default::default(),
+            scope,
+            root,
+            functions: self.functions.clone(),
+        }
+    }
+
+    /// Register a function definition and return its id.
+    ///
+    /// When an equivalent function is already registered, its id is returned instead.
+    pub fn register_function(&mut self, mut function: Function) -> u16 {
+        let mut functions = self.functions.borrow_mut();
+
+        if let Some(existing) = functions.iter().find(|f| f.is_equivalent(&function)) {
+            return existing.id;
+        }
+
+        let id = functions.len() as u16;
+        function.id = id;
+        functions.push(function);
+
+        id
+    }
+
     pub fn into_scope(self) -> Scope {
         core::mem::drop(self.root);
 
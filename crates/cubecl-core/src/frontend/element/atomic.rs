@@ -1,12 +1,12 @@
 use super::{
     init_expand_element, ExpandElementBaseInit, ExpandElementTyped, LaunchArgExpand, Numeric,
-    Vectorized, I32, I64,
+    Vectorized, F16, F32, F64, I32, I64,
 };
 use crate::{
     frontend::{CubeContext, CubePrimitive, CubeType, ExpandElement, UInt},
     ir::{
-        BinaryOperator, CompareAndSwapOperator, Elem, IntKind, Item, Operator, UnaryOperator,
-        Vectorization,
+        BinaryOperator, CompareAndSwapOperator, Elem, FloatKind, IntKind, Item, Operator,
+        UnaryOperator, Vectorization,
     },
     prelude::KernelBuilder,
     unexpanded,
@@ -80,18 +80,24 @@ where
     }
 
     /// Executes an atomic bitwise and operation on the atomic variable. Returns the old value.
+    ///
+    /// Only supported by integer atomics.
     #[allow(unused_variables)]
     fn and(pointer: &Self, value: Self::Primitive) -> Self::Primitive {
         unexpanded!()
     }
 
     /// Executes an atomic bitwise or operation on the atomic variable. Returns the old value.
+    ///
+    /// Only supported by integer atomics.
     #[allow(unused_variables)]
     fn or(pointer: &Self, value: Self::Primitive) -> Self::Primitive {
         unexpanded!()
     }
 
     /// Executes an atomic bitwise xor operation on the atomic variable. Returns the old value.
+    ///
+    /// Only supported by integer atomics.
     #[allow(unused_variables)]
     fn xor(pointer: &Self, value: Self::Primitive) -> Self::Primitive {
         unexpanded!()
@@ -326,6 +332,65 @@ macro_rules! impl_atomic_int {
 impl_atomic_int!(AtomicI32, I32, i32);
 impl_atomic_int!(AtomicI64, I64, i64);
 
+macro_rules! impl_atomic_float {
+    ($type:ident, $inner_type:ident, $primitive:ty) => {
+        /// An atomic float. Can only be acted on atomically.
+        ///
+        /// Requires the [atomic float feature](crate::Feature::AtomicFloat) of its kind.
+        #[derive(Clone, Copy)]
+        pub struct $type {
+            pub val: $primitive,
+            pub vectorization: u8,
+        }
+
+        impl CubeType for $type {
+            type ExpandType = ExpandElementTyped<Self>;
+        }
+
+        impl CubePrimitive for $type {
+            fn as_elem() -> Elem {
+                Elem::AtomicFloat(FloatKind::$inner_type)
+            }
+        }
+
+        impl ExpandElementBaseInit for $type {
+            fn init_elem(context: &mut CubeContext, elem: ExpandElement) -> ExpandElement {
+                init_expand_element(context, elem)
+            }
+        }
+
+        impl LaunchArgExpand for $type {
+            fn expand(
+                builder: &mut KernelBuilder,
+                vectorization: Vectorization,
+            ) -> ExpandElementTyped<Self> {
+                assert_eq!(vectorization, 1, "Attempted to vectorize a scalar");
+                builder
+                    .scalar(Elem::AtomicFloat(FloatKind::$inner_type))
+                    .into()
+            }
+        }
+
+        impl Vectorized for $type {
+            fn vectorization_factor(&self) -> UInt {
+                UInt {
+                    val: self.vectorization as u32,
+                    vectorization: 1,
+                }
+            }
+
+            fn vectorize(mut self, factor: UInt) -> Self {
+                self.vectorization = factor.vectorization;
+                self
+            }
+        }
+    };
+}
+
+impl_atomic_float!(AtomicF16, F16, half::f16);
+impl_atomic_float!(AtomicF32, F32, f32);
+impl_atomic_float!(AtomicF64, F64, f64);
+
 /// An atomic version of `UInt`. Can only be acted on atomically.
 #[allow(clippy::derived_hash_with_manual_eq)]
 #[derive(Clone, Copy, Hash, PartialEq, Eq)]
@@ -380,6 +445,15 @@ impl Atomic for AtomicI64 {
 impl Atomic for AtomicUInt {
     type Primitive = UInt;
 }
+impl Atomic for AtomicF16 {
+    type Primitive = F16;
+}
+impl Atomic for AtomicF32 {
+    type Primitive = F32;
+}
+impl Atomic for AtomicF64 {
+    type Primitive = F64;
+}
 
 impl Vectorized for AtomicUInt {
     fn vectorization_factor(&self) -> UInt {
@@ -1,4 +1,6 @@
-use super::{Bool, CubePrimitive, Numeric, UInt, Vectorized, F32, F64, I32, I64};
+use super::{
+    Bool, CubePrimitive, Numeric, UInt, Vectorized, F32, F64, I16, I32, I64, I8, U16, U64, U8,
+};
 use crate::{
     ir::{ConstantScalarValue, Elem, Item, Operator, Variable, Vectorization},
     prelude::{index_assign, init_expand, CubeContext, KernelBuilder, KernelLauncher},
@@ -151,7 +153,12 @@ from_const!(i32, I32);
 from_const!(f64, F64);
 from_const!(f32, F32);
 from_const!(bool, Bool);
-from_const!(val UInt, I32, I64, F32, F64);
+from_const!(u8, U8);
+from_const!(u16, U16);
+from_const!(u64, U64);
+from_const!(i8, I8);
+from_const!(i16, I16);
+from_const!(val UInt, I8, I16, I32, I64, U8, U16, U64, F32, F64);
 
 macro_rules! tuple_cube_type {
     ($($P:ident),*) => {
@@ -41,12 +41,17 @@ impl_into_expand_element!(bool);
 impl_into_expand_element!(f32);
 impl_into_expand_element!(i32);
 impl_into_expand_element!(i64);
+impl_into_expand_element!(u8);
+impl_into_expand_element!(u16);
+impl_into_expand_element!(u64);
+impl_into_expand_element!(i8);
+impl_into_expand_element!(i16);
 
 /// Useful for Comptime
 impl From<UInt> for ExpandElement {
     fn from(value: UInt) -> Self {
         ExpandElement::Plain(crate::ir::Variable::ConstantScalar(
-            crate::ir::ConstantScalarValue::UInt(value.val as u64),
+            crate::ir::ConstantScalarValue::UInt(value.val as u64, crate::ir::UIntKind::U32),
         ))
     }
 }
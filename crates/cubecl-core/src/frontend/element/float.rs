@@ -1,6 +1,9 @@
 use half::{bf16, f16};
 
-use crate::frontend::{Ceil, Cos, Erf, Exp, Floor, Log, Log1p, Powf, Recip, Sin, Sqrt, Tanh};
+use crate::frontend::{
+    Acos, Asin, Atan, Atan2, Ceil, CopySign, Cos, Cosh, Erf, Exp, Exp2, Floor, Fract, Hypot, IsInf,
+    IsNan, Log, Log1p, Log2, Powf, Recip, Round, Rsqrt, Sign, Sin, Sinh, Sqrt, Tan, Tanh, Trunc,
+};
 use crate::frontend::{
     ComptimeType, CubeContext, CubePrimitive, CubeType, ExpandElement, ExpandElementBaseInit,
     ExpandElementTyped, Numeric,
@@ -8,8 +11,8 @@ use crate::frontend::{
 use crate::ir::{ConstantScalarValue, Elem, FloatKind, Item, Variable, Vectorization};
 
 use super::{
-    init_expand_element, LaunchArgExpand, ScalarArgSettings, UInt, Vectorized, __expand_new,
-    __expand_vectorized,
+    __expand_new, __expand_vectorized, init_expand_element, LaunchArgExpand, ScalarArgSettings,
+    UInt, Vectorized,
 };
 use crate::compute::{KernelBuilder, KernelLauncher};
 use crate::Runtime;
@@ -29,6 +32,24 @@ pub trait Float:
     + Ceil
     + Erf
     + Recip
+    + Tan
+    + Asin
+    + Acos
+    + Atan
+    + Atan2
+    + Sinh
+    + Cosh
+    + Exp2
+    + Log2
+    + Round
+    + Trunc
+    + Fract
+    + Sign
+    + CopySign
+    + Hypot
+    + Rsqrt
+    + IsNan
+    + IsInf
     + From<f32>
     + core::ops::Add<f32, Output = Self>
     + core::ops::Sub<f32, Output = Self>
@@ -7,8 +7,8 @@ use crate::ir::{ConstantScalarValue, Elem, IntKind, Variable, Vectorization};
 use crate::Runtime;
 
 use super::{
-    init_expand_element, LaunchArgExpand, ScalarArgSettings, UInt, Vectorized, __expand_new,
-    __expand_vectorized,
+    __expand_new, __expand_vectorized, init_expand_element, LaunchArgExpand, ScalarArgSettings,
+    UInt, Vectorized,
 };
 
 /// Signed integer. Used as input in int kernels
@@ -86,7 +86,7 @@ macro_rules! impl_int {
                 let elem = Self::as_elem();
                 let value = match elem {
                     Elem::Int(kind) => ConstantScalarValue::Int(self.val as i64, kind),
-                    Elem::UInt => ConstantScalarValue::UInt(self.val as u64),
+                    Elem::UInt(kind) => ConstantScalarValue::UInt(self.val as u64, kind),
                     _ => panic!("Wrong elem type"),
                 };
 
@@ -157,9 +157,29 @@ macro_rules! impl_int {
     };
 }
 
+impl_int!(I8, i8);
+impl_int!(I16, i16);
 impl_int!(I32, i32);
 impl_int!(I64, i64);
 
+impl From<i8> for I8 {
+    fn from(value: i8) -> Self {
+        Self {
+            val: value,
+            vectorization: 1,
+        }
+    }
+}
+
+impl From<i16> for I16 {
+    fn from(value: i16) -> Self {
+        Self {
+            val: value,
+            vectorization: 1,
+        }
+    }
+}
+
 impl From<i64> for I64 {
     fn from(value: i64) -> Self {
         Self {
@@ -169,6 +189,18 @@ impl From<i64> for I64 {
     }
 }
 
+impl ScalarArgSettings for i8 {
+    fn register<R: Runtime>(&self, settings: &mut KernelLauncher<R>) {
+        settings.register_i8(*self);
+    }
+}
+
+impl ScalarArgSettings for i16 {
+    fn register<R: Runtime>(&self, settings: &mut KernelLauncher<R>) {
+        settings.register_i16(*self);
+    }
+}
+
 impl ScalarArgSettings for i32 {
     fn register<R: Runtime>(&self, settings: &mut KernelLauncher<R>) {
         settings.register_i32(*self);
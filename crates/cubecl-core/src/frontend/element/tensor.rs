@@ -3,7 +3,7 @@ use crate::{
     frontend::{
         indexation::Index, ArgSettings, CubeContext, CubePrimitive, CubeType, ExpandElement, UInt,
     },
-    ir::{Elem, Item, Metadata, Variable, Vectorization},
+    ir::{Elem, Item, Metadata, UIntKind, Variable, Vectorization},
     prelude::{KernelBuilder, KernelLauncher},
     unexpanded, KernelSettings, LaunchArg, Runtime,
 };
@@ -201,7 +201,7 @@ impl<T: CubeType> ExpandElementTyped<T> {
         context: &mut CubeContext,
         dim: C,
     ) -> ExpandElementTyped<UInt> {
-        let out = context.create_local(Item::new(Elem::UInt));
+        let out = context.create_local(Item::new(Elem::UInt(UIntKind::U32)));
         context.register(Metadata::Stride {
             dim: dim.value(),
             var: self.expand.into(),
@@ -216,7 +216,7 @@ impl<T: CubeType> ExpandElementTyped<T> {
         context: &mut CubeContext,
         dim: C,
     ) -> ExpandElementTyped<UInt> {
-        let out = context.create_local(Item::new(Elem::UInt));
+        let out = context.create_local(Item::new(Elem::UInt(UIntKind::U32)));
         context.register(Metadata::Shape {
             dim: dim.value(),
             var: self.expand.into(),
@@ -227,7 +227,7 @@ impl<T: CubeType> ExpandElementTyped<T> {
 
     // Expanded version of len
     pub fn __expand_len_method(self, context: &mut CubeContext) -> ExpandElementTyped<UInt> {
-        let out = context.create_local(Item::new(Elem::UInt));
+        let out = context.create_local(Item::new(Elem::UInt(UIntKind::U32)));
         context.register(Metadata::Length {
             var: self.expand.into(),
             out: out.clone().into(),
@@ -1,11 +1,11 @@
 use crate::frontend::{CubeContext, CubePrimitive, CubeType, ExpandElement, Numeric};
-use crate::ir::{Elem, Vectorization};
+use crate::ir::{Elem, UIntKind, Vectorization};
 use crate::prelude::{KernelBuilder, KernelLauncher};
 use crate::{frontend::Comptime, Runtime};
 
 use super::{
-    init_expand_element, ExpandElementBaseInit, ExpandElementTyped, LaunchArgExpand,
-    ScalarArgSettings, Vectorized, __expand_new, __expand_vectorized,
+    __expand_new, __expand_vectorized, init_expand_element, ExpandElementBaseInit,
+    ExpandElementTyped, LaunchArgExpand, ScalarArgSettings, Vectorized,
 };
 
 #[allow(clippy::derived_hash_with_manual_eq)]
@@ -39,7 +39,7 @@ impl ExpandElementBaseInit for UInt {
 
 impl CubePrimitive for UInt {
     fn as_elem() -> Elem {
-        Elem::UInt
+        Elem::UInt(UIntKind::U32)
     }
 }
 
@@ -134,3 +134,138 @@ impl Vectorized for UInt {
         self
     }
 }
+
+/// Unsigned integers of other sizes than [UInt], which is preferred for indexing.
+macro_rules! impl_uint {
+    ($type:ident, $primitive:ty, $kind:ident) => {
+        #[allow(clippy::derived_hash_with_manual_eq)]
+        #[derive(Clone, Copy, Hash)]
+        pub struct $type {
+            pub val: $primitive,
+            pub vectorization: u8,
+        }
+
+        impl CubeType for $type {
+            type ExpandType = ExpandElementTyped<Self>;
+        }
+
+        impl CubePrimitive for $type {
+            fn as_elem() -> Elem {
+                Elem::UInt(UIntKind::$kind)
+            }
+        }
+
+        impl From<u32> for $type {
+            fn from(val: u32) -> Self {
+                Self {
+                    val: val as $primitive,
+                    vectorization: 1,
+                }
+            }
+        }
+
+        impl From<$primitive> for $type {
+            fn from(val: $primitive) -> Self {
+                Self::new(val)
+            }
+        }
+
+        impl From<$type> for ExpandElement {
+            fn from(value: $type) -> Self {
+                let constant = $type::as_elem().from_constant(value.val.into());
+                ExpandElement::Plain(constant)
+            }
+        }
+
+        impl Numeric for $type {
+            type Primitive = $primitive;
+        }
+
+        impl ExpandElementBaseInit for $type {
+            fn init_elem(context: &mut CubeContext, elem: ExpandElement) -> ExpandElement {
+                init_expand_element(context, elem)
+            }
+        }
+
+        impl $type {
+            pub const fn new(val: $primitive) -> Self {
+                Self {
+                    val,
+                    vectorization: 1,
+                }
+            }
+
+            pub fn vectorized(val: $primitive, vectorization: UInt) -> Self {
+                if vectorization.val == 1 {
+                    Self::new(val)
+                } else {
+                    Self {
+                        val,
+                        vectorization: vectorization.val as u8,
+                    }
+                }
+            }
+
+            pub fn __expand_new(
+                context: &mut CubeContext,
+                val: <Self as CubeType>::ExpandType,
+            ) -> <Self as CubeType>::ExpandType {
+                __expand_new(context, val, Self::as_elem())
+            }
+
+            pub fn __expand_vectorized(
+                context: &mut CubeContext,
+                val: <Self as CubeType>::ExpandType,
+                vectorization: UInt,
+            ) -> <Self as CubeType>::ExpandType {
+                __expand_vectorized(context, val, vectorization, Self::as_elem())
+            }
+        }
+
+        impl LaunchArgExpand for $type {
+            fn expand(
+                builder: &mut KernelBuilder,
+                vectorization: Vectorization,
+            ) -> ExpandElementTyped<Self> {
+                assert_eq!(vectorization, 1, "Attempted to vectorize a scalar");
+                builder.scalar($type::as_elem()).into()
+            }
+        }
+
+        impl Vectorized for $type {
+            fn vectorization_factor(&self) -> UInt {
+                UInt {
+                    val: self.vectorization as u32,
+                    vectorization: 1,
+                }
+            }
+
+            fn vectorize(mut self, factor: UInt) -> Self {
+                self.vectorization = factor.vectorization;
+                self
+            }
+        }
+    };
+}
+
+impl_uint!(U8, u8, U8);
+impl_uint!(U16, u16, U16);
+impl_uint!(U64, u64, U64);
+
+impl ScalarArgSettings for u8 {
+    fn register<R: Runtime>(&self, settings: &mut KernelLauncher<R>) {
+        settings.register_u8(*self);
+    }
+}
+
+impl ScalarArgSettings for u16 {
+    fn register<R: Runtime>(&self, settings: &mut KernelLauncher<R>) {
+        settings.register_u16(*self);
+    }
+}
+
+impl ScalarArgSettings for u64 {
+    fn register<R: Runtime>(&self, settings: &mut KernelLauncher<R>) {
+        settings.register_u64(*self);
+    }
+}
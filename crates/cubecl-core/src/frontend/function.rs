@@ -0,0 +1,109 @@
+use std::ops::Deref;
+
+use crate::frontend::{CubeContext, CubeType, ExpandElement, ExpandElementTyped};
+use crate::ir::{Call, Function, Variable};
+
+/// Output of a function that isn't inlined.
+pub trait FunctionOutput: Sized {
+    /// The element returned by the function body, if any.
+    fn into_output(self) -> Option<ExpandElement>;
+    /// Create the output from the element assigned by the call.
+    fn from_output(output: Option<ExpandElement>) -> Self;
+}
+
+impl FunctionOutput for () {
+    fn into_output(self) -> Option<ExpandElement> {
+        None
+    }
+
+    fn from_output(_output: Option<ExpandElement>) -> Self {}
+}
+
+impl<T: CubeType> FunctionOutput for ExpandElementTyped<T> {
+    fn into_output(self) -> Option<ExpandElement> {
+        Some(self.expand)
+    }
+
+    fn from_output(output: Option<ExpandElement>) -> Self {
+        output
+            .expect("A function returning a value has an output")
+            .into()
+    }
+}
+
+/// Expand a call to a function that isn't inlined.
+///
+/// The body is expanded in its own context, where the arguments passed by value are replaced by
+/// the inputs of the [function](Function). Global arrays are accessed directly by the function.
+/// A function is only registered once per name and body, so calling it again with arguments of
+/// the same types reuses the same definition.
+pub fn call_expand<O, F>(
+    context: &mut CubeContext,
+    name: &str,
+    args: Vec<ExpandElement>,
+    body: F,
+) -> O
+where
+    O: FunctionOutput,
+    F: FnOnce(&mut CubeContext, Vec<ExpandElement>) -> O,
+{
+    let mut function_context = context.function();
+    let mut inputs = Vec::new();
+    let mut call_inputs = Vec::new();
+
+    let params = args
+        .into_iter()
+        .map(|arg| match *arg {
+            Variable::GlobalInputArray { .. } | Variable::GlobalOutputArray { .. } => arg,
+            Variable::SharedMemory { .. }
+            | Variable::LocalArray { .. }
+            | Variable::Slice { .. }
+            | Variable::Matrix { .. } => panic!(
+                "Only values and global arrays can be passed to a function that isn't inlined, got {:?}.",
+                *arg
+            ),
+            _ => {
+                let input = function_context
+                    .scope
+                    .borrow_mut()
+                    .create_local(arg.item());
+                inputs.push(input);
+                call_inputs.push(*arg);
+
+                ExpandElement::Plain(input)
+            }
+        })
+        .collect();
+
+    let output = body(&mut function_context, params)
+        .into_output()
+        .map(|output| *output.deref());
+
+    // The compilers access the inputs and the output directly, so they must outlive the
+    // optimization passes.
+    {
+        let mut scope = function_context.scope.borrow_mut();
+        inputs
+            .iter()
+            .chain(output.iter())
+            .for_each(|var| scope.preserve(*var));
+    }
+
+    let id = context.register_function(Function {
+        id: 0,
+        name: name.to_string(),
+        inputs,
+        output,
+        scope: function_context.into_scope(),
+    });
+
+    let output = output.map(|output| context.create_local(output.item()));
+
+    context.register(Call {
+        function: id,
+        inputs: call_inputs,
+        output: output.as_ref().map(|output| *output.deref()),
+    });
+
+    O::from_output(output)
+}
@@ -7,7 +7,10 @@ pub trait Index {
 
 impl Index for Comptime<u32> {
     fn value(self) -> Variable {
-        Variable::ConstantScalar(crate::ir::ConstantScalarValue::UInt(self.inner as u64))
+        Variable::ConstantScalar(crate::ir::ConstantScalarValue::UInt(
+            self.inner as u64,
+            crate::ir::UIntKind::U32,
+        ))
     }
 }
 
@@ -31,13 +34,19 @@ impl Index for i32 {
 
 impl Index for u32 {
     fn value(self) -> Variable {
-        Variable::ConstantScalar(crate::ir::ConstantScalarValue::UInt(self as u64))
+        Variable::ConstantScalar(crate::ir::ConstantScalarValue::UInt(
+            self as u64,
+            crate::ir::UIntKind::U32,
+        ))
     }
 }
 
 impl Index for UInt {
     fn value(self) -> Variable {
-        Variable::ConstantScalar(crate::ir::ConstantScalarValue::UInt(self.val as u64))
+        Variable::ConstantScalar(crate::ir::ConstantScalarValue::UInt(
+            self.val as u64,
+            crate::ir::UIntKind::U32,
+        ))
     }
 }
 
@@ -1,5 +1,6 @@
 pub mod branch;
 pub mod cmma;
+pub mod function;
 pub mod synchronization;
 
 mod base;
@@ -1,5 +1,5 @@
 use crate::frontend::{Array, CubeContext, ExpandElement, SharedMemory, Tensor, UInt};
-use crate::frontend::{BF16, F16, F32, F64, I32, I64};
+use crate::frontend::{BF16, F16, F32, F64, I16, I32, I64, I8, U16, U64, U8};
 use crate::{ir, unexpanded};
 
 macro_rules! impl_op_assign {
@@ -60,9 +60,9 @@ pub mod index_assign {
     {
         let index: Variable = index.expand.into();
         let index = match index {
-            Variable::ConstantScalar(value) => {
-                Variable::ConstantScalar(ir::ConstantScalarValue::UInt(value.as_u64()))
-            }
+            Variable::ConstantScalar(value) => Variable::ConstantScalar(
+                ir::ConstantScalarValue::UInt(value.as_u64(), ir::UIntKind::U32),
+            ),
             _ => index,
         };
         context.register(Operator::IndexAssign(BinaryOperator {
@@ -102,7 +102,7 @@ pub mod index_assign {
     impl_index!(Array);
     impl_index!(Tensor);
     impl_index!(SharedMemory);
-    impl_index_vec!(I64, I32, F16, BF16, F32, F64, UInt);
+    impl_index_vec!(I64, I32, I16, I8, F16, BF16, F32, F64, UInt, U8, U16, U64);
 
     impl<'a, E: CubeType, I: Into<UInt>> core::ops::IndexMut<I> for SliceMut<'a, E> {
         fn index_mut(&mut self, _index: I) -> &mut Self::Output {
@@ -137,7 +137,7 @@ pub mod index {
         let index_var: Variable = *index;
         let index = match index_var {
             Variable::ConstantScalar(value) => ExpandElement::Plain(Variable::ConstantScalar(
-                ir::ConstantScalarValue::UInt(value.as_u64()),
+                ir::ConstantScalarValue::UInt(value.as_u64(), ir::UIntKind::U32),
             )),
             _ => index,
         };
@@ -189,7 +189,7 @@ pub mod index {
     impl_index!(Tensor);
     impl_index!(SharedMemory);
 
-    impl_index_vec!(I64, I32, F16, BF16, F32, F64, UInt);
+    impl_index_vec!(I64, I32, I16, I8, F16, BF16, F32, F64, UInt, U8, U16, U64);
 
     impl<'a, E: CubeType, I: Into<UInt>> core::ops::Index<I> for SliceMut<'a, E> {
         type Output = E;
@@ -275,7 +275,9 @@ pub mod div_assign_array_op {
 }
 
 pub mod add_assign_op {
-    use crate::frontend::{operation::base::assign_op_expand, BF16, F16, F32, F64, I32, I64};
+    use crate::frontend::{
+        operation::base::assign_op_expand, BF16, F16, F32, F64, I16, I32, I64, I8, U16, U64, U8,
+    };
     use core::ops::AddAssign;
 
     use self::ir::Operator;
@@ -298,7 +300,12 @@ pub mod add_assign_op {
             F64 | f32;u32,
             I32 | i32;u32,
             I64 | i32;u32,
-            UInt | u32
+            I8 | i32;u32,
+            I16 | i32;u32,
+            UInt | u32,
+            U8 | u32,
+            U16 | u32,
+            U64 | u32
         }
     );
 }
@@ -306,7 +313,9 @@ pub mod add_assign_op {
 pub mod sub_assign_op {
     use self::ir::Operator;
     use super::*;
-    use crate::frontend::{operation::base::assign_op_expand, BF16, F16, F32, F64, I32, I64};
+    use crate::frontend::{
+        operation::base::assign_op_expand, BF16, F16, F32, F64, I16, I32, I64, I8, U16, U64, U8,
+    };
     use core::ops::SubAssign;
 
     pub fn expand<L: Into<ExpandElement>, R: Into<ExpandElement>>(
@@ -325,7 +334,12 @@ pub mod sub_assign_op {
             F64 | f32;u32,
             I32 | i32;u32,
             I64 | i32;u32,
-            UInt | u32
+            I8 | i32;u32,
+            I16 | i32;u32,
+            UInt | u32,
+            U8 | u32,
+            U16 | u32,
+            U64 | u32
         }
     );
 }
@@ -333,7 +347,9 @@ pub mod sub_assign_op {
 pub mod mul_assign_op {
     use self::ir::Operator;
     use super::*;
-    use crate::frontend::{operation::base::assign_op_expand, BF16, F16, F32, F64, I32, I64};
+    use crate::frontend::{
+        operation::base::assign_op_expand, BF16, F16, F32, F64, I16, I32, I64, I8, U16, U64, U8,
+    };
     use core::ops::MulAssign;
 
     pub fn expand<L: Into<ExpandElement>, R: Into<ExpandElement>>(
@@ -352,7 +368,12 @@ pub mod mul_assign_op {
             F64 | f32;u32,
             I32 | i32;u32,
             I64 | i32;u32,
-            UInt | u32
+            I8 | i32;u32,
+            I16 | i32;u32,
+            UInt | u32,
+            U8 | u32,
+            U16 | u32,
+            U64 | u32
         }
     );
 }
@@ -360,7 +381,9 @@ pub mod mul_assign_op {
 pub mod div_assign_op {
     use self::ir::Operator;
     use super::*;
-    use crate::frontend::{operation::base::assign_op_expand, BF16, F16, F32, F64, I32, I64};
+    use crate::frontend::{
+        operation::base::assign_op_expand, BF16, F16, F32, F64, I16, I32, I64, I8, U16, U64, U8,
+    };
     use core::ops::DivAssign;
 
     pub fn expand<L: Into<ExpandElement>, R: Into<ExpandElement>>(
@@ -379,7 +402,12 @@ pub mod div_assign_op {
             F64 | f32;u32,
             I32 | i32;u32,
             I64 | i32;u32,
-            UInt | u32
+            I8 | i32;u32,
+            I16 | i32;u32,
+            UInt | u32,
+            U8 | u32,
+            U16 | u32,
+            U64 | u32
         }
     );
 }
@@ -166,6 +166,29 @@ where
     out
 }
 
+pub(crate) fn unary_expand_fixed_output<F>(
+    context: &mut CubeContext,
+    input: ExpandElement,
+    out_item: Item,
+    func: F,
+) -> ExpandElement
+where
+    F: Fn(UnaryOperator) -> Operator,
+{
+    let input_var: Variable = *input;
+    let out = context.create_local(out_item);
+    let out_var = *out;
+
+    let op = func(UnaryOperator {
+        input: input_var,
+        out: out_var,
+    });
+
+    context.register(op);
+
+    out
+}
+
 pub fn init_expand<F>(context: &mut CubeContext, input: ExpandElement, func: F) -> ExpandElement
 where
     F: Fn(UnaryOperator) -> Operator,
@@ -1,7 +1,7 @@
 use crate::frontend::operation::base::binary_expand;
 use crate::frontend::{
     AtomicI32, AtomicI64, AtomicUInt, CubeContext, CubePrimitive, ExpandElementTyped, UInt, BF16,
-    F16, F32, F64, I32, I64,
+    F16, F32, F64, I16, I32, I64, I8, U16, U64, U8,
 };
 use crate::ir::Operator;
 use crate::{frontend::CubeType, unexpanded};
@@ -51,7 +51,12 @@ pub mod add {
             F64 | f32;u32,
             I32 | i32;u32,
             I64 | i32;u32,
-            UInt | u32
+            I8 | i32;u32,
+            I16 | i32;u32,
+            UInt | u32,
+            U8 | u32,
+            U16 | u32,
+            U64 | u32
         }
     );
 }
@@ -76,7 +81,12 @@ pub mod sub {
             F64 | f32;u32,
             I32 | i32;u32,
             I64 | i32;u32,
-            UInt | u32
+            I8 | i32;u32,
+            I16 | i32;u32,
+            UInt | u32,
+            U8 | u32,
+            U16 | u32,
+            U64 | u32
         }
     );
 }
@@ -101,7 +111,12 @@ pub mod mul {
             F64 | f32;u32,
             I32 | i32;u32,
             I64 | i32;u32,
-            UInt | u32
+            I8 | i32;u32,
+            I16 | i32;u32,
+            UInt | u32,
+            U8 | u32,
+            U16 | u32,
+            U64 | u32
         }
     );
 }
@@ -127,7 +142,12 @@ pub mod div {
             F64 | f32;u32,
             I32 | i32;u32,
             I64 | i32;u32,
-            UInt | u32
+            I8 | i32;u32,
+            I16 | i32;u32,
+            UInt | u32,
+            U8 | u32,
+            U16 | u32,
+            U64 | u32
         }
     );
 }
@@ -158,6 +178,29 @@ pub mod rem {
     impl_rem!(I32);
     impl_rem!(I64);
     impl_rem!(UInt);
+    impl_rem!(I8);
+    impl_rem!(I16);
+    impl_rem!(U8);
+    impl_rem!(U16);
+    impl_rem!(U64);
+}
+
+/// Bitwise operators only have a concrete implementation for integer types.
+macro_rules! impl_bit_op {
+    ($tr:ident, $func:ident) => {
+        impl_bit_op!($tr, $func, I8, I16, I32, I64, UInt, U8, U16, U64);
+    };
+    ($tr:ident, $func:ident, $($type:ty),*) => {
+        $(
+            impl core::ops::$tr for $type {
+                type Output = Self;
+
+                fn $func(self, _rhs: Self) -> Self::Output {
+                    unexpanded!()
+                }
+            }
+        )*
+    };
 }
 
 pub mod and {
@@ -183,13 +226,7 @@ pub mod bitand {
         binary_expand(context, lhs.into(), rhs.into(), Operator::BitwiseAnd).into()
     }
 
-    impl core::ops::BitAnd for UInt {
-        type Output = UInt;
-
-        fn bitand(self, _rhs: Self) -> Self::Output {
-            unexpanded!()
-        }
-    }
+    impl_bit_op!(BitAnd, bitand);
 }
 
 pub mod or {
@@ -204,7 +241,7 @@ pub mod or {
     }
 }
 
-pub mod bitxor {
+pub mod bitor {
     use super::*;
 
     pub fn expand<C: CubePrimitive>(
@@ -212,16 +249,24 @@ pub mod bitxor {
         lhs: ExpandElementTyped<C>,
         rhs: ExpandElementTyped<C>,
     ) -> ExpandElementTyped<C> {
-        binary_expand(context, lhs.into(), rhs.into(), Operator::BitwiseXor).into()
+        binary_expand(context, lhs.into(), rhs.into(), Operator::BitwiseOr).into()
     }
 
-    impl core::ops::BitXor for UInt {
-        type Output = UInt;
+    impl_bit_op!(BitOr, bitor);
+}
 
-        fn bitxor(self, _rhs: Self) -> Self::Output {
-            unexpanded!()
-        }
+pub mod bitxor {
+    use super::*;
+
+    pub fn expand<C: CubePrimitive>(
+        context: &mut CubeContext,
+        lhs: ExpandElementTyped<C>,
+        rhs: ExpandElementTyped<C>,
+    ) -> ExpandElementTyped<C> {
+        binary_expand(context, lhs.into(), rhs.into(), Operator::BitwiseXor).into()
     }
+
+    impl_bit_op!(BitXor, bitxor);
 }
 
 pub mod shl {
@@ -235,13 +280,7 @@ pub mod shl {
         binary_expand(context, lhs.into(), rhs.into(), Operator::ShiftLeft).into()
     }
 
-    impl core::ops::Shl for UInt {
-        type Output = UInt;
-
-        fn shl(self, _rhs: Self) -> Self::Output {
-            unexpanded!()
-        }
-    }
+    impl_bit_op!(Shl, shl);
 }
 
 pub mod shr {
@@ -255,13 +294,7 @@ pub mod shr {
         binary_expand(context, lhs.into(), rhs.into(), Operator::ShiftRight).into()
     }
 
-    impl core::ops::Shr for UInt {
-        type Output = UInt;
-
-        fn shr(self, _rhs: Self) -> Self::Output {
-            unexpanded!()
-        }
-    }
+    impl_bit_op!(Shr, shr);
 }
 
 /// For binary functions without special syntax
@@ -295,6 +328,36 @@ impl_binary_func!(
     F32,
     F64
 );
+impl_binary_func!(
+    Atan2,
+    atan2,
+    __expand_atan2,
+    Operator::Atan2,
+    F16,
+    BF16,
+    F32,
+    F64
+);
+impl_binary_func!(
+    CopySign,
+    copysign,
+    __expand_copysign,
+    Operator::CopySign,
+    F16,
+    BF16,
+    F32,
+    F64
+);
+impl_binary_func!(
+    Hypot,
+    hypot,
+    __expand_hypot,
+    Operator::Hypot,
+    F16,
+    BF16,
+    F32,
+    F64
+);
 impl_binary_func!(
     Max,
     max,
@@ -304,9 +367,14 @@ impl_binary_func!(
     BF16,
     F32,
     F64,
+    I8,
+    I16,
     I32,
     I64,
     UInt,
+    U8,
+    U16,
+    U64,
     AtomicI32,
     AtomicI64,
     AtomicUInt
@@ -320,9 +388,14 @@ impl_binary_func!(
     BF16,
     F32,
     F64,
+    I8,
+    I16,
     I32,
     I64,
-    UInt
+    UInt,
+    U8,
+    U16,
+    U64
 );
 impl_binary_func!(
     Remainder,
@@ -333,7 +406,12 @@ impl_binary_func!(
     BF16,
     F32,
     F64,
+    I8,
+    I16,
     I32,
     I64,
-    UInt
+    UInt,
+    U8,
+    U16,
+    U64
 );
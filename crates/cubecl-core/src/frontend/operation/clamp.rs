@@ -1,6 +1,9 @@
 use crate::{
     ir::{ClampOperator, Operator},
-    prelude::{CubeContext, CubePrimitive, ExpandElement, UInt, BF16, F16, F32, F64, I32, I64},
+    prelude::{
+        CubeContext, CubePrimitive, ExpandElement, UInt, BF16, F16, F32, F64, I16, I32, I64, I8,
+        U16, U64, U8,
+    },
     unexpanded,
 };
 
@@ -40,4 +43,9 @@ impl Clamp for F32 {}
 impl Clamp for F64 {}
 impl Clamp for I32 {}
 impl Clamp for I64 {}
+impl Clamp for I8 {}
+impl Clamp for I16 {}
+impl Clamp for U8 {}
+impl Clamp for U16 {}
+impl Clamp for U64 {}
 impl Clamp for UInt {}
@@ -1,5 +1,8 @@
 use crate::frontend::operation::base::cmp_expand;
-use crate::frontend::{CubeContext, ExpandElementTyped, UInt, BF16, F16, F32, F64, I32, I64};
+use crate::frontend::{
+    AtomicF16, AtomicF32, AtomicF64, CubeContext, ExpandElementTyped, UInt, BF16, F16, F32, F64,
+    I16, I32, I64, I8, U16, U64, U8,
+};
 use crate::ir::Operator;
 use crate::prelude::CubePrimitive;
 
@@ -55,10 +58,19 @@ impl_cmp!(
         F64 | f32;u32,
         I32 | i32;u32,
         I64 | i32;u32,
-        UInt | u32
+        I8 | i32;u32,
+        I16 | i32;u32,
+        UInt | u32,
+        U8 | u32,
+        U16 | u32,
+        U64 | u32
     }
 );
 
+impl_cmp!(AtomicF16);
+impl_cmp!(AtomicF32);
+impl_cmp!(AtomicF64);
+
 pub mod ne {
 
     use super::*;
@@ -1,21 +1,74 @@
 use crate::{
-    frontend::{CubeContext, UInt, BF16, F16, F32, F64, I32, I64},
-    ir::Operator,
+    frontend::{
+        CubeContext, CubeType, ExpandElement, UInt, BF16, F16, F32, F64, I16, I32, I64, I8, U16,
+        U64, U8,
+    },
+    ir::{Elem, Item, Operator},
     prelude::{CubePrimitive, ExpandElementTyped},
     unexpanded,
 };
 
-use super::base::unary_expand;
+use super::base::{unary_expand, unary_expand_fixed_output};
 
 pub mod not {
     use super::*;
 
-    pub fn expand(
+    /// Logical not on booleans, bitwise not on integers.
+    pub fn expand<C: CubeType>(
         context: &mut CubeContext,
-        x: ExpandElementTyped<bool>,
-    ) -> ExpandElementTyped<bool> {
-        unary_expand(context, x.into(), Operator::Not).into()
+        x: ExpandElementTyped<C>,
+    ) -> ExpandElementTyped<C> {
+        let x: ExpandElement = x.into();
+        let operator = match x.item().elem() {
+            Elem::Bool => Operator::Not,
+            _ => Operator::BitwiseNot,
+        };
+
+        unary_expand(context, x, operator).into()
+    }
+
+    macro_rules! impl_not {
+        ($($type:ty),*) => {
+            $(
+                impl core::ops::Not for $type {
+                    type Output = Self;
+
+                    fn not(self) -> Self::Output {
+                        unexpanded!()
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_not!(I8, I16, I32, I64, UInt, U8, U16, U64);
+}
+
+pub mod neg {
+    use super::*;
+
+    pub fn expand<C: CubePrimitive>(
+        context: &mut CubeContext,
+        x: ExpandElementTyped<C>,
+    ) -> ExpandElementTyped<C> {
+        unary_expand(context, x.into(), Operator::Neg).into()
+    }
+
+    macro_rules! impl_neg {
+        ($($type:ty),*) => {
+            $(
+                impl core::ops::Neg for $type {
+                    type Output = Self;
+
+                    fn neg(self) -> Self::Output {
+                        unexpanded!()
+                    }
+                }
+            )*
+        };
     }
+
+    impl_neg!(F16, BF16, F32, F64, I8, I16, I32, I64);
 }
 
 macro_rules! impl_unary_func {
@@ -35,6 +88,26 @@ macro_rules! impl_unary_func {
     }
 }
 
+/// For unary functions whose output has a fixed element type, such as predicates.
+macro_rules! impl_unary_func_fixed_out_elem {
+    ($trait_name:ident, $method_name:ident, $method_name_expand:ident, $operator:expr, $out_elem:expr, $($type:ty),*) => {
+        pub trait $trait_name: CubePrimitive + Sized {
+            #[allow(unused_variables)]
+            fn $method_name(x: Self) -> bool {
+                unexpanded!()
+            }
+
+            fn $method_name_expand(context: &mut CubeContext, x: Self::ExpandType) -> ExpandElementTyped<bool> {
+                let x: ExpandElement = x.into();
+                let out_item = Item::vectorized($out_elem, x.item().vectorization);
+                unary_expand_fixed_output(context, x, out_item, $operator).into()
+            }
+        }
+
+        $(impl $trait_name for $type {})*
+    }
+}
+
 impl_unary_func!(
     Abs,
     abs,
@@ -44,9 +117,14 @@ impl_unary_func!(
     BF16,
     F32,
     F64,
+    I8,
+    I16,
     I32,
     I64,
-    UInt
+    UInt,
+    U8,
+    U16,
+    U64
 );
 impl_unary_func!(Exp, exp, __expand_exp, Operator::Exp, F16, BF16, F32, F64);
 impl_unary_func!(Log, log, __expand_log, Operator::Log, F16, BF16, F32, F64);
@@ -113,3 +191,202 @@ impl_unary_func!(
     F32,
     F64
 );
+impl_unary_func!(Tan, tan, __expand_tan, Operator::Tan, F16, BF16, F32, F64);
+impl_unary_func!(
+    Asin,
+    asin,
+    __expand_asin,
+    Operator::Asin,
+    F16,
+    BF16,
+    F32,
+    F64
+);
+impl_unary_func!(
+    Acos,
+    acos,
+    __expand_acos,
+    Operator::Acos,
+    F16,
+    BF16,
+    F32,
+    F64
+);
+impl_unary_func!(
+    Atan,
+    atan,
+    __expand_atan,
+    Operator::Atan,
+    F16,
+    BF16,
+    F32,
+    F64
+);
+impl_unary_func!(
+    Sinh,
+    sinh,
+    __expand_sinh,
+    Operator::Sinh,
+    F16,
+    BF16,
+    F32,
+    F64
+);
+impl_unary_func!(
+    Cosh,
+    cosh,
+    __expand_cosh,
+    Operator::Cosh,
+    F16,
+    BF16,
+    F32,
+    F64
+);
+impl_unary_func!(
+    Exp2,
+    exp2,
+    __expand_exp2,
+    Operator::Exp2,
+    F16,
+    BF16,
+    F32,
+    F64
+);
+impl_unary_func!(
+    Log2,
+    log2,
+    __expand_log2,
+    Operator::Log2,
+    F16,
+    BF16,
+    F32,
+    F64
+);
+impl_unary_func!(
+    Round,
+    round,
+    __expand_round,
+    Operator::Round,
+    F16,
+    BF16,
+    F32,
+    F64
+);
+impl_unary_func!(
+    Trunc,
+    trunc,
+    __expand_trunc,
+    Operator::Trunc,
+    F16,
+    BF16,
+    F32,
+    F64
+);
+impl_unary_func!(
+    Fract,
+    fract,
+    __expand_fract,
+    Operator::Fract,
+    F16,
+    BF16,
+    F32,
+    F64
+);
+impl_unary_func!(
+    Sign,
+    sign,
+    __expand_sign,
+    Operator::Sign,
+    F16,
+    BF16,
+    F32,
+    F64
+);
+impl_unary_func!(
+    Rsqrt,
+    rsqrt,
+    __expand_rsqrt,
+    Operator::Rsqrt,
+    F16,
+    BF16,
+    F32,
+    F64
+);
+impl_unary_func_fixed_out_elem!(
+    IsNan,
+    is_nan,
+    __expand_is_nan,
+    Operator::IsNan,
+    Elem::Bool,
+    F16,
+    BF16,
+    F32,
+    F64
+);
+impl_unary_func_fixed_out_elem!(
+    IsInf,
+    is_inf,
+    __expand_is_inf,
+    Operator::IsInf,
+    Elem::Bool,
+    F16,
+    BF16,
+    F32,
+    F64
+);
+impl_unary_func!(
+    CountOnes,
+    count_ones,
+    __expand_count_ones,
+    Operator::CountOnes,
+    I8,
+    I16,
+    I32,
+    I64,
+    UInt,
+    U8,
+    U16,
+    U64
+);
+impl_unary_func!(
+    ReverseBits,
+    reverse_bits,
+    __expand_reverse_bits,
+    Operator::ReverseBits,
+    I8,
+    I16,
+    I32,
+    I64,
+    UInt,
+    U8,
+    U16,
+    U64
+);
+impl_unary_func!(
+    LeadingZeros,
+    leading_zeros,
+    __expand_leading_zeros,
+    Operator::LeadingZeros,
+    I8,
+    I16,
+    I32,
+    I64,
+    UInt,
+    U8,
+    U16,
+    U64
+);
+impl_unary_func!(
+    TrailingZeros,
+    trailing_zeros,
+    __expand_trailing_zeros,
+    Operator::TrailingZeros,
+    I8,
+    I16,
+    I32,
+    I64,
+    UInt,
+    U8,
+    U16,
+    U64
+);
@@ -123,7 +123,7 @@ impl<T: CubeType> SequenceExpand<T> {
         let index = match value {
             crate::ir::Variable::ConstantScalar(value) => match value {
                 crate::ir::ConstantScalarValue::Int(val, _) => val as usize,
-                crate::ir::ConstantScalarValue::UInt(val) => val as usize,
+                crate::ir::ConstantScalarValue::UInt(val, _) => val as usize,
                 _ => panic!("Only integer types are supported"),
             },
             _ => panic!("Only constant are supported"),
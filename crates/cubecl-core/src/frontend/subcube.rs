@@ -1,7 +1,7 @@
-use super::{CubeContext, CubePrimitive, ExpandElement};
+use super::{Bool, CubeContext, CubePrimitive, ExpandElement, UInt};
 use crate::prelude::ExpandElementTyped;
 use crate::{
-    ir::{Elem, InitOperator, Item, Operation, Subcube, UnaryOperator},
+    ir::{BinaryOperator, Elem, InitOperator, Item, Operation, Subcube, UIntKind, UnaryOperator},
     unexpanded,
 };
 
@@ -165,3 +165,235 @@ pub mod subcube_all {
         output.into()
     }
 }
+
+/// Read the value of the unit whose index is `id` in the subcube.
+#[allow(unused_variables)]
+pub fn subcube_shuffle<E: CubePrimitive>(value: E, id: UInt) -> E {
+    unexpanded!()
+}
+
+/// Module containing the expand function for [subcube_shuffle()].
+pub mod subcube_shuffle {
+    use super::*;
+
+    /// Expand method of [subcube_shuffle()].
+    pub fn __expand<E: CubePrimitive>(
+        context: &mut CubeContext,
+        value: ExpandElementTyped<E>,
+        id: ExpandElementTyped<UInt>,
+    ) -> ExpandElementTyped<E> {
+        shuffle_expand(context, value, id, Subcube::Shuffle)
+    }
+}
+
+/// Read the value of the unit whose index is the index of the current unit xor `mask`.
+#[allow(unused_variables)]
+pub fn subcube_shuffle_xor<E: CubePrimitive>(value: E, mask: UInt) -> E {
+    unexpanded!()
+}
+
+/// Module containing the expand function for [subcube_shuffle_xor()].
+pub mod subcube_shuffle_xor {
+    use super::*;
+
+    /// Expand method of [subcube_shuffle_xor()].
+    pub fn __expand<E: CubePrimitive>(
+        context: &mut CubeContext,
+        value: ExpandElementTyped<E>,
+        mask: ExpandElementTyped<UInt>,
+    ) -> ExpandElementTyped<E> {
+        shuffle_expand(context, value, mask, Subcube::ShuffleXor)
+    }
+}
+
+/// Read the value of the unit whose index is the index of the current unit minus `delta`.
+///
+/// The value of the first `delta` units is undefined.
+#[allow(unused_variables)]
+pub fn subcube_shuffle_up<E: CubePrimitive>(value: E, delta: UInt) -> E {
+    unexpanded!()
+}
+
+/// Module containing the expand function for [subcube_shuffle_up()].
+pub mod subcube_shuffle_up {
+    use super::*;
+
+    /// Expand method of [subcube_shuffle_up()].
+    pub fn __expand<E: CubePrimitive>(
+        context: &mut CubeContext,
+        value: ExpandElementTyped<E>,
+        delta: ExpandElementTyped<UInt>,
+    ) -> ExpandElementTyped<E> {
+        shuffle_expand(context, value, delta, Subcube::ShuffleUp)
+    }
+}
+
+/// Read the value of the unit whose index is the index of the current unit plus `delta`.
+///
+/// The value of the last `delta` units is undefined.
+#[allow(unused_variables)]
+pub fn subcube_shuffle_down<E: CubePrimitive>(value: E, delta: UInt) -> E {
+    unexpanded!()
+}
+
+/// Module containing the expand function for [subcube_shuffle_down()].
+pub mod subcube_shuffle_down {
+    use super::*;
+
+    /// Expand method of [subcube_shuffle_down()].
+    pub fn __expand<E: CubePrimitive>(
+        context: &mut CubeContext,
+        value: ExpandElementTyped<E>,
+        delta: ExpandElementTyped<UInt>,
+    ) -> ExpandElementTyped<E> {
+        shuffle_expand(context, value, delta, Subcube::ShuffleDown)
+    }
+}
+
+/// Perform an inclusive prefix sum across the units of a subcube, ordered by their index.
+pub fn subcube_inclusive_sum<E: CubePrimitive>(_elem: E) -> E {
+    unexpanded!()
+}
+
+/// Module containing the expand function for [subcube_inclusive_sum()].
+pub mod subcube_inclusive_sum {
+    use super::*;
+
+    /// Expand method of [subcube_inclusive_sum()].
+    pub fn __expand<E: CubePrimitive>(
+        context: &mut CubeContext,
+        elem: ExpandElementTyped<E>,
+    ) -> ExpandElementTyped<E> {
+        scan_expand(context, elem, Subcube::InclusiveSum)
+    }
+}
+
+/// Perform an exclusive prefix sum across the units of a subcube, ordered by their index.
+///
+/// The first unit gets zero.
+pub fn subcube_exclusive_sum<E: CubePrimitive>(_elem: E) -> E {
+    unexpanded!()
+}
+
+/// Module containing the expand function for [subcube_exclusive_sum()].
+pub mod subcube_exclusive_sum {
+    use super::*;
+
+    /// Expand method of [subcube_exclusive_sum()].
+    pub fn __expand<E: CubePrimitive>(
+        context: &mut CubeContext,
+        elem: ExpandElementTyped<E>,
+    ) -> ExpandElementTyped<E> {
+        scan_expand(context, elem, Subcube::ExclusiveSum)
+    }
+}
+
+/// Perform an inclusive prefix product across the units of a subcube, ordered by their index.
+pub fn subcube_inclusive_prod<E: CubePrimitive>(_elem: E) -> E {
+    unexpanded!()
+}
+
+/// Module containing the expand function for [subcube_inclusive_prod()].
+pub mod subcube_inclusive_prod {
+    use super::*;
+
+    /// Expand method of [subcube_inclusive_prod()].
+    pub fn __expand<E: CubePrimitive>(
+        context: &mut CubeContext,
+        elem: ExpandElementTyped<E>,
+    ) -> ExpandElementTyped<E> {
+        scan_expand(context, elem, Subcube::InclusiveProd)
+    }
+}
+
+/// Perform an exclusive prefix product across the units of a subcube, ordered by their index.
+///
+/// The first unit gets one.
+pub fn subcube_exclusive_prod<E: CubePrimitive>(_elem: E) -> E {
+    unexpanded!()
+}
+
+/// Module containing the expand function for [subcube_exclusive_prod()].
+pub mod subcube_exclusive_prod {
+    use super::*;
+
+    /// Expand method of [subcube_exclusive_prod()].
+    pub fn __expand<E: CubePrimitive>(
+        context: &mut CubeContext,
+        elem: ExpandElementTyped<E>,
+    ) -> ExpandElementTyped<E> {
+        scan_expand(context, elem, Subcube::ExclusiveProd)
+    }
+}
+
+/// Returns the bitmask of the active units of the subcube for which `value` is true.
+///
+/// The mask is a vector of four [UInt], the unit `i` being the bit `i % 32` of the component
+/// `i / 32`, so that subcubes of up to 128 units are supported.
+#[allow(unused_variables)]
+pub fn subcube_ballot(value: Bool) -> UInt {
+    unexpanded!()
+}
+
+/// Module containing the expand function for [subcube_ballot()].
+pub mod subcube_ballot {
+    use super::*;
+
+    /// Expand method of [subcube_ballot()].
+    pub fn __expand(
+        context: &mut CubeContext,
+        value: ExpandElementTyped<Bool>,
+    ) -> ExpandElementTyped<UInt> {
+        let value: ExpandElement = value.into();
+        let output = context.create_local(Item::vectorized(Elem::UInt(UIntKind::U32), 4));
+
+        let out = *output;
+        let input = *value;
+
+        context.register(Operation::Subcube(Subcube::Ballot(UnaryOperator {
+            input,
+            out,
+        })));
+
+        output.into()
+    }
+}
+
+fn shuffle_expand<E: CubePrimitive>(
+    context: &mut CubeContext,
+    value: ExpandElementTyped<E>,
+    id: ExpandElementTyped<UInt>,
+    operation: fn(BinaryOperator) -> Subcube,
+) -> ExpandElementTyped<E> {
+    let value: ExpandElement = value.into();
+    let id: ExpandElement = id.into();
+    let output = context.create_local(value.item());
+
+    let out = *output;
+    let lhs = *value;
+    let rhs = *id;
+
+    context.register(Operation::Subcube(operation(BinaryOperator {
+        lhs,
+        rhs,
+        out,
+    })));
+
+    output.into()
+}
+
+fn scan_expand<E: CubePrimitive>(
+    context: &mut CubeContext,
+    elem: ExpandElementTyped<E>,
+    operation: fn(UnaryOperator) -> Subcube,
+) -> ExpandElementTyped<E> {
+    let elem: ExpandElement = elem.into();
+    let output = context.create_local(elem.item());
+
+    let out = *output;
+    let input = *elem;
+
+    context.register(Operation::Subcube(operation(UnaryOperator { input, out })));
+
+    output.into()
+}
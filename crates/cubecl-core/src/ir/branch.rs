@@ -1,4 +1,4 @@
-use super::{Elem, Item, Scope, Variable};
+use super::{Elem, Item, Scope, UIntKind, Variable};
 use serde::{Deserialize, Serialize};
 
 /// All branching types.
@@ -8,6 +8,8 @@ pub enum Branch {
     If(If),
     /// An if else statement.
     IfElse(IfElse),
+    /// A switch statement.
+    Switch(Switch),
     /// A range loop.
     RangeLoop(RangeLoop),
     /// A loop.
@@ -33,6 +35,14 @@ pub struct IfElse {
     pub scope_else: Scope,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[allow(missing_docs)]
+pub struct Switch {
+    pub value: Variable,
+    pub scope_default: Scope,
+    pub cases: Vec<(Vec<Variable>, Scope)>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[allow(missing_docs)]
 pub struct RangeLoop {
@@ -96,7 +106,7 @@ impl RangeLoop {
         func: F,
     ) {
         let mut scope = parent_scope.child();
-        let index_ty = Item::new(Elem::UInt);
+        let index_ty = Item::new(Elem::UInt(UIntKind::U32));
         let i = scope.create_local_undeclared(index_ty);
 
         func(i, &mut scope);
@@ -0,0 +1,410 @@
+use super::{
+    macros::cpa, Branch, CoopMma, CubeDim, Elem, Item, KernelDefinition, Matrix, MatrixIdent,
+    MatrixLayout, Operation, RangeLoop, Scope, Synchronization, UIntKind, Variable,
+};
+use std::fmt::Display;
+
+/// The number of units cooperating on each matrix.
+const WARP_SIZE: u32 = 32;
+
+/// Why the [cooperative matrix operations](CoopMma) of a kernel can't be emulated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CmmaEmulationError {
+    /// The units of the cube can't be split in warps of 32 units.
+    CubeDim(CubeDim),
+    /// A load or a store is in the [function](super::Function) with the given id, which could be
+    /// called from a divergent branch.
+    FunctionMemoryAccess(u16),
+}
+
+impl KernelDefinition {
+    /// Rewrite the [cooperative matrix operations](CoopMma) of the kernel and of its functions
+    /// into scalar loops over [local arrays](Variable::LocalArray), for the compilers without
+    /// native support. The same kernel then runs on every backend, more slowly.
+    ///
+    /// Each matrix is replaced by a fragment with room for all of its elements, stored in row
+    /// major order. The units of a warp share the work on the accumulators: the unit
+    /// `UNIT_POS % 32` only fills, loads, computes and stores the elements whose index has the
+    /// same value modulo 32. The fragments of `A` and `B` are loaded in full by every unit
+    /// instead, since each element of the accumulator needs a whole row and a whole column of
+    /// them.
+    ///
+    /// The warps are made of 32 consecutive units whatever the subcube size of the device, since
+    /// the kernels using cooperative matrices compute the position of the units in their warp
+    /// this way. A cube of less than 32 units is a single warp, and bigger cubes must have a
+    /// multiple of 32 units.
+    ///
+    /// Those kernels also rely on loads and stores synchronizing the units of the warp, so they
+    /// are surrounded by barriers on the whole cube. A barrier must be reached by every unit, so
+    /// the loads and stores in a branch or a loop get the barriers around the outermost branch
+    /// instead, in the body of the kernel. The loads and stores in functions are rejected, since
+    /// they could be called from a divergent branch.
+    ///
+    /// Notes:
+    ///
+    /// This should be called before the scopes are processed.
+    pub fn emulate_cmma(&mut self) -> Result<(), CmmaEmulationError> {
+        let num_units = self.cube_dim.num_elems();
+        let uses_cmma = has_cmma(&self.body)
+            || self
+                .functions
+                .iter()
+                .any(|function| has_cmma(&function.scope));
+
+        if !uses_cmma {
+            return Ok(());
+        }
+        if num_units > WARP_SIZE && !num_units.is_multiple_of(WARP_SIZE) {
+            return Err(CmmaEmulationError::CubeDim(self.cube_dim));
+        }
+        if let Some(function) = self
+            .functions
+            .iter()
+            .find(|function| has_memory_access(&function.scope))
+        {
+            return Err(CmmaEmulationError::FunctionMemoryAccess(function.id));
+        }
+
+        let mut emulation = Emulation {
+            warp_size: u32::min(num_units, WARP_SIZE),
+            fragments: Vec::new(),
+        };
+        emulation.scope(&mut self.body, true);
+
+        for function in self.functions.iter_mut() {
+            emulation.scope(&mut function.scope, false);
+        }
+
+        Ok(())
+    }
+}
+
+struct Emulation {
+    /// The number of units sharing the elements of the accumulators.
+    warp_size: u32,
+    /// The matrices declared in the enclosing scopes, with the fragments replacing them.
+    fragments: Vec<(Variable, Variable)>,
+}
+
+impl Emulation {
+    /// Emulate the operations of the scope, adding barriers around the loads and stores when
+    /// the scope is reached by every unit of the cube.
+    fn scope(&mut self, scope: &mut Scope, uniform: bool) {
+        let num_fragments = self.fragments.len();
+
+        for matrix in core::mem::take(&mut scope.matrices) {
+            let mat = as_matrix(matrix);
+            let (rows, cols) = shape(&mat);
+            let fragment = scope.create_local_array(Item::new(mat.elem), rows * cols);
+            self.fragments.push((matrix, fragment));
+        }
+
+        for operation in core::mem::take(&mut scope.operations) {
+            match operation {
+                Operation::CoopMma(op) => self.emulate(scope, op, uniform),
+                Operation::Branch(mut branch) => {
+                    let barrier = uniform && branch_scopes(&branch).any(has_memory_access);
+
+                    for child in branch_scopes_mut(&mut branch) {
+                        self.scope(child, false);
+                    }
+
+                    if barrier {
+                        scope.register(Synchronization::SyncUnits);
+                        scope.register(branch);
+                        scope.register(Synchronization::SyncUnits);
+                    } else {
+                        scope.register(branch);
+                    }
+                }
+                _ => scope.register(operation),
+            }
+        }
+
+        // The matrices of this scope can't be used by its parent.
+        self.fragments.truncate(num_fragments);
+    }
+
+    fn emulate(&self, scope: &mut Scope, op: CoopMma, uniform: bool) {
+        let fragments = self.fragments.as_slice();
+        let warp_size = self.warp_size;
+
+        match op {
+            CoopMma::Fill { mat, value } => {
+                let fragment = fragment(fragments, mat);
+                let elem = as_matrix(mat).elem;
+
+                for_each_element(scope, mat, warp_size, |index, scope| {
+                    let value = read(scope, value, None, elem);
+                    cpa!(scope, unchecked(fragment[index]) = value);
+                });
+            }
+            CoopMma::Load { mat, value, stride } => {
+                let fragment = fragment(fragments, mat);
+                let matrix = as_matrix(mat);
+
+                if uniform {
+                    scope.register(Synchronization::SyncUnits);
+                }
+                for_each_element(scope, mat, warp_size, |index, scope| {
+                    let position = position(scope, &matrix, matrix.layout, index, stride);
+                    let value = read(scope, value, Some(position), matrix.elem);
+                    cpa!(scope, unchecked(fragment[index]) = value);
+                });
+            }
+            CoopMma::Execute {
+                mat_a,
+                mat_b,
+                mat_c,
+                mat_d,
+            } => {
+                let (fragment_a, fragment_b) =
+                    (fragment(fragments, mat_a), fragment(fragments, mat_b));
+                let (fragment_c, fragment_d) =
+                    (fragment(fragments, mat_c), fragment(fragments, mat_d));
+                let matrix = as_matrix(mat_d);
+                let (k, n): (Variable, Variable) =
+                    ((matrix.k as u32).into(), (matrix.n as u32).into());
+                let elem = matrix.elem;
+
+                for_each_element(scope, mat_d, warp_size, |index, scope| {
+                    let row = scope.create_local(u32_item());
+                    let col = scope.create_local(u32_item());
+                    let row_offset = scope.create_local(u32_item());
+                    cpa!(scope, row = index / n);
+                    cpa!(scope, col = index % n);
+                    cpa!(scope, row_offset = row * k);
+
+                    let accumulator = read(scope, fragment_c, Some(index), elem);
+
+                    cpa!(
+                        scope,
+                        range(0u32, k).for_each(|i, scope| {
+                            let index_a = scope.create_local(u32_item());
+                            let index_b = scope.create_local(u32_item());
+                            cpa!(scope, index_a = row_offset + i);
+                            cpa!(scope, index_b = i * n);
+                            cpa!(scope, index_b = index_b + col);
+
+                            let a = read(scope, fragment_a, Some(index_a), elem);
+                            let b = read(scope, fragment_b, Some(index_b), elem);
+                            cpa!(scope, a = a * b);
+                            cpa!(scope, accumulator += a);
+                        })
+                    );
+
+                    cpa!(scope, unchecked(fragment_d[index]) = accumulator);
+                });
+            }
+            CoopMma::Store {
+                output,
+                mat,
+                stride,
+                layout,
+            } => {
+                let fragment = fragment(fragments, mat);
+                let matrix = as_matrix(mat);
+                let elem = output.item().elem();
+
+                // Each unit stores the elements of the accumulators it computed, while the
+                // fragments of `A` and `B` are complete in every unit, so their stores can be
+                // split the same way.
+                if uniform {
+                    scope.register(Synchronization::SyncUnits);
+                }
+                for_each_distributed_element(scope, &matrix, warp_size, |index, scope| {
+                    let position = position(scope, &matrix, layout, index, stride);
+                    let value = read(scope, fragment, Some(index), elem);
+                    cpa!(scope, output[position] = value);
+                });
+                if uniform {
+                    scope.register(Synchronization::SyncUnits);
+                }
+            }
+        }
+    }
+}
+
+impl Display for CmmaEmulationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CmmaEmulationError::CubeDim(cube_dim) => f.write_fmt(format_args!(
+                "Cooperative matrices can't be emulated with a cube of {} units, it must be less than {WARP_SIZE} units or a multiple of {WARP_SIZE}",
+                cube_dim.num_elems()
+            )),
+            CmmaEmulationError::FunctionMemoryAccess(id) => f.write_fmt(format_args!(
+                "Cooperative matrix loads and stores can't be emulated in the function {id}, since they synchronize the whole cube"
+            )),
+        }
+    }
+}
+
+fn has_cmma(scope: &Scope) -> bool {
+    scope.operations.iter().any(|operation| match operation {
+        Operation::CoopMma(_) => true,
+        Operation::Branch(branch) => branch_scopes(branch).any(has_cmma),
+        _ => false,
+    })
+}
+
+/// Whether the scope or one of its branches loads or stores a matrix.
+fn has_memory_access(scope: &Scope) -> bool {
+    scope.operations.iter().any(|operation| match operation {
+        Operation::CoopMma(CoopMma::Load { .. } | CoopMma::Store { .. }) => true,
+        Operation::Branch(branch) => branch_scopes(branch).any(has_memory_access),
+        _ => false,
+    })
+}
+
+fn branch_scopes(branch: &Branch) -> impl Iterator<Item = &Scope> {
+    let scopes: Vec<&Scope> = match branch {
+        Branch::If(op) => vec![&op.scope],
+        Branch::IfElse(op) => vec![&op.scope_if, &op.scope_else],
+        Branch::Switch(op) => core::iter::once(&op.scope_default)
+            .chain(op.cases.iter().map(|(_, scope)| scope))
+            .collect(),
+        Branch::RangeLoop(op) => vec![&op.scope],
+        Branch::Loop(op) => vec![&op.scope],
+        Branch::Return | Branch::Break => Vec::new(),
+    };
+
+    scopes.into_iter()
+}
+
+fn branch_scopes_mut(branch: &mut Branch) -> impl Iterator<Item = &mut Scope> {
+    let scopes: Vec<&mut Scope> = match branch {
+        Branch::If(op) => vec![&mut op.scope],
+        Branch::IfElse(op) => vec![&mut op.scope_if, &mut op.scope_else],
+        Branch::Switch(op) => core::iter::once(&mut op.scope_default)
+            .chain(op.cases.iter_mut().map(|(_, scope)| scope))
+            .collect(),
+        Branch::RangeLoop(op) => vec![&mut op.scope],
+        Branch::Loop(op) => vec![&mut op.scope],
+        Branch::Return | Branch::Break => Vec::new(),
+    };
+
+    scopes.into_iter()
+}
+
+/// Iterate over the elements of the fragment handled by the current unit.
+fn for_each_element<F: Fn(Variable, &mut Scope)>(
+    scope: &mut Scope,
+    mat: Variable,
+    warp_size: u32,
+    func: F,
+) {
+    let matrix = as_matrix(mat);
+
+    match matrix.ident {
+        MatrixIdent::Accumulator => for_each_distributed_element(scope, &matrix, warp_size, func),
+        MatrixIdent::A | MatrixIdent::B => {
+            let (rows, cols) = shape(&matrix);
+            cpa!(scope, range(0u32, rows * cols).for_each(func));
+        }
+    }
+}
+
+/// Iterate over the elements of the fragment whose index modulo the warp size is the position
+/// of the unit in its warp.
+fn for_each_distributed_element<F: Fn(Variable, &mut Scope)>(
+    scope: &mut Scope,
+    matrix: &Matrix,
+    warp_size: u32,
+    func: F,
+) {
+    let (rows, cols) = shape(matrix);
+    let unit_pos = Variable::UnitPos;
+    let warp_size: Variable = warp_size.into();
+    let lane = scope.create_local(u32_item());
+    cpa!(scope, lane = unit_pos % warp_size);
+
+    RangeLoop::register(scope, lane, (rows * cols).into(), Some(warp_size), func);
+}
+
+/// The position in memory of the element at the given index of the fragment.
+fn position(
+    scope: &mut Scope,
+    matrix: &Matrix,
+    layout: MatrixLayout,
+    index: Variable,
+    stride: Variable,
+) -> Variable {
+    let (_, cols) = shape(matrix);
+    let cols: Variable = cols.into();
+    let row = scope.create_local(u32_item());
+    let col = scope.create_local(u32_item());
+    let position = scope.create_local(u32_item());
+    cpa!(scope, row = index / cols);
+    cpa!(scope, col = index % cols);
+
+    match layout {
+        // Accumulators are loaded in row major order when no layout is given.
+        MatrixLayout::RowMajor | MatrixLayout::Undefined => {
+            cpa!(scope, position = row * stride);
+            cpa!(scope, position = position + col);
+        }
+        MatrixLayout::ColMajor => {
+            cpa!(scope, position = col * stride);
+            cpa!(scope, position = position + row);
+        }
+    }
+
+    position
+}
+
+/// Read a value, or the element of an array at the given index, into a new local variable of
+/// the given element type.
+fn read(scope: &mut Scope, array: Variable, index: Option<Variable>, elem: Elem) -> Variable {
+    let item = array.item();
+    let value = scope.create_local(item);
+
+    match index {
+        Some(index) if matches!(array, Variable::LocalArray { .. }) => {
+            cpa!(scope, value = unchecked(array[index]));
+        }
+        Some(index) => {
+            cpa!(scope, value = array[index]);
+        }
+        None => {
+            cpa!(scope, value = array);
+        }
+    }
+
+    if item.elem() == elem {
+        return value;
+    }
+
+    let converted = scope.create_local(Item::new(elem));
+    cpa!(scope, converted = cast(value));
+    converted
+}
+
+fn fragment(fragments: &[(Variable, Variable)], mat: Variable) -> Variable {
+    fragments
+        .iter()
+        .find(|(matrix, _)| *matrix == mat)
+        .map(|(_, fragment)| *fragment)
+        .unwrap_or_else(|| panic!("Matrix {mat:?} isn't declared in an enclosing scope."))
+}
+
+fn as_matrix(mat: Variable) -> Matrix {
+    match mat {
+        Variable::Matrix { mat, .. } => mat,
+        _ => panic!("Expected a matrix, got {mat:?}."),
+    }
+}
+
+/// The number of rows and columns of the matrix.
+fn shape(matrix: &Matrix) -> (u32, u32) {
+    let (m, n, k) = (matrix.m as u32, matrix.n as u32, matrix.k as u32);
+
+    match matrix.ident {
+        MatrixIdent::A => (m, k),
+        MatrixIdent::B => (k, n),
+        MatrixIdent::Accumulator => (m, n),
+    }
+}
+
+fn u32_item() -> Item {
+    Item::new(Elem::UInt(UIntKind::U32))
+}
@@ -0,0 +1,35 @@
+use super::{Scope, Variable};
+use serde::{Deserialize, Serialize};
+
+/// A function that isn't inlined into its callers.
+///
+/// The function has its own [scope](Scope), where the inputs are local variables assigned from
+/// the arguments of each [call](Call). Global arrays used by the function are accessed directly.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[allow(missing_docs)]
+pub struct Function {
+    pub id: u16,
+    pub name: String,
+    pub inputs: Vec<Variable>,
+    pub output: Option<Variable>,
+    pub scope: Scope,
+}
+
+/// A call to a [function](Function) of the kernel.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[allow(missing_docs)]
+pub struct Call {
+    pub function: u16,
+    pub inputs: Vec<Variable>,
+    pub output: Option<Variable>,
+}
+
+impl Function {
+    /// Whether the function has the same signature and body as another one, ignoring its id.
+    pub(crate) fn is_equivalent(&self, other: &Function) -> bool {
+        self.name == other.name
+            && self.inputs == other.inputs
+            && self.output == other.output
+            && self.scope == other.scope
+    }
+}
@@ -1,15 +1,16 @@
-use super::{ConstantScalarValue, Scope, Variable, Vectorization};
+use super::{ConstantScalarValue, Function, Scope, Variable, Vectorization};
 use crate::SUBCUBE_DIM_APPROX;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[allow(missing_docs)]
 pub struct KernelDefinition {
     pub inputs: Vec<Binding>,
     pub outputs: Vec<Binding>,
     pub named: Vec<(String, Binding)>,
     pub cube_dim: CubeDim,
+    pub functions: Vec<Function>,
     pub body: Scope,
 }
 
@@ -39,17 +40,29 @@ pub enum FloatKind {
 #[derive(Debug, Clone, PartialEq, Eq, Copy, Hash, Serialize, Deserialize, PartialOrd, Ord)]
 #[allow(missing_docs)]
 pub enum IntKind {
+    I8,
+    I16,
     I32,
     I64,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+#[allow(missing_docs)]
+pub enum UIntKind {
+    U8,
+    U16,
+    U32,
+    U64,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Copy, Hash, Serialize, Deserialize, PartialOrd, Ord)]
 #[allow(missing_docs)]
 pub enum Elem {
     Float(FloatKind),
+    AtomicFloat(FloatKind),
     Int(IntKind),
     AtomicInt(IntKind),
-    UInt,
+    UInt(UIntKind),
     AtomicUInt,
     Bool,
 }
@@ -60,12 +73,12 @@ impl Elem {
     /// The output will have the same type as the element.
     pub fn constant_from_f64(&self, val: f64) -> Variable {
         Variable::ConstantScalar(match self {
-            Elem::Float(kind) => ConstantScalarValue::Float(val, *kind),
+            Elem::Float(kind) | Elem::AtomicFloat(kind) => ConstantScalarValue::Float(val, *kind),
             Elem::Int(kind) => ConstantScalarValue::Int(val as i64, *kind),
-            Elem::UInt => ConstantScalarValue::UInt(val as u64),
+            Elem::UInt(kind) => ConstantScalarValue::UInt(val as u64, *kind),
             Elem::Bool => ConstantScalarValue::Bool(val > 0.0),
             Elem::AtomicInt(kind) => ConstantScalarValue::Int(val as i64, *kind),
-            Elem::AtomicUInt => ConstantScalarValue::UInt(val as u64),
+            Elem::AtomicUInt => ConstantScalarValue::UInt(val as u64, UIntKind::U32),
         })
     }
     /// Create a constant scalar from a signed integer.
@@ -73,12 +86,14 @@ impl Elem {
     /// The output will have the same type as the element.
     pub fn constant_from_i64(&self, val: i64) -> Variable {
         Variable::ConstantScalar(match self {
-            Elem::Float(kind) => ConstantScalarValue::Float(val as f64, *kind),
+            Elem::Float(kind) | Elem::AtomicFloat(kind) => {
+                ConstantScalarValue::Float(val as f64, *kind)
+            }
             Elem::Int(kind) => ConstantScalarValue::Int(val, *kind),
-            Elem::UInt => ConstantScalarValue::UInt(val as u64),
+            Elem::UInt(kind) => ConstantScalarValue::UInt(val as u64, *kind),
             Elem::Bool => ConstantScalarValue::Bool(val > 0),
             Elem::AtomicInt(kind) => ConstantScalarValue::Int(val, *kind),
-            Elem::AtomicUInt => ConstantScalarValue::UInt(val as u64),
+            Elem::AtomicUInt => ConstantScalarValue::UInt(val as u64, UIntKind::U32),
         })
     }
     /// Create a constant scalar from a unsigned integer.
@@ -86,12 +101,14 @@ impl Elem {
     /// The output will have the same type as the element.
     pub fn constant_from_u64(&self, val: u64) -> Variable {
         Variable::ConstantScalar(match self {
-            Elem::Float(kind) => ConstantScalarValue::Float(val as f64, *kind),
+            Elem::Float(kind) | Elem::AtomicFloat(kind) => {
+                ConstantScalarValue::Float(val as f64, *kind)
+            }
             Elem::Int(kind) => ConstantScalarValue::Int(val as i64, *kind),
-            Elem::UInt => ConstantScalarValue::UInt(val),
+            Elem::UInt(kind) => ConstantScalarValue::UInt(val, *kind),
             Elem::Bool => ConstantScalarValue::Bool(val > 0),
             Elem::AtomicInt(kind) => ConstantScalarValue::Int(val as i64, *kind),
-            Elem::AtomicUInt => ConstantScalarValue::UInt(val),
+            Elem::AtomicUInt => ConstantScalarValue::UInt(val, UIntKind::U32),
         })
     }
     /// Create a constant scalar from a boolean.
@@ -99,11 +116,13 @@ impl Elem {
     /// The output will have the same type as the element.
     pub fn constant_from_bool(&self, val: bool) -> Variable {
         Variable::ConstantScalar(match self {
-            Elem::Float(kind) => ConstantScalarValue::Float(val as u32 as f64, *kind),
+            Elem::Float(kind) | Elem::AtomicFloat(kind) => {
+                ConstantScalarValue::Float(val as u32 as f64, *kind)
+            }
             Elem::Int(kind) => ConstantScalarValue::Int(val as i64, *kind),
             Elem::AtomicInt(kind) => ConstantScalarValue::Int(val as i64, *kind),
-            Elem::UInt => ConstantScalarValue::UInt(val as u64),
-            Elem::AtomicUInt => ConstantScalarValue::UInt(val as u64),
+            Elem::UInt(kind) => ConstantScalarValue::UInt(val as u64, *kind),
+            Elem::AtomicUInt => ConstantScalarValue::UInt(val as u64, UIntKind::U32),
             Elem::Bool => ConstantScalarValue::Bool(val),
         })
     }
@@ -118,35 +137,41 @@ impl Elem {
         match value {
             ConstantScalarValue::Int(val, _) => self.constant_from_i64(val),
             ConstantScalarValue::Float(val, _) => self.constant_from_f64(val),
-            ConstantScalarValue::UInt(val) => self.constant_from_u64(val),
+            ConstantScalarValue::UInt(val, _) => self.constant_from_u64(val),
             ConstantScalarValue::Bool(val) => self.constant_from_bool(val),
         }
     }
     /// Get the size in bytes.
     pub fn size(&self) -> usize {
         match self {
-            Elem::Float(kind) => match kind {
+            Elem::Float(kind) | Elem::AtomicFloat(kind) => match kind {
                 FloatKind::F16 => core::mem::size_of::<half::f16>(),
                 FloatKind::BF16 => core::mem::size_of::<half::bf16>(),
                 FloatKind::F32 => core::mem::size_of::<f32>(),
                 FloatKind::F64 => core::mem::size_of::<f64>(),
             },
-            Elem::Int(kind) => match kind {
+            Elem::Int(kind) | Elem::AtomicInt(kind) => match kind {
+                IntKind::I8 => core::mem::size_of::<i8>(),
+                IntKind::I16 => core::mem::size_of::<i16>(),
                 IntKind::I32 => core::mem::size_of::<i32>(),
                 IntKind::I64 => core::mem::size_of::<i64>(),
             },
-            Elem::AtomicInt(kind) => match kind {
-                IntKind::I32 => core::mem::size_of::<i32>(),
-                IntKind::I64 => core::mem::size_of::<i64>(),
+            Elem::UInt(kind) => match kind {
+                UIntKind::U8 => core::mem::size_of::<u8>(),
+                UIntKind::U16 => core::mem::size_of::<u16>(),
+                UIntKind::U32 => core::mem::size_of::<u32>(),
+                UIntKind::U64 => core::mem::size_of::<u64>(),
             },
-            Elem::UInt => core::mem::size_of::<u32>(),
             Elem::AtomicUInt => core::mem::size_of::<u32>(),
             Elem::Bool => core::mem::size_of::<bool>(),
         }
     }
 
     pub fn is_atomic(&self) -> bool {
-        matches!(self, Elem::AtomicInt(_) | Elem::AtomicUInt)
+        matches!(
+            self,
+            Elem::AtomicFloat(_) | Elem::AtomicInt(_) | Elem::AtomicUInt
+        )
     }
 }
 
@@ -165,15 +190,30 @@ impl Display for Elem {
                 FloatKind::F32 => f.write_str("f32"),
                 FloatKind::F64 => f.write_str("f64"),
             },
+            Self::AtomicFloat(kind) => match kind {
+                FloatKind::F16 => f.write_str("atomic<f16>"),
+                FloatKind::BF16 => f.write_str("atomic<bf16>"),
+                FloatKind::F32 => f.write_str("atomic<f32>"),
+                FloatKind::F64 => f.write_str("atomic<f64>"),
+            },
             Self::Int(kind) => match kind {
+                IntKind::I8 => f.write_str("i8"),
+                IntKind::I16 => f.write_str("i16"),
                 IntKind::I32 => f.write_str("i32"),
                 IntKind::I64 => f.write_str("i64"),
             },
             Self::AtomicInt(kind) => match kind {
+                IntKind::I8 => f.write_str("atomic<i8>"),
+                IntKind::I16 => f.write_str("atomic<i16>"),
                 IntKind::I32 => f.write_str("atomic<i32>"),
                 IntKind::I64 => f.write_str("atomic<i64>"),
             },
-            Self::UInt => f.write_str("uint"),
+            Self::UInt(kind) => match kind {
+                UIntKind::U8 => f.write_str("u8"),
+                UIntKind::U16 => f.write_str("u16"),
+                UIntKind::U32 => f.write_str("uint"),
+                UIntKind::U64 => f.write_str("u64"),
+            },
             Self::AtomicUInt => f.write_str("atomic<uint>"),
             Self::Bool => f.write_str("bool"),
         }
@@ -1,6 +1,6 @@
 use crate::ir::ConstantScalarValue;
 
-use super::{FloatKind, IntKind, Variable};
+use super::{FloatKind, IntKind, UIntKind, Variable};
 
 #[macro_export(local_inner_macros)]
 /// Cube Pseudo Assembly.
@@ -109,6 +109,16 @@ macro_rules! cpa {
             cpa!(binary $lhs, $rhs, $out)
         ));
     };
+    // out = lhs | rhs
+    ($scope:expr, $out: ident = $lhs:ident | $rhs:ident) => {
+        cpa!($scope, $out = bitwise_or($lhs, $rhs))
+    };
+    // out = bitwise_or(lhs, rhs)
+    ($scope:expr, $out:ident = bitwise_or($lhs:expr, $rhs:expr)) => {
+        $scope.register($crate::ir::Operator::BitwiseOr(
+            cpa!(binary $lhs, $rhs, $out)
+        ));
+    };
     // out = lhs ^ rhs
     ($scope:expr, $out: ident = $lhs:ident ^ $rhs:ident) => {
         cpa!($scope, $out = bitwise_xor($lhs, $rhs))
@@ -119,6 +129,12 @@ macro_rules! cpa {
             cpa!(binary $lhs, $rhs, $out)
         ));
     };
+    // out = bitwise_not(input)
+    ($scope:expr, $out:ident = bitwise_not($input:expr)) => {
+        $scope.register($crate::ir::Operator::BitwiseNot(
+            cpa!(unary $input, $out)
+        ));
+    };
     // out = lhs << rhs
     ($scope:expr, $out: ident = $lhs:ident << $rhs:ident) => {
         cpa!($scope, $out = shift_left($lhs, $rhs))
@@ -249,6 +265,12 @@ macro_rules! cpa {
             cpa!(unary $input, $out)
         ));
     };
+    // out = neg(input)
+    ($scope:expr, $out:ident = neg($input:expr)) => {
+        $scope.register($crate::ir::Operator::Neg(
+            cpa!(unary $input, $out)
+        ));
+    };
     // out = exp(input)
     ($scope:expr, $out:ident = exp($input:expr)) => {
         $scope.register($crate::ir::Operator::Exp(
@@ -317,7 +339,7 @@ macro_rules! cpa {
     };
     // out = vec4(a, b, c, d)
     ($scope:expr, $out:ident = vec4($a:ident,$b:ident,$c:ident,$d:ident)) => {
-        let i = $scope.zero(Elem::UInt);
+        let i = $scope.zero(Elem::UInt(UIntKind::U32));
         cpa!($scope, $out[i] = $a);
         cpa!($scope, i = i + 1u32);
         cpa!($scope, $out[i] = $b);
@@ -422,6 +444,18 @@ impl From<i32> for Variable {
     }
 }
 
+impl From<i8> for Variable {
+    fn from(value: i8) -> Self {
+        Variable::ConstantScalar(ConstantScalarValue::Int(value as i64, IntKind::I8))
+    }
+}
+
+impl From<i16> for Variable {
+    fn from(value: i16) -> Self {
+        Variable::ConstantScalar(ConstantScalarValue::Int(value as i64, IntKind::I16))
+    }
+}
+
 impl From<i64> for Variable {
     fn from(value: i64) -> Self {
         Variable::ConstantScalar(ConstantScalarValue::Int(value, IntKind::I64))
@@ -442,13 +476,31 @@ impl From<f64> for Variable {
 
 impl From<u32> for Variable {
     fn from(value: u32) -> Self {
-        Variable::ConstantScalar(ConstantScalarValue::UInt(value as u64))
+        Variable::ConstantScalar(ConstantScalarValue::UInt(value as u64, UIntKind::U32))
+    }
+}
+
+impl From<u8> for Variable {
+    fn from(value: u8) -> Self {
+        Variable::ConstantScalar(ConstantScalarValue::UInt(value as u64, UIntKind::U8))
+    }
+}
+
+impl From<u16> for Variable {
+    fn from(value: u16) -> Self {
+        Variable::ConstantScalar(ConstantScalarValue::UInt(value as u64, UIntKind::U16))
+    }
+}
+
+impl From<u64> for Variable {
+    fn from(value: u64) -> Self {
+        Variable::ConstantScalar(ConstantScalarValue::UInt(value, UIntKind::U64))
     }
 }
 
 impl From<usize> for Variable {
     fn from(value: usize) -> Self {
-        Variable::ConstantScalar(ConstantScalarValue::UInt(value as u64))
+        Variable::ConstantScalar(ConstantScalarValue::UInt(value as u64, UIntKind::U32))
     }
 }
 
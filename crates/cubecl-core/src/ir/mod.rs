@@ -1,24 +1,34 @@
 mod branch;
 mod cmma;
+mod cmma_emulation;
+mod function;
 mod kernel;
 mod macros;
 mod operation;
+mod optimization;
 mod procedure;
 mod processing;
 mod scope;
 mod subcube;
 mod synchronization;
+mod text;
+mod validation;
 mod variable;
 mod vectorization;
 
 pub use branch::*;
 pub use cmma::*;
+pub use cmma_emulation::*;
+pub use function::*;
 pub use kernel::*;
 pub use operation::*;
+pub use optimization::*;
 pub use procedure::*;
 pub use scope::*;
 pub use subcube::*;
 pub use synchronization::*;
+pub use text::*;
+pub use validation::*;
 pub use variable::*;
 pub use vectorization::*;
 
@@ -1,4 +1,4 @@
-use super::{Branch, CoopMma, Procedure, Subcube, Synchronization, Variable};
+use super::{Branch, Call, CoopMma, Procedure, Subcube, Synchronization, Variable};
 use serde::{Deserialize, Serialize};
 
 /// All operations that can be used in a GPU compute shader.
@@ -19,6 +19,7 @@ pub enum Operation {
     Synchronization(Synchronization),
     Subcube(Subcube),
     CoopMma(CoopMma),
+    Call(Call),
 }
 
 /// All operators that can be used in a GPU compute shader.
@@ -43,6 +44,24 @@ pub enum Operator {
     Ceil(UnaryOperator),
     Erf(UnaryOperator),
     Recip(UnaryOperator),
+    Tan(UnaryOperator),
+    Asin(UnaryOperator),
+    Acos(UnaryOperator),
+    Atan(UnaryOperator),
+    Atan2(BinaryOperator),
+    Sinh(UnaryOperator),
+    Cosh(UnaryOperator),
+    Exp2(UnaryOperator),
+    Log2(UnaryOperator),
+    Round(UnaryOperator),
+    Trunc(UnaryOperator),
+    Fract(UnaryOperator),
+    Sign(UnaryOperator),
+    CopySign(BinaryOperator),
+    Hypot(BinaryOperator),
+    Rsqrt(UnaryOperator),
+    IsNan(UnaryOperator),
+    IsInf(UnaryOperator),
     Equal(BinaryOperator),
     NotEqual(BinaryOperator),
     Lower(BinaryOperator),
@@ -63,7 +82,14 @@ pub enum Operator {
     Max(BinaryOperator),
     Min(BinaryOperator),
     BitwiseAnd(BinaryOperator),
+    BitwiseOr(BinaryOperator),
     BitwiseXor(BinaryOperator),
+    BitwiseNot(UnaryOperator),
+    Neg(UnaryOperator),
+    CountOnes(UnaryOperator),
+    ReverseBits(UnaryOperator),
+    LeadingZeros(UnaryOperator),
+    TrailingZeros(UnaryOperator),
     ShiftLeft(BinaryOperator),
     ShiftRight(BinaryOperator),
     Remainder(BinaryOperator),
@@ -192,6 +218,12 @@ impl From<Synchronization> for Operation {
     }
 }
 
+impl From<Call> for Operation {
+    fn from(value: Call) -> Self {
+        Self::Call(value)
+    }
+}
+
 impl From<Metadata> for Operation {
     fn from(val: Metadata) -> Self {
         Operation::Metadata(val)
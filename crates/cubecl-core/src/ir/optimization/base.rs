@@ -0,0 +1,63 @@
+use super::{common_subexpression, constant_folding, copy_propagation, dead_code};
+use crate::ir::processing::ScopeProcessing;
+
+/// An optimization pass executed on a [processed scope](ScopeProcessing).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OptimizationPass {
+    /// Replace the operations on [constant scalars](crate::ir::ConstantScalarValue) by their
+    /// result.
+    ConstantFolding,
+    /// Reuse the result of a previous [stride or shape](crate::ir::Metadata) read of the same
+    /// variable and dimension.
+    CommonSubexpressionElimination,
+    /// Read the source of an [assignment](crate::ir::Operator::Assign) instead of its output.
+    CopyPropagation,
+    /// Remove the operations whose output is never read, as well as the unused local variables.
+    DeadCodeElimination,
+}
+
+/// The [optimization passes](OptimizationPass) executed when processing a scope, in order.
+///
+/// No pass is executed by default, each compiler opts in with
+/// [optimizations](crate::Compiler::optimizations).
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Optimizations {
+    passes: Vec<OptimizationPass>,
+}
+
+impl Optimizations {
+    /// Execute the given passes in order.
+    pub fn new(passes: Vec<OptimizationPass>) -> Self {
+        Self { passes }
+    }
+
+    /// Execute every optimization pass.
+    pub fn all() -> Self {
+        Self::new(vec![
+            OptimizationPass::ConstantFolding,
+            OptimizationPass::CommonSubexpressionElimination,
+            OptimizationPass::CopyPropagation,
+            OptimizationPass::DeadCodeElimination,
+        ])
+    }
+
+    /// The passes to execute, in order.
+    pub fn passes(&self) -> &[OptimizationPass] {
+        &self.passes
+    }
+
+    pub(crate) fn apply(&self, mut processing: ScopeProcessing) -> ScopeProcessing {
+        for pass in self.passes.iter() {
+            match pass {
+                OptimizationPass::ConstantFolding => constant_folding::apply(&mut processing),
+                OptimizationPass::CommonSubexpressionElimination => {
+                    common_subexpression::apply(&mut processing)
+                }
+                OptimizationPass::CopyPropagation => copy_propagation::apply(&mut processing),
+                OptimizationPass::DeadCodeElimination => dead_code::apply(&mut processing),
+            }
+        }
+
+        processing
+    }
+}
@@ -0,0 +1,83 @@
+use super::usage::for_each_written_variable;
+use crate::ir::{
+    processing::ScopeProcessing, Metadata, Operation, Operator, UnaryOperator, Variable,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MetadataKind {
+    Stride,
+    Shape,
+}
+
+/// A stride or shape read whose output still holds its value.
+#[derive(Debug, Clone, Copy)]
+struct MetadataRead {
+    kind: MetadataKind,
+    var: Variable,
+    dim: Variable,
+    out: Variable,
+}
+
+impl MetadataRead {
+    fn from_operation(operation: &Operation) -> Option<Self> {
+        match operation {
+            Operation::Metadata(Metadata::Stride { dim, var, out }) => Some(Self {
+                kind: MetadataKind::Stride,
+                var: *var,
+                dim: *dim,
+                out: *out,
+            }),
+            Operation::Metadata(Metadata::Shape { dim, var, out }) => Some(Self {
+                kind: MetadataKind::Shape,
+                var: *var,
+                dim: *dim,
+                out: *out,
+            }),
+            _ => None,
+        }
+    }
+
+    fn reads_same(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.var == other.var && self.dim == other.dim
+    }
+
+    fn uses(&self, variable: &Variable) -> bool {
+        self.var == *variable || self.dim == *variable || self.out == *variable
+    }
+}
+
+/// Replace the stride and shape reads that were already done by an assignment of the previous
+/// result.
+///
+/// Only the operations of the current scope are considered, the nested scopes are optimized when
+/// they are processed.
+pub(super) fn apply(processing: &mut ScopeProcessing) {
+    let mut available = Vec::<MetadataRead>::new();
+
+    for operation in processing.operations.iter_mut() {
+        match MetadataRead::from_operation(operation) {
+            Some(read) => {
+                let previous = available
+                    .iter()
+                    .find(|previous| previous.reads_same(&read) && previous.out != read.out)
+                    .map(|previous| previous.out);
+
+                available.retain(|previous| !previous.uses(&read.out));
+
+                if let Some(input) = previous {
+                    *operation = Operation::Operator(Operator::Assign(UnaryOperator {
+                        input,
+                        out: read.out,
+                    }));
+                }
+
+                if read.out != read.var && read.out != read.dim {
+                    available.push(read);
+                }
+            }
+            None => for_each_written_variable(operation, &mut |variable| {
+                available.retain(|previous| !previous.uses(variable));
+            }),
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use core::cmp::Ordering;
+
+use super::usage::operator_variables;
+use crate::ir::{
+    processing::ScopeProcessing, BinaryOperator, ConstantScalarValue, FloatKind, IntKind,
+    Operation, Operator, UIntKind, UnaryOperator, Variable,
+};
+
+/// Replace the operators whose inputs are all constant scalars by an assignment of their result.
+pub(super) fn apply(processing: &mut ScopeProcessing) {
+    for operation in processing.operations.iter_mut() {
+        if let Operation::Operator(operator) = operation {
+            if let Some(folded) = fold(operator) {
+                *operator = folded;
+            }
+        }
+    }
+}
+
+/// Evaluate the operator at compile time, returning the equivalent assignment.
+///
+/// Only scalar operations that behave the same on every backend are folded: integer overflows
+/// wrap around, while divisions by zero and non-finite float results are left to the device.
+pub(super) fn fold(operator: &Operator) -> Option<Operator> {
+    let value = match operator {
+        Operator::Add(op) => arithmetic(
+            op,
+            |lhs, rhs| Some(lhs.wrapping_add(rhs)),
+            |lhs, rhs| Some(lhs.wrapping_add(rhs)),
+            |lhs, rhs| lhs + rhs,
+        ),
+        Operator::Sub(op) => arithmetic(
+            op,
+            |lhs, rhs| Some(lhs.wrapping_sub(rhs)),
+            |lhs, rhs| Some(lhs.wrapping_sub(rhs)),
+            |lhs, rhs| lhs - rhs,
+        ),
+        Operator::Mul(op) => arithmetic(
+            op,
+            |lhs, rhs| Some(lhs.wrapping_mul(rhs)),
+            |lhs, rhs| Some(lhs.wrapping_mul(rhs)),
+            |lhs, rhs| lhs * rhs,
+        ),
+        Operator::Div(op) => arithmetic(
+            op,
+            |lhs, rhs| (rhs != 0).then(|| lhs.wrapping_div(rhs)),
+            |lhs, rhs| (rhs != 0).then(|| lhs / rhs),
+            |lhs, rhs| lhs / rhs,
+        ),
+        Operator::Max(op) => arithmetic(
+            op,
+            |lhs, rhs| Some(lhs.max(rhs)),
+            |lhs, rhs| Some(lhs.max(rhs)),
+            f64::max,
+        ),
+        Operator::Min(op) => arithmetic(
+            op,
+            |lhs, rhs| Some(lhs.min(rhs)),
+            |lhs, rhs| Some(lhs.min(rhs)),
+            f64::min,
+        ),
+        Operator::BitwiseAnd(op) => {
+            integer(op, |lhs, rhs| Some(lhs & rhs), |lhs, rhs| Some(lhs & rhs))
+        }
+        Operator::BitwiseOr(op) => {
+            integer(op, |lhs, rhs| Some(lhs | rhs), |lhs, rhs| Some(lhs | rhs))
+        }
+        Operator::BitwiseXor(op) => {
+            integer(op, |lhs, rhs| Some(lhs ^ rhs), |lhs, rhs| Some(lhs ^ rhs))
+        }
+        Operator::Equal(op) => comparison(op, |ord| ord == Ordering::Equal),
+        Operator::NotEqual(op) => comparison(op, |ord| ord != Ordering::Equal),
+        Operator::Lower(op) => comparison(op, |ord| ord == Ordering::Less),
+        Operator::LowerEqual(op) => comparison(op, |ord| ord != Ordering::Greater),
+        Operator::Greater(op) => comparison(op, |ord| ord == Ordering::Greater),
+        Operator::GreaterEqual(op) => comparison(op, |ord| ord != Ordering::Less),
+        Operator::And(op) => logical(op, |lhs, rhs| lhs && rhs),
+        Operator::Or(op) => logical(op, |lhs, rhs| lhs || rhs),
+        Operator::Not(op) => match constant(&op.input)? {
+            ConstantScalarValue::Bool(val) => Some(ConstantScalarValue::Bool(!val)),
+            _ => None,
+        },
+        Operator::Neg(op) => match constant(&op.input)? {
+            ConstantScalarValue::Int(val, kind) => Some(ConstantScalarValue::Int(
+                wrap_int(val.wrapping_neg(), kind),
+                kind,
+            )),
+            ConstantScalarValue::Float(val, kind) => Some(ConstantScalarValue::Float(-val, kind)),
+            _ => None,
+        },
+        Operator::Abs(op) => match constant(&op.input)? {
+            ConstantScalarValue::Int(val, kind) => Some(ConstantScalarValue::Int(
+                wrap_int(val.wrapping_abs(), kind),
+                kind,
+            )),
+            ConstantScalarValue::UInt(val, kind) => Some(ConstantScalarValue::UInt(val, kind)),
+            ConstantScalarValue::Float(val, kind) => {
+                Some(ConstantScalarValue::Float(val.abs(), kind))
+            }
+            ConstantScalarValue::Bool(_) => None,
+        },
+        _ => None,
+    }?;
+
+    let out = *operator_variables(&mut operator.clone()).out;
+    let item = out.item();
+
+    if item.vectorization != 1 || item.elem() != value.elem() {
+        return None;
+    }
+
+    Some(Operator::Assign(UnaryOperator {
+        input: Variable::ConstantScalar(value),
+        out,
+    }))
+}
+
+fn constant(var: &Variable) -> Option<ConstantScalarValue> {
+    match var {
+        Variable::ConstantScalar(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn arithmetic(
+    op: &BinaryOperator,
+    int: impl Fn(i64, i64) -> Option<i64>,
+    uint: impl Fn(u64, u64) -> Option<u64>,
+    float: impl Fn(f64, f64) -> f64,
+) -> Option<ConstantScalarValue> {
+    match (constant(&op.lhs)?, constant(&op.rhs)?) {
+        (ConstantScalarValue::Float(lhs, kind), ConstantScalarValue::Float(rhs, kind_rhs))
+            if kind == kind_rhs =>
+        {
+            let val = round_float(float(lhs, rhs), kind);
+            val.is_finite()
+                .then_some(ConstantScalarValue::Float(val, kind))
+        }
+        _ => integer(op, int, uint),
+    }
+}
+
+fn integer(
+    op: &BinaryOperator,
+    int: impl Fn(i64, i64) -> Option<i64>,
+    uint: impl Fn(u64, u64) -> Option<u64>,
+) -> Option<ConstantScalarValue> {
+    match (constant(&op.lhs)?, constant(&op.rhs)?) {
+        (ConstantScalarValue::Int(lhs, kind), ConstantScalarValue::Int(rhs, kind_rhs))
+            if kind == kind_rhs =>
+        {
+            Some(ConstantScalarValue::Int(
+                wrap_int(int(lhs, rhs)?, kind),
+                kind,
+            ))
+        }
+        (ConstantScalarValue::UInt(lhs, kind), ConstantScalarValue::UInt(rhs, kind_rhs))
+            if kind == kind_rhs =>
+        {
+            Some(ConstantScalarValue::UInt(
+                wrap_uint(uint(lhs, rhs)?, kind),
+                kind,
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn comparison(op: &BinaryOperator, cmp: impl Fn(Ordering) -> bool) -> Option<ConstantScalarValue> {
+    let ordering = match (constant(&op.lhs)?, constant(&op.rhs)?) {
+        (ConstantScalarValue::Int(lhs, kind), ConstantScalarValue::Int(rhs, kind_rhs))
+            if kind == kind_rhs =>
+        {
+            lhs.cmp(&rhs)
+        }
+        (ConstantScalarValue::UInt(lhs, kind), ConstantScalarValue::UInt(rhs, kind_rhs))
+            if kind == kind_rhs =>
+        {
+            lhs.cmp(&rhs)
+        }
+        (ConstantScalarValue::Float(lhs, kind), ConstantScalarValue::Float(rhs, kind_rhs))
+            if kind == kind_rhs =>
+        {
+            lhs.partial_cmp(&rhs)?
+        }
+        (ConstantScalarValue::Bool(lhs), ConstantScalarValue::Bool(rhs)) => lhs.cmp(&rhs),
+        _ => return None,
+    };
+
+    Some(ConstantScalarValue::Bool(cmp(ordering)))
+}
+
+fn logical(op: &BinaryOperator, func: impl Fn(bool, bool) -> bool) -> Option<ConstantScalarValue> {
+    match (constant(&op.lhs)?, constant(&op.rhs)?) {
+        (ConstantScalarValue::Bool(lhs), ConstantScalarValue::Bool(rhs)) => {
+            Some(ConstantScalarValue::Bool(func(lhs, rhs)))
+        }
+        _ => None,
+    }
+}
+
+/// Truncate the value to the width of the integer kind.
+fn wrap_int(val: i64, kind: IntKind) -> i64 {
+    match kind {
+        IntKind::I8 => val as i8 as i64,
+        IntKind::I16 => val as i16 as i64,
+        IntKind::I32 => val as i32 as i64,
+        IntKind::I64 => val,
+    }
+}
+
+/// Truncate the value to the width of the unsigned integer kind.
+fn wrap_uint(val: u64, kind: UIntKind) -> u64 {
+    match kind {
+        UIntKind::U8 => val as u8 as u64,
+        UIntKind::U16 => val as u16 as u64,
+        UIntKind::U32 => val as u32 as u64,
+        UIntKind::U64 => val,
+    }
+}
+
+/// Round the value to the precision of the float kind.
+fn round_float(val: f64, kind: FloatKind) -> f64 {
+    match kind {
+        FloatKind::F16 => half::f16::from_f64(val).to_f64(),
+        FloatKind::BF16 => half::bf16::from_f64(val).to_f64(),
+        FloatKind::F32 => val as f32 as f64,
+        FloatKind::F64 => val,
+    }
+}
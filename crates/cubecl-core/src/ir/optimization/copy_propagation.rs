@@ -0,0 +1,118 @@
+use super::{
+    constant_folding::fold,
+    usage::{for_each_written_variable, operator_variables},
+};
+use crate::ir::{processing::ScopeProcessing, Metadata, Operation, Operator, Variable};
+
+/// A local variable holding the same value as its source.
+#[derive(Debug, Clone, Copy)]
+struct CopyAssignment {
+    target: Variable,
+    source: Variable,
+}
+
+/// Replace the reads of variables that were assigned another local or a constant by reads of the
+/// source, as long as neither of them is written in between.
+///
+/// Only the operators and the metadata reads of the current scope are rewritten, the assignments
+/// themselves are left to the dead code elimination.
+pub(super) fn apply(processing: &mut ScopeProcessing) {
+    let mut copies = Vec::<CopyAssignment>::new();
+
+    for operation in processing.operations.iter_mut() {
+        propagate(operation, &copies);
+
+        for_each_written_variable(operation, &mut |variable| {
+            copies.retain(|copy| copy.target != *variable && copy.source != *variable);
+        });
+
+        if let Some(copy) = CopyAssignment::from_operation(operation) {
+            copies.push(copy);
+        }
+    }
+}
+
+impl CopyAssignment {
+    fn from_operation(operation: &Operation) -> Option<Self> {
+        let op = match operation {
+            Operation::Operator(Operator::Assign(op)) => op,
+            _ => return None,
+        };
+        let item = match op.out {
+            Variable::Local { item, .. } if !item.elem().is_atomic() => item,
+            _ => return None,
+        };
+
+        let is_copy = match op.input {
+            Variable::Local { item: source, .. } => source == item && op.input != op.out,
+            Variable::ConstantScalar(value) => {
+                value.elem() == item.elem() && item.vectorization == 1
+            }
+            _ => false,
+        };
+
+        is_copy.then_some(Self {
+            target: op.out,
+            source: op.input,
+        })
+    }
+}
+
+fn propagate(operation: &mut Operation, copies: &[CopyAssignment]) {
+    let source = |variable: &Variable| {
+        copies
+            .iter()
+            .find(|copy| copy.target == *variable)
+            .map(|copy| copy.source)
+    };
+
+    match operation {
+        Operation::Operator(operator) => {
+            let mut propagated = operator.clone();
+            let mut variables = operator_variables(&mut propagated);
+            let mut changed = false;
+
+            for input in variables.inputs.iter_mut() {
+                if let Some(source) = source(input) {
+                    **input = source;
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                return;
+            }
+
+            let is_constant = variables
+                .inputs
+                .iter()
+                .all(|input| matches!(input, Variable::ConstantScalar(_)));
+
+            // Operators on constants are evaluated right away, so the backends never have to
+            // compile them. The ones that can't be folded keep reading the copy.
+            *operator = match (is_constant, &propagated) {
+                (_, Operator::Assign(_)) | (false, _) => propagated,
+                (true, _) => match fold(&propagated) {
+                    Some(folded) => folded,
+                    None => return,
+                },
+            };
+        }
+        Operation::Metadata(op) => match op {
+            Metadata::Stride { dim, var, .. } | Metadata::Shape { dim, var, .. } => {
+                if let Some(source) = source(dim) {
+                    *dim = source;
+                }
+                if let Some(source) = source(var) {
+                    *var = source;
+                }
+            }
+            Metadata::Length { var, .. } => {
+                if let Some(source) = source(var) {
+                    *var = source;
+                }
+            }
+        },
+        _ => {}
+    }
+}
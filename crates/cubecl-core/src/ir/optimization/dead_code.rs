@@ -0,0 +1,67 @@
+use super::usage::{for_each_variable, pure_output};
+use crate::ir::{processing::ScopeProcessing, Operation, Variable};
+
+/// Remove the pure operations writing local variables that are never read, then the declarations
+/// of the local variables that are no longer used.
+///
+/// The [preserved](crate::ir::Scope::preserve) variables are always kept.
+pub(super) fn apply(processing: &mut ScopeProcessing) {
+    let candidates = processing
+        .variables
+        .iter()
+        .filter(|var| matches!(var, Variable::Local { .. }))
+        .filter(|var| !processing.preserved.contains(var))
+        .copied()
+        .collect::<Vec<_>>();
+
+    if candidates.is_empty() {
+        return;
+    }
+
+    // Removing an operation can make the operations computing its inputs dead as well.
+    loop {
+        let reads = reads(&processing.operations);
+        let num_operations = processing.operations.len();
+
+        processing
+            .operations
+            .retain(|operation| match pure_output(operation) {
+                Some(out) => !candidates.contains(&out) || reads.contains(&out),
+                None => true,
+            });
+
+        if processing.operations.len() == num_operations {
+            break;
+        }
+    }
+
+    let mut used = Vec::new();
+    for operation in processing.operations.iter() {
+        for_each_variable(operation, &mut |var| used.push(*var));
+    }
+
+    processing
+        .variables
+        .retain(|var| !candidates.contains(var) || used.contains(var));
+}
+
+/// All the variables used by the operations, except the outputs of pure operations.
+fn reads(operations: &[Operation]) -> Vec<Variable> {
+    let mut reads = Vec::new();
+
+    for operation in operations {
+        let mut variables = Vec::new();
+        for_each_variable(operation, &mut |var| variables.push(*var));
+
+        // The output is always visited last.
+        if let Some(out) = pure_output(operation) {
+            if let Some(position) = variables.iter().rposition(|var| *var == out) {
+                variables.remove(position);
+            }
+        }
+
+        reads.extend(variables);
+    }
+
+    reads
+}
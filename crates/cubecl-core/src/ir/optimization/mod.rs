@@ -0,0 +1,8 @@
+mod base;
+mod common_subexpression;
+mod constant_folding;
+mod copy_propagation;
+mod dead_code;
+mod usage;
+
+pub use base::*;
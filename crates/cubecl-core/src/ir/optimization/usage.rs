@@ -0,0 +1,350 @@
+use crate::ir::{
+    Branch, CoopMma, Metadata, Operation, Operator, Procedure, Scope, Subcube, Variable,
+};
+
+/// The inputs and the output of an [operator](Operator).
+pub(super) struct OperatorVariables<'a> {
+    pub inputs: Vec<&'a mut Variable>,
+    pub out: &'a mut Variable,
+}
+
+/// Get mutable references to the inputs and the output of an [operator](Operator).
+///
+/// For the operators writing into an array, the output is the array itself.
+pub(super) fn operator_variables(operator: &mut Operator) -> OperatorVariables<'_> {
+    match operator {
+        Operator::Add(op)
+        | Operator::Sub(op)
+        | Operator::Mul(op)
+        | Operator::Div(op)
+        | Operator::Powf(op)
+        | Operator::Atan2(op)
+        | Operator::CopySign(op)
+        | Operator::Hypot(op)
+        | Operator::Equal(op)
+        | Operator::NotEqual(op)
+        | Operator::Lower(op)
+        | Operator::Greater(op)
+        | Operator::LowerEqual(op)
+        | Operator::GreaterEqual(op)
+        | Operator::Modulo(op)
+        | Operator::Index(op)
+        | Operator::UncheckedIndex(op)
+        | Operator::IndexAssign(op)
+        | Operator::UncheckedIndexAssign(op)
+        | Operator::And(op)
+        | Operator::Or(op)
+        | Operator::Max(op)
+        | Operator::Min(op)
+        | Operator::BitwiseAnd(op)
+        | Operator::BitwiseOr(op)
+        | Operator::BitwiseXor(op)
+        | Operator::ShiftLeft(op)
+        | Operator::ShiftRight(op)
+        | Operator::Remainder(op)
+        | Operator::AtomicSwap(op)
+        | Operator::AtomicAdd(op)
+        | Operator::AtomicSub(op)
+        | Operator::AtomicMax(op)
+        | Operator::AtomicMin(op)
+        | Operator::AtomicAnd(op)
+        | Operator::AtomicOr(op)
+        | Operator::AtomicXor(op) => OperatorVariables {
+            inputs: vec![&mut op.lhs, &mut op.rhs],
+            out: &mut op.out,
+        },
+        Operator::Abs(op)
+        | Operator::Exp(op)
+        | Operator::Log(op)
+        | Operator::Log1p(op)
+        | Operator::Cos(op)
+        | Operator::Sin(op)
+        | Operator::Tanh(op)
+        | Operator::Sqrt(op)
+        | Operator::Floor(op)
+        | Operator::Ceil(op)
+        | Operator::Erf(op)
+        | Operator::Recip(op)
+        | Operator::Tan(op)
+        | Operator::Asin(op)
+        | Operator::Acos(op)
+        | Operator::Atan(op)
+        | Operator::Sinh(op)
+        | Operator::Cosh(op)
+        | Operator::Exp2(op)
+        | Operator::Log2(op)
+        | Operator::Round(op)
+        | Operator::Trunc(op)
+        | Operator::Fract(op)
+        | Operator::Sign(op)
+        | Operator::Rsqrt(op)
+        | Operator::IsNan(op)
+        | Operator::IsInf(op)
+        | Operator::Assign(op)
+        | Operator::Not(op)
+        | Operator::BitwiseNot(op)
+        | Operator::Neg(op)
+        | Operator::CountOnes(op)
+        | Operator::ReverseBits(op)
+        | Operator::LeadingZeros(op)
+        | Operator::TrailingZeros(op)
+        | Operator::Bitcast(op)
+        | Operator::AtomicLoad(op)
+        | Operator::AtomicStore(op) => OperatorVariables {
+            inputs: vec![&mut op.input],
+            out: &mut op.out,
+        },
+        Operator::Fma(op) => OperatorVariables {
+            inputs: vec![&mut op.a, &mut op.b, &mut op.c],
+            out: &mut op.out,
+        },
+        Operator::Clamp(op) => OperatorVariables {
+            inputs: vec![&mut op.input, &mut op.min_value, &mut op.max_value],
+            out: &mut op.out,
+        },
+        Operator::Slice(op) => OperatorVariables {
+            inputs: vec![&mut op.input, &mut op.start, &mut op.end],
+            out: &mut op.out,
+        },
+        Operator::AtomicCompareAndSwap(op) => OperatorVariables {
+            inputs: vec![&mut op.input, &mut op.cmp, &mut op.val],
+            out: &mut op.out,
+        },
+    }
+}
+
+/// Whether the only effect of the operator is to write its output variable.
+pub(super) fn is_pure(operator: &Operator) -> bool {
+    !matches!(
+        operator,
+        Operator::IndexAssign(_)
+            | Operator::UncheckedIndexAssign(_)
+            | Operator::AtomicLoad(_)
+            | Operator::AtomicStore(_)
+            | Operator::AtomicSwap(_)
+            | Operator::AtomicAdd(_)
+            | Operator::AtomicSub(_)
+            | Operator::AtomicMax(_)
+            | Operator::AtomicMin(_)
+            | Operator::AtomicAnd(_)
+            | Operator::AtomicOr(_)
+            | Operator::AtomicXor(_)
+            | Operator::AtomicCompareAndSwap(_)
+    )
+}
+
+/// The variable written by a pure operation, if any.
+///
+/// Operations with other effects, such as writing into an array or executing nested scopes,
+/// don't have a pure output.
+pub(super) fn pure_output(operation: &Operation) -> Option<Variable> {
+    match operation {
+        Operation::Operator(op) if is_pure(op) => Some(*operator_variables(&mut op.clone()).out),
+        Operation::Metadata(op) => Some(match op {
+            Metadata::Stride { out, .. } => *out,
+            Metadata::Shape { out, .. } => *out,
+            Metadata::Length { out, .. } => *out,
+        }),
+        _ => None,
+    }
+}
+
+/// Visit all variables used by an operation, including the ones of its nested scopes.
+pub(super) fn for_each_variable<F: FnMut(&Variable)>(operation: &Operation, visit: &mut F) {
+    match operation {
+        Operation::Operator(op) => {
+            let mut op = op.clone();
+            let variables = operator_variables(&mut op);
+
+            for input in variables.inputs {
+                visit(input);
+            }
+            visit(variables.out);
+        }
+        Operation::Procedure(proc) => for_each_procedure_variable(proc, visit),
+        Operation::Metadata(op) => match op {
+            Metadata::Stride { dim, var, out } | Metadata::Shape { dim, var, out } => {
+                visit(dim);
+                visit(var);
+                visit(out);
+            }
+            Metadata::Length { var, out } => {
+                visit(var);
+                visit(out);
+            }
+        },
+        Operation::Branch(branch) => match branch {
+            Branch::If(op) => {
+                visit(&op.cond);
+                for_each_scope_variable(&op.scope, visit);
+            }
+            Branch::IfElse(op) => {
+                visit(&op.cond);
+                for_each_scope_variable(&op.scope_if, visit);
+                for_each_scope_variable(&op.scope_else, visit);
+            }
+            Branch::Switch(op) => {
+                visit(&op.value);
+                for (values, scope) in op.cases.iter() {
+                    for value in values {
+                        visit(value);
+                    }
+                    for_each_scope_variable(scope, visit);
+                }
+                for_each_scope_variable(&op.scope_default, visit);
+            }
+            Branch::RangeLoop(op) => {
+                visit(&op.i);
+                visit(&op.start);
+                visit(&op.end);
+                if let Some(step) = &op.step {
+                    visit(step);
+                }
+                for_each_scope_variable(&op.scope, visit);
+            }
+            Branch::Loop(op) => for_each_scope_variable(&op.scope, visit),
+            Branch::Return | Branch::Break => {}
+        },
+        Operation::Synchronization(_) => {}
+        Operation::Subcube(op) => match op {
+            Subcube::Elect(op) => visit(&op.out),
+            Subcube::Broadcast(op)
+            | Subcube::Shuffle(op)
+            | Subcube::ShuffleXor(op)
+            | Subcube::ShuffleUp(op)
+            | Subcube::ShuffleDown(op) => {
+                visit(&op.lhs);
+                visit(&op.rhs);
+                visit(&op.out);
+            }
+            Subcube::All(op)
+            | Subcube::Any(op)
+            | Subcube::Sum(op)
+            | Subcube::Prod(op)
+            | Subcube::And(op)
+            | Subcube::Or(op)
+            | Subcube::Xor(op)
+            | Subcube::Min(op)
+            | Subcube::Max(op)
+            | Subcube::InclusiveSum(op)
+            | Subcube::ExclusiveSum(op)
+            | Subcube::InclusiveProd(op)
+            | Subcube::ExclusiveProd(op)
+            | Subcube::Ballot(op) => {
+                visit(&op.input);
+                visit(&op.out);
+            }
+        },
+        Operation::CoopMma(op) => match op {
+            CoopMma::Fill { mat, value } => {
+                visit(mat);
+                visit(value);
+            }
+            CoopMma::Load { mat, value, stride } => {
+                visit(mat);
+                visit(value);
+                visit(stride);
+            }
+            CoopMma::Execute {
+                mat_a,
+                mat_b,
+                mat_c,
+                mat_d,
+            } => {
+                visit(mat_a);
+                visit(mat_b);
+                visit(mat_c);
+                visit(mat_d);
+            }
+            CoopMma::Store {
+                output,
+                mat,
+                stride,
+                ..
+            } => {
+                visit(output);
+                visit(mat);
+                visit(stride);
+            }
+        },
+        Operation::Call(call) => {
+            call.inputs.iter().for_each(&mut *visit);
+            if let Some(output) = &call.output {
+                visit(output);
+            }
+        }
+    }
+}
+
+fn for_each_scope_variable<F: FnMut(&Variable)>(scope: &Scope, visit: &mut F) {
+    for operation in scope.operations.iter() {
+        for_each_variable(operation, visit);
+    }
+
+    scope.for_each_pending_variable(&mut *visit);
+}
+
+fn for_each_procedure_variable<F: FnMut(&Variable)>(proc: &Procedure, visit: &mut F) {
+    match proc {
+        Procedure::ReadGlobalWithLayout(proc) => {
+            proc.globals.iter().for_each(&mut *visit);
+            proc.outs.iter().for_each(&mut *visit);
+            visit(&proc.layout);
+            visit(&proc.position);
+        }
+        Procedure::IndexOffsetGlobalWithLayout(proc) => {
+            proc.tensors.iter().for_each(&mut *visit);
+            proc.indexes.iter().for_each(&mut *visit);
+            visit(&proc.layout);
+            visit(&proc.position);
+            visit(&proc.dim_start);
+            visit(&proc.dim_end);
+        }
+        Procedure::ReadGlobal(proc) => {
+            visit(&proc.global);
+            visit(&proc.out);
+            visit(&proc.position);
+        }
+        Procedure::WriteGlobal(proc) => {
+            visit(&proc.input);
+            visit(&proc.global);
+            visit(&proc.position);
+        }
+        Procedure::CheckedIndex(proc) => {
+            visit(&proc.lhs);
+            visit(&proc.rhs);
+            visit(&proc.out);
+        }
+        Procedure::CheckedIndexAssign(proc) => {
+            visit(&proc.lhs);
+            visit(&proc.rhs);
+            visit(&proc.out);
+        }
+        Procedure::ConditionalAssign(proc) => {
+            visit(&proc.cond);
+            visit(&proc.lhs);
+            visit(&proc.rhs);
+            visit(&proc.out);
+        }
+        Procedure::EarlyReturn(proc) => {
+            visit(&proc.global);
+            visit(&proc.position);
+        }
+    }
+}
+
+/// Visit the variables that may be written by an operation.
+///
+/// The output of operators and metadata reads is known, but other operations, such as branches
+/// or procedures, are conservatively considered to write every variable they use.
+pub(super) fn for_each_written_variable<F: FnMut(&Variable)>(operation: &Operation, visit: &mut F) {
+    match operation {
+        Operation::Operator(op) => visit(operator_variables(&mut op.clone()).out),
+        Operation::Metadata(_) => {
+            if let Some(out) = pure_output(operation) {
+                visit(&out);
+            }
+        }
+        _ => for_each_variable(operation, visit),
+    }
+}
@@ -1,5 +1,5 @@
 use super::super::{cpa, Elem, Item, Operator, Scope, Variable};
-use crate::ir::{BinaryOperator, Vectorization};
+use crate::ir::{BinaryOperator, UIntKind, Vectorization};
 use serde::{Deserialize, Serialize};
 
 /// Read a global array.
@@ -78,7 +78,7 @@ impl ReadGlobalWithLayout {
         let tensors = self.globals;
         let indexes = tensors
             .iter()
-            .map(|_| scope.create_local(Elem::UInt))
+            .map(|_| scope.create_local(Elem::UInt(UIntKind::U32)))
             .collect::<Vec<_>>();
 
         IndexOffsetGlobalWithLayout {
@@ -140,7 +140,7 @@ impl IndexOffsetGlobalWithLayout {
     #[allow(missing_docs)]
     pub fn expand(self, scope: &mut Scope) {
         let layout = self.layout;
-        let index_item_ty = Item::new(Elem::UInt);
+        let index_item_ty = Item::new(Elem::UInt(UIntKind::U32));
         let offset_ref = self.position;
         let zero: Variable = 0u32.into();
         let vectorization_factor: u8 = self.tensors[0].item().vectorization;
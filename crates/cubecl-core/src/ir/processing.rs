@@ -1,4 +1,7 @@
-use super::{Branch, CoopMma, Elem, Metadata, Operation, Operator, Procedure, Variable};
+use super::{
+    Branch, CoopMma, Elem, Metadata, Operation, Operator, Optimizations, Procedure, UIntKind,
+    Variable,
+};
 use crate::ir::ReadGlobalWithLayout;
 
 /// Information necessary when compiling a scope.
@@ -7,6 +10,8 @@ pub struct ScopeProcessing {
     pub variables: Vec<Variable>,
     /// The operations.
     pub operations: Vec<Operation>,
+    /// The variables that must be kept even if they aren't read by the operations.
+    pub preserved: Vec<Variable>,
 }
 
 impl ScopeProcessing {
@@ -18,8 +23,17 @@ impl ScopeProcessing {
     /// If you built this type from the [scope process function](super::Scope::process), you don't have to
     /// call it again.
     pub fn optimize(self) -> Self {
-        self.sanitize_constant_scalars()
-            .merge_read_global_with_layout()
+        self.optimize_with(&Optimizations::default())
+    }
+
+    /// Same as [optimize](Self::optimize), but running the given [optimization passes](Optimizations)
+    /// after the mandatory ones.
+    pub fn optimize_with(self, optimizations: &Optimizations) -> Self {
+        let processing = self
+            .sanitize_constant_scalars()
+            .merge_read_global_with_layout();
+
+        optimizations.apply(processing)
     }
 
     /// Make sure constant scalars are of the correct type so compilers don't have to do conversion
@@ -88,6 +102,60 @@ impl ScopeProcessing {
                 Operator::Recip(op) => {
                     sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
                 }
+                Operator::Tan(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::Asin(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::Acos(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::Atan(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::Atan2(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.lhs, &op.out);
+                    sanitize_constant_scalar_ref_var(&mut op.rhs, &op.out);
+                }
+                Operator::Sinh(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::Cosh(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::Exp2(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::Log2(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::Round(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::Trunc(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::Fract(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::Sign(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::CopySign(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.lhs, &op.out);
+                    sanitize_constant_scalar_ref_var(&mut op.rhs, &op.out);
+                }
+                Operator::Hypot(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.lhs, &op.out);
+                    sanitize_constant_scalar_ref_var(&mut op.rhs, &op.out);
+                }
+                Operator::Rsqrt(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::IsNan(_) | Operator::IsInf(_) => {
+                    // Nothing to do, the output is a boolean.
+                }
                 Operator::Equal(op) => {
                     sanitize_constant_scalar_ref_var(&mut op.lhs, &op.rhs);
                     sanitize_constant_scalar_ref_var(&mut op.rhs, &op.lhs);
@@ -126,23 +194,23 @@ impl ScopeProcessing {
                 }
                 Operator::Slice(op) => {
                     sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
-                    sanitize_constant_scalar_ref_elem(&mut op.start, Elem::UInt);
-                    sanitize_constant_scalar_ref_elem(&mut op.end, Elem::UInt);
+                    sanitize_constant_scalar_ref_elem(&mut op.start, Elem::UInt(UIntKind::U32));
+                    sanitize_constant_scalar_ref_elem(&mut op.end, Elem::UInt(UIntKind::U32));
                 }
                 Operator::Index(op) => {
                     sanitize_constant_scalar_ref_var(&mut op.lhs, &op.out);
-                    sanitize_constant_scalar_ref_elem(&mut op.rhs, Elem::UInt);
+                    sanitize_constant_scalar_ref_elem(&mut op.rhs, Elem::UInt(UIntKind::U32));
                 }
                 Operator::UncheckedIndex(op) => {
                     sanitize_constant_scalar_ref_var(&mut op.lhs, &op.out);
-                    sanitize_constant_scalar_ref_elem(&mut op.rhs, Elem::UInt);
+                    sanitize_constant_scalar_ref_elem(&mut op.rhs, Elem::UInt(UIntKind::U32));
                 }
                 Operator::IndexAssign(op) => {
-                    sanitize_constant_scalar_ref_elem(&mut op.lhs, Elem::UInt);
+                    sanitize_constant_scalar_ref_elem(&mut op.lhs, Elem::UInt(UIntKind::U32));
                     sanitize_constant_scalar_ref_var(&mut op.rhs, &op.out);
                 }
                 Operator::UncheckedIndexAssign(op) => {
-                    sanitize_constant_scalar_ref_elem(&mut op.lhs, Elem::UInt);
+                    sanitize_constant_scalar_ref_elem(&mut op.lhs, Elem::UInt(UIntKind::U32));
                     sanitize_constant_scalar_ref_var(&mut op.rhs, &op.out);
                 }
                 Operator::And(op) => {
@@ -168,10 +236,32 @@ impl ScopeProcessing {
                     sanitize_constant_scalar_ref_var(&mut op.lhs, &op.out);
                     sanitize_constant_scalar_ref_var(&mut op.rhs, &op.out);
                 }
+                Operator::BitwiseOr(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.lhs, &op.out);
+                    sanitize_constant_scalar_ref_var(&mut op.rhs, &op.out);
+                }
                 Operator::BitwiseXor(op) => {
                     sanitize_constant_scalar_ref_var(&mut op.lhs, &op.out);
                     sanitize_constant_scalar_ref_var(&mut op.rhs, &op.out);
                 }
+                Operator::BitwiseNot(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::Neg(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::CountOnes(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::ReverseBits(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::LeadingZeros(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::TrailingZeros(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
                 Operator::ShiftLeft(op) => {
                     sanitize_constant_scalar_ref_var(&mut op.lhs, &op.out);
                     sanitize_constant_scalar_ref_var(&mut op.rhs, &op.out);
@@ -218,10 +308,10 @@ impl ScopeProcessing {
             },
             Operation::Metadata(op) => match op {
                 Metadata::Stride { dim, .. } => {
-                    sanitize_constant_scalar_ref_elem(dim, Elem::UInt);
+                    sanitize_constant_scalar_ref_elem(dim, Elem::UInt(UIntKind::U32));
                 }
                 Metadata::Shape { dim, .. } => {
-                    sanitize_constant_scalar_ref_elem(dim, Elem::UInt);
+                    sanitize_constant_scalar_ref_elem(dim, Elem::UInt(UIntKind::U32));
                 }
                 Metadata::Length { .. } => {
                     // Nothing to do
@@ -235,9 +325,14 @@ impl ScopeProcessing {
                 Branch::IfElse(op) => {
                     sanitize_constant_scalar_ref_elem(&mut op.cond, Elem::Bool);
                 }
+                Branch::Switch(op) => {
+                    for value in op.cases.iter_mut().flat_map(|(values, _)| values) {
+                        sanitize_constant_scalar_ref_var(value, &op.value);
+                    }
+                }
                 Branch::RangeLoop(op) => {
-                    sanitize_constant_scalar_ref_elem(&mut op.start, Elem::UInt);
-                    sanitize_constant_scalar_ref_elem(&mut op.end, Elem::UInt);
+                    sanitize_constant_scalar_ref_elem(&mut op.start, Elem::UInt(UIntKind::U32));
+                    sanitize_constant_scalar_ref_elem(&mut op.end, Elem::UInt(UIntKind::U32));
                 }
                 _ => {
                     // Nothing to do.
@@ -255,18 +350,21 @@ impl ScopeProcessing {
                 }
                 CoopMma::Load { mat, value, stride } => {
                     sanitize_constant_scalar_ref_var(value, mat);
-                    sanitize_constant_scalar_ref_elem(stride, Elem::UInt);
+                    sanitize_constant_scalar_ref_elem(stride, Elem::UInt(UIntKind::U32));
                 }
                 CoopMma::Execute { .. } => {
                     // Nothing to do.
                 }
                 CoopMma::Store { stride, .. } => {
-                    sanitize_constant_scalar_ref_elem(stride, Elem::UInt);
+                    sanitize_constant_scalar_ref_elem(stride, Elem::UInt(UIntKind::U32));
                 }
             },
             Operation::Procedure(_) => {
                 // Nothing to do since they are re-processed.
             }
+            Operation::Call(_) => {
+                // Nothing to do, the function inputs keep the type of the arguments.
+            }
         });
         self
     }
@@ -364,7 +462,7 @@ fn sanitize_constant_scalar_ref_elem(var: &mut Variable, elem: Elem) {
             *var = match scalar {
                 super::ConstantScalarValue::Int(val, _) => elem.constant_from_i64(*val),
                 super::ConstantScalarValue::Float(val, _) => elem.constant_from_f64(*val),
-                super::ConstantScalarValue::UInt(val) => elem.constant_from_u64(*val),
+                super::ConstantScalarValue::UInt(val, _) => elem.constant_from_u64(*val),
                 super::ConstantScalarValue::Bool(val) => elem.constant_from_bool(*val),
             };
         }
@@ -2,8 +2,8 @@ use crate::ir::ConstantScalarValue;
 
 use super::{
     cpa, processing::ScopeProcessing, Elem, IndexOffsetGlobalWithLayout, Item, Matrix, Operation,
-    Operator, Procedure, ReadGlobal, ReadGlobalWithLayout, UnaryOperator, Variable, Vectorization,
-    WriteGlobal,
+    Operator, Optimizations, Procedure, ReadGlobal, ReadGlobalWithLayout, UIntKind, UnaryOperator,
+    Variable, Vectorization, WriteGlobal,
 };
 use serde::{Deserialize, Serialize};
 
@@ -19,17 +19,18 @@ use serde::{Deserialize, Serialize};
 pub struct Scope {
     pub depth: u8,
     pub operations: Vec<Operation>,
-    locals: Vec<Variable>,
-    matrices: Vec<Variable>,
-    slices: Vec<Variable>,
-    shared_memories: Vec<Variable>,
-    local_arrays: Vec<Variable>,
-    reads_global: Vec<(Variable, ReadingStrategy, Variable, Variable)>,
-    index_offset_with_output_layout_position: Vec<usize>,
-    writes_global: Vec<(Variable, Variable, Variable)>,
-    reads_scalar: Vec<(Variable, Variable)>,
+    pub(crate) locals: Vec<Variable>,
+    pub(crate) matrices: Vec<Variable>,
+    pub(crate) slices: Vec<Variable>,
+    pub(crate) shared_memories: Vec<Variable>,
+    pub(crate) local_arrays: Vec<Variable>,
+    pub(crate) reads_global: Vec<(Variable, ReadingStrategy, Variable, Variable)>,
+    pub(crate) index_offset_with_output_layout_position: Vec<usize>,
+    pub(crate) writes_global: Vec<(Variable, Variable, Variable)>,
+    pub(crate) reads_scalar: Vec<(Variable, Variable)>,
     pub layout_ref: Option<Variable>,
-    undeclared: u16,
+    pub(crate) undeclared: u16,
+    pub(crate) preserved: Vec<Variable>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Hash, Eq)]
@@ -61,6 +62,7 @@ impl Scope {
             reads_scalar: Vec::new(),
             layout_ref: None,
             undeclared: 0,
+            preserved: Vec::new(),
         }
     }
 
@@ -80,11 +82,13 @@ impl Scope {
     {
         let item: Item = item.into();
         let value = match item.elem() {
-            Elem::Float(kind) => ConstantScalarValue::Float(value.to_f64().unwrap(), kind),
+            Elem::Float(kind) | Elem::AtomicFloat(kind) => {
+                ConstantScalarValue::Float(value.to_f64().unwrap(), kind)
+            }
             Elem::Int(kind) => ConstantScalarValue::Int(value.to_i64().unwrap(), kind),
             Elem::AtomicInt(kind) => ConstantScalarValue::Int(value.to_i64().unwrap(), kind),
-            Elem::UInt => ConstantScalarValue::UInt(value.to_u64().unwrap()),
-            Elem::AtomicUInt => ConstantScalarValue::UInt(value.to_u64().unwrap()),
+            Elem::UInt(kind) => ConstantScalarValue::UInt(value.to_u64().unwrap(), kind),
+            Elem::AtomicUInt => ConstantScalarValue::UInt(value.to_u64().unwrap(), UIntKind::U32),
             Elem::Bool => ConstantScalarValue::Bool(value.to_u32().unwrap() == 1),
         };
         let local = self.create_local(item);
@@ -267,6 +271,32 @@ impl Scope {
         self.operations.push(operation.into())
     }
 
+    /// Keep the variable declared and its value computed, even if it isn't read by the scope.
+    ///
+    /// This is the case for the inputs and the output of a [function](super::Function), which
+    /// are accessed by the compilers.
+    pub fn preserve(&mut self, variable: Variable) {
+        self.preserved.push(variable);
+    }
+
+    /// Visit the variables of the operations that are only created when the scope is processed.
+    pub(crate) fn for_each_pending_variable<F: FnMut(&Variable)>(&self, mut visit: F) {
+        for (input, _, local, position) in self.reads_global.iter() {
+            visit(input);
+            visit(local);
+            visit(position);
+        }
+        for (input, global, position) in self.writes_global.iter() {
+            visit(input);
+            visit(global);
+            visit(position);
+        }
+        for (local, scalar) in self.reads_scalar.iter() {
+            visit(local);
+            visit(scalar);
+        }
+    }
+
     /// Create an empty child scope.
     pub fn child(&mut self) -> Self {
         Self {
@@ -283,6 +313,7 @@ impl Scope {
             reads_scalar: Vec::new(),
             layout_ref: self.layout_ref,
             undeclared: 0,
+            preserved: Vec::new(),
         }
     }
 
@@ -293,6 +324,11 @@ impl Scope {
     /// New operations and variables can be created within the same scope without having name
     /// conflicts.
     pub fn process(&mut self) -> ScopeProcessing {
+        self.process_with(&Optimizations::default())
+    }
+
+    /// Same as [process](Self::process), but with the given [optimizations](Optimizations).
+    pub fn process_with(&mut self, optimizations: &Optimizations) -> ScopeProcessing {
         self.undeclared += self.locals.len() as u16;
 
         let mut variables = Vec::new();
@@ -379,8 +415,9 @@ impl Scope {
         ScopeProcessing {
             variables,
             operations,
+            preserved: self.preserved.clone(),
         }
-        .optimize()
+        .optimize_with(optimizations)
     }
 
     fn new_local_index(&self) -> u16 {
@@ -408,7 +445,7 @@ impl Scope {
     ) -> Variable {
         let item_global = match item.elem() {
             Elem::Bool => Item {
-                elem: Elem::UInt,
+                elem: Elem::UInt(UIntKind::U32),
                 vectorization: item.vectorization,
             },
             _ => item,
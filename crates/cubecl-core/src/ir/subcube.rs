@@ -18,4 +18,21 @@ pub enum Subcube {
     Xor(UnaryOperator),
     Min(UnaryOperator),
     Max(UnaryOperator),
+    /// Read the value of `lhs` from the unit whose index is `rhs`.
+    Shuffle(BinaryOperator),
+    /// Read the value of `lhs` from the unit whose index is the index of the current unit xor
+    /// `rhs`.
+    ShuffleXor(BinaryOperator),
+    /// Read the value of `lhs` from the unit whose index is the index of the current unit minus
+    /// `rhs`.
+    ShuffleUp(BinaryOperator),
+    /// Read the value of `lhs` from the unit whose index is the index of the current unit plus
+    /// `rhs`.
+    ShuffleDown(BinaryOperator),
+    InclusiveSum(UnaryOperator),
+    ExclusiveSum(UnaryOperator),
+    InclusiveProd(UnaryOperator),
+    ExclusiveProd(UnaryOperator),
+    /// The bitmask of the units for which the boolean input is true, as a vector of four `u32`.
+    Ballot(UnaryOperator),
 }
@@ -0,0 +1,5 @@
+mod names;
+mod parser;
+mod printer;
+
+pub use parser::*;
@@ -0,0 +1,328 @@
+use crate::ir::{
+    BinaryOperator, ClampOperator, CompareAndSwapOperator, Elem, FloatKind, FmaOperator,
+    InitOperator, IntKind, Location, MatrixIdent, MatrixLayout, Operator, ReadingStrategy,
+    SliceOperator, Subcube, UIntKind, UnaryOperator, Variable, Visibility,
+};
+
+macro_rules! operators {
+    (
+        unary: [$($unary:ident => $unary_name:literal,)*],
+        binary: [$($binary:ident => $binary_name:literal,)*],
+        ternary: [$($ternary:ident($ty:ident { $a:ident, $b:ident, $c:ident }) => $ternary_name:literal,)*],
+    ) => {
+        /// The name, inputs and output of an operator.
+        pub(super) fn operator_parts(operator: &Operator) -> (&'static str, Vec<Variable>, Variable) {
+            match operator {
+                $(Operator::$unary(op) => ($unary_name, vec![op.input], op.out),)*
+                $(Operator::$binary(op) => ($binary_name, vec![op.lhs, op.rhs], op.out),)*
+                $(Operator::$ternary(op) => ($ternary_name, vec![op.$a, op.$b, op.$c], op.out),)*
+            }
+        }
+
+        /// The number of inputs of the operator with the given name.
+        pub(super) fn operator_arity(name: &str) -> Option<usize> {
+            match name {
+                $($unary_name => Some(1),)*
+                $($binary_name => Some(2),)*
+                $($ternary_name => Some(3),)*
+                _ => None,
+            }
+        }
+
+        /// Create the operator with the given name, the number of inputs must match its arity.
+        pub(super) fn operator_from_parts(name: &str, inputs: &[Variable], out: Variable) -> Operator {
+            match name {
+                $($unary_name => Operator::$unary(UnaryOperator { input: inputs[0], out }),)*
+                $($binary_name => Operator::$binary(BinaryOperator {
+                    lhs: inputs[0],
+                    rhs: inputs[1],
+                    out,
+                }),)*
+                $($ternary_name => Operator::$ternary($ty {
+                    $a: inputs[0],
+                    $b: inputs[1],
+                    $c: inputs[2],
+                    out,
+                }),)*
+                _ => unreachable!("Unknown operator {name}"),
+            }
+        }
+    };
+}
+
+operators!(
+    unary: [
+        Abs => "abs",
+        Exp => "exp",
+        Log => "log",
+        Log1p => "log1p",
+        Cos => "cos",
+        Sin => "sin",
+        Tanh => "tanh",
+        Sqrt => "sqrt",
+        Floor => "floor",
+        Ceil => "ceil",
+        Erf => "erf",
+        Recip => "recip",
+        Tan => "tan",
+        Asin => "asin",
+        Acos => "acos",
+        Atan => "atan",
+        Sinh => "sinh",
+        Cosh => "cosh",
+        Exp2 => "exp2",
+        Log2 => "log2",
+        Round => "round",
+        Trunc => "trunc",
+        Fract => "fract",
+        Sign => "sign",
+        Rsqrt => "rsqrt",
+        IsNan => "is_nan",
+        IsInf => "is_inf",
+        Assign => "assign",
+        Not => "not",
+        BitwiseNot => "bitwise_not",
+        Neg => "neg",
+        CountOnes => "count_ones",
+        ReverseBits => "reverse_bits",
+        LeadingZeros => "leading_zeros",
+        TrailingZeros => "trailing_zeros",
+        Bitcast => "bitcast",
+        AtomicLoad => "atomic_load",
+        AtomicStore => "atomic_store",
+    ],
+    binary: [
+        Add => "add",
+        Sub => "sub",
+        Mul => "mul",
+        Div => "div",
+        Powf => "powf",
+        Atan2 => "atan2",
+        CopySign => "copy_sign",
+        Hypot => "hypot",
+        Equal => "equal",
+        NotEqual => "not_equal",
+        Lower => "lower",
+        Greater => "greater",
+        LowerEqual => "lower_equal",
+        GreaterEqual => "greater_equal",
+        Modulo => "modulo",
+        Index => "index",
+        UncheckedIndex => "unchecked_index",
+        IndexAssign => "index_assign",
+        UncheckedIndexAssign => "unchecked_index_assign",
+        And => "and",
+        Or => "or",
+        Max => "max",
+        Min => "min",
+        BitwiseAnd => "bitwise_and",
+        BitwiseOr => "bitwise_or",
+        BitwiseXor => "bitwise_xor",
+        ShiftLeft => "shift_left",
+        ShiftRight => "shift_right",
+        Remainder => "remainder",
+        AtomicSwap => "atomic_swap",
+        AtomicAdd => "atomic_add",
+        AtomicSub => "atomic_sub",
+        AtomicMax => "atomic_max",
+        AtomicMin => "atomic_min",
+        AtomicAnd => "atomic_and",
+        AtomicOr => "atomic_or",
+        AtomicXor => "atomic_xor",
+    ],
+    ternary: [
+        Fma(FmaOperator { a, b, c }) => "fma",
+        Clamp(ClampOperator { input, min_value, max_value }) => "clamp",
+        Slice(SliceOperator { input, start, end }) => "slice",
+        AtomicCompareAndSwap(CompareAndSwapOperator { input, cmp, val }) => "atomic_compare_and_swap",
+    ],
+);
+
+/// The name, inputs and output of a subcube operation.
+pub(super) fn subcube_parts(op: &Subcube) -> (&'static str, Vec<Variable>, Variable) {
+    match op {
+        Subcube::Elect(op) => ("subcube_elect", vec![], op.out),
+        Subcube::All(op) => ("subcube_all", vec![op.input], op.out),
+        Subcube::Any(op) => ("subcube_any", vec![op.input], op.out),
+        Subcube::Broadcast(op) => ("subcube_broadcast", vec![op.lhs, op.rhs], op.out),
+        Subcube::Sum(op) => ("subcube_sum", vec![op.input], op.out),
+        Subcube::Prod(op) => ("subcube_prod", vec![op.input], op.out),
+        Subcube::And(op) => ("subcube_and", vec![op.input], op.out),
+        Subcube::Or(op) => ("subcube_or", vec![op.input], op.out),
+        Subcube::Xor(op) => ("subcube_xor", vec![op.input], op.out),
+        Subcube::Min(op) => ("subcube_min", vec![op.input], op.out),
+        Subcube::Max(op) => ("subcube_max", vec![op.input], op.out),
+        Subcube::Shuffle(op) => ("subcube_shuffle", vec![op.lhs, op.rhs], op.out),
+        Subcube::ShuffleXor(op) => ("subcube_shuffle_xor", vec![op.lhs, op.rhs], op.out),
+        Subcube::ShuffleUp(op) => ("subcube_shuffle_up", vec![op.lhs, op.rhs], op.out),
+        Subcube::ShuffleDown(op) => ("subcube_shuffle_down", vec![op.lhs, op.rhs], op.out),
+        Subcube::InclusiveSum(op) => ("subcube_inclusive_sum", vec![op.input], op.out),
+        Subcube::ExclusiveSum(op) => ("subcube_exclusive_sum", vec![op.input], op.out),
+        Subcube::InclusiveProd(op) => ("subcube_inclusive_prod", vec![op.input], op.out),
+        Subcube::ExclusiveProd(op) => ("subcube_exclusive_prod", vec![op.input], op.out),
+        Subcube::Ballot(op) => ("subcube_ballot", vec![op.input], op.out),
+    }
+}
+
+/// The number of inputs of the subcube operation with the given name.
+pub(super) fn subcube_arity(name: &str) -> Option<usize> {
+    match name {
+        "subcube_elect" => Some(0),
+        "subcube_broadcast"
+        | "subcube_shuffle"
+        | "subcube_shuffle_xor"
+        | "subcube_shuffle_up"
+        | "subcube_shuffle_down" => Some(2),
+        "subcube_all"
+        | "subcube_any"
+        | "subcube_sum"
+        | "subcube_prod"
+        | "subcube_and"
+        | "subcube_or"
+        | "subcube_xor"
+        | "subcube_min"
+        | "subcube_max"
+        | "subcube_inclusive_sum"
+        | "subcube_exclusive_sum"
+        | "subcube_inclusive_prod"
+        | "subcube_exclusive_prod"
+        | "subcube_ballot" => Some(1),
+        _ => None,
+    }
+}
+
+/// Create the subcube operation with the given name, the number of inputs must match its arity.
+pub(super) fn subcube_from_parts(name: &str, inputs: &[Variable], out: Variable) -> Subcube {
+    let unary = || UnaryOperator {
+        input: inputs[0],
+        out,
+    };
+    let binary = || BinaryOperator {
+        lhs: inputs[0],
+        rhs: inputs[1],
+        out,
+    };
+
+    match name {
+        "subcube_elect" => Subcube::Elect(InitOperator { out }),
+        "subcube_broadcast" => Subcube::Broadcast(binary()),
+        "subcube_shuffle" => Subcube::Shuffle(binary()),
+        "subcube_shuffle_xor" => Subcube::ShuffleXor(binary()),
+        "subcube_shuffle_up" => Subcube::ShuffleUp(binary()),
+        "subcube_shuffle_down" => Subcube::ShuffleDown(binary()),
+        "subcube_all" => Subcube::All(unary()),
+        "subcube_any" => Subcube::Any(unary()),
+        "subcube_sum" => Subcube::Sum(unary()),
+        "subcube_prod" => Subcube::Prod(unary()),
+        "subcube_and" => Subcube::And(unary()),
+        "subcube_or" => Subcube::Or(unary()),
+        "subcube_xor" => Subcube::Xor(unary()),
+        "subcube_min" => Subcube::Min(unary()),
+        "subcube_max" => Subcube::Max(unary()),
+        "subcube_inclusive_sum" => Subcube::InclusiveSum(unary()),
+        "subcube_exclusive_sum" => Subcube::ExclusiveSum(unary()),
+        "subcube_inclusive_prod" => Subcube::InclusiveProd(unary()),
+        "subcube_exclusive_prod" => Subcube::ExclusiveProd(unary()),
+        "subcube_ballot" => Subcube::Ballot(unary()),
+        _ => unreachable!("Unknown subcube operation {name}"),
+    }
+}
+
+/// The name of the builtin variables.
+pub(super) const BUILTINS: [(&str, Variable); 22] = [
+    ("rank", Variable::Rank),
+    ("unit_pos", Variable::UnitPos),
+    ("unit_pos_x", Variable::UnitPosX),
+    ("unit_pos_y", Variable::UnitPosY),
+    ("unit_pos_z", Variable::UnitPosZ),
+    ("cube_pos", Variable::CubePos),
+    ("cube_pos_x", Variable::CubePosX),
+    ("cube_pos_y", Variable::CubePosY),
+    ("cube_pos_z", Variable::CubePosZ),
+    ("cube_dim", Variable::CubeDim),
+    ("cube_dim_x", Variable::CubeDimX),
+    ("cube_dim_y", Variable::CubeDimY),
+    ("cube_dim_z", Variable::CubeDimZ),
+    ("cube_count", Variable::CubeCount),
+    ("cube_count_x", Variable::CubeCountX),
+    ("cube_count_y", Variable::CubeCountY),
+    ("cube_count_z", Variable::CubeCountZ),
+    ("subcube_dim", Variable::SubcubeDim),
+    ("absolute_pos", Variable::AbsolutePos),
+    ("absolute_pos_x", Variable::AbsolutePosX),
+    ("absolute_pos_y", Variable::AbsolutePosY),
+    ("absolute_pos_z", Variable::AbsolutePosZ),
+];
+
+/// The name of the elements.
+pub(super) const ELEMS: [(&str, Elem); 22] = [
+    ("f16", Elem::Float(FloatKind::F16)),
+    ("bf16", Elem::Float(FloatKind::BF16)),
+    ("f32", Elem::Float(FloatKind::F32)),
+    ("f64", Elem::Float(FloatKind::F64)),
+    ("atomic_f16", Elem::AtomicFloat(FloatKind::F16)),
+    ("atomic_bf16", Elem::AtomicFloat(FloatKind::BF16)),
+    ("atomic_f32", Elem::AtomicFloat(FloatKind::F32)),
+    ("atomic_f64", Elem::AtomicFloat(FloatKind::F64)),
+    ("i8", Elem::Int(IntKind::I8)),
+    ("i16", Elem::Int(IntKind::I16)),
+    ("i32", Elem::Int(IntKind::I32)),
+    ("i64", Elem::Int(IntKind::I64)),
+    ("atomic_i8", Elem::AtomicInt(IntKind::I8)),
+    ("atomic_i16", Elem::AtomicInt(IntKind::I16)),
+    ("atomic_i32", Elem::AtomicInt(IntKind::I32)),
+    ("atomic_i64", Elem::AtomicInt(IntKind::I64)),
+    ("u8", Elem::UInt(UIntKind::U8)),
+    ("u16", Elem::UInt(UIntKind::U16)),
+    ("u32", Elem::UInt(UIntKind::U32)),
+    ("u64", Elem::UInt(UIntKind::U64)),
+    ("atomic_u32", Elem::AtomicUInt),
+    ("bool", Elem::Bool),
+];
+
+/// The name of the matrix identifiers.
+pub(super) const MATRIX_IDENTS: [(&str, MatrixIdent); 3] = [
+    ("a", MatrixIdent::A),
+    ("b", MatrixIdent::B),
+    ("accumulator", MatrixIdent::Accumulator),
+];
+
+/// The name of the matrix layouts.
+pub(super) const MATRIX_LAYOUTS: [(&str, MatrixLayout); 3] = [
+    ("col_major", MatrixLayout::ColMajor),
+    ("row_major", MatrixLayout::RowMajor),
+    ("undefined", MatrixLayout::Undefined),
+];
+
+/// The name of the reading strategies.
+pub(super) const READING_STRATEGIES: [(&str, ReadingStrategy); 2] = [
+    ("output_layout", ReadingStrategy::OutputLayout),
+    ("plain", ReadingStrategy::Plain),
+];
+
+/// The name of the binding visibilities.
+pub(super) const VISIBILITIES: [(&str, Visibility); 2] = [
+    ("read", Visibility::Read),
+    ("read_write", Visibility::ReadWrite),
+];
+
+/// The name of the binding locations.
+pub(super) const LOCATIONS: [(&str, Location); 2] =
+    [("storage", Location::Storage), ("cube", Location::Cube)];
+
+/// Find the name of a value in a table.
+pub(super) fn name_of<T: PartialEq>(table: &[(&'static str, T)], value: &T) -> &'static str {
+    table
+        .iter()
+        .find(|(_, entry)| entry == value)
+        .map(|(name, _)| *name)
+        .expect("All values have a name")
+}
+
+/// Find the value with the given name in a table.
+pub(super) fn value_of<T: Copy>(table: &[(&'static str, T)], name: &str) -> Option<T> {
+    table
+        .iter()
+        .find(|(entry, _)| *entry == name)
+        .map(|(_, value)| *value)
+}
@@ -0,0 +1,967 @@
+use super::names::{
+    operator_arity, operator_from_parts, subcube_arity, subcube_from_parts, value_of, BUILTINS,
+    ELEMS, LOCATIONS, MATRIX_IDENTS, MATRIX_LAYOUTS, READING_STRATEGIES, VISIBILITIES,
+};
+use crate::ir::{
+    Binding, Branch, Call, CheckedIndex, CheckedIndexAssign, ConditionalAssign,
+    ConstantScalarValue, CoopMma, CubeDim, EarlyReturn, Elem, Function, If, IfElse,
+    IndexOffsetGlobalWithLayout, Item, KernelDefinition, Loop, Matrix, Metadata, Operation,
+    Procedure, RangeLoop, ReadGlobal, ReadGlobalWithLayout, Scope, Switch, Synchronization,
+    Variable, WriteGlobal,
+};
+use std::{fmt::Display, str::FromStr};
+
+/// An error returned when parsing the textual representation of the IR.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line of the error, starting at 1.
+    pub line: usize,
+    /// The column of the error, starting at 1.
+    pub column: usize,
+    /// What went wrong.
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{}:{}: {}",
+            self.line, self.column, self.message
+        ))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl FromStr for KernelDefinition {
+    type Err = ParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(source)?;
+        let kernel = parser.kernel()?;
+        parser.end()?;
+
+        Ok(kernel)
+    }
+}
+
+impl FromStr for Scope {
+    type Err = ParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(source)?;
+        parser.keyword("scope")?;
+        let scope = parser.scope()?;
+        parser.end()?;
+
+        Ok(scope)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    /// A keyword or a name, e.g. `add` or `f32x4`.
+    Ident(String),
+    /// A number, possibly negative or with an exponent, e.g. `-1.5e-7`.
+    Number(String),
+    /// A variable without the `%`, e.g. `local1_2`.
+    Variable(String),
+    /// A function id without the `@`.
+    Function(String),
+    /// A string literal without quotes and escapes.
+    String(String),
+    /// A scope directive without the `.`, e.g. `local`.
+    Directive(String),
+    Punct(char),
+    Arrow,
+    Eof,
+}
+
+impl Display for TokenKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenKind::Ident(name) => f.write_fmt(format_args!("`{name}`")),
+            TokenKind::Number(value) => f.write_fmt(format_args!("number `{value}`")),
+            TokenKind::Variable(name) => f.write_fmt(format_args!("variable `%{name}`")),
+            TokenKind::Function(id) => f.write_fmt(format_args!("function `@{id}`")),
+            TokenKind::String(value) => f.write_fmt(format_args!("string {value:?}")),
+            TokenKind::Directive(name) => f.write_fmt(format_args!("directive `.{name}`")),
+            TokenKind::Punct(c) => f.write_fmt(format_args!("`{c}`")),
+            TokenKind::Arrow => f.write_str("`->`"),
+            TokenKind::Eof => f.write_str("end of input"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    line: usize,
+    column: usize,
+}
+
+/// Split the source into tokens, skipping whitespaces and `//` comments.
+fn tokenize(source: &str) -> Result<Vec<Token>, ParseError> {
+    let chars = source.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let (mut i, mut line, mut column) = (0, 1, 1);
+
+    let is_name = |c: char| c.is_ascii_alphanumeric() || c == '_';
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let error = |message: String| ParseError {
+            line,
+            column,
+            message,
+        };
+
+        // Take all characters matching the predicate, starting at an offset from the current one.
+        let take = |offset: usize, predicate: &dyn Fn(usize) -> bool| {
+            let mut end = start + offset;
+            while end < chars.len() && predicate(end) {
+                end += 1;
+            }
+            end
+        };
+
+        let (kind, end) = match c {
+            '\n' => {
+                i += 1;
+                line += 1;
+                column = 1;
+                continue;
+            }
+            c if c.is_whitespace() => {
+                i += 1;
+                column += 1;
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                i = take(0, &|j| chars[j] != '\n');
+                continue;
+            }
+            '-' if chars.get(i + 1) == Some(&'>') => (TokenKind::Arrow, i + 2),
+            c if c.is_ascii_digit() || c == '-' => {
+                let end = take(1, &|j| {
+                    is_name(chars[j])
+                        || chars[j] == '.'
+                        || (matches!(chars[j], '+' | '-') && matches!(chars[j - 1], 'e' | 'E'))
+                });
+                (TokenKind::Number(chars[i..end].iter().collect()), end)
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let end = take(0, &|j| is_name(chars[j]));
+                (TokenKind::Ident(chars[i..end].iter().collect()), end)
+            }
+            '%' | '@' | '.' => {
+                let end = take(1, &|j| is_name(chars[j]));
+                if end == i + 1 {
+                    return Err(error(format!("Expected a name after `{c}`")));
+                }
+                let name = chars[i + 1..end].iter().collect();
+                let kind = match c {
+                    '%' => TokenKind::Variable(name),
+                    '@' => TokenKind::Function(name),
+                    _ => TokenKind::Directive(name),
+                };
+                (kind, end)
+            }
+            '"' => {
+                let mut value = String::new();
+                let mut end = i + 1;
+                loop {
+                    match chars.get(end) {
+                        Some('"') => break,
+                        Some('\\') => {
+                            value.push(match chars.get(end + 1) {
+                                Some('n') => '\n',
+                                Some('t') => '\t',
+                                Some(c @ ('"' | '\\')) => *c,
+                                _ => return Err(error("Invalid escape in string".to_string())),
+                            });
+                            end += 2;
+                        }
+                        Some('\n') | None => return Err(error("Unterminated string".to_string())),
+                        Some(c) => {
+                            value.push(*c);
+                            end += 1;
+                        }
+                    }
+                }
+                (TokenKind::String(value), end + 1)
+            }
+            '{' | '}' | '(' | ')' | '[' | ']' | ',' | '=' | ':' => (TokenKind::Punct(c), i + 1),
+            c => return Err(error(format!("Unexpected character `{c}`"))),
+        };
+
+        tokens.push(Token { kind, line, column });
+        column += end - i;
+        i = end;
+    }
+
+    tokens.push(Token {
+        kind: TokenKind::Eof,
+        line,
+        column,
+    });
+
+    Ok(tokens)
+}
+
+/// A recursive descent parser for the format written by the printer.
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+type ParseResult<T> = Result<T, ParseError>;
+
+impl Parser {
+    fn new(source: &str) -> ParseResult<Self> {
+        Ok(Self {
+            tokens: tokenize(source)?,
+            position: 0,
+        })
+    }
+
+    fn peek(&self) -> &TokenKind {
+        &self.tokens[self.position].kind
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.position].clone();
+        if token.kind != TokenKind::Eof {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn error<T>(&self, message: String) -> ParseResult<T> {
+        let token = &self.tokens[self.position];
+
+        Err(ParseError {
+            line: token.line,
+            column: token.column,
+            message,
+        })
+    }
+
+    fn unexpected<T>(&self, expected: &str) -> ParseResult<T> {
+        self.error(format!("Expected {expected}, found {}", self.peek()))
+    }
+
+    fn end(&mut self) -> ParseResult<()> {
+        match self.peek() {
+            TokenKind::Eof => Ok(()),
+            _ => self.unexpected("end of input"),
+        }
+    }
+
+    fn is_punct(&self, c: char) -> bool {
+        self.peek() == &TokenKind::Punct(c)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), TokenKind::Ident(name) if name == keyword)
+    }
+
+    fn punct(&mut self, c: char) -> ParseResult<()> {
+        match self.is_punct(c) {
+            true => {
+                self.next();
+                Ok(())
+            }
+            false => self.unexpected(&format!("`{c}`")),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str) -> ParseResult<()> {
+        match self.is_keyword(keyword) {
+            true => {
+                self.next();
+                Ok(())
+            }
+            false => self.unexpected(&format!("`{keyword}`")),
+        }
+    }
+
+    fn ident(&mut self) -> ParseResult<String> {
+        match self.peek().clone() {
+            TokenKind::Ident(name) => {
+                self.next();
+                Ok(name)
+            }
+            _ => self.unexpected("a name"),
+        }
+    }
+
+    fn number<T: FromStr>(&mut self) -> ParseResult<T> {
+        match self.peek().clone() {
+            TokenKind::Number(value) => match value.parse() {
+                Ok(value) => {
+                    self.next();
+                    Ok(value)
+                }
+                Err(_) => self.error(format!("Invalid number `{value}`")),
+            },
+            _ => self.unexpected("a number"),
+        }
+    }
+
+    /// Parse a name found in one of the name tables.
+    fn named<T: Copy>(&mut self, table: &[(&'static str, T)], what: &str) -> ParseResult<T> {
+        match self.peek().clone() {
+            TokenKind::Ident(name) => match value_of(table, &name) {
+                Some(value) => {
+                    self.next();
+                    Ok(value)
+                }
+                None => self.error(format!("Unknown {what} `{name}`")),
+            },
+            _ => self.unexpected(what),
+        }
+    }
+
+    fn kernel(&mut self) -> ParseResult<KernelDefinition> {
+        self.keyword("kernel")?;
+        self.punct('{')?;
+
+        let mut kernel = KernelDefinition {
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            named: Vec::new(),
+            cube_dim: CubeDim::default(),
+            functions: Vec::new(),
+            body: Scope::root(),
+        };
+
+        loop {
+            match self.peek().clone() {
+                TokenKind::Directive(directive) => {
+                    self.next();
+                    match directive.as_str() {
+                        "cube_dim" => {
+                            let x = self.number()?;
+                            self.punct(',')?;
+                            let y = self.number()?;
+                            self.punct(',')?;
+                            let z = self.number()?;
+                            kernel.cube_dim = CubeDim { x, y, z };
+                        }
+                        "input" => kernel.inputs.push(self.binding()?),
+                        "output" => kernel.outputs.push(self.binding()?),
+                        "named" => {
+                            let name = match self.next().kind {
+                                TokenKind::String(name) => name,
+                                _ => {
+                                    self.position -= 1;
+                                    return self.unexpected("a string");
+                                }
+                            };
+                            kernel.named.push((name, self.binding()?));
+                        }
+                        _ => {
+                            self.position -= 1;
+                            return self.error(format!("Unknown kernel directive `.{directive}`"));
+                        }
+                    }
+                }
+                TokenKind::Ident(keyword) if keyword == "function" => {
+                    let function = self.function()?;
+                    kernel.functions.push(function);
+                }
+                TokenKind::Ident(keyword) if keyword == "body" => {
+                    self.next();
+                    kernel.body = self.scope()?;
+                    self.punct('}')?;
+                    return Ok(kernel);
+                }
+                _ => return self.unexpected("a kernel directive, a function or the body"),
+            }
+        }
+    }
+
+    fn binding(&mut self) -> ParseResult<Binding> {
+        let item = self.item()?;
+        self.punct(',')?;
+        let visibility = self.named(&VISIBILITIES, "visibility")?;
+        self.punct(',')?;
+        let location = self.named(&LOCATIONS, "location")?;
+
+        let size = match self.is_punct(',') {
+            true => {
+                self.next();
+                self.keyword("size")?;
+                Some(self.number()?)
+            }
+            false => None,
+        };
+
+        Ok(Binding {
+            location,
+            visibility,
+            item,
+            size,
+        })
+    }
+
+    fn function(&mut self) -> ParseResult<Function> {
+        self.keyword("function")?;
+
+        let id = match self.peek().clone() {
+            TokenKind::Function(id) => match id.parse() {
+                Ok(id) => {
+                    self.next();
+                    id
+                }
+                Err(_) => return self.error(format!("Invalid function id `@{id}`")),
+            },
+            _ => return self.unexpected("a function id"),
+        };
+        let name = match self.peek().clone() {
+            TokenKind::String(name) => {
+                self.next();
+                name
+            }
+            _ => return self.unexpected("a string"),
+        };
+        let inputs = self.variables('(', ')')?;
+        let output = match self.peek() {
+            TokenKind::Arrow => {
+                self.next();
+                Some(self.variable()?)
+            }
+            _ => None,
+        };
+        let scope = self.scope()?;
+
+        Ok(Function {
+            id,
+            name,
+            inputs,
+            output,
+            scope,
+        })
+    }
+
+    fn item(&mut self) -> ParseResult<Item> {
+        let name = match self.peek().clone() {
+            TokenKind::Ident(name) => name,
+            _ => return self.unexpected("a type"),
+        };
+
+        if let Some(elem) = value_of(&ELEMS, &name) {
+            self.next();
+            return Ok(Item::new(elem));
+        }
+
+        let vectorized = name.rsplit_once('x').and_then(|(elem, vectorization)| {
+            Some(Item::vectorized(
+                value_of(&ELEMS, elem)?,
+                vectorization.parse().ok()?,
+            ))
+        });
+
+        match vectorized {
+            Some(item) => {
+                self.next();
+                Ok(item)
+            }
+            None => self.error(format!("Unknown type `{name}`")),
+        }
+    }
+
+    fn elem(&mut self) -> ParseResult<Elem> {
+        self.named(&ELEMS, "element type")
+    }
+
+    /// Parse the `[length]` of an array.
+    fn length(&mut self) -> ParseResult<u32> {
+        self.punct('[')?;
+        let length = self.number()?;
+        self.punct(']')?;
+
+        Ok(length)
+    }
+
+    fn variable(&mut self) -> ParseResult<Variable> {
+        let start = self.position;
+
+        match self.peek().clone() {
+            TokenKind::Variable(name) => {
+                if let Some(builtin) = value_of(&BUILTINS, &name) {
+                    self.next();
+                    return Ok(builtin);
+                }
+
+                let split = name
+                    .find(|c: char| c.is_ascii_digit())
+                    .unwrap_or(name.len());
+                let (kind, indices) = name.split_at(split);
+                let indices = indices
+                    .split('_')
+                    .map(|index| index.parse::<u16>())
+                    .collect::<Result<Vec<_>, _>>();
+
+                // The depth comes first for the variables local to a scope.
+                let (depth, id) = match indices.as_deref() {
+                    Ok([id]) => (None, *id),
+                    Ok([depth, id]) if *depth <= u8::MAX as u16 => (Some(*depth as u8), *id),
+                    _ => return self.error(format!("Invalid variable `%{name}`")),
+                };
+
+                self.next();
+                self.punct(':')?;
+
+                let variable = match (kind, depth) {
+                    ("input", None) => Variable::GlobalInputArray {
+                        id,
+                        item: self.item()?,
+                    },
+                    ("output", None) => Variable::GlobalOutputArray {
+                        id,
+                        item: self.item()?,
+                    },
+                    ("scalar", None) => Variable::GlobalScalar {
+                        id,
+                        elem: self.elem()?,
+                    },
+                    ("shared", None) => Variable::SharedMemory {
+                        id,
+                        item: self.item()?,
+                        length: self.length()?,
+                    },
+                    ("local", Some(depth)) => Variable::Local {
+                        id,
+                        item: self.item()?,
+                        depth,
+                    },
+                    ("local_scalar", Some(depth)) => Variable::LocalScalar {
+                        id,
+                        elem: self.elem()?,
+                        depth,
+                    },
+                    ("local_array", Some(depth)) => Variable::LocalArray {
+                        id,
+                        item: self.item()?,
+                        depth,
+                        length: self.length()?,
+                    },
+                    ("slice", Some(depth)) => Variable::Slice {
+                        id,
+                        item: self.item()?,
+                        depth,
+                    },
+                    ("matrix", Some(depth)) => Variable::Matrix {
+                        id,
+                        mat: self.matrix()?,
+                        depth,
+                    },
+                    _ => {
+                        self.position = start;
+                        return self.error(format!("Invalid variable `%{name}`"));
+                    }
+                };
+
+                Ok(variable)
+            }
+            TokenKind::Number(value) | TokenKind::Ident(value) => {
+                self.next();
+                self.punct(':')?;
+                let elem = self.elem()?;
+                let constant = match elem {
+                    Elem::Float(kind) => value
+                        .parse()
+                        .ok()
+                        .map(|val| ConstantScalarValue::Float(val, kind)),
+                    Elem::Int(kind) => value
+                        .parse()
+                        .ok()
+                        .map(|val| ConstantScalarValue::Int(val, kind)),
+                    Elem::UInt(kind) => value
+                        .parse()
+                        .ok()
+                        .map(|val| ConstantScalarValue::UInt(val, kind)),
+                    Elem::Bool => value.parse().ok().map(ConstantScalarValue::Bool),
+                    Elem::AtomicFloat(_) | Elem::AtomicInt(_) | Elem::AtomicUInt => None,
+                };
+
+                match constant {
+                    Some(constant) => Ok(Variable::ConstantScalar(constant)),
+                    None => {
+                        self.position = start;
+                        self.error(format!("Invalid constant `{value}`"))
+                    }
+                }
+            }
+            _ => self.unexpected("a variable"),
+        }
+    }
+
+    fn matrix(&mut self) -> ParseResult<Matrix> {
+        self.keyword("matrix")?;
+        self.punct('(')?;
+        let ident = self.named(&MATRIX_IDENTS, "matrix identifier")?;
+        self.punct(',')?;
+        let m = self.number()?;
+        self.punct(',')?;
+        let n = self.number()?;
+        self.punct(',')?;
+        let k = self.number()?;
+        self.punct(',')?;
+        let elem = self.elem()?;
+        self.punct(',')?;
+        let layout = self.named(&MATRIX_LAYOUTS, "matrix layout")?;
+        self.punct(')')?;
+
+        Ok(Matrix {
+            ident,
+            m,
+            n,
+            k,
+            elem,
+            layout,
+        })
+    }
+
+    /// Parse a comma separated list of variables between delimiters.
+    fn variables(&mut self, open: char, close: char) -> ParseResult<Vec<Variable>> {
+        self.punct(open)?;
+
+        let mut variables = Vec::new();
+        while !self.is_punct(close) {
+            if !variables.is_empty() {
+                self.punct(',')?;
+            }
+            variables.push(self.variable()?);
+        }
+        self.punct(close)?;
+
+        Ok(variables)
+    }
+
+    /// Parse a comma separated list of `count` variables.
+    fn inputs(&mut self, count: usize) -> ParseResult<Vec<Variable>> {
+        let mut inputs = Vec::with_capacity(count);
+        for i in 0..count {
+            if i > 0 {
+                self.punct(',')?;
+            }
+            inputs.push(self.variable()?);
+        }
+
+        Ok(inputs)
+    }
+
+    /// Parse the name of a field, preceded by a comma if it isn't the first one.
+    fn field_name(&mut self, name: &str) -> ParseResult<()> {
+        if !self.is_keyword(name) {
+            self.punct(',')?;
+        }
+        self.keyword(name)?;
+        self.punct('=')
+    }
+
+    fn field(&mut self, name: &str) -> ParseResult<Variable> {
+        self.field_name(name)?;
+        self.variable()
+    }
+
+    fn list_field(&mut self, name: &str) -> ParseResult<Vec<Variable>> {
+        self.field_name(name)?;
+        self.variables('[', ']')
+    }
+
+    fn scope(&mut self) -> ParseResult<Scope> {
+        self.punct('{')?;
+
+        let mut scope = Scope::root();
+
+        loop {
+            match self.peek().clone() {
+                TokenKind::Punct('}') => {
+                    self.next();
+                    return Ok(scope);
+                }
+                TokenKind::Directive(directive) => {
+                    self.next();
+                    self.directive(&mut scope, &directive)?;
+                }
+                _ => {
+                    let operation = self.operation()?;
+                    scope.operations.push(operation);
+                }
+            }
+        }
+    }
+
+    fn directive(&mut self, scope: &mut Scope, directive: &str) -> ParseResult<()> {
+        match directive {
+            "depth" => scope.depth = self.number()?,
+            "local" => scope.locals.push(self.variable()?),
+            "matrix" => scope.matrices.push(self.variable()?),
+            "slice" => scope.slices.push(self.variable()?),
+            "shared" => scope.shared_memories.push(self.variable()?),
+            "local_array" => scope.local_arrays.push(self.variable()?),
+            "read_global" => {
+                let input = self.variable()?;
+                self.punct(',')?;
+                let strategy = self.named(&READING_STRATEGIES, "reading strategy")?;
+                self.punct(',')?;
+                let local = self.variable()?;
+                self.punct(',')?;
+                let position = self.variable()?;
+                scope.reads_global.push((input, strategy, local, position));
+            }
+            "index_offset_with_output_layout_position" => scope
+                .index_offset_with_output_layout_position
+                .push(self.number()?),
+            "write_global" => {
+                let inputs = self.inputs(3)?;
+                scope.writes_global.push((inputs[0], inputs[1], inputs[2]));
+            }
+            "read_scalar" => {
+                let inputs = self.inputs(2)?;
+                scope.reads_scalar.push((inputs[0], inputs[1]));
+            }
+            "layout_ref" => scope.layout_ref = Some(self.variable()?),
+            "undeclared" => scope.undeclared = self.number()?,
+            "preserve" => scope.preserved.push(self.variable()?),
+            _ => {
+                self.position -= 1;
+                return self.error(format!("Unknown scope directive `.{directive}`"));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn operation(&mut self) -> ParseResult<Operation> {
+        if let TokenKind::Variable(_) = self.peek() {
+            let out = self.variable()?;
+            self.punct('=')?;
+            return self.assignment(out);
+        }
+
+        let name = self.ident()?;
+
+        let operation = match name.as_str() {
+            "call" => Operation::Call(self.call(None)?),
+            "sync_units" => Operation::Synchronization(Synchronization::SyncUnits),
+            "sync_storage" => Operation::Synchronization(Synchronization::SyncStorage),
+            "if" => {
+                let cond = self.variable()?;
+                let scope = self.scope()?;
+
+                match self.is_keyword("else") {
+                    true => {
+                        self.next();
+                        Operation::Branch(Branch::IfElse(IfElse {
+                            cond,
+                            scope_if: scope,
+                            scope_else: self.scope()?,
+                        }))
+                    }
+                    false => Operation::Branch(Branch::If(If { cond, scope })),
+                }
+            }
+            "switch" => {
+                let value = self.variable()?;
+                self.punct('{')?;
+                let mut cases = Vec::new();
+                while self.is_keyword("case") {
+                    self.next();
+                    let mut values = vec![self.variable()?];
+                    while self.is_punct(',') {
+                        self.next();
+                        values.push(self.variable()?);
+                    }
+                    cases.push((values, self.scope()?));
+                }
+                self.keyword("default")?;
+                let scope_default = self.scope()?;
+                self.punct('}')?;
+
+                Operation::Branch(Branch::Switch(Switch {
+                    value,
+                    scope_default,
+                    cases,
+                }))
+            }
+            "range_loop" => {
+                let i = self.variable()?;
+                self.punct('=')?;
+                let start = self.variable()?;
+                self.keyword("to")?;
+                let end = self.variable()?;
+                let step = match self.is_keyword("step") {
+                    true => {
+                        self.next();
+                        Some(self.variable()?)
+                    }
+                    false => None,
+                };
+
+                Operation::Branch(Branch::RangeLoop(RangeLoop {
+                    i,
+                    start,
+                    end,
+                    step,
+                    scope: self.scope()?,
+                }))
+            }
+            "loop" => Operation::Branch(Branch::Loop(Loop {
+                scope: self.scope()?,
+            })),
+            "break" => Operation::Branch(Branch::Break),
+            "return" => Operation::Branch(Branch::Return),
+            "cmma_fill" => Operation::CoopMma(CoopMma::Fill {
+                mat: self.field("mat")?,
+                value: self.field("value")?,
+            }),
+            "cmma_load" => Operation::CoopMma(CoopMma::Load {
+                mat: self.field("mat")?,
+                value: self.field("value")?,
+                stride: self.field("stride")?,
+            }),
+            "cmma_execute" => Operation::CoopMma(CoopMma::Execute {
+                mat_a: self.field("mat_a")?,
+                mat_b: self.field("mat_b")?,
+                mat_c: self.field("mat_c")?,
+                mat_d: self.field("mat_d")?,
+            }),
+            "cmma_store" => Operation::CoopMma(CoopMma::Store {
+                output: self.field("output")?,
+                mat: self.field("mat")?,
+                stride: self.field("stride")?,
+                layout: {
+                    self.field_name("layout")?;
+                    self.named(&MATRIX_LAYOUTS, "matrix layout")?
+                },
+            }),
+            name => match self.procedure(name)? {
+                Some(proc) => Operation::Procedure(proc),
+                None => {
+                    self.position -= 1;
+                    return self.error(format!("Unknown operation `{name}`"));
+                }
+            },
+        };
+
+        Ok(operation)
+    }
+
+    /// Parse the right hand side of an operation writing a single output.
+    fn assignment(&mut self, out: Variable) -> ParseResult<Operation> {
+        let name = self.ident()?;
+
+        if let Some(arity) = operator_arity(&name) {
+            let inputs = self.inputs(arity)?;
+            return Ok(Operation::Operator(operator_from_parts(
+                &name, &inputs, out,
+            )));
+        }
+
+        if let Some(arity) = subcube_arity(&name) {
+            let inputs = self.inputs(arity)?;
+            return Ok(Operation::Subcube(subcube_from_parts(&name, &inputs, out)));
+        }
+
+        let operation = match name.as_str() {
+            "stride" | "shape" => {
+                let inputs = self.inputs(2)?;
+                let (var, dim) = (inputs[0], inputs[1]);
+                Operation::Metadata(match name.as_str() {
+                    "stride" => Metadata::Stride { dim, var, out },
+                    _ => Metadata::Shape { dim, var, out },
+                })
+            }
+            "length" => Operation::Metadata(Metadata::Length {
+                var: self.variable()?,
+                out,
+            }),
+            "call" => Operation::Call(self.call(Some(out))?),
+            _ => {
+                self.position -= 1;
+                return self.error(format!("Unknown operation `{name}`"));
+            }
+        };
+
+        Ok(operation)
+    }
+
+    fn call(&mut self, output: Option<Variable>) -> ParseResult<Call> {
+        let function = match self.peek().clone() {
+            TokenKind::Function(id) => match id.parse() {
+                Ok(id) => {
+                    self.next();
+                    id
+                }
+                Err(_) => return self.error(format!("Invalid function id `@{id}`")),
+            },
+            _ => return self.unexpected("a function id"),
+        };
+
+        Ok(Call {
+            function,
+            inputs: self.variables('(', ')')?,
+            output,
+        })
+    }
+
+    fn procedure(&mut self, name: &str) -> ParseResult<Option<Procedure>> {
+        let proc = match name {
+            "read_global_with_layout" => Procedure::ReadGlobalWithLayout(ReadGlobalWithLayout {
+                globals: self.list_field("globals")?,
+                outs: self.list_field("outs")?,
+                layout: self.field("layout")?,
+                position: self.field("position")?,
+            }),
+            "index_offset_global_with_layout" => {
+                Procedure::IndexOffsetGlobalWithLayout(IndexOffsetGlobalWithLayout {
+                    tensors: self.list_field("tensors")?,
+                    indexes: self.list_field("indexes")?,
+                    layout: self.field("layout")?,
+                    position: self.field("position")?,
+                    dim_start: self.field("dim_start")?,
+                    dim_end: self.field("dim_end")?,
+                })
+            }
+            "read_global" => Procedure::ReadGlobal(ReadGlobal {
+                global: self.field("global")?,
+                out: self.field("out")?,
+                position: self.field("position")?,
+            }),
+            "write_global" => Procedure::WriteGlobal(WriteGlobal {
+                input: self.field("input")?,
+                global: self.field("global")?,
+                position: self.field("position")?,
+            }),
+            "checked_index" => Procedure::CheckedIndex(CheckedIndex {
+                lhs: self.field("lhs")?,
+                rhs: self.field("rhs")?,
+                out: self.field("out")?,
+            }),
+            "checked_index_assign" => Procedure::CheckedIndexAssign(CheckedIndexAssign {
+                lhs: self.field("lhs")?,
+                rhs: self.field("rhs")?,
+                out: self.field("out")?,
+            }),
+            "conditional_assign" => Procedure::ConditionalAssign(ConditionalAssign {
+                cond: self.field("cond")?,
+                lhs: self.field("lhs")?,
+                rhs: self.field("rhs")?,
+                out: self.field("out")?,
+            }),
+            "early_return" => Procedure::EarlyReturn(EarlyReturn {
+                global: self.field("global")?,
+                position: self.field("position")?,
+            }),
+            _ => return Ok(None),
+        };
+
+        Ok(Some(proc))
+    }
+}
@@ -0,0 +1,538 @@
+use super::names::{
+    name_of, operator_parts, subcube_parts, BUILTINS, ELEMS, LOCATIONS, MATRIX_IDENTS,
+    MATRIX_LAYOUTS, READING_STRATEGIES, VISIBILITIES,
+};
+use crate::ir::{
+    Binding, Branch, ConstantScalarValue, CoopMma, Elem, Function, Item, KernelDefinition,
+    Metadata, Operation, Procedure, Scope, Synchronization, Variable,
+};
+use std::fmt::{Display, Formatter, Result};
+
+const INDENTATION: usize = 4;
+
+/// Print the IR with one operation per line, nested scopes being indented.
+struct Printer<'a, 'b> {
+    f: &'a mut Formatter<'b>,
+    indent: usize,
+}
+
+impl<'a, 'b> Printer<'a, 'b> {
+    fn new(f: &'a mut Formatter<'b>) -> Self {
+        Self { f, indent: 0 }
+    }
+
+    fn line(&mut self, args: std::fmt::Arguments<'_>) -> Result {
+        self.f
+            .write_fmt(format_args!("{:1$}", "", self.indent * INDENTATION))?;
+        self.f.write_fmt(args)?;
+        self.f.write_str("\n")
+    }
+
+    fn kernel(&mut self, kernel: &KernelDefinition) -> Result {
+        self.line(format_args!("kernel {{"))?;
+        self.indent += 1;
+
+        let cube_dim = kernel.cube_dim;
+        self.line(format_args!(
+            ".cube_dim {}, {}, {}",
+            cube_dim.x, cube_dim.y, cube_dim.z
+        ))?;
+        for binding in kernel.inputs.iter() {
+            self.line(format_args!(".input {}", BindingDisplay(binding)))?;
+        }
+        for binding in kernel.outputs.iter() {
+            self.line(format_args!(".output {}", BindingDisplay(binding)))?;
+        }
+        for (name, binding) in kernel.named.iter() {
+            self.line(format_args!(".named {name:?} {}", BindingDisplay(binding)))?;
+        }
+        for function in kernel.functions.iter() {
+            self.function(function)?;
+        }
+
+        self.block(format_args!("body"), &kernel.body)?;
+
+        self.indent -= 1;
+        self.line(format_args!("}}"))
+    }
+
+    fn function(&mut self, function: &Function) -> Result {
+        let inputs = function
+            .inputs
+            .iter()
+            .map(|input| VariableDisplay(input).to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        match &function.output {
+            Some(output) => self.block(
+                format_args!(
+                    "function @{} {:?}({inputs}) -> {}",
+                    function.id,
+                    function.name,
+                    VariableDisplay(output)
+                ),
+                &function.scope,
+            ),
+            None => self.block(
+                format_args!("function @{} {:?}({inputs})", function.id, function.name),
+                &function.scope,
+            ),
+        }
+    }
+
+    /// Print a line opening a scope, the scope and its closing brace.
+    fn block(&mut self, header: std::fmt::Arguments<'_>, scope: &Scope) -> Result {
+        self.line(format_args!("{header} {{"))?;
+        self.indent += 1;
+        self.scope(scope)?;
+        self.indent -= 1;
+        self.line(format_args!("}}"))
+    }
+
+    fn scope(&mut self, scope: &Scope) -> Result {
+        self.line(format_args!(".depth {}", scope.depth))?;
+
+        for (directive, variables) in [
+            ("local", &scope.locals),
+            ("matrix", &scope.matrices),
+            ("slice", &scope.slices),
+            ("shared", &scope.shared_memories),
+            ("local_array", &scope.local_arrays),
+        ] {
+            for var in variables.iter() {
+                self.line(format_args!(".{directive} {}", VariableDisplay(var)))?;
+            }
+        }
+        for (input, strategy, local, position) in scope.reads_global.iter() {
+            self.line(format_args!(
+                ".read_global {}, {}, {}, {}",
+                VariableDisplay(input),
+                name_of(&READING_STRATEGIES, strategy),
+                VariableDisplay(local),
+                VariableDisplay(position)
+            ))?;
+        }
+        for position in scope.index_offset_with_output_layout_position.iter() {
+            self.line(format_args!(
+                ".index_offset_with_output_layout_position {position}"
+            ))?;
+        }
+        for (input, output, position) in scope.writes_global.iter() {
+            self.line(format_args!(
+                ".write_global {}, {}, {}",
+                VariableDisplay(input),
+                VariableDisplay(output),
+                VariableDisplay(position)
+            ))?;
+        }
+        for (local, scalar) in scope.reads_scalar.iter() {
+            self.line(format_args!(
+                ".read_scalar {}, {}",
+                VariableDisplay(local),
+                VariableDisplay(scalar)
+            ))?;
+        }
+        if let Some(layout) = &scope.layout_ref {
+            self.line(format_args!(".layout_ref {}", VariableDisplay(layout)))?;
+        }
+        if scope.undeclared > 0 {
+            self.line(format_args!(".undeclared {}", scope.undeclared))?;
+        }
+        for var in scope.preserved.iter() {
+            self.line(format_args!(".preserve {}", VariableDisplay(var)))?;
+        }
+
+        for operation in scope.operations.iter() {
+            self.operation(operation)?;
+        }
+
+        Ok(())
+    }
+
+    fn operation(&mut self, operation: &Operation) -> Result {
+        match operation {
+            Operation::Operator(op) => {
+                let (name, inputs, out) = operator_parts(op);
+                self.assignment(name, &inputs, &out)
+            }
+            Operation::Subcube(op) => {
+                let (name, inputs, out) = subcube_parts(op);
+                self.assignment(name, &inputs, &out)
+            }
+            Operation::Metadata(op) => match op {
+                Metadata::Stride { dim, var, out } => self.assignment("stride", &[*var, *dim], out),
+                Metadata::Shape { dim, var, out } => self.assignment("shape", &[*var, *dim], out),
+                Metadata::Length { var, out } => self.assignment("length", &[*var], out),
+            },
+            Operation::Procedure(proc) => self.procedure(proc),
+            Operation::Branch(branch) => self.branch(branch),
+            Operation::Synchronization(sync) => match sync {
+                Synchronization::SyncUnits => self.line(format_args!("sync_units")),
+                Synchronization::SyncStorage => self.line(format_args!("sync_storage")),
+            },
+            Operation::CoopMma(op) => match op {
+                CoopMma::Fill { mat, value } => {
+                    self.fields("cmma_fill", &[("mat", mat.into()), ("value", value.into())])
+                }
+                CoopMma::Load { mat, value, stride } => self.fields(
+                    "cmma_load",
+                    &[
+                        ("mat", mat.into()),
+                        ("value", value.into()),
+                        ("stride", stride.into()),
+                    ],
+                ),
+                CoopMma::Execute {
+                    mat_a,
+                    mat_b,
+                    mat_c,
+                    mat_d,
+                } => self.fields(
+                    "cmma_execute",
+                    &[
+                        ("mat_a", mat_a.into()),
+                        ("mat_b", mat_b.into()),
+                        ("mat_c", mat_c.into()),
+                        ("mat_d", mat_d.into()),
+                    ],
+                ),
+                CoopMma::Store {
+                    output,
+                    mat,
+                    stride,
+                    layout,
+                } => self.fields(
+                    "cmma_store",
+                    &[
+                        ("output", output.into()),
+                        ("mat", mat.into()),
+                        ("stride", stride.into()),
+                        ("layout", Field::Name(name_of(&MATRIX_LAYOUTS, layout))),
+                    ],
+                ),
+            },
+            Operation::Call(call) => {
+                let inputs = call
+                    .inputs
+                    .iter()
+                    .map(|input| VariableDisplay(input).to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                match &call.output {
+                    Some(out) => self.line(format_args!(
+                        "{} = call @{}({inputs})",
+                        VariableDisplay(out),
+                        call.function
+                    )),
+                    None => self.line(format_args!("call @{}({inputs})", call.function)),
+                }
+            }
+        }
+    }
+
+    /// Print an operation writing a single output, e.g. `%out = add %lhs, %rhs`.
+    fn assignment(&mut self, name: &str, inputs: &[Variable], out: &Variable) -> Result {
+        let inputs = inputs
+            .iter()
+            .map(|input| VariableDisplay(input).to_string())
+            .collect::<Vec<_>>();
+
+        match inputs.is_empty() {
+            true => self.line(format_args!("{} = {name}", VariableDisplay(out))),
+            false => self.line(format_args!(
+                "{} = {name} {}",
+                VariableDisplay(out),
+                inputs.join(", ")
+            )),
+        }
+    }
+
+    /// Print an operation with named fields, e.g. `read_global global=%input0:f32, ...`.
+    fn fields(&mut self, name: &str, fields: &[(&str, Field<'_>)]) -> Result {
+        let fields = fields
+            .iter()
+            .map(|(field, value)| format!("{field}={value}"))
+            .collect::<Vec<_>>();
+
+        self.line(format_args!("{name} {}", fields.join(", ")))
+    }
+
+    fn procedure(&mut self, proc: &Procedure) -> Result {
+        match proc {
+            Procedure::ReadGlobalWithLayout(proc) => self.fields(
+                "read_global_with_layout",
+                &[
+                    ("globals", Field::List(&proc.globals)),
+                    ("outs", Field::List(&proc.outs)),
+                    ("layout", (&proc.layout).into()),
+                    ("position", (&proc.position).into()),
+                ],
+            ),
+            Procedure::IndexOffsetGlobalWithLayout(proc) => self.fields(
+                "index_offset_global_with_layout",
+                &[
+                    ("tensors", Field::List(&proc.tensors)),
+                    ("indexes", Field::List(&proc.indexes)),
+                    ("layout", (&proc.layout).into()),
+                    ("position", (&proc.position).into()),
+                    ("dim_start", (&proc.dim_start).into()),
+                    ("dim_end", (&proc.dim_end).into()),
+                ],
+            ),
+            Procedure::ReadGlobal(proc) => self.fields(
+                "read_global",
+                &[
+                    ("global", (&proc.global).into()),
+                    ("out", (&proc.out).into()),
+                    ("position", (&proc.position).into()),
+                ],
+            ),
+            Procedure::WriteGlobal(proc) => self.fields(
+                "write_global",
+                &[
+                    ("input", (&proc.input).into()),
+                    ("global", (&proc.global).into()),
+                    ("position", (&proc.position).into()),
+                ],
+            ),
+            Procedure::CheckedIndex(proc) => self.fields(
+                "checked_index",
+                &[
+                    ("lhs", (&proc.lhs).into()),
+                    ("rhs", (&proc.rhs).into()),
+                    ("out", (&proc.out).into()),
+                ],
+            ),
+            Procedure::CheckedIndexAssign(proc) => self.fields(
+                "checked_index_assign",
+                &[
+                    ("lhs", (&proc.lhs).into()),
+                    ("rhs", (&proc.rhs).into()),
+                    ("out", (&proc.out).into()),
+                ],
+            ),
+            Procedure::ConditionalAssign(proc) => self.fields(
+                "conditional_assign",
+                &[
+                    ("cond", (&proc.cond).into()),
+                    ("lhs", (&proc.lhs).into()),
+                    ("rhs", (&proc.rhs).into()),
+                    ("out", (&proc.out).into()),
+                ],
+            ),
+            Procedure::EarlyReturn(proc) => self.fields(
+                "early_return",
+                &[
+                    ("global", (&proc.global).into()),
+                    ("position", (&proc.position).into()),
+                ],
+            ),
+        }
+    }
+
+    fn branch(&mut self, branch: &Branch) -> Result {
+        match branch {
+            Branch::If(op) => {
+                self.block(format_args!("if {}", VariableDisplay(&op.cond)), &op.scope)
+            }
+            Branch::IfElse(op) => {
+                self.line(format_args!("if {} {{", VariableDisplay(&op.cond)))?;
+                self.indent += 1;
+                self.scope(&op.scope_if)?;
+                self.indent -= 1;
+                self.block(format_args!("}} else"), &op.scope_else)
+            }
+            Branch::Switch(op) => {
+                self.line(format_args!("switch {} {{", VariableDisplay(&op.value)))?;
+                self.indent += 1;
+                for (values, scope) in op.cases.iter() {
+                    let values = values
+                        .iter()
+                        .map(|value| VariableDisplay(value).to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    self.block(format_args!("case {values}"), scope)?;
+                }
+                self.block(format_args!("default"), &op.scope_default)?;
+                self.indent -= 1;
+                self.line(format_args!("}}"))
+            }
+            Branch::RangeLoop(op) => match &op.step {
+                Some(step) => self.block(
+                    format_args!(
+                        "range_loop {} = {} to {} step {}",
+                        VariableDisplay(&op.i),
+                        VariableDisplay(&op.start),
+                        VariableDisplay(&op.end),
+                        VariableDisplay(step)
+                    ),
+                    &op.scope,
+                ),
+                None => self.block(
+                    format_args!(
+                        "range_loop {} = {} to {}",
+                        VariableDisplay(&op.i),
+                        VariableDisplay(&op.start),
+                        VariableDisplay(&op.end)
+                    ),
+                    &op.scope,
+                ),
+            },
+            Branch::Loop(op) => self.block(format_args!("loop"), &op.scope),
+            Branch::Return => self.line(format_args!("return")),
+            Branch::Break => self.line(format_args!("break")),
+        }
+    }
+}
+
+/// The value of a named field.
+enum Field<'a> {
+    Variable(&'a Variable),
+    List(&'a [Variable]),
+    Name(&'static str),
+}
+
+impl<'a> From<&'a Variable> for Field<'a> {
+    fn from(value: &'a Variable) -> Self {
+        Self::Variable(value)
+    }
+}
+
+impl Display for Field<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Field::Variable(var) => VariableDisplay(var).fmt(f),
+            Field::List(vars) => {
+                let vars = vars
+                    .iter()
+                    .map(|var| VariableDisplay(var).to_string())
+                    .collect::<Vec<_>>();
+                f.write_fmt(format_args!("[{}]", vars.join(", ")))
+            }
+            Field::Name(name) => f.write_str(name),
+        }
+    }
+}
+
+struct BindingDisplay<'a>(&'a Binding);
+
+impl Display for BindingDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let binding = self.0;
+
+        f.write_fmt(format_args!(
+            "{}, {}, {}",
+            ItemDisplay(&binding.item),
+            name_of(&VISIBILITIES, &binding.visibility),
+            name_of(&LOCATIONS, &binding.location)
+        ))?;
+
+        match binding.size {
+            Some(size) => f.write_fmt(format_args!(", size {size}")),
+            None => Ok(()),
+        }
+    }
+}
+
+struct ItemDisplay<'a>(&'a Item);
+
+impl Display for ItemDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let item = self.0;
+
+        match item.vectorization {
+            1 => f.write_str(elem_name(&item.elem)),
+            vectorization => f.write_fmt(format_args!("{}x{vectorization}", elem_name(&item.elem))),
+        }
+    }
+}
+
+fn elem_name(elem: &Elem) -> &'static str {
+    name_of(&ELEMS, elem)
+}
+
+/// Print a variable as `%{kind}{depth}_{id}:{type}`.
+///
+/// Constants are printed as `{value}:{elem}` and builtins only by their name.
+struct VariableDisplay<'a>(&'a Variable);
+
+impl Display for VariableDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.0 {
+            Variable::GlobalInputArray { id, item } => {
+                f.write_fmt(format_args!("%input{id}:{}", ItemDisplay(item)))
+            }
+            Variable::GlobalOutputArray { id, item } => {
+                f.write_fmt(format_args!("%output{id}:{}", ItemDisplay(item)))
+            }
+            Variable::GlobalScalar { id, elem } => {
+                f.write_fmt(format_args!("%scalar{id}:{}", elem_name(elem)))
+            }
+            Variable::Local { id, item, depth } => {
+                f.write_fmt(format_args!("%local{depth}_{id}:{}", ItemDisplay(item)))
+            }
+            Variable::LocalScalar { id, elem, depth } => f.write_fmt(format_args!(
+                "%local_scalar{depth}_{id}:{}",
+                elem_name(elem)
+            )),
+            Variable::ConstantScalar(value) => match value {
+                ConstantScalarValue::Int(val, _) => f.write_fmt(format_args!("{val}")),
+                ConstantScalarValue::Float(val, _) => f.write_fmt(format_args!("{val:?}")),
+                ConstantScalarValue::UInt(val, _) => f.write_fmt(format_args!("{val}")),
+                ConstantScalarValue::Bool(val) => f.write_fmt(format_args!("{val}")),
+            }
+            .and_then(|_| f.write_fmt(format_args!(":{}", elem_name(&value.elem())))),
+            Variable::SharedMemory { id, item, length } => {
+                f.write_fmt(format_args!("%shared{id}:{}[{length}]", ItemDisplay(item)))
+            }
+            Variable::LocalArray {
+                id,
+                item,
+                depth,
+                length,
+            } => f.write_fmt(format_args!(
+                "%local_array{depth}_{id}:{}[{length}]",
+                ItemDisplay(item)
+            )),
+            Variable::Matrix { id, mat, depth } => f.write_fmt(format_args!(
+                "%matrix{depth}_{id}:matrix({}, {}, {}, {}, {}, {})",
+                name_of(&MATRIX_IDENTS, &mat.ident),
+                mat.m,
+                mat.n,
+                mat.k,
+                elem_name(&mat.elem),
+                name_of(&MATRIX_LAYOUTS, &mat.layout)
+            )),
+            Variable::Slice { id, item, depth } => {
+                f.write_fmt(format_args!("%slice{depth}_{id}:{}", ItemDisplay(item)))
+            }
+            builtin => f.write_fmt(format_args!("%{}", name_of(&BUILTINS, builtin))),
+        }
+    }
+}
+
+impl Display for KernelDefinition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Printer::new(f).kernel(self)
+    }
+}
+
+impl Display for Scope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Printer::new(f).block(format_args!("scope"), self)
+    }
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Printer::new(f).operation(self)
+    }
+}
+
+impl Display for Variable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        VariableDisplay(self).fmt(f)
+    }
+}
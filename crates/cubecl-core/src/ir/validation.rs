@@ -0,0 +1,460 @@
+use super::{
+    Branch, CoopMma, KernelDefinition, Metadata, Operation, Operator, Procedure, Scope, Subcube,
+    Variable, Visibility,
+};
+use std::fmt::Display;
+
+/// Check that the [kernel definition](KernelDefinition) is well formed before it is given to a
+/// compiler.
+///
+/// All errors are collected, each of them pointing at the offending operation.
+pub fn validate(kernel: &KernelDefinition) -> Result<(), Vec<ValidationError>> {
+    let mut validator = Validator {
+        kernel,
+        path: vec![PathSegment::Body],
+        errors: Vec::new(),
+    };
+
+    validator.scope(&kernel.body, false);
+
+    for function in kernel.functions.iter() {
+        validator.path = vec![PathSegment::Function(function.id)];
+        validator.scope(&function.scope, false);
+    }
+
+    match validator.errors.is_empty() {
+        true => Ok(()),
+        false => Err(validator.errors),
+    }
+}
+
+/// An invalid operation found by the [validation](validate).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    /// The path leading to the operation, starting at the kernel body or at a function.
+    pub path: Vec<PathSegment>,
+    /// What is wrong with the operation.
+    pub kind: ValidationErrorKind,
+}
+
+/// A step of the path leading to an operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathSegment {
+    /// The body of the kernel.
+    Body,
+    /// The body of the [function](super::Function) with the given id.
+    Function(u16),
+    /// The operation at the given position in the current scope.
+    Operation(usize),
+    /// The scope executed when the condition is true.
+    Then,
+    /// The scope executed when the condition is false.
+    Else,
+    /// The scope of the switch case at the given position.
+    Case(usize),
+    /// The scope of the default switch case.
+    Default,
+    /// The scope of a loop.
+    Loop,
+}
+
+/// The kind of [validation error](ValidationError).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationErrorKind {
+    /// The input is vectorized differently than the output of an element-wise operation.
+    VectorizationMismatch { input: Variable, out: Variable },
+    /// The variable can't be written.
+    ReadOnlyWrite(Variable),
+    /// A cooperative matrix operation is used on a variable that isn't a matrix.
+    NotAMatrix(Variable),
+    /// A break statement isn't inside a loop.
+    BreakOutsideLoop,
+    /// The called function isn't registered in the kernel.
+    UnknownFunction(u16),
+    /// The arguments of a call don't match the signature of the function.
+    CallMismatch(u16),
+}
+
+struct Validator<'a> {
+    kernel: &'a KernelDefinition,
+    path: Vec<PathSegment>,
+    errors: Vec<ValidationError>,
+}
+
+impl<'a> Validator<'a> {
+    fn scope(&mut self, scope: &Scope, in_loop: bool) {
+        for (position, operation) in scope.operations.iter().enumerate() {
+            self.path.push(PathSegment::Operation(position));
+            self.operation(operation, in_loop);
+            self.path.pop();
+        }
+    }
+
+    fn nested(&mut self, segment: PathSegment, scope: &Scope, in_loop: bool) {
+        self.path.push(segment);
+        self.scope(scope, in_loop);
+        self.path.pop();
+    }
+
+    fn operation(&mut self, operation: &Operation, in_loop: bool) {
+        for out in written_variables(operation) {
+            if self.is_read_only(&out) {
+                self.error(ValidationErrorKind::ReadOnlyWrite(out));
+            }
+        }
+
+        match operation {
+            Operation::Operator(op) => self.operator(op),
+            Operation::Branch(branch) => self.branch(branch, in_loop),
+            Operation::CoopMma(op) => self.coop_mma(op),
+            Operation::Call(call) => match self
+                .kernel
+                .functions
+                .iter()
+                .find(|function| function.id == call.function)
+            {
+                Some(function) => {
+                    if function.inputs.len() != call.inputs.len()
+                        || function.output.is_some() != call.output.is_some()
+                    {
+                        self.error(ValidationErrorKind::CallMismatch(call.function));
+                    }
+                }
+                None => self.error(ValidationErrorKind::UnknownFunction(call.function)),
+            },
+            Operation::Procedure(_)
+            | Operation::Metadata(_)
+            | Operation::Synchronization(_)
+            | Operation::Subcube(_) => {}
+        }
+    }
+
+    fn operator(&mut self, operator: &Operator) {
+        let (inputs, out) = match operator {
+            Operator::Add(op)
+            | Operator::Sub(op)
+            | Operator::Mul(op)
+            | Operator::Div(op)
+            | Operator::Powf(op)
+            | Operator::Atan2(op)
+            | Operator::CopySign(op)
+            | Operator::Hypot(op)
+            | Operator::Equal(op)
+            | Operator::NotEqual(op)
+            | Operator::Lower(op)
+            | Operator::Greater(op)
+            | Operator::LowerEqual(op)
+            | Operator::GreaterEqual(op)
+            | Operator::Modulo(op)
+            | Operator::Remainder(op)
+            | Operator::And(op)
+            | Operator::Or(op)
+            | Operator::Max(op)
+            | Operator::Min(op)
+            | Operator::BitwiseAnd(op)
+            | Operator::BitwiseOr(op)
+            | Operator::BitwiseXor(op)
+            | Operator::ShiftLeft(op)
+            | Operator::ShiftRight(op) => (vec![op.lhs, op.rhs], op.out),
+            Operator::Abs(op)
+            | Operator::Exp(op)
+            | Operator::Log(op)
+            | Operator::Log1p(op)
+            | Operator::Cos(op)
+            | Operator::Sin(op)
+            | Operator::Tanh(op)
+            | Operator::Sqrt(op)
+            | Operator::Floor(op)
+            | Operator::Ceil(op)
+            | Operator::Erf(op)
+            | Operator::Recip(op)
+            | Operator::Tan(op)
+            | Operator::Asin(op)
+            | Operator::Acos(op)
+            | Operator::Atan(op)
+            | Operator::Sinh(op)
+            | Operator::Cosh(op)
+            | Operator::Exp2(op)
+            | Operator::Log2(op)
+            | Operator::Round(op)
+            | Operator::Trunc(op)
+            | Operator::Fract(op)
+            | Operator::Sign(op)
+            | Operator::Rsqrt(op)
+            | Operator::IsNan(op)
+            | Operator::IsInf(op)
+            | Operator::Assign(op)
+            | Operator::Not(op)
+            | Operator::BitwiseNot(op)
+            | Operator::Neg(op)
+            | Operator::CountOnes(op)
+            | Operator::ReverseBits(op)
+            | Operator::LeadingZeros(op)
+            | Operator::TrailingZeros(op) => (vec![op.input], op.out),
+            Operator::Fma(op) => (vec![op.a, op.b, op.c], op.out),
+            Operator::Clamp(op) => (vec![op.input, op.min_value, op.max_value], op.out),
+            // Indexing, slicing, casting bits and atomics don't operate element-wise.
+            _ => return,
+        };
+
+        let vectorization = out.item().vectorization;
+
+        for input in inputs {
+            let input_vectorization = input.item().vectorization;
+
+            // Scalars are broadcasted to every element.
+            if input_vectorization != 1 && input_vectorization != vectorization {
+                self.error(ValidationErrorKind::VectorizationMismatch { input, out });
+            }
+        }
+    }
+
+    fn branch(&mut self, branch: &Branch, in_loop: bool) {
+        match branch {
+            Branch::If(op) => self.nested(PathSegment::Then, &op.scope, in_loop),
+            Branch::IfElse(op) => {
+                self.nested(PathSegment::Then, &op.scope_if, in_loop);
+                self.nested(PathSegment::Else, &op.scope_else, in_loop);
+            }
+            Branch::Switch(op) => {
+                for (position, (_, scope)) in op.cases.iter().enumerate() {
+                    self.nested(PathSegment::Case(position), scope, in_loop);
+                }
+                self.nested(PathSegment::Default, &op.scope_default, in_loop);
+            }
+            Branch::RangeLoop(op) => self.nested(PathSegment::Loop, &op.scope, true),
+            Branch::Loop(op) => self.nested(PathSegment::Loop, &op.scope, true),
+            Branch::Break => {
+                if !in_loop {
+                    self.error(ValidationErrorKind::BreakOutsideLoop);
+                }
+            }
+            Branch::Return => {}
+        }
+    }
+
+    fn coop_mma(&mut self, op: &CoopMma) {
+        let matrices = match op {
+            CoopMma::Fill { mat, .. } => vec![*mat],
+            CoopMma::Load { mat, .. } => vec![*mat],
+            CoopMma::Execute {
+                mat_a,
+                mat_b,
+                mat_c,
+                mat_d,
+            } => vec![*mat_a, *mat_b, *mat_c, *mat_d],
+            CoopMma::Store { mat, .. } => vec![*mat],
+        };
+
+        for mat in matrices {
+            if !matches!(mat, Variable::Matrix { .. }) {
+                self.error(ValidationErrorKind::NotAMatrix(mat));
+            }
+        }
+    }
+
+    fn is_read_only(&self, variable: &Variable) -> bool {
+        match variable {
+            Variable::GlobalInputArray { id, .. } => self
+                .kernel
+                .inputs
+                .get(*id as usize)
+                .map(|binding| binding.visibility == Visibility::Read)
+                .unwrap_or(false),
+            Variable::GlobalOutputArray { .. }
+            | Variable::Local { .. }
+            | Variable::LocalScalar { .. }
+            | Variable::SharedMemory { .. }
+            | Variable::LocalArray { .. }
+            | Variable::Matrix { .. }
+            | Variable::Slice { .. } => false,
+            _ => true,
+        }
+    }
+
+    fn error(&mut self, kind: ValidationErrorKind) {
+        self.errors.push(ValidationError {
+            path: self.path.clone(),
+            kind,
+        });
+    }
+}
+
+/// The variables written by the operation itself, without its nested scopes.
+fn written_variables(operation: &Operation) -> Vec<Variable> {
+    match operation {
+        Operation::Operator(op) => match op {
+            Operator::Add(op)
+            | Operator::Sub(op)
+            | Operator::Mul(op)
+            | Operator::Div(op)
+            | Operator::Powf(op)
+            | Operator::Atan2(op)
+            | Operator::CopySign(op)
+            | Operator::Hypot(op)
+            | Operator::Equal(op)
+            | Operator::NotEqual(op)
+            | Operator::Lower(op)
+            | Operator::Greater(op)
+            | Operator::LowerEqual(op)
+            | Operator::GreaterEqual(op)
+            | Operator::Modulo(op)
+            | Operator::Index(op)
+            | Operator::UncheckedIndex(op)
+            | Operator::IndexAssign(op)
+            | Operator::UncheckedIndexAssign(op)
+            | Operator::And(op)
+            | Operator::Or(op)
+            | Operator::Max(op)
+            | Operator::Min(op)
+            | Operator::BitwiseAnd(op)
+            | Operator::BitwiseOr(op)
+            | Operator::BitwiseXor(op)
+            | Operator::ShiftLeft(op)
+            | Operator::ShiftRight(op)
+            | Operator::Remainder(op)
+            | Operator::AtomicSwap(op)
+            | Operator::AtomicAdd(op)
+            | Operator::AtomicSub(op)
+            | Operator::AtomicMax(op)
+            | Operator::AtomicMin(op)
+            | Operator::AtomicAnd(op)
+            | Operator::AtomicOr(op)
+            | Operator::AtomicXor(op) => vec![op.out],
+            Operator::Abs(op)
+            | Operator::Exp(op)
+            | Operator::Log(op)
+            | Operator::Log1p(op)
+            | Operator::Cos(op)
+            | Operator::Sin(op)
+            | Operator::Tanh(op)
+            | Operator::Sqrt(op)
+            | Operator::Floor(op)
+            | Operator::Ceil(op)
+            | Operator::Erf(op)
+            | Operator::Recip(op)
+            | Operator::Tan(op)
+            | Operator::Asin(op)
+            | Operator::Acos(op)
+            | Operator::Atan(op)
+            | Operator::Sinh(op)
+            | Operator::Cosh(op)
+            | Operator::Exp2(op)
+            | Operator::Log2(op)
+            | Operator::Round(op)
+            | Operator::Trunc(op)
+            | Operator::Fract(op)
+            | Operator::Sign(op)
+            | Operator::Rsqrt(op)
+            | Operator::IsNan(op)
+            | Operator::IsInf(op)
+            | Operator::Assign(op)
+            | Operator::Not(op)
+            | Operator::BitwiseNot(op)
+            | Operator::Neg(op)
+            | Operator::CountOnes(op)
+            | Operator::ReverseBits(op)
+            | Operator::LeadingZeros(op)
+            | Operator::TrailingZeros(op)
+            | Operator::Bitcast(op)
+            | Operator::AtomicLoad(op)
+            | Operator::AtomicStore(op) => vec![op.out],
+            Operator::Fma(op) => vec![op.out],
+            Operator::Clamp(op) => vec![op.out],
+            Operator::Slice(op) => vec![op.out],
+            Operator::AtomicCompareAndSwap(op) => vec![op.out],
+        },
+        Operation::Procedure(proc) => match proc {
+            Procedure::ReadGlobalWithLayout(proc) => proc.outs.clone(),
+            Procedure::IndexOffsetGlobalWithLayout(proc) => proc.indexes.clone(),
+            Procedure::ReadGlobal(proc) => vec![proc.out],
+            Procedure::WriteGlobal(proc) => vec![proc.global],
+            Procedure::CheckedIndex(proc) => vec![proc.out],
+            Procedure::CheckedIndexAssign(proc) => vec![proc.out],
+            Procedure::ConditionalAssign(proc) => vec![proc.out],
+            Procedure::EarlyReturn(_) => vec![],
+        },
+        Operation::Metadata(op) => match op {
+            Metadata::Stride { out, .. }
+            | Metadata::Shape { out, .. }
+            | Metadata::Length { out, .. } => vec![*out],
+        },
+        Operation::Branch(Branch::RangeLoop(op)) => vec![op.i],
+        Operation::Branch(_) | Operation::Synchronization(_) => vec![],
+        Operation::Subcube(op) => match op {
+            Subcube::Elect(op) => vec![op.out],
+            Subcube::Broadcast(op)
+            | Subcube::Shuffle(op)
+            | Subcube::ShuffleXor(op)
+            | Subcube::ShuffleUp(op)
+            | Subcube::ShuffleDown(op) => vec![op.out],
+            Subcube::All(op)
+            | Subcube::Any(op)
+            | Subcube::Sum(op)
+            | Subcube::Prod(op)
+            | Subcube::And(op)
+            | Subcube::Or(op)
+            | Subcube::Xor(op)
+            | Subcube::Min(op)
+            | Subcube::Max(op)
+            | Subcube::InclusiveSum(op)
+            | Subcube::ExclusiveSum(op)
+            | Subcube::InclusiveProd(op)
+            | Subcube::ExclusiveProd(op)
+            | Subcube::Ballot(op) => vec![op.out],
+        },
+        Operation::CoopMma(op) => match op {
+            CoopMma::Store { output, .. } => vec![*output],
+            CoopMma::Fill { .. } | CoopMma::Load { .. } | CoopMma::Execute { .. } => vec![],
+        },
+        Operation::Call(call) => call.output.iter().copied().collect(),
+    }
+}
+
+impl Display for PathSegment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathSegment::Body => f.write_str("body"),
+            PathSegment::Function(id) => f.write_fmt(format_args!("function_{id}")),
+            PathSegment::Operation(position) => f.write_fmt(format_args!("[{position}]")),
+            PathSegment::Then => f.write_str(".then"),
+            PathSegment::Else => f.write_str(".else"),
+            PathSegment::Case(position) => f.write_fmt(format_args!(".case_{position}")),
+            PathSegment::Default => f.write_str(".default"),
+            PathSegment::Loop => f.write_str(".loop"),
+        }
+    }
+}
+
+impl Display for ValidationErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationErrorKind::VectorizationMismatch { input, out } => f.write_fmt(format_args!(
+                "The input {input:?} is vectorized differently than the output {out:?}"
+            )),
+            ValidationErrorKind::ReadOnlyWrite(var) => {
+                f.write_fmt(format_args!("The variable {var:?} is read-only"))
+            }
+            ValidationErrorKind::NotAMatrix(var) => {
+                f.write_fmt(format_args!("The variable {var:?} isn't a matrix"))
+            }
+            ValidationErrorKind::BreakOutsideLoop => f.write_str("Break outside of a loop"),
+            ValidationErrorKind::UnknownFunction(id) => {
+                f.write_fmt(format_args!("The function {id} isn't registered"))
+            }
+            ValidationErrorKind::CallMismatch(id) => f.write_fmt(format_args!(
+                "The call doesn't match the signature of the function {id}"
+            )),
+        }
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for segment in self.path.iter() {
+            segment.fmt(f)?;
+        }
+
+        f.write_fmt(format_args!(": {}", self.kind))
+    }
+}
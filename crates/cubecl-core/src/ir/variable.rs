@@ -1,4 +1,4 @@
-use super::{Elem, FloatKind, IntKind, Item, Matrix};
+use super::{Elem, FloatKind, IntKind, Item, Matrix, UIntKind};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
@@ -79,7 +79,7 @@ pub enum Variable {
 pub enum ConstantScalarValue {
     Int(i64, IntKind),
     Float(f64, FloatKind),
-    UInt(u64),
+    UInt(u64, UIntKind),
     Bool(bool),
 }
 
@@ -89,7 +89,7 @@ impl ConstantScalarValue {
         match self {
             ConstantScalarValue::Int(_, kind) => Elem::Int(*kind),
             ConstantScalarValue::Float(_, kind) => Elem::Float(*kind),
-            ConstantScalarValue::UInt(_) => Elem::UInt,
+            ConstantScalarValue::UInt(_, kind) => Elem::UInt(*kind),
             ConstantScalarValue::Bool(_) => Elem::Bool,
         }
     }
@@ -99,7 +99,7 @@ impl ConstantScalarValue {
     /// It will return [None] if the scalar type is a float or a bool.
     pub fn try_as_usize(&self) -> Option<usize> {
         match self {
-            ConstantScalarValue::UInt(val) => Some(*val as usize),
+            ConstantScalarValue::UInt(val, _) => Some(*val as usize),
             ConstantScalarValue::Int(val, _) => Some(*val as usize),
             ConstantScalarValue::Float(_, _) => None,
             ConstantScalarValue::Bool(_) => None,
@@ -119,7 +119,7 @@ impl ConstantScalarValue {
     /// It will return [None] if the scalar type is a float or a bool.
     pub fn try_as_u32(&self) -> Option<u32> {
         match self {
-            ConstantScalarValue::UInt(val) => Some(*val as u32),
+            ConstantScalarValue::UInt(val, _) => Some(*val as u32),
             ConstantScalarValue::Int(val, _) => Some(*val as u32),
             ConstantScalarValue::Float(_, _) => None,
             ConstantScalarValue::Bool(_) => None,
@@ -139,7 +139,7 @@ impl ConstantScalarValue {
     /// It will return [None] if the scalar type is a float or a bool.
     pub fn try_as_u64(&self) -> Option<u64> {
         match self {
-            ConstantScalarValue::UInt(val) => Some(*val),
+            ConstantScalarValue::UInt(val, _) => Some(*val),
             ConstantScalarValue::Int(val, _) => Some(*val as u64),
             ConstantScalarValue::Float(_, _) => None,
             ConstantScalarValue::Bool(_) => None,
@@ -159,7 +159,7 @@ impl ConstantScalarValue {
     /// It will return [None] if the scalar type is a float or a bool.
     pub fn try_as_i64(&self) -> Option<i64> {
         match self {
-            ConstantScalarValue::UInt(val) => Some(*val as i64),
+            ConstantScalarValue::UInt(val, _) => Some(*val as i64),
             ConstantScalarValue::Int(val, _) => Some(*val),
             ConstantScalarValue::Float(_, _) => None,
             ConstantScalarValue::Bool(_) => None,
@@ -226,28 +226,28 @@ impl Variable {
             Variable::LocalArray { item, .. } => *item,
             Variable::Slice { item, .. } => *item,
             Variable::Matrix { mat, .. } => Item::new(mat.elem),
-            Variable::AbsolutePos => Item::new(Elem::UInt),
-            Variable::Rank => Item::new(Elem::UInt),
-            Variable::UnitPos => Item::new(Elem::UInt),
-            Variable::UnitPosX => Item::new(Elem::UInt),
-            Variable::UnitPosY => Item::new(Elem::UInt),
-            Variable::UnitPosZ => Item::new(Elem::UInt),
-            Variable::CubePosX => Item::new(Elem::UInt),
-            Variable::CubePosY => Item::new(Elem::UInt),
-            Variable::CubePosZ => Item::new(Elem::UInt),
-            Variable::AbsolutePosX => Item::new(Elem::UInt),
-            Variable::AbsolutePosY => Item::new(Elem::UInt),
-            Variable::AbsolutePosZ => Item::new(Elem::UInt),
-            Variable::CubeDimX => Item::new(Elem::UInt),
-            Variable::CubeDimY => Item::new(Elem::UInt),
-            Variable::CubeDimZ => Item::new(Elem::UInt),
-            Variable::CubeCountX => Item::new(Elem::UInt),
-            Variable::CubeCountY => Item::new(Elem::UInt),
-            Variable::CubeCountZ => Item::new(Elem::UInt),
-            Variable::CubePos => Item::new(Elem::UInt),
-            Variable::CubeCount => Item::new(Elem::UInt),
-            Variable::CubeDim => Item::new(Elem::UInt),
-            Variable::SubcubeDim => Item::new(Elem::UInt),
+            Variable::AbsolutePos => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::Rank => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::UnitPos => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::UnitPosX => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::UnitPosY => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::UnitPosZ => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::CubePosX => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::CubePosY => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::CubePosZ => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::AbsolutePosX => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::AbsolutePosY => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::AbsolutePosZ => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::CubeDimX => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::CubeDimY => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::CubeDimZ => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::CubeCountX => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::CubeCountY => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::CubeCountZ => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::CubePos => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::CubeCount => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::CubeDim => Item::new(Elem::UInt(UIntKind::U32)),
+            Variable::SubcubeDim => Item::new(Elem::UInt(UIntKind::U32)),
         }
     }
 }
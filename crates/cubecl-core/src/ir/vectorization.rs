@@ -23,6 +23,9 @@ impl Operation {
             Operation::CoopMma(_) => panic!(
                 "Cooperative matrix-multiply and accumulate doesn't support vectorization."
             ),
+            Operation::Call(_) => panic!(
+                "A call can't be vectorized, since the function signature is already fixed."
+            ),
         }
     }
 }
@@ -52,6 +55,24 @@ impl Operator {
             Operator::Sqrt(op) => Operator::Sqrt(op.vectorize(vectorization)),
             Operator::Erf(op) => Operator::Erf(op.vectorize(vectorization)),
             Operator::Recip(op) => Operator::Recip(op.vectorize(vectorization)),
+            Operator::Tan(op) => Operator::Tan(op.vectorize(vectorization)),
+            Operator::Asin(op) => Operator::Asin(op.vectorize(vectorization)),
+            Operator::Acos(op) => Operator::Acos(op.vectorize(vectorization)),
+            Operator::Atan(op) => Operator::Atan(op.vectorize(vectorization)),
+            Operator::Atan2(op) => Operator::Atan2(op.vectorize(vectorization)),
+            Operator::Sinh(op) => Operator::Sinh(op.vectorize(vectorization)),
+            Operator::Cosh(op) => Operator::Cosh(op.vectorize(vectorization)),
+            Operator::Exp2(op) => Operator::Exp2(op.vectorize(vectorization)),
+            Operator::Log2(op) => Operator::Log2(op.vectorize(vectorization)),
+            Operator::Round(op) => Operator::Round(op.vectorize(vectorization)),
+            Operator::Trunc(op) => Operator::Trunc(op.vectorize(vectorization)),
+            Operator::Fract(op) => Operator::Fract(op.vectorize(vectorization)),
+            Operator::Sign(op) => Operator::Sign(op.vectorize(vectorization)),
+            Operator::CopySign(op) => Operator::CopySign(op.vectorize(vectorization)),
+            Operator::Hypot(op) => Operator::Hypot(op.vectorize(vectorization)),
+            Operator::Rsqrt(op) => Operator::Rsqrt(op.vectorize(vectorization)),
+            Operator::IsNan(op) => Operator::IsNan(op.vectorize(vectorization)),
+            Operator::IsInf(op) => Operator::IsInf(op.vectorize(vectorization)),
             Operator::Equal(op) => Operator::Equal(op.vectorize(vectorization)),
             Operator::NotEqual(op) => Operator::NotEqual(op.vectorize(vectorization)),
             Operator::Lower(op) => Operator::Lower(op.vectorize(vectorization)),
@@ -77,7 +98,14 @@ impl Operator {
             Operator::Or(op) => Operator::Or(op.vectorize(vectorization)),
             Operator::Not(op) => Operator::Not(op.vectorize(vectorization)),
             Operator::BitwiseAnd(op) => Operator::BitwiseAnd(op.vectorize(vectorization)),
+            Operator::BitwiseOr(op) => Operator::BitwiseOr(op.vectorize(vectorization)),
             Operator::BitwiseXor(op) => Operator::BitwiseXor(op.vectorize(vectorization)),
+            Operator::BitwiseNot(op) => Operator::BitwiseNot(op.vectorize(vectorization)),
+            Operator::Neg(op) => Operator::Neg(op.vectorize(vectorization)),
+            Operator::CountOnes(op) => Operator::CountOnes(op.vectorize(vectorization)),
+            Operator::ReverseBits(op) => Operator::ReverseBits(op.vectorize(vectorization)),
+            Operator::LeadingZeros(op) => Operator::LeadingZeros(op.vectorize(vectorization)),
+            Operator::TrailingZeros(op) => Operator::TrailingZeros(op.vectorize(vectorization)),
             Operator::ShiftLeft(op) => Operator::ShiftLeft(op.vectorize(vectorization)),
             Operator::ShiftRight(op) => Operator::ShiftRight(op.vectorize(vectorization)),
             Operator::Remainder(op) => Operator::Remainder(op.vectorize(vectorization)),
@@ -173,6 +201,17 @@ impl Subcube {
             Subcube::Xor(op) => Subcube::Xor(op.vectorize(vectorization)),
             Subcube::Min(op) => Subcube::Min(op.vectorize(vectorization)),
             Subcube::Max(op) => Subcube::Max(op.vectorize(vectorization)),
+            Subcube::Shuffle(op) => Subcube::Shuffle(op.vectorize(vectorization)),
+            Subcube::ShuffleXor(op) => Subcube::ShuffleXor(op.vectorize(vectorization)),
+            Subcube::ShuffleUp(op) => Subcube::ShuffleUp(op.vectorize(vectorization)),
+            Subcube::ShuffleDown(op) => Subcube::ShuffleDown(op.vectorize(vectorization)),
+            Subcube::InclusiveSum(op) => Subcube::InclusiveSum(op.vectorize(vectorization)),
+            Subcube::ExclusiveSum(op) => Subcube::ExclusiveSum(op.vectorize(vectorization)),
+            Subcube::InclusiveProd(op) => Subcube::InclusiveProd(op.vectorize(vectorization)),
+            Subcube::ExclusiveProd(op) => Subcube::ExclusiveProd(op.vectorize(vectorization)),
+            Subcube::Ballot(_) => {
+                panic!("A ballot can't be vectorized, since its output is a bitmask of fixed size.")
+            }
         }
     }
 }
@@ -98,3 +98,7 @@ pub type RuntimeArg<'a, T, R> = <T as LaunchArg>::RuntimeArg<'a, R>;
 #[cfg(feature = "export_tests")]
 /// Tests only useful for runtimes.
 pub mod runtime_tests;
+
+#[cfg(feature = "export_tests")]
+/// Tests only useful for compilers.
+pub mod compiler_tests;
@@ -1,4 +1,4 @@
-use crate::ir::{Elem, FloatKind, IntKind};
+use crate::ir::{Elem, FloatKind, IntKind, UIntKind};
 
 /// The base element trait for the jit backend.
 pub trait CubeElement: core::fmt::Debug + Send + Sync + 'static + Clone + bytemuck::Pod {
@@ -27,7 +27,7 @@ impl CubeElement for u32 {
         bytemuck::cast_slice(bytes)
     }
     fn cube_elem() -> Elem {
-        Elem::UInt
+        Elem::UInt(UIntKind::U32)
     }
     fn maximum_value() -> Self {
         u32::MAX
@@ -37,6 +37,111 @@ impl CubeElement for u32 {
     }
 }
 
+impl CubeElement for u8 {
+    fn type_name() -> &'static str {
+        "u8"
+    }
+    fn as_bytes(slice: &[Self]) -> &[u8] {
+        bytemuck::cast_slice(slice)
+    }
+    fn from_bytes(bytes: &[u8]) -> &[Self] {
+        bytemuck::cast_slice(bytes)
+    }
+    fn cube_elem() -> Elem {
+        Elem::UInt(UIntKind::U8)
+    }
+    fn maximum_value() -> Self {
+        u8::MAX
+    }
+    fn minimum_value() -> Self {
+        u8::MIN
+    }
+}
+
+impl CubeElement for u16 {
+    fn type_name() -> &'static str {
+        "u16"
+    }
+    fn as_bytes(slice: &[Self]) -> &[u8] {
+        bytemuck::cast_slice(slice)
+    }
+    fn from_bytes(bytes: &[u8]) -> &[Self] {
+        bytemuck::cast_slice(bytes)
+    }
+    fn cube_elem() -> Elem {
+        Elem::UInt(UIntKind::U16)
+    }
+    fn maximum_value() -> Self {
+        u16::MAX
+    }
+    fn minimum_value() -> Self {
+        u16::MIN
+    }
+}
+
+impl CubeElement for u64 {
+    fn type_name() -> &'static str {
+        "u64"
+    }
+    fn as_bytes(slice: &[Self]) -> &[u8] {
+        bytemuck::cast_slice(slice)
+    }
+    fn from_bytes(bytes: &[u8]) -> &[Self] {
+        bytemuck::cast_slice(bytes)
+    }
+    fn cube_elem() -> Elem {
+        Elem::UInt(UIntKind::U64)
+    }
+    fn maximum_value() -> Self {
+        u64::MAX
+    }
+    fn minimum_value() -> Self {
+        u64::MIN
+    }
+}
+
+impl CubeElement for i8 {
+    fn type_name() -> &'static str {
+        "i8"
+    }
+    fn as_bytes(slice: &[Self]) -> &[u8] {
+        bytemuck::cast_slice(slice)
+    }
+    fn from_bytes(bytes: &[u8]) -> &[Self] {
+        bytemuck::cast_slice(bytes)
+    }
+    fn cube_elem() -> Elem {
+        Elem::Int(IntKind::I8)
+    }
+    fn maximum_value() -> Self {
+        i8::MAX
+    }
+    fn minimum_value() -> Self {
+        i8::MIN
+    }
+}
+
+impl CubeElement for i16 {
+    fn type_name() -> &'static str {
+        "i16"
+    }
+    fn as_bytes(slice: &[Self]) -> &[u8] {
+        bytemuck::cast_slice(slice)
+    }
+    fn from_bytes(bytes: &[u8]) -> &[Self] {
+        bytemuck::cast_slice(bytes)
+    }
+    fn cube_elem() -> Elem {
+        Elem::Int(IntKind::I16)
+    }
+    fn maximum_value() -> Self {
+        i16::MAX
+    }
+    fn minimum_value() -> Self {
+        i16::MIN
+    }
+}
+
 impl CubeElement for i32 {
     fn type_name() -> &'static str {
         "i32"
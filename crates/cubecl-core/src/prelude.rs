@@ -11,8 +11,8 @@ pub use crate::runtime::Runtime;
 
 /// Elements
 pub use crate::frontend::{
-    Array, ArrayHandleRef, AtomicI32, AtomicI64, AtomicUInt, Bool, Float, LaunchArg, Slice,
-    SliceMut, Tensor, TensorArg, UInt, F16, F32, F64, I32, I64,
+    Array, ArrayHandleRef, AtomicF16, AtomicF32, AtomicF64, AtomicI32, AtomicI64, AtomicUInt, Bool,
+    Float, LaunchArg, Slice, SliceMut, Tensor, TensorArg, UInt, F16, F32, F64, I32, I64,
 };
 pub use crate::pod::CubeElement;
 
@@ -1,7 +1,7 @@
 use crate::{
     codegen::Compiler,
     compute::{CubeCount, CubeTask},
-    ir::Elem,
+    ir::{Elem, FloatKind},
 };
 use cubecl_runtime::{channel::ComputeChannel, client::ComputeClient, server::ComputeServer};
 
@@ -65,6 +65,19 @@ impl FeatureSet {
     pub fn register(&mut self, feature: Feature) -> bool {
         self.set.insert(feature)
     }
+
+    /// Register the [cmma features](Feature::Cmma) of the usual shapes for the given element
+    /// types `(a, b, c)`, when the compiler [emulates](crate::ir::KernelDefinition::emulate_cmma) the
+    /// cooperative matrix operations.
+    ///
+    /// This should only be used by a [runtime](Runtime) when initializing a device.
+    pub fn register_emulated_cmma(&mut self, elems: &[(Elem, Elem, Elem)]) {
+        for (a, b, c) in elems.iter().copied() {
+            for (m, k, n) in [(16, 16, 16), (32, 8, 16), (8, 32, 16)] {
+                self.register(Feature::Cmma { a, b, c, m, k, n });
+            }
+        }
+    }
 }
 
 /// Every feature that can be supported by a [cube runtime](Runtime).
@@ -81,4 +94,7 @@ pub enum Feature {
         k: u8,
         n: u8,
     },
+    /// The atomic float feature enables the atomic operations on floats of the given kind,
+    /// except the bitwise ones.
+    AtomicFloat(FloatKind),
 }
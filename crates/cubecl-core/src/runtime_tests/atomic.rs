@@ -0,0 +1,51 @@
+use crate as cubecl;
+use crate::{ir::FloatKind, Feature};
+use cubecl::prelude::*;
+
+#[cube(launch)]
+pub fn kernel_atomic_float(output: &mut Array<AtomicF32>) {
+    let value = F32::cast_from(UNIT_POS);
+
+    AtomicF32::add(&output[0], F32::new(0.5));
+    AtomicF32::max(&output[1], value);
+    AtomicF32::min(&output[2], value);
+    AtomicF32::sub(&output[3], F32::new(0.25));
+}
+
+pub fn test_kernel_atomic_float<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    if !client
+        .features()
+        .enabled(Feature::AtomicFloat(FloatKind::F32))
+    {
+        // Can't execute the test.
+        return;
+    }
+
+    let output = client.create(f32::as_bytes(&[1.0, -1.0, 100.0, 0.0]));
+
+    kernel_atomic_float::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(32, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&output, 4, 1) },
+    );
+
+    let actual = client.read(output.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, &[17.0, 31.0, 0.0, -8.0]);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_atomic {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_atomic_float() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::atomic::test_kernel_atomic_float::<TestRuntime>(client);
+        }
+    };
+}
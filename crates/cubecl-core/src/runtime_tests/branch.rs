@@ -0,0 +1,101 @@
+use crate as cubecl;
+
+use cubecl::prelude::*;
+
+#[cube(launch)]
+pub fn kernel_switch(
+    ops: &Array<UInt>,
+    lhs: &Array<F32>,
+    rhs: &Array<F32>,
+    output: &mut Array<F32>,
+) {
+    if ABSOLUTE_POS < output.len() {
+        let a = lhs[ABSOLUTE_POS];
+        let b = rhs[ABSOLUTE_POS];
+
+        match ops[ABSOLUTE_POS] {
+            0 => output[ABSOLUTE_POS] = a + b,
+            1 => output[ABSOLUTE_POS] = a - b,
+            2 | 3 => output[ABSOLUTE_POS] = a * b,
+            _ => output[ABSOLUTE_POS] = F32::new(-1.0),
+        }
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_switch_break(input: &Array<I32>, output: &mut Array<UInt>) {
+    if UNIT_POS == 0 {
+        let mut count = UInt::new(0);
+
+        for i in range(0u32, input.len(), Comptime::new(false)) {
+            match input[i] {
+                -1 => break,
+                0 => {}
+                _ => count += UInt::new(1),
+            }
+        }
+
+        output[0] = count;
+    }
+}
+
+pub fn test_kernel_switch<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let ops = client.create(u32::as_bytes(&[0, 1, 2, 3, 4]));
+    let lhs = client.create(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0, 5.0]));
+    let rhs = client.create(f32::as_bytes(&[5.0, 4.0, 3.0, 2.0, 1.0]));
+    let output = client.empty(5 * core::mem::size_of::<f32>());
+
+    kernel_switch::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(8, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&ops, 5, 1) },
+        unsafe { ArrayArg::from_raw_parts(&lhs, 5, 1) },
+        unsafe { ArrayArg::from_raw_parts(&rhs, 5, 1) },
+        unsafe { ArrayArg::from_raw_parts(&output, 5, 1) },
+    );
+
+    let actual = client.read(output.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(&actual[0..5], &[6.0, -2.0, 9.0, 8.0, -1.0]);
+}
+
+pub fn test_kernel_switch_break<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let input = client.create(i32::as_bytes(&[3, 0, 5, 0, 7, -1, 9, 2]));
+    let output = client.empty(core::mem::size_of::<u32>());
+
+    kernel_switch_break::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts(&input, 8, 1) },
+        unsafe { ArrayArg::from_raw_parts(&output, 1, 1) },
+    );
+
+    let actual = client.read(output.binding());
+    let actual = u32::from_bytes(&actual);
+
+    // The break exits the loop, not only the match.
+    assert_eq!(actual[0], 3);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_branch {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_switch() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::branch::test_kernel_switch::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_switch_break() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::branch::test_kernel_switch_break::<TestRuntime>(client);
+        }
+    };
+}
@@ -46,6 +46,14 @@ pub fn kernel_simple_1(lhs: &Array<F16>, rhs: &Array<F16>, out: &mut Array<F32>)
 }
 
 pub fn test_simple_1<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    test_simple_1_cube_dim::<R>(client, CubeDim::new(16, 16, 1));
+}
+
+/// Same as [test_simple_1], launched with the given cube dimensions.
+pub fn test_simple_1_cube_dim<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+    cube_dim: CubeDim,
+) {
     if !client.features().enabled(Feature::Cmma {
         a: Elem::Float(FloatKind::F16),
         b: Elem::Float(FloatKind::F16),
@@ -69,7 +77,7 @@ pub fn test_simple_1<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
         kernel_simple_1::launch::<R>(
             &client,
             CubeCount::Static(1, 1, 1),
-            CubeDim::new(16, 16, 1),
+            cube_dim,
             ArrayArg::from_raw_parts(&lhs, 256, 1),
             ArrayArg::from_raw_parts(&rhs, 256, 1),
             ArrayArg::from_raw_parts(&out, 256, 1),
@@ -0,0 +1,127 @@
+use crate as cubecl;
+
+use cubecl::prelude::*;
+
+#[cube(launch)]
+pub fn kernel_float_math(input: &Array<F32>, output: &mut Array<F32>) {
+    if UNIT_POS == 0 {
+        let x = input[0];
+        let y = input[1];
+        output[0] = F32::tan(x);
+        output[1] = F32::asin(x);
+        output[2] = F32::acos(x);
+        output[3] = F32::atan(x);
+        output[4] = F32::atan2(x, y);
+        output[5] = F32::sinh(x);
+        output[6] = F32::cosh(x);
+        output[7] = F32::exp2(x);
+        output[8] = F32::log2(x);
+        output[9] = F32::round(y);
+        output[10] = F32::trunc(y);
+        output[11] = F32::fract(y);
+        output[12] = F32::sign(y);
+        output[13] = F32::copysign(x, y);
+        output[14] = F32::hypot(x, y);
+        output[15] = F32::rsqrt(x);
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_float_predicates(input: &Array<F32>, output: &mut Array<UInt>) {
+    if ABSOLUTE_POS < input.len() {
+        let x = input[ABSOLUTE_POS];
+        output[ABSOLUTE_POS] = UInt::new(0);
+
+        if F32::is_nan(x) {
+            output[ABSOLUTE_POS] = UInt::new(1);
+        }
+        if F32::is_inf(x) {
+            output[ABSOLUTE_POS] = UInt::new(2);
+        }
+    }
+}
+
+pub fn test_kernel_float_math<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let (x, y) = (0.5f32, -2.5f32);
+    let input = client.create(f32::as_bytes(&[x, y]));
+    let output = client.empty(16 * core::mem::size_of::<f32>());
+
+    kernel_float_math::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts(&input, 2, 1) },
+        unsafe { ArrayArg::from_raw_parts(&output, 16, 1) },
+    );
+
+    let actual = client.read(output.binding());
+    let actual = f32::from_bytes(&actual);
+    let expected = [
+        x.tan(),
+        x.asin(),
+        x.acos(),
+        x.atan(),
+        x.atan2(y),
+        x.sinh(),
+        x.cosh(),
+        x.exp2(),
+        x.log2(),
+        -3.0,
+        -2.0,
+        -0.5,
+        -1.0,
+        -0.5,
+        x.hypot(y),
+        1.0 / x.sqrt(),
+    ];
+
+    for (i, (actual, expected)) in actual.iter().zip(expected).enumerate() {
+        assert!(
+            (actual - expected).abs() < 1e-5,
+            "Function {i}: expected {expected}, got {actual}"
+        );
+    }
+}
+
+pub fn test_kernel_float_predicates<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let input = client.create(f32::as_bytes(&[
+        1.0,
+        f32::NAN,
+        f32::INFINITY,
+        f32::NEG_INFINITY,
+    ]));
+    let output = client.empty(4 * core::mem::size_of::<u32>());
+
+    kernel_float_predicates::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(4, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&input, 4, 1) },
+        unsafe { ArrayArg::from_raw_parts(&output, 4, 1) },
+    );
+
+    let actual = client.read(output.binding());
+    let actual = u32::from_bytes(&actual);
+
+    assert_eq!(actual, &[0, 1, 2, 2]);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_float {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_float_math() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::float::test_kernel_float_math::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_float_predicates() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::float::test_kernel_float_predicates::<TestRuntime>(client);
+        }
+    };
+}
@@ -0,0 +1,63 @@
+use crate as cubecl;
+
+use cubecl::prelude::*;
+
+#[cube(noinline)]
+pub fn polynomial(x: F32, a: F32, b: F32) -> F32 {
+    x * x * a + b
+}
+
+#[cube(noinline)]
+pub fn square_minus_one(x: F32) -> F32 {
+    polynomial(x, F32::new(1.0), F32::new(-1.0))
+}
+
+#[cube(noinline)]
+pub fn write_position(output: &mut Array<F32>, value: F32) {
+    output[ABSOLUTE_POS] = value;
+}
+
+#[cube(launch)]
+pub fn kernel_function_call(input: &Array<F32>, output: &mut Array<F32>) {
+    if ABSOLUTE_POS < output.len() {
+        let x = input[ABSOLUTE_POS];
+        let y = polynomial(x, F32::new(2.0), F32::new(1.0));
+        write_position(output, square_minus_one(y));
+    }
+}
+
+pub fn test_kernel_function_call<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let input = client.create(f32::as_bytes(&[0.0, 1.0, -2.0, 0.5]));
+    let output = client.empty(4 * core::mem::size_of::<f32>());
+
+    kernel_function_call::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(8, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&input, 4, 1) },
+        unsafe { ArrayArg::from_raw_parts(&output, 4, 1) },
+    );
+
+    let actual = client.read(output.binding());
+    let actual = f32::from_bytes(&actual);
+
+    let expected = [0.0f32, 1.0, -2.0, 0.5].map(|x| {
+        let y = 2.0 * x * x + 1.0;
+        y * y - 1.0
+    });
+    assert_eq!(&actual[0..4], &expected);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_function {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_function_call() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::function::test_kernel_function_call::<TestRuntime>(client);
+        }
+    };
+}
@@ -0,0 +1,283 @@
+use crate as cubecl;
+
+use cubecl::prelude::*;
+
+#[cube(launch)]
+pub fn kernel_add_u8(lhs: &Array<U8>, rhs: &Array<U8>, output: &mut Array<U8>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = lhs[ABSOLUTE_POS] + rhs[ABSOLUTE_POS];
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_scale_i16(input: &Array<I16>, factor: I16, output: &mut Array<I16>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] * factor;
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_arith_u64(
+    lhs: &Array<U64>,
+    rhs: &Array<U64>,
+    sum: &mut Array<U64>,
+    difference: &mut Array<U64>,
+    product: &mut Array<U64>,
+) {
+    if ABSOLUTE_POS < sum.len() {
+        let x = lhs[ABSOLUTE_POS];
+        let y = rhs[ABSOLUTE_POS];
+        sum[ABSOLUTE_POS] = x + y;
+        difference[ABSOLUTE_POS] = x - y;
+        product[ABSOLUTE_POS] = x * y;
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_signed_i64(lhs: &Array<I64>, rhs: &Array<I64>, output: &mut Array<I64>) {
+    if ABSOLUTE_POS < lhs.len() {
+        let x = lhs[ABSOLUTE_POS];
+        let y = rhs[ABSOLUTE_POS];
+        let position = ABSOLUTE_POS * UInt::new(3);
+        output[position] = -x;
+        output[position + UInt::new(1)] = x * y;
+        output[position + UInt::new(2)] = Max::max(x, y);
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_bit_ops_i8(input: &Array<I8>, output: &mut Array<I8>) {
+    if UNIT_POS == 0 {
+        let x = input[0];
+        output[0] = x | input[1];
+        output[1] = !x;
+        output[2] = -x;
+        output[3] = I8::count_ones(x);
+        output[4] = I8::leading_zeros(x);
+        output[5] = I8::trailing_zeros(x);
+        output[6] = I8::reverse_bits(x);
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_bit_ops_u32(input: &Array<UInt>, output: &mut Array<UInt>) {
+    if UNIT_POS == 0 {
+        let x = input[0];
+        output[0] = x | input[1];
+        output[1] = !x;
+        output[2] = UInt::count_ones(x);
+        output[3] = UInt::leading_zeros(x);
+        output[4] = UInt::trailing_zeros(x);
+        output[5] = UInt::reverse_bits(x);
+    }
+}
+
+pub fn test_kernel_add_u8<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let lhs = client.create(u8::as_bytes(&[1, 2, 200, 255, 7]));
+    let rhs = client.create(u8::as_bytes(&[3, 4, 100, 1, 8]));
+    let output = client.empty(5);
+
+    kernel_add_u8::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(8, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&lhs, 5, 1) },
+        unsafe { ArrayArg::from_raw_parts(&rhs, 5, 1) },
+        unsafe { ArrayArg::from_raw_parts(&output, 5, 1) },
+    );
+
+    let actual = client.read(output.binding());
+    let actual = u8::from_bytes(&actual);
+
+    // The addition wraps around like the primitive type.
+    assert_eq!(actual, &[4, 6, 44, 0, 15]);
+}
+
+pub fn test_kernel_scale_i16<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let input = client.create(i16::as_bytes(&[1, -2, 300, 20000]));
+    let output = client.empty(4 * core::mem::size_of::<i16>());
+
+    kernel_scale_i16::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(4, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&input, 4, 1) },
+        ScalarArg::new(-3),
+        unsafe { ArrayArg::from_raw_parts(&output, 4, 1) },
+    );
+
+    let actual = client.read(output.binding());
+    let actual = i16::from_bytes(&actual);
+
+    assert_eq!(actual, &[-3, 6, -900, 5536]);
+}
+
+pub fn test_kernel_arith_u64<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    // The values carry and borrow between the low and high 32 bits.
+    let lhs = [u32::MAX as u64, 1 << 32, 0x1234_5678_9abc_def0, 3, u64::MAX];
+    let rhs = [1, 1, 0xfedc_ba98_7654_3210, 5, u64::MAX];
+    let size = lhs.len() * core::mem::size_of::<u64>();
+
+    let lhs_handle = client.create(u64::as_bytes(&lhs));
+    let rhs_handle = client.create(u64::as_bytes(&rhs));
+    let sum = client.empty(size);
+    let difference = client.empty(size);
+    let product = client.empty(size);
+
+    kernel_arith_u64::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(8, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&lhs_handle, 5, 1) },
+        unsafe { ArrayArg::from_raw_parts(&rhs_handle, 5, 1) },
+        unsafe { ArrayArg::from_raw_parts(&sum, 5, 1) },
+        unsafe { ArrayArg::from_raw_parts(&difference, 5, 1) },
+        unsafe { ArrayArg::from_raw_parts(&product, 5, 1) },
+    );
+
+    let expected = |op: fn(u64, u64) -> u64| {
+        lhs.iter()
+            .zip(rhs.iter())
+            .map(|(lhs, rhs)| op(*lhs, *rhs))
+            .collect::<Vec<_>>()
+    };
+
+    let actual = client.read(sum.binding());
+    assert_eq!(u64::from_bytes(&actual), expected(u64::wrapping_add));
+    let actual = client.read(difference.binding());
+    assert_eq!(u64::from_bytes(&actual), expected(u64::wrapping_sub));
+    let actual = client.read(product.binding());
+    assert_eq!(u64::from_bytes(&actual), expected(u64::wrapping_mul));
+}
+
+pub fn test_kernel_signed_i64<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let lhs: [i64; 4] = [-1, 1 << 40, -(1 << 33) - 7, 5];
+    let rhs: [i64; 4] = [2, -3, 1 << 20, -5];
+    // The values are transferred as their bits, since i64 isn't a `CubeElement`.
+    let bits = |values: [i64; 4]| values.map(|value| value as u64);
+
+    let lhs_handle = client.create(u64::as_bytes(&bits(lhs)));
+    let rhs_handle = client.create(u64::as_bytes(&bits(rhs)));
+    let output = client.empty(3 * lhs.len() * core::mem::size_of::<i64>());
+
+    kernel_signed_i64::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(4, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&lhs_handle, 4, 1) },
+        unsafe { ArrayArg::from_raw_parts(&rhs_handle, 4, 1) },
+        unsafe { ArrayArg::from_raw_parts(&output, 12, 1) },
+    );
+
+    let actual = client.read(output.binding());
+    let actual = u64::from_bytes(&actual)
+        .iter()
+        .map(|value| *value as i64)
+        .collect::<Vec<_>>();
+    let expected = lhs
+        .iter()
+        .zip(rhs.iter())
+        .flat_map(|(lhs, rhs)| [-lhs, lhs.wrapping_mul(*rhs), *lhs.max(rhs)])
+        .collect::<Vec<_>>();
+
+    assert_eq!(actual, expected);
+}
+
+pub fn test_kernel_bit_ops_i8<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let input = client.create(i8::as_bytes(&[-96, 5]));
+    let output = client.empty(7);
+
+    kernel_bit_ops_i8::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts(&input, 2, 1) },
+        unsafe { ArrayArg::from_raw_parts(&output, 7, 1) },
+    );
+
+    let actual = client.read(output.binding());
+    let actual = i8::from_bytes(&actual);
+
+    let x = -96i8;
+    let expected = [
+        x | 5,
+        !x,
+        -x,
+        x.count_ones() as i8,
+        x.leading_zeros() as i8,
+        x.trailing_zeros() as i8,
+        x.reverse_bits(),
+    ];
+    assert_eq!(actual, &expected);
+}
+
+pub fn test_kernel_bit_ops_u32<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let input = client.create(u32::as_bytes(&[12, 3]));
+    let output = client.empty(6 * core::mem::size_of::<u32>());
+
+    kernel_bit_ops_u32::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts(&input, 2, 1) },
+        unsafe { ArrayArg::from_raw_parts(&output, 6, 1) },
+    );
+
+    let actual = client.read(output.binding());
+    let actual = u32::from_bytes(&actual);
+
+    let x = 12u32;
+    let expected = [
+        x | 3,
+        !x,
+        x.count_ones(),
+        x.leading_zeros(),
+        x.trailing_zeros(),
+        x.reverse_bits(),
+    ];
+    assert_eq!(actual, &expected);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_int {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_add_u8() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::int::test_kernel_add_u8::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_scale_i16() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::int::test_kernel_scale_i16::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_arith_u64() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::int::test_kernel_arith_u64::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_signed_i64() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::int::test_kernel_signed_i64::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_bit_ops_i8() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::int::test_kernel_bit_ops_i8::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_bit_ops_u32() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::int::test_kernel_bit_ops_u32::<TestRuntime>(client);
+        }
+    };
+}
@@ -1,5 +1,10 @@
 pub mod assign;
+pub mod atomic;
+pub mod branch;
 pub mod cmma;
+pub mod float;
+pub mod function;
+pub mod int;
 pub mod launch;
 pub mod sequence;
 pub mod slice;
@@ -19,5 +24,10 @@ macro_rules! testgen_all {
         cubecl_core::testgen_assign!();
         cubecl_core::testgen_topology!();
         cubecl_core::testgen_sequence!();
+        cubecl_core::testgen_int!();
+        cubecl_core::testgen_float!();
+        cubecl_core::testgen_atomic!();
+        cubecl_core::testgen_branch!();
+        cubecl_core::testgen_function!();
     };
 }
@@ -42,6 +42,24 @@ pub fn kernel_min<F: Float>(output: &mut Tensor<F>) {
     }
 }
 
+#[cube(launch)]
+pub fn kernel_shuffle_xor<F: Float>(output: &mut Tensor<F>) {
+    let val = output[UNIT_POS];
+    output[UNIT_POS] = subcube_shuffle_xor::<F>(val, UInt::new(1));
+}
+
+#[cube(launch)]
+pub fn kernel_inclusive_sum<F: Float>(output: &mut Tensor<F>) {
+    let val = output[UNIT_POS];
+    output[UNIT_POS] = subcube_inclusive_sum::<F>(val);
+}
+
+#[cube(launch)]
+pub fn kernel_exclusive_sum<F: Float>(output: &mut Tensor<F>) {
+    let val = output[UNIT_POS];
+    output[UNIT_POS] = subcube_exclusive_sum::<F>(val);
+}
+
 pub fn test_subcube_sum<TestRuntime: Runtime>(
     client: ComputeClient<TestRuntime::Server, TestRuntime::Channel>,
 ) {
@@ -93,6 +111,45 @@ pub fn test_subcube_min<TestRuntime: Runtime>(
     );
 }
 
+pub fn test_subcube_shuffle_xor<TestRuntime: Runtime>(
+    client: ComputeClient<TestRuntime::Server, TestRuntime::Channel>,
+) {
+    test_subcube_operation::<TestRuntime, _>(
+        &[4.0, 5.0, 7.0, 1.0],
+        &[5.0, 4.0, 1.0, 7.0],
+        client.clone(),
+        |cube_dim, settings, handle| {
+            kernel_shuffle_xor::launch::<F32, TestRuntime>(&client, cube_dim, settings, handle)
+        },
+    );
+}
+
+pub fn test_subcube_inclusive_sum<TestRuntime: Runtime>(
+    client: ComputeClient<TestRuntime::Server, TestRuntime::Channel>,
+) {
+    test_subcube_operation::<TestRuntime, _>(
+        &[4.0, 5.0, 7.0, 1.0],
+        &[4.0, 9.0, 16.0, 17.0],
+        client.clone(),
+        |cube_dim, settings, handle| {
+            kernel_inclusive_sum::launch::<F32, TestRuntime>(&client, cube_dim, settings, handle)
+        },
+    );
+}
+
+pub fn test_subcube_exclusive_sum<TestRuntime: Runtime>(
+    client: ComputeClient<TestRuntime::Server, TestRuntime::Channel>,
+) {
+    test_subcube_operation::<TestRuntime, _>(
+        &[4.0, 5.0, 7.0, 1.0],
+        &[0.0, 4.0, 9.0, 16.0],
+        client.clone(),
+        |cube_dim, settings, handle| {
+            kernel_exclusive_sum::launch::<F32, TestRuntime>(&client, cube_dim, settings, handle)
+        },
+    );
+}
+
 fn test_subcube_operation<TestRuntime: Runtime, Launch>(
     input: &[f32],
     expected: &[f32],
@@ -152,5 +209,23 @@ macro_rules! testgen_subcube {
             let client = TestRuntime::client(&Default::default());
             cubecl_core::runtime_tests::subcube::test_subcube_max::<TestRuntime>(client);
         }
+
+        #[test]
+        fn test_subcube_shuffle_xor() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::subcube::test_subcube_shuffle_xor::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_subcube_inclusive_sum() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::subcube::test_subcube_inclusive_sum::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_subcube_exclusive_sum() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::subcube::test_subcube_exclusive_sum::<TestRuntime>(client);
+        }
     };
 }
@@ -0,0 +1,108 @@
+use cubecl_core::{
+    cpa,
+    ir::{
+        Branch, CmmaEmulationError, CoopMma, CubeDim, Elem, FloatKind, Item, KernelDefinition,
+        Matrix, MatrixIdent, MatrixLayout, Operation, Scope, Synchronization, Variable,
+    },
+    prelude::{KernelBuilder, KernelSettings},
+};
+
+fn float() -> Item {
+    Item::new(Elem::Float(FloatKind::F32))
+}
+
+/// A kernel filling an accumulator and storing it to the output in the given function.
+fn kernel<F: Fn(&mut Scope, Variable, Variable)>(cube_dim: CubeDim, store: F) -> KernelDefinition {
+    let mut builder = KernelBuilder::default();
+    let output = builder.output_array(float());
+
+    {
+        let mut scope = builder.context.scope.borrow_mut();
+        let mat = scope.create_matrix(Matrix {
+            ident: MatrixIdent::Accumulator,
+            m: 16,
+            n: 16,
+            k: 16,
+            elem: Elem::Float(FloatKind::F32),
+            layout: MatrixLayout::Undefined,
+        });
+        let value: Variable = 0.0f32.into();
+        scope.register(Operation::CoopMma(CoopMma::Fill { mat, value }));
+
+        store(&mut scope, mat, *output);
+    }
+
+    builder.build(KernelSettings::default().cube_dim(cube_dim))
+}
+
+fn register_store(scope: &mut Scope, mat: Variable, output: Variable) {
+    scope.register(Operation::CoopMma(CoopMma::Store {
+        output,
+        mat,
+        stride: 16u32.into(),
+        layout: MatrixLayout::RowMajor,
+    }));
+}
+
+fn is_barrier(operation: &Operation) -> bool {
+    matches!(
+        operation,
+        Operation::Synchronization(Synchronization::SyncUnits)
+    )
+}
+
+#[test]
+fn emulate_cmma_small_cube_test() {
+    let mut kernel = kernel(CubeDim::new(8, 2, 1), register_store);
+
+    assert_eq!(kernel.emulate_cmma(), Ok(()));
+    // The 16 units of the cube share the elements of the accumulator.
+    let loops = kernel
+        .body
+        .operations
+        .iter()
+        .filter_map(|operation| match operation {
+            Operation::Branch(Branch::RangeLoop(op)) => Some(op.step),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert!(!loops.is_empty());
+    assert!(loops.iter().all(|step| *step == Some(16u32.into())));
+}
+
+#[test]
+fn emulate_cmma_partial_warp_test() {
+    let mut kernel = kernel(CubeDim::new(48, 1, 1), register_store);
+
+    assert_eq!(
+        kernel.emulate_cmma(),
+        Err(CmmaEmulationError::CubeDim(CubeDim::new(48, 1, 1)))
+    );
+}
+
+#[test]
+fn emulate_cmma_barrier_outside_branch_test() {
+    let mut kernel = kernel(CubeDim::new(32, 2, 1), |scope, mat, output| {
+        let cond = scope.create_local(Item::new(Elem::Bool));
+        let unit_pos = Variable::UnitPos;
+        cpa!(scope, cond = unit_pos < 16u32);
+        cpa!(scope, if(cond).then(|scope| {
+            register_store(scope, mat, output);
+        }));
+    });
+
+    assert_eq!(kernel.emulate_cmma(), Ok(()));
+
+    let operations = &kernel.body.operations;
+    let position = operations
+        .iter()
+        .position(|operation| matches!(operation, Operation::Branch(Branch::If(_))))
+        .unwrap();
+    let Operation::Branch(Branch::If(branch)) = &operations[position] else {
+        unreachable!();
+    };
+
+    assert!(is_barrier(&operations[position - 1]));
+    assert!(is_barrier(&operations[position + 1]));
+    assert!(!branch.scope.operations.iter().any(is_barrier));
+}
@@ -0,0 +1,13 @@
+use cubecl_core as cubecl;
+use cubecl::prelude::*;
+
+#[cube]
+fn match_duplicate_arm(value: UInt) {
+    match value {
+        0 => {}
+        1 | 0 => {}
+        _ => {}
+    }
+}
+
+fn main() {}
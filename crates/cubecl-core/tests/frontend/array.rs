@@ -37,7 +37,7 @@ mod tests {
     use super::*;
     use cubecl_core::{
         cpa,
-        ir::{self, Elem, Item, Variable},
+        ir::{self, Elem, Item, UIntKind, Variable},
     };
 
     type ElemType = F32;
@@ -56,7 +56,7 @@ mod tests {
     #[test]
     fn array_add_assign() {
         let mut context = CubeContext::root();
-        let array = context.input(0, Item::new(Elem::UInt));
+        let array = context.input(0, Item::new(Elem::UInt(UIntKind::U32)));
 
         array_add_assign_simple::__expand(&mut context, array.into());
         let scope = context.into_scope();
@@ -109,7 +109,7 @@ mod tests {
     #[test]
     fn array_add_assign_expr() {
         let mut context = CubeContext::root();
-        let array = context.input(0, Item::new(Elem::UInt));
+        let array = context.input(0, Item::new(Elem::UInt(UIntKind::U32)));
 
         array_add_assign_expr::__expand(&mut context, array.into());
         let scope = context.into_scope();
@@ -121,11 +121,11 @@ mod tests {
         let context = CubeContext::root();
 
         let mut scope = context.into_scope();
-        let local = scope.create_local(Item::new(Elem::UInt));
+        let local = scope.create_local(Item::new(Elem::UInt(UIntKind::U32)));
 
         let array = Variable::GlobalInputArray {
             id: 0,
-            item: Item::new(Elem::UInt),
+            item: Item::new(Elem::UInt(UIntKind::U32)),
         };
         let index: Variable = 1u32.into();
         let value: Variable = 1u32.into();
@@ -181,12 +181,12 @@ mod tests {
         let context = CubeContext::root();
 
         let mut scope = context.into_scope();
-        let index = scope.create_local(Item::new(Elem::UInt));
-        let local = scope.create_local(Item::new(Elem::UInt));
+        let index = scope.create_local(Item::new(Elem::UInt(UIntKind::U32)));
+        let local = scope.create_local(Item::new(Elem::UInt(UIntKind::U32)));
 
         let array = Variable::GlobalInputArray {
             id: 0,
-            item: Item::new(Elem::UInt),
+            item: Item::new(Elem::UInt(UIntKind::U32)),
         };
         let const1: Variable = 1u32.into();
         let const2: Variable = 5u32.into();
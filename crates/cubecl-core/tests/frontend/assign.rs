@@ -38,7 +38,7 @@ mod tests {
     use super::*;
     use cubecl_core::{
         cpa,
-        ir::{Elem, Item, Operation, Variable},
+        ir::{Elem, Item, Operation, UIntKind, Variable},
     };
 
     #[test]
@@ -103,7 +103,7 @@ mod tests {
         let context = CubeContext::root();
 
         let mut scope = context.into_scope();
-        let x = scope.create_local(Item::new(Elem::UInt));
+        let x = scope.create_local(Item::new(Elem::UInt(UIntKind::U32)));
 
         let zero: Variable = 0u32.into();
         let one: Variable = 1u32.into();
@@ -116,7 +116,7 @@ mod tests {
 
     fn inline_macro_ref_mut_assign_input() -> Vec<Operation> {
         let mut context = CubeContext::root();
-        let item = Item::new(Elem::UInt);
+        let item = Item::new(Elem::UInt(UIntKind::U32));
         let y = context.create_local(item);
 
         let mut scope = context.into_scope();
@@ -135,7 +135,7 @@ mod tests {
 
     fn inline_macro_ref_assign_mut_input() -> Vec<Operation> {
         let mut context = CubeContext::root();
-        let item = Item::new(Elem::UInt);
+        let item = Item::new(Elem::UInt(UIntKind::U32));
         let y = context.create_local(item);
 
         let mut scope = context.into_scope();
@@ -154,7 +154,7 @@ mod tests {
 
     fn inline_macro_ref_assign_vectorized() -> Vec<Operation> {
         let mut context = CubeContext::root();
-        let item = Item::vectorized(Elem::UInt, 4);
+        let item = Item::vectorized(Elem::UInt(UIntKind::U32), 4);
         let y = context.create_local(item);
 
         let mut scope = context.into_scope();
@@ -178,7 +178,7 @@ mod tests {
     fn inline_macro_ref_assign_deref() -> Vec<Operation> {
         let context = CubeContext::root();
         let mut scope = context.into_scope();
-        let y = scope.create_local(Item::new(Elem::UInt));
+        let y = scope.create_local(Item::new(Elem::UInt(UIntKind::U32)));
 
         let one: Variable = 1u32.into();
 
@@ -119,7 +119,7 @@ mod tests {
     use cubecl_core::{
         cpa,
         frontend::{CubeContext, CubePrimitive},
-        ir::{Elem, Item, Variable},
+        ir::{Elem, Item, UIntKind, Variable},
     };
 
     macro_rules! cast_test {
@@ -159,7 +159,7 @@ mod tests {
         cube_float_to_uint_test,
         float_to_uint::__expand,
         Item::new(F32::as_elem()),
-        Item::new(Elem::UInt)
+        Item::new(Elem::UInt(UIntKind::U32))
     );
 
     cast_test!(
@@ -187,7 +187,7 @@ mod tests {
         cube_int_to_uint_test,
         int_to_uint::__expand,
         Item::new(I32::as_elem()),
-        Item::new(Elem::UInt)
+        Item::new(Elem::UInt(UIntKind::U32))
     );
 
     cast_test!(
@@ -200,28 +200,28 @@ mod tests {
     cast_test!(
         cube_uint_to_float_test,
         uint_to_float::__expand,
-        Item::new(Elem::UInt),
+        Item::new(Elem::UInt(UIntKind::U32)),
         Item::new(F32::as_elem())
     );
 
     cast_test!(
         cube_uint_to_int_test,
         uint_to_int::__expand,
-        Item::new(Elem::UInt),
+        Item::new(Elem::UInt(UIntKind::U32)),
         Item::new(I32::as_elem())
     );
 
     cast_test!(
         cube_uint_to_uint_test,
         uint_to_uint::__expand,
-        Item::new(Elem::UInt),
-        Item::new(Elem::UInt)
+        Item::new(Elem::UInt(UIntKind::U32)),
+        Item::new(Elem::UInt(UIntKind::U32))
     );
 
     cast_test!(
         cube_uint_to_bool_test,
         uint_to_bool::__expand,
-        Item::new(Elem::UInt),
+        Item::new(Elem::UInt(UIntKind::U32)),
         Item::new(Elem::Bool)
     );
 
@@ -243,7 +243,7 @@ mod tests {
         cube_bool_to_uint_test,
         bool_to_uint::__expand,
         Item::new(Elem::Bool),
-        Item::new(Elem::UInt)
+        Item::new(Elem::UInt(UIntKind::U32))
     );
 
     cast_test!(
@@ -262,10 +262,10 @@ mod tests {
         let y = scope.create_local(to_item);
 
         match from_item.elem() {
-            Elem::Float(_) => cpa!(scope, x = x + 2f32),
+            Elem::Float(_) | Elem::AtomicFloat(_) => cpa!(scope, x = x + 2f32),
             Elem::Int(_) => cpa!(scope, x = x + 2i32),
             Elem::AtomicInt(_) => cpa!(scope, x = x + 2i32),
-            Elem::UInt => cpa!(scope, x = x + 2u32),
+            Elem::UInt(_) => cpa!(scope, x = x + 2u32),
             Elem::AtomicUInt => cpa!(scope, x = x + 2u32),
             Elem::Bool => cpa!(scope, x = x && false),
         }
@@ -273,10 +273,10 @@ mod tests {
         cpa!(scope, y = cast(x));
 
         match to_item.elem() {
-            Elem::Float(_) => cpa!(scope, y = y + 34f32),
+            Elem::Float(_) | Elem::AtomicFloat(_) => cpa!(scope, y = y + 34f32),
             Elem::Int(_) => cpa!(scope, y = y + 34i32),
             Elem::AtomicInt(_) => cpa!(scope, y = y + 34i32),
-            Elem::UInt => cpa!(scope, y = y + 34u32),
+            Elem::UInt(_) => cpa!(scope, y = y + 34u32),
             Elem::AtomicUInt => cpa!(scope, y = y + 34u32),
             Elem::Bool => cpa!(scope, y = y || true),
         }
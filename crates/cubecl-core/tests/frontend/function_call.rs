@@ -53,18 +53,18 @@ mod tests {
     use super::*;
     use cubecl_core::{
         frontend::{CubeContext, CubePrimitive, I64},
-        ir::{Elem, Item},
+        ir::{Elem, Item, UIntKind},
     };
 
     #[test]
     fn cube_call_equivalent_to_no_call_no_arg_test() {
         let mut caller_context = CubeContext::root();
-        let x = caller_context.create_local(Item::new(Elem::UInt));
+        let x = caller_context.create_local(Item::new(Elem::UInt(UIntKind::U32)));
         caller_no_arg::__expand(&mut caller_context, x.into());
         let caller_scope = caller_context.into_scope();
 
         let mut no_call_context = CubeContext::root();
-        let x = no_call_context.create_local(Item::new(Elem::UInt));
+        let x = no_call_context.create_local(Item::new(Elem::UInt(UIntKind::U32)));
         no_call_no_arg::__expand(&mut no_call_context, x.into());
         let no_call_scope = no_call_context.into_scope();
 
@@ -78,12 +78,12 @@ mod tests {
     fn cube_call_equivalent_to_no_call_with_arg_test() {
         let mut caller_context = CubeContext::root();
 
-        let x = caller_context.create_local(Item::new(Elem::UInt));
+        let x = caller_context.create_local(Item::new(Elem::UInt(UIntKind::U32)));
         caller_with_arg::__expand(&mut caller_context, x.into());
         let caller_scope = caller_context.into_scope();
 
         let mut no_call_context = CubeContext::root();
-        let x = no_call_context.create_local(Item::new(Elem::UInt));
+        let x = no_call_context.create_local(Item::new(Elem::UInt(UIntKind::U32)));
         no_call_with_arg::__expand(&mut no_call_context, x.into());
         let no_call_scope = no_call_context.into_scope();
 
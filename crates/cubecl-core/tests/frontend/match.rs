@@ -0,0 +1,194 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[derive(Clone, Copy)]
+pub enum Operation {
+    Add,
+    Sub,
+}
+
+impl Init for Operation {
+    fn init(self, _context: &mut CubeContext) -> Self {
+        self
+    }
+}
+
+#[cube]
+pub fn match_runtime<T: Numeric>(lhs: T, op: UInt) {
+    match op {
+        0 => {
+            let _ = lhs + T::from_int(4);
+        }
+        1 | 2 => {
+            let _ = lhs - T::from_int(5);
+        }
+        _ => {
+            let _ = lhs * T::from_int(6);
+        }
+    }
+}
+
+#[cube]
+#[allow(clippy::single_match)]
+pub fn match_runtime_break(op: UInt) {
+    loop {
+        match op {
+            0 => break,
+            _ => {}
+        }
+    }
+}
+
+#[cube]
+pub fn match_comptime_enum<T: Numeric>(lhs: T, op: Comptime<Operation>) {
+    match Comptime::get(op) {
+        Operation::Add => {
+            let _ = lhs + T::from_int(4);
+        }
+        Operation::Sub => {
+            let _ = lhs - T::from_int(5);
+        }
+    }
+}
+
+#[cube]
+pub fn match_comptime_option<T: Numeric>(lhs: T, bound: Comptime<Option<UInt>>) -> T {
+    let mut x = lhs;
+
+    match Comptime::get(bound) {
+        Some(bound) => {
+            for _ in range(0u32, bound, Comptime::new(false)) {
+                x += T::from_int(1);
+            }
+        }
+        None => {
+            x += T::from_int(2);
+        }
+    }
+
+    x
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::{
+        cpa,
+        frontend::{CubeContext, CubePrimitive, F32},
+        ir::{Branch, Elem, Item, Switch, UIntKind, Variable},
+    };
+
+    type ElemType = F32;
+
+    #[test]
+    fn cube_match_runtime_test() {
+        let mut context = CubeContext::root();
+
+        let lhs = context.create_local(Item::new(ElemType::as_elem()));
+        let op = context.create_local(Item::new(Elem::UInt(UIntKind::U32)));
+
+        match_runtime::__expand::<ElemType>(&mut context, lhs.into(), op.into());
+        let scope = context.into_scope();
+
+        assert_eq!(format!("{:?}", scope.operations), inline_macro_ref_switch());
+    }
+
+    #[test]
+    fn cube_match_runtime_break_test() {
+        let mut context = CubeContext::root();
+
+        let op = context.create_local(Item::new(Elem::UInt(UIntKind::U32)));
+
+        match_runtime_break::__expand(&mut context, op.into());
+        let scope = context.into_scope();
+        let operations = format!("{:?}", scope.operations);
+
+        // The break must exit the loop, so no switch can be used.
+        assert!(!operations.contains("Switch"));
+        assert!(operations.contains("IfElse"));
+    }
+
+    #[test]
+    fn cube_match_comptime_enum_test() {
+        for (op, cond) in [(Operation::Add, true), (Operation::Sub, false)] {
+            let mut context = CubeContext::root();
+
+            let lhs = context.create_local(Item::new(ElemType::as_elem()));
+
+            match_comptime_enum::__expand::<ElemType>(&mut context, lhs.into(), op);
+            let scope = context.into_scope();
+
+            assert_eq!(
+                format!("{:?}", scope.operations),
+                inline_macro_ref_comptime(cond)
+            );
+        }
+    }
+
+    #[test]
+    fn cube_match_comptime_option_test() {
+        let mut context_some = CubeContext::root();
+        let lhs = context_some.create_local(Item::new(ElemType::as_elem()));
+        match_comptime_option::__expand::<ElemType>(
+            &mut context_some,
+            lhs.into(),
+            Some(UInt::new(4)),
+        );
+        let scope_some = context_some.into_scope();
+
+        let mut context_none = CubeContext::root();
+        let lhs = context_none.create_local(Item::new(ElemType::as_elem()));
+        match_comptime_option::__expand::<ElemType>(&mut context_none, lhs.into(), None);
+        let scope_none = context_none.into_scope();
+
+        assert!(format!("{:?}", scope_some.operations).contains("RangeLoop"));
+        assert!(!format!("{:?}", scope_none.operations).contains("RangeLoop"));
+    }
+
+    fn inline_macro_ref_switch() -> String {
+        let mut context = CubeContext::root();
+        let item = Item::new(ElemType::as_elem());
+        let lhs = context.create_local(item);
+        let op = context.create_local(Item::new(Elem::UInt(UIntKind::U32)));
+
+        let mut scope = context.into_scope();
+        let lhs: Variable = lhs.into();
+        let op: Variable = op.into();
+        let y = scope.create_local(item);
+
+        let mut case_0 = scope.child();
+        cpa!(case_0, y = lhs + 4.0f32);
+        let mut case_1 = scope.child();
+        cpa!(case_1, y = lhs - 5.0f32);
+        let mut scope_default = scope.child();
+        cpa!(scope_default, y = lhs * 6.0f32);
+
+        scope.register(Branch::Switch(Switch {
+            value: op,
+            scope_default,
+            cases: vec![
+                (vec![0u32.into()], case_0),
+                (vec![1u32.into(), 2u32.into()], case_1),
+            ],
+        }));
+
+        format!("{:?}", scope.operations)
+    }
+
+    fn inline_macro_ref_comptime(cond: bool) -> String {
+        let mut context = CubeContext::root();
+        let item = Item::new(ElemType::as_elem());
+        let x = context.create_local(item);
+
+        let mut scope = context.into_scope();
+        let x: Variable = x.into();
+        let y = scope.create_local(item);
+
+        if cond {
+            cpa!(scope, y = x + 4.0f32);
+        } else {
+            cpa!(scope, y = x - 5.0f32);
+        };
+
+        format!("{:?}", scope.operations)
+    }
+}
@@ -10,7 +10,9 @@ mod generic_kernel;
 mod r#if;
 mod literal;
 mod r#loop;
+mod r#match;
 mod module_import;
+mod noinline;
 mod ops;
 mod parenthesis;
 mod redeclare;
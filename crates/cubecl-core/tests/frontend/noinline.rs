@@ -0,0 +1,132 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube(noinline)]
+pub fn noinline_scale<T: Numeric>(x: T, double: Comptime<bool>) -> T {
+    let mut y = x * T::from_int(3);
+    if Comptime::get(double) {
+        y = x * T::from_int(2);
+    }
+    y
+}
+
+#[cube]
+pub fn inline_scale<T: Numeric>(x: T, double: Comptime<bool>) -> T {
+    let mut y = x * T::from_int(3);
+    if Comptime::get(double) {
+        y = x * T::from_int(2);
+    }
+    y
+}
+
+#[cube(noinline)]
+pub fn noinline_store(output: &mut Array<UInt>, value: UInt) {
+    output[0] = value;
+}
+
+#[cube]
+pub fn caller_twice<T: Numeric>(x: T) {
+    let y = noinline_scale::<T>(x, Comptime::new(true));
+    let _ = noinline_scale::<T>(y, Comptime::new(true));
+}
+
+#[cube]
+pub fn caller_comptime<T: Numeric>(x: T) {
+    let y = noinline_scale::<T>(x, Comptime::new(true));
+    let _ = noinline_scale::<T>(y, Comptime::new(false));
+}
+
+#[cube]
+pub fn caller_global(output: &mut Array<UInt>, x: UInt) {
+    noinline_store(output, x);
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::{
+        frontend::{CubeContext, CubePrimitive, F32},
+        ir::{Call, Elem, Item, Operation, UIntKind, Variable},
+    };
+
+    type ElemType = F32;
+
+    #[test]
+    fn cube_noinline_call_test() {
+        let mut context = CubeContext::root();
+        let x = context.create_local(Item::new(ElemType::as_elem()));
+
+        caller_twice::__expand::<ElemType>(&mut context, x.into());
+        let functions = context.functions.borrow().clone();
+        let scope = context.into_scope();
+
+        let calls = scope
+            .operations
+            .iter()
+            .filter(|op| matches!(op, Operation::Call(_)))
+            .count();
+
+        assert_eq!(calls, 2);
+        assert_eq!(functions.len(), 1, "The same function should be reused");
+        assert_eq!(
+            format!("{:?}", functions[0].scope.operations),
+            inline_scale_ref()
+        );
+    }
+
+    #[test]
+    fn cube_noinline_comptime_test() {
+        let mut context = CubeContext::root();
+        let x = context.create_local(Item::new(ElemType::as_elem()));
+
+        caller_comptime::__expand::<ElemType>(&mut context, x.into());
+        let functions = context.functions.borrow().clone();
+
+        assert_eq!(
+            functions.len(),
+            2,
+            "Different comptime values create different functions"
+        );
+        assert_eq!(functions[0].id, 0);
+        assert_eq!(functions[1].id, 1);
+    }
+
+    #[test]
+    fn cube_noinline_global_array_test() {
+        let mut context = CubeContext::root();
+        let output = context.input(0, Item::new(Elem::UInt(UIntKind::U32)));
+        let x = context.create_local(Item::new(Elem::UInt(UIntKind::U32)));
+        let x_var: Variable = *x;
+
+        caller_global::__expand(&mut context, output.into(), x.into());
+        let functions = context.functions.borrow().clone();
+        let scope = context.into_scope();
+
+        // Only the value is an input of the function, the array is accessed directly.
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].inputs.len(), 1);
+        assert_eq!(functions[0].output, None);
+
+        let call = scope.operations.iter().find_map(|op| match op {
+            Operation::Call(call) => Some(call.clone()),
+            _ => None,
+        });
+        assert_eq!(
+            call,
+            Some(Call {
+                function: 0,
+                inputs: vec![x_var],
+                output: None,
+            })
+        );
+    }
+
+    fn inline_scale_ref() -> String {
+        let mut context = CubeContext::root();
+        let x = context.create_local(Item::new(ElemType::as_elem()));
+
+        inline_scale::__expand::<ElemType>(&mut context, x.into(), true);
+        let scope = context.into_scope();
+
+        format!("{:?}", scope.operations)
+    }
+}
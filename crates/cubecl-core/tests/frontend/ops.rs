@@ -86,6 +86,96 @@ pub fn recip_op<F: Float>(a: F) -> F {
     F::recip(a)
 }
 
+#[cube]
+pub fn tan_op<F: Float>(a: F) -> F {
+    F::tan(a)
+}
+
+#[cube]
+pub fn asin_op<F: Float>(a: F) -> F {
+    F::asin(a)
+}
+
+#[cube]
+pub fn acos_op<F: Float>(a: F) -> F {
+    F::acos(a)
+}
+
+#[cube]
+pub fn atan_op<F: Float>(a: F) -> F {
+    F::atan(a)
+}
+
+#[cube]
+pub fn sinh_op<F: Float>(a: F) -> F {
+    F::sinh(a)
+}
+
+#[cube]
+pub fn cosh_op<F: Float>(a: F) -> F {
+    F::cosh(a)
+}
+
+#[cube]
+pub fn exp2_op<F: Float>(a: F) -> F {
+    F::exp2(a)
+}
+
+#[cube]
+pub fn log2_op<F: Float>(a: F) -> F {
+    F::log2(a)
+}
+
+#[cube]
+pub fn round_op<F: Float>(a: F) -> F {
+    F::round(a)
+}
+
+#[cube]
+pub fn trunc_op<F: Float>(a: F) -> F {
+    F::trunc(a)
+}
+
+#[cube]
+pub fn fract_op<F: Float>(a: F) -> F {
+    F::fract(a)
+}
+
+#[cube]
+pub fn sign_op<F: Float>(a: F) -> F {
+    F::sign(a)
+}
+
+#[cube]
+pub fn rsqrt_op<F: Float>(a: F) -> F {
+    F::rsqrt(a)
+}
+
+#[cube]
+pub fn atan2_op<F: Float>(a: F, b: F) -> F {
+    F::atan2(a, b)
+}
+
+#[cube]
+pub fn copysign_op<F: Float>(a: F, b: F) -> F {
+    F::copysign(a, b)
+}
+
+#[cube]
+pub fn hypot_op<F: Float>(a: F, b: F) -> F {
+    F::hypot(a, b)
+}
+
+#[cube]
+pub fn is_nan_op<F: Float>(a: F) -> bool {
+    F::is_nan(a)
+}
+
+#[cube]
+pub fn is_inf_op<F: Float>(a: F) -> bool {
+    F::is_inf(a)
+}
+
 #[cube]
 pub fn equal_op<T: CubePrimitive>(a: T, b: T) -> bool {
     a == b
@@ -156,6 +246,26 @@ pub fn bitand_op(a: UInt, b: UInt) -> UInt {
     a & b
 }
 
+#[cube]
+pub fn bitor_op(a: UInt, b: UInt) -> UInt {
+    a | b
+}
+
+#[cube]
+pub fn bitnot_op(a: UInt) -> UInt {
+    !a
+}
+
+#[cube]
+pub fn neg_op(a: F32) -> F32 {
+    -a
+}
+
+#[cube]
+pub fn count_ones_op(a: UInt) -> UInt {
+    UInt::count_ones(a)
+}
+
 #[cube]
 pub fn bitxor_op(a: UInt, b: UInt) -> UInt {
     a ^ b
@@ -193,7 +303,7 @@ pub fn div_assign_op<T: Numeric>(mut a: T, b: T) {
 
 mod tests {
     use super::*;
-    use cubecl_core::ir::{Elem, FloatKind, Item};
+    use cubecl_core::ir::{Elem, FloatKind, Item, UIntKind};
 
     macro_rules! binary_test {
         ($test_name:ident, $op_expand:expr, $op_name:expr, $func:ident) => {
@@ -253,8 +363,8 @@ mod tests {
             #[test]
             fn $test_name() {
                 let mut context = CubeContext::root();
-                let x = context.create_local(Item::new(Elem::UInt));
-                let y = context.create_local(Item::new(Elem::UInt));
+                let x = context.create_local(Item::new(Elem::UInt(UIntKind::U32)));
+                let y = context.create_local(Item::new(Elem::UInt(UIntKind::U32)));
 
                 $op_expand(&mut context, x.into(), y.into());
 
@@ -271,6 +381,7 @@ mod tests {
     binary_test!(cube_can_mul, mul_op::__expand::<F32>, "Mul", ref_ops_binary);
     binary_test!(cube_can_div, div_op::__expand::<F32>, "Div", ref_ops_binary);
     unary_test!(cube_can_abs, abs_op::__expand::<F32>, "Abs");
+    unary_test!(cube_can_neg, neg_op::__expand, "Neg");
     unary_test!(cube_can_exp, exp_op::__expand::<F32>, "Exp");
     unary_test!(cube_can_log, log_op::__expand::<F32>, "Log");
     unary_test!(cube_can_log1p, log1p_op::__expand::<F32>, "Log1p");
@@ -288,6 +399,37 @@ mod tests {
     unary_test!(cube_can_recip, recip_op::__expand::<F32>, "Recip");
     unary_test!(cube_can_floor, floor_op::__expand::<F32>, "Floor");
     unary_test!(cube_can_ceil, ceil_op::__expand::<F32>, "Ceil");
+    unary_test!(cube_can_tan, tan_op::__expand::<F32>, "Tan");
+    unary_test!(cube_can_asin, asin_op::__expand::<F32>, "Asin");
+    unary_test!(cube_can_acos, acos_op::__expand::<F32>, "Acos");
+    unary_test!(cube_can_atan, atan_op::__expand::<F32>, "Atan");
+    unary_test!(cube_can_sinh, sinh_op::__expand::<F32>, "Sinh");
+    unary_test!(cube_can_cosh, cosh_op::__expand::<F32>, "Cosh");
+    unary_test!(cube_can_exp2, exp2_op::__expand::<F32>, "Exp2");
+    unary_test!(cube_can_log2, log2_op::__expand::<F32>, "Log2");
+    unary_test!(cube_can_round, round_op::__expand::<F32>, "Round");
+    unary_test!(cube_can_trunc, trunc_op::__expand::<F32>, "Trunc");
+    unary_test!(cube_can_fract, fract_op::__expand::<F32>, "Fract");
+    unary_test!(cube_can_sign, sign_op::__expand::<F32>, "Sign");
+    unary_test!(cube_can_rsqrt, rsqrt_op::__expand::<F32>, "Rsqrt");
+    binary_test!(
+        cube_can_atan2,
+        atan2_op::__expand::<F32>,
+        "Atan2",
+        ref_ops_binary
+    );
+    binary_test!(
+        cube_can_copysign,
+        copysign_op::__expand::<F32>,
+        "CopySign",
+        ref_ops_binary
+    );
+    binary_test!(
+        cube_can_hypot,
+        hypot_op::__expand::<F32>,
+        "Hypot",
+        ref_ops_binary
+    );
     binary_test!(cube_can_eq, equal_op::__expand::<F32>, "Equal", ref_ops_cmp);
     binary_test!(
         cube_can_ne,
@@ -343,6 +485,7 @@ mod tests {
     binary_boolean_test!(cube_can_and, and_op::__expand, "And");
     binary_boolean_test!(cube_can_or, or_op::__expand, "Or");
     binary_uint_test!(cube_can_bitand, bitand_op::__expand, "BitwiseAnd");
+    binary_uint_test!(cube_can_bitor, bitor_op::__expand, "BitwiseOr");
     binary_uint_test!(cube_can_bitxor, bitxor_op::__expand, "BitwiseXor");
     binary_uint_test!(cube_can_shl, shl_op::__expand, "ShiftLeft");
     binary_uint_test!(cube_can_shr, shr_op::__expand, "ShiftRight");
@@ -367,6 +510,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cube_can_bitnot() {
+        let mut context = CubeContext::root();
+        let x = context.create_local(Item::new(Elem::UInt(UIntKind::U32)));
+
+        bitnot_op::__expand(&mut context, x.into());
+
+        assert_eq!(
+            format!("{:?}", context.into_scope().operations),
+            ref_ops_unary_uint("BitwiseNot")
+        );
+    }
+
+    #[test]
+    fn cube_can_count_ones() {
+        let mut context = CubeContext::root();
+        let x = context.create_local(Item::new(Elem::UInt(UIntKind::U32)));
+
+        count_ones_op::__expand(&mut context, x.into());
+
+        assert_eq!(
+            format!("{:?}", context.into_scope().operations),
+            ref_ops_unary_uint("CountOnes")
+        );
+    }
+
+    #[test]
+    fn cube_can_is_nan() {
+        let mut context = CubeContext::root();
+        let x = context.create_local(Item::new(Elem::Float(FloatKind::F32)));
+
+        is_nan_op::__expand::<F32>(&mut context, x.into());
+
+        assert_eq!(
+            format!("{:?}", context.into_scope().operations),
+            ref_ops_unary_cmp("IsNan")
+        );
+    }
+
+    #[test]
+    fn cube_can_is_inf() {
+        let mut context = CubeContext::root();
+        let x = context.create_local(Item::new(Elem::Float(FloatKind::F32)));
+
+        is_inf_op::__expand::<F32>(&mut context, x.into());
+
+        assert_eq!(
+            format!("{:?}", context.into_scope().operations),
+            ref_ops_unary_cmp("IsInf")
+        );
+    }
+
     fn ref_ops_binary(ops_name: &str) -> String {
         ref_ops_template(ops_name, "Float(F32)", "Float(F32)", true)
     }
@@ -379,6 +574,10 @@ mod tests {
         ref_ops_template(ops_name, "Float(F32)", "Bool", true)
     }
 
+    fn ref_ops_unary_cmp(ops_name: &str) -> String {
+        ref_ops_template(ops_name, "Float(F32)", "Bool", false)
+    }
+
     fn ref_ops_unary_boolean(ops_name: &str) -> String {
         ref_ops_template(ops_name, "Bool", "Bool", false)
     }
@@ -387,8 +586,12 @@ mod tests {
         ref_ops_template(ops_name, "Bool", "Bool", true)
     }
 
+    fn ref_ops_unary_uint(ops_name: &str) -> String {
+        ref_ops_template(ops_name, "UInt(U32)", "UInt(U32)", false)
+    }
+
     fn ref_ops_binary_uint(ops_name: &str) -> String {
-        ref_ops_template(ops_name, "UInt", "UInt", true)
+        ref_ops_template(ops_name, "UInt(U32)", "UInt(U32)", true)
     }
 
     fn ref_ops_template(ops_name: &str, in_type: &str, out_type: &str, binary: bool) -> String {
@@ -411,13 +614,14 @@ mod tests {
             }}))]"
             )
         } else {
+            let out_number = if in_type == out_type { 0 } else { 1 };
             format!(
                 "[Operator({ops_name}(UnaryOperator {{ \
                 input: Local {{ id: 0, item: Item {{ \
                     elem: {in_type}, \
                     vectorization: 1 \
                 }}, depth: 0 }}, \
-                out: Local {{ id: 0, item: Item {{ \
+                out: Local {{ id: {out_number}, item: Item {{ \
                     elem: {out_type}, \
                     vectorization: 1 \
                 }}, depth: 0 }} \
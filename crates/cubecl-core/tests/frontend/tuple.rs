@@ -19,7 +19,7 @@ mod tests {
     use super::*;
     use cubecl_core::{
         cpa,
-        ir::{Elem, Item, Operation, Variable},
+        ir::{Elem, Item, Operation, UIntKind, Variable},
     };
 
     #[test]
@@ -36,8 +36,8 @@ mod tests {
         let context = CubeContext::root();
 
         let mut scope = context.into_scope();
-        let x = scope.create_local(Item::new(Elem::UInt));
-        let y = scope.create_local(Item::new(Elem::UInt));
+        let x = scope.create_local(Item::new(Elem::UInt(UIntKind::U32)));
+        let y = scope.create_local(Item::new(Elem::UInt(UIntKind::U32)));
 
         let zero: Variable = 0u32.into();
         let one: Variable = 1u32.into();
@@ -62,8 +62,8 @@ mod tests {
         let context = CubeContext::root();
 
         let mut scope = context.into_scope();
-        let a = scope.create_local(Item::new(Elem::UInt));
-        let b = scope.create_local(Item::new(Elem::UInt));
+        let a = scope.create_local(Item::new(Elem::UInt(UIntKind::U32)));
+        let b = scope.create_local(Item::new(Elem::UInt(UIntKind::U32)));
 
         let zero: Variable = 0u32.into();
         let one: Variable = 1u32.into();
@@ -1,4 +1,5 @@
 mod frontend;
+mod optimization;
 
 #[test]
 fn compile_fail_tests() {
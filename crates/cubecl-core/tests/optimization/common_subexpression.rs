@@ -0,0 +1,63 @@
+use super::{optimizations, uint};
+use cubecl_core::{
+    cpa,
+    ir::{OptimizationPass, Scope, Variable},
+};
+
+#[test]
+fn common_subexpression_metadata_test() {
+    let mut scope = Scope::root();
+    let input = Variable::GlobalInputArray {
+        id: 0,
+        item: uint(),
+    };
+    let dim = scope.create_local(uint());
+    let shape_a = scope.create_local(uint());
+    let shape_b = scope.create_local(uint());
+    let stride_a = scope.create_local(uint());
+    let stride_b = scope.create_local(uint());
+
+    cpa!(scope, shape_a = shape(input, dim));
+    cpa!(scope, stride_a = stride(input, dim));
+    cpa!(scope, shape_b = shape(input, dim));
+    cpa!(scope, stride_b = stride(input, dim));
+
+    let processing = scope.process_with(&optimizations(
+        OptimizationPass::CommonSubexpressionElimination,
+    ));
+
+    let mut expected = Scope::root();
+    cpa!(expected, shape_a = shape(input, dim));
+    cpa!(expected, stride_a = stride(input, dim));
+    cpa!(expected, shape_b = shape_a);
+    cpa!(expected, stride_b = stride_a);
+
+    assert_eq!(processing.operations, expected.operations);
+}
+
+#[test]
+fn common_subexpression_invalidated_test() {
+    let mut scope = Scope::root();
+    let input = Variable::GlobalInputArray {
+        id: 0,
+        item: uint(),
+    };
+    let dim = scope.create_local(uint());
+    let shape_a = scope.create_local(uint());
+    let shape_b = scope.create_local(uint());
+    let shape_c = scope.create_local(uint());
+
+    cpa!(scope, shape_a = shape(input, dim));
+    cpa!(scope, dim = dim + 1u32);
+    cpa!(scope, shape_b = shape(input, dim));
+    cpa!(scope, shape_b = shape_b + 1u32);
+    cpa!(scope, shape_c = shape(input, dim));
+
+    let operations = scope.operations.clone();
+    let processing = scope.process_with(&optimizations(
+        OptimizationPass::CommonSubexpressionElimination,
+    ));
+
+    // The dimension changed, then the previous result was overwritten.
+    assert_eq!(processing.operations, operations);
+}
@@ -0,0 +1,76 @@
+use super::{optimizations, uint};
+use cubecl_core::{
+    cpa,
+    ir::{Elem, IntKind, Item, OptimizationPass, Scope, UIntKind, Variable},
+};
+
+#[test]
+fn constant_folding_arithmetic_test() {
+    let mut scope = Scope::root();
+    let out = scope.create_local(uint());
+    let cond = scope.create_local(Item::new(Elem::Bool));
+
+    cpa!(scope, out = add(2u32, 3u32));
+    cpa!(scope, out = mul(4u32, 5u32));
+    cpa!(scope, cond = lower(4u32, 5u32));
+
+    let processing = scope.process_with(&optimizations(OptimizationPass::ConstantFolding));
+
+    let (sum, product, lower): (Variable, Variable, Variable) =
+        (5u32.into(), 20u32.into(), true.into());
+    let mut expected = Scope::root();
+    cpa!(expected, out = sum);
+    cpa!(expected, out = product);
+    cpa!(expected, cond = lower);
+
+    assert_eq!(processing.operations, expected.operations);
+}
+
+#[test]
+fn constant_folding_wraps_like_the_elem_test() {
+    let mut scope = Scope::root();
+    let out_i8 = scope.create_local(Item::new(Elem::Int(IntKind::I8)));
+    let out_u32 = scope.create_local(uint());
+
+    cpa!(scope, out_i8 = add(100i8, 100i8));
+    cpa!(scope, out_u32 = sub(2u32, 3u32));
+
+    let processing = scope.process_with(&optimizations(OptimizationPass::ConstantFolding));
+
+    let (sum, diff): (Variable, Variable) = ((-56i8).into(), u32::MAX.into());
+    let mut expected = Scope::root();
+    cpa!(expected, out_i8 = sum);
+    cpa!(expected, out_u32 = diff);
+
+    assert_eq!(processing.operations, expected.operations);
+}
+
+#[test]
+fn constant_folding_skips_undefined_results_test() {
+    let mut scope = Scope::root();
+    let x = scope.create_local(uint());
+    let div = scope.create_local(uint());
+    let sum = scope.create_local(uint());
+
+    cpa!(scope, div = div(1u32, 0u32));
+    cpa!(scope, sum = add(x, 1u32));
+
+    let operations = scope.operations.clone();
+    let processing = scope.process_with(&optimizations(OptimizationPass::ConstantFolding));
+
+    assert_eq!(processing.operations, operations);
+}
+
+#[test]
+fn constant_folding_vectorized_test() {
+    let mut scope = Scope::root();
+    let out = scope.create_local(Item::vectorized(Elem::UInt(UIntKind::U32), 4));
+
+    cpa!(scope, out = add(2u32, 3u32));
+
+    let operations = scope.operations.clone();
+    let processing = scope.process_with(&optimizations(OptimizationPass::ConstantFolding));
+
+    // The constants are scalars, but the output is a vector.
+    assert_eq!(processing.operations, operations);
+}
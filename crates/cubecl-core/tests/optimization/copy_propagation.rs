@@ -0,0 +1,69 @@
+use super::{optimizations, uint};
+use cubecl_core::{
+    cpa,
+    ir::{OptimizationPass, Scope, Variable},
+};
+
+#[test]
+fn copy_propagation_chain_test() {
+    let mut scope = Scope::root();
+    let a = scope.create_local(uint());
+    let b = scope.create_local(uint());
+    let c = scope.create_local(uint());
+    let d = scope.create_local(uint());
+
+    cpa!(scope, b = a);
+    cpa!(scope, c = b);
+    cpa!(scope, d = c * c);
+
+    let processing = scope.process_with(&optimizations(OptimizationPass::CopyPropagation));
+
+    let mut expected = Scope::root();
+    cpa!(expected, b = a);
+    cpa!(expected, c = a);
+    cpa!(expected, d = a * a);
+
+    assert_eq!(processing.operations, expected.operations);
+}
+
+#[test]
+fn copy_propagation_invalidated_test() {
+    let mut scope = Scope::root();
+    let a = scope.create_local(uint());
+    let b = scope.create_local(uint());
+    let c = scope.create_local(uint());
+
+    cpa!(scope, b = a);
+    cpa!(scope, a = a + 1u32);
+    cpa!(scope, c = b + 1u32);
+
+    let operations = scope.operations.clone();
+    let processing = scope.process_with(&optimizations(OptimizationPass::CopyPropagation));
+
+    // The source was modified after the copy.
+    assert_eq!(processing.operations, operations);
+}
+
+#[test]
+fn copy_propagation_constant_test() {
+    let mut scope = Scope::root();
+    let two: Variable = 2u32.into();
+    let a = scope.create_local(uint());
+    let b = scope.create_local(uint());
+    let c = scope.create_local(uint());
+    let d = scope.create_local(uint());
+
+    cpa!(scope, a = two);
+    cpa!(scope, b = c + a);
+    cpa!(scope, d = a * a);
+
+    let processing = scope.process_with(&optimizations(OptimizationPass::CopyPropagation));
+
+    let four: Variable = 4u32.into();
+    let mut expected = Scope::root();
+    cpa!(expected, a = two);
+    cpa!(expected, b = c + two);
+    cpa!(expected, d = four);
+
+    assert_eq!(processing.operations, expected.operations);
+}
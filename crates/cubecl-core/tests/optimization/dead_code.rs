@@ -0,0 +1,120 @@
+use super::{optimizations, uint};
+use cubecl_core::{
+    cpa,
+    ir::{Elem, Item, OptimizationPass, Optimizations, Scope, Variable},
+};
+
+#[test]
+fn dead_code_unused_locals_test() {
+    let mut scope = Scope::root();
+    let output = Variable::GlobalOutputArray {
+        id: 0,
+        item: uint(),
+    };
+    let a = scope.create_local(uint());
+    let b = scope.create_local(uint());
+    let unused_a = scope.create_local(uint());
+    let unused_b = scope.create_local(uint());
+
+    cpa!(scope, unused_a = a + 1u32);
+    cpa!(scope, unused_b = unused_a * 2u32);
+    cpa!(scope, b = a + 2u32);
+    cpa!(scope, output[a] = b);
+
+    let processing = scope.process_with(&optimizations(OptimizationPass::DeadCodeElimination));
+
+    let mut expected = Scope::root();
+    cpa!(expected, b = a + 2u32);
+    cpa!(expected, output[a] = b);
+
+    assert_eq!(processing.operations, expected.operations);
+    assert_eq!(processing.variables, vec![a, b]);
+}
+
+#[test]
+fn dead_code_preserved_test() {
+    let mut scope = Scope::root();
+    let a = scope.create_local(uint());
+    let b = scope.create_local(uint());
+    scope.preserve(b);
+
+    cpa!(scope, b = a + 1u32);
+
+    let operations = scope.operations.clone();
+    let processing = scope.process_with(&optimizations(OptimizationPass::DeadCodeElimination));
+
+    assert_eq!(processing.operations, operations);
+    assert_eq!(processing.variables, vec![a, b]);
+}
+
+#[test]
+fn dead_code_nested_scope_test() {
+    let mut scope = Scope::root();
+    let cond = scope.create_local(Item::new(Elem::Bool));
+    let a = scope.create_local(uint());
+    let b = scope.create_local(uint());
+
+    cpa!(scope, b = a + 1u32);
+    cpa!(&mut scope, if(cond).then(|scope| {
+        cpa!(scope, a = b);
+    }));
+
+    let operations = scope.operations.clone();
+    let processing = scope.process_with(&optimizations(OptimizationPass::DeadCodeElimination));
+
+    // The variable is read by the branch.
+    assert_eq!(processing.operations, operations);
+}
+
+#[test]
+fn dead_code_every_pass_test() {
+    let mut scope = Scope::root();
+    let input = Variable::GlobalInputArray {
+        id: 0,
+        item: uint(),
+    };
+    let output = Variable::GlobalOutputArray {
+        id: 1,
+        item: uint(),
+    };
+    let dim = scope.create_local(uint());
+    let stride_a = scope.create_local(uint());
+    let stride_b = scope.create_local(uint());
+    let offset = scope.create_local(uint());
+    let sum = scope.create_local(uint());
+    let index = scope.create_local(uint());
+
+    cpa!(scope, stride_a = stride(input, dim));
+    cpa!(scope, offset = add(2u32, 3u32));
+    cpa!(scope, stride_b = stride(input, dim));
+    cpa!(scope, sum = stride_b + offset);
+    cpa!(scope, index = stride_a + sum);
+    cpa!(scope, output[index] = sum);
+
+    let processing = scope.process_with(&Optimizations::all());
+
+    let five: Variable = 5u32.into();
+    let mut expected = Scope::root();
+    cpa!(expected, stride_a = stride(input, dim));
+    cpa!(expected, sum = stride_a + five);
+    cpa!(expected, index = stride_a + sum);
+    cpa!(expected, output[index] = sum);
+
+    assert_eq!(processing.operations, expected.operations);
+    assert_eq!(processing.variables, vec![dim, stride_a, sum, index]);
+}
+
+#[test]
+fn dead_code_not_removed_without_optimizations_test() {
+    let mut scope = Scope::root();
+    let a = scope.create_local(uint());
+    let unused = scope.create_local(uint());
+
+    cpa!(scope, unused = a + 1u32);
+
+    let operations = scope.operations.clone();
+    let processing = scope.process();
+
+    assert_eq!(processing.operations, operations);
+    assert_eq!(processing.variables, vec![a, unused]);
+}
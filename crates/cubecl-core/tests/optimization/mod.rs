@@ -0,0 +1,15 @@
+use cubecl_core::ir::{Elem, Item, OptimizationPass, Optimizations, UIntKind};
+
+mod common_subexpression;
+mod constant_folding;
+mod copy_propagation;
+mod dead_code;
+
+/// Only execute the given pass, so each pass is tested in isolation.
+fn optimizations(pass: OptimizationPass) -> Optimizations {
+    Optimizations::new(vec![pass])
+}
+
+fn uint() -> Item {
+    Item::new(Elem::UInt(UIntKind::U32))
+}
@@ -0,0 +1,101 @@
+use cubecl_core as cubecl;
+use cubecl_core::{
+    cpa,
+    ir::{Elem, FloatKind, Item, KernelDefinition, Scope, Variable},
+    prelude::*,
+};
+
+#[cube(noinline)]
+pub fn text_scale(x: F32) -> F32 {
+    x * F32::new(2.0)
+}
+
+#[cube]
+pub fn text_kernel(input: &Array<F32>, output: &mut Array<F32>) {
+    let mut sum = F32::new(0.0);
+
+    for i in range(0u32, input.len(), Comptime::new(false)) {
+        if input[i] > F32::new(1.0) {
+            sum += text_scale(input[i]);
+        } else {
+            sum -= F32::new(1e-7);
+        }
+    }
+
+    loop {
+        if sum > F32::new(100.0) {
+            break;
+        }
+        sum *= F32::new(-0.5);
+    }
+
+    output[UNIT_POS] = sum;
+}
+
+fn float() -> Item {
+    Item::new(Elem::Float(FloatKind::F32))
+}
+
+fn kernel() -> KernelDefinition {
+    let mut builder = KernelBuilder::default();
+    let input = builder.input_array(float());
+    let output = builder.output_array(float());
+
+    text_kernel::__expand(&mut builder.context, input.into(), output.into());
+
+    builder.build(KernelSettings::default())
+}
+
+#[test]
+fn text_kernel_round_trip_test() {
+    let kernel = kernel();
+    let text = kernel.to_string();
+
+    let parsed = text.parse::<KernelDefinition>().unwrap();
+
+    assert_eq!(parsed, kernel);
+    assert_eq!(parsed.to_string(), text);
+}
+
+#[test]
+fn text_scope_golden_test() {
+    let mut scope = Scope::root();
+    let input = Variable::GlobalInputArray {
+        id: 0,
+        item: Item::vectorized(Elem::Float(FloatKind::F32), 4),
+    };
+    let value = scope.create_local(input.item());
+    let zero: Variable = 0u32.into();
+
+    cpa!(scope, value = input[zero]);
+    cpa!(scope, value = value * 2.0f32);
+
+    let text = scope.to_string();
+
+    assert_eq!(text, golden());
+    assert_eq!(text.parse::<Scope>().unwrap(), scope);
+}
+
+fn golden() -> &'static str {
+    "scope {
+    .depth 0
+    .local %local0_0:f32x4
+    %local0_0:f32x4 = index %input0:f32x4, 0:u32
+    %local0_0:f32x4 = mul %local0_0:f32x4, 2.0:f32
+}
+"
+}
+
+#[test]
+fn text_parse_error_test() {
+    let source = "scope {
+    .depth 0
+    %local0_0:f32 = unknown_op %local0_0:f32
+}";
+
+    let error = source.parse::<Scope>().unwrap_err();
+
+    assert_eq!(error.line, 3);
+    assert_eq!(error.column, 21);
+    assert_eq!(error.to_string(), "3:21: Unknown operation `unknown_op`");
+}
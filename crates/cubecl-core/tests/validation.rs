@@ -0,0 +1,128 @@
+use cubecl_core::{
+    cpa,
+    ir::{
+        validate, Branch, CoopMma, Elem, FloatKind, Item, KernelDefinition, Operation, PathSegment,
+        Scope, UIntKind, ValidationErrorKind, Variable,
+    },
+    prelude::{KernelBuilder, KernelSettings},
+};
+
+fn float() -> Item {
+    Item::new(Elem::Float(FloatKind::F32))
+}
+
+fn kernel<F: FnOnce(&mut Scope, Variable, Variable)>(func: F) -> KernelDefinition {
+    let mut builder = KernelBuilder::default();
+    let input = builder.input_array(float());
+    let output = builder.output_array(float());
+
+    func(&mut builder.context.scope.borrow_mut(), *input, *output);
+
+    builder.build(KernelSettings::default())
+}
+
+#[test]
+fn validate_valid_kernel_test() {
+    let kernel = kernel(|scope, input, output| {
+        let value = scope.create_local(float());
+        let cond = scope.create_local(Item::new(Elem::Bool));
+        let zero: Variable = 0u32.into();
+
+        cpa!(scope, value = input[zero]);
+        cpa!(scope, value = value * 2.0f32);
+        cpa!(scope, output[zero] = value);
+        cpa!(
+            scope,
+            loop(|scope| {
+                cpa!(scope, cond = value > 1.0f32);
+                cpa!(scope, if(cond).then(|scope| {
+                    scope.register(Branch::Break);
+                }));
+            })
+        );
+    });
+
+    assert_eq!(validate(&kernel), Ok(()));
+}
+
+#[test]
+fn validate_vectorization_mismatch_test() {
+    let kernel = kernel(|scope, _input, _output| {
+        let vectorized = scope.create_local(Item::vectorized(Elem::Float(FloatKind::F32), 4));
+        let scalar = scope.create_local(float());
+
+        cpa!(scope, vectorized = vectorized * scalar);
+        cpa!(scope, scalar = scalar + vectorized);
+    });
+
+    let errors = validate(&kernel).unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].path,
+        vec![PathSegment::Body, PathSegment::Operation(1)]
+    );
+    assert!(matches!(
+        errors[0].kind,
+        ValidationErrorKind::VectorizationMismatch { .. }
+    ));
+}
+
+#[test]
+fn validate_write_input_test() {
+    let kernel = kernel(|scope, input, _output| {
+        let value = scope.create_local(float());
+        let zero: Variable = 0u32.into();
+
+        cpa!(scope, input[zero] = value);
+    });
+
+    let errors = validate(&kernel).unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0].kind,
+        ValidationErrorKind::ReadOnlyWrite(Variable::GlobalInputArray { id: 0, .. })
+    ));
+}
+
+#[test]
+fn validate_coop_mma_not_a_matrix_test() {
+    let kernel = kernel(|scope, _input, _output| {
+        let mat = scope.create_local(float());
+        let value: Variable = 0.0f32.into();
+
+        scope.register(Operation::CoopMma(CoopMma::Fill { mat, value }));
+    });
+
+    let errors = validate(&kernel).unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0].kind,
+        ValidationErrorKind::NotAMatrix(Variable::Local { .. })
+    ));
+}
+
+#[test]
+fn validate_break_outside_loop_test() {
+    let kernel = kernel(|scope, _input, _output| {
+        let cond = scope.create_local(Item::new(Elem::Bool));
+        let index = scope.create_local(Item::new(Elem::UInt(UIntKind::U32)));
+        let zero: Variable = 0u32.into();
+
+        cpa!(scope, index = zero);
+        cpa!(scope, if(cond).then(|scope| {
+            scope.register(Branch::Break);
+        }));
+    });
+
+    let errors = validate(&kernel).unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ValidationErrorKind::BreakOutsideLoop);
+    assert_eq!(
+        errors[0].to_string(),
+        "body[1].then[0]: Break outside of a loop"
+    );
+}
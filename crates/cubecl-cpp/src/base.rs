@@ -6,11 +6,11 @@ use cubecl_core::{
 };
 use cubecl_runtime::ExecutionMode;
 
-use super::{Instruction, WarpInstruction};
+use super::{Dialect, Instruction, WarpInstruction};
 
 #[allow(clippy::too_many_arguments)]
 #[derive(Clone, Debug, Default)]
-pub struct CudaCompiler {
+pub struct CppCompiler<D: Dialect> {
     shared_memories: Vec<super::SharedMemory>,
     local_arrays: Vec<super::LocalArray>,
     idx_global: bool,
@@ -27,10 +27,14 @@ pub struct CudaCompiler {
     num_outputs: usize,
     items: HashSet<super::Item>,
     strategy: ExecutionMode,
+    /// The names of the bindings, passed to the device functions.
+    bindings: Vec<String>,
+    /// The functions compiled so far, indexed by their id.
+    functions: Vec<super::Function<D>>,
 }
 
-impl Compiler for CudaCompiler {
-    type Representation = super::ComputeKernel;
+impl<D: Dialect> Compiler for CppCompiler<D> {
+    type Representation = super::ComputeKernel<D>;
 
     fn compile(
         kernel: cubecl_core::ir::KernelDefinition,
@@ -51,12 +55,27 @@ impl Compiler for CudaCompiler {
         // TODO: Find out this value.
         usize::MAX
     }
+
+    fn optimizations() -> gpu::Optimizations {
+        gpu::Optimizations::all()
+    }
 }
 
-impl CudaCompiler {
-    fn compile_shader(mut self, mut value: gpu::KernelDefinition) -> super::ComputeKernel {
+impl<D: Dialect> CppCompiler<D> {
+    fn compile_shader(mut self, mut value: gpu::KernelDefinition) -> super::ComputeKernel<D> {
         self.num_inputs = value.inputs.len();
         self.num_outputs = value.outputs.len();
+        self.bindings = (0..self.num_inputs)
+            .map(|i| format!("input_{i}"))
+            .chain((0..self.num_outputs).map(|i| format!("output_{i}")))
+            .chain(value.named.iter().map(|(name, _)| name.clone()))
+            .collect();
+
+        // Functions only call the functions registered before them, so they are compiled in order.
+        for function in value.functions.drain(..) {
+            let function = self.compile_function(function);
+            self.functions.push(function);
+        }
 
         let instructions = self.compile_scope(&mut value.body);
         let inputs = value
@@ -93,6 +112,7 @@ impl CudaCompiler {
             outputs,
             named,
             cube_dim: value.cube_dim,
+            functions: self.functions,
             body,
             wmma_activated: self.wmma,
             bf16: self.bf16,
@@ -101,9 +121,77 @@ impl CudaCompiler {
         }
     }
 
-    fn compile_scope(&mut self, scope: &mut gpu::Scope) -> Vec<Instruction> {
+    fn compile_function(&mut self, mut value: gpu::Function) -> super::Function<D> {
+        let mut compiler = Self {
+            strategy: self.strategy,
+            num_inputs: self.num_inputs,
+            num_outputs: self.num_outputs,
+            bindings: self.bindings.clone(),
+            functions: core::mem::take(&mut self.functions),
+            ..Self::default()
+        };
+
+        let mut instructions = compiler.compile_scope(&mut value.scope);
+        let inputs = value
+            .inputs
+            .into_iter()
+            .map(|input| compiler.compile_variable(input))
+            .collect::<Vec<_>>();
+        let output = value.output.map(|output| compiler.compile_variable(output));
+
+        // The inputs are declared as parameters of the function.
+        let names = inputs
+            .iter()
+            .map(|input| input.to_string())
+            .collect::<Vec<_>>();
+        instructions.retain(|instruction| match instruction {
+            Instruction::DeclareVariable { var } => !names.contains(&var.to_string()),
+            _ => true,
+        });
+
+        self.items.extend(compiler.items);
+        self.wmma |= compiler.wmma;
+        self.bf16 |= compiler.bf16;
+        self.f16 |= compiler.f16;
+        self.functions = compiler.functions;
+
+        super::Function {
+            name: format!("{}_{}", value.name, value.id),
+            inputs,
+            output,
+            body: super::Body {
+                instructions,
+                stride: compiler.stride,
+                shape: compiler.shape,
+                shared_memories: compiler.shared_memories,
+                local_arrays: compiler.local_arrays,
+                rank: compiler.rank,
+                idx_global: compiler.idx_global,
+                thread_idx_global: compiler.thread_idx_global,
+                global_invocation_id: compiler.absolute_idx,
+                wrap_size_checked: compiler.wrap_size_checked,
+            },
+        }
+    }
+
+    fn compile_call(&mut self, call: gpu::Call) -> Instruction<D> {
+        let function = self.functions[call.function as usize].name.clone();
+
+        Instruction::Call {
+            function,
+            inputs: call
+                .inputs
+                .into_iter()
+                .map(|input| self.compile_variable(input))
+                .collect(),
+            output: call.output.map(|output| self.compile_variable(output)),
+            bindings: self.bindings.clone(),
+        }
+    }
+
+    fn compile_scope(&mut self, scope: &mut gpu::Scope) -> Vec<Instruction<D>> {
         let mut instructions = Vec::new();
-        let processing = scope.process();
+        let processing = scope.process_with(&Self::optimizations());
 
         for var in processing.variables {
             if let gpu::Variable::Slice { .. } = var {
@@ -124,7 +212,7 @@ impl CudaCompiler {
 
     fn compile_operation(
         &mut self,
-        instructions: &mut Vec<Instruction>,
+        instructions: &mut Vec<Instruction<D>>,
         operation: gpu::Operation,
         scope: &mut gpu::Scope,
     ) {
@@ -166,14 +254,79 @@ impl CudaCompiler {
                         }))
                     }
 
+                    gpu::Subcube::Shuffle(op) => {
+                        instructions.push(Instruction::Wrap(WarpInstruction::Shuffle {
+                            input: self.compile_variable(op.lhs),
+                            id: self.compile_variable(op.rhs),
+                            out: self.compile_variable(op.out),
+                        }))
+                    }
+                    gpu::Subcube::ShuffleXor(op) => {
+                        instructions.push(Instruction::Wrap(WarpInstruction::ShuffleXor {
+                            input: self.compile_variable(op.lhs),
+                            mask: self.compile_variable(op.rhs),
+                            out: self.compile_variable(op.out),
+                        }))
+                    }
+                    gpu::Subcube::ShuffleUp(op) => {
+                        instructions.push(Instruction::Wrap(WarpInstruction::ShuffleUp {
+                            input: self.compile_variable(op.lhs),
+                            delta: self.compile_variable(op.rhs),
+                            out: self.compile_variable(op.out),
+                        }))
+                    }
+                    gpu::Subcube::ShuffleDown(op) => {
+                        instructions.push(Instruction::Wrap(WarpInstruction::ShuffleDown {
+                            input: self.compile_variable(op.lhs),
+                            delta: self.compile_variable(op.rhs),
+                            out: self.compile_variable(op.out),
+                        }))
+                    }
+                    gpu::Subcube::InclusiveSum(op) => {
+                        // The scans need the index of the unit in the warp.
+                        self.thread_idx_global = true;
+                        instructions.push(Instruction::Wrap(WarpInstruction::InclusiveSum {
+                            input: self.compile_variable(op.input),
+                            out: self.compile_variable(op.out),
+                        }))
+                    }
+                    gpu::Subcube::ExclusiveSum(op) => {
+                        self.thread_idx_global = true;
+                        instructions.push(Instruction::Wrap(WarpInstruction::ExclusiveSum {
+                            input: self.compile_variable(op.input),
+                            out: self.compile_variable(op.out),
+                        }))
+                    }
+                    gpu::Subcube::InclusiveProd(op) => {
+                        self.thread_idx_global = true;
+                        instructions.push(Instruction::Wrap(WarpInstruction::InclusiveProd {
+                            input: self.compile_variable(op.input),
+                            out: self.compile_variable(op.out),
+                        }))
+                    }
+                    gpu::Subcube::ExclusiveProd(op) => {
+                        self.thread_idx_global = true;
+                        instructions.push(Instruction::Wrap(WarpInstruction::ExclusiveProd {
+                            input: self.compile_variable(op.input),
+                            out: self.compile_variable(op.out),
+                        }))
+                    }
+                    gpu::Subcube::Ballot(op) => {
+                        instructions.push(Instruction::Wrap(WarpInstruction::Ballot {
+                            input: self.compile_variable(op.input),
+                            out: self.compile_variable(op.out),
+                        }))
+                    }
+
                     _ => todo!(),
                 }
             }
             gpu::Operation::CoopMma(cmma) => instructions.push(self.compile_cmma(cmma)),
+            gpu::Operation::Call(call) => instructions.push(self.compile_call(call)),
         }
     }
 
-    fn compile_cmma(&mut self, cmma: gpu::CoopMma) -> Instruction {
+    fn compile_cmma(&mut self, cmma: gpu::CoopMma) -> Instruction<D> {
         match cmma {
             gpu::CoopMma::Fill { mat: frag, value } => {
                 Instruction::Wmma(super::WmmaInstruction::Fill {
@@ -215,7 +368,7 @@ impl CudaCompiler {
         }
     }
 
-    fn compile_metadata(&mut self, metadata: gpu::Metadata) -> Instruction {
+    fn compile_metadata(&mut self, metadata: gpu::Metadata) -> Instruction<D> {
         match metadata {
             gpu::Metadata::Stride { dim, var, out } => {
                 self.stride = true;
@@ -260,7 +413,7 @@ impl CudaCompiler {
         }
     }
 
-    fn compile_branch(&mut self, instructions: &mut Vec<Instruction>, branch: gpu::Branch) {
+    fn compile_branch(&mut self, instructions: &mut Vec<Instruction<D>>, branch: gpu::Branch) {
         match branch {
             gpu::Branch::If(mut op) => instructions.push(Instruction::If {
                 cond: self.compile_variable(op.cond),
@@ -271,6 +424,21 @@ impl CudaCompiler {
                 instructions_if: self.compile_scope(&mut op.scope_if),
                 instructions_else: self.compile_scope(&mut op.scope_else),
             }),
+            gpu::Branch::Switch(mut op) => instructions.push(Instruction::Switch {
+                value: self.compile_variable(op.value),
+                instructions_default: self.compile_scope(&mut op.scope_default),
+                cases: op
+                    .cases
+                    .into_iter()
+                    .map(|(values, mut scope)| {
+                        let values = values
+                            .into_iter()
+                            .map(|val| self.compile_variable(val))
+                            .collect();
+                        (values, self.compile_scope(&mut scope))
+                    })
+                    .collect(),
+            }),
             gpu::Branch::Return => instructions.push(Instruction::Return),
             gpu::Branch::Break => instructions.push(Instruction::Break),
             gpu::Branch::RangeLoop(mut range_loop) => instructions.push(Instruction::RangeLoop {
@@ -287,7 +455,7 @@ impl CudaCompiler {
     }
     fn compile_procedure(
         &mut self,
-        instructions: &mut Vec<Instruction>,
+        instructions: &mut Vec<Instruction<D>>,
         proc: gpu::Procedure,
         scope: &mut gpu::Scope,
     ) {
@@ -334,7 +502,7 @@ impl CudaCompiler {
     fn compile_instruction(
         &mut self,
         value: gpu::Operator,
-        instructions: &mut Vec<Instruction>,
+        instructions: &mut Vec<Instruction<D>>,
         scope: &mut gpu::Scope,
     ) {
         match value {
@@ -351,6 +519,12 @@ impl CudaCompiler {
                 end: self.compile_variable(op.end),
                 out: self.compile_variable(op.out),
             }),
+            gpu::Operator::Index(op) | gpu::Operator::UncheckedIndex(op)
+                if op.out.item().elem().is_atomic() =>
+            {
+                // Atomics are updated in place, through a pointer to the element.
+                instructions.push(Instruction::AtomicIndex(self.compile_binary(op)))
+            }
             gpu::Operator::Index(op) => {
                 if let ExecutionMode::Checked = self.strategy {
                     if has_length(&op.lhs) {
@@ -426,9 +600,61 @@ impl CudaCompiler {
             }
             gpu::Operator::Sqrt(op) => instructions.push(Instruction::Sqrt(self.compile_unary(op))),
             gpu::Operator::Erf(op) => instructions.push(Instruction::Erf(self.compile_unary(op))),
+            gpu::Operator::Tan(op) => instructions.push(Instruction::Tan(self.compile_unary(op))),
+            gpu::Operator::Asin(op) => instructions.push(Instruction::Asin(self.compile_unary(op))),
+            gpu::Operator::Acos(op) => instructions.push(Instruction::Acos(self.compile_unary(op))),
+            gpu::Operator::Atan(op) => instructions.push(Instruction::Atan(self.compile_unary(op))),
+            gpu::Operator::Atan2(op) => {
+                instructions.push(Instruction::Atan2(self.compile_binary(op)))
+            }
+            gpu::Operator::Sinh(op) => instructions.push(Instruction::Sinh(self.compile_unary(op))),
+            gpu::Operator::Cosh(op) => instructions.push(Instruction::Cosh(self.compile_unary(op))),
+            gpu::Operator::Exp2(op) => instructions.push(Instruction::Exp2(self.compile_unary(op))),
+            gpu::Operator::Log2(op) => instructions.push(Instruction::Log2(self.compile_unary(op))),
+            gpu::Operator::Round(op) => {
+                instructions.push(Instruction::Round(self.compile_unary(op)))
+            }
+            gpu::Operator::Trunc(op) => {
+                instructions.push(Instruction::Trunc(self.compile_unary(op)))
+            }
+            gpu::Operator::Fract(op) => {
+                instructions.push(Instruction::Fract(self.compile_unary(op)))
+            }
+            gpu::Operator::Sign(op) => instructions.push(Instruction::Sign(self.compile_unary(op))),
+            gpu::Operator::CopySign(op) => {
+                instructions.push(Instruction::CopySign(self.compile_binary(op)))
+            }
+            gpu::Operator::Hypot(op) => {
+                instructions.push(Instruction::Hypot(self.compile_binary(op)))
+            }
+            gpu::Operator::Rsqrt(op) => {
+                instructions.push(Instruction::Rsqrt(self.compile_unary(op)))
+            }
+            gpu::Operator::IsNan(op) => {
+                instructions.push(Instruction::IsNan(self.compile_unary(op)))
+            }
+            gpu::Operator::IsInf(op) => {
+                instructions.push(Instruction::IsInf(self.compile_unary(op)))
+            }
             gpu::Operator::And(op) => instructions.push(Instruction::And(self.compile_binary(op))),
             gpu::Operator::Or(op) => instructions.push(Instruction::Or(self.compile_binary(op))),
             gpu::Operator::Not(op) => instructions.push(Instruction::Not(self.compile_unary(op))),
+            gpu::Operator::BitwiseNot(op) => {
+                instructions.push(Instruction::BitwiseNot(self.compile_unary(op)))
+            }
+            gpu::Operator::Neg(op) => instructions.push(Instruction::Neg(self.compile_unary(op))),
+            gpu::Operator::CountOnes(op) => {
+                instructions.push(Instruction::CountOnes(self.compile_unary(op)))
+            }
+            gpu::Operator::ReverseBits(op) => {
+                instructions.push(Instruction::ReverseBits(self.compile_unary(op)))
+            }
+            gpu::Operator::LeadingZeros(op) => {
+                instructions.push(Instruction::LeadingZeros(self.compile_unary(op)))
+            }
+            gpu::Operator::TrailingZeros(op) => {
+                instructions.push(Instruction::TrailingZeros(self.compile_unary(op)))
+            }
             gpu::Operator::Max(op) => instructions.push(Instruction::Max(self.compile_binary(op))),
             gpu::Operator::Min(op) => instructions.push(Instruction::Min(self.compile_binary(op))),
             gpu::Operator::NotEqual(op) => {
@@ -437,6 +663,9 @@ impl CudaCompiler {
             gpu::Operator::BitwiseAnd(op) => {
                 instructions.push(Instruction::BitwiseAnd(self.compile_binary(op)))
             }
+            gpu::Operator::BitwiseOr(op) => {
+                instructions.push(Instruction::BitwiseOr(self.compile_binary(op)))
+            }
             gpu::Operator::BitwiseXor(op) => {
                 instructions.push(Instruction::BitwiseXor(self.compile_binary(op)))
             }
@@ -457,9 +686,9 @@ impl CudaCompiler {
                 let lhs = match elem {
                     gpu::Elem::Float(kind) => ConstantScalarValue::Float(1.0, kind),
                     gpu::Elem::Int(kind) => ConstantScalarValue::Int(1, kind),
-                    gpu::Elem::UInt => ConstantScalarValue::UInt(1),
+                    gpu::Elem::UInt(kind) => ConstantScalarValue::UInt(1, kind),
                     gpu::Elem::Bool => ConstantScalarValue::Bool(true),
-                    gpu::Elem::AtomicInt(_) | gpu::Elem::AtomicUInt => {
+                    gpu::Elem::AtomicFloat(_) | gpu::Elem::AtomicInt(_) | gpu::Elem::AtomicUInt => {
                         panic!("Cannot use recip with atomics")
                     }
                 };
@@ -633,7 +862,10 @@ impl CudaCompiler {
             gpu::Variable::CubePos => todo!(),
             gpu::Variable::CubeDim => todo!(),
             gpu::Variable::CubeCount => todo!(),
-            gpu::Variable::SubcubeDim => todo!(),
+            gpu::Variable::SubcubeDim => super::Variable::ConstantScalar(
+                ConstantScalarValue::UInt(D::WARP_SIZE as u64, gpu::UIntKind::U32),
+                super::Elem::U32,
+            ),
             gpu::Variable::Matrix { id, mat, depth } => {
                 self.wmma = true;
                 super::Variable::WmmaFragment {
@@ -703,15 +935,27 @@ impl CudaCompiler {
                 gpu::FloatKind::F32 => super::Elem::F32,
                 gpu::FloatKind::F64 => panic!("f64 isn't supported yet"),
             },
+            gpu::Elem::AtomicFloat(kind) => match kind {
+                gpu::FloatKind::F32 => super::Elem::F32,
+                _ => panic!("{value} isn't supported yet"),
+            },
             gpu::Elem::Int(kind) => match kind {
+                gpu::IntKind::I8 => super::Elem::I8,
+                gpu::IntKind::I16 => super::Elem::I16,
                 gpu::IntKind::I32 => super::Elem::I32,
-                gpu::IntKind::I64 => panic!("i64 isn't supported yet"),
+                gpu::IntKind::I64 => super::Elem::I64,
             },
             gpu::Elem::AtomicInt(kind) => match kind {
                 gpu::IntKind::I32 => super::Elem::I32,
                 gpu::IntKind::I64 => panic!("atomic<i64> isn't supported yet"),
+                _ => panic!("atomics narrower than 32 bits aren't supported"),
+            },
+            gpu::Elem::UInt(kind) => match kind {
+                gpu::UIntKind::U8 => super::Elem::U8,
+                gpu::UIntKind::U16 => super::Elem::U16,
+                gpu::UIntKind::U32 => super::Elem::U32,
+                gpu::UIntKind::U64 => super::Elem::U64,
             },
-            gpu::Elem::UInt => super::Elem::U32,
             gpu::Elem::AtomicUInt => super::Elem::U32,
             gpu::Elem::Bool => super::Elem::Bool,
         }
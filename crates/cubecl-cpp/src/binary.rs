@@ -113,6 +113,56 @@ macro_rules! function {
     };
 }
 
+/// Half precision has no intrinsic for these functions, so they are computed in single precision
+/// one lane at a time.
+macro_rules! float_function {
+    ($name:ident, $func:expr) => {
+        pub struct $name;
+
+        impl Binary for $name {
+            fn format_scalar<Lhs, Rhs, Out>(
+                f: &mut std::fmt::Formatter<'_>,
+                lhs: Lhs,
+                rhs: Rhs,
+                out: Out,
+                elem: Elem,
+            ) -> std::fmt::Result
+            where
+                Lhs: Component,
+                Rhs: Component,
+                Out: Component,
+            {
+                match elem {
+                    Elem::F16 | Elem::BF16 => f.write_fmt(format_args!(
+                        "{out} = {elem}({}(float({lhs}), float({rhs})));\n",
+                        $func
+                    )),
+                    _ => f.write_fmt(format_args!("{out} = {}({lhs}, {rhs});\n", $func)),
+                }
+            }
+
+            fn unroll_vec(
+                f: &mut Formatter<'_>,
+                lhs: &Variable,
+                rhs: &Variable,
+                out: &Variable,
+                elem: Elem,
+                index: usize,
+            ) -> core::fmt::Result {
+                for i in 0..index {
+                    Self::format_scalar(f, lhs.index(i), rhs.index(i), out.index(i), elem)?;
+                }
+
+                Ok(())
+            }
+        }
+    };
+}
+
+float_function!(Atan2, "atan2");
+float_function!(CopySign, "copysign");
+float_function!(Hypot, "hypot");
+
 operator!(Add, "+");
 operator!(Sub, "-");
 operator!(Div, "/");
@@ -127,6 +177,7 @@ operator!(GreaterEqual, ">=");
 operator!(ShiftLeft, "<<");
 operator!(ShiftRight, ">>");
 operator!(BitwiseAnd, "&");
+operator!(BitwiseOr, "|");
 operator!(BitwiseXor, "^");
 operator!(Or, "||");
 operator!(And, "&&");
@@ -1,10 +1,10 @@
-use super::Instruction;
+use super::{Dialect, Instruction};
 use std::fmt::Display;
 
 /// A body is composed of a list of [instructions](Instruction).
 #[derive(Debug, Clone)]
-pub struct Body {
-    pub instructions: Vec<Instruction>,
+pub struct Body<D: Dialect> {
+    pub instructions: Vec<Instruction<D>>,
     pub shared_memories: Vec<super::SharedMemory>,
     pub local_arrays: Vec<super::LocalArray>,
     pub stride: bool,
@@ -16,7 +16,7 @@ pub struct Body {
     pub wrap_size_checked: bool,
 }
 
-impl Display for Body {
+impl<D: Dialect> Display for Body<D> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.idx_global
             || self.global_invocation_id.0
@@ -50,11 +50,12 @@ impl Display for Body {
             )?;
         }
         if self.wrap_size_checked {
-            f.write_str(
+            f.write_fmt(format_args!(
                 "
- int warpSizeChecked = min(warpSize, blockDim.x * blockDim.y * blockDim.z);
+ int warpSizeChecked = min({}u, blockDim.x * blockDim.y * blockDim.z);
 ",
-            )?;
+                D::WARP_SIZE
+            ))?;
         }
 
         if self.rank || self.stride || self.shape {
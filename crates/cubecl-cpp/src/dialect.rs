@@ -0,0 +1,120 @@
+use std::fmt::{Debug, Formatter};
+use std::hash::Hash;
+
+/// The flavour of C++ understood by the compiler of a GPU vendor.
+///
+/// The generated code is mostly shared between vendors, only the headers, the names of some types
+/// and the warp intrinsics differ.
+pub trait Dialect:
+    Default + Clone + Copy + Debug + PartialEq + Eq + Hash + Send + Sync + 'static
+{
+    /// The number of units of a warp, which is the subcube dimension.
+    const WARP_SIZE: u32;
+
+    /// Include the half precision floats.
+    fn include_f16(f: &mut Formatter<'_>) -> std::fmt::Result;
+    /// Include the brain floats, declaring them as `bfloat16` and `bfloat162`.
+    fn include_bf16(f: &mut Formatter<'_>) -> std::fmt::Result;
+    /// Include the warp level matrix functions, available in the `wmma` namespace.
+    fn include_wmma(f: &mut Formatter<'_>) -> std::fmt::Result;
+    /// Read the value of the unit whose index is the index of the current unit xor the mask.
+    fn warp_shuffle_xor(value: &str, mask: &str) -> String;
+    /// Read the value of the unit whose index is `id`.
+    fn warp_shuffle(value: &str, id: &str) -> String;
+    /// Read the value of the unit whose index is the index of the current unit minus `delta`.
+    fn warp_shuffle_up(value: &str, delta: &str) -> String;
+    /// Read the value of the unit whose index is the index of the current unit plus `delta`.
+    fn warp_shuffle_down(value: &str, delta: &str) -> String;
+    /// The mask of the units for which the input is true, as an `unsigned long long`.
+    fn warp_ballot(input: &str) -> String;
+}
+
+/// The dialect of NVIDIA GPUs.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cuda;
+
+/// The dialect of AMD GPUs, compiled with ROCm.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Hip;
+
+impl Dialect for Cuda {
+    const WARP_SIZE: u32 = 32;
+
+    fn include_f16(f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("#include <cuda_fp16.h>\n")
+    }
+
+    fn include_bf16(f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("#include <cuda_bf16.h>\n")?;
+        f.write_str("typedef __nv_bfloat16 bfloat16;\n")?;
+        f.write_str("typedef __nv_bfloat162 bfloat162;\n")
+    }
+
+    fn include_wmma(f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("#include <mma.h>\n")?;
+        f.write_str("using namespace nvcuda;\n")
+    }
+
+    fn warp_shuffle_xor(value: &str, mask: &str) -> String {
+        format!("__shfl_xor_sync(0xFFFFFFFF, {value}, {mask})")
+    }
+
+    fn warp_shuffle(value: &str, id: &str) -> String {
+        format!("__shfl_sync(0xFFFFFFFF, {value}, {id})")
+    }
+
+    fn warp_shuffle_up(value: &str, delta: &str) -> String {
+        format!("__shfl_up_sync(0xFFFFFFFF, {value}, {delta})")
+    }
+
+    fn warp_shuffle_down(value: &str, delta: &str) -> String {
+        format!("__shfl_down_sync(0xFFFFFFFF, {value}, {delta})")
+    }
+
+    fn warp_ballot(input: &str) -> String {
+        format!("(unsigned long long)__ballot_sync(0xFFFFFFFF, {input})")
+    }
+}
+
+impl Dialect for Hip {
+    // The wavefronts of the MI series have 64 units.
+    const WARP_SIZE: u32 = 64;
+
+    fn include_f16(f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("#include <hip/hip_fp16.h>\n")
+    }
+
+    fn include_bf16(f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("#include <hip/hip_bf16.h>\n")?;
+        f.write_str("typedef __hip_bfloat16 bfloat16;\n")?;
+        f.write_str("typedef __hip_bfloat162 bfloat162;\n")
+    }
+
+    fn include_wmma(f: &mut Formatter<'_>) -> std::fmt::Result {
+        // rocWMMA mirrors the API of nvcuda::wmma.
+        f.write_str("#include <rocwmma/rocwmma.hpp>\n")?;
+        f.write_str("namespace wmma = rocwmma;\n")
+    }
+
+    fn warp_shuffle_xor(value: &str, mask: &str) -> String {
+        // Warp functions don't take a mask of the participating units.
+        format!("__shfl_xor({value}, {mask})")
+    }
+
+    fn warp_shuffle(value: &str, id: &str) -> String {
+        format!("__shfl({value}, {id})")
+    }
+
+    fn warp_shuffle_up(value: &str, delta: &str) -> String {
+        format!("__shfl_up({value}, {delta})")
+    }
+
+    fn warp_shuffle_down(value: &str, delta: &str) -> String {
+        format!("__shfl_down({value}, {delta})")
+    }
+
+    fn warp_ballot(input: &str) -> String {
+        // The mask already has 64 bits, one for each unit of the wavefront.
+        format!("__ballot({input})")
+    }
+}
@@ -11,8 +11,14 @@ pub enum Elem {
     F162,
     BF16,
     BF162,
+    I8,
+    I16,
     I32,
+    I64,
+    U8,
+    U16,
     U32,
+    U64,
     Bool,
 }
 
@@ -28,10 +34,16 @@ impl Display for Elem {
             Elem::F16 => f.write_str("__half"),
             Elem::F162 => f.write_str("__half2"),
             Elem::F32 => f.write_str("float"),
-            Elem::BF16 => f.write_str("__nv_bfloat16"),
-            Elem::BF162 => f.write_str("__nv_bfloat162"),
+            Elem::BF16 => f.write_str("bfloat16"),
+            Elem::BF162 => f.write_str("bfloat162"),
+            Elem::I8 => f.write_str("int8"),
+            Elem::I16 => f.write_str("int16"),
             Elem::I32 => f.write_str("int"),
+            Elem::I64 => f.write_str("int64"),
+            Elem::U8 => f.write_str("uint8"),
+            Elem::U16 => f.write_str("uint16"),
             Elem::U32 => f.write_str("uint"),
+            Elem::U64 => f.write_str("uint64"),
             Elem::Bool => f.write_str("bool"),
         }
     }
@@ -183,6 +195,8 @@ impl Display for Variable {
             // precision related problems.
             Variable::ConstantScalar(number, elem) => match number {
                 ConstantScalarValue::Int(val, kind) => match kind {
+                    gpu::IntKind::I8 => f.write_fmt(format_args!("{elem}({})", *val as i8)),
+                    gpu::IntKind::I16 => f.write_fmt(format_args!("{elem}({})", *val as i16)),
                     gpu::IntKind::I32 => f.write_fmt(format_args!("{elem}({})", *val as i32)),
                     gpu::IntKind::I64 => f.write_fmt(format_args!("{elem}({})", { *val })),
                 },
@@ -196,9 +210,12 @@ impl Display for Variable {
                     gpu::FloatKind::F32 => f.write_fmt(format_args!("{elem}({:?})", *val as f32)),
                     gpu::FloatKind::F64 => f.write_fmt(format_args!("{elem}({:?})", { *val })),
                 },
-                ConstantScalarValue::UInt(val) => {
-                    f.write_fmt(format_args!("{elem}({})", *val as u32))
-                }
+                ConstantScalarValue::UInt(val, kind) => match kind {
+                    gpu::UIntKind::U8 => f.write_fmt(format_args!("{elem}({})", *val as u8)),
+                    gpu::UIntKind::U16 => f.write_fmt(format_args!("{elem}({})", *val as u16)),
+                    gpu::UIntKind::U32 => f.write_fmt(format_args!("{elem}({})", *val as u32)),
+                    gpu::UIntKind::U64 => f.write_fmt(format_args!("{elem}({})", { *val })),
+                },
                 ConstantScalarValue::Bool(val) => f.write_fmt(format_args!("{}", val)),
             },
             Variable::SharedMemory(number, _, _) => {
@@ -467,8 +484,14 @@ impl Elem {
             Self::BF162 => 2 * core::mem::size_of::<bf16>(),
             Self::BF16 => core::mem::size_of::<bf16>(),
             Self::F32 => core::mem::size_of::<f32>(),
+            Self::I8 => core::mem::size_of::<i8>(),
+            Self::I16 => core::mem::size_of::<i16>(),
             Self::I32 => core::mem::size_of::<i32>(),
+            Self::I64 => core::mem::size_of::<i64>(),
+            Self::U8 => core::mem::size_of::<u8>(),
+            Self::U16 => core::mem::size_of::<u16>(),
             Self::U32 => core::mem::size_of::<u32>(),
+            Self::U64 => core::mem::size_of::<u64>(),
             Self::Bool => core::mem::size_of::<bool>(),
         }
     }
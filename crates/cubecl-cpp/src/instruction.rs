@@ -1,4 +1,6 @@
-use super::{binary::*, unary::*, Component, Elem, Variable, WarpInstruction, WmmaInstruction};
+use super::{
+    binary::*, unary::*, Component, Dialect, Elem, Variable, WarpInstruction, WmmaInstruction,
+};
 use std::fmt::Display;
 
 #[derive(Debug, Clone)]
@@ -15,7 +17,7 @@ pub struct UnaryInstruction {
 }
 
 #[derive(Debug, Clone)]
-pub enum Instruction {
+pub enum Instruction<D: Dialect> {
     Length {
         input: Variable,
         out: Variable,
@@ -64,12 +66,23 @@ pub enum Instruction {
         instructions_if: Vec<Self>,
         instructions_else: Vec<Self>,
     },
+    Switch {
+        value: Variable,
+        instructions_default: Vec<Self>,
+        cases: Vec<(Vec<Variable>, Vec<Self>)>,
+    },
     Slice {
         input: Variable,
         start: Variable,
         end: Variable,
         out: Variable,
     },
+    Call {
+        function: String,
+        inputs: Vec<Variable>,
+        output: Option<Variable>,
+        bindings: Vec<String>,
+    },
     Return,
     Break,
     Stride {
@@ -89,7 +102,26 @@ pub enum Instruction {
     LowerEqual(BinaryInstruction),
     GreaterEqual(BinaryInstruction),
     Erf(UnaryInstruction),
+    Tan(UnaryInstruction),
+    Asin(UnaryInstruction),
+    Acos(UnaryInstruction),
+    Atan(UnaryInstruction),
+    Atan2(BinaryInstruction),
+    Sinh(UnaryInstruction),
+    Cosh(UnaryInstruction),
+    Exp2(UnaryInstruction),
+    Log2(UnaryInstruction),
+    Round(UnaryInstruction),
+    Trunc(UnaryInstruction),
+    Fract(UnaryInstruction),
+    Sign(UnaryInstruction),
+    CopySign(BinaryInstruction),
+    Hypot(BinaryInstruction),
+    Rsqrt(UnaryInstruction),
+    IsNan(UnaryInstruction),
+    IsInf(UnaryInstruction),
     BitwiseAnd(BinaryInstruction),
+    BitwiseOr(BinaryInstruction),
     BitwiseXor(BinaryInstruction),
     ShiftLeft(BinaryInstruction),
     ShiftRight(BinaryInstruction),
@@ -105,6 +137,12 @@ pub enum Instruction {
     Min(BinaryInstruction),
     Max(BinaryInstruction),
     Not(UnaryInstruction),
+    BitwiseNot(UnaryInstruction),
+    Neg(UnaryInstruction),
+    CountOnes(UnaryInstruction),
+    ReverseBits(UnaryInstruction),
+    LeadingZeros(UnaryInstruction),
+    TrailingZeros(UnaryInstruction),
     Or(BinaryInstruction),
     And(BinaryInstruction),
     Clamp {
@@ -117,9 +155,10 @@ pub enum Instruction {
     ThreadFence,
     Ceil(UnaryInstruction),
     Floor(UnaryInstruction),
-    Wrap(WarpInstruction),
+    Wrap(WarpInstruction<D>),
     Wmma(WmmaInstruction),
     Bitcast(UnaryInstruction),
+    AtomicIndex(BinaryInstruction),
     AtomicLoad(UnaryInstruction),
     AtomicStore(UnaryInstruction),
     AtomicSwap(BinaryInstruction),
@@ -138,9 +177,26 @@ pub enum Instruction {
     },
 }
 
-impl Display for Instruction {
+impl<D: Dialect> Display for Instruction<D> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Instruction::Call {
+                function,
+                inputs,
+                output,
+                bindings,
+            } => {
+                let args = inputs
+                    .iter()
+                    .map(|input| input.to_string())
+                    .chain(bindings.iter().cloned())
+                    .collect::<Vec<_>>();
+
+                if let Some(output) = output {
+                    f.write_fmt(format_args!("{output} = "))?;
+                }
+                f.write_fmt(format_args!("{function}({});\n", args.join(", ")))
+            }
             Instruction::Return => f.write_str("return;"),
             Instruction::Break => f.write_str("break;"),
             Instruction::DeclareVariable { var } => match var {
@@ -170,6 +226,7 @@ impl Display for Instruction {
             Instruction::Sub(it) => Sub::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Modulo(inst) => Modulo::format(f, &inst.lhs, &inst.rhs, &inst.out),
             Instruction::BitwiseAnd(it) => BitwiseAnd::format(f, &it.lhs, &it.rhs, &it.out),
+            Instruction::BitwiseOr(it) => BitwiseOr::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::BitwiseXor(it) => BitwiseXor::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::ShiftLeft(it) => ShiftLeft::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::ShiftRight(it) => ShiftRight::format(f, &it.lhs, &it.rhs, &it.out),
@@ -231,6 +288,28 @@ for (uint {i} = {start}; {i} < {end}; {increment}) {{
                 }
                 f.write_str("}\n")
             }
+            Instruction::Switch {
+                value,
+                instructions_default,
+                cases,
+            } => {
+                f.write_fmt(format_args!("switch ({value}) {{\n"))?;
+                for (values, block) in cases {
+                    for val in values {
+                        f.write_fmt(format_args!("case {val}:\n"))?;
+                    }
+                    f.write_str("{\n")?;
+                    for i in block {
+                        f.write_fmt(format_args!("{i}"))?;
+                    }
+                    f.write_str("}\nbreak;\n")?;
+                }
+                f.write_str("default: {\n")?;
+                for i in instructions_default {
+                    f.write_fmt(format_args!("{i}"))?;
+                }
+                f.write_str("}\n}\n")
+            }
             Instruction::Stride { dim, position, out } => f.write_fmt(format_args!(
                 "{out} = info[({position} * rank_2) + {dim} + 1];\n"
             )),
@@ -244,6 +323,24 @@ for (uint {i} = {start}; {i} < {end}; {increment}) {{
             Instruction::LowerEqual(it) => LowerEqual::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::GreaterEqual(it) => GreaterEqual::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Erf(it) => Erf::format(f, &it.input, &it.out),
+            Instruction::Tan(it) => Tan::format(f, &it.input, &it.out),
+            Instruction::Asin(it) => Asin::format(f, &it.input, &it.out),
+            Instruction::Acos(it) => Acos::format(f, &it.input, &it.out),
+            Instruction::Atan(it) => Atan::format(f, &it.input, &it.out),
+            Instruction::Atan2(it) => Atan2::format(f, &it.lhs, &it.rhs, &it.out),
+            Instruction::Sinh(it) => Sinh::format(f, &it.input, &it.out),
+            Instruction::Cosh(it) => Cosh::format(f, &it.input, &it.out),
+            Instruction::Exp2(it) => Exp2::format(f, &it.input, &it.out),
+            Instruction::Log2(it) => Log2::format(f, &it.input, &it.out),
+            Instruction::Round(it) => Round::format(f, &it.input, &it.out),
+            Instruction::Trunc(it) => Trunc::format(f, &it.input, &it.out),
+            Instruction::Fract(it) => Fract::format(f, &it.input, &it.out),
+            Instruction::Sign(it) => Sign::format(f, &it.input, &it.out),
+            Instruction::CopySign(it) => CopySign::format(f, &it.lhs, &it.rhs, &it.out),
+            Instruction::Hypot(it) => Hypot::format(f, &it.lhs, &it.rhs, &it.out),
+            Instruction::Rsqrt(it) => Rsqrt::format(f, &it.input, &it.out),
+            Instruction::IsNan(it) => IsNan::format(f, &it.input, &it.out),
+            Instruction::IsInf(it) => IsInf::format(f, &it.input, &it.out),
             Instruction::Abs(it) => Abs::format(f, &it.input, &it.out),
             Instruction::Exp(it) => Exp::format(f, &it.input, &it.out),
             Instruction::Log(it) => Log::format(f, &it.input, &it.out),
@@ -256,6 +353,12 @@ for (uint {i} = {start}; {i} < {end}; {increment}) {{
             Instruction::Max(it) => Max::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Min(it) => Min::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Not(it) => Not::format(f, &it.input, &it.out),
+            Instruction::BitwiseNot(it) => BitwiseNot::format(f, &it.input, &it.out),
+            Instruction::Neg(it) => Neg::format(f, &it.input, &it.out),
+            Instruction::CountOnes(it) => CountOnes::format(f, &it.input, &it.out),
+            Instruction::ReverseBits(it) => ReverseBits::format(f, &it.input, &it.out),
+            Instruction::LeadingZeros(it) => LeadingZeros::format(f, &it.input, &it.out),
+            Instruction::TrailingZeros(it) => TrailingZeros::format(f, &it.input, &it.out),
             Instruction::Or(it) => Or::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::And(it) => And::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Clamp {
@@ -344,22 +447,36 @@ for (uint {i} = {start}; {i} < {end}; {increment}) {{
                 cmp,
                 val,
                 out,
-            } => f.write_fmt(format_args!("{out} = atomicCAS({input}, {cmp}, {val});\n")),
+            } => match input.elem() {
+                Elem::F32 => f.write_fmt(format_args!(
+                    "{out} = __uint_as_float(atomicCAS((unsigned int*){input}, __float_as_uint({cmp}), \
+                     __float_as_uint({val})));\n"
+                )),
+                _ => f.write_fmt(format_args!("{out} = atomicCAS({input}, {cmp}, {val});\n")),
+            },
             Instruction::AtomicSwap(BinaryInstruction { lhs, rhs, out }) => {
                 f.write_fmt(format_args!("{out} = atomicExch({lhs}, {rhs});\n"))
             }
             Instruction::AtomicAdd(BinaryInstruction { lhs, rhs, out }) => {
                 f.write_fmt(format_args!("{out} = atomicAdd({lhs}, {rhs});\n"))
             }
-            Instruction::AtomicSub(BinaryInstruction { lhs, rhs, out }) => {
-                f.write_fmt(format_args!("{out} = atomicSub({lhs}, {rhs});\n"))
-            }
-            Instruction::AtomicMax(BinaryInstruction { lhs, rhs, out }) => {
-                f.write_fmt(format_args!("{out} = atomicMax({lhs}, {rhs});\n"))
-            }
-            Instruction::AtomicMin(BinaryInstruction { lhs, rhs, out }) => {
-                f.write_fmt(format_args!("{out} = atomicMin({lhs}, {rhs});\n"))
-            }
+            Instruction::AtomicSub(BinaryInstruction { lhs, rhs, out }) => match lhs.elem() {
+                // There is no atomicSub for floats.
+                Elem::F32 => f.write_fmt(format_args!("{out} = atomicAdd({lhs}, -{rhs});\n")),
+                _ => f.write_fmt(format_args!("{out} = atomicSub({lhs}, {rhs});\n")),
+            },
+            Instruction::AtomicMax(BinaryInstruction { lhs, rhs, out }) => match lhs.elem() {
+                Elem::F32 => {
+                    AtomicFloatUpdate::format(f, lhs, out, format_args!("fmaxf(current, {rhs})"))
+                }
+                _ => f.write_fmt(format_args!("{out} = atomicMax({lhs}, {rhs});\n")),
+            },
+            Instruction::AtomicMin(BinaryInstruction { lhs, rhs, out }) => match lhs.elem() {
+                Elem::F32 => {
+                    AtomicFloatUpdate::format(f, lhs, out, format_args!("fminf(current, {rhs})"))
+                }
+                _ => f.write_fmt(format_args!("{out} = atomicMin({lhs}, {rhs});\n")),
+            },
             Instruction::AtomicAnd(BinaryInstruction { lhs, rhs, out }) => {
                 f.write_fmt(format_args!("{out} = atomicAnd({lhs}, {rhs});\n"))
             }
@@ -369,6 +486,10 @@ for (uint {i} = {start}; {i} < {end}; {increment}) {{
             Instruction::AtomicXor(BinaryInstruction { lhs, rhs, out }) => {
                 f.write_fmt(format_args!("{out} = atomicXor({lhs}, {rhs});\n"))
             }
+            Instruction::AtomicIndex(BinaryInstruction { lhs, rhs, out }) => {
+                let item = out.item();
+                f.write_fmt(format_args!("{item}* {out} = &{lhs}[{rhs}];\n"))
+            }
             Instruction::AtomicLoad(UnaryInstruction { input, out }) => {
                 f.write_fmt(format_args!("{out} = atomicAdd({input}, 0);\n"))
             }
@@ -380,6 +501,34 @@ for (uint {i} = {start}; {i} < {end}; {increment}) {{
     }
 }
 
+/// Update a float atomic with a compare and swap loop on its bits, for the operations without
+/// a native atomic function. The update can use the `current` value of the atomic.
+struct AtomicFloatUpdate;
+
+impl AtomicFloatUpdate {
+    fn format(
+        f: &mut core::fmt::Formatter<'_>,
+        atomic: &Variable,
+        out: &Variable,
+        update: core::fmt::Arguments<'_>,
+    ) -> core::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{
+unsigned int* bits = (unsigned int*){atomic};
+unsigned int old = *bits;
+unsigned int assumed;
+do {{
+assumed = old;
+float current = __uint_as_float(assumed);
+old = atomicCAS(bits, assumed, __float_as_uint({update}));
+}} while (assumed != old);
+{out} = __uint_as_float(old);
+}}
+"
+        ))
+    }
+}
+
 struct Fma;
 
 impl Fma {
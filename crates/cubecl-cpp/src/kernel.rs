@@ -1,4 +1,4 @@
-use super::{Body, Item};
+use super::{Body, Component, Dialect, Item, Variable};
 use cubecl_core::{ir::CubeDim, CompilerRepresentation};
 use std::{collections::HashSet, fmt::Display, io::Write, process::Command};
 
@@ -40,20 +40,32 @@ impl SharedMemory {
     }
 }
 
+/// A device function called by the kernel.
+///
+/// The inputs are declared as parameters, followed by all the bindings of the kernel.
 #[derive(Debug, Clone)]
-pub struct ComputeKernel {
+pub struct Function<D: Dialect> {
+    pub name: String,
+    pub inputs: Vec<Variable>,
+    pub output: Option<Variable>,
+    pub body: Body<D>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ComputeKernel<D: Dialect> {
     pub inputs: Vec<Binding>,
     pub outputs: Vec<Binding>,
     pub named: Vec<(String, Binding)>,
     pub cube_dim: CubeDim,
-    pub body: Body,
+    pub functions: Vec<Function<D>>,
+    pub body: Body<D>,
     pub wmma_activated: bool,
     pub bf16: bool,
     pub f16: bool,
     pub items: HashSet<super::Item>,
 }
 
-impl CompilerRepresentation for ComputeKernel {
+impl<D: Dialect> CompilerRepresentation for ComputeKernel<D> {
     fn shared_memory_size(&self) -> usize {
         let mut current = 0usize;
 
@@ -67,24 +79,27 @@ impl CompilerRepresentation for ComputeKernel {
     }
 }
 
-impl Display for ComputeKernel {
+impl<D: Dialect> Display for ComputeKernel<D> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.wmma_activated {
-            f.write_str("#include <mma.h>\n")?;
-        }
         if self.bf16 {
-            f.write_str("#include <cuda_bf16.h>\n")?;
+            D::include_bf16(f)?;
         }
 
         if self.f16 {
-            f.write_str("#include <cuda_fp16.h>\n")?;
+            D::include_f16(f)?;
         }
 
         if self.wmma_activated {
-            f.write_str("using namespace nvcuda;\n")?;
+            D::include_wmma(f)?;
         }
 
         f.write_str("typedef unsigned int uint;\n")?;
+        f.write_str("typedef signed char int8;\n")?;
+        f.write_str("typedef short int16;\n")?;
+        f.write_str("typedef long long int64;\n")?;
+        f.write_str("typedef unsigned char uint8;\n")?;
+        f.write_str("typedef unsigned short uint16;\n")?;
+        f.write_str("typedef unsigned long long uint64;\n")?;
 
         for item in self.items.iter() {
             if item.is_vec_native() {
@@ -111,38 +126,40 @@ struct __align__({alignment}) {item} {{"
             }
         }
 
+        for function in self.functions.iter() {
+            let output = match &function.output {
+                Some(output) => output.item().to_string(),
+                None => "void".to_string(),
+            };
+            let params = function
+                .inputs
+                .iter()
+                .map(|input| format!("{} {input}", input.item()))
+                .chain(self.bindings())
+                .collect::<Vec<_>>();
+
+            f.write_fmt(format_args!(
+                "\n__device__ {output} {}({}) {{\n{}",
+                function.name,
+                params.join(","),
+                function.body
+            ))?;
+
+            if let Some(output) = &function.output {
+                f.write_fmt(format_args!("return {output};\n"))?;
+            }
+
+            f.write_str("}\n")?;
+        }
+
         f.write_fmt(format_args!(
             "
 
 extern \"C\" __global__ void kernel(
-",
+{}",
+            self.bindings().join(",")
         ))?;
 
-        let num_bindings = self.inputs.len() + self.outputs.len() + self.named.len();
-        let mut binding_index = 0;
-        for (index, binding) in self.inputs.iter().enumerate() {
-            binding_index += 1;
-            f.write_fmt(format_args!("{} input_{}[]", binding.item, index))?;
-            if binding_index < num_bindings {
-                f.write_str(",")?;
-            }
-        }
-        for (index, binding) in self.outputs.iter().enumerate() {
-            binding_index += 1;
-            f.write_fmt(format_args!("{} output_{}[]", binding.item, index))?;
-            if binding_index < num_bindings {
-                f.write_str(",")?;
-            }
-        }
-        for (name, binding) in self.named.iter() {
-            binding_index += 1;
-            f.write_fmt(format_args!("{} {}[]", binding.item, name))?;
-
-            if binding_index < num_bindings {
-                f.write_str(",")?;
-            }
-        }
-
         f.write_str("\n) {\n")?;
 
         f.write_fmt(format_args!("{}", self.body))?;
@@ -152,8 +169,30 @@ extern \"C\" __global__ void kernel(
     }
 }
 
+impl<D: Dialect> ComputeKernel<D> {
+    /// The declaration of each binding, as parameters of the kernel.
+    fn bindings(&self) -> Vec<String> {
+        let inputs = self
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(index, binding)| format!("{} input_{}[]", binding.item, index));
+        let outputs = self
+            .outputs
+            .iter()
+            .enumerate()
+            .map(|(index, binding)| format!("{} output_{}[]", binding.item, index));
+        let named = self
+            .named
+            .iter()
+            .map(|(name, binding)| format!("{} {}[]", binding.item, name));
+
+        inputs.chain(outputs).chain(named).collect()
+    }
+}
+
 /// Format C++ code, useful when debugging.
-pub(crate) fn format_cpp_code(code: &str) -> Result<String, std::io::Error> {
+pub fn format_cpp_code(code: &str) -> Result<String, std::io::Error> {
     let mut child = Command::new("clang-format")
         .stdin(std::process::Stdio::piped())
         .stdout(std::process::Stdio::piped())
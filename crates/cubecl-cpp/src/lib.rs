@@ -1,8 +1,12 @@
+#[macro_use]
+extern crate derive_new;
+
 pub mod binary;
 pub mod unary;
 
 mod base;
 mod body;
+mod dialect;
 mod element;
 mod instruction;
 mod kernel;
@@ -12,6 +16,7 @@ mod warp;
 
 pub use base::*;
 pub use body::*;
+pub use dialect::*;
 pub use element::*;
 pub use instruction::*;
 pub use kernel::*;
@@ -0,0 +1,430 @@
+use super::{Component, Elem, Variable};
+use std::fmt::Display;
+
+pub trait Unary {
+    fn format(
+        f: &mut std::fmt::Formatter<'_>,
+        input: &Variable,
+        out: &Variable,
+    ) -> std::fmt::Result {
+        let item = out.item();
+
+        Self::unroll_vec(f, input, out, item.elem, item.vectorization)
+    }
+
+    fn format_scalar<Input, Out>(
+        f: &mut std::fmt::Formatter<'_>,
+        input: Input,
+        out: Out,
+        elem: Elem,
+    ) -> std::fmt::Result
+    where
+        Input: Component,
+        Out: Component;
+
+    fn unroll_vec(
+        f: &mut std::fmt::Formatter<'_>,
+        input: &Variable,
+        out: &Variable,
+        elem: Elem,
+        index: usize,
+    ) -> std::fmt::Result {
+        let optimized = Variable::optimized_args([*input, *out]);
+        let [input, out] = optimized.args;
+        let (index, elem) = match optimized.optimization_factor {
+            Some(factor) => (index / factor, out.elem()),
+            None => (index, elem),
+        };
+
+        for i in 0..index {
+            let inputi = input.index(i);
+            let outi = out.index(i);
+
+            Self::format_scalar(f, inputi, outi, elem)?;
+        }
+
+        Ok(())
+    }
+}
+
+macro_rules! function {
+    ($name:ident, $func:expr) => {
+        pub struct $name;
+
+        impl Unary for $name {
+            fn format_scalar<Input: Display, Out: Display>(
+                f: &mut std::fmt::Formatter<'_>,
+                input: Input,
+                out: Out,
+                elem: Elem,
+            ) -> std::fmt::Result {
+                match elem {
+                    Elem::F16 => f.write_fmt(format_args!("{out} = h{}({input});\n", $func)),
+                    Elem::F162 => f.write_fmt(format_args!("{out} = h2{}({input});\n", $func)),
+                    Elem::BF16 => f.write_fmt(format_args!("{out} = h{}({input});\n", $func)),
+                    Elem::BF162 => f.write_fmt(format_args!("{out} = h2{}({input});\n", $func)),
+                    Elem::F32 => f.write_fmt(format_args!("{out} = {}({input});\n", $func)),
+                    _ => f.write_fmt(format_args!("{out} = {}({input});\n", $func)),
+                }
+            }
+        }
+    };
+}
+
+function!(Abs, "abs");
+function!(Log, "log");
+function!(Log1p, "log1p");
+function!(Cos, "cos");
+function!(Sin, "sin");
+function!(Tanh, "tanh");
+function!(Sqrt, "sqrt");
+function!(Exp, "exp");
+function!(Erf, "erf");
+function!(Ceil, "ceil");
+function!(Floor, "floor");
+
+pub struct Not;
+
+impl Unary for Not {
+    fn format_scalar<Input, Out>(
+        f: &mut std::fmt::Formatter<'_>,
+        input: Input,
+        out: Out,
+        _elem: Elem,
+    ) -> std::fmt::Result
+    where
+        Input: Component,
+        Out: Component,
+    {
+        f.write_fmt(format_args!("{out} = !{input};\n"))
+    }
+}
+
+/// Half precision has no intrinsic for most functions, so they are computed in single precision
+/// one lane at a time.
+macro_rules! float_function {
+    ($name:ident, $func:expr) => {
+        pub struct $name;
+
+        impl Unary for $name {
+            fn format_scalar<Input, Out>(
+                f: &mut std::fmt::Formatter<'_>,
+                input: Input,
+                out: Out,
+                elem: Elem,
+            ) -> std::fmt::Result
+            where
+                Input: Component,
+                Out: Component,
+            {
+                match elem {
+                    Elem::F16 | Elem::BF16 => {
+                        f.write_fmt(format_args!("{out} = {elem}({}(float({input})));\n", $func))
+                    }
+                    _ => f.write_fmt(format_args!("{out} = {}({input});\n", $func)),
+                }
+            }
+
+            fn unroll_vec(
+                f: &mut std::fmt::Formatter<'_>,
+                input: &Variable,
+                out: &Variable,
+                elem: Elem,
+                index: usize,
+            ) -> std::fmt::Result {
+                for i in 0..index {
+                    Self::format_scalar(f, input.index(i), out.index(i), elem)?;
+                }
+
+                Ok(())
+            }
+        }
+    };
+}
+
+float_function!(Tan, "tan");
+float_function!(Asin, "asin");
+float_function!(Acos, "acos");
+float_function!(Atan, "atan");
+float_function!(Sinh, "sinh");
+float_function!(Cosh, "cosh");
+float_function!(Exp2, "exp2");
+float_function!(Log2, "log2");
+float_function!(Round, "round");
+float_function!(Trunc, "trunc");
+float_function!(Rsqrt, "rsqrt");
+
+/// A single precision expression of a half precision input, which is kept as is otherwise.
+fn as_float<Input: Component>(input: &Input) -> String {
+    match input.elem() {
+        Elem::F16 | Elem::BF16 => format!("float({input})"),
+        _ => format!("{input}"),
+    }
+}
+
+pub struct Fract;
+
+impl Unary for Fract {
+    fn format_scalar<Input, Out>(
+        f: &mut std::fmt::Formatter<'_>,
+        input: Input,
+        out: Out,
+        elem: Elem,
+    ) -> std::fmt::Result
+    where
+        Input: Component,
+        Out: Component,
+    {
+        let input = as_float(&input);
+        f.write_fmt(format_args!("{out} = {elem}({input} - trunc({input}));\n"))
+    }
+
+    fn unroll_vec(
+        f: &mut std::fmt::Formatter<'_>,
+        input: &Variable,
+        out: &Variable,
+        elem: Elem,
+        index: usize,
+    ) -> std::fmt::Result {
+        for i in 0..index {
+            Self::format_scalar(f, input.index(i), out.index(i), elem)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Zero and NaN are returned unchanged.
+pub struct Sign;
+
+impl Unary for Sign {
+    fn format_scalar<Input, Out>(
+        f: &mut std::fmt::Formatter<'_>,
+        input: Input,
+        out: Out,
+        elem: Elem,
+    ) -> std::fmt::Result
+    where
+        Input: Component,
+        Out: Component,
+    {
+        let input = as_float(&input);
+        f.write_fmt(format_args!(
+            "{out} = {elem}({input} > 0 ? 1 : ({input} < 0 ? -1 : {input}));\n"
+        ))
+    }
+
+    fn unroll_vec(
+        f: &mut std::fmt::Formatter<'_>,
+        input: &Variable,
+        out: &Variable,
+        elem: Elem,
+        index: usize,
+    ) -> std::fmt::Result {
+        for i in 0..index {
+            Self::format_scalar(f, input.index(i), out.index(i), elem)?;
+        }
+
+        Ok(())
+    }
+}
+
+macro_rules! float_predicate {
+    ($name:ident, $func:expr) => {
+        pub struct $name;
+
+        impl Unary for $name {
+            fn format_scalar<Input, Out>(
+                f: &mut std::fmt::Formatter<'_>,
+                input: Input,
+                out: Out,
+                _elem: Elem,
+            ) -> std::fmt::Result
+            where
+                Input: Component,
+                Out: Component,
+            {
+                f.write_fmt(format_args!("{out} = {}({});\n", $func, as_float(&input)))
+            }
+
+            fn unroll_vec(
+                f: &mut std::fmt::Formatter<'_>,
+                input: &Variable,
+                out: &Variable,
+                elem: Elem,
+                index: usize,
+            ) -> std::fmt::Result {
+                for i in 0..index {
+                    Self::format_scalar(f, input.index(i), out.index(i), elem)?;
+                }
+
+                Ok(())
+            }
+        }
+    };
+}
+
+float_predicate!(IsNan, "isnan");
+float_predicate!(IsInf, "isinf");
+
+macro_rules! prefix_operator {
+    ($name:ident, $op:expr) => {
+        pub struct $name;
+
+        impl Unary for $name {
+            fn format_scalar<Input, Out>(
+                f: &mut std::fmt::Formatter<'_>,
+                input: Input,
+                out: Out,
+                _elem: Elem,
+            ) -> std::fmt::Result
+            where
+                Input: Component,
+                Out: Component,
+            {
+                f.write_fmt(format_args!("{out} = {}{input};\n", $op))
+            }
+        }
+    };
+}
+
+prefix_operator!(BitwiseNot, "~");
+prefix_operator!(Neg, "-");
+
+/// The unsigned element of the same width, used to reinterpret an integer as its raw bits
+/// before widening it to the 32 bits intrinsics.
+fn unsigned(elem: Elem) -> Elem {
+    match elem {
+        Elem::I8 | Elem::U8 => Elem::U8,
+        Elem::I16 | Elem::U16 => Elem::U16,
+        Elem::I64 | Elem::U64 => Elem::U64,
+        _ => Elem::U32,
+    }
+}
+
+pub struct CountOnes;
+
+impl Unary for CountOnes {
+    fn format_scalar<Input, Out>(
+        f: &mut std::fmt::Formatter<'_>,
+        input: Input,
+        out: Out,
+        elem: Elem,
+    ) -> std::fmt::Result
+    where
+        Input: Component,
+        Out: Component,
+    {
+        match elem {
+            Elem::I64 | Elem::U64 => {
+                f.write_fmt(format_args!("{out} = {elem}(__popcll(uint64({input})));\n"))
+            }
+            _ => f.write_fmt(format_args!(
+                "{out} = {elem}(__popc(uint({}({input}))));\n",
+                unsigned(elem)
+            )),
+        }
+    }
+}
+
+pub struct ReverseBits;
+
+impl Unary for ReverseBits {
+    fn format_scalar<Input, Out>(
+        f: &mut std::fmt::Formatter<'_>,
+        input: Input,
+        out: Out,
+        elem: Elem,
+    ) -> std::fmt::Result
+    where
+        Input: Component,
+        Out: Component,
+    {
+        match elem {
+            Elem::I64 | Elem::U64 => {
+                f.write_fmt(format_args!("{out} = {elem}(__brevll(uint64({input})));\n"))
+            }
+            _ => f.write_fmt(format_args!(
+                "{out} = {elem}(__brev(uint({}({input}))) >> {});\n",
+                unsigned(elem),
+                32 - elem.size() * 8
+            )),
+        }
+    }
+}
+
+pub struct LeadingZeros;
+
+impl Unary for LeadingZeros {
+    fn format_scalar<Input, Out>(
+        f: &mut std::fmt::Formatter<'_>,
+        input: Input,
+        out: Out,
+        elem: Elem,
+    ) -> std::fmt::Result
+    where
+        Input: Component,
+        Out: Component,
+    {
+        match elem {
+            Elem::I64 | Elem::U64 => {
+                f.write_fmt(format_args!("{out} = {elem}(__clzll(int64({input})));\n"))
+            }
+            _ => f.write_fmt(format_args!(
+                "{out} = {elem}(__clz(int({}({input}))) - {});\n",
+                unsigned(elem),
+                32 - elem.size() * 8
+            )),
+        }
+    }
+}
+
+pub struct TrailingZeros;
+
+impl Unary for TrailingZeros {
+    fn format_scalar<Input, Out>(
+        f: &mut std::fmt::Formatter<'_>,
+        input: Input,
+        out: Out,
+        elem: Elem,
+    ) -> std::fmt::Result
+    where
+        Input: Component,
+        Out: Component,
+    {
+        let bits = elem.size() * 8;
+
+        // `__ffs` gives the one-based position of the first set bit, or zero for zero.
+        match elem {
+            Elem::I64 | Elem::U64 => f.write_fmt(format_args!(
+                "{out} = {elem}({input} == 0 ? {bits} : __ffsll(int64({input})) - 1);\n"
+            )),
+            _ => f.write_fmt(format_args!(
+                "{out} = {elem}({input} == 0 ? {bits} : __ffs(int({}({input}))) - 1);\n",
+                unsigned(elem)
+            )),
+        }
+    }
+}
+
+pub struct Assign;
+
+impl Unary for Assign {
+    fn format_scalar<Input, Out>(
+        f: &mut std::fmt::Formatter<'_>,
+        input: Input,
+        out: Out,
+        elem: Elem,
+    ) -> std::fmt::Result
+    where
+        Input: Component,
+        Out: Component,
+    {
+        // Cast only when necessary.
+        if elem != input.elem() {
+            f.write_fmt(format_args!("{out} = {elem}({input});\n"))
+        } else {
+            f.write_fmt(format_args!("{out} = {input};\n"))
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use std::{fmt::Display, marker::PhantomData};
+
+use super::{Component, Dialect, Variable};
+
+#[derive(Clone, Debug)]
+pub enum WarpInstruction<D: Dialect> {
+    ReduceSum {
+        input: Variable,
+        out: Variable,
+    },
+    ReduceProd {
+        input: Variable,
+        out: Variable,
+    },
+    ReduceMax {
+        input: Variable,
+        out: Variable,
+    },
+    ReduceMin {
+        input: Variable,
+        out: Variable,
+    },
+    Shuffle {
+        input: Variable,
+        id: Variable,
+        out: Variable,
+    },
+    ShuffleXor {
+        input: Variable,
+        mask: Variable,
+        out: Variable,
+    },
+    ShuffleUp {
+        input: Variable,
+        delta: Variable,
+        out: Variable,
+    },
+    ShuffleDown {
+        input: Variable,
+        delta: Variable,
+        out: Variable,
+    },
+    InclusiveSum {
+        input: Variable,
+        out: Variable,
+    },
+    ExclusiveSum {
+        input: Variable,
+        out: Variable,
+    },
+    InclusiveProd {
+        input: Variable,
+        out: Variable,
+    },
+    ExclusiveProd {
+        input: Variable,
+        out: Variable,
+    },
+    Ballot {
+        input: Variable,
+        out: Variable,
+    },
+    #[doc(hidden)]
+    _Dialect(PhantomData<D>),
+}
+
+impl<D: Dialect> Display for WarpInstruction<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WarpInstruction::ReduceSum { input, out } => {
+                reduce::<D>(f, input, out, |out, other| format!("{out} += {other}"))
+            }
+            WarpInstruction::ReduceProd { input, out } => {
+                reduce::<D>(f, input, out, |out, other| format!("{out} *= {other}"))
+            }
+            WarpInstruction::ReduceMax { input, out } => {
+                reduce::<D>(f, input, out, |out, other| {
+                    format!("{out} = max({out}, {other})")
+                })
+            }
+            WarpInstruction::ReduceMin { input, out } => {
+                reduce::<D>(f, input, out, |out, other| {
+                    format!("{out} = min({out}, {other})")
+                })
+            }
+            WarpInstruction::Shuffle { input, id, out } => f.write_fmt(format_args!(
+                "{out} = {};\n",
+                D::warp_shuffle(&input.to_string(), &id.to_string())
+            )),
+            WarpInstruction::ShuffleXor { input, mask, out } => f.write_fmt(format_args!(
+                "{out} = {};\n",
+                D::warp_shuffle_xor(&input.to_string(), &mask.to_string())
+            )),
+            WarpInstruction::ShuffleUp { input, delta, out } => f.write_fmt(format_args!(
+                "{out} = {};\n",
+                D::warp_shuffle_up(&input.to_string(), &delta.to_string())
+            )),
+            WarpInstruction::ShuffleDown { input, delta, out } => f.write_fmt(format_args!(
+                "{out} = {};\n",
+                D::warp_shuffle_down(&input.to_string(), &delta.to_string())
+            )),
+            WarpInstruction::InclusiveSum { input, out } => {
+                scan::<D>(f, input, out, None, |out, other| {
+                    format!("{out} += {other}")
+                })
+            }
+            WarpInstruction::ExclusiveSum { input, out } => {
+                scan::<D>(f, input, out, Some("0"), |out, other| {
+                    format!("{out} += {other}")
+                })
+            }
+            WarpInstruction::InclusiveProd { input, out } => {
+                scan::<D>(f, input, out, None, |out, other| {
+                    format!("{out} *= {other}")
+                })
+            }
+            WarpInstruction::ExclusiveProd { input, out } => {
+                scan::<D>(f, input, out, Some("1"), |out, other| {
+                    format!("{out} *= {other}")
+                })
+            }
+            WarpInstruction::Ballot { input, out } => {
+                let ballot = D::warp_ballot(&input.to_string());
+                f.write_fmt(format_args!(
+                    "
+{{
+    unsigned long long ballot = {ballot};
+    {} = uint(ballot);
+    {} = uint(ballot >> 32);
+    {} = 0;
+    {} = 0;
+}}
+",
+                    out.index(0),
+                    out.index(1),
+                    out.index(2),
+                    out.index(3)
+                ))
+            }
+            WarpInstruction::_Dialect(_) => Ok(()),
+        }
+    }
+}
+
+/// Reduce the values of the warp with a butterfly pattern, so that every unit gets the result.
+fn reduce<D: Dialect>(
+    f: &mut std::fmt::Formatter<'_>,
+    input: &Variable,
+    out: &Variable,
+    accumulate: impl Fn(&Variable, &str) -> String,
+) -> std::fmt::Result {
+    let shuffle = D::warp_shuffle_xor(&out.to_string(), "offset");
+    let accumulate = accumulate(out, &shuffle);
+
+    f.write_fmt(format_args!(
+        "
+{out} = {input};
+{{
+    for (int offset = warpSizeChecked / 2; offset > 0; offset /= 2) {{
+        {accumulate};
+    }}
+}}
+"
+    ))
+}
+
+/// Scan the values of the warp with the Hillis-Steele algorithm, each unit accumulating the value
+/// of the unit `offset` places before it, with an offset doubling at each step.
+///
+/// The exclusive scans shift the inclusive result by one unit, the first unit getting the
+/// identity.
+fn scan<D: Dialect>(
+    f: &mut std::fmt::Formatter<'_>,
+    input: &Variable,
+    out: &Variable,
+    identity: Option<&str>,
+    accumulate: impl Fn(&Variable, &str) -> String,
+) -> std::fmt::Result {
+    let item = out.item();
+    let shuffle = D::warp_shuffle_up(&out.to_string(), "offset");
+    let accumulate = accumulate(out, "other");
+
+    f.write_fmt(format_args!(
+        "
+{out} = {input};
+{{
+    int lane = threadIdxGlobal % warpSizeChecked;
+    for (int offset = 1; offset < warpSizeChecked; offset *= 2) {{
+        {item} other = {shuffle};
+        if (lane >= offset) {{
+            {accumulate};
+        }}
+    }}
+"
+    ))?;
+
+    if let Some(identity) = identity {
+        let shuffle = D::warp_shuffle_up(&out.to_string(), "1");
+        f.write_fmt(format_args!(
+            "    {item} previous = {shuffle};
+    {out} = lane == 0 ? {item}({identity}) : previous;
+"
+        ))?;
+    }
+
+    f.write_str("}\n")
+}
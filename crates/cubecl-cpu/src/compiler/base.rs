@@ -0,0 +1,221 @@
+use super::{CpuKernel, Function, Instruction};
+use crate::interpreter::elem_size;
+use cubecl_core::ir as cube;
+use cubecl_runtime::{server::ComputeError, ExecutionMode};
+
+/// Cpu Compiler.
+///
+/// Lowers the kernel definition to a tree of instructions that are interpreted by the
+/// [cpu server](crate::CpuServer).
+#[derive(Clone, Default)]
+pub struct CpuCompiler {
+    cooperative: bool,
+}
+
+impl core::fmt::Debug for CpuCompiler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CpuCompiler")
+    }
+}
+
+impl cubecl_core::Compiler for CpuCompiler {
+    type Representation = CpuKernel;
+
+    fn compile(kernel: cube::KernelDefinition, mode: ExecutionMode) -> Self::Representation {
+        Self::try_compile(kernel, mode).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    fn try_compile(
+        kernel: cube::KernelDefinition,
+        _mode: ExecutionMode,
+    ) -> Result<Self::Representation, ComputeError> {
+        let mut compiler = Self::default();
+        compiler.compile_kernel(kernel)
+    }
+
+    fn elem_size(elem: cube::Elem) -> usize {
+        elem_size(elem)
+    }
+
+    fn max_shared_memory_size() -> usize {
+        // Same limit as most GPUs, so kernels behave the same on the reference runtime.
+        49152
+    }
+
+    fn optimizations() -> cube::Optimizations {
+        cube::Optimizations::all()
+    }
+}
+
+impl CpuCompiler {
+    fn compile_kernel(
+        &mut self,
+        mut value: cube::KernelDefinition,
+    ) -> Result<CpuKernel, ComputeError> {
+        value
+            .emulate_cmma()
+            .map_err(|err| ComputeError::Compilation(err.to_string()))?;
+
+        let functions = value
+            .functions
+            .into_iter()
+            .map(|function| self.compile_function(function))
+            .collect();
+        let body = self.compile_scope(&mut value.body);
+
+        Ok(CpuKernel {
+            inputs: value.inputs,
+            outputs: value.outputs,
+            named: value.named,
+            cube_dim: value.cube_dim,
+            cooperative: self.cooperative,
+            functions,
+            body,
+        })
+    }
+
+    fn compile_function(&mut self, mut value: cube::Function) -> Function {
+        let mut body = self.compile_scope(&mut value.scope);
+        body.retain(|instruction| match instruction {
+            Instruction::DeclareVariable { var } => !value.inputs.contains(var),
+            _ => true,
+        });
+
+        Function {
+            inputs: value.inputs,
+            output: value.output,
+            body,
+        }
+    }
+
+    fn compile_scope(&mut self, value: &mut cube::Scope) -> Vec<Instruction> {
+        let mut instructions = Vec::new();
+        let processing = value.process_with(&<Self as cubecl_core::Compiler>::optimizations());
+
+        for var in processing.variables {
+            // Slices are references created by the slice operator, they don't need a declaration.
+            if let cube::Variable::Slice { .. } = var {
+                continue;
+            }
+
+            instructions.push(Instruction::DeclareVariable { var });
+        }
+
+        processing
+            .operations
+            .into_iter()
+            .for_each(|op| self.compile_operation(&mut instructions, op, value));
+
+        instructions
+    }
+
+    fn compile_operation(
+        &mut self,
+        instructions: &mut Vec<Instruction>,
+        operation: cube::Operation,
+        scope: &mut cube::Scope,
+    ) {
+        match operation {
+            cube::Operation::Operator(op) => instructions.push(Instruction::Operator(op)),
+            cube::Operation::Procedure(proc) => self.compile_procedure(instructions, proc, scope),
+            cube::Operation::Metadata(op) => instructions.push(Instruction::Metadata(op)),
+            cube::Operation::Branch(val) => self.compile_branch(instructions, val),
+            cube::Operation::Synchronization(val) => {
+                self.cooperative = true;
+                instructions.push(match val {
+                    cube::Synchronization::SyncUnits => Instruction::SyncUnits,
+                    cube::Synchronization::SyncStorage => Instruction::SyncStorage,
+                });
+            }
+            cube::Operation::Subcube(op) => {
+                self.cooperative = true;
+                instructions.push(Instruction::Subcube(op));
+            }
+            cube::Operation::CoopMma(_) => {
+                unreachable!("Cooperative matrix operations are emulated before the compilation.")
+            }
+            cube::Operation::Call(call) => instructions.push(Instruction::Call(call)),
+        }
+    }
+
+    fn compile_branch(&mut self, instructions: &mut Vec<Instruction>, branch: cube::Branch) {
+        match branch {
+            cube::Branch::If(mut op) => instructions.push(Instruction::If {
+                cond: op.cond,
+                instructions: self.compile_scope(&mut op.scope),
+            }),
+            cube::Branch::IfElse(mut op) => instructions.push(Instruction::IfElse {
+                cond: op.cond,
+                instructions_if: self.compile_scope(&mut op.scope_if),
+                instructions_else: self.compile_scope(&mut op.scope_else),
+            }),
+            cube::Branch::Switch(mut op) => instructions.push(Instruction::Switch {
+                value: op.value,
+                instructions_default: self.compile_scope(&mut op.scope_default),
+                cases: op
+                    .cases
+                    .into_iter()
+                    .map(|(values, mut scope)| (values, self.compile_scope(&mut scope)))
+                    .collect(),
+            }),
+            cube::Branch::Return => instructions.push(Instruction::Return),
+            cube::Branch::Break => instructions.push(Instruction::Break),
+            cube::Branch::RangeLoop(mut range_loop) => instructions.push(Instruction::RangeLoop {
+                i: range_loop.i,
+                start: range_loop.start,
+                end: range_loop.end,
+                step: range_loop.step,
+                instructions: self.compile_scope(&mut range_loop.scope),
+            }),
+            cube::Branch::Loop(mut op) => instructions.push(Instruction::Loop {
+                instructions: self.compile_scope(&mut op.scope),
+            }),
+        };
+    }
+
+    fn compile_procedure(
+        &mut self,
+        instructions: &mut Vec<Instruction>,
+        proc: cube::Procedure,
+        scope: &mut cube::Scope,
+    ) {
+        let mut compile = |scope: &mut cube::Scope| {
+            instructions.extend(self.compile_scope(scope));
+        };
+
+        match proc {
+            cube::Procedure::ReadGlobalWithLayout(proc) => {
+                proc.expand(scope);
+                compile(scope);
+            }
+            cube::Procedure::ReadGlobal(proc) => {
+                proc.expand(scope);
+                compile(scope);
+            }
+            cube::Procedure::WriteGlobal(proc) => {
+                proc.expand(scope);
+                compile(scope);
+            }
+            cube::Procedure::ConditionalAssign(proc) => {
+                proc.expand(scope);
+                compile(scope);
+            }
+            cube::Procedure::CheckedIndex(proc) => {
+                proc.expand(scope);
+                compile(scope);
+            }
+            cube::Procedure::CheckedIndexAssign(proc) => {
+                proc.expand(scope);
+                compile(scope);
+            }
+            cube::Procedure::IndexOffsetGlobalWithLayout(proc) => {
+                proc.expand(scope);
+                compile(scope);
+            }
+            cube::Procedure::EarlyReturn(proc) => {
+                proc.expand(scope);
+                compile(scope);
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use alloc::sync::Arc;
+use cubecl_core::{ir as cube, CompilerRepresentation};
+use serde::{Deserialize, Serialize};
+use std::{any::Any, fmt::Display};
+
+/// A kernel lowered to a tree of [instructions](Instruction) that can be interpreted on the CPU.
+///
+/// The kernel is displayed as JSON for debugging, but the [cpu server](crate::CpuServer) executes
+/// the compiled representation directly, since JSON can't hold non-finite float constants.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CpuKernel {
+    pub inputs: Vec<cube::Binding>,
+    pub outputs: Vec<cube::Binding>,
+    pub named: Vec<(String, cube::Binding)>,
+    pub cube_dim: cube::CubeDim,
+    /// Whether the units of a cube have to run concurrently, which is the case when the kernel
+    /// synchronizes units or uses subcube operations.
+    pub cooperative: bool,
+    pub functions: Vec<Function>,
+    pub body: Vec<Instruction>,
+}
+
+/// A function called by the kernel, executed with its own local variables.
+///
+/// The inputs aren't declared by the body, they are assigned from the arguments of the call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub struct Function {
+    pub inputs: Vec<cube::Variable>,
+    pub output: Option<cube::Variable>,
+    pub body: Vec<Instruction>,
+}
+
+/// All instructions that can be interpreted by the [cpu server](crate::CpuServer).
+///
+/// Procedures are expanded during compilation, so only operations that map directly to the
+/// interpreter are left.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub enum Instruction {
+    DeclareVariable {
+        var: cube::Variable,
+    },
+    Operator(cube::Operator),
+    Metadata(cube::Metadata),
+    Subcube(cube::Subcube),
+    SyncUnits,
+    SyncStorage,
+    If {
+        cond: cube::Variable,
+        instructions: Vec<Instruction>,
+    },
+    IfElse {
+        cond: cube::Variable,
+        instructions_if: Vec<Instruction>,
+        instructions_else: Vec<Instruction>,
+    },
+    Switch {
+        value: cube::Variable,
+        instructions_default: Vec<Instruction>,
+        cases: Vec<(Vec<cube::Variable>, Vec<Instruction>)>,
+    },
+    RangeLoop {
+        i: cube::Variable,
+        start: cube::Variable,
+        end: cube::Variable,
+        step: Option<cube::Variable>,
+        instructions: Vec<Instruction>,
+    },
+    Loop {
+        instructions: Vec<Instruction>,
+    },
+    Call(cube::Call),
+    Return,
+    Break,
+}
+
+impl Display for CpuKernel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let source = serde_json::to_string(self).map_err(|_| std::fmt::Error)?;
+        f.write_str(&source)
+    }
+}
+
+impl CompilerRepresentation for CpuKernel {
+    fn shared_memory_size(&self) -> usize {
+        // Shared memories are allocated by the interpreter when they are first accessed.
+        0
+    }
+
+    fn into_repr(self) -> Option<Arc<dyn Any + Send + Sync>> {
+        Some(Arc::new(self))
+    }
+}
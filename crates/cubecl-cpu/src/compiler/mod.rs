@@ -0,0 +1,5 @@
+mod base;
+mod kernel;
+
+pub use base::*;
+pub use kernel::*;
@@ -0,0 +1,360 @@
+use crate::{
+    compiler::CpuKernel,
+    interpreter::{self, KernelBindings},
+};
+use alloc::sync::Arc;
+use core::time::Duration;
+use cubecl_common::{
+    benchmark::{TimestampsError, TimestampsResult},
+    reader::reader_from_concrete,
+    reader::Reader,
+    sync_type::SyncType,
+};
+use cubecl_core::{compute::DebugInformation, prelude::*, FeatureSet, KernelId};
+use cubecl_runtime::{
+    debug::DebugLogger,
+    memory_management::{MemoryManagement, MemoryUsage},
+    profiler::{ProfileEventKind, ProfileTrace, Profiler},
+    server::{self, ComputeError, ComputeServer},
+    staging::StagingBuffer,
+    storage::{BytesResource, BytesStorage, ComputeStorage},
+    stream::{EventId, OrderedStreams, StreamId},
+    ExecutionMode,
+};
+use hashbrown::HashMap;
+use std::time::Instant;
+
+/// Cpu compute server.
+///
+/// Kernels are interpreted synchronously when they are executed, which makes this server a
+/// reference implementation to validate the other runtimes against.
+#[derive(Debug)]
+pub struct CpuServer<MM: MemoryManagement<BytesStorage>> {
+    memory_management: MM,
+    kernels: HashMap<KernelId, Arc<CpuKernel>>,
+    logger: DebugLogger,
+    profiler: Profiler,
+    streams: OrderedStreams,
+    /// The time spent executing kernels since the last [sync_elapsed](ComputeServer::sync_elapsed)
+    /// call, when the timestamps are enabled.
+    elapsed: Option<Duration>,
+}
+
+impl<MM> CpuServer<MM>
+where
+    MM: MemoryManagement<BytesStorage>,
+{
+    /// Create a new server.
+    pub fn new(memory_management: MM) -> Self {
+        Self {
+            memory_management,
+            kernels: HashMap::new(),
+            logger: DebugLogger::new(),
+            profiler: Profiler::new(),
+            streams: OrderedStreams::default(),
+            elapsed: None,
+        }
+    }
+
+    fn kernel(
+        &mut self,
+        kernel: <Self as ComputeServer>::Kernel,
+        mode: ExecutionMode,
+    ) -> Result<Arc<CpuKernel>, ComputeError> {
+        let mut kernel_id = kernel.id();
+        kernel_id.mode(mode);
+
+        if let Some(kernel) = self.kernels.get(&kernel_id) {
+            return Ok(kernel.clone());
+        }
+
+        let started = self.profiler.now();
+        let mut compile = kernel.try_compile(mode)?;
+        if self.logger.is_activated() {
+            compile.debug_info = Some(DebugInformation::new("cpu", kernel_id.clone()));
+            compile.ir = kernel.ir();
+        }
+
+        let mut compile = self.logger.debug(compile);
+        let kernel = compile
+            .repr
+            .take()
+            .and_then(|repr| repr.downcast::<CpuKernel>().ok())
+            .ok_or_else(|| {
+                ComputeError::Compilation(format!(
+                    "Kernel {kernel_id} wasn't compiled by the cpu compiler"
+                ))
+            })?;
+
+        self.profiler.register(
+            started,
+            ProfileEventKind::Compile {
+                name: compile.name.unwrap_or_default().into(),
+                id: kernel_id.to_string(),
+            },
+        );
+        self.kernels.insert(kernel_id, kernel.clone());
+
+        Ok(kernel)
+    }
+}
+
+impl<MM> ComputeServer for CpuServer<MM>
+where
+    MM: MemoryManagement<BytesStorage>,
+{
+    type Kernel = Box<dyn CubeTask>;
+    type DispatchOptions = CubeCount<Self>;
+    type Storage = BytesStorage;
+    type MemoryManagement = MM;
+    type FeatureSet = FeatureSet;
+
+    fn try_read(&mut self, binding: server::Binding<Self>) -> Result<Reader, ComputeError> {
+        let started = self.profiler.now();
+        let resource = self.memory_management.try_get_resource(binding.memory)?;
+        let data = resource.read().to_vec();
+
+        self.profiler
+            .register(started, ProfileEventKind::Read { size: data.len() });
+
+        Ok(reader_from_concrete(data))
+    }
+
+    fn get_resource(&mut self, binding: server::Binding<Self>) -> BytesResource {
+        let handle = self.memory_management.get(binding.memory);
+        self.memory_management.storage().get(&handle)
+    }
+
+    fn try_create(&mut self, data: &[u8]) -> Result<server::Handle<Self>, ComputeError> {
+        let started = self.profiler.now();
+        let handle = server::Handle::new(
+            self.memory_management.try_reserve(data.len(), &[])?,
+            data.len(),
+        );
+        let resource = self.get_resource(handle.clone().binding());
+
+        resource.write().copy_from_slice(data);
+
+        self.profiler
+            .register(started, ProfileEventKind::Create { size: data.len() });
+
+        Ok(handle)
+    }
+
+    fn try_empty(&mut self, size: usize) -> Result<server::Handle<Self>, ComputeError> {
+        let handle = server::Handle::new(self.memory_management.try_reserve(size, &[])?, size);
+        let resource = self.get_resource(handle.clone().binding());
+
+        // Reused memory is cleared, so reading an empty handle is deterministic.
+        resource.write().fill(0);
+
+        Ok(handle)
+    }
+
+    fn try_staging(&mut self, size: usize) -> Result<StagingBuffer, ComputeError> {
+        Ok(vec![0; size].into())
+    }
+
+    fn try_upload(
+        &mut self,
+        buffer: StagingBuffer,
+        stream: StreamId,
+    ) -> Result<server::Handle<Self>, ComputeError> {
+        self.streams.validate(stream)?;
+        // The data lives on the host, so the upload is a copy that completes right away.
+        self.try_create(&buffer)
+    }
+
+    unsafe fn try_execute(
+        &mut self,
+        kernel: Self::Kernel,
+        count: Self::DispatchOptions,
+        bindings: Vec<server::Binding<Self>>,
+        mode: ExecutionMode,
+        stream: StreamId,
+    ) -> Result<(), ComputeError> {
+        self.streams.validate(stream)?;
+
+        let profile = self
+            .profiler
+            .is_activated()
+            .then(|| (kernel.name(), kernel.id().to_string()));
+        let kernel = self.kernel(kernel, mode)?;
+        let started = self.profiler.now();
+
+        let cube_count = match count {
+            CubeCount::Static(x, y, z) => [x, y, z],
+            CubeCount::Dynamic(binding) => {
+                let resource = self.memory_management.try_get_resource(binding.memory)?;
+                let bytes = resource.read();
+
+                if bytes.len() < 3 * core::mem::size_of::<u32>() {
+                    return Err(ComputeError::InvalidBinding(format!(
+                        "The dynamic cube count should hold 3 u32 values, got {} bytes",
+                        bytes.len()
+                    )));
+                }
+
+                let count = |i: usize| {
+                    u32::from_le_bytes([
+                        bytes[i * 4],
+                        bytes[i * 4 + 1],
+                        bytes[i * 4 + 2],
+                        bytes[i * 4 + 3],
+                    ])
+                };
+
+                [count(0), count(1), count(2)]
+            }
+        };
+
+        let buffers = bindings
+            .into_iter()
+            .map(|binding| {
+                let resource = self.memory_management.try_get_resource(binding.memory)?;
+                let bytes = resource.write();
+                Ok((bytes.as_mut_ptr(), bytes.len()))
+            })
+            .collect::<Result<Vec<_>, ComputeError>>()?;
+
+        let binding_sizes: Vec<usize> = buffers.iter().map(|(_, size)| *size).collect();
+        let bindings = KernelBindings::new(&kernel, buffers)?;
+        let start = Instant::now();
+        interpreter::execute(&kernel, bindings, cube_count);
+
+        if let Some(elapsed) = self.elapsed.as_mut() {
+            *elapsed += start.elapsed();
+        }
+
+        if let Some((name, id)) = profile {
+            self.profiler.register(
+                started,
+                ProfileEventKind::Execute {
+                    name: name.into(),
+                    id,
+                    cube_count: Some(cube_count),
+                    binding_sizes,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    fn try_create_stream(&mut self) -> Result<StreamId, ComputeError> {
+        Ok(self.streams.create())
+    }
+
+    fn try_record_event(&mut self, stream: StreamId) -> Result<EventId, ComputeError> {
+        self.streams.record_event(stream)
+    }
+
+    fn try_wait_event(&mut self, stream: StreamId, event: EventId) -> Result<(), ComputeError> {
+        self.streams.wait_event(stream, event)
+    }
+
+    fn sync(&mut self, _sync_type: SyncType) {
+        // Kernels are executed synchronously, so there is nothing to wait for.
+    }
+
+    fn memory_usage(&mut self) -> MemoryUsage {
+        self.memory_management.memory_usage()
+    }
+
+    fn memory_cleanup(&mut self) {
+        self.memory_management.cleanup();
+    }
+
+    fn enable_timestamps(&mut self) {
+        self.elapsed.get_or_insert(Duration::ZERO);
+    }
+
+    fn disable_timestamps(&mut self) {
+        self.elapsed = None;
+    }
+
+    fn timestamps_enabled(&mut self) -> bool {
+        self.elapsed.is_some()
+    }
+
+    fn sync_elapsed(&mut self) -> TimestampsResult {
+        match self.elapsed.as_mut() {
+            Some(elapsed) => Ok(core::mem::take(elapsed)),
+            None => Err(TimestampsError::Disabled),
+        }
+    }
+
+    fn start_profile(&mut self) {
+        self.profiler.start();
+    }
+
+    fn end_profile(&mut self) -> ProfileTrace {
+        self.profiler.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CpuCompiler, CpuRuntime};
+    use cubecl_core as cubecl;
+    use cubecl_core::{ir::KernelDefinition, prelude::*, Kernel, KernelSettings};
+    use cubecl_runtime::server::ComputeError;
+
+    #[cube(launch)]
+    fn kernel_non_finite_constant(output: &mut Array<F32>) {
+        if UNIT_POS == 0 {
+            output[0] = Comptime::runtime(Comptime::new(F32::new(f32::INFINITY)));
+            output[1] = Comptime::runtime(Comptime::new(F32::new(f32::NEG_INFINITY)));
+        }
+    }
+
+    #[test]
+    fn should_execute_kernel_with_non_finite_constants() {
+        let client = CpuRuntime::client(&Default::default());
+        let output = client.empty(2 * core::mem::size_of::<f32>());
+
+        kernel_non_finite_constant::launch::<CpuRuntime>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::default(),
+            unsafe { ArrayArg::from_raw_parts(&output, 2, 1) },
+        );
+
+        let actual = client.read(output.binding());
+        let actual = f32::from_bytes(&actual);
+
+        assert_eq!(actual, &[f32::INFINITY, f32::NEG_INFINITY]);
+    }
+
+    struct EmptyKernel;
+
+    impl Kernel for EmptyKernel {
+        fn define(&self) -> KernelDefinition {
+            KernelBuilder::default().build(KernelSettings::default())
+        }
+    }
+
+    #[test]
+    fn should_reject_short_dynamic_cube_count() {
+        let client = CpuRuntime::client(&Default::default());
+        let count = client.create(u32::as_bytes(&[1, 1]));
+
+        let result = client.try_execute(
+            Box::new(KernelTask::<CpuCompiler, _>::new(EmptyKernel)),
+            CubeCount::Dynamic(count.binding()),
+            Vec::new(),
+        );
+
+        assert!(matches!(result, Err(ComputeError::InvalidBinding(_))));
+    }
+
+    #[test]
+    fn should_emulate_cmma_with_less_units_than_a_warp() {
+        let client = CpuRuntime::client(&Default::default());
+
+        cubecl_core::runtime_tests::cmma::test_simple_1_cube_dim::<CpuRuntime>(
+            client,
+            CubeDim::new(8, 2, 1),
+        );
+    }
+}
@@ -0,0 +1,16 @@
+/// The device struct when using the `cpu` runtime.
+///
+/// The index only identifies a client, every device executes kernels on the host.
+///
+/// # Example
+///
+/// ```no_run
+/// use cubecl_cpu::CpuDevice;
+///
+/// let device = CpuDevice::new(0);
+/// ```
+#[derive(new, Clone, Debug, Hash, PartialEq, Eq, Default)]
+pub struct CpuDevice {
+    /// The index of the device.
+    pub index: usize,
+}
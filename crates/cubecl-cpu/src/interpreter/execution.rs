@@ -0,0 +1,152 @@
+use super::{CubeSync, Memory, OwnedMemory, Unit};
+use crate::compiler::CpuKernel;
+use cubecl_core::ir::{Elem, Item, UIntKind};
+use cubecl_runtime::server::ComputeError;
+use std::sync::Mutex;
+
+/// The memory bound to a kernel execution.
+pub(crate) struct KernelBindings {
+    pub inputs: Vec<Memory>,
+    pub outputs: Vec<Memory>,
+    pub info: Option<Memory>,
+    pub scalars: Vec<(Elem, Memory)>,
+}
+
+impl KernelBindings {
+    /// Map the buffers to the kernel, following the same order used when launching a kernel:
+    /// inputs, outputs, the info buffer and then the scalars.
+    ///
+    /// The info buffer is only bound when at least one tensor is registered.
+    ///
+    /// # Safety
+    ///
+    /// Every buffer must be valid for reads and writes of its size during the kernel execution.
+    pub(crate) unsafe fn new(
+        kernel: &CpuKernel,
+        buffers: Vec<(*mut u8, usize)>,
+    ) -> Result<Self, ComputeError> {
+        let num_arrays = kernel.inputs.len() + kernel.outputs.len();
+        let num_expected = num_arrays + kernel.named.len();
+        let has_info = match buffers.len() {
+            len if len == num_expected => true,
+            len if len + 1 == num_expected => false,
+            len => {
+                return Err(ComputeError::InvalidBinding(format!(
+                    "Expected {num_expected} bindings, got {len}."
+                )))
+            }
+        };
+
+        let info_item = has_info.then(|| Item::new(Elem::UInt(UIntKind::U32)));
+        let items = kernel
+            .inputs
+            .iter()
+            .chain(kernel.outputs.iter())
+            .map(|binding| binding.item)
+            .chain(info_item)
+            .chain(kernel.named.iter().skip(1).map(|(_, binding)| binding.item));
+
+        let mut memories = buffers
+            .into_iter()
+            .zip(items)
+            .map(|((ptr, size), item)| Memory::new(ptr, size, item))
+            .collect::<Vec<_>>();
+
+        let named = memories.split_off(num_arrays);
+        let outputs = memories.split_off(kernel.inputs.len());
+        let inputs = memories;
+        let mut named = named.into_iter();
+        let info = match has_info {
+            true => named.next(),
+            false => None,
+        };
+        let scalars = named.map(|memory| (memory.item().elem, memory)).collect();
+
+        Ok(Self {
+            inputs,
+            outputs,
+            info,
+            scalars,
+        })
+    }
+}
+
+/// Everything shared by the units during the execution of a kernel.
+pub(crate) struct KernelContext<'a> {
+    pub kernel: &'a CpuKernel,
+    pub bindings: KernelBindings,
+    pub cube_count: [u32; 3],
+    /// Atomic operations are serialized, which is enough to guarantee their atomicity.
+    pub atomic_lock: Mutex<()>,
+}
+
+/// Everything shared by the units of the same cube.
+pub(crate) struct CubeContext {
+    pub position: [u32; 3],
+    pub sync: CubeSync,
+    shared_memories: Mutex<Vec<Option<OwnedMemory>>>,
+}
+
+impl CubeContext {
+    fn new(position: [u32; 3], num_units: usize) -> Self {
+        Self {
+            position,
+            sync: CubeSync::new(num_units),
+            shared_memories: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Get the shared memory with the given id, allocating it on first access.
+    pub(crate) fn shared_memory(&self, id: u16, item: Item, length: u32) -> Memory {
+        let mut memories = self
+            .shared_memories
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let id = id as usize;
+
+        if memories.len() <= id {
+            memories.resize_with(id + 1, || None);
+        }
+
+        memories[id]
+            .get_or_insert_with(|| OwnedMemory::new(item, length as usize))
+            .view()
+    }
+}
+
+/// Execute the kernel on every unit of every cube.
+///
+/// Cubes are executed one after the other. When the kernel is cooperative, every unit of a cube
+/// runs on its own thread so that barriers and subcube operations behave like on a GPU, otherwise
+/// units are executed sequentially.
+pub(crate) fn execute(kernel: &CpuKernel, bindings: KernelBindings, cube_count: [u32; 3]) {
+    let context = KernelContext {
+        kernel,
+        bindings,
+        cube_count,
+        atomic_lock: Mutex::new(()),
+    };
+    let cube_dim = kernel.cube_dim;
+    let num_units = (cube_dim.x * cube_dim.y * cube_dim.z) as usize;
+
+    for z in 0..cube_count[2] {
+        for y in 0..cube_count[1] {
+            for x in 0..cube_count[0] {
+                let cube = CubeContext::new([x, y, z], num_units);
+
+                if kernel.cooperative {
+                    std::thread::scope(|scope| {
+                        for unit in 0..num_units {
+                            let (context, cube) = (&context, &cube);
+                            scope.spawn(move || Unit::new(context, cube, unit).run());
+                        }
+                    });
+                } else {
+                    for unit in 0..num_units {
+                        Unit::new(&context, &cube, unit).run();
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use super::{Scalar, Value};
+use cubecl_core::ir::{Elem, FloatKind, IntKind, Item, UIntKind};
+
+/// The number of bytes used to store an element in memory.
+///
+/// Booleans are stored as 32 bits integers, since they are passed as such in bindings.
+pub(crate) fn elem_size(elem: Elem) -> usize {
+    match elem {
+        Elem::Float(kind) | Elem::AtomicFloat(kind) => match kind {
+            FloatKind::F16 | FloatKind::BF16 => 2,
+            FloatKind::F32 => 4,
+            FloatKind::F64 => 8,
+        },
+        Elem::Int(kind) | Elem::AtomicInt(kind) => match kind {
+            IntKind::I8 => 1,
+            IntKind::I16 => 2,
+            IntKind::I32 => 4,
+            IntKind::I64 => 8,
+        },
+        Elem::UInt(kind) => match kind {
+            UIntKind::U8 => 1,
+            UIntKind::U16 => 2,
+            UIntKind::U32 => 4,
+            UIntKind::U64 => 8,
+        },
+        Elem::AtomicUInt => 4,
+        Elem::Bool => 4,
+    }
+}
+
+/// A view over a contiguous memory region where items of the same type are stored.
+///
+/// Accesses are bound checked: reading out of bounds returns zeros and writing out of bounds does
+/// nothing, similar to the robust buffer access of most graphics APIs.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Memory {
+    ptr: *mut u8,
+    size: usize,
+    item: Item,
+}
+
+// Units of the same cube share memory between threads, the same way they would on a GPU.
+unsafe impl Send for Memory {}
+unsafe impl Sync for Memory {}
+
+impl Memory {
+    /// Create a new memory view.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be valid for reads and writes of `size` bytes as long as the view is used.
+    pub(crate) unsafe fn new(ptr: *mut u8, size: usize, item: Item) -> Self {
+        Self { ptr, size, item }
+    }
+
+    /// The item stored in memory.
+    pub(crate) fn item(&self) -> Item {
+        self.item
+    }
+
+    /// The number of items stored in memory.
+    pub(crate) fn len(&self) -> usize {
+        self.size / self.item_size()
+    }
+
+    /// Read the item at the given index.
+    pub(crate) fn read(&self, index: usize) -> Value {
+        if index >= self.len() {
+            return Value::zeros(self.item);
+        }
+
+        let elem = self.item.elem;
+        let offset = index * self.item_size();
+
+        Value::from_fn(self.item, |lane| {
+            let bits = unsafe { self.read_bits(offset + lane * elem_size(elem), elem_size(elem)) };
+            Scalar::from_bits(bits, elem)
+        })
+    }
+
+    /// Write the given value at the given index, casting it to the item stored in memory.
+    pub(crate) fn write(&self, index: usize, value: &Value) {
+        if index >= self.len() {
+            return;
+        }
+
+        let elem = self.item.elem;
+        let offset = index * self.item_size();
+
+        for lane in 0..self.item.vectorization.max(1) as usize {
+            let bits = value.lane(lane).to_bits(elem);
+            unsafe { self.write_bits(offset + lane * elem_size(elem), elem_size(elem), bits) };
+        }
+    }
+
+    fn item_size(&self) -> usize {
+        elem_size(self.item.elem) * self.item.vectorization.max(1) as usize
+    }
+
+    unsafe fn read_bits(&self, offset: usize, size: usize) -> u64 {
+        let mut bytes = [0u8; 8];
+        core::ptr::copy_nonoverlapping(self.ptr.add(offset), bytes.as_mut_ptr(), size);
+        u64::from_le_bytes(bytes)
+    }
+
+    unsafe fn write_bits(&self, offset: usize, size: usize, bits: u64) {
+        let bytes = bits.to_le_bytes();
+        core::ptr::copy_nonoverlapping(bytes.as_ptr(), self.ptr.add(offset), size);
+    }
+}
+
+/// Memory owned by the interpreter, used for shared memories and local arrays.
+pub(crate) struct OwnedMemory {
+    bytes: Box<[u8]>,
+    item: Item,
+}
+
+impl OwnedMemory {
+    /// Allocate zeroed memory for the given number of items.
+    pub(crate) fn new(item: Item, length: usize) -> Self {
+        let size = elem_size(item.elem) * item.vectorization.max(1) as usize * length;
+
+        Self {
+            bytes: vec![0; size].into_boxed_slice(),
+            item,
+        }
+    }
+
+    /// Create a view over the memory.
+    ///
+    /// The view must not outlive the owned memory.
+    pub(crate) fn view(&mut self) -> Memory {
+        unsafe { Memory::new(self.bytes.as_mut_ptr(), self.bytes.len(), self.item) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_read_and_write_vectorized_items() {
+        let item = Item::vectorized(Elem::Float(FloatKind::F32), 2);
+        let mut memory = OwnedMemory::new(item, 3);
+        let view = memory.view();
+
+        view.write(1, &Value::from_fn(item, |i| Scalar::Float(i as f64 + 1.0)));
+
+        let value = view.read(1);
+        assert_eq!(view.len(), 3);
+        assert_eq!(value.lane(0), Scalar::Float(1.0));
+        assert_eq!(value.lane(1), Scalar::Float(2.0));
+    }
+
+    #[test]
+    fn should_ignore_out_of_bounds_accesses() {
+        let item = Item::new(Elem::UInt(UIntKind::U32));
+        let mut memory = OwnedMemory::new(item, 2);
+        let view = memory.view();
+
+        view.write(
+            2,
+            &Value::scalar(Elem::UInt(UIntKind::U32), Scalar::UInt(4)),
+        );
+
+        assert_eq!(view.read(2).lane(0), Scalar::UInt(0));
+    }
+}
@@ -0,0 +1,12 @@
+mod execution;
+mod memory;
+mod operations;
+mod sync;
+mod unit;
+mod value;
+
+pub(crate) use execution::*;
+pub(crate) use memory::*;
+pub(crate) use sync::*;
+pub(crate) use unit::*;
+pub(crate) use value::*;
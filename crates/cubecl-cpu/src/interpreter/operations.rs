@@ -0,0 +1,297 @@
+//! Lane-wise operations.
+//!
+//! The element type of the output decides how the operation is computed: floats are computed
+//! in double precision and rounded when written, while integers wrap on overflow.
+
+use super::{elem_size, Scalar};
+use cubecl_core::ir::Elem;
+use std::cmp::Ordering;
+
+enum Category {
+    Float,
+    Int,
+    UInt,
+    Bool,
+}
+
+fn category(elem: Elem) -> Category {
+    match elem {
+        Elem::Float(_) | Elem::AtomicFloat(_) => Category::Float,
+        Elem::Int(_) | Elem::AtomicInt(_) => Category::Int,
+        Elem::UInt(_) | Elem::AtomicUInt => Category::UInt,
+        Elem::Bool => Category::Bool,
+    }
+}
+
+fn bit_width(elem: Elem) -> u32 {
+    elem_size(elem) as u32 * 8
+}
+
+/// Compare two scalars, returning [None] when one of them is NaN.
+pub(crate) fn compare(lhs: Scalar, rhs: Scalar) -> Option<Ordering> {
+    match (lhs, rhs) {
+        (Scalar::Int(lhs), Scalar::Int(rhs)) => Some(lhs.cmp(&rhs)),
+        (Scalar::UInt(lhs), Scalar::UInt(rhs)) => Some(lhs.cmp(&rhs)),
+        (Scalar::Bool(lhs), Scalar::Bool(rhs)) => Some(lhs.cmp(&rhs)),
+        (Scalar::Int(lhs), Scalar::UInt(rhs)) => Some((lhs as i128).cmp(&(rhs as i128))),
+        (Scalar::UInt(lhs), Scalar::Int(rhs)) => Some((lhs as i128).cmp(&(rhs as i128))),
+        _ => lhs.as_f64().partial_cmp(&rhs.as_f64()),
+    }
+}
+
+pub(crate) fn add(lhs: Scalar, rhs: Scalar, elem: Elem) -> Scalar {
+    match category(elem) {
+        Category::Float => Scalar::Float(lhs.as_f64() + rhs.as_f64()),
+        Category::Int => Scalar::Int(lhs.as_i64().wrapping_add(rhs.as_i64())),
+        Category::UInt => Scalar::UInt(lhs.as_u64().wrapping_add(rhs.as_u64())),
+        Category::Bool => Scalar::Bool(lhs.as_bool() || rhs.as_bool()),
+    }
+}
+
+pub(crate) fn sub(lhs: Scalar, rhs: Scalar, elem: Elem) -> Scalar {
+    match category(elem) {
+        Category::Float => Scalar::Float(lhs.as_f64() - rhs.as_f64()),
+        Category::Int => Scalar::Int(lhs.as_i64().wrapping_sub(rhs.as_i64())),
+        Category::UInt => Scalar::UInt(lhs.as_u64().wrapping_sub(rhs.as_u64())),
+        Category::Bool => Scalar::Bool(lhs.as_bool() != rhs.as_bool()),
+    }
+}
+
+pub(crate) fn mul(lhs: Scalar, rhs: Scalar, elem: Elem) -> Scalar {
+    match category(elem) {
+        Category::Float => Scalar::Float(lhs.as_f64() * rhs.as_f64()),
+        Category::Int => Scalar::Int(lhs.as_i64().wrapping_mul(rhs.as_i64())),
+        Category::UInt => Scalar::UInt(lhs.as_u64().wrapping_mul(rhs.as_u64())),
+        Category::Bool => Scalar::Bool(lhs.as_bool() && rhs.as_bool()),
+    }
+}
+
+/// Integer division by zero returns zero instead of trapping.
+pub(crate) fn div(lhs: Scalar, rhs: Scalar, elem: Elem) -> Scalar {
+    match category(elem) {
+        Category::Float => Scalar::Float(lhs.as_f64() / rhs.as_f64()),
+        Category::Int => {
+            let lhs = lhs.cast(elem).as_i64();
+            Scalar::Int(lhs.checked_div(rhs.cast(elem).as_i64()).unwrap_or(0))
+        }
+        Category::UInt => {
+            let lhs = lhs.cast(elem).as_u64();
+            Scalar::UInt(lhs.checked_div(rhs.cast(elem).as_u64()).unwrap_or(0))
+        }
+        Category::Bool => Scalar::Bool(lhs.as_bool()),
+    }
+}
+
+/// Truncated modulo, the result has the sign of the dividend.
+pub(crate) fn modulo(lhs: Scalar, rhs: Scalar, elem: Elem) -> Scalar {
+    match category(elem) {
+        Category::Float => Scalar::Float(lhs.as_f64() % rhs.as_f64()),
+        Category::Int => {
+            let lhs = lhs.cast(elem).as_i64();
+            Scalar::Int(lhs.checked_rem(rhs.cast(elem).as_i64()).unwrap_or(0))
+        }
+        Category::UInt => {
+            let lhs = lhs.cast(elem).as_u64();
+            Scalar::UInt(lhs.checked_rem(rhs.cast(elem).as_u64()).unwrap_or(0))
+        }
+        Category::Bool => Scalar::Bool(false),
+    }
+}
+
+/// Floored modulo, the result has the sign of the divisor.
+pub(crate) fn remainder(lhs: Scalar, rhs: Scalar, elem: Elem) -> Scalar {
+    match category(elem) {
+        Category::Float => {
+            let (lhs, rhs) = (lhs.as_f64(), rhs.as_f64());
+            Scalar::Float(lhs - rhs * (lhs / rhs).floor())
+        }
+        Category::Int => {
+            let rhs = rhs.cast(elem).as_i64();
+            let rem = modulo(lhs, Scalar::Int(rhs), elem).as_i64();
+            match rem != 0 && (rem < 0) != (rhs < 0) {
+                true => Scalar::Int(rem + rhs),
+                false => Scalar::Int(rem),
+            }
+        }
+        _ => modulo(lhs, rhs, elem),
+    }
+}
+
+pub(crate) fn max(lhs: Scalar, rhs: Scalar, elem: Elem) -> Scalar {
+    let (lhs, rhs) = (lhs.cast(elem), rhs.cast(elem));
+
+    match compare(lhs, rhs) {
+        Some(Ordering::Less) => rhs,
+        Some(_) => lhs,
+        // Like on GPUs, the number is returned when the other value is NaN.
+        None => match lhs.as_f64().is_nan() {
+            true => rhs,
+            false => lhs,
+        },
+    }
+}
+
+pub(crate) fn min(lhs: Scalar, rhs: Scalar, elem: Elem) -> Scalar {
+    let (lhs, rhs) = (lhs.cast(elem), rhs.cast(elem));
+
+    match compare(lhs, rhs) {
+        Some(Ordering::Greater) => rhs,
+        Some(_) => lhs,
+        None => match lhs.as_f64().is_nan() {
+            true => rhs,
+            false => lhs,
+        },
+    }
+}
+
+pub(crate) fn bitwise_and(lhs: Scalar, rhs: Scalar, elem: Elem) -> Scalar {
+    Scalar::from_bits(lhs.to_bits(elem) & rhs.to_bits(elem), elem)
+}
+
+pub(crate) fn bitwise_or(lhs: Scalar, rhs: Scalar, elem: Elem) -> Scalar {
+    Scalar::from_bits(lhs.to_bits(elem) | rhs.to_bits(elem), elem)
+}
+
+pub(crate) fn bitwise_xor(lhs: Scalar, rhs: Scalar, elem: Elem) -> Scalar {
+    Scalar::from_bits(lhs.to_bits(elem) ^ rhs.to_bits(elem), elem)
+}
+
+/// The shift amount is masked by the bit width, like in WGSL.
+pub(crate) fn shift_left(lhs: Scalar, rhs: Scalar, elem: Elem) -> Scalar {
+    let shift = rhs.as_u64() as u32 % bit_width(elem);
+    Scalar::from_bits(lhs.to_bits(elem) << shift, elem)
+}
+
+/// Signed integers use an arithmetic shift, unsigned integers a logical one.
+pub(crate) fn shift_right(lhs: Scalar, rhs: Scalar, elem: Elem) -> Scalar {
+    let shift = rhs.as_u64() as u32 % bit_width(elem);
+
+    match category(elem) {
+        Category::Int => Scalar::Int(lhs.cast(elem).as_i64() >> shift),
+        _ => Scalar::from_bits(lhs.to_bits(elem) >> shift, elem),
+    }
+}
+
+pub(crate) fn abs(input: Scalar, elem: Elem) -> Scalar {
+    match category(elem) {
+        Category::Float => Scalar::Float(input.as_f64().abs()),
+        Category::Int => Scalar::Int(input.cast(elem).as_i64().wrapping_abs()),
+        _ => input,
+    }
+}
+
+pub(crate) fn not(input: Scalar, elem: Elem) -> Scalar {
+    match category(elem) {
+        Category::Bool => Scalar::Bool(!input.as_bool()),
+        _ => Scalar::from_bits(!input.to_bits(elem), elem),
+    }
+}
+
+pub(crate) fn neg(input: Scalar, elem: Elem) -> Scalar {
+    match category(elem) {
+        Category::Float => Scalar::Float(-input.as_f64()),
+        Category::Bool => input,
+        _ => Scalar::from_bits(input.to_bits(elem).wrapping_neg(), elem),
+    }
+}
+
+/// The raw bits of an integer, without the sign extension of narrow signed types.
+fn masked_bits(input: Scalar, elem: Elem) -> u64 {
+    let width = bit_width(elem);
+    let bits = input.to_bits(elem);
+
+    match width {
+        64 => bits,
+        _ => bits & ((1 << width) - 1),
+    }
+}
+
+pub(crate) fn count_ones(input: Scalar, elem: Elem) -> Scalar {
+    Scalar::from_bits(masked_bits(input, elem).count_ones() as u64, elem)
+}
+
+pub(crate) fn reverse_bits(input: Scalar, elem: Elem) -> Scalar {
+    let bits = masked_bits(input, elem).reverse_bits() >> (64 - bit_width(elem));
+    Scalar::from_bits(bits, elem)
+}
+
+pub(crate) fn leading_zeros(input: Scalar, elem: Elem) -> Scalar {
+    let zeros = masked_bits(input, elem).leading_zeros() - (64 - bit_width(elem));
+    Scalar::from_bits(zeros as u64, elem)
+}
+
+pub(crate) fn trailing_zeros(input: Scalar, elem: Elem) -> Scalar {
+    let zeros = masked_bits(input, elem)
+        .trailing_zeros()
+        .min(bit_width(elem));
+    Scalar::from_bits(zeros as u64, elem)
+}
+
+/// Unlike [f64::signum], zero and NaN are returned unchanged like in WGSL and CUDA.
+pub(crate) fn sign(val: f64) -> f64 {
+    if val > 0.0 {
+        1.0
+    } else if val < 0.0 {
+        -1.0
+    } else {
+        val
+    }
+}
+
+pub(crate) fn fma(a: Scalar, b: Scalar, c: Scalar, elem: Elem) -> Scalar {
+    match category(elem) {
+        Category::Float => Scalar::Float(a.as_f64().mul_add(b.as_f64(), c.as_f64())),
+        _ => add(mul(a, b, elem), c, elem),
+    }
+}
+
+/// Same approximation as the one used by the other backends, so results match closely.
+pub(crate) fn erf(x: f64) -> f64 {
+    const P: f64 = 0.3275911;
+    const A1: f64 = 0.254829592;
+    const A2: f64 = -0.284496736;
+    const A3: f64 = 1.421413741;
+    const A4: f64 = -1.453152027;
+    const A5: f64 = 1.061405429;
+
+    let abs = x.abs();
+    let t = 1.0 / (1.0 + P * abs);
+    let poly = (((A5 * t + A4) * t + A3) * t + A2) * t + A1;
+    let result = 1.0 - poly * t * (-abs * abs).exp();
+
+    match x < 0.0 {
+        true => -result,
+        false => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cubecl_core::ir::{IntKind, UIntKind};
+
+    #[test]
+    fn should_compute_floored_remainder() {
+        let elem = Elem::Int(IntKind::I32);
+        let rem = remainder(Scalar::Int(-7), Scalar::Int(3), elem);
+
+        assert_eq!(rem, Scalar::Int(2));
+    }
+
+    #[test]
+    fn should_return_zero_on_integer_division_by_zero() {
+        let div = div(Scalar::UInt(7), Scalar::UInt(0), Elem::UInt(UIntKind::U32));
+
+        assert_eq!(div, Scalar::UInt(0));
+    }
+
+    #[test]
+    fn should_count_bits_within_the_element_width() {
+        let elem = Elem::Int(IntKind::I8);
+
+        assert_eq!(count_ones(Scalar::Int(-1), elem), Scalar::Int(8));
+        assert_eq!(leading_zeros(Scalar::Int(1), elem), Scalar::Int(7));
+        assert_eq!(trailing_zeros(Scalar::Int(0), elem), Scalar::Int(8));
+        assert_eq!(reverse_bits(Scalar::Int(1), elem), Scalar::Int(-128));
+    }
+}
@@ -0,0 +1,94 @@
+use super::Value;
+use std::sync::{Condvar, Mutex, MutexGuard};
+
+/// Synchronization primitives shared by all units of a cube.
+///
+/// Units that returned no longer take part in barriers and subcube operations, so they never
+/// block the units that are still running.
+pub(crate) struct CubeSync {
+    state: Mutex<SyncState>,
+    condvar: Condvar,
+}
+
+struct SyncState {
+    active: usize,
+    arrived: usize,
+    generation: u64,
+    values: Vec<Option<Value>>,
+}
+
+impl CubeSync {
+    /// Create the synchronization primitives for the given number of units.
+    pub(crate) fn new(num_units: usize) -> Self {
+        Self {
+            state: Mutex::new(SyncState {
+                active: num_units,
+                arrived: 0,
+                generation: 0,
+                values: vec![None; num_units],
+            }),
+            condvar: Condvar::new(),
+        }
+    }
+
+    /// Wait until all active units reached the barrier.
+    pub(crate) fn barrier(&self) {
+        let mut state = self.lock();
+        let generation = state.generation;
+        state.arrived += 1;
+
+        if state.arrived >= state.active {
+            self.release(&mut state);
+            return;
+        }
+
+        while state.generation == generation {
+            state = self
+                .condvar
+                .wait(state)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+    }
+
+    /// Share a value with all active units, returning the values of every participating unit
+    /// ordered by unit position.
+    pub(crate) fn exchange(&self, unit: usize, value: Value) -> Vec<(usize, Value)> {
+        self.lock().values[unit] = Some(value);
+        self.barrier();
+
+        let values = self
+            .lock()
+            .values
+            .iter()
+            .enumerate()
+            .filter_map(|(unit, value)| value.map(|value| (unit, value)))
+            .collect();
+
+        self.barrier();
+        values
+    }
+
+    /// Remove the unit from the active units.
+    pub(crate) fn exit(&self, unit: usize) {
+        let mut state = self.lock();
+        state.active -= 1;
+        state.values[unit] = None;
+
+        if state.arrived > 0 && state.arrived >= state.active {
+            self.release(&mut state);
+        }
+    }
+
+    fn release(&self, state: &mut SyncState) {
+        state.arrived = 0;
+        state.generation += 1;
+        self.condvar.notify_all();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SyncState> {
+        // A unit panicking on its own thread must not prevent the other units from finishing.
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
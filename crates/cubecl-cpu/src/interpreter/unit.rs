@@ -0,0 +1,903 @@
+use super::{
+    operations::{self, compare},
+    CubeContext, KernelContext, Memory, OwnedMemory, Scalar, Value,
+};
+use crate::compiler::Instruction;
+use cubecl_core::ir::{
+    BinaryOperator, Call, Elem, Item, Metadata, Operator, Subcube, UIntKind, UnaryOperator,
+    Variable,
+};
+use std::cmp::Ordering;
+
+/// How the execution continues after an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flow {
+    Continue,
+    Break,
+    Return,
+}
+
+/// The content of a local variable.
+#[derive(Debug, Clone, Copy)]
+#[allow(clippy::large_enum_variant)] // Registers are stored inline to avoid an allocation per write.
+enum Register {
+    Value(Value),
+    /// A reference to an item in memory, used by atomic variables.
+    Pointer {
+        memory: Memory,
+        index: usize,
+    },
+}
+
+/// A view over a part of an array.
+#[derive(Debug, Clone, Copy)]
+struct ArrayRef {
+    memory: Memory,
+    offset: usize,
+    length: usize,
+}
+
+impl ArrayRef {
+    fn read(&self, index: usize) -> Value {
+        match index < self.length {
+            true => self.memory.read(self.offset + index),
+            false => Value::zeros(self.memory.item()),
+        }
+    }
+
+    fn write(&self, index: usize, value: &Value) {
+        if index < self.length {
+            self.memory.write(self.offset + index, value);
+        }
+    }
+}
+
+/// Variables indexed by their depth and id.
+struct Table<T> {
+    rows: Vec<Vec<Option<T>>>,
+}
+
+impl<T> Default for Table<T> {
+    fn default() -> Self {
+        Self { rows: Vec::new() }
+    }
+}
+
+impl<T> Table<T> {
+    fn get(&self, depth: u8, id: u16) -> Option<&T> {
+        self.rows
+            .get(depth as usize)
+            .and_then(|row| row.get(id as usize))
+            .and_then(|item| item.as_ref())
+    }
+
+    fn slot(&mut self, depth: u8, id: u16) -> &mut Option<T> {
+        let (depth, id) = (depth as usize, id as usize);
+
+        if self.rows.len() <= depth {
+            self.rows.resize_with(depth + 1, Vec::new);
+        }
+
+        let row = &mut self.rows[depth];
+        if row.len() <= id {
+            row.resize_with(id + 1, || None);
+        }
+
+        &mut row[id]
+    }
+}
+
+/// A unit interpreting the kernel instructions.
+pub(crate) struct Unit<'a> {
+    context: &'a KernelContext<'a>,
+    cube: &'a CubeContext,
+    index: usize,
+    position: [u32; 3],
+    locals: Table<Register>,
+    local_scalars: Table<Register>,
+    slices: Table<ArrayRef>,
+    local_arrays: Table<OwnedMemory>,
+}
+
+/// Remove the unit from the cube synchronization primitives when it stops, even on panic.
+struct ExitGuard<'a> {
+    cube: &'a CubeContext,
+    index: usize,
+}
+
+impl Drop for ExitGuard<'_> {
+    fn drop(&mut self) {
+        self.cube.sync.exit(self.index);
+    }
+}
+
+impl<'a> Unit<'a> {
+    /// Create the unit at the given position in the cube.
+    pub(crate) fn new(context: &'a KernelContext<'a>, cube: &'a CubeContext, index: usize) -> Self {
+        let cube_dim = context.kernel.cube_dim;
+        let index_u32 = index as u32;
+        let position = [
+            index_u32 % cube_dim.x,
+            (index_u32 / cube_dim.x) % cube_dim.y,
+            index_u32 / (cube_dim.x * cube_dim.y),
+        ];
+
+        Self {
+            context,
+            cube,
+            index,
+            position,
+            locals: Table::default(),
+            local_scalars: Table::default(),
+            slices: Table::default(),
+            local_arrays: Table::default(),
+        }
+    }
+
+    /// Run the kernel until the unit returns.
+    pub(crate) fn run(mut self) {
+        let _guard = ExitGuard {
+            cube: self.cube,
+            index: self.index,
+        };
+        let kernel = self.context.kernel;
+
+        self.execute(&kernel.body);
+    }
+
+    fn execute(&mut self, instructions: &[Instruction]) -> Flow {
+        for instruction in instructions {
+            let flow = self.execute_instruction(instruction);
+
+            if flow != Flow::Continue {
+                return flow;
+            }
+        }
+
+        Flow::Continue
+    }
+
+    fn execute_instruction(&mut self, instruction: &Instruction) -> Flow {
+        match instruction {
+            Instruction::DeclareVariable { var } => self.declare(var),
+            Instruction::Operator(op) => self.execute_operator(op),
+            Instruction::Metadata(op) => self.execute_metadata(op),
+            Instruction::Subcube(op) => self.execute_subcube(op),
+            Instruction::SyncUnits | Instruction::SyncStorage => self.cube.sync.barrier(),
+            Instruction::If { cond, instructions } => {
+                if self.read(cond).lane(0).as_bool() {
+                    return self.execute(instructions);
+                }
+            }
+            Instruction::IfElse {
+                cond,
+                instructions_if,
+                instructions_else,
+            } => {
+                return match self.read(cond).lane(0).as_bool() {
+                    true => self.execute(instructions_if),
+                    false => self.execute(instructions_else),
+                };
+            }
+            Instruction::Switch {
+                value,
+                instructions_default,
+                cases,
+            } => {
+                let value = self.read(value).lane(0).as_i64();
+                let instructions = cases
+                    .iter()
+                    .find(|(values, _)| {
+                        values
+                            .iter()
+                            .any(|val| self.read(val).lane(0).as_i64() == value)
+                    })
+                    .map(|(_, instructions)| instructions)
+                    .unwrap_or(instructions_default);
+
+                return self.execute(instructions);
+            }
+            Instruction::RangeLoop {
+                i,
+                start,
+                end,
+                step,
+                instructions,
+            } => return self.execute_range_loop(i, start, end, step.as_ref(), instructions),
+            Instruction::Loop { instructions } => loop {
+                match self.execute(instructions) {
+                    Flow::Continue => continue,
+                    Flow::Break => break,
+                    Flow::Return => return Flow::Return,
+                }
+            },
+            Instruction::Call(call) => self.execute_call(call),
+            Instruction::Return => return Flow::Return,
+            Instruction::Break => return Flow::Break,
+        }
+
+        Flow::Continue
+    }
+
+    fn execute_call(&mut self, call: &Call) {
+        let function = &self.context.kernel.functions[call.function as usize];
+        let args = call
+            .inputs
+            .iter()
+            .map(|input| self.read(input))
+            .collect::<Vec<_>>();
+
+        // The function has its own local variables, the ones of the caller are restored after
+        // the call.
+        let locals = std::mem::take(&mut self.locals);
+        let local_scalars = std::mem::take(&mut self.local_scalars);
+        let slices = std::mem::take(&mut self.slices);
+        let local_arrays = std::mem::take(&mut self.local_arrays);
+
+        for (input, arg) in function.inputs.iter().zip(args) {
+            self.declare(input);
+            self.write(input, arg);
+        }
+
+        // A return only exits the function.
+        self.execute(&function.body);
+        let output = function.output.as_ref().map(|output| self.read(output));
+
+        self.locals = locals;
+        self.local_scalars = local_scalars;
+        self.slices = slices;
+        self.local_arrays = local_arrays;
+
+        if let (Some(out), Some(output)) = (call.output.as_ref(), output) {
+            self.write(out, output);
+        }
+    }
+
+    fn execute_range_loop(
+        &mut self,
+        i: &Variable,
+        start: &Variable,
+        end: &Variable,
+        step: Option<&Variable>,
+        instructions: &[Instruction],
+    ) -> Flow {
+        let start = self.read(start);
+        self.write(i, start);
+
+        loop {
+            let index = self.read(i).lane(0);
+            let end = self.read(end).lane(0);
+
+            if compare(index, end) != Some(Ordering::Less) {
+                return Flow::Continue;
+            }
+
+            match self.execute(instructions) {
+                Flow::Continue => {}
+                Flow::Break => return Flow::Continue,
+                Flow::Return => return Flow::Return,
+            }
+
+            let step = match step {
+                Some(step) => self.read(step).lane(0),
+                None => Scalar::UInt(1),
+            };
+            let elem = i.item().elem;
+            let next = operations::add(self.read(i).lane(0), step, elem);
+            self.write(i, Value::scalar(elem, next));
+        }
+    }
+
+    fn declare(&mut self, var: &Variable) {
+        match var {
+            Variable::Local { id, item, depth } => {
+                *self.locals.slot(*depth, *id) = Some(Register::Value(Value::zeros(*item)));
+            }
+            Variable::LocalScalar { id, elem, depth } => {
+                *self.local_scalars.slot(*depth, *id) =
+                    Some(Register::Value(Value::zeros(Item::new(*elem))));
+            }
+            _ => {}
+        }
+    }
+
+    fn execute_operator(&mut self, operator: &Operator) {
+        match operator {
+            Operator::Add(op) => self.binary(op, operations::add),
+            Operator::Sub(op) => self.binary(op, operations::sub),
+            Operator::Mul(op) => self.binary(op, operations::mul),
+            Operator::Div(op) => self.binary(op, operations::div),
+            Operator::Modulo(op) => self.binary(op, operations::modulo),
+            Operator::Remainder(op) => self.binary(op, operations::remainder),
+            Operator::Powf(op) => self.binary(op, |lhs, rhs, _| {
+                Scalar::Float(lhs.as_f64().powf(rhs.as_f64()))
+            }),
+            Operator::Max(op) => self.binary(op, operations::max),
+            Operator::Min(op) => self.binary(op, operations::min),
+            Operator::BitwiseAnd(op) => self.binary(op, operations::bitwise_and),
+            Operator::BitwiseOr(op) => self.binary(op, operations::bitwise_or),
+            Operator::BitwiseXor(op) => self.binary(op, operations::bitwise_xor),
+            Operator::ShiftLeft(op) => self.binary(op, operations::shift_left),
+            Operator::ShiftRight(op) => self.binary(op, operations::shift_right),
+            Operator::And(op) => self.binary(op, |lhs, rhs, _| {
+                Scalar::Bool(lhs.as_bool() && rhs.as_bool())
+            }),
+            Operator::Or(op) => self.binary(op, |lhs, rhs, _| {
+                Scalar::Bool(lhs.as_bool() || rhs.as_bool())
+            }),
+            Operator::Equal(op) => self.comparison(op, |ord| ord == Some(Ordering::Equal)),
+            Operator::NotEqual(op) => self.comparison(op, |ord| ord != Some(Ordering::Equal)),
+            Operator::Lower(op) => self.comparison(op, |ord| ord == Some(Ordering::Less)),
+            Operator::LowerEqual(op) => self.comparison(op, |ord| {
+                matches!(ord, Some(Ordering::Less | Ordering::Equal))
+            }),
+            Operator::Greater(op) => self.comparison(op, |ord| ord == Some(Ordering::Greater)),
+            Operator::GreaterEqual(op) => self.comparison(op, |ord| {
+                matches!(ord, Some(Ordering::Greater | Ordering::Equal))
+            }),
+            Operator::Abs(op) => self.unary(op, operations::abs),
+            Operator::Exp(op) => self.unary_float(op, f64::exp),
+            Operator::Log(op) => self.unary_float(op, f64::ln),
+            Operator::Log1p(op) => self.unary_float(op, f64::ln_1p),
+            Operator::Cos(op) => self.unary_float(op, f64::cos),
+            Operator::Sin(op) => self.unary_float(op, f64::sin),
+            Operator::Tanh(op) => self.unary_float(op, f64::tanh),
+            Operator::Sqrt(op) => self.unary_float(op, f64::sqrt),
+            Operator::Floor(op) => self.unary_float(op, f64::floor),
+            Operator::Ceil(op) => self.unary_float(op, f64::ceil),
+            Operator::Erf(op) => self.unary_float(op, operations::erf),
+            Operator::Recip(op) => self.unary_float(op, |val| 1.0 / val),
+            Operator::Tan(op) => self.unary_float(op, f64::tan),
+            Operator::Asin(op) => self.unary_float(op, f64::asin),
+            Operator::Acos(op) => self.unary_float(op, f64::acos),
+            Operator::Atan(op) => self.unary_float(op, f64::atan),
+            Operator::Atan2(op) => self.binary(op, |lhs, rhs, _| {
+                Scalar::Float(lhs.as_f64().atan2(rhs.as_f64()))
+            }),
+            Operator::Sinh(op) => self.unary_float(op, f64::sinh),
+            Operator::Cosh(op) => self.unary_float(op, f64::cosh),
+            Operator::Exp2(op) => self.unary_float(op, f64::exp2),
+            Operator::Log2(op) => self.unary_float(op, f64::log2),
+            Operator::Round(op) => self.unary_float(op, f64::round),
+            Operator::Trunc(op) => self.unary_float(op, f64::trunc),
+            Operator::Fract(op) => self.unary_float(op, f64::fract),
+            Operator::Sign(op) => self.unary_float(op, operations::sign),
+            Operator::CopySign(op) => self.binary(op, |lhs, rhs, _| {
+                Scalar::Float(lhs.as_f64().copysign(rhs.as_f64()))
+            }),
+            Operator::Hypot(op) => self.binary(op, |lhs, rhs, _| {
+                Scalar::Float(lhs.as_f64().hypot(rhs.as_f64()))
+            }),
+            Operator::Rsqrt(op) => self.unary_float(op, |val| 1.0 / val.sqrt()),
+            Operator::IsNan(op) => self.unary(op, |input, _| Scalar::Bool(input.as_f64().is_nan())),
+            Operator::IsInf(op) => {
+                self.unary(op, |input, _| Scalar::Bool(input.as_f64().is_infinite()))
+            }
+            Operator::Not(op) | Operator::BitwiseNot(op) => self.unary(op, operations::not),
+            Operator::Neg(op) => self.unary(op, operations::neg),
+            Operator::CountOnes(op) => self.unary(op, operations::count_ones),
+            Operator::ReverseBits(op) => self.unary(op, operations::reverse_bits),
+            Operator::LeadingZeros(op) => self.unary(op, operations::leading_zeros),
+            Operator::TrailingZeros(op) => self.unary(op, operations::trailing_zeros),
+            Operator::Fma(op) => {
+                let (a, b, c) = (self.read(&op.a), self.read(&op.b), self.read(&op.c));
+                let item = op.out.item();
+                let value = Value::from_fn(item, |i| {
+                    operations::fma(a.lane(i), b.lane(i), c.lane(i), item.elem)
+                });
+                self.write(&op.out, value);
+            }
+            Operator::Clamp(op) => {
+                let input = self.read(&op.input);
+                let min = self.read(&op.min_value);
+                let max = self.read(&op.max_value);
+                let item = op.out.item();
+                let value = Value::from_fn(item, |i| {
+                    let val = operations::max(input.lane(i), min.lane(i), item.elem);
+                    operations::min(val, max.lane(i), item.elem)
+                });
+                self.write(&op.out, value);
+            }
+            Operator::Assign(op) => {
+                if op.out.item().elem.is_atomic() {
+                    // Assigning an atomic only creates a new reference to the same memory.
+                    let register = self.register(&op.input);
+                    self.set_register(&op.out, register);
+                } else {
+                    let value = self.read(&op.input);
+                    self.write(&op.out, value);
+                }
+            }
+            Operator::Bitcast(op) => {
+                let input = self.read(&op.input);
+                let item = op.out.item();
+                let value = Value::from_fn(item, |i| {
+                    Scalar::from_bits(input.lane(i).to_bits(input.elem), item.elem)
+                });
+                self.write(&op.out, value);
+            }
+            Operator::Index(op) | Operator::UncheckedIndex(op) => self.index(op),
+            Operator::IndexAssign(op) | Operator::UncheckedIndexAssign(op) => self.index_assign(op),
+            Operator::Slice(op) => {
+                let array = self.array(&op.input);
+                let start = self.read(&op.start).lane(0).as_usize();
+                let end = self.read(&op.end).lane(0).as_usize();
+                let start = start.min(array.length);
+                let slice = ArrayRef {
+                    memory: array.memory,
+                    offset: array.offset + start,
+                    length: end.clamp(start, array.length) - start,
+                };
+
+                match op.out {
+                    Variable::Slice { id, depth, .. } => *self.slices.slot(depth, id) = Some(slice),
+                    _ => panic!("Can only slice into a slice variable, got {:?}", op.out),
+                }
+            }
+            Operator::AtomicLoad(op) => {
+                let (memory, index) = self.pointer(&op.input);
+                let value = {
+                    let _lock = self.atomic_lock();
+                    memory.read(index)
+                };
+                self.write(&op.out, value);
+            }
+            Operator::AtomicStore(op) => {
+                let (memory, index) = self.pointer(&op.out);
+                let value = self.read(&op.input);
+                let _lock = self.atomic_lock();
+                memory.write(index, &value);
+            }
+            Operator::AtomicSwap(op) => self.atomic(op, |_, rhs, _| rhs),
+            Operator::AtomicAdd(op) => self.atomic(op, operations::add),
+            Operator::AtomicSub(op) => self.atomic(op, operations::sub),
+            Operator::AtomicMax(op) => self.atomic(op, operations::max),
+            Operator::AtomicMin(op) => self.atomic(op, operations::min),
+            Operator::AtomicAnd(op) => self.atomic(op, operations::bitwise_and),
+            Operator::AtomicOr(op) => self.atomic(op, operations::bitwise_or),
+            Operator::AtomicXor(op) => self.atomic(op, operations::bitwise_xor),
+            Operator::AtomicCompareAndSwap(op) => {
+                let (memory, index) = self.pointer(&op.input);
+                let cmp = self.read(&op.cmp).lane(0);
+                let val = self.read(&op.val);
+                let old = {
+                    let _lock = self.atomic_lock();
+                    let old = memory.read(index);
+                    if compare(old.lane(0), cmp) == Some(Ordering::Equal) {
+                        memory.write(index, &val);
+                    }
+                    old
+                };
+                self.write(&op.out, old);
+            }
+        }
+    }
+
+    fn execute_metadata(&mut self, metadata: &Metadata) {
+        match metadata {
+            Metadata::Stride { dim, var, out } => {
+                let position = self.info_position(var);
+                let rank = self.info(0);
+                let dim = self.read(dim).lane(0).as_usize();
+                let stride = self.info(1 + position * 2 * rank + dim);
+                self.write(
+                    out,
+                    Value::scalar(Elem::UInt(UIntKind::U32), Scalar::UInt(stride as u64)),
+                );
+            }
+            Metadata::Shape { dim, var, out } => {
+                let position = self.info_position(var);
+                let rank = self.info(0);
+                let dim = self.read(dim).lane(0).as_usize();
+                let shape = self.info(1 + position * 2 * rank + rank + dim);
+                self.write(
+                    out,
+                    Value::scalar(Elem::UInt(UIntKind::U32), Scalar::UInt(shape as u64)),
+                );
+            }
+            Metadata::Length { var, out } => {
+                let length = self.array(var).length;
+                self.write(
+                    out,
+                    Value::scalar(Elem::UInt(UIntKind::U32), Scalar::UInt(length as u64)),
+                );
+            }
+        }
+    }
+
+    fn execute_subcube(&mut self, subcube: &Subcube) {
+        match subcube {
+            Subcube::Elect(op) => {
+                let units = self.exchange(Value::scalar(Elem::Bool, Scalar::Bool(true)));
+                let elected = units.first().map(|(unit, _)| *unit) == Some(self.index);
+                self.write(&op.out, Value::scalar(Elem::Bool, Scalar::Bool(elected)));
+            }
+            Subcube::Broadcast(op) => {
+                let value = self.read(&op.lhs);
+                let source = self.read(&op.rhs).lane(0).as_usize();
+                let units = self.exchange(value);
+                let value = units
+                    .into_iter()
+                    .find(|(unit, _)| *unit == source)
+                    .map(|(_, value)| value)
+                    .unwrap_or_else(|| Value::zeros(value.item()));
+                self.write(&op.out, value);
+            }
+            Subcube::All(op) => self.reduce(op, |lhs, rhs, _| {
+                Scalar::Bool(lhs.as_bool() && rhs.as_bool())
+            }),
+            Subcube::Any(op) => self.reduce(op, |lhs, rhs, _| {
+                Scalar::Bool(lhs.as_bool() || rhs.as_bool())
+            }),
+            Subcube::Sum(op) => self.reduce(op, operations::add),
+            Subcube::Prod(op) => self.reduce(op, operations::mul),
+            Subcube::Min(op) => self.reduce(op, operations::min),
+            Subcube::Max(op) => self.reduce(op, operations::max),
+            Subcube::And(op) => self.reduce(op, operations::bitwise_and),
+            Subcube::Or(op) => self.reduce(op, operations::bitwise_or),
+            Subcube::Xor(op) => self.reduce(op, operations::bitwise_xor),
+            Subcube::Shuffle(op) => self.shuffle(op, |_, id| Some(id)),
+            Subcube::ShuffleXor(op) => self.shuffle(op, |unit, mask| Some(unit ^ mask)),
+            Subcube::ShuffleUp(op) => self.shuffle(op, |unit, delta| unit.checked_sub(delta)),
+            Subcube::ShuffleDown(op) => self.shuffle(op, |unit, delta| Some(unit + delta)),
+            Subcube::InclusiveSum(op) => self.scan(op, None, operations::add),
+            Subcube::ExclusiveSum(op) => self.scan(op, Some(Scalar::UInt(0)), operations::add),
+            Subcube::InclusiveProd(op) => self.scan(op, None, operations::mul),
+            Subcube::ExclusiveProd(op) => self.scan(op, Some(Scalar::UInt(1)), operations::mul),
+            Subcube::Ballot(op) => {
+                let input = self.read(&op.input);
+                let units = self.exchange(input);
+                let mut mask = Value::zeros(op.out.item());
+                // The mask only has room for the first 128 units of the cube.
+                for (unit, value) in units {
+                    if unit < 32 * mask.len && value.lane(0).as_bool() {
+                        let bits = mask.lane(unit / 32).as_u64() | (1 << (unit % 32));
+                        mask.set_lane(unit / 32, Scalar::UInt(bits));
+                    }
+                }
+                self.write(&op.out, mask);
+            }
+        }
+    }
+
+    fn binary<F: Fn(Scalar, Scalar, Elem) -> Scalar>(&mut self, op: &BinaryOperator, func: F) {
+        let lhs = self.read(&op.lhs);
+        let rhs = self.read(&op.rhs);
+        let item = op.out.item();
+        let value = Value::from_fn(item, |i| func(lhs.lane(i), rhs.lane(i), item.elem));
+
+        self.write(&op.out, value);
+    }
+
+    fn comparison<F: Fn(Option<Ordering>) -> bool>(&mut self, op: &BinaryOperator, func: F) {
+        let lhs = self.read(&op.lhs);
+        let rhs = self.read(&op.rhs);
+        let item = op.out.item();
+        let value = Value::from_fn(item, |i| {
+            Scalar::Bool(func(compare(lhs.lane(i), rhs.lane(i))))
+        });
+
+        self.write(&op.out, value);
+    }
+
+    fn unary<F: Fn(Scalar, Elem) -> Scalar>(&mut self, op: &UnaryOperator, func: F) {
+        let input = self.read(&op.input);
+        let item = op.out.item();
+        let value = Value::from_fn(item, |i| func(input.lane(i), item.elem));
+
+        self.write(&op.out, value);
+    }
+
+    fn unary_float<F: Fn(f64) -> f64>(&mut self, op: &UnaryOperator, func: F) {
+        self.unary(op, |input, _| Scalar::Float(func(input.as_f64())))
+    }
+
+    fn atomic<F: Fn(Scalar, Scalar, Elem) -> Scalar>(&mut self, op: &BinaryOperator, func: F) {
+        let (memory, index) = self.pointer(&op.lhs);
+        let rhs = self.read(&op.rhs).lane(0);
+        let elem = memory.item().elem;
+        let old = {
+            let _lock = self.atomic_lock();
+            let old = memory.read(index);
+            let new = Value::scalar(elem, func(old.lane(0), rhs, elem));
+            memory.write(index, &new);
+            old
+        };
+
+        self.write(&op.out, old);
+    }
+
+    fn reduce<F: Fn(Scalar, Scalar, Elem) -> Scalar>(&mut self, op: &UnaryOperator, func: F) {
+        let input = self.read(&op.input);
+        let item = op.out.item();
+        let units = self.exchange(input);
+        let value = units
+            .into_iter()
+            .map(|(_, value)| value)
+            .reduce(|acc, value| {
+                Value::from_fn(item, |i| func(acc.lane(i), value.lane(i), item.elem))
+            })
+            .unwrap_or(input);
+
+        self.write(&op.out, value);
+    }
+
+    /// Read the value of the unit returned by `source`, or keep the value of the current unit
+    /// when that unit doesn't exist, like CUDA does.
+    fn shuffle<F: Fn(usize, usize) -> Option<usize>>(&mut self, op: &BinaryOperator, source: F) {
+        let value = self.read(&op.lhs);
+        let source = source(self.index, self.read(&op.rhs).lane(0).as_usize());
+        let units = self.exchange(value);
+        let value = units
+            .into_iter()
+            .find(|(unit, _)| Some(*unit) == source)
+            .map(|(_, value)| value)
+            .unwrap_or(value);
+
+        self.write(&op.out, value);
+    }
+
+    /// Accumulate the values of the units up to the current one, which is excluded when an
+    /// identity is provided.
+    fn scan<F: Fn(Scalar, Scalar, Elem) -> Scalar>(
+        &mut self,
+        op: &UnaryOperator,
+        identity: Option<Scalar>,
+        func: F,
+    ) {
+        let input = self.read(&op.input);
+        let item = op.out.item();
+        let index = self.index;
+        let units = self.exchange(input);
+        let inclusive = identity.is_none();
+        let value = units
+            .into_iter()
+            .filter(|(unit, _)| *unit < index || (inclusive && *unit == index))
+            .map(|(_, value)| value)
+            .fold(
+                identity.map(|scalar| Value::from_fn(item, |_| scalar)),
+                |acc, value| {
+                    Some(match acc {
+                        Some(acc) => {
+                            Value::from_fn(item, |i| func(acc.lane(i), value.lane(i), item.elem))
+                        }
+                        None => value,
+                    })
+                },
+            )
+            .unwrap_or(input);
+
+        self.write(&op.out, value);
+    }
+
+    fn exchange(&self, value: Value) -> Vec<(usize, Value)> {
+        self.cube.sync.exchange(self.index, value)
+    }
+
+    fn index(&mut self, op: &BinaryOperator) {
+        let index = self.read(&op.rhs).lane(0).as_usize();
+
+        if !is_array(&op.lhs) {
+            let value = self.read(&op.lhs);
+            self.write(&op.out, Value::scalar(value.elem, value.lane(index)));
+            return;
+        }
+
+        let array = self.array(&op.lhs);
+
+        if op.out.item().elem.is_atomic() {
+            let index = match index < array.length {
+                true => array.offset + index,
+                false => usize::MAX,
+            };
+            let register = Register::Pointer {
+                memory: array.memory,
+                index,
+            };
+            self.set_register(&op.out, register);
+        } else {
+            let value = array.read(index);
+            self.write(&op.out, value);
+        }
+    }
+
+    fn index_assign(&mut self, op: &BinaryOperator) {
+        let index = self.read(&op.lhs);
+        let value = self.read(&op.rhs);
+
+        if !is_array(&op.out) {
+            let mut current = self.read(&op.out);
+            current.set_lane(index.lane(0).as_usize(), value.lane(0));
+            self.write(&op.out, current);
+            return;
+        }
+
+        let array = self.array(&op.out);
+
+        if index.len > 1 {
+            // Vectorized indices write each lane of the value at their own position.
+            for i in 0..index.len {
+                let lane = Value::scalar(value.elem, value.lane(i));
+                array.write(index.lane(i).as_usize(), &lane);
+            }
+        } else {
+            array.write(index.lane(0).as_usize(), &value);
+        }
+    }
+
+    fn array(&mut self, var: &Variable) -> ArrayRef {
+        let memory = match var {
+            Variable::GlobalInputArray { id, .. } => self.context.bindings.inputs[*id as usize],
+            Variable::GlobalOutputArray { id, .. } => self.context.bindings.outputs[*id as usize],
+            Variable::SharedMemory { id, item, length } => {
+                self.cube.shared_memory(*id, *item, *length)
+            }
+            Variable::LocalArray {
+                id,
+                item,
+                depth,
+                length,
+            } => self
+                .local_arrays
+                .slot(*depth, *id)
+                .get_or_insert_with(|| OwnedMemory::new(*item, *length as usize))
+                .view(),
+            Variable::Slice { id, depth, .. } => {
+                return *self
+                    .slices
+                    .get(*depth, *id)
+                    .expect("Slices should be created before being used.");
+            }
+            _ => panic!("Expected an array, got {var:?}"),
+        };
+
+        ArrayRef {
+            memory,
+            offset: 0,
+            length: memory.len(),
+        }
+    }
+
+    fn register(&self, var: &Variable) -> Register {
+        let register = match var {
+            Variable::Local { id, depth, .. } => self.locals.get(*depth, *id),
+            Variable::LocalScalar { id, depth, .. } => self.local_scalars.get(*depth, *id),
+            _ => return Register::Value(self.read(var)),
+        };
+
+        register
+            .copied()
+            .unwrap_or_else(|| Register::Value(Value::zeros(var.item())))
+    }
+
+    fn set_register(&mut self, var: &Variable, register: Register) {
+        let slot = match var {
+            Variable::Local { id, depth, .. } => self.locals.slot(*depth, *id),
+            Variable::LocalScalar { id, depth, .. } => self.local_scalars.slot(*depth, *id),
+            _ => panic!("Can only assign a local variable, got {var:?}"),
+        };
+
+        *slot = Some(register);
+    }
+
+    fn pointer(&self, var: &Variable) -> (Memory, usize) {
+        match self.register(var) {
+            Register::Pointer { memory, index } => (memory, index),
+            Register::Value(_) => panic!("Expected an atomic variable, got {var:?}"),
+        }
+    }
+
+    fn read(&self, var: &Variable) -> Value {
+        let uint = |val: u32| Value::scalar(Elem::UInt(UIntKind::U32), Scalar::UInt(val as u64));
+        let cube_dim = self.context.kernel.cube_dim;
+        let cube_dim = [cube_dim.x, cube_dim.y, cube_dim.z];
+        let cube_count = self.context.cube_count;
+        let cube_pos = self.cube.position;
+        let absolute_pos = |axis: usize| cube_pos[axis] * cube_dim[axis] + self.position[axis];
+
+        match var {
+            Variable::Local { .. } | Variable::LocalScalar { .. } => match self.register(var) {
+                Register::Value(value) => value,
+                Register::Pointer { memory, index } => memory.read(index),
+            },
+            Variable::ConstantScalar(value) => Value::constant(*value),
+            Variable::GlobalScalar { id, elem } => {
+                let (_, memory) = self
+                    .context
+                    .bindings
+                    .scalars
+                    .iter()
+                    .find(|(scalar_elem, _)| scalar_elem == elem)
+                    .unwrap_or_else(|| panic!("No scalars of type {elem} are bound."));
+
+                memory.read(*id as usize)
+            }
+            Variable::Rank => uint(self.info(0) as u32),
+            Variable::UnitPos => uint(self.index as u32),
+            Variable::UnitPosX => uint(self.position[0]),
+            Variable::UnitPosY => uint(self.position[1]),
+            Variable::UnitPosZ => uint(self.position[2]),
+            Variable::CubePos => uint(
+                cube_pos[2] * cube_count[0] * cube_count[1]
+                    + cube_pos[1] * cube_count[0]
+                    + cube_pos[0],
+            ),
+            Variable::CubePosX => uint(cube_pos[0]),
+            Variable::CubePosY => uint(cube_pos[1]),
+            Variable::CubePosZ => uint(cube_pos[2]),
+            Variable::CubeDim => uint(cube_dim[0] * cube_dim[1] * cube_dim[2]),
+            Variable::CubeDimX => uint(cube_dim[0]),
+            Variable::CubeDimY => uint(cube_dim[1]),
+            Variable::CubeDimZ => uint(cube_dim[2]),
+            Variable::CubeCount => uint(cube_count[0] * cube_count[1] * cube_count[2]),
+            Variable::CubeCountX => uint(cube_count[0]),
+            Variable::CubeCountY => uint(cube_count[1]),
+            Variable::CubeCountZ => uint(cube_count[2]),
+            // All units of a cube are part of the same subcube.
+            Variable::SubcubeDim => uint(cube_dim[0] * cube_dim[1] * cube_dim[2]),
+            Variable::AbsolutePos => {
+                let size_x = cube_count[0] * cube_dim[0];
+                let size_y = cube_count[1] * cube_dim[1];
+
+                uint(absolute_pos(2) * size_x * size_y + absolute_pos(1) * size_x + absolute_pos(0))
+            }
+            Variable::AbsolutePosX => uint(absolute_pos(0)),
+            Variable::AbsolutePosY => uint(absolute_pos(1)),
+            Variable::AbsolutePosZ => uint(absolute_pos(2)),
+            Variable::GlobalInputArray { .. }
+            | Variable::GlobalOutputArray { .. }
+            | Variable::SharedMemory { .. }
+            | Variable::LocalArray { .. }
+            | Variable::Slice { .. }
+            | Variable::Matrix { .. } => panic!("Can't read {var:?} as a value."),
+        }
+    }
+
+    fn write(&mut self, var: &Variable, value: Value) {
+        let item = var.item();
+
+        match self.register(var) {
+            // Writing to an atomic variable writes to the memory it references.
+            Register::Pointer { memory, index } => memory.write(index, &value),
+            Register::Value(_) => self.set_register(var, Register::Value(value.cast(item))),
+        }
+    }
+
+    fn info(&self, index: usize) -> usize {
+        let info = self
+            .context
+            .bindings
+            .info
+            .expect("The info buffer should be bound to access tensor metadata.");
+
+        info.read(index).lane(0).as_usize()
+    }
+
+    fn info_position(&self, var: &Variable) -> usize {
+        match var {
+            Variable::GlobalInputArray { id, .. } => *id as usize,
+            Variable::GlobalOutputArray { id, .. } => {
+                self.context.kernel.inputs.len() + *id as usize
+            }
+            _ => panic!("Only Input and Output have a stride and a shape, got: {var:?}"),
+        }
+    }
+
+    fn atomic_lock(&self) -> std::sync::MutexGuard<'_, ()> {
+        self.context
+            .atomic_lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn is_array(var: &Variable) -> bool {
+    matches!(
+        var,
+        Variable::GlobalInputArray { .. }
+            | Variable::GlobalOutputArray { .. }
+            | Variable::SharedMemory { .. }
+            | Variable::LocalArray { .. }
+            | Variable::Slice { .. }
+    )
+}
@@ -0,0 +1,272 @@
+use cubecl_core::ir::{ConstantScalarValue, Elem, FloatKind, IntKind, Item, UIntKind};
+
+/// The maximum number of lanes a [value](Value) can hold.
+pub(crate) const MAX_LANES: usize = 16;
+
+/// A single lane of a [value](Value).
+///
+/// Floats are stored with the highest precision possible, but they are always rounded to the
+/// precision of their element type when cast.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Scalar {
+    Float(f64),
+    Int(i64),
+    UInt(u64),
+    Bool(bool),
+}
+
+impl Scalar {
+    /// Convert the scalar to the given element type, applying the same wrapping and rounding
+    /// rules as a GPU would.
+    pub(crate) fn cast(self, elem: Elem) -> Self {
+        match elem {
+            Elem::Float(kind) | Elem::AtomicFloat(kind) => {
+                Scalar::Float(round_float(self.as_f64(), kind))
+            }
+            Elem::Int(kind) | Elem::AtomicInt(kind) => Scalar::Int(match (self, kind) {
+                // Float to integer conversions saturate instead of wrapping.
+                (Scalar::Float(val), IntKind::I8) => val as i8 as i64,
+                (Scalar::Float(val), IntKind::I16) => val as i16 as i64,
+                (Scalar::Float(val), IntKind::I32) => val as i32 as i64,
+                _ => wrap_int(self.as_i64(), kind),
+            }),
+            Elem::UInt(kind) => Scalar::UInt(match (self, kind) {
+                (Scalar::Float(val), UIntKind::U8) => val as u8 as u64,
+                (Scalar::Float(val), UIntKind::U16) => val as u16 as u64,
+                (Scalar::Float(val), UIntKind::U32) => val as u32 as u64,
+                _ => wrap_uint(self.as_u64(), kind),
+            }),
+            Elem::AtomicUInt => Scalar::UInt(match self {
+                Scalar::Float(val) => val as u32 as u64,
+                _ => self.as_u64() as u32 as u64,
+            }),
+            Elem::Bool => Scalar::Bool(self.as_bool()),
+        }
+    }
+
+    pub(crate) fn as_f64(self) -> f64 {
+        match self {
+            Scalar::Float(val) => val,
+            Scalar::Int(val) => val as f64,
+            Scalar::UInt(val) => val as f64,
+            Scalar::Bool(val) => val as u32 as f64,
+        }
+    }
+
+    pub(crate) fn as_i64(self) -> i64 {
+        match self {
+            Scalar::Float(val) => val as i64,
+            Scalar::Int(val) => val,
+            Scalar::UInt(val) => val as i64,
+            Scalar::Bool(val) => val as i64,
+        }
+    }
+
+    pub(crate) fn as_u64(self) -> u64 {
+        match self {
+            Scalar::Float(val) => val as u64,
+            Scalar::Int(val) => val as u64,
+            Scalar::UInt(val) => val,
+            Scalar::Bool(val) => val as u64,
+        }
+    }
+
+    pub(crate) fn as_usize(self) -> usize {
+        self.as_u64() as usize
+    }
+
+    pub(crate) fn as_bool(self) -> bool {
+        match self {
+            Scalar::Float(val) => val != 0.0,
+            Scalar::Int(val) => val != 0,
+            Scalar::UInt(val) => val != 0,
+            Scalar::Bool(val) => val,
+        }
+    }
+
+    /// The raw bits of the scalar encoded as the given element type.
+    pub(crate) fn to_bits(self, elem: Elem) -> u64 {
+        match self.cast(elem) {
+            Scalar::Float(val) => match elem {
+                Elem::Float(kind) | Elem::AtomicFloat(kind) => match kind {
+                    FloatKind::F16 => half::f16::from_f64(val).to_bits() as u64,
+                    FloatKind::BF16 => half::bf16::from_f64(val).to_bits() as u64,
+                    FloatKind::F32 => (val as f32).to_bits() as u64,
+                    FloatKind::F64 => val.to_bits(),
+                },
+                _ => val.to_bits(),
+            },
+            Scalar::Int(val) => val as u64,
+            Scalar::UInt(val) => val,
+            Scalar::Bool(val) => val as u64,
+        }
+    }
+
+    /// Create a scalar of the given element type from its raw bits.
+    pub(crate) fn from_bits(bits: u64, elem: Elem) -> Self {
+        match elem {
+            Elem::Float(kind) | Elem::AtomicFloat(kind) => Scalar::Float(match kind {
+                FloatKind::F16 => half::f16::from_bits(bits as u16).to_f64(),
+                FloatKind::BF16 => half::bf16::from_bits(bits as u16).to_f64(),
+                FloatKind::F32 => f32::from_bits(bits as u32) as f64,
+                FloatKind::F64 => f64::from_bits(bits),
+            }),
+            Elem::Int(kind) | Elem::AtomicInt(kind) => Scalar::Int(wrap_int(bits as i64, kind)),
+            Elem::UInt(kind) => Scalar::UInt(wrap_uint(bits, kind)),
+            Elem::AtomicUInt => Scalar::UInt(bits as u32 as u64),
+            Elem::Bool => Scalar::Bool(bits != 0),
+        }
+    }
+}
+
+fn round_float(val: f64, kind: FloatKind) -> f64 {
+    match kind {
+        FloatKind::F16 => half::f16::from_f64(val).to_f64(),
+        FloatKind::BF16 => half::bf16::from_f64(val).to_f64(),
+        FloatKind::F32 => val as f32 as f64,
+        FloatKind::F64 => val,
+    }
+}
+
+fn wrap_int(val: i64, kind: IntKind) -> i64 {
+    match kind {
+        IntKind::I8 => val as i8 as i64,
+        IntKind::I16 => val as i16 as i64,
+        IntKind::I32 => val as i32 as i64,
+        IntKind::I64 => val,
+    }
+}
+
+fn wrap_uint(val: u64, kind: UIntKind) -> u64 {
+    match kind {
+        UIntKind::U8 => val as u8 as u64,
+        UIntKind::U16 => val as u16 as u64,
+        UIntKind::U32 => val as u32 as u64,
+        UIntKind::U64 => val,
+    }
+}
+
+/// A possibly vectorized value held by a unit.
+///
+/// A value with a single lane is broadcasted when it is used with vectorized values.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Value {
+    pub elem: Elem,
+    pub len: usize,
+    lanes: [Scalar; MAX_LANES],
+}
+
+impl Value {
+    /// Create a value of the given item where every lane is computed by the provided function.
+    ///
+    /// Each lane is cast to the element type of the item.
+    pub(crate) fn from_fn<F: FnMut(usize) -> Scalar>(item: Item, mut func: F) -> Self {
+        let len = item.vectorization.max(1) as usize;
+        assert!(
+            len <= MAX_LANES,
+            "Vectorization factor {len} isn't supported by the cpu runtime."
+        );
+
+        let mut lanes = [Scalar::UInt(0); MAX_LANES];
+        for (i, lane) in lanes.iter_mut().enumerate().take(len) {
+            *lane = func(i).cast(item.elem);
+        }
+
+        Self {
+            elem: item.elem,
+            len,
+            lanes,
+        }
+    }
+
+    /// Create a value of the given item with all lanes set to zero.
+    pub(crate) fn zeros(item: Item) -> Self {
+        Self::from_fn(item, |_| Scalar::UInt(0))
+    }
+
+    /// Create a scalar value.
+    pub(crate) fn scalar(elem: Elem, scalar: Scalar) -> Self {
+        Self::from_fn(Item::new(elem), |_| scalar)
+    }
+
+    /// Create a value from a constant.
+    pub(crate) fn constant(value: ConstantScalarValue) -> Self {
+        let scalar = match value {
+            ConstantScalarValue::Int(val, _) => Scalar::Int(val),
+            ConstantScalarValue::Float(val, _) => Scalar::Float(val),
+            ConstantScalarValue::UInt(val, _) => Scalar::UInt(val),
+            ConstantScalarValue::Bool(val) => Scalar::Bool(val),
+        };
+
+        Self::scalar(value.elem(), scalar)
+    }
+
+    /// The item of the value.
+    pub(crate) fn item(&self) -> Item {
+        Item::vectorized(self.elem, self.len as u8)
+    }
+
+    /// Get the lane at the given position, scalars are broadcasted.
+    pub(crate) fn lane(&self, index: usize) -> Scalar {
+        if self.len == 1 {
+            self.lanes[0]
+        } else {
+            self.lanes[index.min(self.len - 1)]
+        }
+    }
+
+    /// Replace the lane at the given position.
+    pub(crate) fn set_lane(&mut self, index: usize, scalar: Scalar) {
+        if index < self.len {
+            self.lanes[index] = scalar.cast(self.elem);
+        }
+    }
+
+    /// Cast the value to the given item, broadcasting scalars if needed.
+    pub(crate) fn cast(&self, item: Item) -> Self {
+        Self::from_fn(item, |i| self.lane(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_wrap_unsigned_integers() {
+        let value = Scalar::Int(-1).cast(Elem::UInt(UIntKind::U32));
+
+        assert_eq!(value, Scalar::UInt(u32::MAX as u64));
+    }
+
+    #[test]
+    fn should_round_floats_to_their_precision() {
+        let value = Scalar::Float(0.1).cast(Elem::Float(FloatKind::F32));
+
+        assert_eq!(value, Scalar::Float(0.1f32 as f64));
+    }
+
+    #[test]
+    fn should_roundtrip_bits() {
+        for elem in [
+            Elem::Float(FloatKind::F16),
+            Elem::Float(FloatKind::F32),
+            Elem::Int(IntKind::I32),
+            Elem::UInt(UIntKind::U32),
+        ] {
+            let scalar = Scalar::Float(-2.5).cast(elem);
+            let bits = scalar.to_bits(elem);
+
+            assert_eq!(Scalar::from_bits(bits, elem), scalar);
+        }
+    }
+
+    #[test]
+    fn should_broadcast_scalars() {
+        let value = Value::scalar(Elem::UInt(UIntKind::U32), Scalar::UInt(3));
+        let value = value.cast(Item::vectorized(Elem::Float(FloatKind::F32), 4));
+
+        assert_eq!(value.len, 4);
+        assert_eq!(value.lane(3), Scalar::Float(3.0));
+    }
+}
@@ -0,0 +1,23 @@
+#[macro_use]
+extern crate derive_new;
+
+extern crate alloc;
+
+mod compiler;
+mod compute;
+mod device;
+mod interpreter;
+mod runtime;
+
+pub use compiler::*;
+pub use compute::*;
+pub use device::*;
+pub use runtime::*;
+
+#[cfg(test)]
+mod tests {
+    pub type TestRuntime = crate::CpuRuntime;
+
+    cubecl_core::testgen_all!();
+    cubecl_linalg::testgen_all!();
+}
@@ -0,0 +1,75 @@
+use crate::{compiler::CpuCompiler, compute::CpuServer, CpuDevice};
+use alloc::sync::Arc;
+use cubecl_core::{
+    ir::{Elem, FloatKind},
+    Feature, FeatureSet, Runtime,
+};
+use cubecl_runtime::{
+    channel::MutexComputeChannel,
+    client::ComputeClient,
+    memory_management::simple::{DeallocStrategy, SimpleMemoryManagement, SliceStrategy},
+    storage::BytesStorage,
+    ComputeRuntime,
+};
+
+/// Runtime that interprets kernels on the CPU.
+///
+/// It is slow, but it doesn't require any GPU or driver, which makes it useful to test kernels
+/// and to compare the results of the other runtimes against a reference.
+#[derive(Debug)]
+pub struct CpuRuntime;
+
+/// The compute instance is shared across all [cpu runtimes](CpuRuntime).
+static RUNTIME: ComputeRuntime<CpuDevice, Server, MutexComputeChannel<Server>> =
+    ComputeRuntime::new();
+
+type Server = CpuServer<SimpleMemoryManagement<BytesStorage>>;
+
+impl Runtime for CpuRuntime {
+    type Compiler = CpuCompiler;
+    type Server = Server;
+
+    type Channel = MutexComputeChannel<Server>;
+    type Device = CpuDevice;
+
+    fn client(device: &Self::Device) -> ComputeClient<Self::Server, Self::Channel> {
+        RUNTIME.client(device, move || {
+            let memory_management = SimpleMemoryManagement::new(
+                BytesStorage::default(),
+                DeallocStrategy::new_period_tick(32),
+                SliceStrategy::Ratio(0.8),
+            );
+            let server = CpuServer::new(memory_management);
+            let channel = MutexComputeChannel::new(server);
+
+            // All units of a cube are executed as a single subcube.
+            let mut features = FeatureSet::new(&[Feature::Subcube]);
+            let (f16, bf16, f32) = (
+                Elem::Float(FloatKind::F16),
+                Elem::Float(FloatKind::BF16),
+                Elem::Float(FloatKind::F32),
+            );
+            features.register_emulated_cmma(&[
+                (f16, f16, f16),
+                (f16, f16, f32),
+                (bf16, bf16, f32),
+                (f32, f32, f32),
+            ]);
+
+            for kind in [
+                FloatKind::F16,
+                FloatKind::BF16,
+                FloatKind::F32,
+                FloatKind::F64,
+            ] {
+                features.register(Feature::AtomicFloat(kind));
+            }
+
+            ComputeClient::new(channel, Arc::new(features))
+        })
+    }
+
+    fn name() -> &'static str {
+        "cpu"
+    }
+}
@@ -0,0 +1,4 @@
+pub use cubecl_cpp::*;
+
+/// The compiler generating the CUDA C++ source of kernels.
+pub type CudaCompiler = CppCompiler<Cuda>;
@@ -1,4 +1,5 @@
 mod server;
+mod staging;
 mod storage;
 
 pub use server::*;
@@ -1,7 +1,10 @@
 use crate::compiler::format_cpp_code;
 
+use super::staging::PinnedMemory;
 use super::storage::CudaStorage;
 use super::CudaResource;
+use core::time::Duration;
+use cubecl_common::benchmark::{TimestampsError, TimestampsResult};
 use cubecl_common::reader::{reader_from_concrete, Reader};
 use cubecl_common::sync_type::SyncType;
 use cubecl_core::compute::DebugInformation;
@@ -9,10 +12,13 @@ use cubecl_core::ir::CubeDim;
 use cubecl_core::FeatureSet;
 use cubecl_core::{prelude::*, KernelId};
 use cubecl_runtime::debug::DebugLogger;
+use cubecl_runtime::profiler::{ProfileEventKind, ProfileStart, ProfileTrace, Profiler};
 use cubecl_runtime::ExecutionMode;
 use cubecl_runtime::{
-    memory_management::MemoryManagement,
-    server::{self, ComputeServer},
+    memory_management::{MemoryManagement, MemoryUsage},
+    server::{self, ComputeError, ComputeServer},
+    staging::StagingBuffer,
+    stream::{EventId, StreamId},
 };
 use cudarc::driver::sys::CUctx_st;
 use cudarc::driver::sys::CUfunc_st;
@@ -25,6 +31,7 @@ use std::path::PathBuf;
 pub struct CudaServer<MM: MemoryManagement<CudaStorage>> {
     state: CudaServerState<MM>,
     logger: DebugLogger,
+    profiler: Profiler,
     pub(crate) archs: Vec<i32>,
     pub(crate) minimum_arch_version: i32,
 }
@@ -49,8 +56,32 @@ impl<MM: MemoryManagement<CudaStorage>> core::fmt::Debug for CudaServerState<MM>
 pub(crate) struct CudaContext<MM: MemoryManagement<CudaStorage>> {
     context: *mut CUctx_st,
     stream: cudarc::driver::sys::CUstream,
+    /// The streams created with [create_stream](ComputeServer::try_create_stream), the stream at
+    /// index `i` has the id `i + 1` since the default stream has the id zero.
+    streams: Vec<cudarc::driver::sys::CUstream>,
+    /// The events recorded since the last synchronization.
+    events: HashMap<EventId, cudarc::driver::sys::CUevent>,
+    num_events: u64,
+    /// The staging buffers of the uploads submitted since the last synchronization, which must be
+    /// kept alive until the copies are completed.
+    uploads: Vec<StagingBuffer>,
+    /// The bindings used since the last synchronization while other streams exist, which are kept
+    /// alive so that their memory isn't reused on another stream before the tasks are completed.
+    bindings: Vec<MM::Binding>,
     memory_management: MM,
     module_names: HashMap<KernelId, CompiledKernel>,
+    timestamps: Timestamps,
+}
+
+/// Events recorded on the stream to measure the time spent executing kernels.
+#[derive(Debug)]
+enum Timestamps {
+    Disabled,
+    /// The start event is recorded before the first kernel launched since the last measure, so
+    /// that the time the stream was idle isn't included.
+    Enabled {
+        start: Option<cudarc::driver::sys::CUevent>,
+    },
 }
 
 #[derive(Debug)]
@@ -63,17 +94,18 @@ struct CompiledKernel {
 unsafe impl<MM: MemoryManagement<CudaStorage>> Send for CudaServer<MM> {}
 
 impl<MM: MemoryManagement<CudaStorage>> CudaServer<MM> {
-    fn read_sync(&mut self, binding: server::Binding<Self>) -> Vec<u8> {
+    fn read_sync(&mut self, binding: server::Binding<Self>) -> Result<Vec<u8>, ComputeError> {
         let ctx = self.get_context();
-        let resource = ctx.memory_management.get_resource(binding.memory);
+        let resource = ctx.memory_management.try_get_resource(binding.memory)?;
 
         // TODO: Check if it is possible to make this faster
         let mut data = vec![0; resource.size() as usize];
         unsafe {
-            cudarc::driver::result::memcpy_dtoh_async(&mut data, resource.ptr, ctx.stream).unwrap();
+            cudarc::driver::result::memcpy_dtoh_async(&mut data, resource.ptr, ctx.stream)
+                .map_err(execution_error)?;
         };
         ctx.sync();
-        data
+        Ok(data)
     }
 }
 
@@ -84,39 +116,89 @@ impl<MM: MemoryManagement<CudaStorage>> ComputeServer for CudaServer<MM> {
     type MemoryManagement = MM;
     type FeatureSet = FeatureSet;
 
-    fn read(&mut self, binding: server::Binding<Self>) -> Reader {
-        reader_from_concrete(self.read_sync(binding))
+    fn try_read(&mut self, binding: server::Binding<Self>) -> Result<Reader, ComputeError> {
+        let started = self.profiler.now();
+        let data = self.read_sync(binding)?;
+
+        self.profiler
+            .register(started, ProfileEventKind::Read { size: data.len() });
+
+        Ok(reader_from_concrete(data))
     }
 
-    fn create(&mut self, data: &[u8]) -> server::Handle<Self> {
-        let handle = self.empty(data.len());
+    fn try_create(&mut self, data: &[u8]) -> Result<server::Handle<Self>, ComputeError> {
+        let started = self.profiler.now();
+        let handle = self.try_empty(data.len())?;
         let ctx = self.get_context();
 
-        let resource = ctx
-            .memory_management
-            .get_resource(handle.clone().binding().memory);
+        let resource = ctx.try_get_resource(handle.clone().binding().memory)?;
 
         unsafe {
-            cudarc::driver::result::memcpy_htod_async(resource.ptr, data, ctx.stream).unwrap();
+            cudarc::driver::result::memcpy_htod_async(resource.ptr, data, ctx.stream)
+                .map_err(execution_error)?;
         }
 
-        handle
+        self.profiler
+            .register(started, ProfileEventKind::Create { size: data.len() });
+
+        Ok(handle)
     }
 
-    fn empty(&mut self, size: usize) -> server::Handle<Self> {
+    fn try_empty(&mut self, size: usize) -> Result<server::Handle<Self>, ComputeError> {
         let ctx = self.get_context();
-        let handle = ctx.memory_management.reserve(size, &[]);
-        server::Handle::new(handle)
+        let handle = ctx.memory_management.try_reserve(size, &[])?;
+        Ok(server::Handle::new(handle, size))
+    }
+
+    fn try_staging(&mut self, size: usize) -> Result<StagingBuffer, ComputeError> {
+        // Page-locked memory must be allocated while a context is current.
+        self.get_context();
+        Ok(StagingBuffer::new(PinnedMemory::new(size)?))
+    }
+
+    fn try_upload(
+        &mut self,
+        buffer: StagingBuffer,
+        stream: StreamId,
+    ) -> Result<server::Handle<Self>, ComputeError> {
+        let started = self.profiler.now();
+        let stream = self.get_context().stream(stream)?;
+        let handle = self.try_empty(buffer.len())?;
+        let ctx = self.get_context();
+
+        let resource = ctx.try_get_resource(handle.clone().binding().memory)?;
+
+        // The copy is only asynchronous when the buffer is page-locked, otherwise the driver
+        // stages the data before returning.
+        unsafe {
+            cudarc::driver::result::memcpy_htod_async(resource.ptr, &buffer[..], stream)
+                .map_err(execution_error)?;
+        }
+
+        let size = buffer.len();
+        ctx.uploads.push(buffer);
+        self.profiler
+            .register(started, ProfileEventKind::Create { size });
+
+        Ok(handle)
     }
 
-    unsafe fn execute(
+    unsafe fn try_execute(
         &mut self,
         kernel: Self::Kernel,
         count: Self::DispatchOptions,
         bindings: Vec<server::Binding<Self>>,
         mode: ExecutionMode,
-    ) {
+        stream: StreamId,
+    ) -> Result<(), ComputeError> {
+        // Fail before compiling the kernel when the stream doesn't exist.
+        self.get_context().stream(stream)?;
+
         let arch = self.minimum_arch_version;
+        let profile = self
+            .profiler
+            .is_activated()
+            .then(|| (kernel.name(), kernel.id().to_string()));
 
         let mut kernel_id = kernel.id();
         kernel_id.mode(mode);
@@ -127,7 +209,7 @@ impl<MM: MemoryManagement<CudaStorage>> ComputeServer for CudaServer<MM> {
             // One option is to create a dummy kernel with 1 thread that launches the real kernel with the dynamic dispatch settings.
             // For now, just read the dispatch settings from the buffer.
             CubeCount::Dynamic(binding) => {
-                let data = self.read_sync(binding);
+                let data = self.read_sync(binding)?;
                 let data = bytemuck::cast_slice(&data);
                 assert!(
                     data.len() == 3,
@@ -137,18 +219,114 @@ impl<MM: MemoryManagement<CudaStorage>> ComputeServer for CudaServer<MM> {
             }
         };
 
-        let (ctx, logger) = self.get_context_with_logger();
-
-        if !ctx.module_names.contains_key(&kernel_id) {
-            ctx.compile_kernel(&kernel_id, kernel, arch, logger, mode);
+        if !self.get_context().module_names.contains_key(&kernel_id) {
+            let started = self.profiler.now();
+            let (ctx, logger) = self.get_context_with_logger();
+            ctx.compile_kernel(&kernel_id, kernel, arch, logger, mode)?;
+
+            if let Some((name, id)) = &profile {
+                self.profiler.register(
+                    started,
+                    ProfileEventKind::Compile {
+                        name: name.to_string(),
+                        id: id.clone(),
+                    },
+                );
+            }
         }
 
+        let ctx = self.get_context();
         let resources = bindings
             .into_iter()
-            .map(|binding| ctx.memory_management.get_resource(binding.memory))
-            .collect::<Vec<_>>();
+            .map(|binding| ctx.try_get_resource(binding.memory))
+            .collect::<Result<Vec<_>, _>>()?;
+        let binding_sizes = resources
+            .iter()
+            .map(|resource| resource.size() as usize)
+            .collect();
+
+        let started = profile.is_some().then(ProfileStart::now);
+        ctx.execute_task(kernel_id, count, resources, stream)?;
+
+        if let Some((name, id)) = profile {
+            // Wait for the kernel to complete to measure its execution time.
+            ctx.sync();
+            self.profiler.register(
+                started,
+                ProfileEventKind::Execute {
+                    name: name.into(),
+                    id,
+                    cube_count: Some([count.0, count.1, count.2]),
+                    binding_sizes,
+                },
+            );
+        }
 
-        ctx.execute_task(kernel_id, count, resources);
+        Ok(())
+    }
+
+    fn try_create_stream(&mut self) -> Result<StreamId, ComputeError> {
+        let ctx = self.get_context();
+        // The memory released by the pending tasks of the default stream isn't tracked, so it must
+        // be available before another stream can reuse it.
+        ctx.sync();
+        let stream = cudarc::driver::result::stream::create(
+            cudarc::driver::result::stream::StreamKind::NonBlocking,
+        )
+        .map_err(execution_error)?;
+        ctx.streams.push(stream);
+
+        Ok(StreamId {
+            index: ctx.streams.len() as u32,
+        })
+    }
+
+    fn try_record_event(&mut self, stream: StreamId) -> Result<EventId, ComputeError> {
+        let ctx = self.get_context();
+        let stream = ctx.stream(stream)?;
+
+        let event = unsafe {
+            let event = cudarc::driver::result::event::create(
+                cudarc::driver::sys::CUevent_flags::CU_EVENT_DISABLE_TIMING,
+            )
+            .map_err(execution_error)?;
+            cudarc::driver::result::event::record(event, stream).map_err(execution_error)?;
+            event
+        };
+
+        ctx.num_events += 1;
+        let id = EventId {
+            index: ctx.num_events,
+        };
+        ctx.events.insert(id, event);
+
+        Ok(id)
+    }
+
+    fn try_wait_event(&mut self, stream: StreamId, event: EventId) -> Result<(), ComputeError> {
+        let ctx = self.get_context();
+        let stream = ctx.stream(stream)?;
+
+        if event.index > ctx.num_events {
+            return Err(ComputeError::InvalidEvent(format!(
+                "The event {} doesn't exist, only {} events were recorded.",
+                event.index, ctx.num_events
+            )));
+        }
+
+        // Events are cleared when every stream is synchronized, so they are already completed.
+        let Some(event) = ctx.events.get(&event) else {
+            return Ok(());
+        };
+
+        unsafe {
+            cudarc::driver::result::stream::wait_event(
+                stream,
+                *event,
+                cudarc::driver::sys::CUevent_wait_flags::CU_EVENT_WAIT_DEFAULT,
+            )
+            .map_err(execution_error)
+        }
     }
 
     fn sync(&mut self, sync_type: SyncType) {
@@ -163,6 +341,80 @@ impl<MM: MemoryManagement<CudaStorage>> ComputeServer for CudaServer<MM> {
         }
     }
 
+    fn memory_usage(&mut self) -> MemoryUsage {
+        let ctx = self.get_context();
+        ctx.memory_management.memory_usage()
+    }
+
+    fn memory_cleanup(&mut self) {
+        let ctx = self.get_context();
+        ctx.memory_management.cleanup();
+        // The memory used by the pending tasks of the other streams is still bound, so only the
+        // default stream can still use the freed memory, and frees are ordered on it.
+        ctx.memory_management.storage().perform_deallocations();
+    }
+
+    fn enable_timestamps(&mut self) {
+        let ctx = self.get_context();
+        if let Timestamps::Disabled = ctx.timestamps {
+            ctx.timestamps = Timestamps::Enabled { start: None };
+        }
+    }
+
+    fn disable_timestamps(&mut self) {
+        let ctx = self.get_context();
+        if let Timestamps::Enabled { start: Some(start) } = ctx.timestamps {
+            unsafe {
+                let _ = cudarc::driver::result::event::destroy(start);
+            }
+        }
+        ctx.timestamps = Timestamps::Disabled;
+    }
+
+    fn timestamps_enabled(&mut self) -> bool {
+        !matches!(self.get_context().timestamps, Timestamps::Disabled)
+    }
+
+    fn sync_elapsed(&mut self) -> TimestampsResult {
+        let ctx = self.get_context();
+        let start = match &mut ctx.timestamps {
+            Timestamps::Disabled => return Err(TimestampsError::Disabled),
+            Timestamps::Enabled { start } => start.take(),
+        };
+
+        let Some(start) = start else {
+            // No kernel was launched since the last measure.
+            ctx.sync();
+            return Ok(Duration::ZERO);
+        };
+
+        let elapsed = unsafe {
+            let end = cudarc::driver::result::event::create(
+                cudarc::driver::sys::CUevent_flags::CU_EVENT_DEFAULT,
+            )
+            .map_err(timestamps_error)?;
+            ctx.wait_streams().map_err(timestamps_error)?;
+            cudarc::driver::result::event::record(end, ctx.stream).map_err(timestamps_error)?;
+            ctx.sync();
+
+            let elapsed = cudarc::driver::result::event::elapsed(start, end);
+            let _ = cudarc::driver::result::event::destroy(start);
+            let _ = cudarc::driver::result::event::destroy(end);
+            elapsed.map_err(timestamps_error)?
+        };
+
+        // The elapsed time is in milliseconds.
+        Ok(Duration::from_secs_f64(elapsed as f64 / 1000.0))
+    }
+
+    fn start_profile(&mut self) {
+        self.profiler.start();
+    }
+
+    fn end_profile(&mut self) -> ProfileTrace {
+        self.profiler.end()
+    }
+
     fn get_resource(
         &mut self,
         binding: server::Binding<Self>,
@@ -183,14 +435,30 @@ impl<MM: MemoryManagement<CudaStorage>> CudaContext<MM> {
             memory_management,
             module_names: HashMap::new(),
             stream,
+            streams: Vec::new(),
+            events: HashMap::new(),
+            num_events: 0,
+            uploads: Vec::new(),
+            bindings: Vec::new(),
+            timestamps: Timestamps::Disabled,
         }
     }
 
     fn sync(&mut self) {
         unsafe {
             cudarc::driver::result::stream::synchronize(self.stream).unwrap();
+            for stream in self.streams.iter() {
+                cudarc::driver::result::stream::synchronize(*stream).unwrap();
+            }
+            for (_, event) in self.events.drain() {
+                let _ = cudarc::driver::result::event::destroy(event);
+            }
         };
-        self.memory_management.storage().flush();
+        self.uploads.clear();
+        self.bindings.clear();
+        let storage = self.memory_management.storage();
+        storage.flush();
+        storage.perform_deallocations();
     }
 
     fn compile_kernel(
@@ -200,11 +468,12 @@ impl<MM: MemoryManagement<CudaStorage>> CudaContext<MM> {
         arch: i32,
         logger: &mut DebugLogger,
         mode: ExecutionMode,
-    ) {
-        let mut kernel_compiled = kernel.compile(mode);
+    ) -> Result<(), ComputeError> {
+        let mut kernel_compiled = kernel.try_compile(mode)?;
 
         if logger.is_activated() {
             kernel_compiled.debug_info = Some(DebugInformation::new("cpp", kernel_id.clone()));
+            kernel_compiled.ir = kernel.ir();
 
             if let Ok(formatted) = format_cpp_code(&kernel_compiled.source) {
                 kernel_compiled.source = formatted;
@@ -220,9 +489,11 @@ impl<MM: MemoryManagement<CudaStorage>> CudaContext<MM> {
         let options = &[arch.as_str(), include_option.as_str()];
 
         let kernel_compiled = logger.debug(kernel_compiled);
+        let source = kernel_compiled.source.clone();
 
         let ptx = unsafe {
-            let program = cudarc::nvrtc::result::create_program(kernel_compiled.source).unwrap();
+            let program = cudarc::nvrtc::result::create_program(kernel_compiled.source)
+                .map_err(|err| ComputeError::Compilation(format!("{err:?}")))?;
             if cudarc::nvrtc::result::compile_program(program, options).is_err() {
                 let log_raw = cudarc::nvrtc::result::get_program_log(program).unwrap();
                 let log_ptr = log_raw.as_ptr();
@@ -233,17 +504,20 @@ impl<MM: MemoryManagement<CudaStorage>> CudaContext<MM> {
                         message += format!("\n    {line}").as_str();
                     }
                 }
-                let source = kernel.compile(mode).source;
-                panic!("{message}\n[Source]  \n{source}");
+                return Err(ComputeError::Compilation(format!(
+                    "{message}\n[Source]  \n{source}"
+                )));
             };
-            cudarc::nvrtc::result::get_ptx(program).unwrap()
+            cudarc::nvrtc::result::get_ptx(program)
+                .map_err(|err| ComputeError::Compilation(format!("{err:?}")))?
         };
 
         let func_name = CString::new("kernel".to_string()).unwrap();
         let func = unsafe {
-            let module =
-                cudarc::driver::result::module::load_data(ptx.as_ptr() as *const _).unwrap();
-            cudarc::driver::result::module::get_function(module, func_name).unwrap()
+            let module = cudarc::driver::result::module::load_data(ptx.as_ptr() as *const _)
+                .map_err(execution_error)?;
+            cudarc::driver::result::module::get_function(module, func_name)
+                .map_err(execution_error)?
         };
 
         self.module_names.insert(
@@ -254,6 +528,8 @@ impl<MM: MemoryManagement<CudaStorage>> CudaContext<MM> {
                 func,
             },
         );
+
+        Ok(())
     }
 
     fn execute_task(
@@ -261,12 +537,29 @@ impl<MM: MemoryManagement<CudaStorage>> CudaContext<MM> {
         kernel_id: KernelId,
         dispatch_count: (u32, u32, u32),
         resources: Vec<CudaResource>,
-    ) {
+        stream: StreamId,
+    ) -> Result<(), ComputeError> {
+        let stream = self.stream(stream)?;
         let mut bindings = resources
             .iter()
             .map(|memory| memory.as_binding())
             .collect::<Vec<_>>();
 
+        // Start measuring right before the first kernel.
+        if let Timestamps::Enabled { start } = &mut self.timestamps {
+            if start.is_none() {
+                unsafe {
+                    let event = cudarc::driver::result::event::create(
+                        cudarc::driver::sys::CUevent_flags::CU_EVENT_DEFAULT,
+                    )
+                    .map_err(execution_error)?;
+                    cudarc::driver::result::event::record(event, stream)
+                        .map_err(execution_error)?;
+                    *start = Some(event);
+                }
+            }
+        }
+
         let kernel = self.module_names.get(&kernel_id).unwrap();
         let cube_dim = kernel.cube_dim;
         unsafe {
@@ -275,11 +568,75 @@ impl<MM: MemoryManagement<CudaStorage>> CudaContext<MM> {
                 dispatch_count,
                 (cube_dim.x, cube_dim.y, cube_dim.z),
                 kernel.shared_mem_bytes as u32,
-                self.stream,
+                stream,
                 &mut bindings,
             )
-            .unwrap();
-        };
+            .map_err(execution_error)
+        }
+    }
+}
+
+impl<MM: MemoryManagement<CudaStorage>> Drop for CudaContext<MM> {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = cudarc::driver::result::ctx::set_current(self.context);
+            for (_, event) in self.events.drain() {
+                let _ = cudarc::driver::result::event::destroy(event);
+            }
+            // The pending uploads must complete before their staging buffers are dropped.
+            for stream in self.streams.drain(..) {
+                let _ = cudarc::driver::result::stream::synchronize(stream);
+                let _ = cudarc::driver::result::stream::destroy(stream);
+            }
+        }
+    }
+}
+
+impl<MM: MemoryManagement<CudaStorage>> CudaContext<MM> {
+    /// Get the resource of the binding, which is kept alive until the next synchronization when
+    /// it can be used concurrently with the tasks of other streams.
+    fn try_get_resource(&mut self, binding: MM::Binding) -> Result<CudaResource, ComputeError> {
+        if !self.streams.is_empty() {
+            self.bindings.push(binding.clone());
+        }
+        self.memory_management.try_get_resource(binding)
+    }
+
+    /// Make the default stream wait for the tasks submitted to the other streams.
+    fn wait_streams(&mut self) -> Result<(), cudarc::driver::DriverError> {
+        for stream in self.streams.iter() {
+            unsafe {
+                let event = cudarc::driver::result::event::create(
+                    cudarc::driver::sys::CUevent_flags::CU_EVENT_DISABLE_TIMING,
+                )?;
+                cudarc::driver::result::event::record(event, *stream)?;
+                let waited = cudarc::driver::result::stream::wait_event(
+                    self.stream,
+                    event,
+                    cudarc::driver::sys::CUevent_wait_flags::CU_EVENT_WAIT_DEFAULT,
+                );
+                // The event is destroyed once completed, after the wait is enqueued.
+                cudarc::driver::result::event::destroy(event)?;
+                waited?;
+            }
+        }
+        Ok(())
+    }
+
+    fn stream(&self, stream: StreamId) -> Result<cudarc::driver::sys::CUstream, ComputeError> {
+        match stream.index {
+            0 => Ok(self.stream),
+            index => self
+                .streams
+                .get(index as usize - 1)
+                .copied()
+                .ok_or_else(|| {
+                    ComputeError::InvalidStream(format!(
+                        "The stream {index} doesn't exist, only {} streams were created.",
+                        self.streams.len()
+                    ))
+                }),
+        }
     }
 }
 
@@ -304,6 +661,7 @@ impl<MM: MemoryManagement<CudaStorage>> CudaServer<MM> {
                 init,
             },
             logger: DebugLogger::new(),
+            profiler: Profiler::new(),
             archs,
             minimum_arch_version,
         }
@@ -329,6 +687,14 @@ impl<MM: MemoryManagement<CudaStorage>> CudaServer<MM> {
     }
 }
 
+fn execution_error(err: cudarc::driver::DriverError) -> ComputeError {
+    ComputeError::Execution(format!("{err:?}"))
+}
+
+fn timestamps_error(err: cudarc::driver::DriverError) -> TimestampsError {
+    TimestampsError::Unknown(format!("{err:?}"))
+}
+
 fn include_path() -> PathBuf {
     let mut path = cuda_path().expect("
         CUDA installation not found.
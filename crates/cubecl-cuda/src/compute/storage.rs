@@ -1,4 +1,7 @@
-use cubecl_runtime::storage::{ComputeStorage, StorageHandle, StorageId, StorageUtilization};
+use cubecl_runtime::{
+    server::ComputeError,
+    storage::{ComputeStorage, StorageHandle, StorageId, StorageUtilization},
+};
 use cudarc::driver::sys::CUstream;
 use std::collections::HashMap;
 
@@ -132,11 +135,20 @@ impl ComputeStorage for CudaStorage {
         }
     }
 
-    fn alloc(&mut self, size: usize) -> StorageHandle {
+    fn try_alloc(&mut self, size: usize) -> Result<StorageHandle, ComputeError> {
         let id = StorageId::new();
-        let ptr = unsafe { cudarc::driver::result::malloc_async(self.stream, size).unwrap() };
+        let ptr = match unsafe { cudarc::driver::result::malloc_async(self.stream, size) } {
+            Ok(ptr) => ptr,
+            Err(err) if err.0 == cudarc::driver::sys::CUresult::CUDA_ERROR_OUT_OF_MEMORY => {
+                return Err(ComputeError::OutOfMemory {
+                    size,
+                    reason: format!("{err:?}"),
+                })
+            }
+            Err(err) => return Err(ComputeError::Execution(format!("{err:?}"))),
+        };
         self.memory.insert(id, ptr);
-        StorageHandle::new(id, StorageUtilization::Full(size))
+        Ok(StorageHandle::new(id, StorageUtilization::Full(size)))
     }
 
     fn dealloc(&mut self, id: StorageId) {
@@ -54,7 +54,8 @@ impl Runtime for CudaRuntime {
 
         RUNTIME.client(device, move || {
             let mut server = CudaServer::new(device.index, Box::new(init));
-            let mut features = FeatureSet::new(&[Feature::Subcube]);
+            let mut features =
+                FeatureSet::new(&[Feature::Subcube, Feature::AtomicFloat(FloatKind::F32)]);
 
             if let Some(wmma_minimum_version) = register_wmma_features(&mut features, &server.archs)
             {
@@ -0,0 +1,4 @@
+pub use cubecl_cpp::*;
+
+/// The compiler generating the HIP C++ source of kernels.
+pub type HipCompiler = CppCompiler<Hip>;
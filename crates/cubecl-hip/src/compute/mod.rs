@@ -0,0 +1,7 @@
+mod server;
+mod staging;
+mod storage;
+pub(crate) mod sys;
+
+pub use server::*;
+pub use storage::*;
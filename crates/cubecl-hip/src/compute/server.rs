@@ -0,0 +1,794 @@
+use crate::compiler::format_cpp_code;
+
+use super::staging::PinnedMemory;
+use super::storage::HipStorage;
+use super::sys::{self, hip, hip_result, hiprtc, hiprtc_result, HipError};
+use super::HipResource;
+use core::ffi::{c_char, c_void};
+use core::time::Duration;
+use cubecl_common::benchmark::{TimestampsError, TimestampsResult};
+use cubecl_common::reader::{reader_from_concrete, Reader};
+use cubecl_common::sync_type::SyncType;
+use cubecl_core::compute::DebugInformation;
+use cubecl_core::ir::CubeDim;
+use cubecl_core::FeatureSet;
+use cubecl_core::{prelude::*, KernelId};
+use cubecl_runtime::debug::DebugLogger;
+use cubecl_runtime::profiler::{ProfileEventKind, ProfileStart, ProfileTrace, Profiler};
+use cubecl_runtime::ExecutionMode;
+use cubecl_runtime::{
+    memory_management::{MemoryManagement, MemoryUsage},
+    server::{self, ComputeError, ComputeServer},
+    staging::StagingBuffer,
+    stream::{EventId, StreamId},
+};
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub struct HipServer<MM: MemoryManagement<HipStorage>> {
+    state: HipServerState<MM>,
+    logger: DebugLogger,
+    profiler: Profiler,
+}
+
+pub(crate) enum HipServerState<MM: MemoryManagement<HipStorage>> {
+    Uninitialized {
+        device_index: usize,
+        init: Box<dyn Fn(usize) -> HipContext<MM>>,
+    },
+    Initialized {
+        ctx: HipContext<MM>,
+    },
+}
+
+impl<MM: MemoryManagement<HipStorage>> core::fmt::Debug for HipServerState<MM> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Context")
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct HipContext<MM: MemoryManagement<HipStorage>> {
+    /// HIP has no explicit context, the device is made current on the calling thread instead.
+    device_index: usize,
+    stream: sys::hipStream_t,
+    /// The streams created with [create_stream](ComputeServer::try_create_stream), the stream at
+    /// index `i` has the id `i + 1` since the default stream has the id zero.
+    streams: Vec<sys::hipStream_t>,
+    /// The events recorded since the last synchronization.
+    events: HashMap<EventId, sys::hipEvent_t>,
+    num_events: u64,
+    /// The staging buffers of the uploads submitted since the last synchronization, which must be
+    /// kept alive until the copies are completed.
+    uploads: Vec<StagingBuffer>,
+    /// The bindings used since the last synchronization while other streams exist, which are kept
+    /// alive so that their memory isn't reused on another stream before the tasks are completed.
+    bindings: Vec<MM::Binding>,
+    memory_management: MM,
+    module_names: HashMap<KernelId, CompiledKernel>,
+    timestamps: Timestamps,
+}
+
+/// Events recorded on the stream to measure the time spent executing kernels.
+#[derive(Debug)]
+enum Timestamps {
+    Disabled,
+    /// The start event is recorded before the first kernel launched since the last measure, so
+    /// that the time the stream was idle isn't included.
+    Enabled {
+        start: Option<sys::hipEvent_t>,
+    },
+}
+
+#[derive(Debug)]
+struct CompiledKernel {
+    cube_dim: CubeDim,
+    shared_mem_bytes: usize,
+    func: sys::hipFunction_t,
+}
+
+unsafe impl<MM: MemoryManagement<HipStorage>> Send for HipServer<MM> {}
+
+impl<MM: MemoryManagement<HipStorage>> HipServer<MM> {
+    fn read_sync(&mut self, binding: server::Binding<Self>) -> Result<Vec<u8>, ComputeError> {
+        let ctx = self.get_context();
+        let resource = ctx.memory_management.try_get_resource(binding.memory)?;
+
+        let mut data = vec![0; resource.size() as usize];
+        unsafe {
+            hip_result((hip().hipMemcpyDtoHAsync)(
+                data.as_mut_ptr() as *mut c_void,
+                resource.ptr as sys::hipDeviceptr_t,
+                data.len(),
+                ctx.stream,
+            ))
+            .map_err(execution_error)?;
+        };
+        ctx.sync();
+        Ok(data)
+    }
+}
+
+impl<MM: MemoryManagement<HipStorage>> ComputeServer for HipServer<MM> {
+    type Kernel = Box<dyn CubeTask>;
+    type DispatchOptions = CubeCount<Self>;
+    type Storage = HipStorage;
+    type MemoryManagement = MM;
+    type FeatureSet = FeatureSet;
+
+    fn try_read(&mut self, binding: server::Binding<Self>) -> Result<Reader, ComputeError> {
+        let started = self.profiler.now();
+        let data = self.read_sync(binding)?;
+
+        self.profiler
+            .register(started, ProfileEventKind::Read { size: data.len() });
+
+        Ok(reader_from_concrete(data))
+    }
+
+    fn try_create(&mut self, data: &[u8]) -> Result<server::Handle<Self>, ComputeError> {
+        let started = self.profiler.now();
+        let handle = self.try_empty(data.len())?;
+        let ctx = self.get_context();
+
+        let resource = ctx.try_get_resource(handle.clone().binding().memory)?;
+
+        unsafe {
+            memcpy_htod_async(&resource, data, ctx.stream).map_err(execution_error)?;
+        }
+
+        self.profiler
+            .register(started, ProfileEventKind::Create { size: data.len() });
+
+        Ok(handle)
+    }
+
+    fn try_empty(&mut self, size: usize) -> Result<server::Handle<Self>, ComputeError> {
+        let ctx = self.get_context();
+        let handle = ctx.memory_management.try_reserve(size, &[])?;
+        Ok(server::Handle::new(handle, size))
+    }
+
+    fn try_staging(&mut self, size: usize) -> Result<StagingBuffer, ComputeError> {
+        // Page-locked memory must be allocated once the runtime is initialized.
+        self.get_context();
+        Ok(StagingBuffer::new(PinnedMemory::new(size)?))
+    }
+
+    fn try_upload(
+        &mut self,
+        buffer: StagingBuffer,
+        stream: StreamId,
+    ) -> Result<server::Handle<Self>, ComputeError> {
+        let started = self.profiler.now();
+        let stream = self.get_context().stream(stream)?;
+        let handle = self.try_empty(buffer.len())?;
+        let ctx = self.get_context();
+
+        let resource = ctx.try_get_resource(handle.clone().binding().memory)?;
+
+        // The copy is only asynchronous when the buffer is page-locked, otherwise the runtime
+        // stages the data before returning.
+        unsafe {
+            memcpy_htod_async(&resource, &buffer[..], stream).map_err(execution_error)?;
+        }
+
+        let size = buffer.len();
+        ctx.uploads.push(buffer);
+        self.profiler
+            .register(started, ProfileEventKind::Create { size });
+
+        Ok(handle)
+    }
+
+    unsafe fn try_execute(
+        &mut self,
+        kernel: Self::Kernel,
+        count: Self::DispatchOptions,
+        bindings: Vec<server::Binding<Self>>,
+        mode: ExecutionMode,
+        stream: StreamId,
+    ) -> Result<(), ComputeError> {
+        // Fail before compiling the kernel when the stream doesn't exist.
+        self.get_context().stream(stream)?;
+
+        let profile = self
+            .profiler
+            .is_activated()
+            .then(|| (kernel.name(), kernel.id().to_string()));
+
+        let mut kernel_id = kernel.id();
+        kernel_id.mode(mode);
+
+        let count = match count {
+            CubeCount::Static(x, y, z) => (x, y, z),
+            // Like CUDA, HIP has no dynamic dispatch, the dispatch settings are read from the
+            // buffer instead.
+            CubeCount::Dynamic(binding) => {
+                let data = self.read_sync(binding)?;
+                let data = bytemuck::cast_slice(&data);
+                assert!(
+                    data.len() == 3,
+                    "Dynamic cube count should contain 3 values"
+                );
+                (data[0], data[1], data[2])
+            }
+        };
+
+        if !self.get_context().module_names.contains_key(&kernel_id) {
+            let started = self.profiler.now();
+            let (ctx, logger) = self.get_context_with_logger();
+            ctx.compile_kernel(&kernel_id, kernel, logger, mode)?;
+
+            if let Some((name, id)) = &profile {
+                self.profiler.register(
+                    started,
+                    ProfileEventKind::Compile {
+                        name: name.to_string(),
+                        id: id.clone(),
+                    },
+                );
+            }
+        }
+
+        let ctx = self.get_context();
+        let resources = bindings
+            .into_iter()
+            .map(|binding| ctx.try_get_resource(binding.memory))
+            .collect::<Result<Vec<_>, _>>()?;
+        let binding_sizes = resources
+            .iter()
+            .map(|resource| resource.size() as usize)
+            .collect();
+
+        let started = profile.is_some().then(ProfileStart::now);
+        ctx.execute_task(kernel_id, count, resources, stream)?;
+
+        if let Some((name, id)) = profile {
+            // Wait for the kernel to complete to measure its execution time.
+            ctx.sync();
+            self.profiler.register(
+                started,
+                ProfileEventKind::Execute {
+                    name: name.into(),
+                    id,
+                    cube_count: Some([count.0, count.1, count.2]),
+                    binding_sizes,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    fn try_create_stream(&mut self) -> Result<StreamId, ComputeError> {
+        let ctx = self.get_context();
+        // The memory released by the pending tasks of the default stream isn't tracked, so it must
+        // be available before another stream can reuse it.
+        ctx.sync();
+        let stream = create_stream().map_err(execution_error)?;
+        ctx.streams.push(stream);
+
+        Ok(StreamId {
+            index: ctx.streams.len() as u32,
+        })
+    }
+
+    fn try_record_event(&mut self, stream: StreamId) -> Result<EventId, ComputeError> {
+        let ctx = self.get_context();
+        let stream = ctx.stream(stream)?;
+
+        let event = unsafe {
+            let event = create_event(sys::HIP_EVENT_DISABLE_TIMING).map_err(execution_error)?;
+            hip_result((hip().hipEventRecord)(event, stream)).map_err(execution_error)?;
+            event
+        };
+
+        ctx.num_events += 1;
+        let id = EventId {
+            index: ctx.num_events,
+        };
+        ctx.events.insert(id, event);
+
+        Ok(id)
+    }
+
+    fn try_wait_event(&mut self, stream: StreamId, event: EventId) -> Result<(), ComputeError> {
+        let ctx = self.get_context();
+        let stream = ctx.stream(stream)?;
+
+        if event.index > ctx.num_events {
+            return Err(ComputeError::InvalidEvent(format!(
+                "The event {} doesn't exist, only {} events were recorded.",
+                event.index, ctx.num_events
+            )));
+        }
+
+        // Events are cleared when every stream is synchronized, so they are already completed.
+        let Some(event) = ctx.events.get(&event) else {
+            return Ok(());
+        };
+
+        unsafe {
+            hip_result((hip().hipStreamWaitEvent)(stream, *event, 0)).map_err(execution_error)
+        }
+    }
+
+    fn sync(&mut self, sync_type: SyncType) {
+        match sync_type {
+            // Synchronize the stream if waiting.
+            SyncType::Wait => {
+                let ctx = self.get_context();
+                ctx.sync();
+            }
+            // Nothing to do - all tasks are already submitted to the stream.
+            SyncType::Flush => (),
+        }
+    }
+
+    fn memory_usage(&mut self) -> MemoryUsage {
+        let ctx = self.get_context();
+        ctx.memory_management.memory_usage()
+    }
+
+    fn memory_cleanup(&mut self) {
+        let ctx = self.get_context();
+        ctx.memory_management.cleanup();
+        // The memory used by the pending tasks of the other streams is still bound, so only the
+        // default stream can still use the freed memory, and frees are ordered on it.
+        ctx.memory_management.storage().perform_deallocations();
+    }
+
+    fn enable_timestamps(&mut self) {
+        let ctx = self.get_context();
+        if let Timestamps::Disabled = ctx.timestamps {
+            ctx.timestamps = Timestamps::Enabled { start: None };
+        }
+    }
+
+    fn disable_timestamps(&mut self) {
+        let ctx = self.get_context();
+        if let Timestamps::Enabled { start: Some(start) } = ctx.timestamps {
+            unsafe {
+                let _ = (hip().hipEventDestroy)(start);
+            }
+        }
+        ctx.timestamps = Timestamps::Disabled;
+    }
+
+    fn timestamps_enabled(&mut self) -> bool {
+        !matches!(self.get_context().timestamps, Timestamps::Disabled)
+    }
+
+    fn sync_elapsed(&mut self) -> TimestampsResult {
+        let ctx = self.get_context();
+        let start = match &mut ctx.timestamps {
+            Timestamps::Disabled => return Err(TimestampsError::Disabled),
+            Timestamps::Enabled { start } => start.take(),
+        };
+
+        let Some(start) = start else {
+            // No kernel was launched since the last measure.
+            ctx.sync();
+            return Ok(Duration::ZERO);
+        };
+
+        let elapsed = unsafe {
+            let end = create_event(sys::HIP_EVENT_DEFAULT).map_err(timestamps_error)?;
+            ctx.wait_streams().map_err(timestamps_error)?;
+            hip_result((hip().hipEventRecord)(end, ctx.stream)).map_err(timestamps_error)?;
+            ctx.sync();
+
+            let mut elapsed = 0.0f32;
+            let result = hip_result((hip().hipEventElapsedTime)(&mut elapsed, start, end));
+            let _ = (hip().hipEventDestroy)(start);
+            let _ = (hip().hipEventDestroy)(end);
+            result.map_err(timestamps_error)?;
+            elapsed
+        };
+
+        // The elapsed time is in milliseconds.
+        Ok(Duration::from_secs_f64(elapsed as f64 / 1000.0))
+    }
+
+    fn start_profile(&mut self) {
+        self.profiler.start();
+    }
+
+    fn end_profile(&mut self) -> ProfileTrace {
+        self.profiler.end()
+    }
+
+    fn get_resource(
+        &mut self,
+        binding: server::Binding<Self>,
+    ) -> <Self::Storage as cubecl_runtime::storage::ComputeStorage>::Resource {
+        let ctx = self.get_context();
+        ctx.memory_management.get_resource(binding.memory)
+    }
+}
+
+impl<MM: MemoryManagement<HipStorage>> HipContext<MM> {
+    pub fn new(memory_management: MM, stream: sys::hipStream_t, device_index: usize) -> Self {
+        Self {
+            device_index,
+            memory_management,
+            module_names: HashMap::new(),
+            stream,
+            streams: Vec::new(),
+            events: HashMap::new(),
+            num_events: 0,
+            uploads: Vec::new(),
+            bindings: Vec::new(),
+            timestamps: Timestamps::Disabled,
+        }
+    }
+
+    fn sync(&mut self) {
+        unsafe {
+            hip_result((hip().hipStreamSynchronize)(self.stream)).unwrap();
+            for stream in self.streams.iter() {
+                hip_result((hip().hipStreamSynchronize)(*stream)).unwrap();
+            }
+            for (_, event) in self.events.drain() {
+                let _ = (hip().hipEventDestroy)(event);
+            }
+        };
+        self.uploads.clear();
+        self.bindings.clear();
+        let storage = self.memory_management.storage();
+        storage.flush();
+        storage.perform_deallocations();
+    }
+
+    fn compile_kernel(
+        &mut self,
+        kernel_id: &KernelId,
+        kernel: Box<dyn CubeTask>,
+        logger: &mut DebugLogger,
+        mode: ExecutionMode,
+    ) -> Result<(), ComputeError> {
+        let mut kernel_compiled = kernel.try_compile(mode)?;
+
+        if logger.is_activated() {
+            kernel_compiled.debug_info = Some(DebugInformation::new("cpp", kernel_id.clone()));
+            kernel_compiled.ir = kernel.ir();
+
+            if let Ok(formatted) = format_cpp_code(&kernel_compiled.source) {
+                kernel_compiled.source = formatted;
+            }
+        }
+
+        let shared_mem_bytes = kernel_compiled.shared_mem_bytes;
+        let cube_dim = kernel_compiled.cube_dim;
+        let kernel_compiled = logger.debug(kernel_compiled);
+
+        let code = compile_program(&kernel_compiled.source)?;
+
+        let func_name = CString::new("kernel".to_string()).unwrap();
+        let func = unsafe {
+            let mut module = core::ptr::null_mut();
+            hip_result((hip().hipModuleLoadData)(
+                &mut module,
+                code.as_ptr() as *const c_void,
+            ))
+            .map_err(execution_error)?;
+
+            let mut func = core::ptr::null_mut();
+            hip_result((hip().hipModuleGetFunction)(
+                &mut func,
+                module,
+                func_name.as_ptr(),
+            ))
+            .map_err(execution_error)?;
+            func
+        };
+
+        self.module_names.insert(
+            kernel_id.clone(),
+            CompiledKernel {
+                cube_dim,
+                shared_mem_bytes,
+                func,
+            },
+        );
+
+        Ok(())
+    }
+
+    fn execute_task(
+        &mut self,
+        kernel_id: KernelId,
+        dispatch_count: (u32, u32, u32),
+        resources: Vec<HipResource>,
+        stream: StreamId,
+    ) -> Result<(), ComputeError> {
+        let stream = self.stream(stream)?;
+        let mut bindings = resources
+            .iter()
+            .map(|memory| memory.as_binding())
+            .collect::<Vec<_>>();
+
+        // Start measuring right before the first kernel.
+        if let Timestamps::Enabled { start } = &mut self.timestamps {
+            if start.is_none() {
+                unsafe {
+                    let event = create_event(sys::HIP_EVENT_DEFAULT).map_err(execution_error)?;
+                    hip_result((hip().hipEventRecord)(event, stream)).map_err(execution_error)?;
+                    *start = Some(event);
+                }
+            }
+        }
+
+        let kernel = self.module_names.get(&kernel_id).unwrap();
+        let cube_dim = kernel.cube_dim;
+        unsafe {
+            hip_result((hip().hipModuleLaunchKernel)(
+                kernel.func,
+                dispatch_count.0,
+                dispatch_count.1,
+                dispatch_count.2,
+                cube_dim.x,
+                cube_dim.y,
+                cube_dim.z,
+                kernel.shared_mem_bytes as u32,
+                stream,
+                bindings.as_mut_ptr(),
+                core::ptr::null_mut(),
+            ))
+            .map_err(execution_error)
+        }
+    }
+}
+
+impl<MM: MemoryManagement<HipStorage>> Drop for HipContext<MM> {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = (hip().hipSetDevice)(self.device_index as i32);
+            for (_, event) in self.events.drain() {
+                let _ = (hip().hipEventDestroy)(event);
+            }
+            // The pending uploads must complete before their staging buffers are dropped.
+            for stream in self.streams.drain(..) {
+                let _ = (hip().hipStreamSynchronize)(stream);
+                let _ = (hip().hipStreamDestroy)(stream);
+            }
+        }
+    }
+}
+
+impl<MM: MemoryManagement<HipStorage>> HipContext<MM> {
+    /// Get the resource of the binding, which is kept alive until the next synchronization when
+    /// it can be used concurrently with the tasks of other streams.
+    fn try_get_resource(&mut self, binding: MM::Binding) -> Result<HipResource, ComputeError> {
+        if !self.streams.is_empty() {
+            self.bindings.push(binding.clone());
+        }
+        self.memory_management.try_get_resource(binding)
+    }
+
+    /// Make the default stream wait for the tasks submitted to the other streams.
+    fn wait_streams(&mut self) -> Result<(), HipError> {
+        for stream in self.streams.iter() {
+            unsafe {
+                let event = create_event(sys::HIP_EVENT_DISABLE_TIMING)?;
+                hip_result((hip().hipEventRecord)(event, *stream))?;
+                let waited = hip_result((hip().hipStreamWaitEvent)(self.stream, event, 0));
+                // The event is destroyed once completed, after the wait is enqueued.
+                hip_result((hip().hipEventDestroy)(event))?;
+                waited?;
+            }
+        }
+        Ok(())
+    }
+
+    fn stream(&self, stream: StreamId) -> Result<sys::hipStream_t, ComputeError> {
+        match stream.index {
+            0 => Ok(self.stream),
+            index => self
+                .streams
+                .get(index as usize - 1)
+                .copied()
+                .ok_or_else(|| {
+                    ComputeError::InvalidStream(format!(
+                        "The stream {index} doesn't exist, only {} streams were created.",
+                        self.streams.len()
+                    ))
+                }),
+        }
+    }
+}
+
+impl<MM: MemoryManagement<HipStorage>> HipServer<MM> {
+    /// Create a new hip server.
+    pub(crate) fn new(index: usize, init: Box<dyn Fn(usize) -> HipContext<MM>>) -> Self {
+        Self {
+            state: HipServerState::Uninitialized {
+                device_index: index,
+                init,
+            },
+            logger: DebugLogger::new(),
+            profiler: Profiler::new(),
+        }
+    }
+
+    fn get_context(&mut self) -> &mut HipContext<MM> {
+        self.get_context_with_logger().0
+    }
+
+    fn get_context_with_logger(&mut self) -> (&mut HipContext<MM>, &mut DebugLogger) {
+        if let HipServerState::Uninitialized { device_index, init } = &self.state {
+            let ctx = init(*device_index);
+            self.state = HipServerState::Initialized { ctx };
+        }
+        if let HipServerState::Initialized { ctx } = &mut self.state {
+            unsafe {
+                hip_result((hip().hipSetDevice)(ctx.device_index as i32)).unwrap();
+            };
+            (ctx, &mut self.logger)
+        } else {
+            panic!("Context should be initialized");
+        }
+    }
+}
+
+/// Compile the source of a kernel for the current device, returning the code object.
+pub(crate) fn compile_program(source: &str) -> Result<Vec<u8>, ComputeError> {
+    let include_path = include_path();
+    let include_option = format!("-I{}", include_path.to_str().unwrap());
+    // rocWMMA requires C++17.
+    let options = ["-std=c++17", include_option.as_str()]
+        .into_iter()
+        .map(|option| CString::new(option).unwrap())
+        .collect::<Vec<_>>();
+    let options = options
+        .iter()
+        .map(|option| option.as_ptr())
+        .collect::<Vec<_>>();
+
+    let source_c = CString::new(source).unwrap();
+    let name = CString::new("kernel.cpp").unwrap();
+
+    unsafe {
+        let mut program = core::ptr::null_mut();
+        hiprtc_result((hiprtc().hiprtcCreateProgram)(
+            &mut program,
+            source_c.as_ptr(),
+            name.as_ptr(),
+            0,
+            core::ptr::null(),
+            core::ptr::null(),
+        ))
+        .map_err(|err| ComputeError::Compilation(format!("{err:?}")))?;
+
+        let compiled = hiprtc_result((hiprtc().hiprtcCompileProgram)(
+            program,
+            options.len() as i32,
+            options.as_ptr(),
+        ));
+
+        let result = match compiled {
+            Ok(()) => program_code(program),
+            Err(_) => {
+                let log = program_log(program);
+                let mut message = "[Compilation Error] ".to_string();
+                for line in log.split('\n') {
+                    if !line.is_empty() {
+                        message += format!("\n    {line}").as_str();
+                    }
+                }
+                Err(ComputeError::Compilation(format!(
+                    "{message}\n[Source]  \n{source}"
+                )))
+            }
+        };
+
+        let _ = (hiprtc().hiprtcDestroyProgram)(&mut program);
+        result
+    }
+}
+
+unsafe fn program_code(program: sys::hiprtcProgram) -> Result<Vec<u8>, ComputeError> {
+    let mut size = 0;
+    hiprtc_result((hiprtc().hiprtcGetCodeSize)(program, &mut size))
+        .map_err(|err| ComputeError::Compilation(format!("{err:?}")))?;
+
+    let mut code = vec![0u8; size];
+    hiprtc_result((hiprtc().hiprtcGetCode)(
+        program,
+        code.as_mut_ptr() as *mut c_char,
+    ))
+    .map_err(|err| ComputeError::Compilation(format!("{err:?}")))?;
+
+    Ok(code)
+}
+
+unsafe fn program_log(program: sys::hiprtcProgram) -> String {
+    let mut size = 0;
+    if hiprtc_result((hiprtc().hiprtcGetProgramLogSize)(program, &mut size)).is_err() {
+        return String::new();
+    }
+
+    let mut log = vec![0u8; size];
+    if hiprtc_result((hiprtc().hiprtcGetProgramLog)(
+        program,
+        log.as_mut_ptr() as *mut c_char,
+    ))
+    .is_err()
+    {
+        return String::new();
+    }
+
+    String::from_utf8_lossy(&log)
+        .trim_end_matches('\0')
+        .to_string()
+}
+
+pub(crate) fn create_stream() -> Result<sys::hipStream_t, HipError> {
+    let mut stream = core::ptr::null_mut();
+    unsafe {
+        hip_result((hip().hipStreamCreateWithFlags)(
+            &mut stream,
+            sys::HIP_STREAM_NON_BLOCKING,
+        ))?;
+    }
+    Ok(stream)
+}
+
+unsafe fn create_event(flags: u32) -> Result<sys::hipEvent_t, HipError> {
+    let mut event = core::ptr::null_mut();
+    hip_result((hip().hipEventCreateWithFlags)(&mut event, flags))?;
+    Ok(event)
+}
+
+unsafe fn memcpy_htod_async(
+    resource: &HipResource,
+    data: &[u8],
+    stream: sys::hipStream_t,
+) -> Result<(), HipError> {
+    hip_result((hip().hipMemcpyHtoDAsync)(
+        resource.ptr as sys::hipDeviceptr_t,
+        data.as_ptr() as *const c_void,
+        data.len(),
+        stream,
+    ))
+}
+
+fn execution_error(err: HipError) -> ComputeError {
+    ComputeError::Execution(format!("{err:?}"))
+}
+
+fn timestamps_error(err: HipError) -> TimestampsError {
+    TimestampsError::Unknown(format!("{err:?}"))
+}
+
+fn include_path() -> PathBuf {
+    let mut path = rocm_path().expect("
+        ROCm installation not found.
+        Please ensure that ROCm is installed and the ROCM_PATH environment variable is set correctly.
+        Note: Default paths are used for Linux (/opt/rocm) and Windows (C:/Program Files/AMD/ROCm/), which may not be correct.
+    ");
+    path.push("include");
+    path
+}
+
+fn rocm_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("ROCM_PATH") {
+        return Some(PathBuf::from(path));
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        return Some(PathBuf::from("/opt/rocm"));
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        return Some(PathBuf::from("C:/Program Files/AMD/ROCm/"));
+    }
+
+    #[allow(unreachable_code)]
+    None
+}
@@ -0,0 +1,63 @@
+use super::sys::{hip, hip_result, HIP_HOST_MALLOC_PORTABLE};
+use cubecl_runtime::{server::ComputeError, staging::StagingMemory};
+
+/// Page-locked host memory, which the device can copy from without blocking the host.
+#[derive(Debug)]
+pub struct PinnedMemory {
+    ptr: *mut u8,
+    size: usize,
+}
+
+unsafe impl Send for PinnedMemory {}
+
+impl PinnedMemory {
+    /// Allocate `size` bytes of page-locked memory, usable from every device.
+    pub fn new(size: usize) -> Result<Self, ComputeError> {
+        let mut ptr = core::ptr::null_mut();
+
+        if size > 0 {
+            unsafe {
+                hip_result((hip().hipHostMalloc)(
+                    &mut ptr,
+                    size,
+                    HIP_HOST_MALLOC_PORTABLE,
+                ))
+                .map_err(|err| ComputeError::OutOfMemory {
+                    size,
+                    reason: format!("{err:?}"),
+                })?;
+            }
+        }
+
+        Ok(Self {
+            ptr: ptr as *mut u8,
+            size,
+        })
+    }
+}
+
+impl StagingMemory for PinnedMemory {
+    fn as_slice(&self) -> &[u8] {
+        match self.size {
+            0 => &[],
+            size => unsafe { core::slice::from_raw_parts(self.ptr, size) },
+        }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        match self.size {
+            0 => &mut [],
+            size => unsafe { core::slice::from_raw_parts_mut(self.ptr, size) },
+        }
+    }
+}
+
+impl Drop for PinnedMemory {
+    fn drop(&mut self) {
+        if self.size > 0 {
+            unsafe {
+                let _ = (hip().hipHostFree)(self.ptr as *mut core::ffi::c_void);
+            }
+        }
+    }
+}
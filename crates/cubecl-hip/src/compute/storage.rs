@@ -0,0 +1,159 @@
+use super::sys::{self, hip, hip_result};
+use cubecl_runtime::{
+    server::ComputeError,
+    storage::{ComputeStorage, StorageHandle, StorageId, StorageUtilization},
+};
+use std::collections::HashMap;
+
+/// Buffer storage for HIP.
+pub struct HipStorage {
+    memory: HashMap<StorageId, u64>,
+    deallocations: Vec<StorageId>,
+    stream: sys::hipStream_t,
+    activate_slices: HashMap<ActiveResource, u64>,
+}
+
+#[derive(new, Debug, Hash, PartialEq, Eq, Clone)]
+struct ActiveResource {
+    ptr: u64,
+    kind: HipResourceKind,
+}
+
+unsafe impl Send for HipStorage {}
+
+impl core::fmt::Debug for HipStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(format!("HipStorage {{ device: {:?} }}", self.stream).as_str())
+    }
+}
+
+/// Keeps the device pointers in a hashmap with ids as key.
+impl HipStorage {
+    /// Create a new storage allocating on the given stream.
+    pub fn new(stream: sys::hipStream_t) -> Self {
+        Self {
+            memory: HashMap::new(),
+            deallocations: Vec::new(),
+            stream,
+            activate_slices: HashMap::new(),
+        }
+    }
+
+    /// Actually deallocates buffers tagged to be deallocated.
+    pub fn perform_deallocations(&mut self) {
+        for id in self.deallocations.drain(..) {
+            if let Some(ptr) = self.memory.remove(&id) {
+                unsafe {
+                    hip_result((hip().hipFreeAsync)(
+                        ptr as sys::hipDeviceptr_t,
+                        self.stream,
+                    ))
+                    .unwrap();
+                }
+            }
+        }
+    }
+
+    pub fn flush(&mut self) {
+        self.activate_slices.clear();
+    }
+}
+
+/// The memory resource that can be allocated for HIP.
+#[derive(new, Debug)]
+pub struct HipResource {
+    /// The device pointer.
+    pub ptr: u64,
+    pub binding: *mut std::ffi::c_void,
+    /// How the resource is used.
+    pub kind: HipResourceKind,
+}
+
+unsafe impl Send for HipResource {}
+
+pub type Binding = *mut std::ffi::c_void;
+
+impl HipResource {
+    /// Return the binding view of the buffer.
+    pub fn as_binding(&self) -> Binding {
+        match self.kind {
+            HipResourceKind::Full { .. } => self.binding,
+            HipResourceKind::Slice { .. } => self.binding,
+        }
+    }
+
+    /// Return the buffer size.
+    pub fn size(&self) -> u64 {
+        match self.kind {
+            HipResourceKind::Full { size } => size as u64,
+            HipResourceKind::Slice { size, offset: _ } => size as u64,
+        }
+    }
+
+    /// Return the buffer offset.
+    pub fn offset(&self) -> u64 {
+        match self.kind {
+            HipResourceKind::Full { size: _ } => 0,
+            HipResourceKind::Slice { size: _, offset } => offset as u64,
+        }
+    }
+}
+
+/// How the resource is used, either as a slice or fully.
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub enum HipResourceKind {
+    /// Represents an entire buffer.
+    Full { size: usize },
+    /// A slice over a buffer.
+    Slice { size: usize, offset: usize },
+}
+
+impl ComputeStorage for HipStorage {
+    type Resource = HipResource;
+
+    fn get(&mut self, handle: &StorageHandle) -> Self::Resource {
+        let ptr = self.memory.get(&handle.id).unwrap();
+
+        match handle.utilization {
+            StorageUtilization::Full(size) => HipResource::new(
+                *ptr,
+                ptr as *const u64 as *mut std::ffi::c_void,
+                HipResourceKind::Full { size },
+            ),
+            StorageUtilization::Slice { offset, size } => {
+                let ptr = ptr + offset as u64;
+                let kind = HipResourceKind::Slice { size, offset };
+                let key = ActiveResource::new(ptr, kind.clone());
+
+                self.activate_slices.insert(key.clone(), ptr);
+
+                // The ptr needs to stay alive until we send the task to the server.
+                let ptr = self.activate_slices.get(&key).unwrap();
+
+                HipResource::new(*ptr, ptr as *const u64 as *mut std::ffi::c_void, kind)
+            }
+        }
+    }
+
+    fn try_alloc(&mut self, size: usize) -> Result<StorageHandle, ComputeError> {
+        let id = StorageId::new();
+        let mut ptr = core::ptr::null_mut();
+        match unsafe { hip_result((hip().hipMallocAsync)(&mut ptr, size, self.stream)) } {
+            Ok(()) => {}
+            Err(err) if err.0 == sys::HIP_ERROR_OUT_OF_MEMORY => {
+                return Err(ComputeError::OutOfMemory {
+                    size,
+                    reason: format!("{err:?}"),
+                })
+            }
+            Err(err) => return Err(ComputeError::Execution(format!("{err:?}"))),
+        };
+        let ptr = ptr as u64;
+        self.memory.insert(id, ptr);
+        Ok(StorageHandle::new(id, StorageUtilization::Full(size)))
+    }
+
+    fn dealloc(&mut self, id: StorageId) {
+        self.deallocations.push(id);
+    }
+}
@@ -0,0 +1,172 @@
+//! Bindings to the HIP runtime and to hiprtc.
+//!
+//! The libraries are loaded the first time they are used, so that the crate can be built and the
+//! kernels generated on machines without ROCm.
+#![allow(non_camel_case_types, non_snake_case)]
+
+use core::ffi::{c_char, c_int, c_uint, c_void, CStr};
+use libloading::Library;
+use std::sync::OnceLock;
+
+pub type hipError_t = c_int;
+pub type hiprtcResult = c_int;
+pub type hipDeviceptr_t = *mut c_void;
+pub type hipStream_t = *mut c_void;
+pub type hipEvent_t = *mut c_void;
+pub type hipModule_t = *mut c_void;
+pub type hipFunction_t = *mut c_void;
+pub type hiprtcProgram = *mut c_void;
+
+pub const HIP_SUCCESS: hipError_t = 0;
+pub const HIP_ERROR_OUT_OF_MEMORY: hipError_t = 2;
+pub const HIPRTC_SUCCESS: hiprtcResult = 0;
+
+pub const HIP_STREAM_NON_BLOCKING: c_uint = 1;
+pub const HIP_EVENT_DEFAULT: c_uint = 0;
+pub const HIP_EVENT_DISABLE_TIMING: c_uint = 2;
+pub const HIP_HOST_MALLOC_PORTABLE: c_uint = 1;
+
+macro_rules! library {
+    ($library:ident, [$($file:literal),*], { $($function:ident($($arg:ty),*) -> $output:ty;)* }) => {
+        pub struct $library {
+            $(pub $function: unsafe extern "C" fn($($arg),*) -> $output,)*
+            _library: Library,
+        }
+
+        impl $library {
+            fn load() -> Result<Self, String> {
+                let files = [$($file),*];
+                let library = files
+                    .iter()
+                    .find_map(|file| unsafe { Library::new(file).ok() })
+                    .ok_or_else(|| format!("Unable to load any of {files:?}"))?;
+
+                unsafe {
+                    Ok(Self {
+                        $($function: *library
+                            .get(concat!(stringify!($function), "\0").as_bytes())
+                            .map_err(|err| format!("{err}"))?,)*
+                        _library: library,
+                    })
+                }
+            }
+        }
+    };
+}
+
+library!(Hip, ["libamdhip64.so", "libamdhip64.so.6", "amdhip64_6.dll", "amdhip64.dll"], {
+    hipInit(c_uint) -> hipError_t;
+    hipSetDevice(c_int) -> hipError_t;
+    hipGetErrorName(hipError_t) -> *const c_char;
+    hipStreamCreateWithFlags(*mut hipStream_t, c_uint) -> hipError_t;
+    hipStreamSynchronize(hipStream_t) -> hipError_t;
+    hipStreamDestroy(hipStream_t) -> hipError_t;
+    hipStreamWaitEvent(hipStream_t, hipEvent_t, c_uint) -> hipError_t;
+    hipEventCreateWithFlags(*mut hipEvent_t, c_uint) -> hipError_t;
+    hipEventRecord(hipEvent_t, hipStream_t) -> hipError_t;
+    hipEventElapsedTime(*mut f32, hipEvent_t, hipEvent_t) -> hipError_t;
+    hipEventDestroy(hipEvent_t) -> hipError_t;
+    hipMallocAsync(*mut hipDeviceptr_t, usize, hipStream_t) -> hipError_t;
+    hipFreeAsync(hipDeviceptr_t, hipStream_t) -> hipError_t;
+    hipHostMalloc(*mut *mut c_void, usize, c_uint) -> hipError_t;
+    hipHostFree(*mut c_void) -> hipError_t;
+    hipMemcpyHtoDAsync(hipDeviceptr_t, *const c_void, usize, hipStream_t) -> hipError_t;
+    hipMemcpyDtoHAsync(*mut c_void, hipDeviceptr_t, usize, hipStream_t) -> hipError_t;
+    hipModuleLoadData(*mut hipModule_t, *const c_void) -> hipError_t;
+    hipModuleGetFunction(*mut hipFunction_t, hipModule_t, *const c_char) -> hipError_t;
+    hipModuleLaunchKernel(
+        hipFunction_t,
+        c_uint,
+        c_uint,
+        c_uint,
+        c_uint,
+        c_uint,
+        c_uint,
+        c_uint,
+        hipStream_t,
+        *mut *mut c_void,
+        *mut *mut c_void
+    ) -> hipError_t;
+});
+
+library!(Hiprtc, ["libhiprtc.so", "libhiprtc.so.6", "hiprtc0605.dll", "hiprtc.dll"], {
+    hiprtcGetErrorString(hiprtcResult) -> *const c_char;
+    hiprtcCreateProgram(
+        *mut hiprtcProgram,
+        *const c_char,
+        *const c_char,
+        c_int,
+        *const *const c_char,
+        *const *const c_char
+    ) -> hiprtcResult;
+    hiprtcCompileProgram(hiprtcProgram, c_int, *const *const c_char) -> hiprtcResult;
+    hiprtcGetProgramLogSize(hiprtcProgram, *mut usize) -> hiprtcResult;
+    hiprtcGetProgramLog(hiprtcProgram, *mut c_char) -> hiprtcResult;
+    hiprtcGetCodeSize(hiprtcProgram, *mut usize) -> hiprtcResult;
+    hiprtcGetCode(hiprtcProgram, *mut c_char) -> hiprtcResult;
+    hiprtcDestroyProgram(*mut hiprtcProgram) -> hiprtcResult;
+});
+
+/// The HIP runtime, panics when ROCm isn't installed.
+pub fn hip() -> &'static Hip {
+    static HIP: OnceLock<Result<Hip, String>> = OnceLock::new();
+
+    match HIP.get_or_init(Hip::load) {
+        Ok(hip) => hip,
+        Err(err) => panic!("HIP runtime not found, please ensure that ROCm is installed: {err}"),
+    }
+}
+
+/// The HIP runtime compiler, panics when ROCm isn't installed.
+pub fn hiprtc() -> &'static Hiprtc {
+    static HIPRTC: OnceLock<Result<Hiprtc, String>> = OnceLock::new();
+
+    match HIPRTC.get_or_init(Hiprtc::load) {
+        Ok(hiprtc) => hiprtc,
+        Err(err) => panic!("hiprtc not found, please ensure that ROCm is installed: {err}"),
+    }
+}
+
+/// An error returned by the HIP runtime.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct HipError(pub hipError_t);
+
+impl core::fmt::Debug for HipError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let name = unsafe { CStr::from_ptr((hip().hipGetErrorName)(self.0)) };
+        f.write_fmt(format_args!(
+            "HipError({}, {})",
+            self.0,
+            name.to_string_lossy()
+        ))
+    }
+}
+
+/// An error returned by hiprtc.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct HiprtcError(pub hiprtcResult);
+
+impl core::fmt::Debug for HiprtcError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let name = unsafe { CStr::from_ptr((hiprtc().hiprtcGetErrorString)(self.0)) };
+        f.write_fmt(format_args!(
+            "HiprtcError({}, {})",
+            self.0,
+            name.to_string_lossy()
+        ))
+    }
+}
+
+pub fn hip_result(error: hipError_t) -> Result<(), HipError> {
+    match error {
+        HIP_SUCCESS => Ok(()),
+        err => Err(HipError(err)),
+    }
+}
+
+pub fn hiprtc_result(result: hiprtcResult) -> Result<(), HiprtcError> {
+    match result {
+        HIPRTC_SUCCESS => Ok(()),
+        err => Err(HiprtcError(err)),
+    }
+}
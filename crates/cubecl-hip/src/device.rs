@@ -0,0 +1,4 @@
+#[derive(new, Clone, Debug, PartialEq, Eq, Default, Hash)]
+pub struct HipDevice {
+    pub index: usize,
+}
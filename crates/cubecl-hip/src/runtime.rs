@@ -0,0 +1,116 @@
+use cubecl_core::{
+    ir::{Elem, FloatKind},
+    Feature, FeatureSet, Runtime,
+};
+use cubecl_runtime::{
+    channel::MutexComputeChannel,
+    client::ComputeClient,
+    memory_management::dynamic::{DynamicMemoryManagement, DynamicMemoryManagementOptions},
+    ComputeRuntime,
+};
+use std::sync::Arc;
+
+use crate::{
+    compiler::HipCompiler,
+    compute::{
+        compile_program, create_stream,
+        sys::{hip, hip_result},
+        HipContext, HipServer, HipStorage,
+    },
+    device::HipDevice,
+};
+
+#[derive(Debug)]
+pub struct HipRuntime;
+
+static RUNTIME: ComputeRuntime<HipDevice, Server, MutexComputeChannel<Server>> =
+    ComputeRuntime::new();
+
+type Server = HipServer<DynamicMemoryManagement<HipStorage>>;
+
+impl Runtime for HipRuntime {
+    type Compiler = HipCompiler;
+    type Server = HipServer<DynamicMemoryManagement<HipStorage>>;
+
+    type Channel = MutexComputeChannel<HipServer<DynamicMemoryManagement<HipStorage>>>;
+    type Device = HipDevice;
+
+    fn client(device: &Self::Device) -> ComputeClient<Self::Server, Self::Channel> {
+        fn init(index: usize) -> HipContext<DynamicMemoryManagement<HipStorage>> {
+            set_device(index);
+
+            let stream = create_stream().unwrap();
+            let storage = HipStorage::new(stream);
+            let options = DynamicMemoryManagementOptions::preset(2048 + 512 * 1024 * 1024, 32);
+            let memory_management = DynamicMemoryManagement::new(storage, options);
+            HipContext::new(memory_management, stream, index)
+        }
+
+        RUNTIME.client(device, move || {
+            let server = HipServer::new(device.index, Box::new(init));
+            let mut features =
+                FeatureSet::new(&[Feature::Subcube, Feature::AtomicFloat(FloatKind::F32)]);
+
+            set_device(device.index);
+            register_wmma_features(&mut features);
+
+            ComputeClient::new(MutexComputeChannel::new(server), Arc::new(features))
+        })
+    }
+
+    fn name() -> &'static str {
+        "hip"
+    }
+
+    fn require_array_lengths() -> bool {
+        true
+    }
+}
+
+fn set_device(index: usize) {
+    unsafe {
+        hip_result((hip().hipInit)(0)).unwrap();
+        hip_result((hip().hipSetDevice)(index as i32)).unwrap();
+    }
+}
+
+/// A kernel using the matrix cores, which only compiles for the architectures supported by
+/// rocWMMA.
+const WMMA_PROBE: &str = "
+#include <rocwmma/rocwmma.hpp>
+
+extern \"C\" __global__ void kernel(float output[]) {
+    rocwmma::fragment<rocwmma::accumulator, 16, 16, 16, float> frag;
+    rocwmma::fill_fragment(frag, 0.0f);
+    rocwmma::store_matrix_sync(output, frag, 16, rocwmma::mem_row_major);
+}
+";
+
+fn register_wmma_features(features: &mut FeatureSet) {
+    if compile_program(WMMA_PROBE).is_err() {
+        return;
+    }
+
+    // Types fully supported.
+    for (a, b, c) in [
+        (
+            Elem::Float(FloatKind::F16),
+            Elem::Float(FloatKind::F16),
+            Elem::Float(FloatKind::F16),
+        ),
+        (
+            Elem::Float(FloatKind::F16),
+            Elem::Float(FloatKind::F16),
+            Elem::Float(FloatKind::F32),
+        ),
+    ] {
+        features.register(Feature::Cmma {
+            a,
+            b,
+            c,
+            m: 16,
+            k: 16,
+            n: 16,
+        });
+    }
+}
@@ -0,0 +1,122 @@
+use cubecl_core as cubecl;
+use cubecl_core::{
+    assert_golden,
+    compiler_tests::{self, compile, float},
+    ir::{Elem, FloatKind, Item},
+    prelude::*,
+};
+use cubecl_hip::compiler::HipCompiler;
+
+#[cube]
+pub fn golden_cmma_kernel(lhs: &Array<F16>, rhs: &Array<F16>, out: &mut Array<F32>) {
+    let a = cmma::Matrix::<F16>::new(
+        cmma::MatrixIdent::A,
+        16,
+        16,
+        16,
+        cmma::MatrixLayout::RowMajor,
+    );
+    let b = cmma::Matrix::<F16>::new(
+        cmma::MatrixIdent::B,
+        16,
+        16,
+        16,
+        cmma::MatrixLayout::RowMajor,
+    );
+    let c = cmma::Matrix::<F32>::new(
+        cmma::MatrixIdent::Accumulator,
+        16,
+        16,
+        16,
+        cmma::MatrixLayout::Undefined,
+    );
+    cmma::fill::<F32>(&c, F32::new(0.0));
+    cmma::load::<F16>(&a, lhs.as_slice(), UInt::new(16));
+    cmma::load::<F16>(&b, rhs.as_slice(), UInt::new(16));
+
+    cmma::execute::<F16, F16, F32, F32>(&a, &b, &c, &c);
+
+    cmma::store::<F32>(
+        out.as_slice_mut(),
+        &c,
+        UInt::new(16),
+        cmma::MatrixLayout::RowMajor,
+    );
+}
+
+#[cube]
+pub fn golden_bf16_kernel(input: &Array<BF16>, output: &mut Array<F32>) {
+    output[UNIT_POS] = F32::cast_from(input[UNIT_POS]);
+}
+
+#[cube]
+pub fn golden_atomic_float_kernel(input: &Array<F32>, output: &mut Array<AtomicF32>) {
+    let value = input[UNIT_POS];
+    AtomicF32::add(&output[0], value);
+    AtomicF32::sub(&output[1], value);
+    AtomicF32::max(&output[2], value);
+    AtomicF32::min(&output[3], value);
+}
+
+#[test]
+fn golden_kernel_test() {
+    assert_golden!(
+        "kernel.cpp",
+        compile::<HipCompiler>(compiler_tests::kernel())
+    );
+}
+
+#[test]
+fn golden_subcube_test() {
+    let source = compile::<HipCompiler>(compiler_tests::subcube());
+
+    // Warp functions don't take a mask on AMD GPUs.
+    assert!(!source.contains("_sync("));
+    assert_golden!("subcube.cpp", source);
+}
+
+#[test]
+fn golden_subcube_shuffle_test() {
+    let source = compile::<HipCompiler>(compiler_tests::subcube_shuffle());
+
+    assert!(source.contains("__shfl_xor("));
+    assert_golden!("subcube_shuffle.cpp", source);
+}
+
+#[test]
+fn golden_cmma_test() {
+    let mut builder = KernelBuilder::default();
+    let lhs = builder.input_array(float(FloatKind::F16));
+    let rhs = builder.input_array(float(FloatKind::F16));
+    let out = builder.output_array(float(FloatKind::F32));
+
+    golden_cmma_kernel::__expand(&mut builder.context, lhs.into(), rhs.into(), out.into());
+    let kernel = builder.build(KernelSettings::default());
+
+    assert_golden!("cmma.cpp", compile::<HipCompiler>(kernel));
+}
+
+#[test]
+fn golden_bf16_test() {
+    let mut builder = KernelBuilder::default();
+    let input = builder.input_array(float(FloatKind::BF16));
+    let output = builder.output_array(float(FloatKind::F32));
+
+    golden_bf16_kernel::__expand(&mut builder.context, input.into(), output.into());
+    let kernel = builder.build(KernelSettings::default());
+
+    assert_golden!("bf16.cpp", compile::<HipCompiler>(kernel));
+}
+
+#[test]
+fn golden_atomic_float_test() {
+    let mut builder = KernelBuilder::default();
+    let input = builder.input_array(float(FloatKind::F32));
+    let output = builder.output_array(Item::new(Elem::AtomicFloat(FloatKind::F32)));
+
+    golden_atomic_float_kernel::__expand(&mut builder.context, input.into(), output.into());
+    let source = compile::<HipCompiler>(builder.build(KernelSettings::default()));
+
+    assert!(source.contains("atomicAdd("));
+    assert_golden!("atomic_float.cpp", source);
+}
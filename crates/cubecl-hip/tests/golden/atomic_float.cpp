@@ -0,0 +1,57 @@
+typedef unsigned int uint;
+typedef signed char int8;
+typedef short int16;
+typedef long long int64;
+typedef unsigned char uint8;
+typedef unsigned short uint16;
+typedef unsigned long long uint64;
+
+
+extern "C" __global__ void kernel(
+float input_0[],float output_0[],uint info[]
+) {
+
+    int threadIdxGlobal = threadIdx.x + threadIdx.y * blockDim.x + threadIdx.z * (blockDim.x * blockDim.y);
+            uint rank = info[0];
+uint rank_2 = rank * 2;
+float l_0_0;
+float l_0_2;
+uint l_0_6;
+bool l_0_7;
+l_0_6 = info[(2 * 2 * info[0]) + 1];
+l_0_7 = threadIdxGlobal < l_0_6;
+if (l_0_7) {
+l_0_0 = input_0[threadIdxGlobal];
+} else {
+l_0_0 = float(0.0);
+}
+float* l_0_1 = &output_0[uint(0)];
+l_0_2 = atomicAdd(l_0_1, l_0_0);
+float* l_0_3 = &output_0[uint(1)];
+l_0_2 = atomicAdd(l_0_3, -l_0_0);
+float* l_0_4 = &output_0[uint(2)];
+{
+unsigned int* bits = (unsigned int*)l_0_4;
+unsigned int old = *bits;
+unsigned int assumed;
+do {
+assumed = old;
+float current = __uint_as_float(assumed);
+old = atomicCAS(bits, assumed, __float_as_uint(fmaxf(current, l_0_0)));
+} while (assumed != old);
+l_0_2 = __uint_as_float(old);
+}
+float* l_0_5 = &output_0[uint(3)];
+{
+unsigned int* bits = (unsigned int*)l_0_5;
+unsigned int old = *bits;
+unsigned int assumed;
+do {
+assumed = old;
+float current = __uint_as_float(assumed);
+old = atomicCAS(bits, assumed, __float_as_uint(fminf(current, l_0_0)));
+} while (assumed != old);
+l_0_2 = __uint_as_float(old);
+}
+
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+#include <hip/hip_bf16.h>
+typedef __hip_bfloat16 bfloat16;
+typedef __hip_bfloat162 bfloat162;
+typedef unsigned int uint;
+typedef signed char int8;
+typedef short int16;
+typedef long long int64;
+typedef unsigned char uint8;
+typedef unsigned short uint16;
+typedef unsigned long long uint64;
+
+
+extern "C" __global__ void kernel(
+bfloat16 input_0[],float output_0[],uint info[]
+) {
+
+    int threadIdxGlobal = threadIdx.x + threadIdx.y * blockDim.x + threadIdx.z * (blockDim.x * blockDim.y);
+            uint rank = info[0];
+uint rank_2 = rank * 2;
+bfloat16 l_0_0;
+float l_0_1;
+uint l_0_2;
+bool l_0_3;
+l_0_2 = info[(2 * 2 * info[0]) + 1];
+l_0_3 = threadIdxGlobal < l_0_2;
+if (l_0_3) {
+l_0_0 = input_0[threadIdxGlobal];
+} else {
+l_0_0 = bfloat16(0.0);
+}
+l_0_1 = float(l_0_0);
+uint l_0_4;
+bool l_0_5;
+l_0_4 = info[(2 * 2 * info[0]) + 2];
+l_0_5 = threadIdxGlobal < l_0_4;
+if (l_0_5) {
+output_0[threadIdxGlobal] = l_0_1;
+}
+
+}
\ No newline at end of file
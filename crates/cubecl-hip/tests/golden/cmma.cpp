@@ -0,0 +1,27 @@
+#include <hip/hip_fp16.h>
+#include <rocwmma/rocwmma.hpp>
+namespace wmma = rocwmma;
+typedef unsigned int uint;
+typedef signed char int8;
+typedef short int16;
+typedef long long int64;
+typedef unsigned char uint8;
+typedef unsigned short uint16;
+typedef unsigned long long uint64;
+
+
+extern "C" __global__ void kernel(
+__half input_0[],__half input_1[],float output_0[],uint info[]
+) {
+uint rank = info[0];
+uint rank_2 = rank * 2;
+wmma::fragment<wmma::matrix_a, 16, 16, 16, __half, wmma::row_major> frag_0_0;
+wmma::fragment<wmma::matrix_b, 16, 16, 16, __half, wmma::row_major> frag_1_0;
+wmma::fragment<wmma::accumulator, 16, 16, 16, float> frag_2_0;
+wmma::fill_fragment(frag_2_0, float(0.0));
+wmma::load_matrix_sync(frag_0_0, input_0, uint(16));
+wmma::load_matrix_sync(frag_1_0, input_1, uint(16));
+wmma::mma_sync(frag_2_0, frag_0_0, frag_1_0, frag_2_0);
+wmma::store_matrix_sync(output_0, frag_2_0, uint(16), wmma::mem_row_major);
+
+}
\ No newline at end of file
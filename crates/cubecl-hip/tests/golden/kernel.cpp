@@ -0,0 +1,65 @@
+typedef unsigned int uint;
+typedef signed char int8;
+typedef short int16;
+typedef long long int64;
+typedef unsigned char uint8;
+typedef unsigned short uint16;
+typedef unsigned long long uint64;
+
+__device__ float golden_scale_0(float l_0_0,float input_0[],float output_0[],uint info[]) {
+float l_0_1;
+l_0_1 = l_0_0 * float(2.0);
+return l_0_1;
+}
+
+
+extern "C" __global__ void kernel(
+float input_0[],float output_0[],uint info[]
+) {
+
+    int threadIdxGlobal = threadIdx.x + threadIdx.y * blockDim.x + threadIdx.z * (blockDim.x * blockDim.y);
+            uint rank = info[0];
+uint rank_2 = rank * 2;
+float l_0_0;
+uint l_0_1;
+float l_0_2;
+bool l_0_3;
+l_0_0 = float(0.0);
+l_0_1 = info[(2 * 2 * info[0]) + 1];
+
+for (uint l_1_0 = uint(0); l_1_0 < l_0_1; ++l_1_0) {
+uint l_1_1;
+bool l_1_2;
+l_1_1 = info[(2 * 2 * info[0]) + 1];
+l_1_2 = l_1_0 < l_1_1;
+if (l_1_2) {
+l_0_2 = input_0[l_1_0];
+} else {
+l_0_2 = float(0.0);
+}
+l_0_3 = l_0_2 > float(1.0);
+if (l_0_3) {
+uint l_2_0;
+bool l_2_1;
+l_2_0 = info[(2 * 2 * info[0]) + 1];
+l_2_1 = l_1_0 < l_2_0;
+if (l_2_1) {
+l_0_2 = input_0[l_1_0];
+} else {
+l_0_2 = float(0.0);
+}
+l_0_2 = golden_scale_0(l_0_2, input_0, output_0, info);
+l_0_0 = l_0_0 + l_0_2;
+} else {
+l_0_0 = l_0_0 - float(1e-7);
+}
+}
+uint l_0_4;
+bool l_0_5;
+l_0_4 = info[(2 * 2 * info[0]) + 2];
+l_0_5 = threadIdxGlobal < l_0_4;
+if (l_0_5) {
+output_0[threadIdxGlobal] = l_0_0;
+}
+
+}
\ No newline at end of file
@@ -0,0 +1,58 @@
+typedef unsigned int uint;
+typedef signed char int8;
+typedef short int16;
+typedef long long int64;
+typedef unsigned char uint8;
+typedef unsigned short uint16;
+typedef unsigned long long uint64;
+
+
+extern "C" __global__ void kernel(
+float output_0[],uint info[]
+) {
+
+    int threadIdxGlobal = threadIdx.x + threadIdx.y * blockDim.x + threadIdx.z * (blockDim.x * blockDim.y);
+            
+ int warpSizeChecked = min(64u, blockDim.x * blockDim.y * blockDim.z);
+uint rank = info[0];
+uint rank_2 = rank * 2;
+float l_0_0;
+float l_0_1;
+float l_0_2;
+bool l_0_3;
+uint l_0_4;
+bool l_0_5;
+l_0_4 = info[(1 * 2 * info[0]) + 1];
+l_0_5 = threadIdxGlobal < l_0_4;
+if (l_0_5) {
+l_0_0 = output_0[threadIdxGlobal];
+} else {
+l_0_0 = float(0.0);
+}
+
+l_0_1 = l_0_0;
+{
+    for (int offset = warpSizeChecked / 2; offset > 0; offset /= 2) {
+        l_0_1 += __shfl_xor(l_0_1, offset);
+    }
+}
+
+l_0_2 = l_0_0;
+{
+    for (int offset = warpSizeChecked / 2; offset > 0; offset /= 2) {
+        l_0_2 = max(l_0_2, __shfl_xor(l_0_2, offset));
+    }
+}
+l_0_3 = threadIdxGlobal < uint(64);
+if (l_0_3) {
+l_0_0 = l_0_1 + l_0_2;
+uint l_1_0;
+bool l_1_1;
+l_1_0 = info[(1 * 2 * info[0]) + 1];
+l_1_1 = threadIdxGlobal < l_1_0;
+if (l_1_1) {
+output_0[threadIdxGlobal] = l_0_0;
+}
+}
+
+}
\ No newline at end of file
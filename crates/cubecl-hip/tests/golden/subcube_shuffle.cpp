@@ -0,0 +1,82 @@
+typedef unsigned int uint;
+typedef signed char int8;
+typedef short int16;
+typedef long long int64;
+typedef unsigned char uint8;
+typedef unsigned short uint16;
+typedef unsigned long long uint64;
+
+struct __align__(16) uint_4 {
+    uint i_0;
+    uint i_1;
+    uint i_2;
+    uint i_3;
+};
+
+
+extern "C" __global__ void kernel(
+float output_0[],uint_4 output_1[],uint info[]
+) {
+
+    int threadIdxGlobal = threadIdx.x + threadIdx.y * blockDim.x + threadIdx.z * (blockDim.x * blockDim.y);
+            
+ int warpSizeChecked = min(64u, blockDim.x * blockDim.y * blockDim.z);
+uint rank = info[0];
+uint rank_2 = rank * 2;
+float l_0_0;
+float l_0_1;
+float l_0_2;
+float l_0_3;
+bool l_0_4;
+uint_4 l_0_5;
+uint l_0_6;
+bool l_0_7;
+l_0_6 = info[(2 * 2 * info[0]) + 1];
+l_0_7 = threadIdxGlobal < l_0_6;
+if (l_0_7) {
+l_0_0 = output_0[threadIdxGlobal];
+} else {
+l_0_0 = float(0.0);
+}
+l_0_1 = __shfl_xor(l_0_0, uint(1));
+l_0_2 = __shfl_down(l_0_0, uint(1));
+
+l_0_3 = l_0_0;
+{
+    int lane = threadIdxGlobal % warpSizeChecked;
+    for (int offset = 1; offset < warpSizeChecked; offset *= 2) {
+        float other = __shfl_up(l_0_3, offset);
+        if (lane >= offset) {
+            l_0_3 += other;
+        }
+    }
+    float previous = __shfl_up(l_0_3, 1);
+    l_0_3 = lane == 0 ? float(0) : previous;
+}
+l_0_1 = l_0_1 + l_0_2;
+l_0_1 = l_0_1 + l_0_3;
+uint l_0_8;
+bool l_0_9;
+l_0_8 = info[(2 * 2 * info[0]) + 1];
+l_0_9 = threadIdxGlobal < l_0_8;
+if (l_0_9) {
+output_0[threadIdxGlobal] = l_0_1;
+}
+l_0_4 = l_0_0 > float(0.0);
+
+{
+    unsigned long long ballot = __ballot(l_0_4);
+    l_0_5.i_0 = uint(ballot);
+    l_0_5.i_1 = uint(ballot >> 32);
+    l_0_5.i_2 = 0;
+    l_0_5.i_3 = 0;
+}
+uint l_0_10;
+bool l_0_11;
+l_0_10 = info[(2 * 2 * info[0]) + 2] / 4;
+l_0_11 = threadIdxGlobal < l_0_10;
+if (l_0_11) {
+output_1[threadIdxGlobal] = l_0_5;
+}
+
+}
\ No newline at end of file
@@ -1,6 +1,6 @@
 use syn::{Member, Pat, PathArguments, Stmt};
 
-use crate::tracker::VariableTracker;
+use crate::{codegen_function::pattern_idents, tracker::VariableTracker};
 
 pub const KEYWORDS: [&str; 21] = [
     "ABSOLUTE_POS",
@@ -152,6 +152,30 @@ impl VariableAnalyzer {
                     }
                 }
             }
+            syn::Expr::Match(expr) => {
+                self.find_occurrences_in_expr(&expr.expr, depth);
+
+                let depth = depth + 1;
+
+                for arm in expr.arms.iter() {
+                    // Bindings only exist when matching on comptime values.
+                    for ident in pattern_idents(&arm.pat) {
+                        self.variable_tracker
+                            .analyze_declare(ident.to_string(), depth, true);
+                    }
+
+                    if let Some((_, guard)) = &arm.guard {
+                        self.find_occurrences_in_expr(guard, depth);
+                    }
+
+                    match &*arm.body {
+                        syn::Expr::Block(expr_block) => {
+                            self.find_occurrences_in_stmts(&expr_block.block.stmts, depth);
+                        }
+                        body => self.find_occurrences_in_expr(body, depth),
+                    }
+                }
+            }
             syn::Expr::Assign(expr) => {
                 self.find_occurrences_in_expr(&expr.left, depth);
                 self.find_occurrences_in_expr(&expr.right, depth);
@@ -292,7 +316,7 @@ fn find_local_declaration_ident(pat: &syn::Pat) -> (Option<&syn::Ident>, bool) {
     (id, is_comptime)
 }
 
-fn is_ty_comptime(ty: &syn::Type) -> bool {
+pub(crate) fn is_ty_comptime(ty: &syn::Type) -> bool {
     if let syn::Type::Path(path) = ty {
         for segment in path.path.segments.iter() {
             if segment.ident == "Comptime" {
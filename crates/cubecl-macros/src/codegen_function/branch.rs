@@ -242,3 +242,227 @@ pub(crate) fn codegen_while_loop(
         cubecl::frontend::branch::while_loop_expand(context, |context| #cond, |context| #block);
     }
 }
+
+/// Codegen for match expressions
+/// Supports:
+/// match Comptime::get(...) { pattern => ..., }
+/// match runtime_int { 0 => ..., 1 | 2 => ..., _ => ... }
+pub(crate) fn codegen_match(
+    expr_match: &syn::ExprMatch,
+    loop_level: usize,
+    variable_tracker: &mut VariableTracker,
+) -> TokenStream {
+    let (value, kind, _) = codegen_expr(&expr_match.expr, loop_level, variable_tracker).process();
+
+    if let CodegenKind::Comptime = kind {
+        let mut arms = quote::quote! {};
+
+        for arm in expr_match.arms.iter() {
+            for ident in pattern_idents(&arm.pat) {
+                variable_tracker.codegen_declare(ident.to_string(), loop_level as u8 + 1);
+            }
+
+            let pat = &arm.pat;
+            let guard = match &arm.guard {
+                Some((_, guard)) => {
+                    let guard = codegen_expr(guard, loop_level + 1, variable_tracker);
+                    quote::quote! { if #guard }
+                }
+                None => quote::quote! {},
+            };
+            let body = codegen_match_arm_body(&arm.body, loop_level, variable_tracker);
+
+            arms.extend(quote::quote! {
+                #pat #guard => #body,
+            });
+        }
+
+        return quote::quote! {
+            match #value {
+                #arms
+            }
+        };
+    }
+
+    variable_tracker.runtime_matches.push(expr_match.clone());
+
+    let mut cases = quote::quote! {};
+    let mut default = quote::quote! {};
+    let mut matched = Vec::new();
+
+    for arm in expr_match.arms.iter() {
+        if let Some((if_token, _)) = &arm.guard {
+            return syn::Error::new_spanned(
+                if_token,
+                "Match guards are not supported when matching on runtime values",
+            )
+            .into_compile_error();
+        }
+
+        let values = match runtime_case_values(&arm.pat) {
+            Ok(values) => values,
+            Err(err) => return err.into_compile_error(),
+        };
+
+        if let Some(values) = &values {
+            for value in values.iter() {
+                let digits = value.base10_digits();
+
+                if matched.contains(&digits) {
+                    return syn::Error::new_spanned(
+                        value,
+                        "Value already matched by a previous arm",
+                    )
+                    .into_compile_error();
+                }
+
+                matched.push(digits);
+            }
+        }
+
+        let block = codegen_match_arm_body(&arm.body, loop_level, variable_tracker);
+
+        match values {
+            Some(values) => {
+                let values = values
+                    .iter()
+                    .map(|value| value.base10_digits().parse::<TokenStream>().unwrap());
+                cases.extend(quote::quote! {
+                    .cases(context, &[#(#values),*], |context| { #block; })
+                })
+            }
+            None => {
+                default = quote::quote! {
+                    .default(context, |context| { #block; })
+                }
+            }
+        }
+    }
+
+    quote::quote! {
+        {
+            let _value = #value;
+            cubecl::frontend::branch::switch_expand(_value.into())
+                #cases
+                #default
+                .finish(context);
+        }
+    }
+}
+
+fn codegen_match_arm_body(
+    body: &syn::Expr,
+    loop_level: usize,
+    variable_tracker: &mut VariableTracker,
+) -> TokenStream {
+    match body {
+        syn::Expr::Block(expr_block) => {
+            codegen_block(&expr_block.block, loop_level + 1, variable_tracker)
+        }
+        _ => codegen_expr(body, loop_level + 1, variable_tracker).tokens(),
+    }
+}
+
+/// Returns the values matched by a pattern on a runtime integer, or [None] for the wildcard.
+fn runtime_case_values(pat: &syn::Pat) -> Result<Option<Vec<&syn::LitInt>>, syn::Error> {
+    let invalid = || {
+        syn::Error::new_spanned(
+            pat,
+            "Only literal and wildcard patterns are supported when matching on runtime values",
+        )
+    };
+
+    match pat {
+        syn::Pat::Wild(_) => Ok(None),
+        syn::Pat::Lit(lit) => match &lit.lit {
+            syn::Lit::Int(int) => Ok(Some(vec![int])),
+            _ => Err(invalid()),
+        },
+        syn::Pat::Or(pat_or) => {
+            let mut values = Vec::new();
+            for case in pat_or.cases.iter() {
+                match runtime_case_values(case)? {
+                    Some(case_values) => values.extend(case_values),
+                    None => return Err(invalid()),
+                }
+            }
+            Ok(Some(values))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// Returns all identifiers bound by a pattern.
+pub(crate) fn pattern_idents(pat: &syn::Pat) -> Vec<&syn::Ident> {
+    match pat {
+        syn::Pat::Ident(pat_ident) => {
+            let mut idents = vec![&pat_ident.ident];
+            if let Some((_, sub)) = &pat_ident.subpat {
+                idents.extend(pattern_idents(sub));
+            }
+            idents
+        }
+        syn::Pat::Or(pat_or) => pat_or.cases.first().map(pattern_idents).unwrap_or_default(),
+        syn::Pat::Paren(pat_paren) => pattern_idents(&pat_paren.pat),
+        syn::Pat::Reference(pat_ref) => pattern_idents(&pat_ref.pat),
+        syn::Pat::Slice(pat_slice) => pat_slice.elems.iter().flat_map(pattern_idents).collect(),
+        syn::Pat::Struct(pat_struct) => pat_struct
+            .fields
+            .iter()
+            .flat_map(|field| pattern_idents(&field.pat))
+            .collect(),
+        syn::Pat::Tuple(pat_tuple) => pat_tuple.elems.iter().flat_map(pattern_idents).collect(),
+        syn::Pat::TupleStruct(pat_tuple) => {
+            pat_tuple.elems.iter().flat_map(pattern_idents).collect()
+        }
+        syn::Pat::Type(pat_type) => pattern_idents(&pat_type.pat),
+        _ => Vec::new(),
+    }
+}
+
+/// Makes the runtime matches of a block compile in plain Rust, where literal patterns can't be
+/// matched against cube types.
+pub(crate) fn plain_runtime_matches(block: &mut syn::Block, runtime_matches: &[syn::ExprMatch]) {
+    for stmt in block.stmts.iter_mut() {
+        match stmt {
+            syn::Stmt::Local(local) => {
+                if let Some(init) = &mut local.init {
+                    plain_runtime_matches_expr(&mut init.expr, runtime_matches);
+                }
+            }
+            syn::Stmt::Expr(expr, _) => plain_runtime_matches_expr(expr, runtime_matches),
+            _ => {}
+        }
+    }
+}
+
+fn plain_runtime_matches_expr(expr: &mut syn::Expr, runtime_matches: &[syn::ExprMatch]) {
+    match expr {
+        syn::Expr::Block(expr) => plain_runtime_matches(&mut expr.block, runtime_matches),
+        syn::Expr::Unsafe(expr) => plain_runtime_matches(&mut expr.block, runtime_matches),
+        syn::Expr::Loop(expr) => plain_runtime_matches(&mut expr.body, runtime_matches),
+        syn::Expr::While(expr) => plain_runtime_matches(&mut expr.body, runtime_matches),
+        syn::Expr::ForLoop(expr) => plain_runtime_matches(&mut expr.body, runtime_matches),
+        syn::Expr::Paren(expr) => plain_runtime_matches_expr(&mut expr.expr, runtime_matches),
+        syn::Expr::If(expr) => {
+            plain_runtime_matches(&mut expr.then_branch, runtime_matches);
+            if let Some((_, else_branch)) = &mut expr.else_branch {
+                plain_runtime_matches_expr(else_branch, runtime_matches);
+            }
+        }
+        syn::Expr::Match(expr_match) => {
+            let is_runtime = runtime_matches.contains(expr_match);
+
+            for arm in expr_match.arms.iter_mut() {
+                plain_runtime_matches_expr(&mut arm.body, runtime_matches);
+            }
+
+            if is_runtime {
+                let value = &expr_match.expr;
+                expr_match.expr =
+                    syn::parse_quote! { cubecl::frontend::branch::switch_value(#value) };
+            }
+        }
+        _ => {}
+    }
+}
@@ -4,7 +4,7 @@ use proc_macro2::{Ident, Span, TokenStream};
 use super::{
     base::{codegen_block, Codegen, CodegenKind},
     branch::{
-        codegen_break, codegen_for_loop, codegen_if, codegen_loop, codegen_return,
+        codegen_break, codegen_for_loop, codegen_if, codegen_loop, codegen_match, codegen_return,
         codegen_while_loop,
     },
     function::{codegen_call, codegen_closure, codegen_expr_method_call},
@@ -52,6 +52,9 @@ pub(crate) fn codegen_expr(
                 syn::Expr::Break(_) => codegen_break(),
                 syn::Expr::Return(return_expr) => codegen_return(return_expr),
                 syn::Expr::If(expr_if) => codegen_if(expr_if, loop_level, variable_tracker),
+                syn::Expr::Match(expr_match) => {
+                    codegen_match(expr_match, loop_level, variable_tracker)
+                }
                 syn::Expr::MethodCall(call) => {
                     codegen_expr_method_call(call, loop_level, variable_tracker)
                 }
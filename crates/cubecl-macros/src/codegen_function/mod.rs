@@ -7,4 +7,5 @@ mod operation;
 mod variable;
 
 pub(crate) use base::codegen_statement;
+pub(crate) use branch::{pattern_idents, plain_runtime_matches};
 pub(crate) use launch::codegen_launch;
@@ -3,6 +3,7 @@ use crate::tracker::VariableTracker;
 use super::{
     base::{Codegen, CodegenKind},
     expr::codegen_expr,
+    variable::codegen_lit,
 };
 
 /// Codegen for binary operations (+, -, *, etc.)
@@ -214,6 +215,13 @@ pub(crate) fn codegen_binary(
                     cubecl::frontend::bitand::expand(context, _lhs, _rhs)
                 }
             },
+            syn::BinOp::BitOr(_) => quote::quote! {
+                {
+                    let _lhs = #lhs;
+                    let _rhs = #rhs;
+                    cubecl::frontend::bitor::expand(context, _lhs, _rhs)
+                }
+            },
             syn::BinOp::BitXor(_) => quote::quote! {
                 {
                     let _lhs = #lhs;
@@ -247,6 +255,12 @@ pub(crate) fn codegen_unary(
     loop_level: usize,
     variable_tracker: &mut VariableTracker,
 ) -> Codegen {
+    // Negative literals stay literals so they can still be lifted to any element type.
+    if let (syn::UnOp::Neg(_), syn::Expr::Lit(lit)) = (unary.op, unary.expr.as_ref()) {
+        let lit = codegen_lit(lit);
+        return Codegen::new(quote::quote! { -#lit }, CodegenKind::Literal);
+    }
+
     let (inner, kind, _) = codegen_expr(&unary.expr, loop_level, variable_tracker).process();
 
     if matches!(kind, CodegenKind::Comptime) {
@@ -266,6 +280,12 @@ pub(crate) fn codegen_unary(
                     cubecl::frontend::not::expand(context, _inner)
                 }
             },
+            syn::UnOp::Neg(_) => quote::quote! {
+                {
+                    let _inner = #inner;
+                    cubecl::frontend::neg::expand(context, _inner)
+                }
+            },
             syn::UnOp::Deref(_) => inner,
             _ => todo!("Codegen: unsupported op {:?}", unary.op),
         },
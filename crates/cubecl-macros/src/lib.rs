@@ -9,9 +9,9 @@ mod tracker;
 
 pub(crate) mod codegen_common;
 
-use analyzer::VariableAnalyzer;
+use analyzer::{is_ty_comptime, VariableAnalyzer};
 use codegen_common::signature::{expand_sig, ExpandMode};
-use codegen_function::{codegen_launch, codegen_statement};
+use codegen_function::{codegen_launch, codegen_statement, plain_runtime_matches};
 use codegen_trait::{expand_trait_def, expand_trait_impl};
 use codegen_type::generate_cube_type;
 use proc_macro::TokenStream;
@@ -46,6 +46,7 @@ struct SupportedAttributes {
     mode: CubeMode,
     launch: bool,
     launch_unchecked: bool,
+    noinline: bool,
 }
 
 /// Derive macro for the module.
@@ -75,6 +76,7 @@ fn cube_fn(func: syn::ItemFn, attrs: &SupportedAttributes) -> TokenStream {
         &mut variable_tracker,
         attrs.launch,
         attrs.launch_unchecked,
+        attrs.noinline,
     ) {
         Ok(code) => code.into(),
         Err(err) => err.into(),
@@ -85,6 +87,7 @@ fn parse_attributes(args: &Punctuated<Meta, Comma>) -> SupportedAttributes {
     let mut mode = CubeMode::Default;
     let mut launch = false;
     let mut launch_unchecked = false;
+    let mut noinline = false;
 
     for arg in args.iter() {
         match arg {
@@ -100,6 +103,9 @@ fn parse_attributes(args: &Punctuated<Meta, Comma>) -> SupportedAttributes {
                         "launch_unchecked" => {
                             launch_unchecked = true;
                         }
+                        "noinline" => {
+                            noinline = true;
+                        }
                         _ => {
                             panic!("Attribute {ident} is not supported")
                         }
@@ -117,6 +123,7 @@ fn parse_attributes(args: &Punctuated<Meta, Comma>) -> SupportedAttributes {
         mode,
         launch,
         launch_unchecked,
+        noinline,
     }
 }
 
@@ -126,6 +133,7 @@ fn codegen_cube(
     variable_tracker: &mut VariableTracker,
     launch: bool,
     launch_unchecked: bool,
+    noinline: bool,
 ) -> Result<proc_macro2::TokenStream, proc_macro2::TokenStream> {
     let signature = expand_sig(
         &func.sig,
@@ -141,6 +149,10 @@ fn codegen_cube(
         body.extend(tokens);
     }
 
+    if noinline {
+        body = codegen_noinline(func, body);
+    }
+
     let is_in_error = !variable_tracker.errors.is_empty();
 
     if is_in_error {
@@ -182,6 +194,9 @@ fn codegen_cube(
     let vis = &func.vis;
     let doc = format!("Module containing the expand {launch_doc}of {mod_name}.");
 
+    let mut func = func.clone();
+    plain_runtime_matches(&mut func.block, &variable_tracker.runtime_matches);
+
     Ok(quote::quote! {
         #[allow(dead_code)]
         #[allow(clippy::too_many_arguments)]
@@ -202,3 +217,41 @@ fn codegen_cube(
         }
     })
 }
+
+/// Wrap the expanded body into a call to a function that isn't inlined.
+///
+/// Comptime arguments are captured by the body, while the other arguments become the inputs of
+/// the function.
+fn codegen_noinline(
+    func: &syn::ItemFn,
+    body: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let name = func.sig.ident.to_string();
+    let mut args = Vec::new();
+    let mut types = Vec::new();
+
+    for input in func.sig.inputs.iter() {
+        if let syn::FnArg::Typed(pat) = input {
+            if is_ty_comptime(&pat.ty) {
+                continue;
+            }
+
+            if let syn::Pat::Ident(ident) = pat.pat.as_ref() {
+                args.push(ident.ident.clone());
+                types.push(codegen_common::signature::no_ref(&pat.ty).clone());
+            }
+        }
+    }
+
+    quote::quote! {
+        let _args = vec![#(#args.into()),*];
+        cubecl::frontend::function::call_expand(context, #name, _args, |context, _inputs| {
+            let mut _inputs = _inputs.into_iter();
+            #(
+                let #args: <#types as cubecl::frontend::CubeType>::ExpandType =
+                    _inputs.next().unwrap().into();
+            )*
+            #body
+        })
+    }
+}
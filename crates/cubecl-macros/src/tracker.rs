@@ -24,6 +24,7 @@ pub(crate) struct VariableTracker {
     codegen_repeats: HashMap<VariableKey, u8>,
     variable_uses: HashMap<VariableIdent, VariableUse>,
     pub errors: Vec<syn::Error>,
+    pub runtime_matches: Vec<syn::ExprMatch>,
 }
 
 #[derive(Debug, Default)]
@@ -0,0 +1,1003 @@
+use std::collections::BTreeSet;
+
+use cubecl_core::{
+    ir::{self as cube, ConstantScalarValue},
+    Compiler,
+};
+use cubecl_runtime::{server::ComputeError, ExecutionMode};
+
+use crate::{
+    Binding, Body, Builtin, ComputeKernel, Elem, Extension, Function, Instruction, Item,
+    LocalArray, Matrix, MatrixInstruction, MatrixLayout, SharedMemory, Subcube, Variable,
+    SIMDGROUP_MATRIX_DIM,
+};
+
+/// Metal Shading Language compiler.
+///
+/// Subcube operations are mapped to the `simd_*` intrinsics and cooperative matrices to
+/// `simdgroup_matrix`, which aren't exposed by WGSL on Apple GPUs.
+#[derive(Clone, Default)]
+pub struct MslCompiler {
+    mode: ExecutionMode,
+    num_inputs: usize,
+    num_outputs: usize,
+    /// The bindings of the kernel, passed to the functions.
+    bindings: Vec<Binding>,
+    /// The builtins used by the body being compiled and by the functions it calls.
+    builtins: BTreeSet<Builtin>,
+    /// The body being compiled, without its instructions.
+    body: Body,
+    extensions: Vec<Extension>,
+    matrices: bool,
+    /// The functions compiled so far, indexed by their id.
+    functions: Vec<Function>,
+    /// The output of the function being compiled, which is returned by its return statements.
+    output: Option<Variable>,
+    /// The first error encountered during compilation.
+    ///
+    /// The compilation continues with placeholders after an error, but the resulting kernel is
+    /// discarded.
+    error: Option<ComputeError>,
+}
+
+impl core::fmt::Debug for MslCompiler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("MslCompiler")
+    }
+}
+
+impl Compiler for MslCompiler {
+    type Representation = ComputeKernel;
+
+    fn compile(kernel: cube::KernelDefinition, mode: ExecutionMode) -> Self::Representation {
+        Self::try_compile(kernel, mode).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    fn try_compile(
+        kernel: cube::KernelDefinition,
+        mode: ExecutionMode,
+    ) -> Result<Self::Representation, ComputeError> {
+        let mut compiler = Self {
+            mode,
+            ..Self::default()
+        };
+        let kernel = compiler.compile_kernel(kernel);
+
+        match compiler.error {
+            Some(err) => Err(err),
+            None => Ok(kernel),
+        }
+    }
+
+    fn elem_size(elem: cube::Elem) -> usize {
+        match elem {
+            cube::Elem::Float(cube::FloatKind::F64) => {
+                panic!("f64 isn't supported by the MSL compiler")
+            }
+            _ => elem.size(),
+        }
+    }
+
+    fn max_shared_memory_size() -> usize {
+        // The threadgroup memory of Apple GPUs.
+        32768
+    }
+
+    fn optimizations() -> cube::Optimizations {
+        cube::Optimizations::all()
+    }
+}
+
+impl MslCompiler {
+    fn compile_kernel(&mut self, mut value: cube::KernelDefinition) -> ComputeKernel {
+        self.num_inputs = value.inputs.len();
+        self.num_outputs = value.outputs.len();
+
+        let inputs = value
+            .inputs
+            .into_iter()
+            .enumerate()
+            .map(|(i, binding)| self.compile_binding(format!("input_{i}"), binding))
+            .collect::<Vec<_>>();
+        let outputs = value
+            .outputs
+            .into_iter()
+            .enumerate()
+            .map(|(i, binding)| self.compile_binding(format!("output_{i}"), binding))
+            .collect::<Vec<_>>();
+        let named = value
+            .named
+            .into_iter()
+            .map(|(name, binding)| self.compile_binding(name, binding))
+            .collect::<Vec<_>>();
+        self.bindings = inputs
+            .iter()
+            .chain(outputs.iter())
+            .chain(named.iter())
+            .cloned()
+            .collect();
+
+        // Functions only call the functions registered before them, so they are compiled in order.
+        for function in value.functions.drain(..) {
+            let function = self.compile_function(function);
+            self.functions.push(function);
+        }
+
+        let instructions = self.compile_scope(&mut value.body);
+
+        ComputeKernel {
+            inputs,
+            outputs,
+            named,
+            cube_dim: value.cube_dim,
+            builtins: core::mem::take(&mut self.builtins),
+            extensions: core::mem::take(&mut self.extensions),
+            functions: core::mem::take(&mut self.functions),
+            body: Body {
+                instructions,
+                ..core::mem::take(&mut self.body)
+            },
+            matrices: self.matrices,
+        }
+    }
+
+    fn compile_function(&mut self, mut value: cube::Function) -> Function {
+        let mut compiler = Self {
+            mode: self.mode,
+            num_inputs: self.num_inputs,
+            num_outputs: self.num_outputs,
+            bindings: self.bindings.clone(),
+            extensions: core::mem::take(&mut self.extensions),
+            functions: core::mem::take(&mut self.functions),
+            ..Self::default()
+        };
+
+        compiler.output = value.output.map(|output| compiler.compile_variable(output));
+        let mut instructions = compiler.compile_scope(&mut value.scope);
+        let inputs = value
+            .inputs
+            .into_iter()
+            .map(|input| compiler.compile_variable(input))
+            .collect::<Vec<_>>();
+
+        if inputs
+            .iter()
+            .any(|input| input.is_array() || matches!(input, Variable::Matrix { .. }))
+        {
+            compiler.unsupported(format!(
+                "Only values can be passed to function {}, since it isn't inlined",
+                value.name
+            ));
+        }
+        if !compiler.body.shared_memories.is_empty() {
+            compiler.unsupported(format!(
+                "Shared memories can't be declared in function {}, since it isn't inlined",
+                value.name
+            ));
+        }
+
+        // The inputs are declared as parameters of the function.
+        instructions.retain(|instruction| match instruction {
+            Instruction::DeclareVariable { var } => !inputs.contains(var),
+            _ => true,
+        });
+
+        if let Some(err) = compiler.error.take() {
+            self.fail(err);
+        }
+        self.extensions = core::mem::take(&mut compiler.extensions);
+        self.functions = core::mem::take(&mut compiler.functions);
+        self.matrices |= compiler.matrices;
+
+        Function {
+            name: format!("{}_{}", value.name, value.id),
+            inputs,
+            output: compiler.output,
+            builtins: compiler.builtins,
+            body: Body {
+                instructions,
+                ..compiler.body
+            },
+        }
+    }
+
+    fn compile_call(&mut self, call: cube::Call) -> Instruction {
+        let function = &self.functions[call.function as usize];
+        let name = function.name.clone();
+        let builtins = function.builtins.clone();
+
+        let args = self
+            .bindings
+            .iter()
+            .map(|binding| binding.name.clone())
+            .chain(builtins.iter().map(|builtin| builtin.to_string()))
+            .collect();
+        self.builtins.extend(builtins);
+
+        Instruction::Call {
+            function: name,
+            inputs: call
+                .inputs
+                .into_iter()
+                .map(|input| self.compile_variable(input))
+                .collect(),
+            output: call.output.map(|output| self.compile_variable(output)),
+            args,
+        }
+    }
+
+    fn compile_scope(&mut self, scope: &mut cube::Scope) -> Vec<Instruction> {
+        let mut instructions = Vec::new();
+        let processing = scope.process_with(&Self::optimizations());
+
+        for var in processing.variables {
+            // Slices and atomics are pointers, declared when they are assigned.
+            if let cube::Variable::Slice { .. } = var {
+                continue;
+            }
+            if var.item().elem().is_atomic() {
+                continue;
+            }
+            instructions.push(Instruction::DeclareVariable {
+                var: self.compile_variable(var),
+            });
+        }
+
+        processing
+            .operations
+            .into_iter()
+            .for_each(|op| self.compile_operation(&mut instructions, op, scope));
+
+        instructions
+    }
+
+    fn compile_operation(
+        &mut self,
+        instructions: &mut Vec<Instruction>,
+        operation: cube::Operation,
+        scope: &mut cube::Scope,
+    ) {
+        match operation {
+            cube::Operation::Operator(op) => self.compile_instruction(instructions, op, scope),
+            cube::Operation::Procedure(proc) => self.compile_procedure(instructions, proc, scope),
+            cube::Operation::Metadata(op) => instructions.push(self.compile_metadata(op)),
+            cube::Operation::Branch(val) => self.compile_branch(instructions, val),
+            cube::Operation::Synchronization(val) => match val {
+                cube::Synchronization::SyncUnits => instructions.push(Instruction::SyncThreadgroup),
+                cube::Synchronization::SyncStorage => instructions.push(Instruction::SyncDevice),
+            },
+            cube::Operation::Subcube(op) => {
+                instructions.push(Instruction::Subcube(self.compile_subcube(op)))
+            }
+            cube::Operation::CoopMma(op) => {
+                instructions.push(Instruction::Matrix(self.compile_cmma(op)))
+            }
+            cube::Operation::Call(call) => instructions.push(self.compile_call(call)),
+        }
+    }
+
+    fn compile_subcube(&mut self, subcube: cube::Subcube) -> Subcube {
+        match subcube {
+            cube::Subcube::Elect(op) => Subcube::Elect {
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::All(op) => Subcube::All {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Any(op) => Subcube::Any {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Broadcast(op) => Subcube::Broadcast {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Sum(op) => Subcube::Sum {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Prod(op) => Subcube::Prod {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::And(op) => Subcube::And {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Or(op) => Subcube::Or {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Xor(op) => Subcube::Xor {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Min(op) => Subcube::Min {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Max(op) => Subcube::Max {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Shuffle(op) => Subcube::Shuffle {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::ShuffleXor(op) => Subcube::ShuffleXor {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::ShuffleUp(op) => Subcube::ShuffleUp {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::ShuffleDown(op) => Subcube::ShuffleDown {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::InclusiveSum(op) => Subcube::InclusiveSum {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::ExclusiveSum(op) => Subcube::ExclusiveSum {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::InclusiveProd(op) => Subcube::InclusiveProd {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::ExclusiveProd(op) => Subcube::ExclusiveProd {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Ballot(op) => Subcube::Ballot {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+        }
+    }
+
+    fn compile_cmma(&mut self, cmma: cube::CoopMma) -> MatrixInstruction {
+        match cmma {
+            cube::CoopMma::Fill { mat, value } => MatrixInstruction::Fill {
+                mat: self.compile_variable(mat),
+                value: self.compile_variable(value),
+            },
+            cube::CoopMma::Load { mat, value, stride } => MatrixInstruction::Load {
+                mat: self.compile_variable(mat),
+                value: self.compile_variable(value),
+                stride: self.compile_variable(stride),
+            },
+            cube::CoopMma::Execute {
+                mat_a,
+                mat_b,
+                mat_c,
+                mat_d,
+            } => MatrixInstruction::Execute {
+                mat_a: self.compile_variable(mat_a),
+                mat_b: self.compile_variable(mat_b),
+                mat_c: self.compile_variable(mat_c),
+                mat_d: self.compile_variable(mat_d),
+            },
+            cube::CoopMma::Store {
+                output,
+                mat,
+                stride,
+                layout,
+            } => {
+                let layout = self.compile_matrix_layout(layout).unwrap_or_else(|| {
+                    self.unsupported("A layout is required to store a matrix".to_string());
+                    MatrixLayout::RowMajor
+                });
+
+                MatrixInstruction::Store {
+                    output: self.compile_variable(output),
+                    mat: self.compile_variable(mat),
+                    stride: self.compile_variable(stride),
+                    layout,
+                }
+            }
+        }
+    }
+
+    fn compile_metadata(&mut self, metadata: cube::Metadata) -> Instruction {
+        match metadata {
+            cube::Metadata::Stride { dim, var, out } => {
+                self.body.stride_or_shape = true;
+                Instruction::Stride {
+                    dim: self.compile_variable(dim),
+                    position: self.position(&var),
+                    out: self.compile_variable(out),
+                }
+            }
+            cube::Metadata::Shape { dim, var, out } => {
+                self.body.stride_or_shape = true;
+                Instruction::Shape {
+                    dim: self.compile_variable(dim),
+                    position: self.position(&var),
+                    out: self.compile_variable(out),
+                }
+            }
+            cube::Metadata::Length { var, out } => Instruction::Length {
+                var: self.compile_variable(var),
+                out: self.compile_variable(out),
+                num_inputs: self.num_inputs,
+                num_outputs: self.num_outputs,
+            },
+        }
+    }
+
+    /// The position of a global array in the metadata.
+    fn position(&mut self, var: &cube::Variable) -> usize {
+        match var {
+            cube::Variable::GlobalInputArray { id, .. } => *id as usize,
+            cube::Variable::GlobalOutputArray { id, .. } => self.num_inputs + *id as usize,
+            _ => {
+                self.unsupported(format!("Only global arrays have a layout, got {var:?}"));
+                0
+            }
+        }
+    }
+
+    fn compile_branch(&mut self, instructions: &mut Vec<Instruction>, branch: cube::Branch) {
+        match branch {
+            cube::Branch::If(mut op) => instructions.push(Instruction::If {
+                cond: self.compile_variable(op.cond),
+                instructions: self.compile_scope(&mut op.scope),
+            }),
+            cube::Branch::IfElse(mut op) => instructions.push(Instruction::IfElse {
+                cond: self.compile_variable(op.cond),
+                instructions_if: self.compile_scope(&mut op.scope_if),
+                instructions_else: self.compile_scope(&mut op.scope_else),
+            }),
+            cube::Branch::Switch(mut op) => instructions.push(Instruction::Switch {
+                value: self.compile_variable(op.value),
+                instructions_default: self.compile_scope(&mut op.scope_default),
+                cases: op
+                    .cases
+                    .into_iter()
+                    .map(|(values, mut scope)| {
+                        let values = values
+                            .into_iter()
+                            .map(|val| self.compile_variable(val))
+                            .collect();
+                        (values, self.compile_scope(&mut scope))
+                    })
+                    .collect(),
+            }),
+            cube::Branch::Return => instructions.push(Instruction::Return {
+                output: self.output,
+            }),
+            cube::Branch::Break => instructions.push(Instruction::Break),
+            cube::Branch::RangeLoop(mut range_loop) => instructions.push(Instruction::RangeLoop {
+                i: self.compile_variable(range_loop.i),
+                start: self.compile_variable(range_loop.start),
+                end: self.compile_variable(range_loop.end),
+                step: range_loop.step.map(|it| self.compile_variable(it)),
+                instructions: self.compile_scope(&mut range_loop.scope),
+            }),
+            cube::Branch::Loop(mut op) => instructions.push(Instruction::Loop {
+                instructions: self.compile_scope(&mut op.scope),
+            }),
+        };
+    }
+
+    fn compile_procedure(
+        &mut self,
+        instructions: &mut Vec<Instruction>,
+        proc: cube::Procedure,
+        scope: &mut cube::Scope,
+    ) {
+        match proc {
+            cube::Procedure::ReadGlobalWithLayout(proc) => proc.expand(scope),
+            cube::Procedure::ReadGlobal(proc) => proc.expand(scope),
+            cube::Procedure::WriteGlobal(proc) => proc.expand(scope),
+            cube::Procedure::ConditionalAssign(proc) => proc.expand(scope),
+            cube::Procedure::CheckedIndex(proc) => proc.expand(scope),
+            cube::Procedure::CheckedIndexAssign(proc) => proc.expand(scope),
+            cube::Procedure::IndexOffsetGlobalWithLayout(proc) => proc.expand(scope),
+            cube::Procedure::EarlyReturn(proc) => proc.expand(scope),
+        }
+
+        instructions.extend(self.compile_scope(scope));
+    }
+
+    fn compile_instruction(
+        &mut self,
+        instructions: &mut Vec<Instruction>,
+        value: cube::Operator,
+        scope: &mut cube::Scope,
+    ) {
+        let instruction = match value {
+            cube::Operator::Add(op) => self.binary_operator("+", op),
+            cube::Operator::Sub(op) => self.binary_operator("-", op),
+            cube::Operator::Mul(op) => self.binary_operator("*", op),
+            cube::Operator::Div(op) => self.binary_operator("/", op),
+            cube::Operator::Modulo(op) => match op.out.item().elem() {
+                cube::Elem::Float(_) => self.binary_function("fmod", op),
+                _ => self.binary_operator("%", op),
+            },
+            cube::Operator::Remainder(op) => Instruction::Remainder {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Equal(op) => self.binary_operator("==", op),
+            cube::Operator::NotEqual(op) => self.binary_operator("!=", op),
+            cube::Operator::Lower(op) => self.binary_operator("<", op),
+            cube::Operator::Greater(op) => self.binary_operator(">", op),
+            cube::Operator::LowerEqual(op) => self.binary_operator("<=", op),
+            cube::Operator::GreaterEqual(op) => self.binary_operator(">=", op),
+            cube::Operator::And(op) => self.binary_operator("&&", op),
+            cube::Operator::Or(op) => self.binary_operator("||", op),
+            cube::Operator::BitwiseAnd(op) => self.binary_operator("&", op),
+            cube::Operator::BitwiseOr(op) => self.binary_operator("|", op),
+            cube::Operator::BitwiseXor(op) => self.binary_operator("^", op),
+            cube::Operator::ShiftLeft(op) => self.binary_operator("<<", op),
+            cube::Operator::ShiftRight(op) => self.binary_operator(">>", op),
+            cube::Operator::Not(op) => self.unary_operator("!", op),
+            cube::Operator::BitwiseNot(op) => self.unary_operator("~", op),
+            cube::Operator::Neg(op) => self.unary_operator("-", op),
+            cube::Operator::Abs(op) => self.unary_function("abs", op),
+            cube::Operator::Exp(op) => self.unary_function("exp", op),
+            cube::Operator::Exp2(op) => self.unary_function("exp2", op),
+            cube::Operator::Log(op) => self.unary_function("log", op),
+            cube::Operator::Log2(op) => self.unary_function("log2", op),
+            cube::Operator::Cos(op) => self.unary_function("cos", op),
+            cube::Operator::Sin(op) => self.unary_function("sin", op),
+            cube::Operator::Tanh(op) => self.unary_function("tanh", op),
+            cube::Operator::Sqrt(op) => self.unary_function("sqrt", op),
+            cube::Operator::Floor(op) => self.unary_function("floor", op),
+            cube::Operator::Ceil(op) => self.unary_function("ceil", op),
+            cube::Operator::Tan(op) => self.unary_function("tan", op),
+            cube::Operator::Asin(op) => self.unary_function("asin", op),
+            cube::Operator::Acos(op) => self.unary_function("acos", op),
+            cube::Operator::Atan(op) => self.unary_function("atan", op),
+            cube::Operator::Sinh(op) => self.unary_function("sinh", op),
+            cube::Operator::Cosh(op) => self.unary_function("cosh", op),
+            cube::Operator::Round(op) => self.unary_function("round", op),
+            cube::Operator::Trunc(op) => self.unary_function("trunc", op),
+            cube::Operator::Fract(op) => self.unary_function("fract", op),
+            cube::Operator::Sign(op) => self.unary_function("sign", op),
+            cube::Operator::Rsqrt(op) => self.unary_function("rsqrt", op),
+            cube::Operator::IsNan(op) => self.unary_function("isnan", op),
+            cube::Operator::IsInf(op) => self.unary_function("isinf", op),
+            cube::Operator::CountOnes(op) => self.unary_function("popcount", op),
+            cube::Operator::ReverseBits(op) => self.unary_function("reverse_bits", op),
+            cube::Operator::LeadingZeros(op) => self.unary_function("clz", op),
+            cube::Operator::TrailingZeros(op) => self.unary_function("ctz", op),
+            cube::Operator::Erf(op) => {
+                let item = self.compile_item(op.input.item());
+                self.register_extension(Extension::Erf(Item::scalar(item.elem)));
+                self.register_extension(Extension::Erf(item));
+                self.unary_function("erf", op)
+            }
+            cube::Operator::Log1p(op) => Instruction::Log1p {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Max(op) => self.binary_function("max", op),
+            cube::Operator::Min(op) => self.binary_function("min", op),
+            cube::Operator::CopySign(op) => self.binary_function("copysign", op),
+            cube::Operator::Atan2(op) => self.binary_function("atan2", op),
+            cube::Operator::Powf(op) => {
+                let item = self.compile_item(op.out.item());
+                self.register_extension(Extension::Powf(Item::scalar(item.elem)));
+                self.register_extension(Extension::Powf(item));
+                self.binary_function("powf", op)
+            }
+            cube::Operator::Hypot(op) => Instruction::Hypot {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Recip(op) => {
+                let elem = op.input.item().elem();
+                let one = match elem {
+                    cube::Elem::Float(kind) => ConstantScalarValue::Float(1.0, kind),
+                    cube::Elem::Int(kind) => ConstantScalarValue::Int(1, kind),
+                    cube::Elem::UInt(kind) => ConstantScalarValue::UInt(1, kind),
+                    _ => {
+                        self.unsupported(format!("Can't compute the reciprocal of {elem}"));
+                        ConstantScalarValue::Float(1.0, cube::FloatKind::F32)
+                    }
+                };
+
+                Instruction::BinaryOperator {
+                    op: "/",
+                    lhs: self.compile_variable(cube::Variable::ConstantScalar(one)),
+                    rhs: self.compile_variable(op.input),
+                    out: self.compile_variable(op.out),
+                }
+            }
+            cube::Operator::Clamp(op) => Instruction::Clamp {
+                input: self.compile_variable(op.input),
+                min_value: self.compile_variable(op.min_value),
+                max_value: self.compile_variable(op.max_value),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Fma(op) => Instruction::Fma {
+                a: self.compile_variable(op.a),
+                b: self.compile_variable(op.b),
+                c: self.compile_variable(op.c),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Assign(op) => Instruction::Assign {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Bitcast(op) => Instruction::Bitcast {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Slice(op) => Instruction::Slice {
+                input: self.compile_variable(op.input),
+                start: self.compile_variable(op.start),
+                end: self.compile_variable(op.end),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Index(op) => {
+                if self.mode == ExecutionMode::Checked && has_length(&op.lhs) {
+                    let proc = cube::Procedure::CheckedIndex(cube::CheckedIndex {
+                        lhs: op.lhs,
+                        rhs: op.rhs,
+                        out: op.out,
+                    });
+                    return self.compile_procedure(instructions, proc, scope);
+                }
+                self.index(op)
+            }
+            cube::Operator::UncheckedIndex(op) => self.index(op),
+            cube::Operator::IndexAssign(op) => {
+                if self.mode == ExecutionMode::Checked && has_length(&op.out) {
+                    let proc = cube::Procedure::CheckedIndexAssign(cube::CheckedIndexAssign {
+                        lhs: op.lhs,
+                        rhs: op.rhs,
+                        out: op.out,
+                    });
+                    return self.compile_procedure(instructions, proc, scope);
+                }
+                self.index_assign(op)
+            }
+            cube::Operator::UncheckedIndexAssign(op) => self.index_assign(op),
+            cube::Operator::AtomicLoad(op) => Instruction::AtomicLoad {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::AtomicStore(op) => Instruction::AtomicStore {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::AtomicSwap(op) => self.atomic("atomic_exchange_explicit", op),
+            cube::Operator::AtomicAdd(op) => self.atomic("atomic_fetch_add_explicit", op),
+            cube::Operator::AtomicSub(op) => self.atomic("atomic_fetch_sub_explicit", op),
+            cube::Operator::AtomicMax(op) => self.atomic("atomic_fetch_max_explicit", op),
+            cube::Operator::AtomicMin(op) => self.atomic("atomic_fetch_min_explicit", op),
+            cube::Operator::AtomicAnd(op) => self.atomic("atomic_fetch_and_explicit", op),
+            cube::Operator::AtomicOr(op) => self.atomic("atomic_fetch_or_explicit", op),
+            cube::Operator::AtomicXor(op) => self.atomic("atomic_fetch_xor_explicit", op),
+            cube::Operator::AtomicCompareAndSwap(op) => Instruction::AtomicCompareAndSwap {
+                input: self.compile_variable(op.input),
+                cmp: self.compile_variable(op.cmp),
+                val: self.compile_variable(op.val),
+                out: self.compile_variable(op.out),
+            },
+        };
+
+        instructions.push(instruction);
+    }
+
+    fn binary_operator(&mut self, op: &'static str, value: cube::BinaryOperator) -> Instruction {
+        Instruction::BinaryOperator {
+            op,
+            lhs: self.compile_variable(value.lhs),
+            rhs: self.compile_variable(value.rhs),
+            out: self.compile_variable(value.out),
+        }
+    }
+
+    fn unary_operator(&mut self, op: &'static str, value: cube::UnaryOperator) -> Instruction {
+        Instruction::UnaryOperator {
+            op,
+            input: self.compile_variable(value.input),
+            out: self.compile_variable(value.out),
+        }
+    }
+
+    fn binary_function(&mut self, func: &'static str, value: cube::BinaryOperator) -> Instruction {
+        Instruction::BinaryFunction {
+            func,
+            lhs: self.compile_variable(value.lhs),
+            rhs: self.compile_variable(value.rhs),
+            out: self.compile_variable(value.out),
+        }
+    }
+
+    fn unary_function(&mut self, func: &'static str, value: cube::UnaryOperator) -> Instruction {
+        Instruction::UnaryFunction {
+            func,
+            input: self.compile_variable(value.input),
+            out: self.compile_variable(value.out),
+        }
+    }
+
+    fn atomic(&mut self, func: &'static str, value: cube::BinaryOperator) -> Instruction {
+        Instruction::Atomic {
+            func,
+            lhs: self.compile_variable(value.lhs),
+            rhs: self.compile_variable(value.rhs),
+            out: self.compile_variable(value.out),
+        }
+    }
+
+    fn index(&mut self, value: cube::BinaryOperator) -> Instruction {
+        Instruction::Index {
+            lhs: self.compile_variable(value.lhs),
+            rhs: self.compile_variable(value.rhs),
+            out: self.compile_variable(value.out),
+        }
+    }
+
+    fn index_assign(&mut self, value: cube::BinaryOperator) -> Instruction {
+        Instruction::IndexAssign {
+            lhs: self.compile_variable(value.lhs),
+            rhs: self.compile_variable(value.rhs),
+            out: self.compile_variable(value.out),
+        }
+    }
+
+    fn register_extension(&mut self, extension: Extension) {
+        if !self.extensions.contains(&extension) {
+            self.extensions.push(extension);
+        }
+    }
+
+    fn builtin(&mut self, builtin: Builtin) -> Variable {
+        self.builtins.insert(builtin);
+        Variable::Builtin(builtin)
+    }
+
+    fn builtin_axis(&mut self, builtin: Builtin, axis: char) -> Variable {
+        self.builtins.insert(builtin);
+        Variable::BuiltinAxis(builtin, axis)
+    }
+
+    fn compile_variable(&mut self, value: cube::Variable) -> Variable {
+        match value {
+            cube::Variable::GlobalInputArray { id, item } => {
+                Variable::GlobalInputArray(id, self.compile_item(item))
+            }
+            cube::Variable::GlobalOutputArray { id, item } => {
+                Variable::GlobalOutputArray(id, self.compile_item(item))
+            }
+            cube::Variable::GlobalScalar { id, elem } => {
+                Variable::GlobalScalar(id, self.compile_elem(elem), elem)
+            }
+            cube::Variable::ConstantScalar(value) => {
+                Variable::ConstantScalar(value, self.compile_elem(value.elem()))
+            }
+            cube::Variable::Local { id, item, depth } => Variable::Local {
+                id,
+                item: self.compile_item(item),
+                depth,
+            },
+            cube::Variable::LocalScalar { id, elem, depth } => Variable::LocalScalar {
+                id,
+                elem: self.compile_elem(elem),
+                depth,
+            },
+            cube::Variable::Slice { id, item, depth } => Variable::Slice {
+                id,
+                item: self.compile_item(item),
+                depth,
+            },
+            cube::Variable::SharedMemory { id, item, length } => {
+                let item = self.compile_item(item);
+                if !self.body.shared_memories.iter().any(|s| s.index == id) {
+                    self.body.shared_memories.push(SharedMemory {
+                        index: id,
+                        item,
+                        size: length,
+                    });
+                }
+                Variable::SharedMemory(id, item, length)
+            }
+            cube::Variable::LocalArray {
+                id,
+                item,
+                depth,
+                length,
+            } => {
+                let item = self.compile_item(item);
+                if !self
+                    .body
+                    .local_arrays
+                    .iter()
+                    .any(|s| s.index == id && s.depth == depth)
+                {
+                    self.body.local_arrays.push(LocalArray {
+                        index: id,
+                        item,
+                        depth,
+                        size: length,
+                    });
+                }
+                Variable::LocalArray(id, item, depth, length)
+            }
+            cube::Variable::Matrix { id, mat, depth } => {
+                self.matrices = true;
+                Variable::Matrix {
+                    id,
+                    mat: self.compile_matrix(mat),
+                    depth,
+                }
+            }
+            cube::Variable::Rank => {
+                self.body.rank = true;
+                Variable::Rank
+            }
+            cube::Variable::UnitPos => self.builtin(Builtin::ThreadIndexInThreadgroup),
+            cube::Variable::UnitPosX => {
+                self.builtin_axis(Builtin::ThreadPositionInThreadgroup, 'x')
+            }
+            cube::Variable::UnitPosY => {
+                self.builtin_axis(Builtin::ThreadPositionInThreadgroup, 'y')
+            }
+            cube::Variable::UnitPosZ => {
+                self.builtin_axis(Builtin::ThreadPositionInThreadgroup, 'z')
+            }
+            cube::Variable::CubePos => {
+                self.body.cube_pos = true;
+                self.builtins.insert(Builtin::ThreadgroupPositionInGrid);
+                self.builtins.insert(Builtin::ThreadgroupsPerGrid);
+                Variable::CubePos
+            }
+            cube::Variable::CubePosX => self.builtin_axis(Builtin::ThreadgroupPositionInGrid, 'x'),
+            cube::Variable::CubePosY => self.builtin_axis(Builtin::ThreadgroupPositionInGrid, 'y'),
+            cube::Variable::CubePosZ => self.builtin_axis(Builtin::ThreadgroupPositionInGrid, 'z'),
+            cube::Variable::CubeDim => {
+                self.body.cube_dim = true;
+                self.builtins.insert(Builtin::ThreadsPerThreadgroup);
+                Variable::CubeDim
+            }
+            cube::Variable::CubeDimX => self.builtin_axis(Builtin::ThreadsPerThreadgroup, 'x'),
+            cube::Variable::CubeDimY => self.builtin_axis(Builtin::ThreadsPerThreadgroup, 'y'),
+            cube::Variable::CubeDimZ => self.builtin_axis(Builtin::ThreadsPerThreadgroup, 'z'),
+            cube::Variable::CubeCount => {
+                self.body.cube_count = true;
+                self.builtins.insert(Builtin::ThreadgroupsPerGrid);
+                Variable::CubeCount
+            }
+            cube::Variable::CubeCountX => self.builtin_axis(Builtin::ThreadgroupsPerGrid, 'x'),
+            cube::Variable::CubeCountY => self.builtin_axis(Builtin::ThreadgroupsPerGrid, 'y'),
+            cube::Variable::CubeCountZ => self.builtin_axis(Builtin::ThreadgroupsPerGrid, 'z'),
+            cube::Variable::AbsolutePos => {
+                self.body.absolute_pos = true;
+                self.builtins.insert(Builtin::ThreadPositionInGrid);
+                self.builtins.insert(Builtin::ThreadsPerGrid);
+                Variable::AbsolutePos
+            }
+            cube::Variable::AbsolutePosX => self.builtin_axis(Builtin::ThreadPositionInGrid, 'x'),
+            cube::Variable::AbsolutePosY => self.builtin_axis(Builtin::ThreadPositionInGrid, 'y'),
+            cube::Variable::AbsolutePosZ => self.builtin_axis(Builtin::ThreadPositionInGrid, 'z'),
+            cube::Variable::SubcubeDim => self.builtin(Builtin::ThreadsPerSimdgroup),
+        }
+    }
+
+    fn compile_matrix(&mut self, matrix: cube::Matrix) -> Matrix {
+        if [matrix.m, matrix.n, matrix.k] != [SIMDGROUP_MATRIX_DIM; 3] {
+            self.unsupported(format!(
+                "SIMD-group matrices are {SIMDGROUP_MATRIX_DIM}x{SIMDGROUP_MATRIX_DIM}x{SIMDGROUP_MATRIX_DIM}, got {}x{}x{}",
+                matrix.m, matrix.n, matrix.k
+            ));
+        }
+
+        let elem = self.compile_elem(matrix.elem);
+        if !matches!(elem, Elem::F16 | Elem::BF16 | Elem::F32) {
+            self.unsupported(format!(
+                "SIMD-group matrices of {} aren't supported",
+                matrix.elem
+            ));
+        }
+
+        Matrix {
+            elem,
+            layout: self.compile_matrix_layout(matrix.layout),
+        }
+    }
+
+    fn compile_matrix_layout(&mut self, layout: cube::MatrixLayout) -> Option<MatrixLayout> {
+        match layout {
+            cube::MatrixLayout::ColMajor => Some(MatrixLayout::ColMajor),
+            cube::MatrixLayout::RowMajor => Some(MatrixLayout::RowMajor),
+            cube::MatrixLayout::Undefined => None,
+        }
+    }
+
+    fn compile_binding(&mut self, name: String, binding: cube::Binding) -> Binding {
+        Binding {
+            name,
+            item: self.compile_item(binding.item),
+        }
+    }
+
+    fn compile_item(&mut self, item: cube::Item) -> Item {
+        let elem = self.compile_elem(item.elem);
+
+        if !(1..=4).contains(&item.vectorization) {
+            self.unsupported(format!(
+                "Vectors of {} elements aren't supported",
+                item.vectorization
+            ));
+        } else if item.vectorization > 1 && elem.is_atomic() {
+            self.unsupported("Vectors of atomics aren't supported".to_string());
+        }
+
+        Item::new(elem, item.vectorization)
+    }
+
+    fn compile_elem(&mut self, elem: cube::Elem) -> Elem {
+        match elem {
+            cube::Elem::Float(kind) => match kind {
+                cube::FloatKind::F16 => Elem::F16,
+                cube::FloatKind::BF16 => Elem::BF16,
+                cube::FloatKind::F32 => Elem::F32,
+                cube::FloatKind::F64 => {
+                    self.unsupported("Metal doesn't support f64".to_string());
+                    Elem::F32
+                }
+            },
+            cube::Elem::Int(kind) => match kind {
+                cube::IntKind::I8 => Elem::I8,
+                cube::IntKind::I16 => Elem::I16,
+                cube::IntKind::I32 => Elem::I32,
+                cube::IntKind::I64 => Elem::I64,
+            },
+            cube::Elem::UInt(kind) => match kind {
+                cube::UIntKind::U8 => Elem::U8,
+                cube::UIntKind::U16 => Elem::U16,
+                cube::UIntKind::U32 => Elem::U32,
+                cube::UIntKind::U64 => Elem::U64,
+            },
+            cube::Elem::AtomicInt(kind) => match kind {
+                cube::IntKind::I32 => Elem::AtomicI32,
+                _ => {
+                    self.unsupported(format!("atomic<{elem}> isn't supported by Metal"));
+                    Elem::AtomicI32
+                }
+            },
+            cube::Elem::AtomicUInt => Elem::AtomicU32,
+            cube::Elem::AtomicFloat(_) => {
+                self.unsupported(format!("{elem} isn't supported by Metal"));
+                Elem::AtomicU32
+            }
+            cube::Elem::Bool => Elem::Bool,
+        }
+    }
+
+    fn unsupported(&mut self, reason: String) {
+        self.fail(ComputeError::UnsupportedFeature(reason));
+    }
+
+    fn fail(&mut self, error: ComputeError) {
+        // Only the first error is kept, the following ones are often a consequence of it.
+        self.error.get_or_insert(error);
+    }
+}
+
+fn has_length(var: &cube::Variable) -> bool {
+    matches!(
+        var,
+        cube::Variable::GlobalInputArray { .. }
+            | cube::Variable::GlobalOutputArray { .. }
+            | cube::Variable::Slice { .. }
+    )
+}
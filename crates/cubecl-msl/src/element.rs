@@ -0,0 +1,303 @@
+use cubecl_core::ir::{self as cube, ConstantScalarValue};
+use std::fmt::Display;
+
+use crate::Matrix;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Elem {
+    F16,
+    BF16,
+    F32,
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    Bool,
+    AtomicI32,
+    AtomicU32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Item {
+    pub elem: Elem,
+    pub vectorization: u8,
+}
+
+/// The builtin values of a kernel, declared as parameters of the kernel with the attribute of the
+/// same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Builtin {
+    ThreadIndexInThreadgroup,
+    ThreadPositionInThreadgroup,
+    ThreadgroupPositionInGrid,
+    ThreadsPerThreadgroup,
+    ThreadgroupsPerGrid,
+    ThreadPositionInGrid,
+    ThreadsPerGrid,
+    ThreadsPerSimdgroup,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Variable {
+    GlobalInputArray(u16, Item),
+    GlobalOutputArray(u16, Item),
+    GlobalScalar(u16, Elem, cube::Elem),
+    ConstantScalar(ConstantScalarValue, Elem),
+    Local {
+        id: u16,
+        item: Item,
+        depth: u8,
+    },
+    LocalScalar {
+        id: u16,
+        elem: Elem,
+        depth: u8,
+    },
+    Slice {
+        id: u16,
+        item: Item,
+        depth: u8,
+    },
+    SharedMemory(u16, Item, u32),
+    LocalArray(u16, Item, u8, u32),
+    Matrix {
+        id: u16,
+        mat: Matrix,
+        depth: u8,
+    },
+    Builtin(Builtin),
+    /// A component of a builtin vector.
+    BuiltinAxis(Builtin, char),
+    /// The values computed from the builtins at the start of the body.
+    AbsolutePos,
+    CubePos,
+    CubeDim,
+    CubeCount,
+    Rank,
+}
+
+impl Elem {
+    pub fn size(&self) -> usize {
+        match self {
+            Elem::F16 | Elem::BF16 => core::mem::size_of::<half::f16>(),
+            Elem::F32 => core::mem::size_of::<f32>(),
+            Elem::I8 => core::mem::size_of::<i8>(),
+            Elem::I16 => core::mem::size_of::<i16>(),
+            Elem::I32 | Elem::AtomicI32 => core::mem::size_of::<i32>(),
+            Elem::I64 => core::mem::size_of::<i64>(),
+            Elem::U8 => core::mem::size_of::<u8>(),
+            Elem::U16 => core::mem::size_of::<u16>(),
+            Elem::U32 | Elem::AtomicU32 => core::mem::size_of::<u32>(),
+            Elem::U64 => core::mem::size_of::<u64>(),
+            Elem::Bool => core::mem::size_of::<bool>(),
+        }
+    }
+
+    pub fn is_atomic(&self) -> bool {
+        matches!(self, Elem::AtomicI32 | Elem::AtomicU32)
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, Elem::F16 | Elem::BF16 | Elem::F32)
+    }
+
+    /// The element stored by an atomic.
+    pub fn value(&self) -> Elem {
+        match self {
+            Elem::AtomicI32 => Elem::I32,
+            Elem::AtomicU32 => Elem::U32,
+            elem => *elem,
+        }
+    }
+}
+
+impl Item {
+    pub fn new(elem: Elem, vectorization: u8) -> Self {
+        Self {
+            elem,
+            vectorization,
+        }
+    }
+
+    pub fn scalar(elem: Elem) -> Self {
+        Self::new(elem, 1)
+    }
+}
+
+impl Builtin {
+    pub fn ty(&self) -> &'static str {
+        match self {
+            Builtin::ThreadIndexInThreadgroup | Builtin::ThreadsPerSimdgroup => "uint",
+            _ => "uint3",
+        }
+    }
+}
+
+impl Variable {
+    pub fn item(&self) -> Item {
+        match self {
+            Variable::GlobalInputArray(_, item) => *item,
+            Variable::GlobalOutputArray(_, item) => *item,
+            Variable::GlobalScalar(_, elem, _) => Item::scalar(*elem),
+            Variable::ConstantScalar(_, elem) => Item::scalar(*elem),
+            Variable::Local { item, .. } => *item,
+            Variable::LocalScalar { elem, .. } => Item::scalar(*elem),
+            Variable::Slice { item, .. } => *item,
+            Variable::SharedMemory(_, item, _) => *item,
+            Variable::LocalArray(_, item, _, _) => *item,
+            Variable::Matrix { mat, .. } => Item::scalar(mat.elem),
+            Variable::Builtin(builtin) => match builtin.ty() {
+                "uint" => Item::scalar(Elem::U32),
+                _ => Item::new(Elem::U32, 3),
+            },
+            Variable::BuiltinAxis(..)
+            | Variable::AbsolutePos
+            | Variable::CubePos
+            | Variable::CubeDim
+            | Variable::CubeCount
+            | Variable::Rank => Item::scalar(Elem::U32),
+        }
+    }
+
+    pub fn elem(&self) -> Elem {
+        self.item().elem
+    }
+
+    /// Whether the variable is a pointer to many values, which is indexed to read a single one.
+    pub fn is_array(&self) -> bool {
+        matches!(
+            self,
+            Variable::GlobalInputArray(..)
+                | Variable::GlobalOutputArray(..)
+                | Variable::Slice { .. }
+                | Variable::SharedMemory(..)
+                | Variable::LocalArray(..)
+        )
+    }
+
+    /// The item of a value read by indexing the variable.
+    pub fn indexed_item(&self) -> Item {
+        match self.is_array() {
+            true => self.item(),
+            false => Item::scalar(self.elem()),
+        }
+    }
+}
+
+impl Display for Elem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Elem::F16 => f.write_str("half"),
+            Elem::BF16 => f.write_str("bfloat"),
+            Elem::F32 => f.write_str("float"),
+            Elem::I8 => f.write_str("char"),
+            Elem::I16 => f.write_str("short"),
+            Elem::I32 => f.write_str("int"),
+            Elem::I64 => f.write_str("long"),
+            Elem::U8 => f.write_str("uchar"),
+            Elem::U16 => f.write_str("ushort"),
+            Elem::U32 => f.write_str("uint"),
+            Elem::U64 => f.write_str("ulong"),
+            Elem::Bool => f.write_str("bool"),
+            Elem::AtomicI32 => f.write_str("atomic_int"),
+            Elem::AtomicU32 => f.write_str("atomic_uint"),
+        }
+    }
+}
+
+impl Display for Item {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.vectorization {
+            1 => f.write_fmt(format_args!("{}", self.elem)),
+            size => f.write_fmt(format_args!("{}{size}", self.elem)),
+        }
+    }
+}
+
+impl Display for Builtin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Builtin::ThreadIndexInThreadgroup => f.write_str("thread_index_in_threadgroup"),
+            Builtin::ThreadPositionInThreadgroup => f.write_str("thread_position_in_threadgroup"),
+            Builtin::ThreadgroupPositionInGrid => f.write_str("threadgroup_position_in_grid"),
+            Builtin::ThreadsPerThreadgroup => f.write_str("threads_per_threadgroup"),
+            Builtin::ThreadgroupsPerGrid => f.write_str("threadgroups_per_grid"),
+            Builtin::ThreadPositionInGrid => f.write_str("thread_position_in_grid"),
+            Builtin::ThreadsPerGrid => f.write_str("threads_per_grid"),
+            Builtin::ThreadsPerSimdgroup => f.write_str("threads_per_simdgroup"),
+        }
+    }
+}
+
+impl Display for Variable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Variable::GlobalInputArray(id, _) => f.write_fmt(format_args!("input_{id}")),
+            Variable::GlobalOutputArray(id, _) => f.write_fmt(format_args!("output_{id}")),
+            Variable::GlobalScalar(id, _, elem) => {
+                f.write_fmt(format_args!("scalars_{elem}[{id}]"))
+            }
+            Variable::ConstantScalar(value, elem) => format_constant(f, value, elem),
+            Variable::Local { id, depth, .. } => f.write_fmt(format_args!("l_{depth}_{id}")),
+            Variable::LocalScalar { id, depth, .. } => f.write_fmt(format_args!("s_{depth}_{id}")),
+            Variable::Slice { id, depth, .. } => f.write_fmt(format_args!("slice_{depth}_{id}")),
+            Variable::SharedMemory(id, _, _) => f.write_fmt(format_args!("shared_memory_{id}")),
+            Variable::LocalArray(id, _, depth, _) => {
+                f.write_fmt(format_args!("l_arr_{id}_{depth}"))
+            }
+            Variable::Matrix { id, depth, .. } => f.write_fmt(format_args!("mat_{id}_{depth}")),
+            Variable::Builtin(builtin) => f.write_fmt(format_args!("{builtin}")),
+            Variable::BuiltinAxis(builtin, axis) => f.write_fmt(format_args!("{builtin}.{axis}")),
+            Variable::AbsolutePos => f.write_str("absolute_pos"),
+            Variable::CubePos => f.write_str("cube_pos"),
+            Variable::CubeDim => f.write_str("cube_dim"),
+            Variable::CubeCount => f.write_str("cube_count"),
+            Variable::Rank => f.write_str("rank"),
+        }
+    }
+}
+
+// The conversion is done in Rust and the number is rendered, to avoid overflow or other precision
+// related problems.
+fn format_constant(
+    f: &mut std::fmt::Formatter<'_>,
+    value: &ConstantScalarValue,
+    elem: &Elem,
+) -> std::fmt::Result {
+    match value {
+        ConstantScalarValue::Int(val, kind) => match kind {
+            cube::IntKind::I8 => f.write_fmt(format_args!("{elem}({})", *val as i8)),
+            cube::IntKind::I16 => f.write_fmt(format_args!("{elem}({})", *val as i16)),
+            cube::IntKind::I32 => f.write_fmt(format_args!("{elem}({})", *val as i32)),
+            cube::IntKind::I64 => f.write_fmt(format_args!("{elem}({val})")),
+        },
+        ConstantScalarValue::UInt(val, kind) => match kind {
+            cube::UIntKind::U8 => f.write_fmt(format_args!("{elem}({})", *val as u8)),
+            cube::UIntKind::U16 => f.write_fmt(format_args!("{elem}({})", *val as u16)),
+            cube::UIntKind::U32 => f.write_fmt(format_args!("{elem}({})", *val as u32)),
+            cube::UIntKind::U64 => f.write_fmt(format_args!("{elem}({val})")),
+        },
+        ConstantScalarValue::Float(val, kind) => {
+            let val = match kind {
+                cube::FloatKind::F16 => half::f16::from_f64(*val).to_f64(),
+                cube::FloatKind::BF16 => half::bf16::from_f64(*val).to_f64(),
+                _ => *val as f32 as f64,
+            };
+
+            if val.is_nan() {
+                f.write_fmt(format_args!("{elem}(NAN)"))
+            } else if val.is_infinite() && val > 0.0 {
+                f.write_fmt(format_args!("{elem}(INFINITY)"))
+            } else if val.is_infinite() {
+                f.write_fmt(format_args!("{elem}(-INFINITY)"))
+            } else {
+                f.write_fmt(format_args!("{elem}({:?})", val as f32))
+            }
+        }
+        ConstantScalarValue::Bool(val) => f.write_fmt(format_args!("{val}")),
+    }
+}
@@ -0,0 +1,95 @@
+use std::fmt::Display;
+
+use crate::Item;
+
+/// Not all functions are native to MSL, so this struct allows to support more functions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Extension {
+    Erf(Item),
+    Powf(Item),
+}
+
+impl Display for Extension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Extension::Erf(item) => format_erf(f, item),
+            Extension::Powf(item) => format_powf(f, item),
+        }
+    }
+}
+
+fn format_erf(f: &mut std::fmt::Formatter<'_>, item: &Item) -> std::fmt::Result {
+    if item.vectorization > 1 {
+        return format_componentwise(f, item, "erf", &["x"]);
+    }
+
+    f.write_fmt(format_args!(
+        "
+// An approximation of the error function: https://en.wikipedia.org/wiki/Error_function#Numerical_approximations
+// with a maximum error of 1.5×10−7, valid for x ≥ 0 and extended with erf(x) = −erf(−x).
+{item} erf({item} x) {{
+    float t = 1.0 / (1.0 + 0.3275911 * abs(float(x)));
+    float tmp = ((((1.061405429 * t - 1.453152027) * t) + 1.421413741) * t - 0.284496736) * t + 0.254829592;
+    float result = 1.0 - (tmp * t * exp(-float(x) * float(x)));
+    return {item}(x < 0.0 ? -result : result);
+}}
+"
+    ))
+}
+
+fn format_powf(f: &mut std::fmt::Formatter<'_>, item: &Item) -> std::fmt::Result {
+    if item.vectorization > 1 {
+        return format_componentwise(f, item, "powf", &["lhs", "rhs"]);
+    }
+
+    f.write_fmt(format_args!(
+        "
+// The builtin pow function is undefined for a negative base, even when the exponent is an integer.
+{item} powf({item} lhs, {item} rhs) {{
+    if (rhs == 0.0) {{
+        return 1.0;
+    }}
+    {item} modulo = fmod(rhs, {item}(2.0));
+    if (modulo == 0.0) {{
+        return pow(abs(lhs), rhs);
+    }} else if (abs(modulo) == 1.0 && lhs < 0.0) {{
+        return -pow(-lhs, rhs);
+    }}
+    return pow(lhs, rhs);
+}}
+"
+    ))
+}
+
+/// Apply the scalar extension to each component of a vector.
+fn format_componentwise(
+    f: &mut std::fmt::Formatter<'_>,
+    item: &Item,
+    name: &str,
+    args: &[&str],
+) -> std::fmt::Result {
+    let params = args
+        .iter()
+        .map(|arg| format!("{item} {arg}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let components = (0..item.vectorization)
+        .map(|i| {
+            let args = args
+                .iter()
+                .map(|arg| format!("{arg}[{i}]"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("{name}({args})")
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    f.write_fmt(format_args!(
+        "
+{item} {name}({params}) {{
+    return {item}({components});
+}}
+"
+    ))
+}
@@ -0,0 +1,226 @@
+use std::collections::BTreeSet;
+use std::fmt::Display;
+
+use cubecl_core::{ir::CubeDim, CompilerRepresentation};
+
+use crate::{Builtin, Extension, Instruction, Item, Variable};
+
+/// The name of the kernel function in the generated library.
+pub const ENTRY_POINT: &str = "kernel_main";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Binding {
+    pub name: String,
+    pub item: Item,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedMemory {
+    pub index: u16,
+    pub item: Item,
+    pub size: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalArray {
+    pub index: u16,
+    pub item: Item,
+    pub depth: u8,
+    pub size: u32,
+}
+
+/// A body is composed of a list of [instructions](Instruction), preceded by the values computed
+/// from the builtins and the arrays it declares.
+#[derive(Debug, Clone, Default)]
+pub struct Body {
+    pub instructions: Vec<Instruction>,
+    pub shared_memories: Vec<SharedMemory>,
+    pub local_arrays: Vec<LocalArray>,
+    pub absolute_pos: bool,
+    pub cube_pos: bool,
+    pub cube_dim: bool,
+    pub cube_count: bool,
+    pub rank: bool,
+    pub stride_or_shape: bool,
+}
+
+/// A function called by the kernel.
+///
+/// The inputs are declared as parameters, followed by all the bindings of the kernel and the
+/// builtins used by the function, which aren't visible outside of the kernel.
+#[derive(Debug, Clone)]
+pub struct Function {
+    pub name: String,
+    pub inputs: Vec<Variable>,
+    pub output: Option<Variable>,
+    pub builtins: BTreeSet<Builtin>,
+    pub body: Body,
+}
+
+#[derive(Debug, Clone)]
+pub struct ComputeKernel {
+    pub inputs: Vec<Binding>,
+    pub outputs: Vec<Binding>,
+    pub named: Vec<Binding>,
+    pub cube_dim: CubeDim,
+    pub builtins: BTreeSet<Builtin>,
+    pub extensions: Vec<Extension>,
+    pub functions: Vec<Function>,
+    pub body: Body,
+    pub matrices: bool,
+}
+
+impl CompilerRepresentation for ComputeKernel {
+    fn shared_memory_size(&self) -> usize {
+        self.body
+            .shared_memories
+            .iter()
+            .map(|shared| {
+                shared.size as usize * shared.item.vectorization as usize * shared.item.elem.size()
+            })
+            .sum()
+    }
+}
+
+impl ComputeKernel {
+    fn bindings(&self) -> impl Iterator<Item = &Binding> {
+        self.inputs
+            .iter()
+            .chain(self.outputs.iter())
+            .chain(self.named.iter())
+    }
+}
+
+impl Display for ComputeKernel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("#include <metal_stdlib>\n")?;
+        if self.matrices {
+            f.write_str("#include <metal_simdgroup_matrix>\n")?;
+        }
+        f.write_str("using namespace metal;\n")?;
+
+        for extension in self.extensions.iter() {
+            f.write_fmt(format_args!("{extension}"))?;
+        }
+
+        for function in self.functions.iter() {
+            let output = match &function.output {
+                Some(output) => output.item().to_string(),
+                None => "void".to_string(),
+            };
+            let params = function
+                .inputs
+                .iter()
+                .map(|input| format!("{} {input}", input.item()))
+                .chain(
+                    self.bindings()
+                        .map(|binding| format!("device {}* {}", binding.item, binding.name)),
+                )
+                .chain(
+                    function
+                        .builtins
+                        .iter()
+                        .map(|builtin| format!("{} {builtin}", builtin.ty())),
+                )
+                .collect::<Vec<_>>();
+
+            f.write_fmt(format_args!(
+                "\n{output} {}({}) {{\n{}",
+                function.name,
+                params.join(", "),
+                function.body
+            ))?;
+
+            if let Some(output) = &function.output {
+                f.write_fmt(format_args!("return {output};\n"))?;
+            }
+
+            f.write_str("}\n")?;
+        }
+
+        let params = self
+            .bindings()
+            .enumerate()
+            .map(|(index, binding)| {
+                format!(
+                    "device {}* {} [[buffer({index})]]",
+                    binding.item, binding.name
+                )
+            })
+            .chain(
+                self.builtins
+                    .iter()
+                    .map(|builtin| format!("{} {builtin} [[{builtin}]]", builtin.ty())),
+            )
+            .collect::<Vec<_>>();
+        let num_threads = self.cube_dim.x * self.cube_dim.y * self.cube_dim.z;
+
+        f.write_fmt(format_args!(
+            "
+[[max_total_threads_per_threadgroup({num_threads})]]
+kernel void {ENTRY_POINT}(
+{}
+) {{
+{}}}
+",
+            params.join(",\n"),
+            self.body
+        ))
+    }
+}
+
+impl Display for Body {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.absolute_pos {
+            f.write_str(
+                "uint absolute_pos = (thread_position_in_grid.z * threads_per_grid.x * threads_per_grid.y) + (thread_position_in_grid.y * threads_per_grid.x) + thread_position_in_grid.x;\n",
+            )?;
+        }
+
+        if self.cube_pos {
+            f.write_str(
+                "uint cube_pos = (threadgroup_position_in_grid.z * threadgroups_per_grid.x * threadgroups_per_grid.y) + (threadgroup_position_in_grid.y * threadgroups_per_grid.x) + threadgroup_position_in_grid.x;\n",
+            )?;
+        }
+
+        if self.cube_dim {
+            f.write_str(
+                "uint cube_dim = threads_per_threadgroup.x * threads_per_threadgroup.y * threads_per_threadgroup.z;\n",
+            )?;
+        }
+
+        if self.cube_count {
+            f.write_str(
+                "uint cube_count = threadgroups_per_grid.x * threadgroups_per_grid.y * threadgroups_per_grid.z;\n",
+            )?;
+        }
+
+        if self.rank || self.stride_or_shape {
+            f.write_str("uint rank = info[0];\n")?;
+        }
+
+        if self.stride_or_shape {
+            f.write_str("uint rank_2 = rank * 2;\n")?;
+        }
+
+        for shared in self.shared_memories.iter() {
+            f.write_fmt(format_args!(
+                "threadgroup {} shared_memory_{}[{}];\n",
+                shared.item, shared.index, shared.size
+            ))?;
+        }
+
+        for array in self.local_arrays.iter() {
+            f.write_fmt(format_args!(
+                "{} l_arr_{}_{}[{}];\n",
+                array.item, array.index, array.depth, array.size
+            ))?;
+        }
+
+        for instruction in self.instructions.iter() {
+            f.write_fmt(format_args!("{instruction}"))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+mod compiler;
+mod element;
+mod extension;
+mod instruction;
+mod kernel;
+mod matrix;
+mod subcube;
+
+pub use compiler::*;
+pub use element::*;
+pub use extension::*;
+pub use instruction::*;
+pub use kernel::*;
+pub use matrix::*;
+pub use subcube::*;
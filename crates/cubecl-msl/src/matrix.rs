@@ -0,0 +1,103 @@
+use std::fmt::Display;
+
+use crate::{Elem, Variable};
+
+/// The dimension of the square matrices of a SIMD-group.
+pub const SIMDGROUP_MATRIX_DIM: u8 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixLayout {
+    ColMajor,
+    RowMajor,
+}
+
+/// A matrix distributed over the units of a SIMD-group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Matrix {
+    pub elem: Elem,
+    pub layout: Option<MatrixLayout>,
+}
+
+/// SIMD-group matrix instruction.
+#[derive(Debug, Clone)]
+pub enum MatrixInstruction {
+    /// Fill the matrix with the value.
+    Fill { mat: Variable, value: Variable },
+    /// Load the matrix from memory, with the number of elements between the rows.
+    Load {
+        mat: Variable,
+        value: Variable,
+        stride: Variable,
+    },
+    /// Executes D=A*B+C;
+    Execute {
+        mat_a: Variable,
+        mat_b: Variable,
+        mat_c: Variable,
+        mat_d: Variable,
+    },
+    /// Store the matrix in memory following the stride and the layout.
+    Store {
+        output: Variable,
+        mat: Variable,
+        stride: Variable,
+        layout: MatrixLayout,
+    },
+}
+
+impl Display for Matrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "simdgroup_matrix<{}, {SIMDGROUP_MATRIX_DIM}, {SIMDGROUP_MATRIX_DIM}>",
+            self.elem
+        ))
+    }
+}
+
+impl Display for MatrixInstruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatrixInstruction::Fill { mat, value } => {
+                let elem = mat.elem();
+                f.write_fmt(format_args!(
+                    "{mat} = make_filled_simdgroup_matrix<{elem}, {SIMDGROUP_MATRIX_DIM}, {SIMDGROUP_MATRIX_DIM}>({value});\n"
+                ))
+            }
+            MatrixInstruction::Load { mat, value, stride } => {
+                let layout = match mat {
+                    Variable::Matrix { mat, .. } => mat.layout,
+                    _ => None,
+                };
+                f.write_fmt(format_args!(
+                    "simdgroup_load({mat}, {value}, {stride}{});\n",
+                    transpose(layout)
+                ))
+            }
+            MatrixInstruction::Execute {
+                mat_a,
+                mat_b,
+                mat_c,
+                mat_d,
+            } => f.write_fmt(format_args!(
+                "simdgroup_multiply_accumulate({mat_d}, {mat_a}, {mat_b}, {mat_c});\n"
+            )),
+            MatrixInstruction::Store {
+                output,
+                mat,
+                stride,
+                layout,
+            } => f.write_fmt(format_args!(
+                "simdgroup_store({mat}, {output}, {stride}{});\n",
+                transpose(Some(*layout))
+            )),
+        }
+    }
+}
+
+/// The matrices are stored row major in memory unless they are transposed.
+fn transpose(layout: Option<MatrixLayout>) -> &'static str {
+    match layout {
+        Some(MatrixLayout::ColMajor) => ", ulong2(0, 0), true",
+        _ => "",
+    }
+}
@@ -0,0 +1,161 @@
+use std::fmt::Display;
+
+use crate::Variable;
+
+/// The operations over the units of a SIMD-group, which is the subcube of Apple GPUs.
+#[derive(Debug, Clone)]
+pub enum Subcube {
+    Elect {
+        out: Variable,
+    },
+    All {
+        input: Variable,
+        out: Variable,
+    },
+    Any {
+        input: Variable,
+        out: Variable,
+    },
+    Broadcast {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    Sum {
+        input: Variable,
+        out: Variable,
+    },
+    Prod {
+        input: Variable,
+        out: Variable,
+    },
+    And {
+        input: Variable,
+        out: Variable,
+    },
+    Or {
+        input: Variable,
+        out: Variable,
+    },
+    Xor {
+        input: Variable,
+        out: Variable,
+    },
+    Min {
+        input: Variable,
+        out: Variable,
+    },
+    Max {
+        input: Variable,
+        out: Variable,
+    },
+    Shuffle {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    ShuffleXor {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    ShuffleUp {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    ShuffleDown {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    InclusiveSum {
+        input: Variable,
+        out: Variable,
+    },
+    ExclusiveSum {
+        input: Variable,
+        out: Variable,
+    },
+    InclusiveProd {
+        input: Variable,
+        out: Variable,
+    },
+    ExclusiveProd {
+        input: Variable,
+        out: Variable,
+    },
+    Ballot {
+        input: Variable,
+        out: Variable,
+    },
+}
+
+impl Display for Subcube {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Subcube::Elect { out } => f.write_fmt(format_args!("{out} = simd_is_first();\n")),
+            Subcube::All { input, out } => {
+                f.write_fmt(format_args!("{out} = simd_all({input});\n"))
+            }
+            Subcube::Any { input, out } => {
+                f.write_fmt(format_args!("{out} = simd_any({input});\n"))
+            }
+            Subcube::Broadcast { lhs, rhs, out } => f.write_fmt(format_args!(
+                "{out} = simd_broadcast({lhs}, ushort({rhs}));\n"
+            )),
+            Subcube::Sum { input, out } => {
+                f.write_fmt(format_args!("{out} = simd_sum({input});\n"))
+            }
+            Subcube::Prod { input, out } => {
+                f.write_fmt(format_args!("{out} = simd_product({input});\n"))
+            }
+            Subcube::And { input, out } => {
+                f.write_fmt(format_args!("{out} = simd_and({input});\n"))
+            }
+            Subcube::Or { input, out } => f.write_fmt(format_args!("{out} = simd_or({input});\n")),
+            Subcube::Xor { input, out } => {
+                f.write_fmt(format_args!("{out} = simd_xor({input});\n"))
+            }
+            Subcube::Min { input, out } => {
+                f.write_fmt(format_args!("{out} = simd_min({input});\n"))
+            }
+            Subcube::Max { input, out } => {
+                f.write_fmt(format_args!("{out} = simd_max({input});\n"))
+            }
+            Subcube::Shuffle { lhs, rhs, out } => f.write_fmt(format_args!(
+                "{out} = simd_shuffle({lhs}, ushort({rhs}));\n"
+            )),
+            Subcube::ShuffleXor { lhs, rhs, out } => f.write_fmt(format_args!(
+                "{out} = simd_shuffle_xor({lhs}, ushort({rhs}));\n"
+            )),
+            Subcube::ShuffleUp { lhs, rhs, out } => f.write_fmt(format_args!(
+                "{out} = simd_shuffle_up({lhs}, ushort({rhs}));\n"
+            )),
+            Subcube::ShuffleDown { lhs, rhs, out } => f.write_fmt(format_args!(
+                "{out} = simd_shuffle_down({lhs}, ushort({rhs}));\n"
+            )),
+            Subcube::InclusiveSum { input, out } => f.write_fmt(format_args!(
+                "{out} = simd_prefix_inclusive_sum({input});\n"
+            )),
+            Subcube::ExclusiveSum { input, out } => f.write_fmt(format_args!(
+                "{out} = simd_prefix_exclusive_sum({input});\n"
+            )),
+            Subcube::InclusiveProd { input, out } => f.write_fmt(format_args!(
+                "{out} = simd_prefix_inclusive_product({input});\n"
+            )),
+            Subcube::ExclusiveProd { input, out } => f.write_fmt(format_args!(
+                "{out} = simd_prefix_exclusive_product({input});\n"
+            )),
+            // The vote of a SIMD-group has 64 bits, one for each unit.
+            Subcube::Ballot { input, out } => f.write_fmt(format_args!(
+                "
+{{
+    ulong ballot = static_cast<ulong>(static_cast<simd_vote::vote_t>(simd_ballot({input})));
+    {out} = uint4(uint(ballot), uint(ballot >> 32), 0, 0);
+}}
+"
+            )),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use cubecl_core as cubecl;
+use cubecl_core::{
+    assert_golden,
+    compiler_tests::{self, compile, float},
+    ir::FloatKind,
+    prelude::*,
+    Compiler,
+};
+use cubecl_msl::MslCompiler;
+use cubecl_runtime::ExecutionMode;
+
+#[cube]
+pub fn golden_cmma_kernel(lhs: &Array<F16>, rhs: &Array<F16>, out: &mut Array<F32>) {
+    let a = cmma::Matrix::<F16>::new(cmma::MatrixIdent::A, 8, 8, 8, cmma::MatrixLayout::RowMajor);
+    let b = cmma::Matrix::<F16>::new(cmma::MatrixIdent::B, 8, 8, 8, cmma::MatrixLayout::ColMajor);
+    let c = cmma::Matrix::<F32>::new(
+        cmma::MatrixIdent::Accumulator,
+        8,
+        8,
+        8,
+        cmma::MatrixLayout::Undefined,
+    );
+    cmma::fill::<F32>(&c, F32::new(0.0));
+    cmma::load::<F16>(&a, lhs.as_slice(), UInt::new(8));
+    cmma::load::<F16>(&b, rhs.as_slice(), UInt::new(8));
+
+    cmma::execute::<F16, F16, F32, F32>(&a, &b, &c, &c);
+
+    cmma::store::<F32>(
+        out.as_slice_mut(),
+        &c,
+        UInt::new(8),
+        cmma::MatrixLayout::RowMajor,
+    );
+}
+
+#[cube]
+pub fn golden_unsupported_cmma_kernel(out: &mut Array<F32>) {
+    let c = cmma::Matrix::<F32>::new(
+        cmma::MatrixIdent::Accumulator,
+        16,
+        16,
+        16,
+        cmma::MatrixLayout::Undefined,
+    );
+    cmma::fill::<F32>(&c, F32::new(0.0));
+    cmma::store::<F32>(
+        out.as_slice_mut(),
+        &c,
+        UInt::new(16),
+        cmma::MatrixLayout::RowMajor,
+    );
+}
+
+#[test]
+fn golden_kernel_test() {
+    assert_golden!(
+        "kernel.metal",
+        compile::<MslCompiler>(compiler_tests::kernel())
+    );
+}
+
+#[test]
+fn golden_vectorized_test() {
+    assert_golden!(
+        "vectorized.metal",
+        compile::<MslCompiler>(compiler_tests::vectorized())
+    );
+}
+
+#[test]
+fn golden_subcube_test() {
+    let source = compile::<MslCompiler>(compiler_tests::subcube());
+
+    assert!(source.contains("simd_sum("));
+    assert_golden!("subcube.metal", source);
+}
+
+#[test]
+fn golden_subcube_shuffle_test() {
+    let source = compile::<MslCompiler>(compiler_tests::subcube_shuffle());
+
+    assert!(source.contains("simd_shuffle_xor("));
+    assert_golden!("subcube_shuffle.metal", source);
+}
+
+#[test]
+fn golden_cmma_test() {
+    let mut builder = KernelBuilder::default();
+    let lhs = builder.input_array(float(FloatKind::F16));
+    let rhs = builder.input_array(float(FloatKind::F16));
+    let out = builder.output_array(float(FloatKind::F32));
+
+    golden_cmma_kernel::__expand(&mut builder.context, lhs.into(), rhs.into(), out.into());
+    let kernel = builder.build(KernelSettings::default());
+
+    assert_golden!("cmma.metal", compile::<MslCompiler>(kernel));
+}
+
+#[test]
+fn unsupported_cmma_test() {
+    let mut builder = KernelBuilder::default();
+    let out = builder.output_array(float(FloatKind::F32));
+
+    golden_unsupported_cmma_kernel::__expand(&mut builder.context, out.into());
+    let kernel = builder.build(KernelSettings::default());
+
+    // SIMD-group matrices are always 8x8.
+    assert!(MslCompiler::try_compile(kernel, ExecutionMode::Checked).is_err());
+}
@@ -0,0 +1,20 @@
+#include <metal_stdlib>
+#include <metal_simdgroup_matrix>
+using namespace metal;
+
+[[max_total_threads_per_threadgroup(256)]]
+kernel void kernel_main(
+device half* input_0 [[buffer(0)]],
+device half* input_1 [[buffer(1)]],
+device float* output_0 [[buffer(2)]],
+device uint* info [[buffer(3)]]
+) {
+simdgroup_matrix<half, 8, 8> mat_0_0;
+simdgroup_matrix<half, 8, 8> mat_1_0;
+simdgroup_matrix<float, 8, 8> mat_2_0;
+mat_2_0 = make_filled_simdgroup_matrix<float, 8, 8>(float(0.0));
+simdgroup_load(mat_0_0, input_0, uint(8));
+simdgroup_load(mat_1_0, input_1, uint(8), ulong2(0, 0), true);
+simdgroup_multiply_accumulate(mat_2_0, mat_0_0, mat_1_0, mat_2_0);
+simdgroup_store(mat_2_0, output_0, uint(8));
+}
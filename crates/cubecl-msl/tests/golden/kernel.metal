@@ -0,0 +1,57 @@
+#include <metal_stdlib>
+using namespace metal;
+
+float golden_scale_0(float l_0_0, device float* input_0, device float* output_0, device uint* info) {
+float l_0_1;
+l_0_1 = l_0_0 * float(2.0);
+return l_0_1;
+}
+
+[[max_total_threads_per_threadgroup(256)]]
+kernel void kernel_main(
+device float* input_0 [[buffer(0)]],
+device float* output_0 [[buffer(1)]],
+device uint* info [[buffer(2)]],
+uint thread_index_in_threadgroup [[thread_index_in_threadgroup]]
+) {
+float l_0_0;
+uint l_0_1;
+float l_0_2;
+bool l_0_3;
+l_0_0 = float(0.0);
+l_0_1 = info[(2 * 2 * info[0]) + 1];
+for (uint l_1_0 = uint(0); l_1_0 < l_0_1; ++l_1_0) {
+uint l_1_1;
+bool l_1_2;
+l_1_1 = info[(2 * 2 * info[0]) + 1];
+l_1_2 = l_1_0 < l_1_1;
+if (l_1_2) {
+l_0_2 = input_0[l_1_0];
+} else {
+l_0_2 = float(0.0);
+}
+l_0_3 = l_0_2 > float(1.0);
+if (l_0_3) {
+uint l_2_0;
+bool l_2_1;
+l_2_0 = info[(2 * 2 * info[0]) + 1];
+l_2_1 = l_1_0 < l_2_0;
+if (l_2_1) {
+l_0_2 = input_0[l_1_0];
+} else {
+l_0_2 = float(0.0);
+}
+l_0_2 = golden_scale_0(l_0_2, input_0, output_0, info);
+l_0_0 = l_0_0 + l_0_2;
+} else {
+l_0_0 = l_0_0 - float(1e-7);
+}
+}
+uint l_0_4;
+bool l_0_5;
+l_0_4 = info[(2 * 2 * info[0]) + 2];
+l_0_5 = thread_index_in_threadgroup < l_0_4;
+if (l_0_5) {
+output_0[thread_index_in_threadgroup] = l_0_0;
+}
+}
@@ -0,0 +1,37 @@
+#include <metal_stdlib>
+using namespace metal;
+
+[[max_total_threads_per_threadgroup(256)]]
+kernel void kernel_main(
+device float* output_0 [[buffer(0)]],
+device uint* info [[buffer(1)]],
+uint thread_index_in_threadgroup [[thread_index_in_threadgroup]],
+uint threads_per_simdgroup [[threads_per_simdgroup]]
+) {
+float l_0_0;
+float l_0_1;
+float l_0_2;
+bool l_0_3;
+uint l_0_4;
+bool l_0_5;
+l_0_4 = info[(1 * 2 * info[0]) + 1];
+l_0_5 = thread_index_in_threadgroup < l_0_4;
+if (l_0_5) {
+l_0_0 = output_0[thread_index_in_threadgroup];
+} else {
+l_0_0 = float(0.0);
+}
+l_0_1 = simd_sum(l_0_0);
+l_0_2 = simd_max(l_0_0);
+l_0_3 = thread_index_in_threadgroup < threads_per_simdgroup;
+if (l_0_3) {
+l_0_0 = l_0_1 + l_0_2;
+uint l_1_0;
+bool l_1_1;
+l_1_0 = info[(1 * 2 * info[0]) + 1];
+l_1_1 = thread_index_in_threadgroup < l_1_0;
+if (l_1_1) {
+output_0[thread_index_in_threadgroup] = l_0_0;
+}
+}
+}
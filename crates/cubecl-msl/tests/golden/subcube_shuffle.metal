@@ -0,0 +1,51 @@
+#include <metal_stdlib>
+using namespace metal;
+
+[[max_total_threads_per_threadgroup(256)]]
+kernel void kernel_main(
+device float* output_0 [[buffer(0)]],
+device uint4* output_1 [[buffer(1)]],
+device uint* info [[buffer(2)]],
+uint thread_index_in_threadgroup [[thread_index_in_threadgroup]]
+) {
+float l_0_0;
+float l_0_1;
+float l_0_2;
+float l_0_3;
+bool l_0_4;
+uint4 l_0_5;
+uint l_0_6;
+bool l_0_7;
+l_0_6 = info[(2 * 2 * info[0]) + 1];
+l_0_7 = thread_index_in_threadgroup < l_0_6;
+if (l_0_7) {
+l_0_0 = output_0[thread_index_in_threadgroup];
+} else {
+l_0_0 = float(0.0);
+}
+l_0_1 = simd_shuffle_xor(l_0_0, ushort(uint(1)));
+l_0_2 = simd_shuffle_down(l_0_0, ushort(uint(1)));
+l_0_3 = simd_prefix_exclusive_sum(l_0_0);
+l_0_1 = l_0_1 + l_0_2;
+l_0_1 = l_0_1 + l_0_3;
+uint l_0_8;
+bool l_0_9;
+l_0_8 = info[(2 * 2 * info[0]) + 1];
+l_0_9 = thread_index_in_threadgroup < l_0_8;
+if (l_0_9) {
+output_0[thread_index_in_threadgroup] = l_0_1;
+}
+l_0_4 = l_0_0 > float(0.0);
+
+{
+    ulong ballot = static_cast<ulong>(static_cast<simd_vote::vote_t>(simd_ballot(l_0_4)));
+    l_0_5 = uint4(uint(ballot), uint(ballot >> 32), 0, 0);
+}
+uint l_0_10;
+bool l_0_11;
+l_0_10 = info[(2 * 2 * info[0]) + 2] / 4;
+l_0_11 = thread_index_in_threadgroup < l_0_10;
+if (l_0_11) {
+output_1[thread_index_in_threadgroup] = l_0_5;
+}
+}
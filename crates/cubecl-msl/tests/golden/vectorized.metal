@@ -0,0 +1,91 @@
+#include <metal_stdlib>
+using namespace metal;
+
+// An approximation of the error function: https://en.wikipedia.org/wiki/Error_function#Numerical_approximations
+// with a maximum error of 1.5×10−7, valid for x ≥ 0 and extended with erf(x) = −erf(−x).
+float erf(float x) {
+    float t = 1.0 / (1.0 + 0.3275911 * abs(float(x)));
+    float tmp = ((((1.061405429 * t - 1.453152027) * t) + 1.421413741) * t - 0.284496736) * t + 0.254829592;
+    float result = 1.0 - (tmp * t * exp(-float(x) * float(x)));
+    return float(x < 0.0 ? -result : result);
+}
+
+float4 erf(float4 x) {
+    return float4(erf(x[0]), erf(x[1]), erf(x[2]), erf(x[3]));
+}
+
+// The builtin pow function is undefined for a negative base, even when the exponent is an integer.
+float powf(float lhs, float rhs) {
+    if (rhs == 0.0) {
+        return 1.0;
+    }
+    float modulo = fmod(rhs, float(2.0));
+    if (modulo == 0.0) {
+        return pow(abs(lhs), rhs);
+    } else if (abs(modulo) == 1.0 && lhs < 0.0) {
+        return -pow(-lhs, rhs);
+    }
+    return pow(lhs, rhs);
+}
+
+float4 powf(float4 lhs, float4 rhs) {
+    return float4(powf(lhs[0], rhs[0]), powf(lhs[1], rhs[1]), powf(lhs[2], rhs[2]), powf(lhs[3], rhs[3]));
+}
+
+[[max_total_threads_per_threadgroup(256)]]
+kernel void kernel_main(
+device float4* input_0 [[buffer(0)]],
+device float4* input_1 [[buffer(1)]],
+device float4* output_0 [[buffer(2)]],
+device uint* info [[buffer(3)]],
+uint3 thread_position_in_grid [[thread_position_in_grid]],
+uint3 threads_per_grid [[threads_per_grid]]
+) {
+uint absolute_pos = (thread_position_in_grid.z * threads_per_grid.x * threads_per_grid.y) + (thread_position_in_grid.y * threads_per_grid.x) + thread_position_in_grid.x;
+uint l_0_0;
+bool l_0_1;
+float4 l_0_2;
+float4 l_0_3;
+float4 l_0_4;
+l_0_0 = info[(3 * 2 * info[0]) + 3] / 4;
+l_0_1 = absolute_pos < l_0_0;
+if (l_0_1) {
+uint l_1_0;
+bool l_1_1;
+l_1_0 = info[(3 * 2 * info[0]) + 1] / 4;
+l_1_1 = absolute_pos < l_1_0;
+if (l_1_1) {
+l_0_2 = input_0[absolute_pos];
+} else {
+l_0_2 = float4(float(0.0));
+}
+l_0_2 = erf(l_0_2);
+uint l_1_2;
+bool l_1_3;
+l_1_2 = info[(3 * 2 * info[0]) + 1] / 4;
+l_1_3 = absolute_pos < l_1_2;
+if (l_1_3) {
+l_0_3 = input_0[absolute_pos];
+} else {
+l_0_3 = float4(float(0.0));
+}
+uint l_1_4;
+bool l_1_5;
+l_1_4 = info[(3 * 2 * info[0]) + 2] / 4;
+l_1_5 = absolute_pos < l_1_4;
+if (l_1_5) {
+l_0_4 = input_1[absolute_pos];
+} else {
+l_0_4 = float4(float(0.0));
+}
+l_0_3 = powf(l_0_3, l_0_4);
+l_0_2 = l_0_2 + l_0_3;
+uint l_1_6;
+bool l_1_7;
+l_1_6 = info[(3 * 2 * info[0]) + 3] / 4;
+l_1_7 = absolute_pos < l_1_6;
+if (l_1_7) {
+output_0[absolute_pos] = l_0_2;
+}
+}
+}
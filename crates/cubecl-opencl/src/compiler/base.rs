@@ -0,0 +1,950 @@
+use std::collections::HashMap;
+
+use cubecl_core::{
+    ir::{self as cube, ConstantScalarValue},
+    Compiler,
+};
+use cubecl_runtime::{server::ComputeError, ExecutionMode};
+
+use super::{
+    AddressSpace, Binding, Body, Builtin, ComputeKernel, Elem, Extension, Function, Instruction,
+    Item, LocalArray, Pragmas, SharedMemory, Subcube, Variable,
+};
+
+/// OpenCL C compiler.
+///
+/// The generated programs only require OpenCL C 1.2, with `cl_khr_subgroups` for the subcube
+/// operations, so that they can run on older accelerators and FPGAs. Cooperative matrices are
+/// [emulated](cube::KernelDefinition::emulate_cmma) with scalar loops.
+#[derive(Clone, Default)]
+pub struct OpenClCompiler {
+    mode: ExecutionMode,
+    num_inputs: usize,
+    num_outputs: usize,
+    /// The bindings of the kernel, passed to the functions.
+    bindings: Vec<Binding>,
+    /// The body being compiled, without its instructions.
+    body: Body,
+    pragmas: Pragmas,
+    extensions: Vec<Extension>,
+    /// The address space of the arrays sliced by each slice.
+    slices: HashMap<(u16, u8), AddressSpace>,
+    /// The functions compiled so far, indexed by their id.
+    functions: Vec<Function>,
+    /// The output of the function being compiled, which is returned by its return statements.
+    output: Option<Variable>,
+    /// The first error encountered during compilation.
+    ///
+    /// The compilation continues with placeholders after an error, but the resulting kernel is
+    /// discarded.
+    error: Option<ComputeError>,
+}
+
+impl core::fmt::Debug for OpenClCompiler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("OpenClCompiler")
+    }
+}
+
+impl Compiler for OpenClCompiler {
+    type Representation = ComputeKernel;
+
+    fn compile(kernel: cube::KernelDefinition, mode: ExecutionMode) -> Self::Representation {
+        Self::try_compile(kernel, mode).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    fn try_compile(
+        kernel: cube::KernelDefinition,
+        mode: ExecutionMode,
+    ) -> Result<Self::Representation, ComputeError> {
+        let mut compiler = Self {
+            mode,
+            ..Self::default()
+        };
+        let kernel = compiler.compile_kernel(kernel);
+
+        match compiler.error {
+            Some(err) => Err(err),
+            None => Ok(kernel),
+        }
+    }
+
+    fn elem_size(elem: cube::Elem) -> usize {
+        elem.size()
+    }
+
+    fn max_shared_memory_size() -> usize {
+        // The minimum local memory of the devices of the full profile.
+        32768
+    }
+
+    fn optimizations() -> cube::Optimizations {
+        cube::Optimizations::all()
+    }
+}
+
+impl OpenClCompiler {
+    fn compile_kernel(&mut self, mut value: cube::KernelDefinition) -> ComputeKernel {
+        if let Err(err) = value.emulate_cmma() {
+            self.fail(ComputeError::Compilation(err.to_string()));
+        }
+
+        self.num_inputs = value.inputs.len();
+        self.num_outputs = value.outputs.len();
+
+        let inputs = value
+            .inputs
+            .into_iter()
+            .enumerate()
+            .map(|(i, binding)| self.compile_binding(format!("input_{i}"), binding))
+            .collect::<Vec<_>>();
+        let outputs = value
+            .outputs
+            .into_iter()
+            .enumerate()
+            .map(|(i, binding)| self.compile_binding(format!("output_{i}"), binding))
+            .collect::<Vec<_>>();
+        let named = value
+            .named
+            .into_iter()
+            .map(|(name, binding)| self.compile_binding(name, binding))
+            .collect::<Vec<_>>();
+        self.bindings = inputs
+            .iter()
+            .chain(outputs.iter())
+            .chain(named.iter())
+            .cloned()
+            .collect();
+
+        // Functions only call the functions registered before them, so they are compiled in order.
+        for function in value.functions.drain(..) {
+            let function = self.compile_function(function);
+            self.functions.push(function);
+        }
+
+        let instructions = self.compile_scope(&mut value.body);
+
+        ComputeKernel {
+            inputs,
+            outputs,
+            named,
+            cube_dim: value.cube_dim,
+            pragmas: self.pragmas,
+            extensions: core::mem::take(&mut self.extensions),
+            functions: core::mem::take(&mut self.functions),
+            body: Body {
+                instructions,
+                ..core::mem::take(&mut self.body)
+            },
+        }
+    }
+
+    fn compile_function(&mut self, mut value: cube::Function) -> Function {
+        let mut compiler = Self {
+            mode: self.mode,
+            num_inputs: self.num_inputs,
+            num_outputs: self.num_outputs,
+            bindings: self.bindings.clone(),
+            extensions: core::mem::take(&mut self.extensions),
+            functions: core::mem::take(&mut self.functions),
+            ..Self::default()
+        };
+
+        compiler.output = value.output.map(|output| compiler.compile_variable(output));
+        let mut instructions = compiler.compile_scope(&mut value.scope);
+        let inputs = value
+            .inputs
+            .into_iter()
+            .map(|input| compiler.compile_variable(input))
+            .collect::<Vec<_>>();
+
+        if inputs.iter().any(|input| input.is_array()) {
+            compiler.unsupported(format!(
+                "Only values can be passed to function {}, since it isn't inlined",
+                value.name
+            ));
+        }
+        if !compiler.body.shared_memories.is_empty() {
+            compiler.unsupported(format!(
+                "Shared memories can't be declared in function {}, since it isn't inlined",
+                value.name
+            ));
+        }
+
+        // The inputs are declared as parameters of the function.
+        instructions.retain(|instruction| match instruction {
+            Instruction::DeclareVariable { var } => !inputs.contains(var),
+            _ => true,
+        });
+
+        if let Some(err) = compiler.error.take() {
+            self.fail(err);
+        }
+        self.extensions = core::mem::take(&mut compiler.extensions);
+        self.functions = core::mem::take(&mut compiler.functions);
+        self.pragmas.fp16 |= compiler.pragmas.fp16;
+        self.pragmas.fp64 |= compiler.pragmas.fp64;
+        self.pragmas.subgroups |= compiler.pragmas.subgroups;
+        self.pragmas.subgroup_arithmetic |= compiler.pragmas.subgroup_arithmetic;
+        self.pragmas.subgroup_shuffle |= compiler.pragmas.subgroup_shuffle;
+        self.pragmas.subgroup_shuffle_relative |= compiler.pragmas.subgroup_shuffle_relative;
+        self.pragmas.subgroup_ballot |= compiler.pragmas.subgroup_ballot;
+
+        Function {
+            name: format!("{}_{}", value.name, value.id),
+            inputs,
+            output: compiler.output,
+            body: Body {
+                instructions,
+                ..compiler.body
+            },
+        }
+    }
+
+    fn compile_call(&mut self, call: cube::Call) -> Instruction {
+        let function = &self.functions[call.function as usize];
+        let name = function.name.clone();
+        let args = self
+            .bindings
+            .iter()
+            .map(|binding| binding.name.clone())
+            .collect();
+
+        Instruction::Call {
+            function: name,
+            inputs: call
+                .inputs
+                .into_iter()
+                .map(|input| self.compile_variable(input))
+                .collect(),
+            output: call.output.map(|output| self.compile_variable(output)),
+            args,
+        }
+    }
+
+    fn compile_scope(&mut self, scope: &mut cube::Scope) -> Vec<Instruction> {
+        let mut instructions = Vec::new();
+        let processing = scope.process_with(&Self::optimizations());
+
+        for var in processing.variables {
+            // Slices and atomics are pointers, declared when they are assigned.
+            if let cube::Variable::Slice { .. } = var {
+                continue;
+            }
+            if var.item().elem().is_atomic() {
+                continue;
+            }
+            instructions.push(Instruction::DeclareVariable {
+                var: self.compile_variable(var),
+            });
+        }
+
+        processing
+            .operations
+            .into_iter()
+            .for_each(|op| self.compile_operation(&mut instructions, op, scope));
+
+        instructions
+    }
+
+    fn compile_operation(
+        &mut self,
+        instructions: &mut Vec<Instruction>,
+        operation: cube::Operation,
+        scope: &mut cube::Scope,
+    ) {
+        match operation {
+            cube::Operation::Operator(op) => self.compile_instruction(instructions, op, scope),
+            cube::Operation::Procedure(proc) => self.compile_procedure(instructions, proc, scope),
+            cube::Operation::Metadata(op) => instructions.push(self.compile_metadata(op)),
+            cube::Operation::Branch(val) => self.compile_branch(instructions, val),
+            cube::Operation::Synchronization(val) => match val {
+                cube::Synchronization::SyncUnits => instructions.push(Instruction::SyncLocal),
+                cube::Synchronization::SyncStorage => instructions.push(Instruction::SyncGlobal),
+            },
+            cube::Operation::Subcube(op) => {
+                self.pragmas.subgroups = true;
+                instructions.push(Instruction::Subcube(self.compile_subcube(op)))
+            }
+            cube::Operation::CoopMma(_) => {
+                unreachable!("Cooperative matrix operations are emulated before the compilation.")
+            }
+            cube::Operation::Call(call) => instructions.push(self.compile_call(call)),
+        }
+    }
+
+    fn compile_subcube(&mut self, subcube: cube::Subcube) -> Subcube {
+        match subcube {
+            cube::Subcube::Prod(_)
+            | cube::Subcube::And(_)
+            | cube::Subcube::Or(_)
+            | cube::Subcube::Xor(_)
+            | cube::Subcube::InclusiveProd(_)
+            | cube::Subcube::ExclusiveProd(_) => self.pragmas.subgroup_arithmetic = true,
+            cube::Subcube::Shuffle(_) | cube::Subcube::ShuffleXor(_) => {
+                self.pragmas.subgroup_shuffle = true
+            }
+            cube::Subcube::ShuffleUp(_) | cube::Subcube::ShuffleDown(_) => {
+                self.pragmas.subgroup_shuffle_relative = true
+            }
+            cube::Subcube::Ballot(_) => self.pragmas.subgroup_ballot = true,
+            _ => {}
+        }
+
+        match subcube {
+            cube::Subcube::Elect(op) => Subcube::Elect {
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::All(op) => Subcube::All {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Any(op) => Subcube::Any {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Broadcast(op) => Subcube::Broadcast {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Sum(op) => Subcube::Sum {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Prod(op) => Subcube::Prod {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::And(op) => Subcube::And {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Or(op) => Subcube::Or {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Xor(op) => Subcube::Xor {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Min(op) => Subcube::Min {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Max(op) => Subcube::Max {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Shuffle(op) => Subcube::Shuffle {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::ShuffleXor(op) => Subcube::ShuffleXor {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::ShuffleUp(op) => Subcube::ShuffleUp {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::ShuffleDown(op) => Subcube::ShuffleDown {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::InclusiveSum(op) => Subcube::InclusiveSum {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::ExclusiveSum(op) => Subcube::ExclusiveSum {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::InclusiveProd(op) => Subcube::InclusiveProd {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::ExclusiveProd(op) => Subcube::ExclusiveProd {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Ballot(op) => Subcube::Ballot {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+        }
+    }
+
+    fn compile_metadata(&mut self, metadata: cube::Metadata) -> Instruction {
+        match metadata {
+            cube::Metadata::Stride { dim, var, out } => {
+                self.body.stride_or_shape = true;
+                Instruction::Stride {
+                    dim: self.compile_variable(dim),
+                    position: self.position(&var),
+                    out: self.compile_variable(out),
+                }
+            }
+            cube::Metadata::Shape { dim, var, out } => {
+                self.body.stride_or_shape = true;
+                Instruction::Shape {
+                    dim: self.compile_variable(dim),
+                    position: self.position(&var),
+                    out: self.compile_variable(out),
+                }
+            }
+            cube::Metadata::Length { var, out } => Instruction::Length {
+                var: self.compile_variable(var),
+                out: self.compile_variable(out),
+                num_inputs: self.num_inputs,
+                num_outputs: self.num_outputs,
+            },
+        }
+    }
+
+    /// The position of a global array in the metadata.
+    fn position(&mut self, var: &cube::Variable) -> usize {
+        match var {
+            cube::Variable::GlobalInputArray { id, .. } => *id as usize,
+            cube::Variable::GlobalOutputArray { id, .. } => self.num_inputs + *id as usize,
+            _ => {
+                self.unsupported(format!("Only global arrays have a layout, got {var:?}"));
+                0
+            }
+        }
+    }
+
+    fn compile_branch(&mut self, instructions: &mut Vec<Instruction>, branch: cube::Branch) {
+        match branch {
+            cube::Branch::If(mut op) => instructions.push(Instruction::If {
+                cond: self.compile_variable(op.cond),
+                instructions: self.compile_scope(&mut op.scope),
+            }),
+            cube::Branch::IfElse(mut op) => instructions.push(Instruction::IfElse {
+                cond: self.compile_variable(op.cond),
+                instructions_if: self.compile_scope(&mut op.scope_if),
+                instructions_else: self.compile_scope(&mut op.scope_else),
+            }),
+            cube::Branch::Switch(mut op) => instructions.push(Instruction::Switch {
+                value: self.compile_variable(op.value),
+                instructions_default: self.compile_scope(&mut op.scope_default),
+                cases: op
+                    .cases
+                    .into_iter()
+                    .map(|(values, mut scope)| {
+                        let values = values
+                            .into_iter()
+                            .map(|val| self.compile_variable(val))
+                            .collect();
+                        (values, self.compile_scope(&mut scope))
+                    })
+                    .collect(),
+            }),
+            cube::Branch::Return => instructions.push(Instruction::Return {
+                output: self.output,
+            }),
+            cube::Branch::Break => instructions.push(Instruction::Break),
+            cube::Branch::RangeLoop(mut range_loop) => instructions.push(Instruction::RangeLoop {
+                i: self.compile_variable(range_loop.i),
+                start: self.compile_variable(range_loop.start),
+                end: self.compile_variable(range_loop.end),
+                step: range_loop.step.map(|it| self.compile_variable(it)),
+                instructions: self.compile_scope(&mut range_loop.scope),
+            }),
+            cube::Branch::Loop(mut op) => instructions.push(Instruction::Loop {
+                instructions: self.compile_scope(&mut op.scope),
+            }),
+        };
+    }
+
+    fn compile_procedure(
+        &mut self,
+        instructions: &mut Vec<Instruction>,
+        proc: cube::Procedure,
+        scope: &mut cube::Scope,
+    ) {
+        match proc {
+            cube::Procedure::ReadGlobalWithLayout(proc) => proc.expand(scope),
+            cube::Procedure::ReadGlobal(proc) => proc.expand(scope),
+            cube::Procedure::WriteGlobal(proc) => proc.expand(scope),
+            cube::Procedure::ConditionalAssign(proc) => proc.expand(scope),
+            cube::Procedure::CheckedIndex(proc) => proc.expand(scope),
+            cube::Procedure::CheckedIndexAssign(proc) => proc.expand(scope),
+            cube::Procedure::IndexOffsetGlobalWithLayout(proc) => proc.expand(scope),
+            cube::Procedure::EarlyReturn(proc) => proc.expand(scope),
+        }
+
+        instructions.extend(self.compile_scope(scope));
+    }
+
+    fn compile_instruction(
+        &mut self,
+        instructions: &mut Vec<Instruction>,
+        value: cube::Operator,
+        scope: &mut cube::Scope,
+    ) {
+        let instruction = match value {
+            cube::Operator::Add(op) => self.binary_operator("+", op),
+            cube::Operator::Sub(op) => self.binary_operator("-", op),
+            cube::Operator::Mul(op) => self.binary_operator("*", op),
+            cube::Operator::Div(op) => self.binary_operator("/", op),
+            cube::Operator::Modulo(op) => match op.out.item().elem() {
+                cube::Elem::Float(_) => self.binary_function("fmod", op),
+                _ => self.binary_operator("%", op),
+            },
+            cube::Operator::Remainder(op) => Instruction::Remainder {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Equal(op) => self.binary_operator("==", op),
+            cube::Operator::NotEqual(op) => self.binary_operator("!=", op),
+            cube::Operator::Lower(op) => self.binary_operator("<", op),
+            cube::Operator::Greater(op) => self.binary_operator(">", op),
+            cube::Operator::LowerEqual(op) => self.binary_operator("<=", op),
+            cube::Operator::GreaterEqual(op) => self.binary_operator(">=", op),
+            cube::Operator::And(op) => self.binary_operator("&&", op),
+            cube::Operator::Or(op) => self.binary_operator("||", op),
+            cube::Operator::BitwiseAnd(op) => self.binary_operator("&", op),
+            cube::Operator::BitwiseOr(op) => self.binary_operator("|", op),
+            cube::Operator::BitwiseXor(op) => self.binary_operator("^", op),
+            cube::Operator::ShiftLeft(op) => self.binary_operator("<<", op),
+            cube::Operator::ShiftRight(op) => self.binary_operator(">>", op),
+            cube::Operator::Not(op) => self.unary_operator("!", op),
+            cube::Operator::BitwiseNot(op) => self.unary_operator("~", op),
+            cube::Operator::Neg(op) => self.unary_operator("-", op),
+            cube::Operator::Abs(op) => Instruction::Abs {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Exp(op) => self.unary_function("exp", op),
+            cube::Operator::Exp2(op) => self.unary_function("exp2", op),
+            cube::Operator::Log(op) => self.unary_function("log", op),
+            cube::Operator::Log2(op) => self.unary_function("log2", op),
+            cube::Operator::Cos(op) => self.unary_function("cos", op),
+            cube::Operator::Sin(op) => self.unary_function("sin", op),
+            cube::Operator::Tanh(op) => self.unary_function("tanh", op),
+            cube::Operator::Sqrt(op) => self.unary_function("sqrt", op),
+            cube::Operator::Floor(op) => self.unary_function("floor", op),
+            cube::Operator::Ceil(op) => self.unary_function("ceil", op),
+            cube::Operator::Tan(op) => self.unary_function("tan", op),
+            cube::Operator::Asin(op) => self.unary_function("asin", op),
+            cube::Operator::Acos(op) => self.unary_function("acos", op),
+            cube::Operator::Atan(op) => self.unary_function("atan", op),
+            cube::Operator::Sinh(op) => self.unary_function("sinh", op),
+            cube::Operator::Cosh(op) => self.unary_function("cosh", op),
+            cube::Operator::Round(op) => self.unary_function("round", op),
+            cube::Operator::Trunc(op) => self.unary_function("trunc", op),
+            cube::Operator::Fract(op) => Instruction::Fract {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Sign(op) => self.unary_function("sign", op),
+            cube::Operator::Rsqrt(op) => self.unary_function("rsqrt", op),
+            cube::Operator::IsNan(op) => self.unary_function("isnan", op),
+            cube::Operator::IsInf(op) => self.unary_function("isinf", op),
+            cube::Operator::CountOnes(op) => self.unary_function("popcount", op),
+            cube::Operator::ReverseBits(op) => {
+                let item = self.compile_item(op.input.item());
+                if item.elem.size() != 4 {
+                    self.unsupported(format!("Can't reverse the bits of {}", item.elem));
+                }
+                self.register_extension(Extension::ReverseBits(Item::scalar(item.elem)));
+                self.register_extension(Extension::ReverseBits(item));
+                Instruction::ReverseBits {
+                    input: self.compile_variable(op.input),
+                    out: self.compile_variable(op.out),
+                }
+            }
+            cube::Operator::LeadingZeros(op) => self.unary_function("clz", op),
+            cube::Operator::TrailingZeros(op) => Instruction::TrailingZeros {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Erf(op) => self.unary_function("erf", op),
+            cube::Operator::Log1p(op) => self.unary_function("log1p", op),
+            cube::Operator::Max(op) => self.binary_function("max", op),
+            cube::Operator::Min(op) => self.binary_function("min", op),
+            cube::Operator::CopySign(op) => self.binary_function("copysign", op),
+            cube::Operator::Atan2(op) => self.binary_function("atan2", op),
+            cube::Operator::Powf(op) => {
+                let item = self.compile_item(op.out.item());
+                self.register_extension(Extension::Powf(Item::scalar(item.elem)));
+                self.register_extension(Extension::Powf(item));
+                Instruction::Powf {
+                    lhs: self.compile_variable(op.lhs),
+                    rhs: self.compile_variable(op.rhs),
+                    out: self.compile_variable(op.out),
+                }
+            }
+            cube::Operator::Hypot(op) => self.binary_function("hypot", op),
+            cube::Operator::Recip(op) => {
+                let elem = op.input.item().elem();
+                let one = match elem {
+                    cube::Elem::Float(kind) => ConstantScalarValue::Float(1.0, kind),
+                    cube::Elem::Int(kind) => ConstantScalarValue::Int(1, kind),
+                    cube::Elem::UInt(kind) => ConstantScalarValue::UInt(1, kind),
+                    _ => {
+                        self.unsupported(format!("Can't compute the reciprocal of {elem}"));
+                        ConstantScalarValue::Float(1.0, cube::FloatKind::F32)
+                    }
+                };
+
+                Instruction::BinaryOperator {
+                    op: "/",
+                    lhs: self.compile_variable(cube::Variable::ConstantScalar(one)),
+                    rhs: self.compile_variable(op.input),
+                    out: self.compile_variable(op.out),
+                }
+            }
+            cube::Operator::Clamp(op) => Instruction::Clamp {
+                input: self.compile_variable(op.input),
+                min_value: self.compile_variable(op.min_value),
+                max_value: self.compile_variable(op.max_value),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Fma(op) => Instruction::Fma {
+                a: self.compile_variable(op.a),
+                b: self.compile_variable(op.b),
+                c: self.compile_variable(op.c),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Assign(op) => Instruction::Assign {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Bitcast(op) => Instruction::Bitcast {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Slice(op) => {
+                let input = self.compile_variable(op.input);
+                // The slices are pointers in the address space of the sliced array.
+                if let (Some(space), cube::Variable::Slice { id, depth, .. }) =
+                    (input.address_space(), op.out)
+                {
+                    self.slices.insert((id, depth), space);
+                }
+
+                Instruction::Slice {
+                    input,
+                    start: self.compile_variable(op.start),
+                    end: self.compile_variable(op.end),
+                    out: self.compile_variable(op.out),
+                }
+            }
+            cube::Operator::Index(op) => {
+                if self.mode == ExecutionMode::Checked && has_length(&op.lhs) {
+                    let proc = cube::Procedure::CheckedIndex(cube::CheckedIndex {
+                        lhs: op.lhs,
+                        rhs: op.rhs,
+                        out: op.out,
+                    });
+                    return self.compile_procedure(instructions, proc, scope);
+                }
+                self.index(op)
+            }
+            cube::Operator::UncheckedIndex(op) => self.index(op),
+            cube::Operator::IndexAssign(op) => {
+                if self.mode == ExecutionMode::Checked && has_length(&op.out) {
+                    let proc = cube::Procedure::CheckedIndexAssign(cube::CheckedIndexAssign {
+                        lhs: op.lhs,
+                        rhs: op.rhs,
+                        out: op.out,
+                    });
+                    return self.compile_procedure(instructions, proc, scope);
+                }
+                self.index_assign(op)
+            }
+            cube::Operator::UncheckedIndexAssign(op) => self.index_assign(op),
+            cube::Operator::AtomicLoad(op) => Instruction::AtomicLoad {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::AtomicStore(op) => Instruction::AtomicStore {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::AtomicSwap(op) => self.atomic("atomic_xchg", op),
+            cube::Operator::AtomicAdd(op) => self.atomic("atomic_add", op),
+            cube::Operator::AtomicSub(op) => self.atomic("atomic_sub", op),
+            cube::Operator::AtomicMax(op) => self.atomic("atomic_max", op),
+            cube::Operator::AtomicMin(op) => self.atomic("atomic_min", op),
+            cube::Operator::AtomicAnd(op) => self.atomic("atomic_and", op),
+            cube::Operator::AtomicOr(op) => self.atomic("atomic_or", op),
+            cube::Operator::AtomicXor(op) => self.atomic("atomic_xor", op),
+            cube::Operator::AtomicCompareAndSwap(op) => Instruction::AtomicCompareAndSwap {
+                input: self.compile_variable(op.input),
+                cmp: self.compile_variable(op.cmp),
+                val: self.compile_variable(op.val),
+                out: self.compile_variable(op.out),
+            },
+        };
+
+        instructions.push(instruction);
+    }
+
+    fn binary_operator(&mut self, op: &'static str, value: cube::BinaryOperator) -> Instruction {
+        Instruction::BinaryOperator {
+            op,
+            lhs: self.compile_variable(value.lhs),
+            rhs: self.compile_variable(value.rhs),
+            out: self.compile_variable(value.out),
+        }
+    }
+
+    fn unary_operator(&mut self, op: &'static str, value: cube::UnaryOperator) -> Instruction {
+        Instruction::UnaryOperator {
+            op,
+            input: self.compile_variable(value.input),
+            out: self.compile_variable(value.out),
+        }
+    }
+
+    fn binary_function(&mut self, func: &'static str, value: cube::BinaryOperator) -> Instruction {
+        Instruction::BinaryFunction {
+            func,
+            lhs: self.compile_variable(value.lhs),
+            rhs: self.compile_variable(value.rhs),
+            out: self.compile_variable(value.out),
+        }
+    }
+
+    fn unary_function(&mut self, func: &'static str, value: cube::UnaryOperator) -> Instruction {
+        Instruction::UnaryFunction {
+            func,
+            input: self.compile_variable(value.input),
+            out: self.compile_variable(value.out),
+        }
+    }
+
+    fn atomic(&mut self, func: &'static str, value: cube::BinaryOperator) -> Instruction {
+        Instruction::Atomic {
+            func,
+            lhs: self.compile_variable(value.lhs),
+            rhs: self.compile_variable(value.rhs),
+            out: self.compile_variable(value.out),
+        }
+    }
+
+    fn index(&mut self, value: cube::BinaryOperator) -> Instruction {
+        Instruction::Index {
+            lhs: self.compile_variable(value.lhs),
+            rhs: self.compile_variable(value.rhs),
+            out: self.compile_variable(value.out),
+        }
+    }
+
+    fn index_assign(&mut self, value: cube::BinaryOperator) -> Instruction {
+        Instruction::IndexAssign {
+            lhs: self.compile_variable(value.lhs),
+            rhs: self.compile_variable(value.rhs),
+            out: self.compile_variable(value.out),
+        }
+    }
+
+    fn register_extension(&mut self, extension: Extension) {
+        if !self.extensions.contains(&extension) {
+            self.extensions.push(extension);
+        }
+    }
+
+    fn builtin(&mut self, builtin: Builtin) -> Variable {
+        self.body.builtins.insert(builtin);
+        Variable::Builtin(builtin)
+    }
+
+    fn compile_variable(&mut self, value: cube::Variable) -> Variable {
+        match value {
+            cube::Variable::GlobalInputArray { id, item } => {
+                Variable::GlobalInputArray(id, self.compile_item(item))
+            }
+            cube::Variable::GlobalOutputArray { id, item } => {
+                Variable::GlobalOutputArray(id, self.compile_item(item))
+            }
+            cube::Variable::GlobalScalar { id, elem } => {
+                Variable::GlobalScalar(id, self.compile_elem(elem), elem)
+            }
+            cube::Variable::ConstantScalar(value) => {
+                Variable::ConstantScalar(value, self.compile_elem(value.elem()))
+            }
+            cube::Variable::Local { id, item, depth } => Variable::Local {
+                id,
+                item: self.compile_item(item),
+                depth,
+            },
+            cube::Variable::LocalScalar { id, elem, depth } => Variable::LocalScalar {
+                id,
+                elem: self.compile_elem(elem),
+                depth,
+            },
+            cube::Variable::Slice { id, item, depth } => Variable::Slice {
+                id,
+                item: self.compile_item(item),
+                depth,
+                space: self
+                    .slices
+                    .get(&(id, depth))
+                    .copied()
+                    .unwrap_or(AddressSpace::Global),
+            },
+            cube::Variable::SharedMemory { id, item, length } => {
+                let item = self.compile_item(item);
+                if !self.body.shared_memories.iter().any(|s| s.index == id) {
+                    self.body.shared_memories.push(SharedMemory {
+                        index: id,
+                        item,
+                        size: length,
+                    });
+                }
+                Variable::SharedMemory(id, item, length)
+            }
+            cube::Variable::LocalArray {
+                id,
+                item,
+                depth,
+                length,
+            } => {
+                let item = self.compile_item(item);
+                if !self
+                    .body
+                    .local_arrays
+                    .iter()
+                    .any(|s| s.index == id && s.depth == depth)
+                {
+                    self.body.local_arrays.push(LocalArray {
+                        index: id,
+                        item,
+                        depth,
+                        size: length,
+                    });
+                }
+                Variable::LocalArray(id, item, depth, length)
+            }
+            cube::Variable::Matrix { .. } => {
+                unreachable!("Cooperative matrices are emulated before the compilation.")
+            }
+            cube::Variable::Rank => {
+                self.body.rank = true;
+                Variable::Rank
+            }
+            cube::Variable::UnitPos => self.builtin(Builtin::UnitPos),
+            cube::Variable::UnitPosX => self.builtin(Builtin::UnitPosX),
+            cube::Variable::UnitPosY => self.builtin(Builtin::UnitPosY),
+            cube::Variable::UnitPosZ => self.builtin(Builtin::UnitPosZ),
+            cube::Variable::CubePos => self.builtin(Builtin::CubePos),
+            cube::Variable::CubePosX => self.builtin(Builtin::CubePosX),
+            cube::Variable::CubePosY => self.builtin(Builtin::CubePosY),
+            cube::Variable::CubePosZ => self.builtin(Builtin::CubePosZ),
+            cube::Variable::CubeDim => self.builtin(Builtin::CubeDim),
+            cube::Variable::CubeDimX => self.builtin(Builtin::CubeDimX),
+            cube::Variable::CubeDimY => self.builtin(Builtin::CubeDimY),
+            cube::Variable::CubeDimZ => self.builtin(Builtin::CubeDimZ),
+            cube::Variable::CubeCount => self.builtin(Builtin::CubeCount),
+            cube::Variable::CubeCountX => self.builtin(Builtin::CubeCountX),
+            cube::Variable::CubeCountY => self.builtin(Builtin::CubeCountY),
+            cube::Variable::CubeCountZ => self.builtin(Builtin::CubeCountZ),
+            cube::Variable::AbsolutePos => self.builtin(Builtin::AbsolutePos),
+            cube::Variable::AbsolutePosX => self.builtin(Builtin::AbsolutePosX),
+            cube::Variable::AbsolutePosY => self.builtin(Builtin::AbsolutePosY),
+            cube::Variable::AbsolutePosZ => self.builtin(Builtin::AbsolutePosZ),
+            cube::Variable::SubcubeDim => {
+                self.pragmas.subgroups = true;
+                self.builtin(Builtin::SubcubeDim)
+            }
+        }
+    }
+
+    fn compile_binding(&mut self, name: String, binding: cube::Binding) -> Binding {
+        let item = self.compile_item(binding.item);
+        if item.elem == Elem::Bool {
+            self.unsupported(format!(
+                "The binding {name} can't contain booleans, since their size isn't defined by OpenCL"
+            ));
+        }
+
+        Binding { name, item }
+    }
+
+    fn compile_item(&mut self, item: cube::Item) -> Item {
+        let elem = self.compile_elem(item.elem);
+
+        if ![1, 2, 3, 4, 8, 16].contains(&item.vectorization) {
+            self.unsupported(format!(
+                "Vectors of {} elements aren't supported",
+                item.vectorization
+            ));
+        } else if item.vectorization > 1 && elem.is_atomic() {
+            self.unsupported("Vectors of atomics aren't supported".to_string());
+        }
+
+        Item::new(elem, item.vectorization)
+    }
+
+    fn compile_elem(&mut self, elem: cube::Elem) -> Elem {
+        match elem {
+            cube::Elem::Float(kind) => match kind {
+                cube::FloatKind::F16 => {
+                    self.pragmas.fp16 = true;
+                    Elem::F16
+                }
+                cube::FloatKind::BF16 => {
+                    self.unsupported("OpenCL doesn't support bf16".to_string());
+                    Elem::F32
+                }
+                cube::FloatKind::F32 => Elem::F32,
+                cube::FloatKind::F64 => {
+                    self.pragmas.fp64 = true;
+                    Elem::F64
+                }
+            },
+            cube::Elem::Int(kind) => match kind {
+                cube::IntKind::I8 => Elem::I8,
+                cube::IntKind::I16 => Elem::I16,
+                cube::IntKind::I32 => Elem::I32,
+                cube::IntKind::I64 => Elem::I64,
+            },
+            cube::Elem::UInt(kind) => match kind {
+                cube::UIntKind::U8 => Elem::U8,
+                cube::UIntKind::U16 => Elem::U16,
+                cube::UIntKind::U32 => Elem::U32,
+                cube::UIntKind::U64 => Elem::U64,
+            },
+            cube::Elem::AtomicInt(kind) => match kind {
+                cube::IntKind::I32 => Elem::AtomicI32,
+                _ => {
+                    self.unsupported(format!("atomic<{elem}> isn't supported by OpenCL"));
+                    Elem::AtomicI32
+                }
+            },
+            cube::Elem::AtomicUInt => Elem::AtomicU32,
+            cube::Elem::AtomicFloat(_) => {
+                self.unsupported(format!("{elem} isn't supported by OpenCL"));
+                Elem::AtomicU32
+            }
+            cube::Elem::Bool => Elem::Bool,
+        }
+    }
+
+    fn unsupported(&mut self, reason: String) {
+        self.fail(ComputeError::UnsupportedFeature(reason));
+    }
+
+    fn fail(&mut self, error: ComputeError) {
+        // Only the first error is kept, the following ones are often a consequence of it.
+        self.error.get_or_insert(error);
+    }
+}
+
+fn has_length(var: &cube::Variable) -> bool {
+    matches!(
+        var,
+        cube::Variable::GlobalInputArray { .. }
+            | cube::Variable::GlobalOutputArray { .. }
+            | cube::Variable::Slice { .. }
+    )
+}
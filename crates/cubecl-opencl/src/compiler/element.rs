@@ -0,0 +1,383 @@
+use cubecl_core::ir::{self as cube, ConstantScalarValue};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Elem {
+    F16,
+    F32,
+    F64,
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    Bool,
+    AtomicI32,
+    AtomicU32,
+}
+
+/// A scalar or a vector, the vectors of OpenCL C have 2, 3, 4, 8 or 16 components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Item {
+    pub elem: Elem,
+    pub vectorization: u8,
+}
+
+/// The address space of the memory pointed to by an array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AddressSpace {
+    Global,
+    Local,
+    Private,
+}
+
+/// The builtin values of a kernel, computed from the work-item functions at the start of the
+/// body using them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Builtin {
+    UnitPos,
+    UnitPosX,
+    UnitPosY,
+    UnitPosZ,
+    CubePos,
+    CubePosX,
+    CubePosY,
+    CubePosZ,
+    CubeDim,
+    CubeDimX,
+    CubeDimY,
+    CubeDimZ,
+    CubeCount,
+    CubeCountX,
+    CubeCountY,
+    CubeCountZ,
+    AbsolutePos,
+    AbsolutePosX,
+    AbsolutePosY,
+    AbsolutePosZ,
+    SubcubeDim,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Variable {
+    GlobalInputArray(u16, Item),
+    GlobalOutputArray(u16, Item),
+    GlobalScalar(u16, Elem, cube::Elem),
+    ConstantScalar(ConstantScalarValue, Elem),
+    Local {
+        id: u16,
+        item: Item,
+        depth: u8,
+    },
+    LocalScalar {
+        id: u16,
+        elem: Elem,
+        depth: u8,
+    },
+    Slice {
+        id: u16,
+        item: Item,
+        depth: u8,
+        space: AddressSpace,
+    },
+    SharedMemory(u16, Item, u32),
+    LocalArray(u16, Item, u8, u32),
+    Builtin(Builtin),
+    Rank,
+}
+
+impl Elem {
+    pub fn size(&self) -> usize {
+        match self {
+            Elem::F16 => core::mem::size_of::<half::f16>(),
+            Elem::F32 => core::mem::size_of::<f32>(),
+            Elem::F64 => core::mem::size_of::<f64>(),
+            Elem::I8 => core::mem::size_of::<i8>(),
+            Elem::I16 => core::mem::size_of::<i16>(),
+            Elem::I32 | Elem::AtomicI32 => core::mem::size_of::<i32>(),
+            Elem::I64 => core::mem::size_of::<i64>(),
+            Elem::U8 => core::mem::size_of::<u8>(),
+            Elem::U16 => core::mem::size_of::<u16>(),
+            Elem::U32 | Elem::AtomicU32 => core::mem::size_of::<u32>(),
+            Elem::U64 => core::mem::size_of::<u64>(),
+            Elem::Bool => core::mem::size_of::<bool>(),
+        }
+    }
+
+    pub fn is_atomic(&self) -> bool {
+        matches!(self, Elem::AtomicI32 | Elem::AtomicU32)
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, Elem::F16 | Elem::F32 | Elem::F64)
+    }
+
+    pub fn is_signed_int(&self) -> bool {
+        matches!(
+            self,
+            Elem::I8 | Elem::I16 | Elem::I32 | Elem::I64 | Elem::AtomicI32
+        )
+    }
+}
+
+impl Item {
+    pub fn new(elem: Elem, vectorization: u8) -> Self {
+        Self {
+            elem,
+            vectorization,
+        }
+    }
+
+    pub fn scalar(elem: Elem) -> Self {
+        Self::new(elem, 1)
+    }
+
+    pub fn is_vectorized(&self) -> bool {
+        self.vectorization > 1
+    }
+}
+
+impl Builtin {
+    /// The expression computing the builtin.
+    pub fn expression(&self) -> &'static str {
+        match self {
+            Builtin::UnitPos => {
+                "(get_local_id(2) * get_local_size(0) * get_local_size(1)) + (get_local_id(1) * get_local_size(0)) + get_local_id(0)"
+            }
+            Builtin::UnitPosX => "get_local_id(0)",
+            Builtin::UnitPosY => "get_local_id(1)",
+            Builtin::UnitPosZ => "get_local_id(2)",
+            Builtin::CubePos => {
+                "(get_group_id(2) * get_num_groups(0) * get_num_groups(1)) + (get_group_id(1) * get_num_groups(0)) + get_group_id(0)"
+            }
+            Builtin::CubePosX => "get_group_id(0)",
+            Builtin::CubePosY => "get_group_id(1)",
+            Builtin::CubePosZ => "get_group_id(2)",
+            Builtin::CubeDim => "get_local_size(0) * get_local_size(1) * get_local_size(2)",
+            Builtin::CubeDimX => "get_local_size(0)",
+            Builtin::CubeDimY => "get_local_size(1)",
+            Builtin::CubeDimZ => "get_local_size(2)",
+            Builtin::CubeCount => "get_num_groups(0) * get_num_groups(1) * get_num_groups(2)",
+            Builtin::CubeCountX => "get_num_groups(0)",
+            Builtin::CubeCountY => "get_num_groups(1)",
+            Builtin::CubeCountZ => "get_num_groups(2)",
+            Builtin::AbsolutePos => {
+                "(get_global_id(2) * get_global_size(0) * get_global_size(1)) + (get_global_id(1) * get_global_size(0)) + get_global_id(0)"
+            }
+            Builtin::AbsolutePosX => "get_global_id(0)",
+            Builtin::AbsolutePosY => "get_global_id(1)",
+            Builtin::AbsolutePosZ => "get_global_id(2)",
+            Builtin::SubcubeDim => "get_sub_group_size()",
+        }
+    }
+}
+
+impl Variable {
+    pub fn item(&self) -> Item {
+        match self {
+            Variable::GlobalInputArray(_, item) => *item,
+            Variable::GlobalOutputArray(_, item) => *item,
+            Variable::GlobalScalar(_, elem, _) => Item::scalar(*elem),
+            Variable::ConstantScalar(_, elem) => Item::scalar(*elem),
+            Variable::Local { item, .. } => *item,
+            Variable::LocalScalar { elem, .. } => Item::scalar(*elem),
+            Variable::Slice { item, .. } => *item,
+            Variable::SharedMemory(_, item, _) => *item,
+            Variable::LocalArray(_, item, _, _) => *item,
+            Variable::Builtin(_) | Variable::Rank => Item::scalar(Elem::U32),
+        }
+    }
+
+    pub fn elem(&self) -> Elem {
+        self.item().elem
+    }
+
+    /// Whether the variable is a pointer to many values, which is indexed to read a single one.
+    pub fn is_array(&self) -> bool {
+        self.address_space().is_some()
+    }
+
+    /// The address space of the values of an array.
+    pub fn address_space(&self) -> Option<AddressSpace> {
+        match self {
+            Variable::GlobalInputArray(..) | Variable::GlobalOutputArray(..) => {
+                Some(AddressSpace::Global)
+            }
+            Variable::Slice { space, .. } => Some(*space),
+            Variable::SharedMemory(..) => Some(AddressSpace::Local),
+            Variable::LocalArray(..) => Some(AddressSpace::Private),
+            _ => None,
+        }
+    }
+
+    /// The item of a value read by indexing the variable.
+    pub fn indexed_item(&self) -> Item {
+        match self.is_array() {
+            true => self.item(),
+            false => Item::scalar(self.elem()),
+        }
+    }
+}
+
+impl Display for Elem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Elem::F16 => f.write_str("half"),
+            Elem::F32 => f.write_str("float"),
+            Elem::F64 => f.write_str("double"),
+            Elem::I8 => f.write_str("char"),
+            Elem::I16 => f.write_str("short"),
+            // The atomic functions operate on plain integers.
+            Elem::I32 | Elem::AtomicI32 => f.write_str("int"),
+            Elem::I64 => f.write_str("long"),
+            Elem::U8 => f.write_str("uchar"),
+            Elem::U16 => f.write_str("ushort"),
+            Elem::U32 | Elem::AtomicU32 => f.write_str("uint"),
+            Elem::U64 => f.write_str("ulong"),
+            Elem::Bool => f.write_str("bool"),
+        }
+    }
+}
+
+impl Display for Item {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.vectorization, self.elem) {
+            (1, elem) => f.write_fmt(format_args!("{elem}")),
+            // There are no boolean vectors, the relational operators return -1 for true and 0
+            // for false in each component of an integer vector.
+            (size, Elem::Bool) => f.write_fmt(format_args!("int{size}")),
+            (size, elem) => f.write_fmt(format_args!("{elem}{size}")),
+        }
+    }
+}
+
+impl Display for AddressSpace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AddressSpace::Global => f.write_str("__global"),
+            AddressSpace::Local => f.write_str("__local"),
+            AddressSpace::Private => f.write_str("__private"),
+        }
+    }
+}
+
+impl Display for Builtin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Builtin::UnitPos => f.write_str("unit_pos"),
+            Builtin::UnitPosX => f.write_str("unit_pos_x"),
+            Builtin::UnitPosY => f.write_str("unit_pos_y"),
+            Builtin::UnitPosZ => f.write_str("unit_pos_z"),
+            Builtin::CubePos => f.write_str("cube_pos"),
+            Builtin::CubePosX => f.write_str("cube_pos_x"),
+            Builtin::CubePosY => f.write_str("cube_pos_y"),
+            Builtin::CubePosZ => f.write_str("cube_pos_z"),
+            Builtin::CubeDim => f.write_str("cube_dim"),
+            Builtin::CubeDimX => f.write_str("cube_dim_x"),
+            Builtin::CubeDimY => f.write_str("cube_dim_y"),
+            Builtin::CubeDimZ => f.write_str("cube_dim_z"),
+            Builtin::CubeCount => f.write_str("cube_count"),
+            Builtin::CubeCountX => f.write_str("cube_count_x"),
+            Builtin::CubeCountY => f.write_str("cube_count_y"),
+            Builtin::CubeCountZ => f.write_str("cube_count_z"),
+            Builtin::AbsolutePos => f.write_str("absolute_pos"),
+            Builtin::AbsolutePosX => f.write_str("absolute_pos_x"),
+            Builtin::AbsolutePosY => f.write_str("absolute_pos_y"),
+            Builtin::AbsolutePosZ => f.write_str("absolute_pos_z"),
+            Builtin::SubcubeDim => f.write_str("subcube_dim"),
+        }
+    }
+}
+
+impl Display for Variable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Variable::GlobalInputArray(id, _) => f.write_fmt(format_args!("input_{id}")),
+            Variable::GlobalOutputArray(id, _) => f.write_fmt(format_args!("output_{id}")),
+            Variable::GlobalScalar(id, _, elem) => {
+                f.write_fmt(format_args!("scalars_{elem}[{id}]"))
+            }
+            Variable::ConstantScalar(value, elem) => format_constant(f, value, elem),
+            Variable::Local { id, depth, .. } => f.write_fmt(format_args!("l_{depth}_{id}")),
+            Variable::LocalScalar { id, depth, .. } => f.write_fmt(format_args!("s_{depth}_{id}")),
+            Variable::Slice { id, depth, .. } => f.write_fmt(format_args!("slice_{depth}_{id}")),
+            Variable::SharedMemory(id, _, _) => f.write_fmt(format_args!("shared_memory_{id}")),
+            Variable::LocalArray(id, _, depth, _) => {
+                f.write_fmt(format_args!("l_arr_{id}_{depth}"))
+            }
+            Variable::Builtin(builtin) => f.write_fmt(format_args!("{builtin}")),
+            Variable::Rank => f.write_str("rank"),
+        }
+    }
+}
+
+/// Convert the value of the item `from` to `item`.
+///
+/// Scalars are converted with a cast, which is undefined for vectors, so vectors are converted
+/// with the `convert_` functions and scalars are broadcast to the components of the vectors.
+pub fn cast(item: Item, from: Item, value: impl Display) -> String {
+    if item == from {
+        return value.to_string();
+    }
+
+    match (item.is_vectorized(), from.is_vectorized()) {
+        (false, _) => format!("({item})({value})"),
+        (true, false) => {
+            let value = cast(Item::scalar(item.elem), from, value);
+            format!("({item})({value})")
+        }
+        // The true components of the boolean vectors are -1.
+        (true, true) if from.elem == Elem::Bool => format!("convert_{item}(-({value}))"),
+        (true, true) if item.elem == Elem::Bool => {
+            format!("convert_{item}({value} != ({from})(0))")
+        }
+        (true, true) => format!("convert_{item}({value})"),
+    }
+}
+
+// The conversion is done in Rust and the number is rendered, to avoid overflow or other precision
+// related problems.
+fn format_constant(
+    f: &mut std::fmt::Formatter<'_>,
+    value: &ConstantScalarValue,
+    elem: &Elem,
+) -> std::fmt::Result {
+    match value {
+        ConstantScalarValue::Int(val, kind) => match kind {
+            cube::IntKind::I8 => f.write_fmt(format_args!("({elem}){}", *val as i8)),
+            cube::IntKind::I16 => f.write_fmt(format_args!("({elem}){}", *val as i16)),
+            cube::IntKind::I32 => f.write_fmt(format_args!("({elem}){}", *val as i32)),
+            cube::IntKind::I64 => f.write_fmt(format_args!("({elem}){val}")),
+        },
+        ConstantScalarValue::UInt(val, kind) => match kind {
+            cube::UIntKind::U8 => f.write_fmt(format_args!("({elem}){}", *val as u8)),
+            cube::UIntKind::U16 => f.write_fmt(format_args!("({elem}){}", *val as u16)),
+            cube::UIntKind::U32 => f.write_fmt(format_args!("({elem}){}", *val as u32)),
+            cube::UIntKind::U64 => f.write_fmt(format_args!("({elem}){val}")),
+        },
+        ConstantScalarValue::Float(val, kind) => {
+            let val = match kind {
+                cube::FloatKind::F16 => half::f16::from_f64(*val).to_f64(),
+                cube::FloatKind::BF16 => half::bf16::from_f64(*val).to_f64(),
+                cube::FloatKind::F32 => *val as f32 as f64,
+                cube::FloatKind::F64 => *val,
+            };
+
+            if val.is_nan() {
+                f.write_fmt(format_args!("({elem})NAN"))
+            } else if val.is_infinite() && val > 0.0 {
+                f.write_fmt(format_args!("({elem})INFINITY"))
+            } else if val.is_infinite() {
+                f.write_fmt(format_args!("({elem})-INFINITY"))
+            } else if let cube::FloatKind::F64 = kind {
+                f.write_fmt(format_args!("({elem}){val:?}"))
+            } else {
+                f.write_fmt(format_args!("({elem}){:?}", val as f32))
+            }
+        }
+        ConstantScalarValue::Bool(val) => f.write_fmt(format_args!("{val}")),
+    }
+}
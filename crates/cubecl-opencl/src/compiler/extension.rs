@@ -0,0 +1,100 @@
+use std::fmt::Display;
+
+use super::Item;
+
+/// Not all functions are native to OpenCL C, so this struct allows to support more functions.
+///
+/// Functions can't be overloaded in C, so the name of each extension ends with its item, e.g.
+/// `powf_float4`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Extension {
+    Powf(Item),
+    ReverseBits(Item),
+}
+
+impl Display for Extension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Extension::Powf(item) => format_powf(f, item),
+            Extension::ReverseBits(item) => format_reverse_bits(f, item),
+        }
+    }
+}
+
+fn format_powf(f: &mut std::fmt::Formatter<'_>, item: &Item) -> std::fmt::Result {
+    if item.is_vectorized() {
+        return format_componentwise(f, item, "powf", &["lhs", "rhs"]);
+    }
+
+    f.write_fmt(format_args!(
+        "
+// The builtin pow function is undefined for a negative base, even when the exponent is an integer.
+{item} powf_{item}({item} lhs, {item} rhs) {{
+    if (rhs == 0.0) {{
+        return 1.0;
+    }}
+    {item} modulo = fmod(rhs, ({item})2.0);
+    if (modulo == 0.0) {{
+        return pow(fabs(lhs), rhs);
+    }} else if (fabs(modulo) == 1.0 && lhs < 0.0) {{
+        return -pow(-lhs, rhs);
+    }}
+    return pow(lhs, rhs);
+}}
+"
+    ))
+}
+
+fn format_reverse_bits(f: &mut std::fmt::Formatter<'_>, item: &Item) -> std::fmt::Result {
+    if item.is_vectorized() {
+        return format_componentwise(f, item, "reverse_bits", &["x"]);
+    }
+
+    // Swap the bits by pairs, then the pairs, the nibbles, the bytes and the halves.
+    f.write_fmt(format_args!(
+        "
+{item} reverse_bits_{item}({item} x) {{
+    uint bits = (uint)x;
+    bits = ((bits >> 1) & 0x55555555u) | ((bits & 0x55555555u) << 1);
+    bits = ((bits >> 2) & 0x33333333u) | ((bits & 0x33333333u) << 2);
+    bits = ((bits >> 4) & 0x0F0F0F0Fu) | ((bits & 0x0F0F0F0Fu) << 4);
+    bits = ((bits >> 8) & 0x00FF00FFu) | ((bits & 0x00FF00FFu) << 8);
+    return ({item})((bits >> 16) | (bits << 16));
+}}
+"
+    ))
+}
+
+/// Apply the scalar extension to each component of a vector.
+fn format_componentwise(
+    f: &mut std::fmt::Formatter<'_>,
+    item: &Item,
+    name: &str,
+    args: &[&str],
+) -> std::fmt::Result {
+    let params = args
+        .iter()
+        .map(|arg| format!("{item} {arg}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let scalar = Item::scalar(item.elem);
+    let components = (0..item.vectorization)
+        .map(|i| {
+            let args = args
+                .iter()
+                .map(|arg| format!("{arg}.s{i:x}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("{name}_{scalar}({args})")
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    f.write_fmt(format_args!(
+        "
+{item} {name}_{item}({params}) {{
+    return ({item})({components});
+}}
+"
+    ))
+}
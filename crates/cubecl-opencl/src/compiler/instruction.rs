@@ -0,0 +1,459 @@
+use std::fmt::Display;
+
+use super::{cast, Elem, Subcube, Variable};
+
+/// An instruction of the body of a kernel or of a function.
+///
+/// The vectors of OpenCL C support the arithmetic operators and the builtin functions
+/// component-wise, so the same instruction handles scalars and vectors.
+#[derive(Debug, Clone)]
+pub enum Instruction {
+    DeclareVariable {
+        var: Variable,
+    },
+    /// An infix operator, such as `+` or `<`.
+    BinaryOperator {
+        op: &'static str,
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    /// A prefix operator, such as `!` or `-`.
+    UnaryOperator {
+        op: &'static str,
+        input: Variable,
+        out: Variable,
+    },
+    BinaryFunction {
+        func: &'static str,
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    UnaryFunction {
+        func: &'static str,
+        input: Variable,
+        out: Variable,
+    },
+    Abs {
+        input: Variable,
+        out: Variable,
+    },
+    Fract {
+        input: Variable,
+        out: Variable,
+    },
+    TrailingZeros {
+        input: Variable,
+        out: Variable,
+    },
+    /// Call the [powf extension](super::Extension::Powf).
+    Powf {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    /// Call the [reverse bits extension](super::Extension::ReverseBits).
+    ReverseBits {
+        input: Variable,
+        out: Variable,
+    },
+    Remainder {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    Fma {
+        a: Variable,
+        b: Variable,
+        c: Variable,
+        out: Variable,
+    },
+    Clamp {
+        input: Variable,
+        min_value: Variable,
+        max_value: Variable,
+        out: Variable,
+    },
+    Assign {
+        input: Variable,
+        out: Variable,
+    },
+    Bitcast {
+        input: Variable,
+        out: Variable,
+    },
+    Index {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    IndexAssign {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    Slice {
+        input: Variable,
+        start: Variable,
+        end: Variable,
+        out: Variable,
+    },
+    Length {
+        var: Variable,
+        out: Variable,
+        num_inputs: usize,
+        num_outputs: usize,
+    },
+    Stride {
+        dim: Variable,
+        position: usize,
+        out: Variable,
+    },
+    Shape {
+        dim: Variable,
+        position: usize,
+        out: Variable,
+    },
+    If {
+        cond: Variable,
+        instructions: Vec<Self>,
+    },
+    IfElse {
+        cond: Variable,
+        instructions_if: Vec<Self>,
+        instructions_else: Vec<Self>,
+    },
+    Switch {
+        value: Variable,
+        instructions_default: Vec<Self>,
+        cases: Vec<(Vec<Variable>, Vec<Self>)>,
+    },
+    RangeLoop {
+        i: Variable,
+        start: Variable,
+        end: Variable,
+        step: Option<Variable>,
+        instructions: Vec<Self>,
+    },
+    Loop {
+        instructions: Vec<Self>,
+    },
+    Call {
+        function: String,
+        inputs: Vec<Variable>,
+        output: Option<Variable>,
+        /// The bindings of the kernel.
+        args: Vec<String>,
+    },
+    Return {
+        output: Option<Variable>,
+    },
+    Break,
+    SyncLocal,
+    SyncGlobal,
+    /// An atomic read-modify-write operation, such as `atomic_add`.
+    Atomic {
+        func: &'static str,
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    AtomicLoad {
+        input: Variable,
+        out: Variable,
+    },
+    AtomicStore {
+        input: Variable,
+        out: Variable,
+    },
+    AtomicCompareAndSwap {
+        input: Variable,
+        cmp: Variable,
+        val: Variable,
+        out: Variable,
+    },
+    Subcube(Subcube),
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::DeclareVariable { var } => {
+                f.write_fmt(format_args!("{} {var};\n", var.item()))
+            }
+            Instruction::BinaryOperator { op, lhs, rhs, out } => {
+                let item = out.item();
+                match item.is_vectorized() && item.elem == Elem::Bool && lhs.elem().size() != 4 {
+                    // The relational operators return vectors of integers of the size of their
+                    // operands.
+                    true => {
+                        f.write_fmt(format_args!("{out} = convert_{item}({lhs} {op} {rhs});\n"))
+                    }
+                    false => f.write_fmt(format_args!("{out} = {lhs} {op} {rhs};\n")),
+                }
+            }
+            Instruction::UnaryOperator { op, input, out } => {
+                f.write_fmt(format_args!("{out} = {op}{input};\n"))
+            }
+            Instruction::BinaryFunction {
+                func,
+                lhs,
+                rhs,
+                out,
+            } => f.write_fmt(format_args!("{out} = {func}({lhs}, {rhs});\n")),
+            Instruction::UnaryFunction { func, input, out } => {
+                let item = out.item();
+                // The bit counting functions return the type of their input, and the relational
+                // functions already return -1 for the true components of vectors.
+                match (input.item() == item, item.is_vectorized()) {
+                    (true, _) => f.write_fmt(format_args!("{out} = {func}({input});\n")),
+                    (false, true) => {
+                        f.write_fmt(format_args!("{out} = convert_{item}({func}({input}));\n"))
+                    }
+                    (false, false) => {
+                        f.write_fmt(format_args!("{out} = ({item})({func}({input}));\n"))
+                    }
+                }
+            }
+            Instruction::Abs { input, out } => match input.elem() {
+                elem if elem.is_float() => f.write_fmt(format_args!("{out} = fabs({input});\n")),
+                // The integer abs function returns an unsigned integer.
+                elem if elem.is_signed_int() => {
+                    f.write_fmt(format_args!("{out} = max({input}, -{input});\n"))
+                }
+                _ => f.write_fmt(format_args!("{out} = {input};\n")),
+            },
+            Instruction::Fract { input, out } => {
+                // The fract function also returns the floor through a pointer.
+                f.write_fmt(format_args!("{out} = {input} - floor({input});\n"))
+            }
+            Instruction::TrailingZeros { input, out } => {
+                // The ctz function is only available since OpenCL C 2.0, the trailing zeros are
+                // the bits set by subtracting one from the lowest set bit.
+                let value = cast(
+                    out.item(),
+                    input.item(),
+                    format!("popcount(~{input} & ({input} - 1))"),
+                );
+                f.write_fmt(format_args!("{out} = {value};\n"))
+            }
+            Instruction::Powf { lhs, rhs, out } => {
+                let item = out.item();
+                f.write_fmt(format_args!("{out} = powf_{item}({lhs}, {rhs});\n"))
+            }
+            Instruction::ReverseBits { input, out } => {
+                let value = cast(
+                    out.item(),
+                    input.item(),
+                    format!("reverse_bits_{}({input})", input.item()),
+                );
+                f.write_fmt(format_args!("{out} = {value};\n"))
+            }
+            Instruction::Remainder { lhs, rhs, out } => match out.elem().is_float() {
+                true => f.write_fmt(format_args!(
+                    "{out} = {lhs} - {rhs} * floor({lhs} / {rhs});\n"
+                )),
+                // The remainder of the integer division has the sign of the dividend.
+                false => f.write_fmt(format_args!("{out} = (({lhs} % {rhs}) + {rhs}) % {rhs};\n")),
+            },
+            Instruction::Fma { a, b, c, out } => {
+                f.write_fmt(format_args!("{out} = fma({a}, {b}, {c});\n"))
+            }
+            Instruction::Clamp {
+                input,
+                min_value,
+                max_value,
+                out,
+            } => f.write_fmt(format_args!(
+                "{out} = clamp({input}, {min_value}, {max_value});\n"
+            )),
+            Instruction::Assign { input, out } => {
+                let value = cast(out.item(), input.item(), input);
+                f.write_fmt(format_args!("{out} = {value};\n"))
+            }
+            Instruction::Bitcast { input, out } => {
+                let item = out.item();
+                f.write_fmt(format_args!("{out} = as_{item}({input});\n"))
+            }
+            Instruction::Index { lhs, rhs, out } => {
+                let item = out.item();
+                if item.elem.is_atomic() {
+                    // Atomics are only accessed through pointers, in the address space of the array.
+                    let space = lhs.address_space().unwrap();
+                    f.write_fmt(format_args!(
+                        "{space} volatile {item}* {out} = &{lhs}[{rhs}];\n"
+                    ))
+                } else {
+                    let value = cast(item, lhs.indexed_item(), format!("{lhs}[{rhs}]"));
+                    f.write_fmt(format_args!("{out} = {value};\n"))
+                }
+            }
+            Instruction::IndexAssign { lhs, rhs, out } => {
+                let value = cast(out.indexed_item(), rhs.item(), rhs);
+                f.write_fmt(format_args!("{out}[{lhs}] = {value};\n"))
+            }
+            Instruction::Slice {
+                input,
+                start,
+                end,
+                out,
+            } => {
+                let space = input.address_space().unwrap();
+                let item = out.item();
+                f.write_fmt(format_args!("uint {out}_length = {end} - {start};\n"))?;
+                f.write_fmt(format_args!("{space} {item}* {out} = {input} + {start};\n"))
+            }
+            Instruction::Length {
+                var,
+                out,
+                num_inputs,
+                num_outputs,
+            } => match var {
+                Variable::Slice { .. } => f.write_fmt(format_args!("{out} = {var}_length;\n")),
+                _ => {
+                    let offset = num_inputs + num_outputs;
+                    let index = match var {
+                        Variable::GlobalInputArray(id, _) => *id as usize,
+                        Variable::GlobalOutputArray(id, _) => *id as usize + num_inputs,
+                        _ => panic!("Can only know the length of a global array or a slice"),
+                    } + 1;
+
+                    match var.item().vectorization {
+                        1 => f.write_fmt(format_args!(
+                            "{out} = info[({offset} * 2 * info[0]) + {index}];\n"
+                        )),
+                        factor => f.write_fmt(format_args!(
+                            "{out} = info[({offset} * 2 * info[0]) + {index}] / {factor};\n"
+                        )),
+                    }
+                }
+            },
+            Instruction::Stride { dim, position, out } => f.write_fmt(format_args!(
+                "{out} = info[({position} * rank_2) + {dim} + 1];\n"
+            )),
+            Instruction::Shape { dim, position, out } => f.write_fmt(format_args!(
+                "{out} = info[({position} * rank_2) + rank + {dim} + 1];\n"
+            )),
+            Instruction::If { cond, instructions } => {
+                f.write_fmt(format_args!("if ({cond}) {{\n"))?;
+                for i in instructions {
+                    f.write_fmt(format_args!("{i}"))?;
+                }
+                f.write_str("}\n")
+            }
+            Instruction::IfElse {
+                cond,
+                instructions_if,
+                instructions_else,
+            } => {
+                f.write_fmt(format_args!("if ({cond}) {{\n"))?;
+                for i in instructions_if {
+                    f.write_fmt(format_args!("{i}"))?;
+                }
+                f.write_str("} else {\n")?;
+                for i in instructions_else {
+                    f.write_fmt(format_args!("{i}"))?;
+                }
+                f.write_str("}\n")
+            }
+            Instruction::Switch {
+                value,
+                instructions_default,
+                cases,
+            } => {
+                f.write_fmt(format_args!("switch ({value}) {{\n"))?;
+                for (values, block) in cases {
+                    for val in values {
+                        f.write_fmt(format_args!("case {val}:\n"))?;
+                    }
+                    f.write_str("{\n")?;
+                    for i in block {
+                        f.write_fmt(format_args!("{i}"))?;
+                    }
+                    f.write_str("}\nbreak;\n")?;
+                }
+                f.write_str("default: {\n")?;
+                for i in instructions_default {
+                    f.write_fmt(format_args!("{i}"))?;
+                }
+                f.write_str("}\n}\n")
+            }
+            Instruction::RangeLoop {
+                i,
+                start,
+                end,
+                step,
+                instructions,
+            } => {
+                let item = i.item();
+                let increment = step
+                    .map(|step| format!("{i} += {step}"))
+                    .unwrap_or_else(|| format!("++{i}"));
+
+                f.write_fmt(format_args!(
+                    "for ({item} {i} = {start}; {i} < {end}; {increment}) {{\n"
+                ))?;
+                for instruction in instructions {
+                    f.write_fmt(format_args!("{instruction}"))?;
+                }
+                f.write_str("}\n")
+            }
+            Instruction::Loop { instructions } => {
+                f.write_str("while (true) {\n")?;
+                for i in instructions {
+                    f.write_fmt(format_args!("{i}"))?;
+                }
+                f.write_str("}\n")
+            }
+            Instruction::Call {
+                function,
+                inputs,
+                output,
+                args,
+            } => {
+                let args = inputs
+                    .iter()
+                    .map(|input| input.to_string())
+                    .chain(args.iter().cloned())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                match output {
+                    Some(output) => f.write_fmt(format_args!("{output} = {function}({args});\n")),
+                    None => f.write_fmt(format_args!("{function}({args});\n")),
+                }
+            }
+            Instruction::Return { output } => match output {
+                Some(output) => f.write_fmt(format_args!("return {output};\n")),
+                None => f.write_str("return;\n"),
+            },
+            Instruction::Break => f.write_str("break;\n"),
+            Instruction::SyncLocal => f.write_str("barrier(CLK_LOCAL_MEM_FENCE);\n"),
+            Instruction::SyncGlobal => f.write_str("barrier(CLK_GLOBAL_MEM_FENCE);\n"),
+            Instruction::Atomic {
+                func,
+                lhs,
+                rhs,
+                out,
+            } => f.write_fmt(format_args!("{out} = {func}({lhs}, {rhs});\n")),
+            // There are no atomic loads and stores before OpenCL C 2.0, they are emulated with
+            // read-modify-write operations.
+            Instruction::AtomicLoad { input, out } => {
+                f.write_fmt(format_args!("{out} = atomic_add({input}, 0);\n"))
+            }
+            Instruction::AtomicStore { input, out } => {
+                f.write_fmt(format_args!("atomic_xchg({out}, {input});\n"))
+            }
+            Instruction::AtomicCompareAndSwap {
+                input,
+                cmp,
+                val,
+                out,
+            } => f.write_fmt(format_args!(
+                "{out} = atomic_cmpxchg({input}, {cmp}, {val});\n"
+            )),
+            Instruction::Subcube(op) => f.write_fmt(format_args!("{op}")),
+        }
+    }
+}
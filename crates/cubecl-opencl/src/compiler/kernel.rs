@@ -0,0 +1,227 @@
+use std::collections::BTreeSet;
+use std::fmt::Display;
+
+use cubecl_core::{ir::CubeDim, CompilerRepresentation};
+
+use super::{Builtin, Extension, Instruction, Item, Variable};
+
+/// The name of the kernel function in the generated program.
+pub const ENTRY_POINT: &str = "kernel_main";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Binding {
+    pub name: String,
+    pub item: Item,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedMemory {
+    pub index: u16,
+    pub item: Item,
+    pub size: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalArray {
+    pub index: u16,
+    pub item: Item,
+    pub depth: u8,
+    pub size: u32,
+}
+
+/// The OpenCL extensions enabled with a pragma.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Pragmas {
+    pub fp16: bool,
+    pub fp64: bool,
+    pub subgroups: bool,
+    pub subgroup_arithmetic: bool,
+    pub subgroup_shuffle: bool,
+    pub subgroup_shuffle_relative: bool,
+    pub subgroup_ballot: bool,
+}
+
+/// A body is composed of a list of [instructions](Instruction), preceded by the builtins and the
+/// arrays it declares.
+#[derive(Debug, Clone, Default)]
+pub struct Body {
+    pub instructions: Vec<Instruction>,
+    pub shared_memories: Vec<SharedMemory>,
+    pub local_arrays: Vec<LocalArray>,
+    pub builtins: BTreeSet<Builtin>,
+    pub rank: bool,
+    pub stride_or_shape: bool,
+}
+
+/// A function called by the kernel.
+///
+/// The inputs are declared as parameters, followed by all the bindings of the kernel, which are
+/// the only global memory the function can access.
+#[derive(Debug, Clone)]
+pub struct Function {
+    pub name: String,
+    pub inputs: Vec<Variable>,
+    pub output: Option<Variable>,
+    pub body: Body,
+}
+
+#[derive(Debug, Clone)]
+pub struct ComputeKernel {
+    pub inputs: Vec<Binding>,
+    pub outputs: Vec<Binding>,
+    pub named: Vec<Binding>,
+    pub cube_dim: CubeDim,
+    pub pragmas: Pragmas,
+    pub extensions: Vec<Extension>,
+    pub functions: Vec<Function>,
+    pub body: Body,
+}
+
+impl CompilerRepresentation for ComputeKernel {
+    fn shared_memory_size(&self) -> usize {
+        self.body
+            .shared_memories
+            .iter()
+            .map(|shared| {
+                shared.size as usize * shared.item.vectorization as usize * shared.item.elem.size()
+            })
+            .sum()
+    }
+}
+
+impl ComputeKernel {
+    fn bindings(&self) -> impl Iterator<Item = &Binding> {
+        self.inputs
+            .iter()
+            .chain(self.outputs.iter())
+            .chain(self.named.iter())
+    }
+}
+
+impl Display for ComputeKernel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}", self.pragmas))?;
+
+        for extension in self.extensions.iter() {
+            f.write_fmt(format_args!("{extension}"))?;
+        }
+
+        let bindings = self
+            .bindings()
+            .map(|binding| format!("__global {}* {}", binding.item, binding.name))
+            .collect::<Vec<_>>();
+
+        for function in self.functions.iter() {
+            let output = match &function.output {
+                Some(output) => output.item().to_string(),
+                None => "void".to_string(),
+            };
+            let params = function
+                .inputs
+                .iter()
+                .map(|input| format!("{} {input}", input.item()))
+                .chain(bindings.iter().cloned())
+                .collect::<Vec<_>>();
+
+            f.write_fmt(format_args!(
+                "\n{output} {}({}) {{\n{}",
+                function.name,
+                params.join(", "),
+                function.body
+            ))?;
+
+            if let Some(output) = &function.output {
+                f.write_fmt(format_args!("return {output};\n"))?;
+            }
+
+            f.write_str("}\n")?;
+        }
+
+        f.write_fmt(format_args!(
+            "
+__kernel __attribute__((reqd_work_group_size({}, {}, {})))
+void {ENTRY_POINT}(
+{}
+) {{
+{}}}
+",
+            self.cube_dim.x,
+            self.cube_dim.y,
+            self.cube_dim.z,
+            bindings.join(",\n"),
+            self.body
+        ))
+    }
+}
+
+impl Display for Pragmas {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.fp16 {
+            f.write_str("#pragma OPENCL EXTENSION cl_khr_fp16 : enable\n")?;
+        }
+
+        if self.fp64 {
+            f.write_str("#pragma OPENCL EXTENSION cl_khr_fp64 : enable\n")?;
+        }
+
+        if self.subgroups {
+            f.write_str("#pragma OPENCL EXTENSION cl_khr_subgroups : enable\n")?;
+        }
+
+        if self.subgroup_arithmetic {
+            f.write_str(
+                "#pragma OPENCL EXTENSION cl_khr_subgroup_non_uniform_arithmetic : enable\n",
+            )?;
+        }
+
+        if self.subgroup_shuffle {
+            f.write_str("#pragma OPENCL EXTENSION cl_khr_subgroup_shuffle : enable\n")?;
+        }
+
+        if self.subgroup_shuffle_relative {
+            f.write_str("#pragma OPENCL EXTENSION cl_khr_subgroup_shuffle_relative : enable\n")?;
+        }
+
+        if self.subgroup_ballot {
+            f.write_str("#pragma OPENCL EXTENSION cl_khr_subgroup_ballot : enable\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Body {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for builtin in self.builtins.iter() {
+            f.write_fmt(format_args!("uint {builtin} = {};\n", builtin.expression()))?;
+        }
+
+        if self.rank || self.stride_or_shape {
+            f.write_str("uint rank = info[0];\n")?;
+        }
+
+        if self.stride_or_shape {
+            f.write_str("uint rank_2 = rank * 2;\n")?;
+        }
+
+        for shared in self.shared_memories.iter() {
+            f.write_fmt(format_args!(
+                "__local {} shared_memory_{}[{}];\n",
+                shared.item, shared.index, shared.size
+            ))?;
+        }
+
+        for array in self.local_arrays.iter() {
+            f.write_fmt(format_args!(
+                "{} l_arr_{}_{}[{}];\n",
+                array.item, array.index, array.depth, array.size
+            ))?;
+        }
+
+        for instruction in self.instructions.iter() {
+            f.write_fmt(format_args!("{instruction}"))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,13 @@
+mod base;
+mod element;
+mod extension;
+mod instruction;
+mod kernel;
+mod subcube;
+
+pub use base::*;
+pub use element::*;
+pub use extension::*;
+pub use instruction::*;
+pub use kernel::*;
+pub use subcube::*;
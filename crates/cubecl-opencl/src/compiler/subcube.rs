@@ -0,0 +1,193 @@
+use std::fmt::Display;
+
+use super::{Elem, Variable};
+
+/// The operations over the work-items of a sub-group, which is the subcube of OpenCL.
+///
+/// The elect, vote and broadcast functions come from `cl_khr_subgroups`, while the reductions
+/// and the scans other than the sum, the minimum and the maximum come from
+/// `cl_khr_subgroup_non_uniform_arithmetic`. The shuffles come from `cl_khr_subgroup_shuffle` and
+/// `cl_khr_subgroup_shuffle_relative`, the ballot from `cl_khr_subgroup_ballot`.
+#[derive(Debug, Clone)]
+pub enum Subcube {
+    Elect {
+        out: Variable,
+    },
+    All {
+        input: Variable,
+        out: Variable,
+    },
+    Any {
+        input: Variable,
+        out: Variable,
+    },
+    Broadcast {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    Sum {
+        input: Variable,
+        out: Variable,
+    },
+    Prod {
+        input: Variable,
+        out: Variable,
+    },
+    And {
+        input: Variable,
+        out: Variable,
+    },
+    Or {
+        input: Variable,
+        out: Variable,
+    },
+    Xor {
+        input: Variable,
+        out: Variable,
+    },
+    Min {
+        input: Variable,
+        out: Variable,
+    },
+    Max {
+        input: Variable,
+        out: Variable,
+    },
+    Shuffle {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    ShuffleXor {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    ShuffleUp {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    ShuffleDown {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    InclusiveSum {
+        input: Variable,
+        out: Variable,
+    },
+    ExclusiveSum {
+        input: Variable,
+        out: Variable,
+    },
+    InclusiveProd {
+        input: Variable,
+        out: Variable,
+    },
+    ExclusiveProd {
+        input: Variable,
+        out: Variable,
+    },
+    Ballot {
+        input: Variable,
+        out: Variable,
+    },
+}
+
+impl Display for Subcube {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Subcube::Elect { out } => {
+                f.write_fmt(format_args!("{out} = get_sub_group_local_id() == 0;\n"))
+            }
+            Subcube::All { input, out } => format_componentwise(f, "sub_group_all", input, out, ""),
+            Subcube::Any { input, out } => format_componentwise(f, "sub_group_any", input, out, ""),
+            Subcube::Broadcast { lhs, rhs, out } => {
+                format_componentwise(f, "sub_group_broadcast", lhs, out, &format!(", {rhs}"))
+            }
+            Subcube::Sum { input, out } => {
+                format_componentwise(f, "sub_group_reduce_add", input, out, "")
+            }
+            Subcube::Prod { input, out } => {
+                format_componentwise(f, "sub_group_non_uniform_reduce_mul", input, out, "")
+            }
+            Subcube::And { input, out } => {
+                format_componentwise(f, "sub_group_non_uniform_reduce_and", input, out, "")
+            }
+            Subcube::Or { input, out } => {
+                format_componentwise(f, "sub_group_non_uniform_reduce_or", input, out, "")
+            }
+            Subcube::Xor { input, out } => {
+                format_componentwise(f, "sub_group_non_uniform_reduce_xor", input, out, "")
+            }
+            Subcube::Min { input, out } => {
+                format_componentwise(f, "sub_group_reduce_min", input, out, "")
+            }
+            Subcube::Max { input, out } => {
+                format_componentwise(f, "sub_group_reduce_max", input, out, "")
+            }
+            Subcube::Shuffle { lhs, rhs, out } => {
+                format_componentwise(f, "sub_group_shuffle", lhs, out, &format!(", {rhs}"))
+            }
+            Subcube::ShuffleXor { lhs, rhs, out } => {
+                format_componentwise(f, "sub_group_shuffle_xor", lhs, out, &format!(", {rhs}"))
+            }
+            Subcube::ShuffleUp { lhs, rhs, out } => {
+                format_componentwise(f, "sub_group_shuffle_up", lhs, out, &format!(", {rhs}"))
+            }
+            Subcube::ShuffleDown { lhs, rhs, out } => {
+                format_componentwise(f, "sub_group_shuffle_down", lhs, out, &format!(", {rhs}"))
+            }
+            Subcube::InclusiveSum { input, out } => {
+                format_componentwise(f, "sub_group_scan_inclusive_add", input, out, "")
+            }
+            Subcube::ExclusiveSum { input, out } => {
+                format_componentwise(f, "sub_group_scan_exclusive_add", input, out, "")
+            }
+            Subcube::InclusiveProd { input, out } => format_componentwise(
+                f,
+                "sub_group_non_uniform_scan_inclusive_mul",
+                input,
+                out,
+                "",
+            ),
+            Subcube::ExclusiveProd { input, out } => format_componentwise(
+                f,
+                "sub_group_non_uniform_scan_exclusive_mul",
+                input,
+                out,
+                "",
+            ),
+            Subcube::Ballot { input, out } => {
+                f.write_fmt(format_args!("{out} = sub_group_ballot({input});\n"))
+            }
+        }
+    }
+}
+
+/// The sub-group functions only take scalars, so they are applied to each component of a vector.
+fn format_componentwise(
+    f: &mut std::fmt::Formatter<'_>,
+    func: &str,
+    input: &Variable,
+    out: &Variable,
+    args: &str,
+) -> std::fmt::Result {
+    let item = out.item();
+    if !item.is_vectorized() {
+        return f.write_fmt(format_args!("{out} = {func}({input}{args});\n"));
+    }
+
+    let components = (0..item.vectorization)
+        .map(|i| match item.elem {
+            // The true components of the boolean vectors are -1.
+            Elem::Bool => format!("-({func}({input}.s{i:x}{args}) != 0)"),
+            _ => format!("{func}({input}.s{i:x}{args})"),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    f.write_fmt(format_args!("{out} = ({item})({components});\n"))
+}
@@ -0,0 +1,6 @@
+mod server;
+mod storage;
+pub(crate) mod sys;
+
+pub use server::*;
+pub use storage::*;
@@ -0,0 +1,790 @@
+use super::storage::OpenClStorage;
+use super::sys::{self, cl, cl_result, ClError};
+use super::OpenClResource;
+use crate::compiler::ENTRY_POINT;
+use core::ffi::c_void;
+use core::time::Duration;
+use cubecl_common::benchmark::{TimestampsError, TimestampsResult};
+use cubecl_common::reader::{reader_from_concrete, Reader};
+use cubecl_common::sync_type::SyncType;
+use cubecl_core::compute::DebugInformation;
+use cubecl_core::ir::CubeDim;
+use cubecl_core::FeatureSet;
+use cubecl_core::{prelude::*, KernelId};
+use cubecl_runtime::debug::DebugLogger;
+use cubecl_runtime::profiler::{ProfileEventKind, ProfileStart, ProfileTrace, Profiler};
+use cubecl_runtime::ExecutionMode;
+use cubecl_runtime::{
+    memory_management::{MemoryManagement, MemoryUsage},
+    server::{self, ComputeError, ComputeServer},
+    staging::StagingBuffer,
+    stream::{EventId, StreamId},
+};
+use std::collections::HashMap;
+use std::ffi::CString;
+
+#[derive(Debug)]
+pub struct OpenClServer<MM: MemoryManagement<OpenClStorage>> {
+    state: OpenClServerState<MM>,
+    logger: DebugLogger,
+    profiler: Profiler,
+}
+
+pub(crate) enum OpenClServerState<MM: MemoryManagement<OpenClStorage>> {
+    Uninitialized {
+        device: sys::cl_device_id,
+        init: Box<dyn Fn(sys::cl_device_id) -> OpenClContext<MM>>,
+    },
+    Initialized {
+        ctx: Box<OpenClContext<MM>>,
+    },
+}
+
+impl<MM: MemoryManagement<OpenClStorage>> core::fmt::Debug for OpenClServerState<MM> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Context")
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct OpenClContext<MM: MemoryManagement<OpenClStorage>> {
+    device: sys::cl_device_id,
+    context: sys::cl_context,
+    queue: sys::cl_command_queue,
+    /// The command queues created with [create_stream](ComputeServer::try_create_stream), the
+    /// queue at index `i` has the id `i + 1` since the default queue has the id zero.
+    queues: Vec<sys::cl_command_queue>,
+    /// The events recorded since the last synchronization.
+    events: HashMap<EventId, sys::cl_event>,
+    num_events: u64,
+    /// The staging buffers of the uploads submitted since the last synchronization, which must be
+    /// kept alive until the copies are completed.
+    uploads: Vec<StagingBuffer>,
+    /// The bindings used since the last synchronization while other queues exist, which are kept
+    /// alive so that their memory isn't reused on another queue before the tasks are completed.
+    bindings: Vec<MM::Binding>,
+    memory_management: MM,
+    module_names: HashMap<KernelId, CompiledKernel>,
+    timestamps: Timestamps,
+}
+
+/// The profiling information of the kernels, used to measure the time spent executing them.
+#[derive(Debug)]
+enum Timestamps {
+    Disabled,
+    /// The events of the first kernel launched since the last measure and of the last kernel
+    /// launched on each queue, so that the time the queues were idle isn't included.
+    Enabled {
+        first: Option<sys::cl_event>,
+        last: HashMap<u32, sys::cl_event>,
+    },
+}
+
+#[derive(Debug)]
+struct CompiledKernel {
+    cube_dim: CubeDim,
+    kernel: sys::cl_kernel,
+}
+
+unsafe impl<MM: MemoryManagement<OpenClStorage>> Send for OpenClServer<MM> {}
+
+impl<MM: MemoryManagement<OpenClStorage>> OpenClServer<MM> {
+    fn read_sync(&mut self, binding: server::Binding<Self>) -> Result<Vec<u8>, ComputeError> {
+        let ctx = self.get_context();
+        let resource = ctx.memory_management.try_get_resource(binding.memory)?;
+
+        let mut data = vec![0; resource.size()];
+        // The read blocks until the queue is done with the previous commands.
+        unsafe {
+            cl_result((cl().clEnqueueReadBuffer)(
+                ctx.queue,
+                resource.buffer,
+                sys::CL_TRUE,
+                resource.offset(),
+                data.len(),
+                data.as_mut_ptr() as *mut c_void,
+                0,
+                core::ptr::null(),
+                core::ptr::null_mut(),
+            ))
+            .map_err(execution_error)?;
+        };
+        ctx.sync();
+        Ok(data)
+    }
+}
+
+impl<MM: MemoryManagement<OpenClStorage>> ComputeServer for OpenClServer<MM> {
+    type Kernel = Box<dyn CubeTask>;
+    type DispatchOptions = CubeCount<Self>;
+    type Storage = OpenClStorage;
+    type MemoryManagement = MM;
+    type FeatureSet = FeatureSet;
+
+    fn try_read(&mut self, binding: server::Binding<Self>) -> Result<Reader, ComputeError> {
+        let started = self.profiler.now();
+        let data = self.read_sync(binding)?;
+
+        self.profiler
+            .register(started, ProfileEventKind::Read { size: data.len() });
+
+        Ok(reader_from_concrete(data))
+    }
+
+    fn try_create(&mut self, data: &[u8]) -> Result<server::Handle<Self>, ComputeError> {
+        let started = self.profiler.now();
+        let handle = self.try_empty(data.len())?;
+        let ctx = self.get_context();
+
+        let resource = ctx.try_get_resource(handle.clone().binding().memory)?;
+
+        // The data is borrowed, so the write blocks until it is copied.
+        unsafe {
+            enqueue_write(&resource, data, ctx.queue, sys::CL_TRUE).map_err(execution_error)?;
+        }
+
+        self.profiler
+            .register(started, ProfileEventKind::Create { size: data.len() });
+
+        Ok(handle)
+    }
+
+    fn try_empty(&mut self, size: usize) -> Result<server::Handle<Self>, ComputeError> {
+        let ctx = self.get_context();
+        let handle = ctx.memory_management.try_reserve(size, &[])?;
+        Ok(server::Handle::new(handle, size))
+    }
+
+    fn try_staging(&mut self, size: usize) -> Result<StagingBuffer, ComputeError> {
+        // The implementation decides how to transfer host memory, so any buffer can be used.
+        Ok(vec![0; size].into())
+    }
+
+    fn try_upload(
+        &mut self,
+        buffer: StagingBuffer,
+        stream: StreamId,
+    ) -> Result<server::Handle<Self>, ComputeError> {
+        let started = self.profiler.now();
+        let queue = self.get_context().queue(stream)?;
+        let handle = self.try_empty(buffer.len())?;
+        let ctx = self.get_context();
+
+        let resource = ctx.try_get_resource(handle.clone().binding().memory)?;
+
+        unsafe {
+            enqueue_write(&resource, &buffer[..], queue, sys::CL_FALSE).map_err(execution_error)?;
+        }
+
+        let size = buffer.len();
+        ctx.uploads.push(buffer);
+        self.profiler
+            .register(started, ProfileEventKind::Create { size });
+
+        Ok(handle)
+    }
+
+    unsafe fn try_execute(
+        &mut self,
+        kernel: Self::Kernel,
+        count: Self::DispatchOptions,
+        bindings: Vec<server::Binding<Self>>,
+        mode: ExecutionMode,
+        stream: StreamId,
+    ) -> Result<(), ComputeError> {
+        // Fail before compiling the kernel when the queue doesn't exist.
+        self.get_context().queue(stream)?;
+
+        let profile = self
+            .profiler
+            .is_activated()
+            .then(|| (kernel.name(), kernel.id().to_string()));
+
+        let mut kernel_id = kernel.id();
+        kernel_id.mode(mode);
+
+        let count = match count {
+            CubeCount::Static(x, y, z) => (x, y, z),
+            // OpenCL has no dynamic dispatch, the dispatch settings are read from the buffer
+            // instead.
+            CubeCount::Dynamic(binding) => {
+                let data = self.read_sync(binding)?;
+                let data = bytemuck::cast_slice(&data);
+                assert!(
+                    data.len() == 3,
+                    "Dynamic cube count should contain 3 values"
+                );
+                (data[0], data[1], data[2])
+            }
+        };
+
+        if !self.get_context().module_names.contains_key(&kernel_id) {
+            let started = self.profiler.now();
+            let (ctx, logger) = self.get_context_with_logger();
+            ctx.compile_kernel(&kernel_id, kernel, logger, mode)?;
+
+            if let Some((name, id)) = &profile {
+                self.profiler.register(
+                    started,
+                    ProfileEventKind::Compile {
+                        name: name.to_string(),
+                        id: id.clone(),
+                    },
+                );
+            }
+        }
+
+        let ctx = self.get_context();
+        let resources = bindings
+            .into_iter()
+            .map(|binding| ctx.try_get_resource(binding.memory))
+            .collect::<Result<Vec<_>, _>>()?;
+        let binding_sizes = resources.iter().map(|resource| resource.size()).collect();
+
+        let started = profile.is_some().then(ProfileStart::now);
+        ctx.execute_task(kernel_id, count, resources, stream)?;
+
+        if let Some((name, id)) = profile {
+            // Wait for the kernel to complete to measure its execution time.
+            ctx.sync();
+            self.profiler.register(
+                started,
+                ProfileEventKind::Execute {
+                    name: name.into(),
+                    id,
+                    cube_count: Some([count.0, count.1, count.2]),
+                    binding_sizes,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    fn try_create_stream(&mut self) -> Result<StreamId, ComputeError> {
+        let ctx = self.get_context();
+        // The memory released by the pending tasks of the default queue isn't tracked, so it must
+        // be available before another queue can reuse it.
+        ctx.sync();
+        let queue = create_queue(ctx.context, ctx.device).map_err(execution_error)?;
+        ctx.queues.push(queue);
+
+        Ok(StreamId {
+            index: ctx.queues.len() as u32,
+        })
+    }
+
+    fn try_record_event(&mut self, stream: StreamId) -> Result<EventId, ComputeError> {
+        let ctx = self.get_context();
+        let queue = ctx.queue(stream)?;
+
+        // A marker without events completes when all the previous commands are completed.
+        let event = unsafe {
+            let mut event = core::ptr::null_mut();
+            cl_result((cl().clEnqueueMarkerWithWaitList)(
+                queue,
+                0,
+                core::ptr::null(),
+                &mut event,
+            ))
+            .map_err(execution_error)?;
+            event
+        };
+
+        ctx.num_events += 1;
+        let id = EventId {
+            index: ctx.num_events,
+        };
+        ctx.events.insert(id, event);
+
+        Ok(id)
+    }
+
+    fn try_wait_event(&mut self, stream: StreamId, event: EventId) -> Result<(), ComputeError> {
+        let ctx = self.get_context();
+        let queue = ctx.queue(stream)?;
+
+        if event.index > ctx.num_events {
+            return Err(ComputeError::InvalidEvent(format!(
+                "The event {} doesn't exist, only {} events were recorded.",
+                event.index, ctx.num_events
+            )));
+        }
+
+        // Events are cleared when every queue is synchronized, so they are already completed.
+        let Some(event) = ctx.events.get(&event) else {
+            return Ok(());
+        };
+
+        unsafe {
+            cl_result((cl().clEnqueueBarrierWithWaitList)(
+                queue,
+                1,
+                event,
+                core::ptr::null_mut(),
+            ))
+            .map_err(execution_error)
+        }
+    }
+
+    fn sync(&mut self, sync_type: SyncType) {
+        match sync_type {
+            // Synchronize the queues if waiting.
+            SyncType::Wait => {
+                let ctx = self.get_context();
+                ctx.sync();
+            }
+            // Nothing to do - all tasks are already submitted to the queue.
+            SyncType::Flush => (),
+        }
+    }
+
+    fn memory_usage(&mut self) -> MemoryUsage {
+        let ctx = self.get_context();
+        ctx.memory_management.memory_usage()
+    }
+
+    fn memory_cleanup(&mut self) {
+        let ctx = self.get_context();
+        ctx.memory_management.cleanup();
+        // The buffers are only freed once the pending kernels are completed.
+        ctx.memory_management.storage().perform_deallocations();
+    }
+
+    fn enable_timestamps(&mut self) {
+        let ctx = self.get_context();
+        if let Timestamps::Disabled = ctx.timestamps {
+            ctx.timestamps = Timestamps::Enabled {
+                first: None,
+                last: HashMap::new(),
+            };
+        }
+    }
+
+    fn disable_timestamps(&mut self) {
+        let ctx = self.get_context();
+        if let Timestamps::Enabled { first, last } = &mut ctx.timestamps {
+            release_events(
+                first
+                    .take()
+                    .into_iter()
+                    .chain(last.drain().map(|(_, event)| event)),
+            );
+        }
+        ctx.timestamps = Timestamps::Disabled;
+    }
+
+    fn timestamps_enabled(&mut self) -> bool {
+        !matches!(self.get_context().timestamps, Timestamps::Disabled)
+    }
+
+    fn sync_elapsed(&mut self) -> TimestampsResult {
+        let ctx = self.get_context();
+        let (first, last) = match &mut ctx.timestamps {
+            Timestamps::Disabled => return Err(TimestampsError::Disabled),
+            Timestamps::Enabled { first, last } => (first.take(), core::mem::take(last)),
+        };
+        ctx.sync();
+
+        let Some(first) = first else {
+            // No kernel was launched since the last measure.
+            return Ok(Duration::ZERO);
+        };
+
+        let events = core::iter::once(first)
+            .chain(last.into_values())
+            .collect::<Vec<_>>();
+        let elapsed = unsafe {
+            let start = profiling_info(first, sys::CL_PROFILING_COMMAND_START);
+            let end = events
+                .iter()
+                .map(|event| profiling_info(*event, sys::CL_PROFILING_COMMAND_END))
+                .try_fold(0, |end, value| value.map(|value| end.max(value)));
+            release_events(events);
+            end.map_err(timestamps_error)? - start.map_err(timestamps_error)?
+        };
+
+        // The profiling information is in nanoseconds.
+        Ok(Duration::from_nanos(elapsed))
+    }
+
+    fn start_profile(&mut self) {
+        self.profiler.start();
+    }
+
+    fn end_profile(&mut self) -> ProfileTrace {
+        self.profiler.end()
+    }
+
+    fn get_resource(
+        &mut self,
+        binding: server::Binding<Self>,
+    ) -> <Self::Storage as cubecl_runtime::storage::ComputeStorage>::Resource {
+        let ctx = self.get_context();
+        ctx.memory_management.get_resource(binding.memory)
+    }
+}
+
+impl<MM: MemoryManagement<OpenClStorage>> OpenClContext<MM> {
+    pub fn new(
+        memory_management: MM,
+        device: sys::cl_device_id,
+        context: sys::cl_context,
+        queue: sys::cl_command_queue,
+    ) -> Self {
+        Self {
+            device,
+            context,
+            memory_management,
+            module_names: HashMap::new(),
+            queue,
+            queues: Vec::new(),
+            events: HashMap::new(),
+            num_events: 0,
+            uploads: Vec::new(),
+            bindings: Vec::new(),
+            timestamps: Timestamps::Disabled,
+        }
+    }
+
+    fn sync(&mut self) {
+        unsafe {
+            cl_result((cl().clFinish)(self.queue)).unwrap();
+            for queue in self.queues.iter() {
+                cl_result((cl().clFinish)(*queue)).unwrap();
+            }
+            for (_, event) in self.events.drain() {
+                let _ = (cl().clReleaseEvent)(event);
+            }
+        };
+        self.uploads.clear();
+        self.bindings.clear();
+        let storage = self.memory_management.storage();
+        storage.flush();
+        storage.perform_deallocations();
+    }
+
+    fn compile_kernel(
+        &mut self,
+        kernel_id: &KernelId,
+        kernel: Box<dyn CubeTask>,
+        logger: &mut DebugLogger,
+        mode: ExecutionMode,
+    ) -> Result<(), ComputeError> {
+        let mut kernel_compiled = kernel.try_compile(mode)?;
+
+        if logger.is_activated() {
+            kernel_compiled.debug_info = Some(DebugInformation::new("c", kernel_id.clone()));
+            kernel_compiled.ir = kernel.ir();
+        }
+
+        let cube_dim = kernel_compiled.cube_dim;
+        let kernel_compiled = logger.debug(kernel_compiled);
+
+        let program = compile_program(self.context, self.device, &kernel_compiled.source)?;
+
+        let entry_point = CString::new(ENTRY_POINT).unwrap();
+        let kernel = unsafe {
+            let mut error = sys::CL_SUCCESS;
+            let kernel = (cl().clCreateKernel)(program, entry_point.as_ptr(), &mut error);
+            // The kernel keeps a reference to its program.
+            let _ = (cl().clReleaseProgram)(program);
+            cl_result(error).map_err(execution_error)?;
+            kernel
+        };
+
+        self.module_names
+            .insert(kernel_id.clone(), CompiledKernel { cube_dim, kernel });
+
+        Ok(())
+    }
+
+    fn execute_task(
+        &mut self,
+        kernel_id: KernelId,
+        dispatch_count: (u32, u32, u32),
+        resources: Vec<OpenClResource>,
+        stream: StreamId,
+    ) -> Result<(), ComputeError> {
+        let queue = self.queue(stream)?;
+        let kernel = self.module_names.get(&kernel_id).unwrap();
+        let cube_dim = kernel.cube_dim;
+
+        // The arguments are copied when set, but the kernel object keeps them, so the kernel can't
+        // be launched concurrently from multiple threads, which the server never does.
+        for (index, resource) in resources.iter().enumerate() {
+            let binding = resource.as_binding();
+            unsafe {
+                cl_result((cl().clSetKernelArg)(
+                    kernel.kernel,
+                    index as u32,
+                    core::mem::size_of::<sys::cl_mem>(),
+                    &binding as *const sys::cl_mem as *const c_void,
+                ))
+                .map_err(execution_error)?;
+            }
+        }
+
+        // Unlike CUDA, the global size is given in units instead of cubes.
+        let local_size = [
+            cube_dim.x as usize,
+            cube_dim.y as usize,
+            cube_dim.z as usize,
+        ];
+        let global_size = [
+            dispatch_count.0 as usize * local_size[0],
+            dispatch_count.1 as usize * local_size[1],
+            dispatch_count.2 as usize * local_size[2],
+        ];
+
+        // OpenCL fails to launch empty dispatches.
+        if global_size.contains(&0) {
+            return Ok(());
+        }
+
+        let measure = matches!(self.timestamps, Timestamps::Enabled { .. });
+        let mut event = core::ptr::null_mut();
+        unsafe {
+            cl_result((cl().clEnqueueNDRangeKernel)(
+                queue,
+                kernel.kernel,
+                3,
+                core::ptr::null(),
+                global_size.as_ptr(),
+                local_size.as_ptr(),
+                0,
+                core::ptr::null(),
+                if measure {
+                    &mut event
+                } else {
+                    core::ptr::null_mut()
+                },
+            ))
+            .map_err(execution_error)?;
+        }
+
+        if let Timestamps::Enabled { first, last } = &mut self.timestamps {
+            if measure {
+                match first {
+                    None => *first = Some(event),
+                    Some(_) => release_events(last.insert(stream.index, event)),
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<MM: MemoryManagement<OpenClStorage>> Drop for OpenClContext<MM> {
+    fn drop(&mut self) {
+        unsafe {
+            for (_, event) in self.events.drain() {
+                let _ = (cl().clReleaseEvent)(event);
+            }
+            // The pending uploads must complete before their staging buffers are dropped.
+            for queue in self.queues.drain(..) {
+                let _ = (cl().clFinish)(queue);
+                let _ = (cl().clReleaseCommandQueue)(queue);
+            }
+        }
+    }
+}
+
+impl<MM: MemoryManagement<OpenClStorage>> OpenClContext<MM> {
+    /// Get the resource of the binding, which is kept alive until the next synchronization when
+    /// it can be used concurrently with the tasks of other queues.
+    fn try_get_resource(&mut self, binding: MM::Binding) -> Result<OpenClResource, ComputeError> {
+        if !self.queues.is_empty() {
+            self.bindings.push(binding.clone());
+        }
+        self.memory_management.try_get_resource(binding)
+    }
+
+    fn queue(&self, stream: StreamId) -> Result<sys::cl_command_queue, ComputeError> {
+        match stream.index {
+            0 => Ok(self.queue),
+            index => self.queues.get(index as usize - 1).copied().ok_or_else(|| {
+                ComputeError::InvalidStream(format!(
+                    "The stream {index} doesn't exist, only {} streams were created.",
+                    self.queues.len()
+                ))
+            }),
+        }
+    }
+}
+
+impl<MM: MemoryManagement<OpenClStorage>> OpenClServer<MM> {
+    /// Create a new OpenCL server.
+    pub(crate) fn new(
+        device: sys::cl_device_id,
+        init: Box<dyn Fn(sys::cl_device_id) -> OpenClContext<MM>>,
+    ) -> Self {
+        Self {
+            state: OpenClServerState::Uninitialized { device, init },
+            logger: DebugLogger::new(),
+            profiler: Profiler::new(),
+        }
+    }
+
+    fn get_context(&mut self) -> &mut OpenClContext<MM> {
+        self.get_context_with_logger().0
+    }
+
+    fn get_context_with_logger(&mut self) -> (&mut OpenClContext<MM>, &mut DebugLogger) {
+        if let OpenClServerState::Uninitialized { device, init } = &self.state {
+            let ctx = init(*device);
+            self.state = OpenClServerState::Initialized { ctx: Box::new(ctx) };
+        }
+        if let OpenClServerState::Initialized { ctx } = &mut self.state {
+            (ctx, &mut self.logger)
+        } else {
+            panic!("Context should be initialized");
+        }
+    }
+}
+
+/// Build the source of a kernel for the given device, returning the program.
+pub(crate) fn compile_program(
+    context: sys::cl_context,
+    device: sys::cl_device_id,
+    source: &str,
+) -> Result<sys::cl_program, ComputeError> {
+    let source_c = CString::new(source).unwrap();
+    let options = CString::new("").unwrap();
+
+    unsafe {
+        let mut error = sys::CL_SUCCESS;
+        let program = (cl().clCreateProgramWithSource)(
+            context,
+            1,
+            &source_c.as_ptr(),
+            core::ptr::null(),
+            &mut error,
+        );
+        cl_result(error).map_err(|err| ComputeError::Compilation(format!("{err:?}")))?;
+
+        let built = cl_result((cl().clBuildProgram)(
+            program,
+            1,
+            &device,
+            options.as_ptr(),
+            core::ptr::null(),
+            core::ptr::null_mut(),
+        ));
+
+        match built {
+            Ok(()) => Ok(program),
+            Err(_) => {
+                let log = program_log(program, device);
+                let _ = (cl().clReleaseProgram)(program);
+                let mut message = "[Compilation Error] ".to_string();
+                for line in log.split('\n') {
+                    if !line.is_empty() {
+                        message += format!("\n    {line}").as_str();
+                    }
+                }
+                Err(ComputeError::Compilation(format!(
+                    "{message}\n[Source]  \n{source}"
+                )))
+            }
+        }
+    }
+}
+
+unsafe fn program_log(program: sys::cl_program, device: sys::cl_device_id) -> String {
+    let mut size = 0;
+    if cl_result((cl().clGetProgramBuildInfo)(
+        program,
+        device,
+        sys::CL_PROGRAM_BUILD_LOG,
+        0,
+        core::ptr::null_mut(),
+        &mut size,
+    ))
+    .is_err()
+    {
+        return String::new();
+    }
+
+    let mut log = vec![0u8; size];
+    if cl_result((cl().clGetProgramBuildInfo)(
+        program,
+        device,
+        sys::CL_PROGRAM_BUILD_LOG,
+        size,
+        log.as_mut_ptr() as *mut c_void,
+        core::ptr::null_mut(),
+    ))
+    .is_err()
+    {
+        return String::new();
+    }
+
+    String::from_utf8_lossy(&log)
+        .trim_end_matches('\0')
+        .to_string()
+}
+
+/// Create an in-order command queue, with profiling enabled for the timestamps.
+pub(crate) fn create_queue(
+    context: sys::cl_context,
+    device: sys::cl_device_id,
+) -> Result<sys::cl_command_queue, ClError> {
+    let mut error = sys::CL_SUCCESS;
+    let queue = unsafe {
+        (cl().clCreateCommandQueue)(context, device, sys::CL_QUEUE_PROFILING_ENABLE, &mut error)
+    };
+    cl_result(error)?;
+    Ok(queue)
+}
+
+unsafe fn enqueue_write(
+    resource: &OpenClResource,
+    data: &[u8],
+    queue: sys::cl_command_queue,
+    blocking: sys::cl_bool,
+) -> Result<(), ClError> {
+    cl_result((cl().clEnqueueWriteBuffer)(
+        queue,
+        resource.buffer,
+        blocking,
+        resource.offset(),
+        data.len(),
+        data.as_ptr() as *const c_void,
+        0,
+        core::ptr::null(),
+        core::ptr::null_mut(),
+    ))
+}
+
+unsafe fn profiling_info(
+    event: sys::cl_event,
+    info: sys::cl_profiling_info,
+) -> Result<u64, ClError> {
+    let mut value: sys::cl_ulong = 0;
+    cl_result((cl().clGetEventProfilingInfo)(
+        event,
+        info,
+        core::mem::size_of::<sys::cl_ulong>(),
+        &mut value as *mut sys::cl_ulong as *mut c_void,
+        core::ptr::null_mut(),
+    ))?;
+    Ok(value)
+}
+
+fn release_events(events: impl IntoIterator<Item = sys::cl_event>) {
+    for event in events {
+        unsafe {
+            let _ = (cl().clReleaseEvent)(event);
+        }
+    }
+}
+
+fn execution_error(err: ClError) -> ComputeError {
+    ComputeError::Execution(format!("{err:?}"))
+}
+
+fn timestamps_error(err: ClError) -> TimestampsError {
+    TimestampsError::Unknown(format!("{err:?}"))
+}
@@ -0,0 +1,193 @@
+use super::sys::{self, cl, cl_result};
+use core::ffi::c_void;
+use cubecl_runtime::{
+    server::ComputeError,
+    storage::{ComputeStorage, StorageHandle, StorageId, StorageUtilization},
+};
+use std::collections::HashMap;
+
+/// Buffer storage for OpenCL.
+pub struct OpenClStorage {
+    memory: HashMap<StorageId, sys::cl_mem>,
+    deallocations: Vec<StorageId>,
+    context: sys::cl_context,
+    activate_slices: HashMap<ActiveResource, sys::cl_mem>,
+}
+
+#[derive(new, Debug, Hash, PartialEq, Eq, Clone)]
+struct ActiveResource {
+    buffer: usize,
+    kind: OpenClResourceKind,
+}
+
+unsafe impl Send for OpenClStorage {}
+
+impl core::fmt::Debug for OpenClStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(format!("OpenClStorage {{ context: {:?} }}", self.context).as_str())
+    }
+}
+
+/// Keeps the buffers in a hashmap with ids as key.
+impl OpenClStorage {
+    /// Create a new storage allocating in the given context.
+    pub fn new(context: sys::cl_context) -> Self {
+        Self {
+            memory: HashMap::new(),
+            deallocations: Vec::new(),
+            context,
+            activate_slices: HashMap::new(),
+        }
+    }
+
+    /// Actually deallocates buffers tagged to be deallocated.
+    ///
+    /// The buffers are only freed by OpenCL once the commands using them are completed.
+    pub fn perform_deallocations(&mut self) {
+        for id in self.deallocations.drain(..) {
+            if let Some(buffer) = self.memory.remove(&id) {
+                unsafe {
+                    cl_result((cl().clReleaseMemObject)(buffer)).unwrap();
+                }
+            }
+        }
+    }
+
+    /// Release the sub-buffers created for the slices.
+    pub fn flush(&mut self) {
+        for (_, buffer) in self.activate_slices.drain() {
+            unsafe {
+                cl_result((cl().clReleaseMemObject)(buffer)).unwrap();
+            }
+        }
+    }
+}
+
+/// The memory resource that can be allocated for OpenCL.
+#[derive(new, Debug)]
+pub struct OpenClResource {
+    /// The whole buffer, used for the copies.
+    pub buffer: sys::cl_mem,
+    /// The buffer passed to the kernels, which is a sub-buffer for slices.
+    pub binding: sys::cl_mem,
+    /// How the resource is used.
+    pub kind: OpenClResourceKind,
+}
+
+unsafe impl Send for OpenClResource {}
+
+impl OpenClResource {
+    /// Return the binding view of the buffer.
+    pub fn as_binding(&self) -> sys::cl_mem {
+        self.binding
+    }
+
+    /// Return the buffer size.
+    pub fn size(&self) -> usize {
+        match self.kind {
+            OpenClResourceKind::Full { size } => size,
+            OpenClResourceKind::Slice { size, offset: _ } => size,
+        }
+    }
+
+    /// Return the buffer offset.
+    pub fn offset(&self) -> usize {
+        match self.kind {
+            OpenClResourceKind::Full { size: _ } => 0,
+            OpenClResourceKind::Slice { size: _, offset } => offset,
+        }
+    }
+}
+
+/// How the resource is used, either as a slice or fully.
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub enum OpenClResourceKind {
+    /// Represents an entire buffer.
+    Full { size: usize },
+    /// A slice over a buffer.
+    Slice { size: usize, offset: usize },
+}
+
+impl ComputeStorage for OpenClStorage {
+    type Resource = OpenClResource;
+
+    fn get(&mut self, handle: &StorageHandle) -> Self::Resource {
+        let buffer = *self.memory.get(&handle.id).unwrap();
+
+        match handle.utilization {
+            StorageUtilization::Full(size) => {
+                OpenClResource::new(buffer, buffer, OpenClResourceKind::Full { size })
+            }
+            StorageUtilization::Slice { offset, size } => {
+                let kind = OpenClResourceKind::Slice { size, offset };
+                let key = ActiveResource::new(buffer as usize, kind.clone());
+
+                // Kernels can't take a pointer with an offset, so the slice is bound as a
+                // sub-buffer, which must stay alive until the task is completed.
+                let binding = *self
+                    .activate_slices
+                    .entry(key)
+                    .or_insert_with(|| unsafe { create_sub_buffer(buffer, offset, size).unwrap() });
+
+                OpenClResource::new(buffer, binding, kind)
+            }
+        }
+    }
+
+    fn try_alloc(&mut self, size: usize) -> Result<StorageHandle, ComputeError> {
+        let id = StorageId::new();
+        let mut error = sys::CL_SUCCESS;
+        let buffer = unsafe {
+            (cl().clCreateBuffer)(
+                self.context,
+                sys::CL_MEM_READ_WRITE,
+                size,
+                core::ptr::null_mut(),
+                &mut error,
+            )
+        };
+        match cl_result(error) {
+            Ok(()) => {}
+            Err(err)
+                if err.0 == sys::CL_MEM_OBJECT_ALLOCATION_FAILURE
+                    || err.0 == sys::CL_OUT_OF_RESOURCES
+                    || err.0 == sys::CL_INVALID_BUFFER_SIZE =>
+            {
+                return Err(ComputeError::OutOfMemory {
+                    size,
+                    reason: format!("{err:?}"),
+                })
+            }
+            Err(err) => return Err(ComputeError::Execution(format!("{err:?}"))),
+        };
+        self.memory.insert(id, buffer);
+        Ok(StorageHandle::new(id, StorageUtilization::Full(size)))
+    }
+
+    fn dealloc(&mut self, id: StorageId) {
+        self.deallocations.push(id);
+    }
+}
+
+/// The offset must be a multiple of the base address alignment of the device, which the memory
+/// management uses as its alignment.
+unsafe fn create_sub_buffer(
+    buffer: sys::cl_mem,
+    offset: usize,
+    size: usize,
+) -> Result<sys::cl_mem, sys::ClError> {
+    let region = sys::cl_buffer_region {
+        origin: offset,
+        size,
+    };
+    let mut error = sys::CL_SUCCESS;
+    let sub_buffer = (cl().clCreateSubBuffer)(
+        buffer,
+        sys::CL_MEM_READ_WRITE,
+        sys::CL_BUFFER_CREATE_TYPE_REGION,
+        &region as *const sys::cl_buffer_region as *const c_void,
+        &mut error,
+    );
+    cl_result(error)?;
+    Ok(sub_buffer)
+}
@@ -0,0 +1,212 @@
+//! Bindings to the OpenCL ICD loader.
+//!
+//! The loader is loaded the first time it is used, so that the crate can be built and the kernels
+//! generated on machines without OpenCL. It dispatches the calls to the installed implementations,
+//! e.g. the GPU drivers or PoCL.
+#![allow(non_camel_case_types, non_snake_case)]
+
+use core::ffi::{c_char, c_void};
+use libloading::Library;
+use std::sync::OnceLock;
+
+pub type cl_int = i32;
+pub type cl_uint = u32;
+pub type cl_ulong = u64;
+pub type cl_bool = cl_uint;
+pub type cl_bitfield = cl_ulong;
+pub type cl_device_type = cl_bitfield;
+pub type cl_mem_flags = cl_bitfield;
+pub type cl_command_queue_properties = cl_bitfield;
+pub type cl_device_info = cl_uint;
+pub type cl_program_build_info = cl_uint;
+pub type cl_profiling_info = cl_uint;
+pub type cl_buffer_create_type = cl_uint;
+pub type cl_platform_id = *mut c_void;
+pub type cl_device_id = *mut c_void;
+pub type cl_context = *mut c_void;
+pub type cl_command_queue = *mut c_void;
+pub type cl_mem = *mut c_void;
+pub type cl_program = *mut c_void;
+pub type cl_kernel = *mut c_void;
+pub type cl_event = *mut c_void;
+
+pub const CL_SUCCESS: cl_int = 0;
+pub const CL_MEM_OBJECT_ALLOCATION_FAILURE: cl_int = -4;
+pub const CL_OUT_OF_RESOURCES: cl_int = -5;
+pub const CL_INVALID_BUFFER_SIZE: cl_int = -61;
+
+pub const CL_TRUE: cl_bool = 1;
+pub const CL_FALSE: cl_bool = 0;
+
+pub const CL_DEVICE_TYPE_ALL: cl_device_type = 0xFFFFFFFF;
+pub const CL_DEVICE_MAX_MEM_ALLOC_SIZE: cl_device_info = 0x1010;
+pub const CL_DEVICE_MEM_BASE_ADDR_ALIGN: cl_device_info = 0x1019;
+pub const CL_DEVICE_EXTENSIONS: cl_device_info = 0x1030;
+pub const CL_QUEUE_PROFILING_ENABLE: cl_command_queue_properties = 1 << 1;
+pub const CL_MEM_READ_WRITE: cl_mem_flags = 1 << 0;
+pub const CL_BUFFER_CREATE_TYPE_REGION: cl_buffer_create_type = 0x1220;
+pub const CL_PROGRAM_BUILD_LOG: cl_program_build_info = 0x1183;
+pub const CL_PROFILING_COMMAND_START: cl_profiling_info = 0x1282;
+pub const CL_PROFILING_COMMAND_END: cl_profiling_info = 0x1283;
+
+/// The region of a buffer viewed by a sub-buffer.
+#[repr(C)]
+pub struct cl_buffer_region {
+    pub origin: usize,
+    pub size: usize,
+}
+
+macro_rules! library {
+    ($library:ident, [$($file:literal),*], { $($function:ident($($arg:ty),*) -> $output:ty;)* }) => {
+        pub struct $library {
+            $(pub $function: unsafe extern "C" fn($($arg),*) -> $output,)*
+            _library: Library,
+        }
+
+        impl $library {
+            fn load() -> Result<Self, String> {
+                let files = [$($file),*];
+                let library = files
+                    .iter()
+                    .find_map(|file| unsafe { Library::new(file).ok() })
+                    .ok_or_else(|| format!("Unable to load any of {files:?}"))?;
+
+                unsafe {
+                    Ok(Self {
+                        $($function: *library
+                            .get(concat!(stringify!($function), "\0").as_bytes())
+                            .map_err(|err| format!("{err}"))?,)*
+                        _library: library,
+                    })
+                }
+            }
+        }
+    };
+}
+
+library!(OpenCl, ["libOpenCL.so.1", "libOpenCL.so", "OpenCL.dll", "/System/Library/Frameworks/OpenCL.framework/OpenCL"], {
+    clGetPlatformIDs(cl_uint, *mut cl_platform_id, *mut cl_uint) -> cl_int;
+    clGetDeviceIDs(cl_platform_id, cl_device_type, cl_uint, *mut cl_device_id, *mut cl_uint) -> cl_int;
+    clGetDeviceInfo(cl_device_id, cl_device_info, usize, *mut c_void, *mut usize) -> cl_int;
+    clCreateContext(
+        *const isize,
+        cl_uint,
+        *const cl_device_id,
+        *const c_void,
+        *mut c_void,
+        *mut cl_int
+    ) -> cl_context;
+    clCreateCommandQueue(cl_context, cl_device_id, cl_command_queue_properties, *mut cl_int) -> cl_command_queue;
+    clFinish(cl_command_queue) -> cl_int;
+    clReleaseCommandQueue(cl_command_queue) -> cl_int;
+    clCreateBuffer(cl_context, cl_mem_flags, usize, *mut c_void, *mut cl_int) -> cl_mem;
+    clCreateSubBuffer(cl_mem, cl_mem_flags, cl_buffer_create_type, *const c_void, *mut cl_int) -> cl_mem;
+    clReleaseMemObject(cl_mem) -> cl_int;
+    clEnqueueWriteBuffer(
+        cl_command_queue,
+        cl_mem,
+        cl_bool,
+        usize,
+        usize,
+        *const c_void,
+        cl_uint,
+        *const cl_event,
+        *mut cl_event
+    ) -> cl_int;
+    clEnqueueReadBuffer(
+        cl_command_queue,
+        cl_mem,
+        cl_bool,
+        usize,
+        usize,
+        *mut c_void,
+        cl_uint,
+        *const cl_event,
+        *mut cl_event
+    ) -> cl_int;
+    clCreateProgramWithSource(cl_context, cl_uint, *const *const c_char, *const usize, *mut cl_int) -> cl_program;
+    clBuildProgram(cl_program, cl_uint, *const cl_device_id, *const c_char, *const c_void, *mut c_void) -> cl_int;
+    clGetProgramBuildInfo(cl_program, cl_device_id, cl_program_build_info, usize, *mut c_void, *mut usize) -> cl_int;
+    clReleaseProgram(cl_program) -> cl_int;
+    clCreateKernel(cl_program, *const c_char, *mut cl_int) -> cl_kernel;
+    clSetKernelArg(cl_kernel, cl_uint, usize, *const c_void) -> cl_int;
+    clEnqueueNDRangeKernel(
+        cl_command_queue,
+        cl_kernel,
+        cl_uint,
+        *const usize,
+        *const usize,
+        *const usize,
+        cl_uint,
+        *const cl_event,
+        *mut cl_event
+    ) -> cl_int;
+    clEnqueueMarkerWithWaitList(cl_command_queue, cl_uint, *const cl_event, *mut cl_event) -> cl_int;
+    clEnqueueBarrierWithWaitList(cl_command_queue, cl_uint, *const cl_event, *mut cl_event) -> cl_int;
+    clGetEventProfilingInfo(cl_event, cl_profiling_info, usize, *mut c_void, *mut usize) -> cl_int;
+    clReleaseEvent(cl_event) -> cl_int;
+});
+
+/// The OpenCL ICD loader, panics when it isn't installed.
+pub fn cl() -> &'static OpenCl {
+    static OPENCL: OnceLock<Result<OpenCl, String>> = OnceLock::new();
+
+    match OPENCL.get_or_init(OpenCl::load) {
+        Ok(cl) => cl,
+        Err(err) => panic!(
+            "OpenCL not found, please ensure that an ICD loader and an implementation are installed: {err}"
+        ),
+    }
+}
+
+/// An error returned by OpenCL.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ClError(pub cl_int);
+
+impl core::fmt::Debug for ClError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!("ClError({}, {})", self.0, error_name(self.0)))
+    }
+}
+
+pub fn cl_result(error: cl_int) -> Result<(), ClError> {
+    match error {
+        CL_SUCCESS => Ok(()),
+        err => Err(ClError(err)),
+    }
+}
+
+/// OpenCL has no function returning the name of an error.
+fn error_name(error: cl_int) -> &'static str {
+    match error {
+        -1 => "CL_DEVICE_NOT_FOUND",
+        -2 => "CL_DEVICE_NOT_AVAILABLE",
+        -3 => "CL_COMPILER_NOT_AVAILABLE",
+        -4 => "CL_MEM_OBJECT_ALLOCATION_FAILURE",
+        -5 => "CL_OUT_OF_RESOURCES",
+        -6 => "CL_OUT_OF_HOST_MEMORY",
+        -7 => "CL_PROFILING_INFO_NOT_AVAILABLE",
+        -11 => "CL_BUILD_PROGRAM_FAILURE",
+        -13 => "CL_MISALIGNED_SUB_BUFFER_OFFSET",
+        -30 => "CL_INVALID_VALUE",
+        -33 => "CL_INVALID_DEVICE",
+        -34 => "CL_INVALID_CONTEXT",
+        -36 => "CL_INVALID_COMMAND_QUEUE",
+        -38 => "CL_INVALID_MEM_OBJECT",
+        -44 => "CL_INVALID_PROGRAM",
+        -46 => "CL_INVALID_KERNEL_NAME",
+        -48 => "CL_INVALID_KERNEL",
+        -49 => "CL_INVALID_ARG_INDEX",
+        -50 => "CL_INVALID_ARG_VALUE",
+        -51 => "CL_INVALID_ARG_SIZE",
+        -52 => "CL_INVALID_KERNEL_ARGS",
+        -53 => "CL_INVALID_WORK_DIMENSION",
+        -54 => "CL_INVALID_WORK_GROUP_SIZE",
+        -55 => "CL_INVALID_WORK_ITEM_SIZE",
+        -58 => "CL_INVALID_EVENT",
+        -61 => "CL_INVALID_BUFFER_SIZE",
+        -63 => "CL_INVALID_GLOBAL_WORK_SIZE",
+        -1001 => "CL_PLATFORM_NOT_FOUND_KHR",
+        _ => "CL_UNKNOWN_ERROR",
+    }
+}
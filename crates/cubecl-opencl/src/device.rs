@@ -0,0 +1,5 @@
+/// An OpenCL device, indexed among the devices of all the platforms installed.
+#[derive(new, Clone, Debug, PartialEq, Eq, Default, Hash)]
+pub struct OpenClDevice {
+    pub index: usize,
+}
@@ -0,0 +1,228 @@
+use core::ffi::c_void;
+use cubecl_core::{
+    ir::{Elem, FloatKind},
+    Feature, FeatureSet, Runtime,
+};
+use cubecl_runtime::{
+    channel::MutexComputeChannel,
+    client::ComputeClient,
+    memory_management::dynamic::{DynamicMemoryManagement, DynamicMemoryManagementOptions},
+    ComputeRuntime,
+};
+use std::sync::Arc;
+
+use crate::{
+    compiler::OpenClCompiler,
+    compute::{
+        create_queue,
+        sys::{self, cl, cl_result, ClError},
+        OpenClContext, OpenClServer, OpenClStorage,
+    },
+    device::OpenClDevice,
+};
+
+#[derive(Debug)]
+pub struct OpenClRuntime;
+
+static RUNTIME: ComputeRuntime<OpenClDevice, Server, MutexComputeChannel<Server>> =
+    ComputeRuntime::new();
+
+type Server = OpenClServer<DynamicMemoryManagement<OpenClStorage>>;
+
+impl Runtime for OpenClRuntime {
+    type Compiler = OpenClCompiler;
+    type Server = OpenClServer<DynamicMemoryManagement<OpenClStorage>>;
+
+    type Channel = MutexComputeChannel<OpenClServer<DynamicMemoryManagement<OpenClStorage>>>;
+    type Device = OpenClDevice;
+
+    fn client(device: &Self::Device) -> ComputeClient<Self::Server, Self::Channel> {
+        fn init(
+            device: sys::cl_device_id,
+        ) -> OpenClContext<DynamicMemoryManagement<OpenClStorage>> {
+            let context = create_context(device).unwrap();
+            let queue = create_queue(context, device).unwrap();
+
+            // Sub-buffers must start at the base address alignment of the device.
+            let alignment = device_info::<sys::cl_uint>(device, sys::CL_DEVICE_MEM_BASE_ADDR_ALIGN)
+                .unwrap() as usize
+                / 8;
+            let max_alloc =
+                device_info::<sys::cl_ulong>(device, sys::CL_DEVICE_MAX_MEM_ALLOC_SIZE).unwrap();
+            let max_chunk_size = usize::min(max_alloc as usize, 2048 + 512 * 1024 * 1024);
+
+            let storage = OpenClStorage::new(context);
+            let options = DynamicMemoryManagementOptions::preset(max_chunk_size, alignment);
+            let memory_management = DynamicMemoryManagement::new(storage, options);
+            OpenClContext::new(memory_management, device, context, queue)
+        }
+
+        RUNTIME.client(device, move || {
+            let device_id = device_id(device.index);
+            let server = OpenClServer::new(device_id, Box::new(init));
+            let mut features = FeatureSet::new(&[]);
+
+            // The products and the bitwise reductions are only in the non-uniform arithmetic.
+            let extensions = device_extensions(device_id);
+            if [
+                "cl_khr_subgroups",
+                "cl_khr_subgroup_non_uniform_arithmetic",
+                "cl_khr_subgroup_shuffle",
+                "cl_khr_subgroup_shuffle_relative",
+                "cl_khr_subgroup_ballot",
+            ]
+            .iter()
+            .all(|extension| extensions.contains(extension))
+            {
+                features.register(Feature::Subcube);
+            }
+
+            let (f16, f32) = (Elem::Float(FloatKind::F16), Elem::Float(FloatKind::F32));
+            features.register_emulated_cmma(&[(f32, f32, f32)]);
+            if extensions.contains("cl_khr_fp16") {
+                features.register_emulated_cmma(&[(f16, f16, f16), (f16, f16, f32)]);
+            }
+
+            ComputeClient::new(MutexComputeChannel::new(server), Arc::new(features))
+        })
+    }
+
+    fn name() -> &'static str {
+        "opencl"
+    }
+
+    fn require_array_lengths() -> bool {
+        true
+    }
+}
+
+/// Find the device at the given index, counting the devices of every platform.
+fn device_id(index: usize) -> sys::cl_device_id {
+    let devices = platform_ids()
+        .into_iter()
+        .flat_map(|platform| device_ids(platform).unwrap_or_default())
+        .collect::<Vec<_>>();
+
+    match devices.get(index) {
+        Some(device) => *device,
+        None => panic!(
+            "OpenCL device {index} not found, only {} devices are available",
+            devices.len()
+        ),
+    }
+}
+
+fn platform_ids() -> Vec<sys::cl_platform_id> {
+    unsafe {
+        let mut num_platforms = 0;
+        // The ICD loader fails when no platform is installed.
+        if cl_result((cl().clGetPlatformIDs)(
+            0,
+            core::ptr::null_mut(),
+            &mut num_platforms,
+        ))
+        .is_err()
+        {
+            return Vec::new();
+        }
+
+        let mut platforms = vec![core::ptr::null_mut(); num_platforms as usize];
+        cl_result((cl().clGetPlatformIDs)(
+            num_platforms,
+            platforms.as_mut_ptr(),
+            core::ptr::null_mut(),
+        ))
+        .unwrap();
+        platforms
+    }
+}
+
+fn device_ids(platform: sys::cl_platform_id) -> Result<Vec<sys::cl_device_id>, ClError> {
+    unsafe {
+        let mut num_devices = 0;
+        cl_result((cl().clGetDeviceIDs)(
+            platform,
+            sys::CL_DEVICE_TYPE_ALL,
+            0,
+            core::ptr::null_mut(),
+            &mut num_devices,
+        ))?;
+
+        let mut devices = vec![core::ptr::null_mut(); num_devices as usize];
+        cl_result((cl().clGetDeviceIDs)(
+            platform,
+            sys::CL_DEVICE_TYPE_ALL,
+            num_devices,
+            devices.as_mut_ptr(),
+            core::ptr::null_mut(),
+        ))?;
+        Ok(devices)
+    }
+}
+
+fn device_info<T: bytemuck::Pod>(
+    device: sys::cl_device_id,
+    info: sys::cl_device_info,
+) -> Result<T, ClError> {
+    let mut value = T::zeroed();
+    unsafe {
+        cl_result((cl().clGetDeviceInfo)(
+            device,
+            info,
+            core::mem::size_of::<T>(),
+            &mut value as *mut T as *mut c_void,
+            core::ptr::null_mut(),
+        ))?;
+    }
+    Ok(value)
+}
+
+fn device_extensions(device: sys::cl_device_id) -> String {
+    unsafe {
+        let mut size = 0;
+        if cl_result((cl().clGetDeviceInfo)(
+            device,
+            sys::CL_DEVICE_EXTENSIONS,
+            0,
+            core::ptr::null_mut(),
+            &mut size,
+        ))
+        .is_err()
+        {
+            return String::new();
+        }
+
+        let mut extensions = vec![0u8; size];
+        if cl_result((cl().clGetDeviceInfo)(
+            device,
+            sys::CL_DEVICE_EXTENSIONS,
+            size,
+            extensions.as_mut_ptr() as *mut c_void,
+            core::ptr::null_mut(),
+        ))
+        .is_err()
+        {
+            return String::new();
+        }
+
+        String::from_utf8_lossy(&extensions)
+            .trim_end_matches('\0')
+            .to_string()
+    }
+}
+
+fn create_context(device: sys::cl_device_id) -> Result<sys::cl_context, ClError> {
+    let mut error = sys::CL_SUCCESS;
+    let context = unsafe {
+        (cl().clCreateContext)(
+            core::ptr::null(),
+            1,
+            &device,
+            core::ptr::null(),
+            core::ptr::null_mut(),
+            &mut error,
+        )
+    };
+    cl_result(error)?;
+    Ok(context)
+}
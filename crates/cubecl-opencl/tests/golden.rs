@@ -0,0 +1,109 @@
+use cubecl_core as cubecl;
+use cubecl_core::{
+    assert_golden,
+    compiler_tests::{self, compile, float},
+    ir::FloatKind,
+    prelude::*,
+};
+use cubecl_opencl::compiler::OpenClCompiler;
+
+#[cube]
+pub fn golden_shared_kernel(input: &Array<F32>, output: &mut Array<F32>) {
+    let mut shared = SharedMemory::<F32>::new(64);
+    shared[UNIT_POS] = input[ABSOLUTE_POS];
+
+    sync_units();
+
+    output[ABSOLUTE_POS] = shared[CUBE_DIM - UNIT_POS - UInt::new(1)];
+}
+
+#[cube]
+pub fn golden_subcube_prod_kernel(output: &mut Array<F32>) {
+    let val = output[UNIT_POS];
+    let sum = subcube_sum::<F32>(val);
+    let prod = subcube_prod::<F32>(val);
+
+    if UNIT_POS < SUBCUBE_DIM {
+        output[UNIT_POS] = sum + prod;
+    }
+}
+
+#[cube]
+pub fn golden_cmma_kernel(out: &mut Array<F32>) {
+    let c = cmma::Matrix::<F32>::new(
+        cmma::MatrixIdent::Accumulator,
+        16,
+        16,
+        16,
+        cmma::MatrixLayout::Undefined,
+    );
+    cmma::fill::<F32>(&c, F32::new(0.0));
+    cmma::store::<F32>(
+        out.as_slice_mut(),
+        &c,
+        UInt::new(16),
+        cmma::MatrixLayout::RowMajor,
+    );
+}
+
+#[test]
+fn golden_kernel_test() {
+    assert_golden!(
+        "kernel.cl",
+        compile::<OpenClCompiler>(compiler_tests::kernel())
+    );
+}
+
+#[test]
+fn golden_vectorized_test() {
+    assert_golden!(
+        "vectorized.cl",
+        compile::<OpenClCompiler>(compiler_tests::vectorized())
+    );
+}
+
+#[test]
+fn golden_shared_test() {
+    let mut builder = KernelBuilder::default();
+    let input = builder.input_array(float(FloatKind::F32));
+    let output = builder.output_array(float(FloatKind::F32));
+
+    golden_shared_kernel::__expand(&mut builder.context, input.into(), output.into());
+    let source = compile::<OpenClCompiler>(builder.build(KernelSettings::default()));
+
+    assert!(source.contains("__local float shared_memory_0[64];"));
+    assert!(source.contains("barrier(CLK_LOCAL_MEM_FENCE);"));
+    assert_golden!("shared.cl", source);
+}
+
+#[test]
+fn golden_subcube_test() {
+    let mut builder = KernelBuilder::default();
+    let output = builder.output_array(float(FloatKind::F32));
+
+    golden_subcube_prod_kernel::__expand(&mut builder.context, output.into());
+    let source = compile::<OpenClCompiler>(builder.build(KernelSettings::default()));
+
+    assert!(source.contains("sub_group_reduce_add("));
+    assert_golden!("subcube.cl", source);
+}
+
+#[test]
+fn golden_subcube_shuffle_test() {
+    let source = compile::<OpenClCompiler>(compiler_tests::subcube_shuffle());
+
+    assert!(source.contains("sub_group_shuffle_xor("));
+    assert_golden!("subcube_shuffle.cl", source);
+}
+
+#[test]
+fn golden_cmma_test() {
+    let mut builder = KernelBuilder::default();
+    let out = builder.output_array(float(FloatKind::F32));
+
+    golden_cmma_kernel::__expand(&mut builder.context, out.into());
+    let source = compile::<OpenClCompiler>(builder.build(KernelSettings::default()));
+
+    assert!(source.contains("barrier(CLK_LOCAL_MEM_FENCE)"));
+    assert_golden!("cmma.cl", source);
+}
@@ -0,0 +1,36 @@
+
+__kernel __attribute__((reqd_work_group_size(16, 16, 1)))
+void kernel_main(
+__global float* output_0,
+__global uint* info
+) {
+uint unit_pos = (get_local_id(2) * get_local_size(0) * get_local_size(1)) + (get_local_id(1) * get_local_size(0)) + get_local_id(0);
+float l_arr_0_0[256];
+uint l_0_0;
+uint l_0_1;
+l_0_0 = unit_pos % (uint)32;
+for (uint l_1_0 = l_0_0; l_1_0 < (uint)256; l_1_0 += (uint)32) {
+l_arr_0_0[l_1_0] = (float)0.0;
+}
+barrier(CLK_LOCAL_MEM_FENCE);
+l_0_1 = unit_pos % (uint)32;
+for (uint l_1_0 = l_0_1; l_1_0 < (uint)256; l_1_0 += (uint)32) {
+uint l_1_1;
+uint l_1_2;
+uint l_1_3;
+float l_1_4;
+l_1_1 = l_1_0 / (uint)16;
+l_1_2 = l_1_0 % (uint)16;
+l_1_3 = l_1_1 * (uint)16;
+l_1_3 = l_1_3 + l_1_2;
+l_1_4 = l_arr_0_0[l_1_0];
+uint l_1_5;
+bool l_1_6;
+l_1_5 = info[(1 * 2 * info[0]) + 1];
+l_1_6 = l_1_3 < l_1_5;
+if (l_1_6) {
+output_0[l_1_3] = l_1_4;
+}
+}
+barrier(CLK_LOCAL_MEM_FENCE);
+}
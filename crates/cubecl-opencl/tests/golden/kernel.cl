@@ -0,0 +1,55 @@
+
+float golden_scale_0(float l_0_0, __global float* input_0, __global float* output_0, __global uint* info) {
+float l_0_1;
+l_0_1 = l_0_0 * (float)2.0;
+return l_0_1;
+}
+
+__kernel __attribute__((reqd_work_group_size(16, 16, 1)))
+void kernel_main(
+__global float* input_0,
+__global float* output_0,
+__global uint* info
+) {
+uint unit_pos = (get_local_id(2) * get_local_size(0) * get_local_size(1)) + (get_local_id(1) * get_local_size(0)) + get_local_id(0);
+float l_0_0;
+uint l_0_1;
+float l_0_2;
+bool l_0_3;
+l_0_0 = (float)0.0;
+l_0_1 = info[(2 * 2 * info[0]) + 1];
+for (uint l_1_0 = (uint)0; l_1_0 < l_0_1; ++l_1_0) {
+uint l_1_1;
+bool l_1_2;
+l_1_1 = info[(2 * 2 * info[0]) + 1];
+l_1_2 = l_1_0 < l_1_1;
+if (l_1_2) {
+l_0_2 = input_0[l_1_0];
+} else {
+l_0_2 = (float)0.0;
+}
+l_0_3 = l_0_2 > (float)1.0;
+if (l_0_3) {
+uint l_2_0;
+bool l_2_1;
+l_2_0 = info[(2 * 2 * info[0]) + 1];
+l_2_1 = l_1_0 < l_2_0;
+if (l_2_1) {
+l_0_2 = input_0[l_1_0];
+} else {
+l_0_2 = (float)0.0;
+}
+l_0_2 = golden_scale_0(l_0_2, input_0, output_0, info);
+l_0_0 = l_0_0 + l_0_2;
+} else {
+l_0_0 = l_0_0 - (float)1e-7;
+}
+}
+uint l_0_4;
+bool l_0_5;
+l_0_4 = info[(2 * 2 * info[0]) + 2];
+l_0_5 = unit_pos < l_0_4;
+if (l_0_5) {
+output_0[unit_pos] = l_0_0;
+}
+}
@@ -0,0 +1,35 @@
+
+__kernel __attribute__((reqd_work_group_size(16, 16, 1)))
+void kernel_main(
+__global float* input_0,
+__global float* output_0,
+__global uint* info
+) {
+uint unit_pos = (get_local_id(2) * get_local_size(0) * get_local_size(1)) + (get_local_id(1) * get_local_size(0)) + get_local_id(0);
+uint cube_dim = get_local_size(0) * get_local_size(1) * get_local_size(2);
+uint absolute_pos = (get_global_id(2) * get_global_size(0) * get_global_size(1)) + (get_global_id(1) * get_global_size(0)) + get_global_id(0);
+__local float shared_memory_0[64];
+float l_0_0;
+uint l_0_1;
+uint l_0_2;
+bool l_0_3;
+l_0_2 = info[(2 * 2 * info[0]) + 1];
+l_0_3 = absolute_pos < l_0_2;
+if (l_0_3) {
+l_0_0 = input_0[absolute_pos];
+} else {
+l_0_0 = (float)0.0;
+}
+shared_memory_0[unit_pos] = l_0_0;
+barrier(CLK_LOCAL_MEM_FENCE);
+l_0_1 = cube_dim - unit_pos;
+l_0_1 = l_0_1 - (uint)1;
+l_0_0 = shared_memory_0[l_0_1];
+uint l_0_4;
+bool l_0_5;
+l_0_4 = info[(2 * 2 * info[0]) + 2];
+l_0_5 = absolute_pos < l_0_4;
+if (l_0_5) {
+output_0[absolute_pos] = l_0_0;
+}
+}
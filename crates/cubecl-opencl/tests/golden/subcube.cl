@@ -0,0 +1,37 @@
+#pragma OPENCL EXTENSION cl_khr_subgroups : enable
+#pragma OPENCL EXTENSION cl_khr_subgroup_non_uniform_arithmetic : enable
+
+__kernel __attribute__((reqd_work_group_size(16, 16, 1)))
+void kernel_main(
+__global float* output_0,
+__global uint* info
+) {
+uint unit_pos = (get_local_id(2) * get_local_size(0) * get_local_size(1)) + (get_local_id(1) * get_local_size(0)) + get_local_id(0);
+uint subcube_dim = get_sub_group_size();
+float l_0_0;
+float l_0_1;
+float l_0_2;
+bool l_0_3;
+uint l_0_4;
+bool l_0_5;
+l_0_4 = info[(1 * 2 * info[0]) + 1];
+l_0_5 = unit_pos < l_0_4;
+if (l_0_5) {
+l_0_0 = output_0[unit_pos];
+} else {
+l_0_0 = (float)0.0;
+}
+l_0_1 = sub_group_reduce_add(l_0_0);
+l_0_2 = sub_group_non_uniform_reduce_mul(l_0_0);
+l_0_3 = unit_pos < subcube_dim;
+if (l_0_3) {
+l_0_0 = l_0_1 + l_0_2;
+uint l_1_0;
+bool l_1_1;
+l_1_0 = info[(1 * 2 * info[0]) + 1];
+l_1_1 = unit_pos < l_1_0;
+if (l_1_1) {
+output_0[unit_pos] = l_0_0;
+}
+}
+}
@@ -0,0 +1,49 @@
+#pragma OPENCL EXTENSION cl_khr_subgroups : enable
+#pragma OPENCL EXTENSION cl_khr_subgroup_shuffle : enable
+#pragma OPENCL EXTENSION cl_khr_subgroup_shuffle_relative : enable
+#pragma OPENCL EXTENSION cl_khr_subgroup_ballot : enable
+
+__kernel __attribute__((reqd_work_group_size(16, 16, 1)))
+void kernel_main(
+__global float* output_0,
+__global uint4* output_1,
+__global uint* info
+) {
+uint unit_pos = (get_local_id(2) * get_local_size(0) * get_local_size(1)) + (get_local_id(1) * get_local_size(0)) + get_local_id(0);
+float l_0_0;
+float l_0_1;
+float l_0_2;
+float l_0_3;
+bool l_0_4;
+uint4 l_0_5;
+uint l_0_6;
+bool l_0_7;
+l_0_6 = info[(2 * 2 * info[0]) + 1];
+l_0_7 = unit_pos < l_0_6;
+if (l_0_7) {
+l_0_0 = output_0[unit_pos];
+} else {
+l_0_0 = (float)0.0;
+}
+l_0_1 = sub_group_shuffle_xor(l_0_0, (uint)1);
+l_0_2 = sub_group_shuffle_down(l_0_0, (uint)1);
+l_0_3 = sub_group_scan_exclusive_add(l_0_0);
+l_0_1 = l_0_1 + l_0_2;
+l_0_1 = l_0_1 + l_0_3;
+uint l_0_8;
+bool l_0_9;
+l_0_8 = info[(2 * 2 * info[0]) + 1];
+l_0_9 = unit_pos < l_0_8;
+if (l_0_9) {
+output_0[unit_pos] = l_0_1;
+}
+l_0_4 = l_0_0 > (float)0.0;
+l_0_5 = sub_group_ballot(l_0_4);
+uint l_0_10;
+bool l_0_11;
+l_0_10 = info[(2 * 2 * info[0]) + 2] / 4;
+l_0_11 = unit_pos < l_0_10;
+if (l_0_11) {
+output_1[unit_pos] = l_0_5;
+}
+}
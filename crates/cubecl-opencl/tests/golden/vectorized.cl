@@ -0,0 +1,74 @@
+
+// The builtin pow function is undefined for a negative base, even when the exponent is an integer.
+float powf_float(float lhs, float rhs) {
+    if (rhs == 0.0) {
+        return 1.0;
+    }
+    float modulo = fmod(rhs, (float)2.0);
+    if (modulo == 0.0) {
+        return pow(fabs(lhs), rhs);
+    } else if (fabs(modulo) == 1.0 && lhs < 0.0) {
+        return -pow(-lhs, rhs);
+    }
+    return pow(lhs, rhs);
+}
+
+float4 powf_float4(float4 lhs, float4 rhs) {
+    return (float4)(powf_float(lhs.s0, rhs.s0), powf_float(lhs.s1, rhs.s1), powf_float(lhs.s2, rhs.s2), powf_float(lhs.s3, rhs.s3));
+}
+
+__kernel __attribute__((reqd_work_group_size(16, 16, 1)))
+void kernel_main(
+__global float4* input_0,
+__global float4* input_1,
+__global float4* output_0,
+__global uint* info
+) {
+uint absolute_pos = (get_global_id(2) * get_global_size(0) * get_global_size(1)) + (get_global_id(1) * get_global_size(0)) + get_global_id(0);
+uint l_0_0;
+bool l_0_1;
+float4 l_0_2;
+float4 l_0_3;
+float4 l_0_4;
+l_0_0 = info[(3 * 2 * info[0]) + 3] / 4;
+l_0_1 = absolute_pos < l_0_0;
+if (l_0_1) {
+uint l_1_0;
+bool l_1_1;
+l_1_0 = info[(3 * 2 * info[0]) + 1] / 4;
+l_1_1 = absolute_pos < l_1_0;
+if (l_1_1) {
+l_0_2 = input_0[absolute_pos];
+} else {
+l_0_2 = (float4)((float)0.0);
+}
+l_0_2 = erf(l_0_2);
+uint l_1_2;
+bool l_1_3;
+l_1_2 = info[(3 * 2 * info[0]) + 1] / 4;
+l_1_3 = absolute_pos < l_1_2;
+if (l_1_3) {
+l_0_3 = input_0[absolute_pos];
+} else {
+l_0_3 = (float4)((float)0.0);
+}
+uint l_1_4;
+bool l_1_5;
+l_1_4 = info[(3 * 2 * info[0]) + 2] / 4;
+l_1_5 = absolute_pos < l_1_4;
+if (l_1_5) {
+l_0_4 = input_1[absolute_pos];
+} else {
+l_0_4 = (float4)((float)0.0);
+}
+l_0_3 = powf_float4(l_0_3, l_0_4);
+l_0_2 = l_0_2 + l_0_3;
+uint l_1_6;
+bool l_1_7;
+l_1_6 = info[(3 * 2 * info[0]) + 3] / 4;
+l_1_7 = absolute_pos < l_1_6;
+if (l_1_7) {
+output_0[absolute_pos] = l_0_2;
+}
+}
+}
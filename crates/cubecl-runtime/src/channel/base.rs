@@ -1,16 +1,29 @@
 use crate::{
-    server::{Binding, ComputeServer, Handle},
+    memory_management::MemoryUsage,
+    profiler::ProfileTrace,
+    server::{Binding, ComputeError, ComputeServer, Handle},
+    staging::StagingBuffer,
     storage::ComputeStorage,
+    stream::{EventId, StreamId},
     ExecutionMode,
 };
 use alloc::vec::Vec;
-use cubecl_common::{reader::Reader, sync_type::SyncType};
+use cubecl_common::{benchmark::TimestampsResult, reader::Reader, sync_type::SyncType};
 
 /// The ComputeChannel trait links the ComputeClient to the ComputeServer
 /// while ensuring thread-safety
 pub trait ComputeChannel<Server: ComputeServer>: Clone + core::fmt::Debug + Send + Sync {
     /// Given a binding, returns owned resource as bytes
-    fn read(&self, binding: Binding<Server>) -> Reader;
+    fn try_read(&self, binding: Binding<Server>) -> Result<Reader, ComputeError>;
+
+    /// Given a binding, returns owned resource as bytes
+    ///
+    /// # Panics
+    ///
+    /// If the resource can't be read.
+    fn read(&self, binding: Binding<Server>) -> Reader {
+        self.try_read(binding).unwrap_or_else(|err| panic!("{err}"))
+    }
 
     /// Given a resource handle, return the storage resource.
     fn get_resource(
@@ -19,24 +32,109 @@ pub trait ComputeChannel<Server: ComputeServer>: Clone + core::fmt::Debug + Send
     ) -> <Server::Storage as ComputeStorage>::Resource;
 
     /// Given a resource as bytes, stores it and returns the resource handle
-    fn create(&self, data: &[u8]) -> Handle<Server>;
+    fn try_create(&self, data: &[u8]) -> Result<Handle<Server>, ComputeError>;
+
+    /// Given a resource as bytes, stores it and returns the resource handle
+    ///
+    /// # Panics
+    ///
+    /// If the memory can't be reserved.
+    fn create(&self, data: &[u8]) -> Handle<Server> {
+        self.try_create(data).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Reserves `size` bytes in the storage, and returns a handle over them
+    fn try_empty(&self, size: usize) -> Result<Handle<Server>, ComputeError>;
 
     /// Reserves `size` bytes in the storage, and returns a handle over them
-    fn empty(&self, size: usize) -> Handle<Server>;
+    ///
+    /// # Panics
+    ///
+    /// If the memory can't be reserved.
+    fn empty(&self, size: usize) -> Handle<Server> {
+        self.try_empty(size).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Allocates a host buffer of `size` bytes from which the data can be uploaded without
+    /// blocking.
+    fn try_staging(&self, size: usize) -> Result<StagingBuffer, ComputeError>;
+
+    /// Enqueues the upload of the `buffer` to a new handle on the given `stream`.
+    fn try_upload(
+        &self,
+        buffer: StagingBuffer,
+        stream: StreamId,
+    ) -> Result<Handle<Server>, ComputeError>;
+
+    /// Executes the `kernel` over the given `bindings` on the given `stream`.
+    ///
+    /// # Safety
+    ///
+    /// When executing with mode [ExecutionMode::Unchecked], out-of-bound reads and writes can happen.
+    unsafe fn try_execute(
+        &self,
+        kernel: Server::Kernel,
+        count: Server::DispatchOptions,
+        bindings: Vec<Binding<Server>>,
+        mode: ExecutionMode,
+        stream: StreamId,
+    ) -> Result<(), ComputeError>;
 
-    /// Executes the `kernel` over the given `bindings`.
+    /// Executes the `kernel` over the given `bindings` on the given `stream`.
     ///
     /// # Safety
     ///
     /// When executing with mode [ExecutionMode::Unchecked], out-of-bound reads and writes can happen.
+    ///
+    /// # Panics
+    ///
+    /// If the kernel can't be executed.
     unsafe fn execute(
         &self,
         kernel: Server::Kernel,
         count: Server::DispatchOptions,
         bindings: Vec<Binding<Server>>,
         mode: ExecutionMode,
-    );
+        stream: StreamId,
+    ) {
+        self.try_execute(kernel, count, bindings, mode, stream)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Create a new stream on the server.
+    fn try_create_stream(&self) -> Result<StreamId, ComputeError>;
+
+    /// Record an event on the stream.
+    fn try_record_event(&self, stream: StreamId) -> Result<EventId, ComputeError>;
+
+    /// Make the stream wait for the completion of the event.
+    fn try_wait_event(&self, stream: StreamId, event: EventId) -> Result<(), ComputeError>;
 
     /// Perform some synchronization of commands on the server.
     fn sync(&self, sync_type: SyncType);
+
+    /// Get the memory usage of the server.
+    fn memory_usage(&self) -> MemoryUsage;
+
+    /// Release the memory that isn't used anymore on the server.
+    fn memory_cleanup(&self);
+
+    /// Enable the timestamps on the server.
+    fn enable_timestamps(&self);
+
+    /// Disable the timestamps on the server.
+    fn disable_timestamps(&self);
+
+    /// Whether the timestamps are enabled on the server.
+    fn timestamps_enabled(&self) -> bool;
+
+    /// Wait for the completion of every task and returns the time the server spent executing
+    /// them.
+    fn sync_elapsed(&self) -> TimestampsResult;
+
+    /// Start recording the requests handled by the server.
+    fn start_profile(&self);
+
+    /// Stop recording the requests handled by the server and returns them.
+    fn end_profile(&self) -> ProfileTrace;
 }
@@ -1,9 +1,14 @@
 use super::ComputeChannel;
-use crate::server::{Binding, ComputeServer, Handle};
+use crate::memory_management::MemoryUsage;
+use crate::profiler::ProfileTrace;
+use crate::server::{Binding, ComputeError, ComputeServer, Handle};
+use crate::staging::StagingBuffer;
 use crate::storage::ComputeStorage;
+use crate::stream::{EventId, StreamId};
 use crate::ExecutionMode;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
+use cubecl_common::benchmark::TimestampsResult;
 use cubecl_common::reader::Reader;
 use cubecl_common::sync_type::SyncType;
 
@@ -45,8 +50,8 @@ impl<Server> ComputeChannel<Server> for RefCellComputeChannel<Server>
 where
     Server: ComputeServer + Send,
 {
-    fn read(&self, binding: Binding<Server>) -> Reader {
-        self.server.borrow_mut().read(binding)
+    fn try_read(&self, binding: Binding<Server>) -> Result<Reader, ComputeError> {
+        self.server.borrow_mut().try_read(binding)
     }
 
     fn get_resource(
@@ -56,29 +61,86 @@ where
         self.server.borrow_mut().get_resource(binding)
     }
 
-    fn create(&self, resource: &[u8]) -> Handle<Server> {
-        self.server.borrow_mut().create(resource)
+    fn try_create(&self, resource: &[u8]) -> Result<Handle<Server>, ComputeError> {
+        self.server.borrow_mut().try_create(resource)
     }
 
-    fn empty(&self, size: usize) -> Handle<Server> {
-        self.server.borrow_mut().empty(size)
+    fn try_empty(&self, size: usize) -> Result<Handle<Server>, ComputeError> {
+        self.server.borrow_mut().try_empty(size)
     }
 
-    unsafe fn execute(
+    unsafe fn try_execute(
         &self,
         kernel_description: Server::Kernel,
         count: Server::DispatchOptions,
         bindings: Vec<Binding<Server>>,
         kind: ExecutionMode,
-    ) {
+        stream: StreamId,
+    ) -> Result<(), ComputeError> {
         self.server
             .borrow_mut()
-            .execute(kernel_description, count, bindings, kind)
+            .try_execute(kernel_description, count, bindings, kind, stream)
+    }
+
+    fn try_staging(&self, size: usize) -> Result<StagingBuffer, ComputeError> {
+        self.server.borrow_mut().try_staging(size)
+    }
+
+    fn try_upload(
+        &self,
+        buffer: StagingBuffer,
+        stream: StreamId,
+    ) -> Result<Handle<Server>, ComputeError> {
+        self.server.borrow_mut().try_upload(buffer, stream)
+    }
+
+    fn try_create_stream(&self) -> Result<StreamId, ComputeError> {
+        self.server.borrow_mut().try_create_stream()
+    }
+
+    fn try_record_event(&self, stream: StreamId) -> Result<EventId, ComputeError> {
+        self.server.borrow_mut().try_record_event(stream)
+    }
+
+    fn try_wait_event(&self, stream: StreamId, event: EventId) -> Result<(), ComputeError> {
+        self.server.borrow_mut().try_wait_event(stream, event)
     }
 
     fn sync(&self, sync_type: SyncType) {
         self.server.borrow_mut().sync(sync_type)
     }
+
+    fn memory_usage(&self) -> MemoryUsage {
+        self.server.borrow_mut().memory_usage()
+    }
+
+    fn memory_cleanup(&self) {
+        self.server.borrow_mut().memory_cleanup()
+    }
+
+    fn enable_timestamps(&self) {
+        self.server.borrow_mut().enable_timestamps()
+    }
+
+    fn disable_timestamps(&self) {
+        self.server.borrow_mut().disable_timestamps()
+    }
+
+    fn timestamps_enabled(&self) -> bool {
+        self.server.borrow_mut().timestamps_enabled()
+    }
+
+    fn sync_elapsed(&self) -> TimestampsResult {
+        self.server.borrow_mut().sync_elapsed()
+    }
+
+    fn start_profile(&self) {
+        self.server.borrow_mut().start_profile()
+    }
+
+    fn end_profile(&self) -> ProfileTrace {
+        self.server.borrow_mut().end_profile()
+    }
 }
 
 /// This is unsafe, since no concurrency is supported by the `RefCell` channel.
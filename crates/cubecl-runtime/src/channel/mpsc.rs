@@ -1,10 +1,18 @@
-use cubecl_common::{reader::Reader, sync_type::SyncType};
+use cubecl_common::{
+    benchmark::TimestampsResult,
+    reader::{reader_from_concrete, Reader},
+    sync_type::SyncType,
+};
 use std::{sync::Arc, thread};
 
 use super::ComputeChannel;
 use crate::{
-    server::{Binding, ComputeServer, Handle},
+    memory_management::MemoryUsage,
+    profiler::ProfileTrace,
+    server::{Binding, ComputeError, ComputeServer, Handle},
+    staging::StagingBuffer,
     storage::ComputeStorage,
+    stream::{EventId, StreamId},
     ExecutionMode,
 };
 
@@ -33,18 +41,42 @@ enum Message<Server>
 where
     Server: ComputeServer,
 {
-    Read(Binding<Server>, Callback<Vec<u8>>),
+    Read(Binding<Server>, Callback<Result<Vec<u8>, ComputeError>>),
     GetResource(
         Binding<Server>,
         Callback<<Server::Storage as ComputeStorage>::Resource>,
     ),
-    Create(Vec<u8>, Callback<Handle<Server>>),
-    Empty(usize, Callback<Handle<Server>>),
+    Create(Vec<u8>, Callback<Result<Handle<Server>, ComputeError>>),
+    Empty(usize, Callback<Result<Handle<Server>, ComputeError>>),
+    Staging(usize, Callback<Result<StagingBuffer, ComputeError>>),
+    Upload(
+        StagingBuffer,
+        StreamId,
+        Callback<Result<Handle<Server>, ComputeError>>,
+    ),
     ExecuteKernel(
-        (Server::Kernel, Server::DispatchOptions, ExecutionMode),
+        (
+            Server::Kernel,
+            Server::DispatchOptions,
+            ExecutionMode,
+            StreamId,
+        ),
         Vec<Binding<Server>>,
+        // Without a callback, the server panics if the kernel can't be executed.
+        Option<Callback<Result<(), ComputeError>>>,
     ),
+    CreateStream(Callback<Result<StreamId, ComputeError>>),
+    RecordEvent(StreamId, Callback<Result<EventId, ComputeError>>),
+    WaitEvent(StreamId, EventId, Callback<Result<(), ComputeError>>),
     Sync(SyncType, Callback<()>),
+    MemoryUsage(Callback<MemoryUsage>),
+    MemoryCleanup(Callback<()>),
+    EnableTimestamps(Callback<()>),
+    DisableTimestamps(Callback<()>),
+    TimestampsEnabled(Callback<bool>),
+    SyncElapsed(Callback<TimestampsResult>),
+    StartProfile(Callback<()>),
+    EndProfile(Callback<ProfileTrace>),
 }
 
 impl<Server> MpscComputeChannel<Server>
@@ -62,7 +94,10 @@ where
                 while let Ok(message) = receiver.recv().await {
                     match message {
                         Message::Read(binding, callback) => {
-                            let data = server.read(binding).await;
+                            let data = match server.try_read(binding) {
+                                Ok(reader) => Ok(reader.await),
+                                Err(err) => Err(err),
+                            };
                             callback.send(data).await.unwrap();
                         }
                         Message::GetResource(binding, callback) => {
@@ -70,20 +105,81 @@ where
                             callback.send(data).await.unwrap();
                         }
                         Message::Create(data, callback) => {
-                            let handle = server.create(&data);
+                            let handle = server.try_create(&data);
                             callback.send(handle).await.unwrap();
                         }
                         Message::Empty(size, callback) => {
-                            let handle = server.empty(size);
+                            let handle = server.try_empty(size);
+                            callback.send(handle).await.unwrap();
+                        }
+                        Message::Staging(size, callback) => {
+                            callback.send(server.try_staging(size)).await.unwrap();
+                        }
+                        Message::Upload(buffer, stream, callback) => {
+                            let handle = server.try_upload(buffer, stream);
                             callback.send(handle).await.unwrap();
                         }
-                        Message::ExecuteKernel(kernel, bindings) => unsafe {
-                            server.execute(kernel.0, kernel.1, bindings, kernel.2);
-                        },
+                        Message::ExecuteKernel(kernel, bindings, callback) => {
+                            let result = unsafe {
+                                server.try_execute(kernel.0, kernel.1, bindings, kernel.2, kernel.3)
+                            };
+
+                            match callback {
+                                Some(callback) => callback.send(result).await.unwrap(),
+                                None => {
+                                    if let Err(err) = result {
+                                        panic!("{err}");
+                                    }
+                                }
+                            }
+                        }
+                        Message::CreateStream(callback) => {
+                            callback.send(server.try_create_stream()).await.unwrap();
+                        }
+                        Message::RecordEvent(stream, callback) => {
+                            callback
+                                .send(server.try_record_event(stream))
+                                .await
+                                .unwrap();
+                        }
+                        Message::WaitEvent(stream, event, callback) => {
+                            callback
+                                .send(server.try_wait_event(stream, event))
+                                .await
+                                .unwrap();
+                        }
                         Message::Sync(sync_type, callback) => {
                             server.sync(sync_type);
                             callback.send(()).await.unwrap();
                         }
+                        Message::MemoryUsage(callback) => {
+                            callback.send(server.memory_usage()).await.unwrap();
+                        }
+                        Message::MemoryCleanup(callback) => {
+                            server.memory_cleanup();
+                            callback.send(()).await.unwrap();
+                        }
+                        Message::EnableTimestamps(callback) => {
+                            server.enable_timestamps();
+                            callback.send(()).await.unwrap();
+                        }
+                        Message::DisableTimestamps(callback) => {
+                            server.disable_timestamps();
+                            callback.send(()).await.unwrap();
+                        }
+                        Message::TimestampsEnabled(callback) => {
+                            callback.send(server.timestamps_enabled()).await.unwrap();
+                        }
+                        Message::SyncElapsed(callback) => {
+                            callback.send(server.sync_elapsed()).await.unwrap();
+                        }
+                        Message::StartProfile(callback) => {
+                            server.start_profile();
+                            callback.send(()).await.unwrap();
+                        }
+                        Message::EndProfile(callback) => {
+                            callback.send(server.end_profile()).await.unwrap();
+                        }
                     };
                 }
             });
@@ -113,10 +209,23 @@ where
         Box::pin(async move {
             let (callback, response) = async_channel::unbounded();
             sender.send(Message::Read(binding, callback)).await.unwrap();
-            handle_response(response.recv().await)
+            handle_response(response.recv().await).unwrap_or_else(|err| panic!("{err}"))
         })
     }
 
+    /// The error is only known once the server handled the request, so unlike
+    /// [read](ComputeChannel::read), this waits for the data to be available.
+    fn try_read(&self, binding: Binding<Server>) -> Result<Reader, ComputeError> {
+        let (callback, response) = async_channel::unbounded();
+
+        self.state
+            .sender
+            .send_blocking(Message::Read(binding, callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking()).map(reader_from_concrete)
+    }
+
     fn get_resource(
         &self,
         binding: Binding<Server>,
@@ -131,7 +240,7 @@ where
         handle_response(response.recv_blocking())
     }
 
-    fn create(&self, data: &[u8]) -> Handle<Server> {
+    fn try_create(&self, data: &[u8]) -> Result<Handle<Server>, ComputeError> {
         let (callback, response) = async_channel::unbounded();
 
         self.state
@@ -142,7 +251,7 @@ where
         handle_response(response.recv_blocking())
     }
 
-    fn empty(&self, size: usize) -> Handle<Server> {
+    fn try_empty(&self, size: usize) -> Result<Handle<Server>, ComputeError> {
         let (callback, response) = async_channel::unbounded();
         self.state
             .sender
@@ -152,19 +261,99 @@ where
         handle_response(response.recv_blocking())
     }
 
+    fn try_staging(&self, size: usize) -> Result<StagingBuffer, ComputeError> {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::Staging(size, callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    fn try_upload(
+        &self,
+        buffer: StagingBuffer,
+        stream: StreamId,
+    ) -> Result<Handle<Server>, ComputeError> {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::Upload(buffer, stream, callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
     unsafe fn execute(
         &self,
         kernel: Server::Kernel,
         count: Server::DispatchOptions,
         bindings: Vec<Binding<Server>>,
         kind: ExecutionMode,
+        stream: StreamId,
     ) {
         self.state
             .sender
-            .send_blocking(Message::ExecuteKernel((kernel, count, kind), bindings))
+            .send_blocking(Message::ExecuteKernel(
+                (kernel, count, kind, stream),
+                bindings,
+                None,
+            ))
             .unwrap()
     }
 
+    /// The error is only known once the server handled the request, so unlike
+    /// [execute](ComputeChannel::execute), this waits for the kernel to be submitted.
+    unsafe fn try_execute(
+        &self,
+        kernel: Server::Kernel,
+        count: Server::DispatchOptions,
+        bindings: Vec<Binding<Server>>,
+        kind: ExecutionMode,
+        stream: StreamId,
+    ) -> Result<(), ComputeError> {
+        let (callback, response) = async_channel::unbounded();
+
+        self.state
+            .sender
+            .send_blocking(Message::ExecuteKernel(
+                (kernel, count, kind, stream),
+                bindings,
+                Some(callback),
+            ))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    fn try_create_stream(&self) -> Result<StreamId, ComputeError> {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::CreateStream(callback))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
+
+    fn try_record_event(&self, stream: StreamId) -> Result<EventId, ComputeError> {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::RecordEvent(stream, callback))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
+
+    fn try_wait_event(&self, stream: StreamId, event: EventId) -> Result<(), ComputeError> {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::WaitEvent(stream, event, callback))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
+
     fn sync(&self, sync_type: SyncType) {
         let (callback, response) = async_channel::unbounded();
         self.state
@@ -173,6 +362,78 @@ where
             .unwrap();
         handle_response(response.recv_blocking())
     }
+
+    fn memory_usage(&self) -> MemoryUsage {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::MemoryUsage(callback))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
+
+    fn memory_cleanup(&self) {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::MemoryCleanup(callback))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
+
+    fn enable_timestamps(&self) {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::EnableTimestamps(callback))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
+
+    fn disable_timestamps(&self) {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::DisableTimestamps(callback))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
+
+    fn timestamps_enabled(&self) -> bool {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::TimestampsEnabled(callback))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
+
+    fn sync_elapsed(&self) -> TimestampsResult {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::SyncElapsed(callback))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
+
+    fn start_profile(&self) {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::StartProfile(callback))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
+
+    fn end_profile(&self) -> ProfileTrace {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::EndProfile(callback))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
 }
 
 fn handle_response<Response, Err: core::fmt::Debug>(response: Result<Response, Err>) -> Response {
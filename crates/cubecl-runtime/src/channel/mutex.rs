@@ -1,9 +1,14 @@
 use super::ComputeChannel;
-use crate::server::{Binding, ComputeServer, Handle};
+use crate::memory_management::MemoryUsage;
+use crate::profiler::ProfileTrace;
+use crate::server::{Binding, ComputeError, ComputeServer, Handle};
+use crate::staging::StagingBuffer;
 use crate::storage::ComputeStorage;
+use crate::stream::{EventId, StreamId};
 use crate::ExecutionMode;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
+use cubecl_common::benchmark::TimestampsResult;
 use cubecl_common::reader::Reader;
 use cubecl_common::sync_type::SyncType;
 use spin::Mutex;
@@ -38,8 +43,8 @@ impl<Server> ComputeChannel<Server> for MutexComputeChannel<Server>
 where
     Server: ComputeServer,
 {
-    fn read(&self, handle: Binding<Server>) -> Reader {
-        self.server.lock().read(handle)
+    fn try_read(&self, handle: Binding<Server>) -> Result<Reader, ComputeError> {
+        self.server.lock().try_read(handle)
     }
 
     fn get_resource(
@@ -49,25 +54,84 @@ where
         self.server.lock().get_resource(binding)
     }
 
-    fn create(&self, data: &[u8]) -> Handle<Server> {
-        self.server.lock().create(data)
+    fn try_create(&self, data: &[u8]) -> Result<Handle<Server>, ComputeError> {
+        self.server.lock().try_create(data)
     }
 
-    fn empty(&self, size: usize) -> Handle<Server> {
-        self.server.lock().empty(size)
+    fn try_empty(&self, size: usize) -> Result<Handle<Server>, ComputeError> {
+        self.server.lock().try_empty(size)
     }
 
-    unsafe fn execute(
+    unsafe fn try_execute(
         &self,
         kernel: Server::Kernel,
         count: Server::DispatchOptions,
         handles: Vec<Binding<Server>>,
         kind: ExecutionMode,
-    ) {
-        self.server.lock().execute(kernel, count, handles, kind)
+        stream: StreamId,
+    ) -> Result<(), ComputeError> {
+        self.server
+            .lock()
+            .try_execute(kernel, count, handles, kind, stream)
+    }
+
+    fn try_staging(&self, size: usize) -> Result<StagingBuffer, ComputeError> {
+        self.server.lock().try_staging(size)
+    }
+
+    fn try_upload(
+        &self,
+        buffer: StagingBuffer,
+        stream: StreamId,
+    ) -> Result<Handle<Server>, ComputeError> {
+        self.server.lock().try_upload(buffer, stream)
+    }
+
+    fn try_create_stream(&self) -> Result<StreamId, ComputeError> {
+        self.server.lock().try_create_stream()
+    }
+
+    fn try_record_event(&self, stream: StreamId) -> Result<EventId, ComputeError> {
+        self.server.lock().try_record_event(stream)
+    }
+
+    fn try_wait_event(&self, stream: StreamId, event: EventId) -> Result<(), ComputeError> {
+        self.server.lock().try_wait_event(stream, event)
     }
 
     fn sync(&self, sync_type: SyncType) {
         self.server.lock().sync(sync_type)
     }
+
+    fn memory_usage(&self) -> MemoryUsage {
+        self.server.lock().memory_usage()
+    }
+
+    fn memory_cleanup(&self) {
+        self.server.lock().memory_cleanup()
+    }
+
+    fn enable_timestamps(&self) {
+        self.server.lock().enable_timestamps()
+    }
+
+    fn disable_timestamps(&self) {
+        self.server.lock().disable_timestamps()
+    }
+
+    fn timestamps_enabled(&self) -> bool {
+        self.server.lock().timestamps_enabled()
+    }
+
+    fn sync_elapsed(&self) -> TimestampsResult {
+        self.server.lock().sync_elapsed()
+    }
+
+    fn start_profile(&self) {
+        self.server.lock().start_profile()
+    }
+
+    fn end_profile(&self) -> ProfileTrace {
+        self.server.lock().end_profile()
+    }
 }
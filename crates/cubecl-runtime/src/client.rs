@@ -1,11 +1,16 @@
 use crate::{
     channel::ComputeChannel,
-    server::{Binding, ComputeServer, Handle},
+    memory_management::MemoryUsage,
+    profiler::ProfileTrace,
+    server::{Binding, ComputeError, ComputeServer, Handle},
+    staging::StagingBuffer,
     storage::ComputeStorage,
+    stream::{EventId, StreamId},
     ExecutionMode,
 };
 use alloc::sync::Arc;
 use alloc::vec::Vec;
+use cubecl_common::benchmark::TimestampsResult;
 
 pub use cubecl_common::sync_type::SyncType;
 
@@ -53,6 +58,17 @@ where
         cubecl_common::reader::read_sync(self.channel.read(binding))
     }
 
+    /// Given a binding, returns owned resource as bytes, or the error that prevented reading it.
+    pub async fn try_read_async(&self, binding: Binding<Server>) -> Result<Vec<u8>, ComputeError> {
+        Ok(self.channel.try_read(binding)?.await)
+    }
+
+    /// Given a binding, returns owned resource as bytes, or the error that prevented reading it.
+    pub fn try_read(&self, binding: Binding<Server>) -> Result<Vec<u8>, ComputeError> {
+        let reader = self.channel.try_read(binding)?;
+        Ok(cubecl_common::reader::read_sync(reader))
+    }
+
     /// Given a resource handle, returns the storage resource.
     pub fn get_resource(
         &self,
@@ -66,11 +82,61 @@ where
         self.channel.create(data)
     }
 
+    /// Given a resource, stores it and returns the resource handle, or the error that prevented
+    /// reserving the memory.
+    pub fn try_create(&self, data: &[u8]) -> Result<Handle<Server>, ComputeError> {
+        self.channel.try_create(data)
+    }
+
     /// Reserves `size` bytes in the storage, and returns a handle over them.
     pub fn empty(&self, size: usize) -> Handle<Server> {
         self.channel.empty(size)
     }
 
+    /// Reserves `size` bytes in the storage, and returns a handle over them, or the error that
+    /// prevented reserving the memory.
+    ///
+    /// Running out of memory isn't fatal, the handles can be dropped and the request retried.
+    pub fn try_empty(&self, size: usize) -> Result<Handle<Server>, ComputeError> {
+        self.channel.try_empty(size)
+    }
+
+    /// Allocates a host buffer of `size` bytes, e.g. page-locked memory, from which the data can
+    /// be [uploaded](Self::upload) without blocking.
+    pub fn staging(&self, size: usize) -> StagingBuffer {
+        self.try_staging(size).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Allocates a host buffer of `size` bytes, or returns the error that prevented the
+    /// allocation.
+    pub fn try_staging(&self, size: usize) -> Result<StagingBuffer, ComputeError> {
+        self.channel.try_staging(size)
+    }
+
+    /// Enqueues the upload of the `buffer` to a new handle on the given `stream`, without waiting
+    /// for the copy to complete.
+    ///
+    /// Returns the handle with an event completed once the upload is done, which the other
+    /// streams can [wait](Self::wait_event) on before using the handle. The tasks submitted to the
+    /// same stream are executed after the upload.
+    pub fn upload(&self, buffer: StagingBuffer, stream: StreamId) -> (Handle<Server>, EventId) {
+        self.try_upload(buffer, stream)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Enqueues the upload of the `buffer` to a new handle on the given `stream`, or returns the
+    /// error that prevented the upload.
+    pub fn try_upload(
+        &self,
+        buffer: StagingBuffer,
+        stream: StreamId,
+    ) -> Result<(Handle<Server>, EventId), ComputeError> {
+        let handle = self.channel.try_upload(buffer, stream)?;
+        let event = self.channel.try_record_event(stream)?;
+
+        Ok((handle, event))
+    }
+
     /// Executes the `kernel` over the given `bindings`.
     pub fn execute(
         &self,
@@ -78,9 +144,65 @@ where
         count: Server::DispatchOptions,
         bindings: Vec<Binding<Server>>,
     ) {
+        unsafe {
+            self.channel.execute(
+                kernel,
+                count,
+                bindings,
+                ExecutionMode::Checked,
+                StreamId::DEFAULT,
+            )
+        }
+    }
+
+    /// Executes the `kernel` over the given `bindings`, returning the error that prevented the
+    /// execution, if any.
+    pub fn try_execute(
+        &self,
+        kernel: Server::Kernel,
+        count: Server::DispatchOptions,
+        bindings: Vec<Binding<Server>>,
+    ) -> Result<(), ComputeError> {
+        unsafe {
+            self.channel.try_execute(
+                kernel,
+                count,
+                bindings,
+                ExecutionMode::Checked,
+                StreamId::DEFAULT,
+            )
+        }
+    }
+
+    /// Executes the `kernel` over the given `bindings` on the given `stream`.
+    ///
+    /// The kernel can run concurrently with the tasks of the other streams, use
+    /// [wait_event](Self::wait_event) to order it after them.
+    pub fn execute_on_stream(
+        &self,
+        kernel: Server::Kernel,
+        count: Server::DispatchOptions,
+        bindings: Vec<Binding<Server>>,
+        stream: StreamId,
+    ) {
+        unsafe {
+            self.channel
+                .execute(kernel, count, bindings, ExecutionMode::Checked, stream)
+        }
+    }
+
+    /// Executes the `kernel` over the given `bindings` on the given `stream`, returning the
+    /// error that prevented the execution, if any.
+    pub fn try_execute_on_stream(
+        &self,
+        kernel: Server::Kernel,
+        count: Server::DispatchOptions,
+        bindings: Vec<Binding<Server>>,
+        stream: StreamId,
+    ) -> Result<(), ComputeError> {
         unsafe {
             self.channel
-                .execute(kernel, count, bindings, ExecutionMode::Checked)
+                .try_execute(kernel, count, bindings, ExecutionMode::Checked, stream)
         }
     }
 
@@ -95,15 +217,131 @@ where
         count: Server::DispatchOptions,
         bindings: Vec<Binding<Server>>,
     ) {
-        self.channel
-            .execute(kernel, count, bindings, ExecutionMode::Unchecked)
+        self.channel.execute(
+            kernel,
+            count,
+            bindings,
+            ExecutionMode::Unchecked,
+            StreamId::DEFAULT,
+        )
+    }
+
+    /// Executes the `kernel` over the given `bindings` without performing any bound checks,
+    /// returning the error that prevented the execution, if any.
+    ///
+    /// # Safety
+    ///
+    /// Without checks, the out-of-bound reads and writes can happen.
+    pub unsafe fn try_execute_unchecked(
+        &self,
+        kernel: Server::Kernel,
+        count: Server::DispatchOptions,
+        bindings: Vec<Binding<Server>>,
+    ) -> Result<(), ComputeError> {
+        self.channel.try_execute(
+            kernel,
+            count,
+            bindings,
+            ExecutionMode::Unchecked,
+            StreamId::DEFAULT,
+        )
+    }
+
+    /// Create a new stream, whose tasks can be executed concurrently with the tasks of the other
+    /// streams.
+    pub fn create_stream(&self) -> StreamId {
+        self.try_create_stream()
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Create a new stream, returning the error that prevented its creation, if any.
+    pub fn try_create_stream(&self) -> Result<StreamId, ComputeError> {
+        self.channel.try_create_stream()
+    }
+
+    /// Record an event on the `stream`, which completes once every task submitted to the stream
+    /// so far is completed.
+    pub fn record_event(&self, stream: StreamId) -> EventId {
+        self.try_record_event(stream)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Record an event on the `stream`, returning the error that prevented the recording, if any.
+    pub fn try_record_event(&self, stream: StreamId) -> Result<EventId, ComputeError> {
+        self.channel.try_record_event(stream)
+    }
+
+    /// Make the tasks submitted to the `stream` after this call wait for the completion of the
+    /// `event`, which can be recorded on another stream.
+    pub fn wait_event(&self, stream: StreamId, event: EventId) {
+        self.try_wait_event(stream, event)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Make the `stream` wait for the completion of the `event`, returning the error that
+    /// prevented it, if any.
+    pub fn try_wait_event(&self, stream: StreamId, event: EventId) -> Result<(), ComputeError> {
+        self.channel.try_wait_event(stream, event)
     }
 
-    /// Wait for the completion of every task in the server.
+    /// Wait for the completion of every task in the server, on every stream.
     pub fn sync(&self, sync_type: SyncType) {
         self.channel.sync(sync_type)
     }
 
+    /// Get the memory usage of the server, useful to understand how much memory the pools are
+    /// holding.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.channel.memory_usage()
+    }
+
+    /// Release every chunk of memory that isn't used anymore back to the device.
+    ///
+    /// Memory pools keep their chunks to reuse them for later allocations, this can be called
+    /// after a burst of large allocations to shrink the memory back.
+    pub fn memory_cleanup(&self) {
+        self.channel.memory_cleanup()
+    }
+
+    /// Enable the timestamps on the device, see [sync_elapsed](Self::sync_elapsed).
+    pub fn enable_timestamps(&self) {
+        self.channel.enable_timestamps()
+    }
+
+    /// Disable the timestamps on the device.
+    pub fn disable_timestamps(&self) {
+        self.channel.disable_timestamps()
+    }
+
+    /// Whether the timestamps are enabled on the device. Unlike [sync_elapsed](Self::sync_elapsed),
+    /// the time measured so far isn't consumed.
+    pub fn timestamps_enabled(&self) -> bool {
+        self.channel.timestamps_enabled()
+    }
+
+    /// Wait for the completion of every task in the server, and returns the time the device spent
+    /// executing them since the last call.
+    ///
+    /// The timestamps must be [enabled](Self::enable_timestamps) first.
+    pub fn sync_elapsed(&self) -> TimestampsResult {
+        self.channel.sync_elapsed()
+    }
+
+    /// Start profiling the server, recording every kernel compilation and execution as well as
+    /// every data transfer until [end_profile](Self::end_profile) is called.
+    ///
+    /// The server waits for every kernel to complete while profiling, so that their execution
+    /// time can be measured.
+    pub fn start_profile(&self) {
+        self.channel.start_profile()
+    }
+
+    /// Stop profiling the server and returns the recorded requests, which can be exported to the
+    /// [Chrome trace event format](ProfileTrace::to_chrome_trace).
+    pub fn end_profile(&self) -> ProfileTrace {
+        self.channel.end_profile()
+    }
+
     /// Get the features supported by the compute server.
     pub fn features(&self) -> &Server::FeatureSet {
         self.features.as_ref()
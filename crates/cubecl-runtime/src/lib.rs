@@ -22,8 +22,12 @@ pub mod tune;
 pub mod memory_management;
 /// Compute server module.
 pub mod server;
+/// Staging buffer module.
+pub mod staging;
 /// Compute Storage module.
 pub mod storage;
+/// Stream and event module.
+pub mod stream;
 
 mod base;
 pub use base::*;
@@ -31,3 +35,5 @@ pub use cubecl_common::benchmark;
 
 /// Debugging utilities.
 pub mod debug;
+/// Profiling utilities.
+pub mod profiler;
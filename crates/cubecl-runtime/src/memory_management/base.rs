@@ -1,4 +1,8 @@
-use crate::storage::{ComputeStorage, StorageHandle, StorageId};
+use super::MemoryUsage;
+use crate::{
+    server::ComputeError,
+    storage::{ComputeStorage, StorageHandle, StorageId},
+};
 
 /// The managed tensor buffer handle that points to some memory segment.
 /// It should not contain actual data.
@@ -24,7 +28,25 @@ pub trait MemoryManagement<Storage: ComputeStorage>: Send + core::fmt::Debug {
     type Binding: MemoryBinding;
 
     /// Returns the storage from the specified binding
-    fn get(&mut self, binding: Self::Binding) -> StorageHandle;
+    fn try_get(&mut self, binding: Self::Binding) -> Result<StorageHandle, ComputeError>;
+
+    /// Returns the storage from the specified binding
+    ///
+    /// # Panics
+    ///
+    /// If the binding isn't managed by the current memory management.
+    fn get(&mut self, binding: Self::Binding) -> StorageHandle {
+        self.try_get(binding).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Returns the resource from the storage at the specified handle
+    fn try_get_resource(
+        &mut self,
+        binding: Self::Binding,
+    ) -> Result<Storage::Resource, ComputeError> {
+        let handle = self.try_get(binding)?;
+        Ok(self.storage().get(&handle))
+    }
 
     /// Returns the resource from the storage at the specified handle
     fn get_resource(&mut self, binding: Self::Binding) -> Storage::Resource {
@@ -33,14 +55,43 @@ pub trait MemoryManagement<Storage: ComputeStorage>: Send + core::fmt::Debug {
     }
 
     /// Finds a spot in memory for a resource with the given size in bytes, and returns a handle to it
-    fn reserve(&mut self, size: usize, exclude: &[StorageId]) -> Self::Handle;
+    fn try_reserve(
+        &mut self,
+        size: usize,
+        exclude: &[StorageId],
+    ) -> Result<Self::Handle, ComputeError>;
+
+    /// Finds a spot in memory for a resource with the given size in bytes, and returns a handle to it
+    ///
+    /// # Panics
+    ///
+    /// If the memory can't be reserved, use [try_reserve](MemoryManagement::try_reserve) to
+    /// handle the error.
+    fn reserve(&mut self, size: usize, exclude: &[StorageId]) -> Self::Handle {
+        self.try_reserve(size, exclude)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
 
     /// Bypass the memory allocation algorithm to allocate data directly.
     ///
     /// # Notes
     ///
     /// Can be useful for servers that want specific control over memory.
-    fn alloc(&mut self, size: usize) -> Self::Handle;
+    fn try_alloc(&mut self, size: usize) -> Result<Self::Handle, ComputeError>;
+
+    /// Bypass the memory allocation algorithm to allocate data directly.
+    ///
+    /// # Notes
+    ///
+    /// Can be useful for servers that want specific control over memory.
+    ///
+    /// # Panics
+    ///
+    /// If the memory can't be allocated, use [try_alloc](MemoryManagement::try_alloc) to handle
+    /// the error.
+    fn alloc(&mut self, size: usize) -> Self::Handle {
+        self.try_alloc(size).unwrap_or_else(|err| panic!("{err}"))
+    }
 
     /// Bypass the memory allocation algorithm to deallocate data directly.
     ///
@@ -49,6 +100,16 @@ pub trait MemoryManagement<Storage: ComputeStorage>: Send + core::fmt::Debug {
     /// Can be useful for servers that want specific control over memory.
     fn dealloc(&mut self, binding: Self::Binding);
 
+    /// Releases every chunk of memory that isn't used anymore back to the storage.
+    ///
+    /// # Notes
+    ///
+    /// Storages can defer the deallocations, e.g. until the pending tasks are executed.
+    fn cleanup(&mut self);
+
+    /// Returns the amount of memory reserved and used, along with statistics for every pool.
+    fn memory_usage(&self) -> MemoryUsage;
+
     /// Fetch the storage used by the memory manager.
     ///
     /// # Notes
@@ -2,10 +2,14 @@ use super::memory_pool::{
     MemoryExtensionStrategy, MemoryPool, MemoryPoolBinding, MemoryPoolHandle, RoundingStrategy,
     SmallMemoryPool,
 };
-use crate::storage::{ComputeStorage, StorageHandle, StorageId};
+use crate::{
+    server::ComputeError,
+    storage::{ComputeStorage, StorageHandle, StorageId},
+};
+use alloc::format;
 use alloc::vec::Vec;
 
-use super::MemoryManagement;
+use super::{simple::DeallocStrategy, MemoryManagement, MemoryUsage};
 
 /// Reserves and keeps track of chunks of memory in the storage, and slices upon these chunks.
 pub struct DynamicMemoryManagement<Storage> {
@@ -13,6 +17,7 @@ pub struct DynamicMemoryManagement<Storage> {
     small_memory_pool: SmallMemoryPool,
     pools: Vec<MemoryPool>,
     options: Vec<MemoryPoolOptions>,
+    dealloc_strategy: DeallocStrategy,
     storage: Storage,
 }
 
@@ -21,6 +26,8 @@ pub struct DynamicMemoryManagement<Storage> {
 pub struct DynamicMemoryManagementOptions {
     pools: Vec<MemoryPoolOptions>,
     min_chunk_alignment_offset: usize,
+    #[new(value = "DeallocStrategy::Never")]
+    dealloc_strategy: DeallocStrategy,
 }
 
 /// Options to create a memory pool.
@@ -68,8 +75,18 @@ impl DynamicMemoryManagementOptions {
         Self {
             pools,
             min_chunk_alignment_offset,
+            dealloc_strategy: DeallocStrategy::Never,
         }
     }
+
+    /// Set the frequency at which the chunks that aren't used anymore are released back to the
+    /// storage.
+    ///
+    /// By default, chunks are only released when [cleanup](MemoryManagement::cleanup) is called.
+    pub fn with_dealloc_strategy(mut self, dealloc_strategy: DeallocStrategy) -> Self {
+        self.dealloc_strategy = dealloc_strategy;
+        self
+    }
 }
 
 impl<Storage: ComputeStorage> DynamicMemoryManagement<Storage> {
@@ -92,7 +109,8 @@ impl<Storage: ComputeStorage> DynamicMemoryManagement<Storage> {
                 );
 
                 for _ in 0..option.chunk_num_prealloc {
-                    pool.alloc(&mut storage, option.chunk_size);
+                    pool.alloc(&mut storage, option.chunk_size)
+                        .unwrap_or_else(|err| panic!("Can't preallocate memory chunks: {err}"));
                 }
 
                 pool
@@ -104,11 +122,30 @@ impl<Storage: ComputeStorage> DynamicMemoryManagement<Storage> {
             small_memory_pool: SmallMemoryPool::new(min_chunk_alignment_offset),
             pools,
             options: options.pools,
+            dealloc_strategy: options.dealloc_strategy,
             storage,
         }
     }
 }
 
+impl<Storage> DynamicMemoryManagement<Storage> {
+    fn no_pool_big_enough(&self, size: usize) -> ComputeError {
+        let max_size = self
+            .options
+            .iter()
+            .map(|option| option.slice_max_size)
+            .max()
+            .unwrap_or(0);
+
+        ComputeError::OutOfMemory {
+            size,
+            reason: format!(
+                "No memory pool big enough, the maximum slice size is {max_size} bytes"
+            ),
+        }
+    }
+}
+
 impl<Storage> core::fmt::Debug for DynamicMemoryManagement<Storage> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_str(
@@ -125,21 +162,31 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> for DynamicMemoryManagem
     type Handle = MemoryPoolHandle;
     type Binding = MemoryPoolBinding;
 
-    fn get(&mut self, binding: Self::Binding) -> StorageHandle {
+    fn try_get(&mut self, binding: Self::Binding) -> Result<StorageHandle, ComputeError> {
         if let Some(handle) = self.small_memory_pool.get(&binding) {
-            return handle.clone();
+            return Ok(handle.clone());
         }
 
         for pool in &self.pools {
             if let Some(handle) = pool.get(&binding) {
-                return handle.clone();
+                return Ok(handle.clone());
             }
         }
 
-        panic!("No handle found in memory pools");
+        Err(ComputeError::InvalidBinding(
+            "No handle found in memory pools".into(),
+        ))
     }
 
-    fn reserve(&mut self, size: usize, exclude: &[StorageId]) -> Self::Handle {
+    fn try_reserve(
+        &mut self,
+        size: usize,
+        exclude: &[StorageId],
+    ) -> Result<Self::Handle, ComputeError> {
+        if self.dealloc_strategy.should_dealloc() {
+            self.cleanup();
+        }
+
         if size <= self.min_chunk_alignment_offset {
             return self
                 .small_memory_pool
@@ -153,10 +200,10 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> for DynamicMemoryManagem
             }
         }
 
-        panic!("No memory pool big enough to reserve {size} bytes.");
+        Err(self.no_pool_big_enough(size))
     }
 
-    fn alloc(&mut self, size: usize) -> Self::Handle {
+    fn try_alloc(&mut self, size: usize) -> Result<Self::Handle, ComputeError> {
         if size <= self.min_chunk_alignment_offset {
             return self.small_memory_pool.alloc(&mut self.storage, size);
         }
@@ -168,14 +215,167 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> for DynamicMemoryManagem
             }
         }
 
-        panic!("No memory pool big enough to alloc {size} bytes.");
+        Err(self.no_pool_big_enough(size))
     }
 
     fn dealloc(&mut self, _binding: Self::Binding) {
         // Can't dealloc slices.
     }
 
+    fn cleanup(&mut self) {
+        self.small_memory_pool.cleanup(&mut self.storage);
+
+        for pool in self.pools.iter_mut() {
+            pool.cleanup(&mut self.storage);
+        }
+    }
+
+    fn memory_usage(&self) -> MemoryUsage {
+        let small_pool = self
+            .small_memory_pool
+            .memory_usage(self.min_chunk_alignment_offset);
+        let pools = self
+            .pools
+            .iter()
+            .zip(self.options.iter())
+            .map(|(pool, option)| pool.memory_usage(option.slice_max_size));
+
+        MemoryUsage::from_pools(core::iter::once(small_pool).chain(pools).collect())
+    }
+
     fn storage(&mut self) -> &mut Storage {
         &mut self.storage
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{memory_management::MemoryHandle, storage::BytesStorage};
+
+    #[test]
+    fn reserve_returns_an_error_when_no_pool_is_big_enough() {
+        let options = DynamicMemoryManagementOptions::new(
+            Vec::from([MemoryPoolOptions {
+                chunk_size: 1024,
+                chunk_num_prealloc: 0,
+                slice_max_size: 1024,
+            }]),
+            32,
+        );
+        let mut memory_management = DynamicMemoryManagement::new(BytesStorage::default(), options);
+
+        let result = memory_management.try_reserve(2048, &[]);
+        assert!(matches!(
+            result,
+            Err(ComputeError::OutOfMemory { size: 2048, .. })
+        ));
+
+        let handle = memory_management.try_reserve(512, &[]);
+        assert!(handle.is_ok());
+    }
+
+    #[test]
+    fn memory_usage_reports_every_pool() {
+        let options = DynamicMemoryManagementOptions::new(
+            Vec::from([MemoryPoolOptions {
+                chunk_size: 1024,
+                chunk_num_prealloc: 0,
+                slice_max_size: 1024,
+            }]),
+            32,
+        );
+        let mut memory_management = DynamicMemoryManagement::new(BytesStorage::default(), options);
+
+        let _small = memory_management.reserve(16, &[]);
+        let large = memory_management.reserve(512, &[]);
+        let usage = memory_management.memory_usage();
+
+        assert_eq!(usage.pools.len(), 2);
+        assert_eq!(usage.bytes_reserved, 32 + 1024);
+        assert_eq!(usage.bytes_in_use, 16 + 512);
+
+        let pool = &usage.pools[1];
+        assert_eq!(pool.slice_max_size, Some(1024));
+        assert_eq!(pool.num_chunks, 1);
+        assert_eq!(pool.num_slices, 2);
+        assert_eq!(pool.num_free_slices, 1);
+        assert_eq!(pool.largest_free_slice, 512);
+
+        core::mem::drop(large);
+        let usage = memory_management.memory_usage();
+
+        assert_eq!(usage.bytes_in_use, 16);
+        assert_eq!(usage.pools[1].num_free_slices, 2);
+        assert_eq!(usage.pools[1].fragmentation(), 0.5);
+    }
+
+    #[test]
+    fn cleanup_releases_free_chunks() {
+        let mut memory_management = DynamicMemoryManagement::new(
+            BytesStorage::default(),
+            DynamicMemoryManagementOptions::new(
+                Vec::from([MemoryPoolOptions {
+                    chunk_size: 1024,
+                    chunk_num_prealloc: 0,
+                    slice_max_size: 1024,
+                }]),
+                32,
+            ),
+        );
+
+        let small = memory_management.reserve(16, &[]);
+        let first = memory_management.reserve(1024, &[]);
+        let second = memory_management.reserve(1024, &[]);
+        core::mem::drop(small);
+        core::mem::drop(first);
+
+        memory_management.cleanup();
+        let usage = memory_management.memory_usage();
+
+        assert_eq!(usage.bytes_reserved, 1024);
+        assert_eq!(usage.pools[0].num_chunks, 0);
+        assert_eq!(usage.pools[1].num_chunks, 1);
+
+        // The remaining chunk is still valid and the pool can grow again.
+        memory_management.get(second.clone().binding());
+        let _handle = memory_management.reserve(1024, &[]);
+        assert_eq!(memory_management.memory_usage().bytes_reserved, 2048);
+    }
+
+    #[test]
+    fn dealloc_strategy_releases_free_chunks_periodically() {
+        let mut memory_management = DynamicMemoryManagement::new(
+            BytesStorage::default(),
+            DynamicMemoryManagementOptions::new(
+                Vec::from([MemoryPoolOptions {
+                    chunk_size: 1024,
+                    chunk_num_prealloc: 0,
+                    slice_max_size: 1024,
+                }]),
+                32,
+            )
+            .with_dealloc_strategy(DeallocStrategy::new_period_tick(3)),
+        );
+
+        let first = memory_management.reserve(1024, &[]);
+        let first_id = memory_management.get(first.clone().binding()).id;
+        core::mem::drop(first);
+
+        // The period hasn't elapsed, so the free chunk is reused.
+        let second = memory_management.reserve(1024, &[]);
+        let second_id = memory_management.get(second.clone().binding()).id;
+        core::mem::drop(second);
+        assert_eq!(second_id, first_id);
+        assert_eq!(memory_management.memory_usage().pools[1].num_chunks, 1);
+
+        // The free chunk is released before reserving, so a new one is allocated.
+        let third = memory_management.reserve(1024, &[]);
+        let third_id = memory_management.get(third.clone().binding()).id;
+        let usage = memory_management.memory_usage();
+        assert_ne!(third_id, first_id);
+        assert_eq!(usage.pools[1].num_chunks, 1);
+        assert_eq!(usage.bytes_reserved, 1024);
+        assert_eq!(usage.bytes_in_use, 1024);
+    }
+}
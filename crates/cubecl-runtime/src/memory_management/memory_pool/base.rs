@@ -1,5 +1,7 @@
 use super::index::SearchIndex;
 use super::{MemoryPoolBinding, MemoryPoolHandle, RingBuffer, SliceHandle, SliceId};
+use crate::memory_management::MemoryPoolUsage;
+use crate::server::ComputeError;
 use crate::storage::{ComputeStorage, StorageHandle, StorageId, StorageUtilization};
 use alloc::vec::Vec;
 use hashbrown::HashMap;
@@ -181,13 +183,13 @@ impl MemoryPool {
         storage: &mut Storage,
         size: usize,
         exclude: &[StorageId],
-    ) -> MemoryPoolHandle {
+    ) -> Result<MemoryPoolHandle, ComputeError> {
         let slice = self.get_free_slice(size, exclude);
 
         match slice {
-            Some(slice) => MemoryPoolHandle {
+            Some(slice) => Ok(MemoryPoolHandle {
                 slice: slice.clone(),
-            },
+            }),
             None => self.alloc(storage, size),
         }
     }
@@ -196,7 +198,7 @@ impl MemoryPool {
         &mut self,
         storage: &mut Storage,
         size: usize,
-    ) -> MemoryPoolHandle {
+    ) -> Result<MemoryPoolHandle, ComputeError> {
         let alloc_size = self.rounding.alloc_size(size);
         self.alloc_slice(storage, alloc_size, size)
     }
@@ -206,9 +208,9 @@ impl MemoryPool {
         storage: &mut Storage,
         alloc_size: usize,
         slice_size: usize,
-    ) -> MemoryPoolHandle {
+    ) -> Result<MemoryPoolHandle, ComputeError> {
         let chunk_size = self.rounding.alloc_size(alloc_size);
-        let storage_id = self.create_chunk(storage, chunk_size);
+        let storage_id = self.create_chunk(storage, chunk_size)?;
         let chunk_size = self.chunks.get(&storage_id).unwrap().alloc_size;
         self.recently_added_chunks.push(storage_id);
         self.recently_allocated_size += chunk_size;
@@ -218,9 +220,9 @@ impl MemoryPool {
         let handle_slice = slice.handle.clone();
         self.update_chunk_metadata(slice, extra_slice);
 
-        MemoryPoolHandle {
+        Ok(MemoryPoolHandle {
             slice: handle_slice,
-        }
+        })
     }
 
     fn allocate_slices(
@@ -262,6 +264,62 @@ impl MemoryPool {
         }
     }
 
+    /// Releases the chunks where every slice is free back to the storage.
+    pub fn cleanup<Storage: ComputeStorage>(&mut self, storage: &mut Storage) {
+        let free_chunks = self
+            .chunks
+            .iter()
+            .filter(|(_, chunk)| {
+                chunk
+                    .slices
+                    .slices
+                    .values()
+                    .all(|id| self.slices.get(id).map(Slice::is_free).unwrap_or(true))
+            })
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+
+        for id in free_chunks {
+            let chunk = self.chunks.remove(&id).unwrap();
+            for slice_id in chunk.slices.slices.values() {
+                self.slices.remove(slice_id);
+            }
+
+            self.ring.remove_chunk(id);
+            self.storage_index.remove(&id);
+            self.recently_added_chunks
+                .retain(|chunk_id| chunk_id != &id);
+            storage.dealloc(id);
+        }
+    }
+
+    /// Returns the memory usage of the pool, the slices being limited to `slice_max_size` bytes.
+    pub fn memory_usage(&self, slice_max_size: usize) -> MemoryPoolUsage {
+        let free_slices = self.slices.values().filter(|slice| slice.is_free());
+
+        MemoryPoolUsage {
+            slice_max_size: Some(slice_max_size),
+            num_chunks: self.chunks.len(),
+            num_slices: self.slices.len(),
+            num_free_slices: free_slices.clone().count(),
+            bytes_reserved: self.chunks.values().map(|chunk| chunk.alloc_size).sum(),
+            bytes_in_use: self
+                .slices
+                .values()
+                .filter(|slice| !slice.is_free())
+                .map(|slice| slice.storage.size())
+                .sum(),
+            bytes_free: free_slices
+                .clone()
+                .map(|slice| slice.effective_size())
+                .sum(),
+            largest_free_slice: free_slices
+                .map(|slice| slice.effective_size())
+                .max()
+                .unwrap_or(0),
+        }
+    }
+
     #[allow(unused)]
     fn display_memory_usage(&self) {
         let total_memory_usage: f64 = self
@@ -343,11 +401,11 @@ impl MemoryPool {
         &mut self,
         storage: &mut Storage,
         size: usize,
-    ) -> StorageId {
+    ) -> Result<StorageId, ComputeError> {
         let padding = calculate_padding(size, self.buffer_alignment);
         let effective_size = size + padding;
 
-        let storage = storage.alloc(effective_size);
+        let storage = storage.try_alloc(effective_size)?;
 
         let id = storage.id;
         self.ring.push_chunk(id);
@@ -358,7 +416,7 @@ impl MemoryPool {
         );
         self.storage_index.insert(id, size);
 
-        id
+        Ok(id)
     }
 }
 
@@ -31,6 +31,21 @@ impl RingBuffer {
             .insert(storage_id, self.queue.len() - 1);
     }
 
+    pub fn remove_chunk(&mut self, storage_id: StorageId) {
+        if self.chunk_positions.remove(&storage_id).is_none() {
+            return;
+        }
+
+        self.queue.retain(|id| id != &storage_id);
+        for (position, id) in self.queue.iter().enumerate() {
+            self.chunk_positions.insert(*id, position);
+        }
+
+        // The positions changed, so the search starts over.
+        self.cursor_chunk = 0;
+        self.cursor_slice = 0;
+    }
+
     pub fn find_free_slice(
         &mut self,
         size: usize,
@@ -1,4 +1,6 @@
 use super::{MemoryPoolBinding, MemoryPoolHandle, SliceHandle, SliceId};
+use crate::memory_management::MemoryPoolUsage;
+use crate::server::ComputeError;
 use crate::storage::{ComputeStorage, StorageHandle, StorageId, StorageUtilization};
 use alloc::vec::Vec;
 use hashbrown::HashMap;
@@ -55,6 +57,34 @@ impl SmallMemoryPool {
         self.slices.get(binding.slice.id()).map(|s| &s.storage)
     }
 
+    /// Returns the memory usage of the pool, the slices being limited to `slice_max_size` bytes.
+    pub fn memory_usage(&self, slice_max_size: usize) -> MemoryPoolUsage {
+        let free_slices = self.slices.values().filter(|slice| slice.handle.is_free());
+
+        MemoryPoolUsage {
+            slice_max_size: Some(slice_max_size),
+            num_chunks: self.chunks.len(),
+            num_slices: self.slices.len(),
+            num_free_slices: free_slices.clone().count(),
+            // Every chunk holds a single slice that takes the whole chunk.
+            bytes_reserved: self.chunks.len() * self.buffer_storage_alignment_offset,
+            bytes_in_use: self
+                .slices
+                .values()
+                .filter(|slice| !slice.handle.is_free())
+                .map(|slice| slice.storage.size())
+                .sum(),
+            bytes_free: free_slices
+                .clone()
+                .map(|slice| slice.effective_size())
+                .sum(),
+            largest_free_slice: free_slices
+                .map(|slice| slice.effective_size())
+                .max()
+                .unwrap_or(0),
+        }
+    }
+
     /// Reserves memory of specified size using the reserve algorithm, and return
     /// a handle to the reserved memory.
     ///
@@ -64,14 +94,14 @@ impl SmallMemoryPool {
         storage: &mut Storage,
         size: usize,
         exclude: &[StorageId],
-    ) -> MemoryPoolHandle {
+    ) -> Result<MemoryPoolHandle, ComputeError> {
         assert!(size <= self.buffer_storage_alignment_offset);
         let slice = self.get_free_slice(size, exclude);
 
         match slice {
-            Some(slice) => MemoryPoolHandle {
+            Some(slice) => Ok(MemoryPoolHandle {
                 slice: slice.clone(),
-            },
+            }),
             None => self.alloc(storage, size),
         }
     }
@@ -80,7 +110,7 @@ impl SmallMemoryPool {
         &mut self,
         storage: &mut Storage,
         size: usize,
-    ) -> MemoryPoolHandle {
+    ) -> Result<MemoryPoolHandle, ComputeError> {
         assert!(size <= self.buffer_storage_alignment_offset);
 
         self.alloc_slice(storage, size)
@@ -90,16 +120,16 @@ impl SmallMemoryPool {
         &mut self,
         storage: &mut Storage,
         slice_size: usize,
-    ) -> MemoryPoolHandle {
-        let storage_id = self.create_chunk(storage, self.buffer_storage_alignment_offset);
+    ) -> Result<MemoryPoolHandle, ComputeError> {
+        let storage_id = self.create_chunk(storage, self.buffer_storage_alignment_offset)?;
         let slice = self.allocate_slice(storage_id, slice_size);
 
         let handle_slice = slice.handle.clone();
         self.update_chunk_metadata(slice);
 
-        MemoryPoolHandle {
+        Ok(MemoryPoolHandle {
             slice: handle_slice,
-        }
+        })
     }
 
     fn allocate_slice(&self, storage_id: StorageId, slice_size: usize) -> SmallSlice {
@@ -180,20 +210,40 @@ impl SmallMemoryPool {
         &mut self,
         storage: &mut Storage,
         size: usize,
-    ) -> StorageId {
+    ) -> Result<StorageId, ComputeError> {
         let padding = calculate_padding(size, self.buffer_storage_alignment_offset);
         let effective_size = size + padding;
 
-        let storage = storage.alloc(effective_size);
+        let storage = storage.try_alloc(effective_size)?;
         let id = storage.id;
         self.ring_buffer.push(id);
         self.chunks.insert(id, SmallChunk::new(None));
-        id
+        Ok(id)
     }
 
-    #[allow(unused)]
-    fn deallocate<Storage: ComputeStorage>(&mut self, _storage: &mut Storage) {
-        todo!()
+    /// Releases the chunks whose slice is free back to the storage.
+    pub fn cleanup<Storage: ComputeStorage>(&mut self, storage: &mut Storage) {
+        let free_chunks = self
+            .chunks
+            .iter()
+            .filter(|(_, chunk)| match chunk.slice {
+                Some(id) => self.slices.get(&id).unwrap().handle.is_free(),
+                None => true,
+            })
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+
+        for id in free_chunks {
+            let chunk = self.chunks.remove(&id).unwrap();
+            if let Some(slice_id) = chunk.slice {
+                self.slices.remove(&slice_id);
+            }
+
+            self.ring_buffer.retain(|chunk_id| chunk_id != &id);
+            storage.dealloc(id);
+        }
+
+        self.index = 0;
     }
 }
 
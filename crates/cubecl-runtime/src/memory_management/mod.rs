@@ -1,8 +1,10 @@
 pub(crate) mod memory_pool;
 
 mod base;
+mod usage;
 
 pub use base::*;
+pub use usage::*;
 
 /// Dynamic memory management strategy.
 pub mod dynamic;
@@ -1,5 +1,6 @@
 use crate::{
     memory_id_type,
+    server::ComputeError,
     storage::{ComputeStorage, StorageHandle, StorageId, StorageUtilization},
 };
 use alloc::vec::Vec;
@@ -10,7 +11,7 @@ use std::time;
 #[cfg(all(target_family = "wasm", feature = "std"))]
 use web_time as time;
 
-use super::{MemoryBinding, MemoryHandle, MemoryManagement};
+use super::{MemoryBinding, MemoryHandle, MemoryManagement, MemoryPoolUsage, MemoryUsage};
 
 // The ChunkId allows to keep track of how many references there are to a specific chunk.
 memory_id_type!(ChunkId, ChunkHandle, ChunkBinding);
@@ -92,7 +93,7 @@ impl DeallocStrategy {
         DeallocStrategy::PeriodTick { period, state: 0 }
     }
 
-    fn should_dealloc(&mut self) -> bool {
+    pub(crate) fn should_dealloc(&mut self) -> bool {
         match self {
             DeallocStrategy::PeriodTick { period, state } => {
                 *state = (*state + 1) % *period;
@@ -175,40 +176,38 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> for SimpleMemoryManageme
     type Binding = SimpleBinding;
 
     /// Returns the resource from the storage, for the specified handle.
-    fn get(&mut self, binding: Self::Binding) -> StorageHandle {
-        match binding {
-            SimpleBinding::Chunk(chunk) => self
-                .chunks
-                .get(chunk.id())
-                .expect("Storage found for the given execution buffer handle")
-                .storage
-                .clone(),
-            SimpleBinding::Slice(slice) => self
-                .slices
-                .get(slice.id())
-                .expect("Storage found for the given execution buffer handle")
-                .storage
-                .clone(),
-        }
+    fn try_get(&mut self, binding: Self::Binding) -> Result<StorageHandle, ComputeError> {
+        let storage = match binding {
+            SimpleBinding::Chunk(chunk) => self.chunks.get(chunk.id()).map(|chunk| &chunk.storage),
+            SimpleBinding::Slice(slice) => self.slices.get(slice.id()).map(|slice| &slice.storage),
+        };
+
+        storage.cloned().ok_or_else(|| {
+            ComputeError::InvalidBinding("No storage found for the given binding".into())
+        })
     }
 
     /// Reserves memory of specified size using the reserve algorithm, and return
     /// a handle to the reserved memory.
     ///
     /// Also clean ups, removing unused slices, and chunks if permitted by deallocation strategy.
-    fn reserve(&mut self, size: usize, exclude: &[StorageId]) -> Self::Handle {
+    fn try_reserve(
+        &mut self,
+        size: usize,
+        exclude: &[StorageId],
+    ) -> Result<Self::Handle, ComputeError> {
         self.cleanup_slices();
 
-        let handle = self.reserve_algorithm(size, exclude);
+        let handle = self.reserve_algorithm(size, exclude)?;
 
         if self.dealloc_strategy.should_dealloc() {
             self.cleanup_chunks();
         }
 
-        handle
+        Ok(handle)
     }
 
-    fn alloc(&mut self, size: usize) -> Self::Handle {
+    fn try_alloc(&mut self, size: usize) -> Result<Self::Handle, ComputeError> {
         self.create_chunk(size)
     }
 
@@ -223,6 +222,51 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> for SimpleMemoryManageme
         }
     }
 
+    fn cleanup(&mut self) {
+        self.cleanup_slices();
+        self.cleanup_chunks();
+    }
+
+    fn memory_usage(&self) -> MemoryUsage {
+        let mut usage = MemoryPoolUsage {
+            num_chunks: self.chunks.len(),
+            ..Default::default()
+        };
+
+        for chunk in self.chunks.values() {
+            let chunk_size = chunk.storage.size();
+            usage.bytes_reserved += chunk_size;
+
+            // Free slices are only removed on the next reservation, but they don't keep the
+            // chunk busy.
+            let slices_in_use = chunk
+                .slices
+                .iter()
+                .filter_map(|id| self.slices.get(id))
+                .filter(|slice| !slice.handle.is_free())
+                .map(|slice| slice.storage.size());
+            let bytes_in_use = match chunk.slices.is_empty() {
+                true if chunk.handle.is_free() => None,
+                true => Some(chunk_size),
+                false => slices_in_use.reduce(|a, b| a + b),
+            };
+
+            // A chunk used without slices counts as a single slice.
+            usage.num_slices += usize::max(chunk.slices.len(), 1);
+
+            match bytes_in_use {
+                Some(bytes) => usage.bytes_in_use += bytes,
+                None => {
+                    usage.num_free_slices += 1;
+                    usage.bytes_free += chunk_size;
+                    usage.largest_free_slice = usize::max(usage.largest_free_slice, chunk_size);
+                }
+            }
+        }
+
+        MemoryUsage::from_pools(Vec::from([usage]))
+    }
+
     fn storage(&mut self) -> &mut Storage {
         &mut self.storage
     }
@@ -244,7 +288,11 @@ impl<Storage: ComputeStorage> SimpleMemoryManagement<Storage> {
         }
     }
 
-    fn reserve_algorithm(&mut self, size: usize, exclude: &[StorageId]) -> SimpleHandle {
+    fn reserve_algorithm(
+        &mut self,
+        size: usize,
+        exclude: &[StorageId],
+    ) -> Result<SimpleHandle, ComputeError> {
         // Looks for a large enough, existing but unused chunk of memory.
         let chunk = self.find_free_chunk(size, exclude);
 
@@ -252,10 +300,10 @@ impl<Storage: ComputeStorage> SimpleMemoryManagement<Storage> {
             Some(chunk) => {
                 if size == chunk.storage.size() {
                     // If there is one of exactly the same size, it reuses it.
-                    SimpleHandle::Chunk(chunk.handle.clone())
+                    Ok(SimpleHandle::Chunk(chunk.handle.clone()))
                 } else {
                     // Otherwise creates a slice of the right size upon it, always starting at zero.
-                    self.create_slice(size, chunk.handle.clone())
+                    Ok(self.create_slice(size, chunk.handle.clone()))
                 }
             }
             // If no chunk available, creates one of exactly the right size.
@@ -329,8 +377,8 @@ impl<Storage: ComputeStorage> SimpleMemoryManagement<Storage> {
     }
 
     /// Creates a chunk of given size by allocating on the storage.
-    fn create_chunk(&mut self, size: usize) -> SimpleHandle {
-        let storage = self.storage.alloc(size);
+    fn create_chunk(&mut self, size: usize) -> Result<SimpleHandle, ComputeError> {
+        let storage = self.storage.try_alloc(size)?;
         let handle = ChunkHandle::new();
 
         self.chunks.insert(
@@ -338,7 +386,7 @@ impl<Storage: ComputeStorage> SimpleMemoryManagement<Storage> {
             Chunk::new(storage, handle.clone(), Vec::new()),
         );
 
-        SimpleHandle::Chunk(handle)
+        Ok(SimpleHandle::Chunk(handle))
     }
 
     /// Deallocates free chunks and remove them from chunks map.
@@ -402,7 +450,7 @@ mod tests {
         );
 
         let chunk_size = 4;
-        let simple_handle = memory_management.create_chunk(chunk_size);
+        let simple_handle = memory_management.create_chunk(chunk_size).unwrap();
 
         let x = simple_handle.clone();
         core::mem::drop(simple_handle);
@@ -419,7 +467,7 @@ mod tests {
         );
 
         let chunk_size = 4;
-        let simple_handle = memory_management.create_chunk(chunk_size);
+        let simple_handle = memory_management.create_chunk(chunk_size).unwrap();
 
         let x = simple_handle.clone();
 
@@ -477,6 +525,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn memory_usage_counts_chunks_and_slices() {
+        let mut memory_management = SimpleMemoryManagement::new(
+            BytesStorage::default(),
+            DeallocStrategy::Never,
+            SliceStrategy::Ratio(0.5),
+        );
+        let chunk = memory_management.reserve_no_sync(10);
+        let other_chunk = memory_management.reserve_no_sync(20);
+        drop(chunk);
+        let _slice = memory_management.reserve_no_sync(8);
+
+        let usage = memory_management.memory_usage();
+
+        assert_eq!(usage.bytes_reserved, 30);
+        assert_eq!(usage.bytes_in_use, 28);
+        assert_eq!(usage.pools[0].num_chunks, 2);
+        assert_eq!(usage.pools[0].num_slices, 2);
+        assert_eq!(usage.pools[0].num_free_slices, 0);
+
+        drop(other_chunk);
+        let usage = memory_management.memory_usage();
+
+        assert_eq!(usage.bytes_in_use, 8);
+        assert_eq!(usage.pools[0].num_free_slices, 1);
+        assert_eq!(usage.pools[0].largest_free_slice, 20);
+    }
+
+    #[test]
+    fn cleanup_deallocs_chunks_without_references() {
+        let mut memory_management = SimpleMemoryManagement::new(
+            BytesStorage::default(),
+            DeallocStrategy::Never,
+            SliceStrategy::Never,
+        );
+        let chunk = memory_management.reserve_no_sync(10);
+        let _other_chunk = memory_management.reserve_no_sync(20);
+        drop(chunk);
+
+        memory_management.cleanup();
+
+        assert_eq!(memory_management.chunks.len(), 1);
+        assert_eq!(memory_management.memory_usage().bytes_reserved, 20);
+    }
+
     #[test]
     fn slice_strategy_minimum_bytes() {
         let strategy = SliceStrategy::MinimumSize(100);
@@ -0,0 +1,108 @@
+use alloc::vec::Vec;
+
+/// Memory usage of a [memory management](crate::memory_management::MemoryManagement).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// The amount of bytes reserved on the storage.
+    pub bytes_reserved: usize,
+    /// The amount of bytes used by the handles that are still alive.
+    pub bytes_in_use: usize,
+    /// The usage of every memory pool.
+    pub pools: Vec<MemoryPoolUsage>,
+}
+
+/// Memory usage of a single memory pool.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryPoolUsage {
+    /// The max size in bytes a slice can take in the pool, [None] when the size isn't bounded.
+    pub slice_max_size: Option<usize>,
+    /// The number of chunks allocated on the storage.
+    pub num_chunks: usize,
+    /// The number of slices created upon the chunks.
+    pub num_slices: usize,
+    /// The number of slices that can be reused.
+    pub num_free_slices: usize,
+    /// The amount of bytes reserved on the storage.
+    pub bytes_reserved: usize,
+    /// The amount of bytes used by the handles that are still alive.
+    pub bytes_in_use: usize,
+    /// The amount of bytes in the free slices.
+    pub bytes_free: usize,
+    /// The size in bytes of the largest free slice.
+    pub largest_free_slice: usize,
+}
+
+impl MemoryUsage {
+    /// Combine the usage of multiple pools.
+    pub fn from_pools(pools: Vec<MemoryPoolUsage>) -> Self {
+        Self {
+            bytes_reserved: pools.iter().map(|pool| pool.bytes_reserved).sum(),
+            bytes_in_use: pools.iter().map(|pool| pool.bytes_in_use).sum(),
+            pools,
+        }
+    }
+
+    /// The fragmentation of the free memory of every pool, see
+    /// [MemoryPoolUsage::fragmentation].
+    pub fn fragmentation(&self) -> f32 {
+        let bytes_free: usize = self.pools.iter().map(|pool| pool.bytes_free).sum();
+        let largest_free_slice = self
+            .pools
+            .iter()
+            .map(|pool| pool.largest_free_slice)
+            .max()
+            .unwrap_or(0);
+
+        fragmentation(bytes_free, largest_free_slice)
+    }
+}
+
+impl MemoryPoolUsage {
+    /// The fragmentation of the free memory, between 0 and 1.
+    ///
+    /// Zero means that all the free memory can be used by a single allocation, while values close
+    /// to one mean that the free memory is spread over many small slices.
+    pub fn fragmentation(&self) -> f32 {
+        fragmentation(self.bytes_free, self.largest_free_slice)
+    }
+}
+
+fn fragmentation(bytes_free: usize, largest_free_slice: usize) -> f32 {
+    if bytes_free == 0 {
+        return 0.0;
+    }
+
+    1.0 - largest_free_slice as f32 / bytes_free as f32
+}
+
+impl core::fmt::Display for MemoryUsage {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(
+            f,
+            "Memory usage: {} bytes in use, {} bytes reserved, fragmentation {:.2}",
+            self.bytes_in_use,
+            self.bytes_reserved,
+            self.fragmentation()
+        )?;
+
+        for pool in self.pools.iter() {
+            match pool.slice_max_size {
+                Some(size) => write!(f, "  - Pool (slices up to {size} bytes): ")?,
+                None => write!(f, "  - Pool: ")?,
+            }
+
+            writeln!(
+                f,
+                "{} chunks, {} slices ({} free), {} bytes in use, {} bytes reserved, fragmentation {:.2}",
+                pool.num_chunks,
+                pool.num_slices,
+                pool.num_free_slices,
+                pool.bytes_in_use,
+                pool.bytes_reserved,
+                pool.fragmentation()
+            )?;
+        }
+
+        Ok(())
+    }
+}
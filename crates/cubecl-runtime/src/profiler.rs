@@ -0,0 +1,323 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+use core::time::Duration;
+
+#[cfg(all(not(target_family = "wasm"), feature = "std"))]
+use std::time::Instant;
+#[cfg(all(target_family = "wasm", feature = "std"))]
+use web_time::Instant;
+
+/// A request handled by a [compute server](crate::server::ComputeServer).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProfileEventKind {
+    /// The compilation of a kernel, which happens the first time it is executed.
+    Compile {
+        /// The name of the kernel.
+        name: String,
+        /// The id of the kernel.
+        id: String,
+    },
+    /// The execution of a kernel.
+    Execute {
+        /// The name of the kernel.
+        name: String,
+        /// The id of the kernel.
+        id: String,
+        /// The number of cubes dispatched, [None] when it's only known by the device.
+        cube_count: Option<[u32; 3]>,
+        /// The size in bytes of every binding.
+        binding_sizes: Vec<usize>,
+    },
+    /// The creation of a handle from data.
+    Create {
+        /// The amount of bytes transferred to the device.
+        size: usize,
+    },
+    /// The read of a handle.
+    Read {
+        /// The amount of bytes transferred from the device.
+        size: usize,
+    },
+}
+
+impl ProfileEventKind {
+    /// The category of the event.
+    pub fn category(&self) -> &'static str {
+        match self {
+            Self::Compile { .. } => "compile",
+            Self::Execute { .. } => "execute",
+            Self::Create { .. } => "create",
+            Self::Read { .. } => "read",
+        }
+    }
+}
+
+/// A request recorded by the [profiler](Profiler).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileEvent {
+    /// The request.
+    pub kind: ProfileEventKind,
+    /// When the request started, relative to the start of the profile.
+    pub start: Duration,
+    /// The time spent handling the request.
+    pub duration: Duration,
+}
+
+/// The requests recorded while profiling a compute server.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProfileTrace {
+    /// The recorded requests, in the order they completed.
+    pub events: Vec<ProfileEvent>,
+}
+
+impl ProfileTrace {
+    /// The total time spent handling the requests of the given [category](ProfileEventKind::category).
+    pub fn total_duration(&self, category: &str) -> Duration {
+        self.events
+            .iter()
+            .filter(|event| event.kind.category() == category)
+            .map(|event| event.duration)
+            .sum()
+    }
+
+    /// Format the trace with the Chrome trace event format, which can be opened with
+    /// `chrome://tracing` or [Perfetto](https://ui.perfetto.dev).
+    pub fn to_chrome_trace(&self) -> String {
+        let mut trace = String::from("{\"traceEvents\":[");
+
+        for (i, event) in self.events.iter().enumerate() {
+            if i > 0 {
+                trace.push(',');
+            }
+
+            let name = match &event.kind {
+                ProfileEventKind::Compile { name, .. } | ProfileEventKind::Execute { name, .. } => {
+                    name.as_str()
+                }
+                ProfileEventKind::Create { .. } => "create",
+                ProfileEventKind::Read { .. } => "read",
+            };
+
+            write!(
+                trace,
+                "{{\"name\":\"{}\",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":0,\"tid\":0,\"args\":{{{}}}}}",
+                escape_json(name),
+                event.kind.category(),
+                event.start.as_secs_f64() * 1_000_000.0,
+                event.duration.as_secs_f64() * 1_000_000.0,
+                chrome_trace_args(&event.kind),
+            )
+            .unwrap();
+        }
+
+        trace.push_str("],\"displayTimeUnit\":\"ms\"}");
+        trace
+    }
+
+    /// Write the trace with the [Chrome trace event format](Self::to_chrome_trace) to a file.
+    #[cfg(feature = "std")]
+    pub fn write_chrome_trace(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_chrome_trace())
+    }
+}
+
+fn chrome_trace_args(kind: &ProfileEventKind) -> String {
+    match kind {
+        ProfileEventKind::Compile { id, .. } => format!("\"id\":\"{}\"", escape_json(id)),
+        ProfileEventKind::Execute {
+            id,
+            cube_count,
+            binding_sizes,
+            ..
+        } => {
+            let cube_count = match cube_count {
+                Some([x, y, z]) => format!("[{x},{y},{z}]"),
+                None => "\"dynamic\"".into(),
+            };
+            let binding_sizes = binding_sizes
+                .iter()
+                .map(|size| format!("{size}"))
+                .collect::<Vec<_>>()
+                .join(",");
+
+            format!(
+                "\"id\":\"{}\",\"cube_count\":{cube_count},\"binding_sizes\":[{binding_sizes}]",
+                escape_json(id)
+            )
+        }
+        ProfileEventKind::Create { size } | ProfileEventKind::Read { size } => {
+            format!("\"size\":{size}")
+        }
+    }
+}
+
+fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// The moment a profiled request started.
+#[derive(Debug, Clone, Copy)]
+pub struct ProfileStart {
+    #[cfg(feature = "std")]
+    instant: Instant,
+}
+
+impl ProfileStart {
+    /// The current moment.
+    #[cfg(feature = "std")]
+    pub fn now() -> Self {
+        Self {
+            instant: Instant::now(),
+        }
+    }
+}
+
+/// Records the requests handled by a compute server between [start](Profiler::start) and
+/// [end](Profiler::end).
+///
+/// Profiling is only available with std, the profiler is never activated otherwise.
+#[derive(Debug, Default)]
+pub struct Profiler {
+    #[cfg(feature = "std")]
+    state: Option<(Instant, Vec<ProfileEvent>)>,
+}
+
+impl Profiler {
+    /// Create a new profiler, which isn't activated.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether the profiler is activated.
+    pub fn is_activated(&self) -> bool {
+        #[cfg(feature = "std")]
+        return self.state.is_some();
+
+        #[cfg(not(feature = "std"))]
+        false
+    }
+
+    /// Start recording the requests, discarding the ones recorded so far.
+    pub fn start(&mut self) {
+        #[cfg(feature = "std")]
+        {
+            self.state = Some((Instant::now(), Vec::new()));
+        }
+    }
+
+    /// Stop recording the requests and returns them.
+    pub fn end(&mut self) -> ProfileTrace {
+        #[cfg(feature = "std")]
+        if let Some((_, events)) = self.state.take() {
+            return ProfileTrace { events };
+        }
+
+        ProfileTrace::default()
+    }
+
+    /// The current moment when the profiler is activated.
+    pub fn now(&self) -> Option<ProfileStart> {
+        #[cfg(feature = "std")]
+        return self.is_activated().then(ProfileStart::now);
+
+        #[cfg(not(feature = "std"))]
+        None
+    }
+
+    /// Record a request that started at the given moment and is now completed.
+    ///
+    /// Nothing is recorded when the profiler isn't activated or when the request started before.
+    pub fn register(&mut self, started: Option<ProfileStart>, kind: ProfileEventKind) {
+        #[cfg(feature = "std")]
+        if let (Some(started), Some((start, events))) = (started, self.state.as_mut()) {
+            if started.instant < *start {
+                return;
+            }
+
+            events.push(ProfileEvent {
+                kind,
+                start: started.instant - *start,
+                duration: started.instant.elapsed(),
+            });
+        }
+
+        #[cfg(not(feature = "std"))]
+        let _ = (started, kind);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn profiler_records_only_when_activated() {
+        let mut profiler = Profiler::new();
+        let started = ProfileStart::now();
+        profiler.register(Some(started), ProfileEventKind::Create { size: 4 });
+        assert!(profiler.now().is_none());
+
+        profiler.start();
+        let started = profiler.now();
+        profiler.register(started, ProfileEventKind::Read { size: 8 });
+        let trace = profiler.end();
+
+        assert!(!profiler.is_activated());
+        assert_eq!(trace.events.len(), 1);
+        assert_eq!(trace.events[0].kind, ProfileEventKind::Read { size: 8 });
+    }
+
+    #[test]
+    fn chrome_trace_contains_every_event() {
+        let trace = ProfileTrace {
+            events: vec![
+                ProfileEvent {
+                    kind: ProfileEventKind::Compile {
+                        name: "add".into(),
+                        id: "Key { \"vectorized\" }".into(),
+                    },
+                    start: Duration::from_micros(1),
+                    duration: Duration::from_micros(20),
+                },
+                ProfileEvent {
+                    kind: ProfileEventKind::Execute {
+                        name: "add".into(),
+                        id: "Key { \"vectorized\" }".into(),
+                        cube_count: Some([4, 1, 1]),
+                        binding_sizes: vec![16, 16],
+                    },
+                    start: Duration::from_micros(21),
+                    duration: Duration::from_micros(2),
+                },
+            ],
+        };
+
+        assert_eq!(
+            trace.to_chrome_trace(),
+            "{\"traceEvents\":[\
+            {\"name\":\"add\",\"cat\":\"compile\",\"ph\":\"X\",\"ts\":1.000,\"dur\":20.000,\"pid\":0,\"tid\":0,\
+            \"args\":{\"id\":\"Key { \\\"vectorized\\\" }\"}},\
+            {\"name\":\"add\",\"cat\":\"execute\",\"ph\":\"X\",\"ts\":21.000,\"dur\":2.000,\"pid\":0,\"tid\":0,\
+            \"args\":{\"id\":\"Key { \\\"vectorized\\\" }\",\"cube_count\":[4,1,1],\"binding_sizes\":[16,16]}}\
+            ],\"displayTimeUnit\":\"ms\"}"
+        );
+        assert_eq!(trace.total_duration("compile"), Duration::from_micros(20));
+    }
+}
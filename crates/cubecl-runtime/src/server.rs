@@ -1,11 +1,73 @@
 use crate::{
-    memory_management::{MemoryHandle, MemoryManagement},
+    memory_management::{MemoryHandle, MemoryManagement, MemoryUsage},
+    profiler::ProfileTrace,
+    staging::StagingBuffer,
     storage::ComputeStorage,
+    stream::{EventId, StreamId},
     ExecutionMode,
 };
+use alloc::string::String;
 use alloc::vec::Vec;
 use core::fmt::Debug;
-use cubecl_common::{reader::Reader, sync_type::SyncType};
+use cubecl_common::{benchmark::TimestampsResult, reader::Reader, sync_type::SyncType};
+
+/// Error that can happen when a [compute server](ComputeServer) handles a request.
+///
+/// Those errors leave the server in a valid state, so the request can be retried later, e.g.
+/// after some memory is released.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ComputeError {
+    /// Not enough memory is available to reserve the given amount of bytes.
+    OutOfMemory {
+        /// The amount of bytes requested.
+        size: usize,
+        /// Why the memory couldn't be reserved.
+        reason: String,
+    },
+    /// The request uses a feature that isn't supported by the server.
+    UnsupportedFeature(String),
+    /// The kernel couldn't be compiled.
+    Compilation(String),
+    /// The binding doesn't refer to memory managed by the server.
+    InvalidBinding(String),
+    /// The device failed to execute the request.
+    Execution(String),
+    /// The stream wasn't created on the server.
+    InvalidStream(String),
+    /// The event wasn't recorded on the server.
+    InvalidEvent(String),
+}
+
+impl core::fmt::Display for ComputeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ComputeError::OutOfMemory { size, reason } => f.write_fmt(format_args!(
+                "Out of memory reserving {size} bytes: {reason}"
+            )),
+            ComputeError::UnsupportedFeature(reason) => {
+                f.write_fmt(format_args!("Unsupported feature: {reason}"))
+            }
+            ComputeError::Compilation(reason) => {
+                f.write_fmt(format_args!("Compilation error: {reason}"))
+            }
+            ComputeError::InvalidBinding(reason) => {
+                f.write_fmt(format_args!("Invalid binding: {reason}"))
+            }
+            ComputeError::Execution(reason) => {
+                f.write_fmt(format_args!("Execution error: {reason}"))
+            }
+            ComputeError::InvalidStream(reason) => {
+                f.write_fmt(format_args!("Invalid stream: {reason}"))
+            }
+            ComputeError::InvalidEvent(reason) => {
+                f.write_fmt(format_args!("Invalid event: {reason}"))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ComputeError {}
 
 /// The compute server is responsible for handling resources and computations over resources.
 ///
@@ -27,7 +89,16 @@ where
     type FeatureSet: Send + Sync;
 
     /// Given a handle, returns the owned resource as bytes.
-    fn read(&mut self, binding: Binding<Self>) -> Reader;
+    fn try_read(&mut self, binding: Binding<Self>) -> Result<Reader, ComputeError>;
+
+    /// Given a handle, returns the owned resource as bytes.
+    ///
+    /// # Panics
+    ///
+    /// If the resource can't be read, use [try_read](ComputeServer::try_read) to handle the error.
+    fn read(&mut self, binding: Binding<Self>) -> Reader {
+        self.try_read(binding).unwrap_or_else(|err| panic!("{err}"))
+    }
 
     /// Given a resource handle, returns the storage resource.
     fn get_resource(
@@ -36,12 +107,64 @@ where
     ) -> <Self::Storage as ComputeStorage>::Resource;
 
     /// Given a resource as bytes, stores it and returns the memory handle.
-    fn create(&mut self, data: &[u8]) -> Handle<Self>;
+    fn try_create(&mut self, data: &[u8]) -> Result<Handle<Self>, ComputeError>;
+
+    /// Given a resource as bytes, stores it and returns the memory handle.
+    ///
+    /// # Panics
+    ///
+    /// If the memory can't be reserved, use [try_create](ComputeServer::try_create) to handle
+    /// the error.
+    fn create(&mut self, data: &[u8]) -> Handle<Self> {
+        self.try_create(data).unwrap_or_else(|err| panic!("{err}"))
+    }
 
     /// Reserves `size` bytes in the storage, and returns a handle over them.
-    fn empty(&mut self, size: usize) -> Handle<Self>;
+    fn try_empty(&mut self, size: usize) -> Result<Handle<Self>, ComputeError>;
+
+    /// Reserves `size` bytes in the storage, and returns a handle over them.
+    ///
+    /// # Panics
+    ///
+    /// If the memory can't be reserved, use [try_empty](ComputeServer::try_empty) to handle the
+    /// error.
+    fn empty(&mut self, size: usize) -> Handle<Self> {
+        self.try_empty(size).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Allocates a host buffer of `size` bytes from which the data can be uploaded without
+    /// blocking, e.g. page-locked memory.
+    fn try_staging(&mut self, size: usize) -> Result<StagingBuffer, ComputeError>;
+
+    /// Enqueues the upload of the `buffer` to a new handle on the given `stream`, without waiting
+    /// for the copy to complete.
+    ///
+    /// The handle can be used by the tasks submitted to the same stream, while the other streams
+    /// must wait for an event recorded on the stream after the upload.
+    fn try_upload(
+        &mut self,
+        buffer: StagingBuffer,
+        stream: StreamId,
+    ) -> Result<Handle<Self>, ComputeError>;
+
+    /// Executes the `kernel` over the given memory `handles` on the given `stream`.
+    ///
+    /// Kernels have mutable access to every resource they are given
+    /// and are responsible of determining which should be read or written.
+    ///
+    /// # Safety
+    ///
+    /// When executing with mode [ExecutionMode::Unchecked], out-of-bound reads and writes can happen.
+    unsafe fn try_execute(
+        &mut self,
+        kernel: Self::Kernel,
+        count: Self::DispatchOptions,
+        bindings: Vec<Binding<Self>>,
+        kind: ExecutionMode,
+        stream: StreamId,
+    ) -> Result<(), ComputeError>;
 
-    /// Executes the `kernel` over the given memory `handles`.
+    /// Executes the `kernel` over the given memory `handles` on the given `stream`.
     ///
     /// Kernels have mutable access to every resource they are given
     /// and are responsible of determining which should be read or written.
@@ -49,16 +172,71 @@ where
     /// # Safety
     ///
     /// When executing with mode [ExecutionMode::Unchecked], out-of-bound reads and writes can happen.
+    ///
+    /// # Panics
+    ///
+    /// If the kernel can't be executed, use [try_execute](ComputeServer::try_execute) to handle
+    /// the error.
     unsafe fn execute(
         &mut self,
         kernel: Self::Kernel,
         count: Self::DispatchOptions,
         bindings: Vec<Binding<Self>>,
         kind: ExecutionMode,
-    );
+        stream: StreamId,
+    ) {
+        self.try_execute(kernel, count, bindings, kind, stream)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Create a new stream, whose tasks can be executed concurrently with the tasks of the other
+    /// streams.
+    ///
+    /// Memory released while the tasks of a stream are pending can be reused by the other
+    /// streams, so the handles used by a stream must be kept alive until its tasks are
+    /// synchronized with the other streams.
+    fn try_create_stream(&mut self) -> Result<StreamId, ComputeError>;
+
+    /// Record an event on the stream, which completes once every task submitted to the stream so
+    /// far is completed.
+    fn try_record_event(&mut self, stream: StreamId) -> Result<EventId, ComputeError>;
 
-    /// Wait for the completion of every task in the server.
+    /// Make the tasks submitted to the stream after this call wait for the completion of the
+    /// event.
+    fn try_wait_event(&mut self, stream: StreamId, event: EventId) -> Result<(), ComputeError>;
+
+    /// Wait for the completion of every task in the server, on every stream.
     fn sync(&mut self, command: SyncType);
+
+    /// The memory usage of the [memory management](MemoryManagement).
+    fn memory_usage(&mut self) -> MemoryUsage;
+
+    /// Releases the memory that isn't used anymore back to the device, see
+    /// [cleanup](MemoryManagement::cleanup).
+    fn memory_cleanup(&mut self);
+
+    /// Enable the timestamps on the device, used to measure the time spent executing the tasks
+    /// with [sync_elapsed](ComputeServer::sync_elapsed).
+    fn enable_timestamps(&mut self);
+
+    /// Disable the timestamps on the device.
+    fn disable_timestamps(&mut self);
+
+    /// Whether the timestamps are enabled, without consuming the time measured so far.
+    fn timestamps_enabled(&mut self) -> bool;
+
+    /// Wait for the completion of every task, and returns the time the device spent executing
+    /// them since the last call or since the timestamps were enabled.
+    ///
+    /// Unlike measuring the time on the host, this doesn't include the launch and submission
+    /// overhead.
+    fn sync_elapsed(&mut self) -> TimestampsResult;
+
+    /// Start recording the requests handled by the server with its [profiler](crate::profiler::Profiler).
+    fn start_profile(&mut self);
+
+    /// Stop recording the requests and returns them.
+    fn end_profile(&mut self) -> ProfileTrace;
 }
 
 /// Server handle containing the [memory handle](MemoryManagement::Handle).
@@ -66,6 +244,9 @@ where
 pub struct Handle<Server: ComputeServer> {
     /// Memory handle.
     pub memory: <Server::MemoryManagement as MemoryManagement<Server::Storage>>::Handle,
+    /// The size of the data in bytes, which can be smaller than the reserved memory when the
+    /// server rounds up the allocations.
+    pub size: usize,
 }
 
 /// Binding of a [tensor handle](Handle) to execute a kernel.
@@ -73,6 +254,8 @@ pub struct Handle<Server: ComputeServer> {
 pub struct Binding<Server: ComputeServer> {
     /// Memory binding.
     pub memory: <Server::MemoryManagement as MemoryManagement<Server::Storage>>::Binding,
+    /// The size of the data in bytes, see [Handle::size].
+    pub size: usize,
 }
 
 impl<Server: ComputeServer> Handle<Server> {
@@ -87,6 +270,7 @@ impl<Server: ComputeServer> Handle<Server> {
     pub fn binding(self) -> Binding<Server> {
         Binding {
             memory: MemoryHandle::binding(self.memory),
+            size: self.size,
         }
     }
 }
@@ -95,6 +279,7 @@ impl<Server: ComputeServer> Clone for Handle<Server> {
     fn clone(&self) -> Self {
         Self {
             memory: self.memory.clone(),
+            size: self.size,
         }
     }
 }
@@ -103,6 +288,7 @@ impl<Server: ComputeServer> Clone for Binding<Server> {
     fn clone(&self) -> Self {
         Self {
             memory: self.memory.clone(),
+            size: self.size,
         }
     }
 }
@@ -0,0 +1,61 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
+
+/// Host memory that can be uploaded asynchronously to a [compute server](crate::server::ComputeServer).
+pub trait StagingMemory: Send + core::fmt::Debug {
+    /// The content of the memory.
+    fn as_slice(&self) -> &[u8];
+    /// The mutable content of the memory.
+    fn as_mut_slice(&mut self) -> &mut [u8];
+}
+
+impl StagingMemory for Vec<u8> {
+    fn as_slice(&self) -> &[u8] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+/// Host buffer allocated with [staging](crate::client::ComputeClient::staging), e.g. page-locked
+/// memory, from which the data can be [uploaded](crate::client::ComputeClient::upload) without
+/// blocking.
+///
+/// The buffer is owned by the server during the upload and is released once the upload is
+/// completed.
+#[derive(Debug)]
+pub struct StagingBuffer {
+    memory: Box<dyn StagingMemory>,
+}
+
+impl StagingBuffer {
+    /// Create a staging buffer from host memory.
+    pub fn new(memory: impl StagingMemory + 'static) -> Self {
+        Self {
+            memory: Box::new(memory),
+        }
+    }
+}
+
+impl From<Vec<u8>> for StagingBuffer {
+    fn from(data: Vec<u8>) -> Self {
+        Self::new(data)
+    }
+}
+
+impl Deref for StagingBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.memory.as_slice()
+    }
+}
+
+impl DerefMut for StagingBuffer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.memory.as_mut_slice()
+    }
+}
@@ -1,4 +1,4 @@
-use crate::storage_id_type;
+use crate::{server::ComputeError, storage_id_type};
 
 // This ID is used to map a handle to its actual data.
 storage_id_type!(StorageId);
@@ -54,7 +54,17 @@ pub trait ComputeStorage: Send {
     fn get(&mut self, handle: &StorageHandle) -> Self::Resource;
 
     /// Allocates `size` units of memory and returns a handle to it
-    fn alloc(&mut self, size: usize) -> StorageHandle;
+    fn try_alloc(&mut self, size: usize) -> Result<StorageHandle, ComputeError>;
+
+    /// Allocates `size` units of memory and returns a handle to it
+    ///
+    /// # Panics
+    ///
+    /// If the memory can't be allocated, use [try_alloc](ComputeStorage::try_alloc) to handle the
+    /// error.
+    fn alloc(&mut self, size: usize) -> StorageHandle {
+        self.try_alloc(size).unwrap_or_else(|err| panic!("{err}"))
+    }
 
     /// Deallocates the memory pointed by the given storage id.
     fn dealloc(&mut self, id: StorageId);
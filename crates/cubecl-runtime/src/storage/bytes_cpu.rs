@@ -1,4 +1,5 @@
 use super::{ComputeStorage, StorageHandle, StorageId, StorageUtilization};
+use crate::server::ComputeError;
 use alloc::alloc::{alloc, dealloc, Layout};
 use hashbrown::HashMap;
 
@@ -65,7 +66,7 @@ impl ComputeStorage for BytesStorage {
         }
     }
 
-    fn alloc(&mut self, size: usize) -> StorageHandle {
+    fn try_alloc(&mut self, size: usize) -> Result<StorageHandle, ComputeError> {
         let id = StorageId::new();
         let handle = StorageHandle {
             id,
@@ -73,14 +74,25 @@ impl ComputeStorage for BytesStorage {
         };
 
         unsafe {
-            let layout = Layout::array::<u8>(size).unwrap();
+            let layout = Layout::array::<u8>(size).map_err(|_| ComputeError::OutOfMemory {
+                size,
+                reason: "The size exceeds the maximum allocation size".into(),
+            })?;
             let ptr = alloc(layout);
+
+            if ptr.is_null() {
+                return Err(ComputeError::OutOfMemory {
+                    size,
+                    reason: "The allocator returned a null pointer".into(),
+                });
+            }
+
             let memory = AllocatedBytes { ptr, layout };
 
             self.memory.insert(id, memory);
         }
 
-        handle
+        Ok(handle)
     }
 
     fn dealloc(&mut self, id: StorageId) {
@@ -0,0 +1,119 @@
+use crate::server::ComputeError;
+use alloc::format;
+
+/// Identifier of a stream of a [compute server](crate::server::ComputeServer).
+///
+/// The tasks of a stream are executed in order, but can be executed concurrently with the tasks
+/// of the other streams. Every server has a [default stream](StreamId::DEFAULT), and more can be
+/// created with [create_stream](crate::client::ComputeClient::create_stream).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct StreamId {
+    /// The index of the stream on the server.
+    pub index: u32,
+}
+
+impl StreamId {
+    /// The stream used when none is specified.
+    pub const DEFAULT: Self = Self { index: 0 };
+}
+
+/// Identifier of an event recorded on a stream with
+/// [record_event](crate::client::ComputeClient::record_event).
+///
+/// The event completes once every task submitted to the stream before it was recorded is
+/// completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EventId {
+    /// The index of the event on the server.
+    pub index: u64,
+}
+
+/// Streams of a server executing all the tasks in submission order, e.g. with a single queue.
+///
+/// Every stream shares the same order, so an event is completed for every other stream as soon as
+/// it is recorded and waiting on it doesn't require anything.
+#[derive(Debug, Default)]
+pub struct OrderedStreams {
+    num_streams: u32,
+    num_events: u64,
+}
+
+impl OrderedStreams {
+    /// Create a new stream.
+    pub fn create(&mut self) -> StreamId {
+        self.num_streams += 1;
+
+        StreamId {
+            index: self.num_streams,
+        }
+    }
+
+    /// Returns an error when the stream wasn't created on this server.
+    pub fn validate(&self, stream: StreamId) -> Result<(), ComputeError> {
+        match stream.index <= self.num_streams {
+            true => Ok(()),
+            false => Err(ComputeError::InvalidStream(format!(
+                "The stream {} doesn't exist, only {} streams were created.",
+                stream.index, self.num_streams
+            ))),
+        }
+    }
+
+    /// Record a new event on the stream.
+    pub fn record_event(&mut self, stream: StreamId) -> Result<EventId, ComputeError> {
+        self.validate(stream)?;
+        self.num_events += 1;
+
+        Ok(EventId {
+            index: self.num_events,
+        })
+    }
+
+    /// Wait on the event, which is always completed.
+    pub fn wait_event(&self, stream: StreamId, event: EventId) -> Result<(), ComputeError> {
+        self.validate(stream)?;
+        self.validate_event(event)
+    }
+
+    /// Returns an error when the event wasn't recorded on this server.
+    pub fn validate_event(&self, event: EventId) -> Result<(), ComputeError> {
+        match event.index <= self.num_events {
+            true => Ok(()),
+            false => Err(ComputeError::InvalidEvent(format!(
+                "The event {} doesn't exist, only {} events were recorded.",
+                event.index, self.num_events
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ordered_streams_reject_unknown_streams() {
+        let mut streams = OrderedStreams::default();
+        let stream = streams.create();
+
+        assert_eq!(stream, StreamId { index: 1 });
+        assert!(streams.validate(StreamId::DEFAULT).is_ok());
+        assert!(streams.record_event(stream).is_ok());
+        assert!(matches!(
+            streams.record_event(StreamId { index: 2 }),
+            Err(ComputeError::InvalidStream(_))
+        ));
+    }
+
+    #[test]
+    fn ordered_streams_reject_unknown_events() {
+        let mut streams = OrderedStreams::default();
+        let event = streams.record_event(StreamId::DEFAULT).unwrap();
+
+        assert!(streams.wait_event(StreamId::DEFAULT, event).is_ok());
+        assert!(matches!(
+            streams.wait_event(StreamId::DEFAULT, EventId { index: 2 }),
+            Err(ComputeError::InvalidEvent(_))
+        ));
+    }
+}
@@ -3,7 +3,11 @@ use hashbrown::HashMap;
 
 use crate::{channel::ComputeChannel, client::ComputeClient, server::ComputeServer};
 
-use super::{AutotuneKey, AutotuneOperationSet, Tuner};
+#[cfg(autotune_persistent_cache)]
+use super::TuneCacheError;
+use super::{AutotuneKey, AutotuneOperationSet, TuneOptions, Tuner};
+#[cfg(autotune_persistent_cache)]
+use std::path::Path;
 
 #[cfg(not(feature = "std"))]
 use alloc::{boxed::Box, string::ToString};
@@ -13,6 +17,8 @@ use alloc::{boxed::Box, string::ToString};
 pub struct LocalTuner<AK: AutotuneKey, ID> {
     state: spin::RwLock<Option<HashMap<ID, Tuner<AK>>>>,
     name: &'static str,
+    cache_dir: Option<&'static str>,
+    options: spin::RwLock<TuneOptions>,
 }
 
 /// Create a local tuner with the provided name.
@@ -34,6 +40,32 @@ impl<AK: AutotuneKey, ID: Hash + PartialEq + Eq + Clone + Display> LocalTuner<AK
         Self {
             state: spin::RwLock::new(None),
             name,
+            cache_dir: None,
+            options: spin::RwLock::new(TuneOptions::new()),
+        }
+    }
+
+    /// Create a new local tuner storing its persistent cache in the given directory instead of
+    /// the [default one](super::get_persistent_cache_dir).
+    pub const fn with_cache_dir(name: &'static str, cache_dir: &'static str) -> Self {
+        Self {
+            state: spin::RwLock::new(None),
+            name,
+            cache_dir: Some(cache_dir),
+            options: spin::RwLock::new(TuneOptions::new()),
+        }
+    }
+
+    /// Set the options of the benchmarks used to find the fastest operations.
+    ///
+    /// Only the operations that aren't tuned yet are affected.
+    pub fn set_options(&self, options: TuneOptions) {
+        *self.options.write() = options;
+
+        if let Some(state) = self.state.write().as_mut() {
+            for tuner in state.values_mut() {
+                tuner.set_options(options);
+            }
         }
     }
 
@@ -71,16 +103,51 @@ impl<AK: AutotuneKey, ID: Hash + PartialEq + Eq + Clone + Display> LocalTuner<AK
         let mut state = self.state.write();
         let map = state.get_or_insert_with(Default::default);
 
-        let tuner = if let Some(tuner) = map.get_mut(id) {
-            tuner
-        } else {
+        let tuner = self.tuner(map, id);
+
+        tuner.execute_autotune(autotune_operation_set, client)
+    }
+
+    /// Export the autotune results of the given device to a file.
+    ///
+    /// This can be used to tune the operations ahead of time, and ship the results with the
+    /// application so that they can be [imported](LocalTuner::import) on every machine with the
+    /// same device.
+    #[cfg(autotune_persistent_cache)]
+    pub fn export(&self, id: &ID, path: impl AsRef<Path>) -> Result<(), TuneCacheError> {
+        let mut state = self.state.write();
+        let map = state.get_or_insert_with(Default::default);
+
+        self.tuner(map, id).export(path.as_ref())
+    }
+
+    /// Import the autotune results of the given device from a file created with
+    /// [export](LocalTuner::export), returning the number of imported results.
+    #[cfg(autotune_persistent_cache)]
+    pub fn import(&self, id: &ID, path: impl AsRef<Path>) -> Result<usize, TuneCacheError> {
+        let mut state = self.state.write();
+        let map = state.get_or_insert_with(Default::default);
+
+        self.tuner(map, id).import(path.as_ref())
+    }
+
+    fn tuner<'a>(&self, map: &'a mut HashMap<ID, Tuner<AK>>, id: &ID) -> &'a mut Tuner<AK> {
+        if !map.contains_key(id) {
             let name = self.name.replace("::", "-");
-            let tuner = Tuner::new(&name, &id.to_string());
+
+            #[cfg(autotune_persistent_cache)]
+            let mut tuner = match self.cache_dir {
+                Some(cache_dir) => Tuner::with_cache_dir(&name, &id.to_string(), cache_dir),
+                None => Tuner::new(&name, &id.to_string()),
+            };
+            #[cfg(not(autotune_persistent_cache))]
+            let mut tuner = Tuner::new(&name, &id.to_string());
+
+            tuner.set_options(*self.options.read());
             map.insert(id.clone(), tuner);
-            map.get_mut(id).unwrap()
-        };
+        }
 
-        tuner.execute_autotune(autotune_operation_set, client)
+        map.get_mut(id).unwrap()
     }
 
     /// Return the autotune result given a key.
@@ -1,4 +1,4 @@
-use cubecl_common::benchmark::Benchmark;
+use cubecl_common::benchmark::{Benchmark, TimestampsResult, TimingMethod};
 use cubecl_common::sync_type::SyncType;
 
 use crate::channel::ComputeChannel;
@@ -8,12 +8,60 @@ use crate::server::ComputeServer;
 use super::AutotuneOperation;
 use alloc::boxed::Box;
 use alloc::string::{String, ToString};
+use core::time::Duration;
+
+/// The number of samples collected before a benchmark can be stopped early.
+const EARLY_STOP_MIN_SAMPLES: usize = 3;
+
+/// Options of the benchmarks used to find the fastest [autotune operation](AutotuneOperation).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TuneOptions {
+    /// The number of executions of an operation before measuring its execution time.
+    pub num_warmup: usize,
+    /// The number of measured executions of an operation.
+    pub num_samples: usize,
+    /// How the executions are measured. When timing on the device, the system time is used
+    /// instead if the device doesn't support timestamps.
+    pub timing_method: TimingMethod,
+    /// Stop benchmarking an operation when its fastest sample is this many times slower than the
+    /// fastest operation benchmarked so far, [None] to always collect every sample.
+    pub early_stop_ratio: Option<f64>,
+    /// The number of standard deviations from the median after which a sample is considered an
+    /// outlier and ignored. Every sample is kept when the threshold isn't positive.
+    pub outlier_threshold: f64,
+    /// The relative difference between the median durations of two operations under which they
+    /// are considered tied, in which case the operation declared first is selected.
+    pub tie_tolerance: f64,
+}
+
+impl TuneOptions {
+    /// Create the default options.
+    pub const fn new() -> Self {
+        Self {
+            num_warmup: 1,
+            num_samples: 10,
+            timing_method: TimingMethod::Device,
+            early_stop_ratio: Some(1.5),
+            outlier_threshold: 2.0,
+            tie_tolerance: 0.05,
+        }
+    }
+}
+
+impl Default for TuneOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 /// A benchmark that runs on server handles
 #[derive(new)]
 pub struct TuneBenchmark<S: ComputeServer, C, Out = ()> {
     operation: Box<dyn AutotuneOperation<Out>>,
     client: ComputeClient<S, C>,
+    options: TuneOptions,
+    /// The median duration of the fastest operation benchmarked so far.
+    fastest: Option<Duration>,
 }
 
 impl<Out> Clone for Box<dyn AutotuneOperation<Out>> {
@@ -29,8 +77,34 @@ impl<S: ComputeServer, C: ComputeChannel<S>, Out> Benchmark for TuneBenchmark<S,
         self.operation.clone()
     }
 
+    fn num_warmup(&self) -> usize {
+        self.options.num_warmup
+    }
+
     fn num_samples(&self) -> usize {
-        10
+        self.options.num_samples
+    }
+
+    fn timing_method(&self) -> TimingMethod {
+        self.options.timing_method
+    }
+
+    fn sync_elapsed(&self) -> TimestampsResult {
+        self.client.sync_elapsed()
+    }
+
+    fn should_stop(&self, durations: &[Duration]) -> bool {
+        let (Some(ratio), Some(fastest)) = (self.options.early_stop_ratio, self.fastest) else {
+            return false;
+        };
+
+        if durations.len() < EARLY_STOP_MIN_SAMPLES {
+            return false;
+        }
+
+        // Even the fastest sample is slower, so the operation can't be selected.
+        let min = durations.iter().min().unwrap();
+        min.as_secs_f64() > fastest.as_secs_f64() * ratio
     }
 
     fn execute(&self, operation: Self::Args) {
@@ -18,14 +18,105 @@ use super::AutotuneOperationSet;
 use alloc::boxed::Box;
 use hashbrown::HashMap;
 
+/// Environment variable used to change the directory of the persistent cache.
+#[cfg(autotune_persistent_cache)]
+pub const AUTOTUNE_CACHE_DIR_ENV: &str = "CUBECL_AUTOTUNE_CACHE_DIR";
+
+/// Version of the persistent cache format.
+///
+/// It must be increased when the format or the way checksums are computed changes, so that
+/// outdated files are detected.
+#[cfg(autotune_persistent_cache)]
+const PERSISTENT_CACHE_VERSION: u32 = 1;
+
+#[cfg(autotune_persistent_cache)]
+/// Return the default directory for the persistent cache on disk.
+///
+/// The directory can be set with the `CUBECL_AUTOTUNE_CACHE_DIR` environment variable, otherwise
+/// `~/.cache/cubecl/autotune` is used, falling back to the temporary directory when there is no
+/// home directory.
+pub fn get_persistent_cache_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(AUTOTUNE_CACHE_DIR_ENV) {
+        return PathBuf::from(dir);
+    }
+
+    let root = match dirs::home_dir() {
+        Some(home_dir) => home_dir.join(".cache"),
+        None => std::env::temp_dir(),
+    };
+
+    root.join("cubecl").join("autotune")
+}
+
 #[cfg(autotune_persistent_cache)]
 /// Return the file path for the persistent cache on disk
 /// prefix should be the device id computed at the backend level
 pub fn get_persistent_cache_file_path(prefix: &str) -> PathBuf {
-    let home_dir = dirs::home_dir().expect("An home directory should exist");
-    let path_dir = home_dir.join(".cache").join("cubecl").join("autotune");
-    let path = Path::new(&path_dir);
-    path.join(format!("{}-autotune-cache.json", prefix))
+    persistent_cache_file_path(&get_persistent_cache_dir(), prefix)
+}
+
+#[cfg(autotune_persistent_cache)]
+fn persistent_cache_file_path(dir: &Path, prefix: &str) -> PathBuf {
+    dir.join(format!("{}-autotune-cache.json", prefix))
+}
+
+/// Error that can happen when reading or writing autotune results.
+#[cfg(autotune_persistent_cache)]
+#[derive(Debug)]
+pub enum TuneCacheError {
+    /// The file couldn't be read or written.
+    Io(io::Error),
+    /// The file isn't a valid autotune cache.
+    InvalidFormat(serde_json::Error),
+    /// The file was written with another version of the cache format.
+    VersionMismatch {
+        /// The version of the current cache format.
+        expected: u32,
+        /// The version of the file.
+        found: u32,
+    },
+    /// The results were tuned on another device.
+    DeviceMismatch {
+        /// The device of the tuner.
+        expected: String,
+        /// The device the results were tuned on.
+        found: String,
+    },
+}
+
+#[cfg(autotune_persistent_cache)]
+impl core::fmt::Display for TuneCacheError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TuneCacheError::Io(err) => write!(f, "{err}"),
+            TuneCacheError::InvalidFormat(err) => write!(f, "Invalid autotune cache: {err}"),
+            TuneCacheError::VersionMismatch { expected, found } => write!(
+                f,
+                "Autotune cache version {found} isn't supported, expected version {expected}"
+            ),
+            TuneCacheError::DeviceMismatch { expected, found } => write!(
+                f,
+                "Autotune results were tuned on device '{found}', expected device '{expected}'"
+            ),
+        }
+    }
+}
+
+#[cfg(autotune_persistent_cache)]
+impl std::error::Error for TuneCacheError {}
+
+#[cfg(autotune_persistent_cache)]
+impl From<io::Error> for TuneCacheError {
+    fn from(err: io::Error) -> Self {
+        TuneCacheError::Io(err)
+    }
+}
+
+#[cfg(autotune_persistent_cache)]
+impl From<serde_json::Error> for TuneCacheError {
+    fn from(err: serde_json::Error) -> Self {
+        TuneCacheError::InvalidFormat(err)
+    }
 }
 
 /// In-memory cache entry
@@ -44,6 +135,15 @@ pub(crate) struct PersistentCacheEntry {
     fastest_index: usize,
 }
 
+/// Content of a persistent cache file.
+#[cfg(autotune_persistent_cache)]
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistentCacheFile<Entries> {
+    version: u32,
+    device_id: String,
+    entries: Entries,
+}
+
 /// Use to find and reuse the best kernel for some input
 #[derive(Debug)]
 pub(crate) struct TuneCache<K> {
@@ -54,6 +154,8 @@ pub(crate) struct TuneCache<K> {
     device_id: String,
     #[cfg(autotune_persistent_cache)]
     name: String,
+    #[cfg(autotune_persistent_cache)]
+    cache_dir: PathBuf,
 }
 
 /// Result of the cache try
@@ -71,19 +173,7 @@ impl<K: AutotuneKey> TuneCache<K> {
     ) -> Self {
         #[cfg(autotune_persistent_cache)]
         {
-            let mut cache = TuneCache {
-                in_memory_cache: HashMap::new(),
-                persistent_cache: HashMap::new(),
-                device_id: device_id.to_string(),
-                name: name.to_string(),
-            };
-            if let Err(e) = cache.load() {
-                log::warn!(
-                    "Unable to load autotune cache. Cache will be ignored ({}).",
-                    e
-                );
-            }
-            cache
+            Self::with_cache_dir(name, device_id, get_persistent_cache_dir())
         }
 
         #[cfg(not(autotune_persistent_cache))]
@@ -129,6 +219,11 @@ impl<K: AutotuneKey> TuneCache<K> {
                         .get(&key)
                         .expect("Both caches should be in sync");
                     if checksum != persistent_entry.checksum {
+                        log::warn!(
+                            "Autotune result for '{key}' is outdated since the operations changed, it will be tuned again."
+                        );
+                        self.in_memory_cache.remove(&key);
+                        self.persistent_cache.remove(&key);
                         return TuneCacheResult::Miss(autotune_operation_set);
                     }
                     *checksum_checked = true;
@@ -161,6 +256,23 @@ impl<K: AutotuneKey> TuneCache<K> {
 
 #[cfg(autotune_persistent_cache)]
 impl<K: AutotuneKey> TuneCache<K> {
+    pub(crate) fn with_cache_dir(name: &str, device_id: &str, cache_dir: PathBuf) -> Self {
+        let mut cache = TuneCache {
+            in_memory_cache: HashMap::new(),
+            persistent_cache: HashMap::new(),
+            device_id: device_id.to_string(),
+            name: name.to_string(),
+            cache_dir,
+        };
+        if let Err(e) = cache.load() {
+            log::warn!(
+                "Unable to load autotune cache. Cache will be ignored ({}).",
+                e
+            );
+        }
+        cache
+    }
+
     pub(crate) fn persistent_cache_insert(
         &mut self,
         key: K,
@@ -177,65 +289,106 @@ impl<K: AutotuneKey> TuneCache<K> {
     }
 
     /// Load the persistent cache data from disk
-    pub(crate) fn load(&mut self) -> Result<(), io::Error> {
-        let file_path = self.get_persistent_cache_file_path();
-        // note: reading file from memory is faster than using
-        // serde from_reader with a buffered reader
-        // see issue:
-        // https://github.com/serde-rs/json/issues/160
-        match fs::read_to_string(file_path) {
-            Ok(data) => {
-                let data: Vec<(K, PersistentCacheEntry)> = serde_json::from_str(&data)?;
-                for (key, value) in data.into_iter() {
-                    self.persistent_cache.insert(key, value);
-                }
+    pub(crate) fn load(&mut self) -> Result<(), TuneCacheError> {
+        match self.read(&self.get_persistent_cache_file_path()) {
+            Ok(entries) => {
+                self.insert_entries(entries);
                 Ok(())
             }
-            Err(e) => {
-                if e.kind() == std::io::ErrorKind::NotFound {
-                    Ok(())
-                } else {
-                    Err(e)
-                }
+            Err(TuneCacheError::Io(e)) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Import the results from a file created with [export](TuneCache::export), returning the
+    /// number of imported results.
+    ///
+    /// The results are also saved in the persistent cache.
+    pub(crate) fn import(&mut self, path: &Path) -> Result<usize, TuneCacheError> {
+        let entries = self.read(path)?;
+        let num_entries = entries.len();
+
+        self.insert_entries(entries);
+        self.save();
+
+        Ok(num_entries)
+    }
+
+    /// Export the results to a file that can be imported on another machine with the same device.
+    pub(crate) fn export(&self, path: &Path) -> Result<(), TuneCacheError> {
+        if let Some(parent_dir) = path.parent() {
+            if !parent_dir.exists() {
+                fs::create_dir_all(parent_dir)?;
             }
-        }?;
-        for (key, entry) in self.persistent_cache.iter() {
-            self.in_memory_cache.insert(
-                key.clone(),
-                InMemoryCacheEntry {
-                    checksum_checked: false,
-                    fastest_index: entry.fastest_index,
-                },
-            );
         }
+        let file = File::create(path)?;
+        let data = PersistentCacheFile {
+            version: PERSISTENT_CACHE_VERSION,
+            device_id: self.device_id.clone(),
+            entries: self.persistent_cache.iter().collect::<Vec<_>>(),
+        };
+        serde_json::to_writer_pretty(file, &data)?;
+
         Ok(())
     }
 
     /// Save the persistent cache on disk
     pub(crate) fn save(&self) {
         let file_path = self.get_persistent_cache_file_path();
-        if let Some(parent_dir) = file_path.parent() {
-            if !parent_dir.exists() {
-                fs::create_dir_all(parent_dir).unwrap_or_else(|_| {
-                    panic!(
-                    "Should be able to create directory '{}' for autotune persistent cache file",
-                    parent_dir.to_str().unwrap())
-                });
-            }
+
+        if let Err(e) = self.export(&file_path) {
+            log::warn!(
+                "Unable to save autotune cache to '{}' ({}).",
+                file_path.display(),
+                e
+            );
         }
-        let file = File::create(file_path.clone()).unwrap_or_else(|_| {
-            panic!(
-                "Should be able to open autotune persistent cache file '{}'",
-                file_path.to_str().unwrap()
-            )
-        });
-        let data = self.persistent_cache.iter().collect::<Vec<_>>();
-        serde_json::to_writer_pretty(file, &data)
-            .expect("Should be able to write to autotune persistent cache");
     }
 
     /// Return the file path for the persistent cache on disk
     pub fn get_persistent_cache_file_path(&self) -> PathBuf {
-        get_persistent_cache_file_path(&format!("{}/{}", self.name, self.device_id))
+        persistent_cache_file_path(
+            &self.cache_dir,
+            &format!("{}/{}", self.name, self.device_id),
+        )
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<(K, PersistentCacheEntry)>, TuneCacheError> {
+        // note: reading file from memory is faster than using
+        // serde from_reader with a buffered reader
+        // see issue:
+        // https://github.com/serde-rs/json/issues/160
+        let data = fs::read_to_string(path)?;
+        let file: PersistentCacheFile<Vec<(K, PersistentCacheEntry)>> =
+            serde_json::from_str(&data)?;
+
+        if file.version != PERSISTENT_CACHE_VERSION {
+            return Err(TuneCacheError::VersionMismatch {
+                expected: PERSISTENT_CACHE_VERSION,
+                found: file.version,
+            });
+        }
+
+        if file.device_id != self.device_id {
+            return Err(TuneCacheError::DeviceMismatch {
+                expected: self.device_id.clone(),
+                found: file.device_id,
+            });
+        }
+
+        Ok(file.entries)
+    }
+
+    fn insert_entries(&mut self, entries: Vec<(K, PersistentCacheEntry)>) {
+        for (key, entry) in entries.into_iter() {
+            self.in_memory_cache.insert(
+                key.clone(),
+                InMemoryCacheEntry {
+                    checksum_checked: false,
+                    fastest_index: entry.fastest_index,
+                },
+            );
+            self.persistent_cache.insert(key, entry);
+        }
     }
 }
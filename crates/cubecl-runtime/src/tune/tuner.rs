@@ -7,19 +7,26 @@ use core::time::Duration;
 use alloc::boxed::Box;
 use alloc::string::ToString;
 use alloc::vec::Vec;
-use cubecl_common::benchmark::{Benchmark, BenchmarkComputations, BenchmarkDurations};
+use cubecl_common::benchmark::{
+    Benchmark, BenchmarkComputations, BenchmarkDurations, TimingMethod,
+};
 
 use crate::channel::ComputeChannel;
 use crate::client::ComputeClient;
 use crate::server::ComputeServer;
-use crate::tune::{AutotuneOperation, AutotuneOperationSet, TuneBenchmark, TuneCache};
+use crate::tune::{AutotuneOperation, AutotuneOperationSet, TuneBenchmark, TuneCache, TuneOptions};
 
 use super::AutotuneKey;
+#[cfg(autotune_persistent_cache)]
+use super::TuneCacheError;
+#[cfg(autotune_persistent_cache)]
+use std::path::{Path, PathBuf};
 
 #[derive(Debug)]
 /// Executes autotune benchmarking and caching
 pub struct Tuner<K: AutotuneKey> {
     tune_cache: TuneCache<K>,
+    options: TuneOptions,
 }
 
 #[allow(clippy::new_without_default)]
@@ -28,9 +35,46 @@ impl<K: AutotuneKey> Tuner<K> {
     pub fn new(name: &str, device_id: &str) -> Self {
         Self {
             tune_cache: TuneCache::new(name, device_id),
+            options: TuneOptions::new(),
         }
     }
 
+    /// Returns a tuner with cache initialized from the persistent cache stored in the given
+    /// directory.
+    #[cfg(autotune_persistent_cache)]
+    pub fn with_cache_dir(name: &str, device_id: &str, cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            tune_cache: TuneCache::with_cache_dir(name, device_id, cache_dir.into()),
+            options: TuneOptions::new(),
+        }
+    }
+
+    /// Set the options of the benchmarks used to find the fastest operations.
+    pub fn set_options(&mut self, options: TuneOptions) {
+        assert!(
+            options.num_samples > 0,
+            "At least one sample is needed to benchmark an operation."
+        );
+        self.options = options;
+    }
+
+    /// Export the autotune results to a file, so that they can be [imported](Tuner::import) on
+    /// another machine with the same device.
+    #[cfg(autotune_persistent_cache)]
+    pub fn export(&self, path: &Path) -> Result<(), TuneCacheError> {
+        self.tune_cache.export(path)
+    }
+
+    /// Import the autotune results from a file created with [export](Tuner::export), returning
+    /// the number of imported results.
+    ///
+    /// The results are also saved to the persistent cache. Results that are outdated because the
+    /// operations changed are reported and tuned again when they are used.
+    #[cfg(autotune_persistent_cache)]
+    pub fn import(&mut self, path: &Path) -> Result<usize, TuneCacheError> {
+        self.tune_cache.import(path)
+    }
+
     /// Fetch the fastest autotune operation index for an autotune key.
     pub fn autotune_fastest(&self, key: &K) -> Option<usize> {
         self.tune_cache.find_fastest(key)
@@ -66,17 +110,31 @@ impl<K: AutotuneKey> Tuner<K> {
         let key = autotune_operation_set.key();
         let autotunables = autotune_operation_set.autotunables();
         let mut names = Vec::with_capacity(autotunables.len());
+        let mut results = Vec::with_capacity(autotunables.len());
+        let mut fastest = None;
+        let (timing_method, timestamps_enabled) = self.enable_timing(client);
+
+        for op in autotunables {
+            names.push(op.name().to_string());
+
+            let durations = self
+                .run_benchmark(op, client, timing_method, fastest)
+                .without_outliers(self.options.outlier_threshold);
+            let computed = BenchmarkComputations::new(&durations);
+
+            fastest = Some(match fastest {
+                Some(duration) if duration < computed.median => duration,
+                _ => computed.median,
+            });
+            results.push(computed);
+        }
 
-        let results: Vec<BenchmarkDurations> = autotunables
-            .into_iter()
-            .map(|op| {
-                names.push(op.name().to_string());
-                self.run_benchmark(op, client)
-            })
-            .collect();
+        if timing_method == TimingMethod::Device && !timestamps_enabled {
+            client.disable_timestamps();
+        }
 
         // Finds the fastest operation, stores it and returns it
-        let fastest_index = self.find_fastest(results);
+        let fastest_index = find_fastest(&results, self.options.tie_tolerance);
         let fastest_name = names.get(fastest_index).unwrap();
         log::info!("Fastest result {fastest_name}-{key}");
 
@@ -99,27 +157,89 @@ impl<K: AutotuneKey> Tuner<K> {
         &mut self,
         operation: Box<dyn AutotuneOperation<Out>>,
         client: &ComputeClient<S, C>,
+        timing_method: TimingMethod,
+        fastest: Option<Duration>,
     ) -> BenchmarkDurations
     where
         S: ComputeServer,
         C: ComputeChannel<S>,
     {
-        TuneBenchmark::new(operation, client.clone()).run()
+        let options = TuneOptions {
+            timing_method,
+            ..self.options
+        };
+
+        TuneBenchmark::new(operation, client.clone(), options, fastest).run()
     }
 
-    fn find_fastest(&self, results: Vec<BenchmarkDurations>) -> usize {
-        let mut smallest_duration = Duration::MAX;
-        let mut fastest_tunable = None;
+    /// Enable the timestamps when the operations are timed on the device, returning the timing
+    /// method to use and whether the timestamps were already enabled before autotuning.
+    fn enable_timing<S, C>(&self, client: &ComputeClient<S, C>) -> (TimingMethod, bool)
+    where
+        S: ComputeServer,
+        C: ComputeChannel<S>,
+    {
+        if self.options.timing_method == TimingMethod::System {
+            return (TimingMethod::System, false);
+        }
+
+        // The timestamps are left as they are when they were enabled by the user.
+        if client.timestamps_enabled() {
+            return (TimingMethod::Device, true);
+        }
 
-        for (i, result) in results.into_iter().enumerate() {
-            let computed = BenchmarkComputations::new(&result);
+        client.enable_timestamps();
 
-            if computed.median < smallest_duration {
-                smallest_duration = computed.median;
-                fastest_tunable = Some(i);
+        match client.sync_elapsed() {
+            Ok(_) => (TimingMethod::Device, false),
+            Err(err) => {
+                log::info!("{err}, falling back to the system time for autotuning");
+                client.disable_timestamps();
+                (TimingMethod::System, false)
             }
         }
+    }
+}
+
+/// Find the operation with the lowest median duration.
+///
+/// Operations within the tie tolerance of the fastest one can't be told apart from noise, so the
+/// first declared one is selected to keep the result stable between runs.
+fn find_fastest(results: &[BenchmarkComputations], tie_tolerance: f64) -> usize {
+    let smallest_duration = results
+        .iter()
+        .map(|computed| computed.median)
+        .min()
+        .expect("At least one kernel needed. ");
+    let max_duration = smallest_duration.as_secs_f64() * (1.0 + tie_tolerance);
+
+    results
+        .iter()
+        .position(|computed| computed.median.as_secs_f64() <= max_duration)
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn computations(median_millis: u64) -> BenchmarkComputations {
+        let durations = BenchmarkDurations::new(alloc::vec![Duration::from_millis(median_millis)]);
+        BenchmarkComputations::new(&durations)
+    }
+
+    #[test]
+    fn find_fastest_selects_lowest_median() {
+        let results = [computations(30), computations(10), computations(20)];
+
+        assert_eq!(find_fastest(&results, 0.05), 1);
+    }
+
+    #[test]
+    fn find_fastest_selects_first_declared_when_tied() {
+        let results = [computations(102), computations(100), computations(300)];
 
-        fastest_tunable.expect("At least one kernel needed. ")
+        assert_eq!(find_fastest(&results, 0.05), 0);
+        assert_eq!(find_fastest(&results, 0.0), 1);
     }
 }
@@ -1,11 +1,18 @@
 use std::sync::Arc;
 
-use cubecl_common::{reader::reader_from_concrete, sync_type::SyncType};
+use cubecl_common::{
+    benchmark::{TimestampsError, TimestampsResult},
+    reader::reader_from_concrete,
+    sync_type::SyncType,
+};
+use cubecl_runtime::profiler::{ProfileEventKind, ProfileTrace, Profiler};
 use cubecl_runtime::storage::ComputeStorage;
 use cubecl_runtime::{
-    memory_management::{simple::SimpleMemoryManagement, MemoryManagement},
-    server::{Binding, ComputeServer, Handle},
+    memory_management::{simple::SimpleMemoryManagement, MemoryManagement, MemoryUsage},
+    server::{Binding, ComputeError, ComputeServer, Handle},
+    staging::StagingBuffer,
     storage::{BytesResource, BytesStorage},
+    stream::{EventId, OrderedStreams, StreamId},
     ExecutionMode,
 };
 use derive_new::new;
@@ -17,6 +24,10 @@ use super::DummyKernel;
 #[derive(new, Debug)]
 pub struct DummyServer<MM = SimpleMemoryManagement<BytesStorage>> {
     memory_management: MM,
+    #[new(default)]
+    profiler: Profiler,
+    #[new(default)]
+    streams: OrderedStreams,
 }
 
 impl<MM> ComputeServer for DummyServer<MM>
@@ -29,10 +40,18 @@ where
     type MemoryManagement = MM;
     type FeatureSet = ();
 
-    fn read(&mut self, binding: Binding<Self>) -> cubecl_common::reader::Reader {
-        let bytes_handle = self.memory_management.get(binding.memory);
-        let bytes = self.memory_management.storage().get(&bytes_handle);
-        reader_from_concrete(bytes.read().to_vec())
+    fn try_read(
+        &mut self,
+        binding: Binding<Self>,
+    ) -> Result<cubecl_common::reader::Reader, ComputeError> {
+        let started = self.profiler.now();
+        let bytes = self.memory_management.try_get_resource(binding.memory)?;
+        let data = bytes.read().to_vec();
+
+        self.profiler
+            .register(started, ProfileEventKind::Read { size: data.len() });
+
+        Ok(reader_from_concrete(data))
     }
 
     fn get_resource(&mut self, binding: Binding<Self>) -> BytesResource {
@@ -40,8 +59,9 @@ where
         self.memory_management.storage().get(&handle)
     }
 
-    fn create(&mut self, data: &[u8]) -> Handle<Self> {
-        let handle = self.empty(data.len());
+    fn try_create(&mut self, data: &[u8]) -> Result<Handle<Self>, ComputeError> {
+        let started = self.profiler.now();
+        let handle = self.try_empty(data.len())?;
         let resource = self.get_resource(handle.clone().binding());
 
         let bytes = resource.write();
@@ -50,29 +70,105 @@ where
             bytes[i] = *val;
         }
 
-        handle
+        self.profiler
+            .register(started, ProfileEventKind::Create { size: data.len() });
+
+        Ok(handle)
     }
 
-    fn empty(&mut self, size: usize) -> Handle<Self> {
-        Handle::new(self.memory_management.reserve(size, &[]))
+    fn try_empty(&mut self, size: usize) -> Result<Handle<Self>, ComputeError> {
+        let memory = self.memory_management.try_reserve(size, &[])?;
+        Ok(Handle::new(memory, size))
     }
 
-    unsafe fn execute(
+    fn try_staging(&mut self, size: usize) -> Result<StagingBuffer, ComputeError> {
+        Ok(vec![0; size].into())
+    }
+
+    fn try_upload(
+        &mut self,
+        buffer: StagingBuffer,
+        stream: StreamId,
+    ) -> Result<Handle<Self>, ComputeError> {
+        self.streams.validate(stream)?;
+        self.try_create(&buffer)
+    }
+
+    unsafe fn try_execute(
         &mut self,
         kernel: Self::Kernel,
         _count: Self::DispatchOptions,
         bindings: Vec<Binding<Self>>,
         _mode: ExecutionMode,
-    ) {
+        stream: StreamId,
+    ) -> Result<(), ComputeError> {
+        self.streams.validate(stream)?;
+
+        let started = self.profiler.now();
         let mut resources = bindings
             .into_iter()
-            .map(|binding| self.get_resource(binding))
-            .collect::<Vec<_>>();
+            .map(|binding| self.memory_management.try_get_resource(binding.memory))
+            .collect::<Result<Vec<_>, _>>()?;
 
         kernel.compute(&mut resources);
+
+        self.profiler.register(
+            started,
+            ProfileEventKind::Execute {
+                name: "dummy".into(),
+                id: "dummy".into(),
+                cube_count: None,
+                binding_sizes: resources
+                    .iter()
+                    .map(|resource| resource.read().len())
+                    .collect(),
+            },
+        );
+
+        Ok(())
+    }
+
+    fn try_create_stream(&mut self) -> Result<StreamId, ComputeError> {
+        Ok(self.streams.create())
+    }
+
+    fn try_record_event(&mut self, stream: StreamId) -> Result<EventId, ComputeError> {
+        self.streams.record_event(stream)
+    }
+
+    fn try_wait_event(&mut self, stream: StreamId, event: EventId) -> Result<(), ComputeError> {
+        self.streams.wait_event(stream, event)
     }
 
     fn sync(&mut self, _: SyncType) {
         // Nothing to do with dummy backend.
     }
+
+    fn memory_usage(&mut self) -> MemoryUsage {
+        self.memory_management.memory_usage()
+    }
+
+    fn memory_cleanup(&mut self) {
+        self.memory_management.cleanup();
+    }
+
+    fn enable_timestamps(&mut self) {}
+
+    fn disable_timestamps(&mut self) {}
+
+    fn timestamps_enabled(&mut self) -> bool {
+        false
+    }
+
+    fn sync_elapsed(&mut self) -> TimestampsResult {
+        Err(TimestampsError::Unavailable)
+    }
+
+    fn start_profile(&mut self) {
+        self.profiler.start();
+    }
+
+    fn end_profile(&mut self) -> ProfileTrace {
+        self.profiler.end()
+    }
 }
@@ -4,12 +4,12 @@ use std::sync::Arc;
 
 use crate::dummy::autotune_execute;
 use crate::dummy::TEST_TUNER;
-use crate::dummy::{client, DummyDevice, DummyElementwiseAddition};
+use crate::dummy::{client, init_client, DummyDevice, DummyElementwiseAddition};
 
 #[cfg(autotune_persistent_cache)]
 use crate::dummy::{TUNER_DEVICE_ID, TUNER_PREFIX};
 
-use cubecl_runtime::ComputeRuntime;
+use cubecl_runtime::{server::ComputeError, stream::StreamId, ComputeRuntime};
 
 #[allow(unused)]
 use serial_test::serial;
@@ -37,6 +37,44 @@ fn empty_allocates_memory() {
     assert_eq!(empty_resource.len(), 4);
 }
 
+#[test]
+fn empty_returns_an_error_when_out_of_memory() {
+    let client = client(&DummyDevice);
+
+    let result = client.try_empty(usize::MAX);
+
+    assert!(matches!(result, Err(ComputeError::OutOfMemory { .. })));
+
+    // The client is still usable after the error.
+    let resource = client.create(&[0, 1, 2]);
+    assert_eq!(client.read(resource.binding()), Vec::from([0, 1, 2]));
+}
+
+#[test]
+fn memory_usage_includes_live_handles() {
+    let client = client(&DummyDevice);
+
+    let handle = client.create(&[0; 64]);
+    let usage = client.memory_usage();
+
+    assert!(usage.bytes_in_use >= 64);
+    assert!(usage.bytes_reserved >= usage.bytes_in_use);
+    assert_eq!(usage.pools.len(), 1);
+
+    core::mem::drop(handle);
+}
+
+#[test]
+fn memory_cleanup_keeps_live_handles() {
+    let client = client(&DummyDevice);
+
+    let handle = client.create(&[0, 1, 2]);
+    client.empty(8);
+    client.memory_cleanup();
+
+    assert_eq!(client.read(handle.binding()), Vec::from([0, 1, 2]));
+}
+
 #[test]
 fn execute_elementwise_addition() {
     let client = client(&DummyDevice);
@@ -55,6 +93,82 @@ fn execute_elementwise_addition() {
     assert_eq!(obtained_resource, Vec::from([4, 5, 6]))
 }
 
+#[test]
+fn execute_elementwise_addition_on_stream() {
+    let client = init_client();
+    let stream = client.create_stream();
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+
+    client.execute_on_stream(
+        Arc::new(DummyElementwiseAddition),
+        (),
+        vec![lhs.binding(), rhs.binding(), out.clone().binding()],
+        stream,
+    );
+    let event = client.record_event(stream);
+    client.wait_event(StreamId::DEFAULT, event);
+
+    assert_eq!(client.read(out.binding()), Vec::from([4, 5, 6]));
+}
+
+#[test]
+fn uploaded_staging_buffer_is_the_same_when_read() {
+    let client = init_client();
+    let stream = client.create_stream();
+    let mut buffer = client.staging(3);
+    buffer.copy_from_slice(&[0, 1, 2]);
+
+    let (handle, event) = client.upload(buffer, stream);
+    client.wait_event(StreamId::DEFAULT, event);
+
+    assert_eq!(client.read(handle.binding()), Vec::from([0, 1, 2]));
+}
+
+#[test]
+fn execute_on_unknown_stream_returns_an_error() {
+    let client = init_client();
+    let out = client.empty(3);
+
+    let result = client.try_execute_on_stream(
+        Arc::new(DummyElementwiseAddition),
+        (),
+        vec![out.clone().binding(), out.clone().binding(), out.binding()],
+        StreamId { index: 1 },
+    );
+
+    assert!(matches!(result, Err(ComputeError::InvalidStream(_))));
+}
+
+#[test]
+fn profile_records_transfers_and_executions() {
+    // A dedicated client, so that the other tests aren't profiled.
+    let client = init_client();
+    let lhs = client.create(&[0, 1, 2]);
+
+    client.start_profile();
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+    client.execute(
+        Arc::new(DummyElementwiseAddition),
+        (),
+        vec![lhs.binding(), rhs.binding(), out.clone().binding()],
+    );
+    client.read(out.binding());
+    let trace = client.end_profile();
+
+    let categories = trace
+        .events
+        .iter()
+        .map(|event| event.kind.category())
+        .collect::<Vec<_>>();
+    assert_eq!(categories, ["create", "execute", "read"]);
+    assert!(trace
+        .to_chrome_trace()
+        .contains("\"binding_sizes\":[3,3,3]"));
+}
+
 #[test]
 #[serial]
 #[cfg(feature = "std")]
@@ -298,3 +412,108 @@ fn autotune_cache_different_checksums_return_a_cache_miss() {
     // so CacheTestSlowOn3 (but faster on 4) should be used, returning rhs
     assert_eq!(obtained_resource, Vec::from([5, 6, 7, 8]));
 }
+
+#[test]
+#[serial]
+#[cfg(autotune_persistent_cache)]
+fn autotune_cache_exported_results_are_reused_after_import() {
+    TEST_TUNER.clear();
+
+    let device_id = TUNER_DEVICE_ID.to_string();
+    let export_path = std::env::temp_dir()
+        .join("cubecl-tests")
+        .join("exported-autotune-cache.json");
+    let cache_path = cubecl_runtime::tune::get_persistent_cache_file_path(&format!(
+        "{}/{}",
+        TUNER_PREFIX, TUNER_DEVICE_ID
+    ));
+    let _ = std::fs::remove_file(&cache_path);
+
+    let runtime = Runtime::new();
+    let client = runtime.client(&DummyDevice, dummy::init_client);
+
+    // Tuned on shape [1,3], where CacheTestFastOn3 is the fastest.
+    let shapes = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+    let handles = vec![lhs.binding(), rhs.binding(), out.binding()];
+    let cache_test_autotune_kernel =
+        dummy::CacheTestAutotuneOperationSet::new(client.clone(), shapes, handles);
+    autotune_execute(&client, Box::new(cache_test_autotune_kernel));
+
+    TEST_TUNER.export(&device_id, &export_path).unwrap();
+
+    // Start from scratch, like on another machine.
+    TEST_TUNER.clear();
+    let _ = std::fs::remove_file(&cache_path);
+    let num_imported = TEST_TUNER.import(&device_id, &export_path).unwrap();
+    assert_eq!(num_imported, 1);
+    assert!(cache_path.exists(), "Imported results should be persisted");
+
+    // Shape [1,4] has the same key, so the imported result is used.
+    let shapes = vec![vec![1, 4], vec![1, 4], vec![1, 4]];
+    let lhs = client.create(&[0, 1, 2, 3]);
+    let rhs = client.create(&[5, 6, 7, 8]);
+    let out = client.empty(4);
+    let handles = vec![lhs.binding(), rhs.binding(), out.clone().binding()];
+    let cache_test_autotune_kernel =
+        dummy::CacheTestAutotuneOperationSet::new(client.clone(), shapes, handles);
+    autotune_execute(&client, Box::new(cache_test_autotune_kernel));
+
+    assert_eq!(client.read(out.binding()), Vec::from([0, 1, 2, 3]));
+}
+
+#[test]
+#[serial]
+#[cfg(autotune_persistent_cache)]
+fn autotune_cache_import_rejects_results_of_other_devices() {
+    use cubecl_runtime::tune::{TuneCacheError, Tuner};
+
+    let cache_dir = std::env::temp_dir().join("cubecl-tests").join("autotune");
+    let export_path = cache_dir.join("other-device-autotune-cache.json");
+
+    let tuner = Tuner::<String>::with_cache_dir("import-test", "device-a", &cache_dir);
+    tuner.export(&export_path).unwrap();
+
+    let mut tuner = Tuner::<String>::with_cache_dir("import-test", "device-b", &cache_dir);
+    let result = tuner.import(&export_path);
+
+    assert!(matches!(
+        result,
+        Err(TuneCacheError::DeviceMismatch { expected, found })
+            if expected == "device-b" && found == "device-a"
+    ));
+}
+
+#[test]
+#[serial]
+#[cfg(autotune_persistent_cache)]
+fn autotune_cache_is_stored_in_the_configured_directory() {
+    use cubecl_runtime::tune::LocalTuner;
+
+    static TUNER: LocalTuner<String, String> =
+        LocalTuner::with_cache_dir("cache-dir-test", env!("CARGO_TARGET_TMPDIR"));
+
+    let file_path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!(
+        "cache-dir-test/{}-autotune-cache.json",
+        TUNER_DEVICE_ID
+    ));
+    let _ = std::fs::remove_file(&file_path);
+
+    let client = client(&DummyDevice);
+    let shapes = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+    let handles = vec![lhs.binding(), rhs.binding(), out.binding()];
+    let cache_test_autotune_kernel =
+        dummy::CacheTestAutotuneOperationSet::new(client.clone(), shapes, handles);
+    TUNER.execute(
+        &TUNER_DEVICE_ID.to_string(),
+        &client,
+        Box::new(cache_test_autotune_kernel),
+    );
+
+    assert!(file_path.exists(), "Cache file should exist");
+}
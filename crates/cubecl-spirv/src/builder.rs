@@ -0,0 +1,549 @@
+use crate::module::{Instruction, Operand, SpirvModule, Word};
+use cubecl_core::ir::CubeDim;
+use hashbrown::HashMap;
+use spirv::{Capability, Op, StorageClass};
+
+/// A SPIR-V type, deduplicated by the [module builder](ModuleBuilder).
+///
+/// The types are referenced by the id of their components, which are always declared before them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Type {
+    Void,
+    Bool,
+    Int {
+        width: u32,
+        signed: bool,
+    },
+    Float {
+        width: u32,
+    },
+    Vector {
+        component: Word,
+        count: u32,
+    },
+    /// An array with a length known at compile time, the length being the id of a constant.
+    Array {
+        element: Word,
+        length: Word,
+    },
+    /// An array of a storage buffer, decorated with its stride.
+    RuntimeArray {
+        element: Word,
+        stride: u32,
+    },
+    /// The structure wrapping the array of a storage buffer.
+    Block {
+        member: Word,
+    },
+    Pointer {
+        class: StorageClass,
+        pointee: Word,
+    },
+    Function {
+        output: Word,
+        inputs: Vec<Word>,
+    },
+    CooperativeMatrix {
+        component: Word,
+        scope: Word,
+        rows: Word,
+        columns: Word,
+        usage: Word,
+    },
+}
+
+/// A constant, deduplicated by the [module builder](ModuleBuilder).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Constant {
+    Bool(bool),
+    /// The literal words of a number, the lowest order word first.
+    Number {
+        ty: Word,
+        words: Vec<u32>,
+    },
+    Null {
+        ty: Word,
+    },
+    /// A vector made of other constants.
+    Composite {
+        ty: Word,
+        constituents: Vec<Word>,
+    },
+}
+
+/// The instructions of the function being compiled.
+#[derive(Debug, Clone, Default)]
+struct FunctionState {
+    /// The declaration of the function and its parameters.
+    header: Vec<Instruction>,
+    /// The function variables, which must be declared at the start of the first block.
+    variables: Vec<Instruction>,
+    /// The blocks of the function, each one starting with a label.
+    blocks: Vec<Instruction>,
+    /// Whether the current block ended with a branch or a return.
+    terminated: bool,
+}
+
+/// Assembles a SPIR-V module following its logical layout.
+///
+/// The instructions are written into the section where they belong, so that types, constants and
+/// variables can be declared while compiling the body of a function.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ModuleBuilder {
+    bound: Word,
+    capabilities: Vec<Capability>,
+    extensions: Vec<&'static str>,
+    glsl: Option<Word>,
+    entry_points: Vec<Instruction>,
+    execution_modes: Vec<Instruction>,
+    names: Vec<Instruction>,
+    annotations: Vec<Instruction>,
+    /// The types, constants and global variables.
+    globals: Vec<Instruction>,
+    types: HashMap<Type, Word>,
+    constants: HashMap<Constant, Word>,
+    functions: Vec<Instruction>,
+    function: FunctionState,
+}
+
+impl ModuleBuilder {
+    /// Allocate a new id.
+    pub fn id(&mut self) -> Word {
+        self.bound += 1;
+        self.bound
+    }
+
+    pub fn capability(&mut self, capability: Capability) {
+        if !self.capabilities.contains(&capability) {
+            self.capabilities.push(capability);
+        }
+    }
+
+    pub fn extension(&mut self, extension: &'static str) {
+        if !self.extensions.contains(&extension) {
+            self.extensions.push(extension);
+        }
+    }
+
+    /// The id of the imported `GLSL.std.450` extended instruction set.
+    pub fn glsl(&mut self) -> Word {
+        match self.glsl {
+            Some(id) => id,
+            None => {
+                let id = self.id();
+                self.glsl = Some(id);
+                id
+            }
+        }
+    }
+
+    pub fn name(&mut self, target: Word, name: &str) {
+        self.names.push(Instruction::new(
+            Op::Name,
+            vec![Operand::Id(target), Operand::String(name.to_string())],
+        ));
+    }
+
+    pub fn decorate(&mut self, target: Word, decoration: spirv::Decoration, literals: &[u32]) {
+        let mut operands = vec![
+            Operand::Id(target),
+            Operand::Enumerant(decoration_name(decoration), decoration as u32),
+        ];
+        operands.extend(literals.iter().map(|literal| Operand::Literal(*literal)));
+
+        self.annotations
+            .push(Instruction::new(Op::Decorate, operands));
+    }
+
+    pub fn entry_point(
+        &mut self,
+        function: Word,
+        name: &str,
+        interface: Vec<Word>,
+        cube_dim: CubeDim,
+    ) {
+        let mut operands = vec![
+            Operand::Enumerant("GLCompute", spirv::ExecutionModel::GLCompute as u32),
+            Operand::Id(function),
+            Operand::String(name.to_string()),
+        ];
+        operands.extend(interface.into_iter().map(Operand::Id));
+        self.entry_points
+            .push(Instruction::new(Op::EntryPoint, operands));
+
+        self.execution_modes.push(Instruction::new(
+            Op::ExecutionMode,
+            vec![
+                Operand::Id(function),
+                Operand::Enumerant("LocalSize", spirv::ExecutionMode::LocalSize as u32),
+                Operand::Literal(cube_dim.x),
+                Operand::Literal(cube_dim.y),
+                Operand::Literal(cube_dim.z),
+            ],
+        ));
+    }
+
+    /// Get the id of a type, declaring it the first time it is used.
+    pub fn ty(&mut self, ty: Type) -> Word {
+        if let Some(id) = self.types.get(&ty) {
+            return *id;
+        }
+
+        let id = self.id();
+        let (op, operands) = match &ty {
+            Type::Void => (Op::TypeVoid, vec![]),
+            Type::Bool => (Op::TypeBool, vec![]),
+            Type::Int { width, signed } => (
+                Op::TypeInt,
+                vec![Operand::Literal(*width), Operand::Literal(*signed as u32)],
+            ),
+            Type::Float { width } => (Op::TypeFloat, vec![Operand::Literal(*width)]),
+            Type::Vector { component, count } => (
+                Op::TypeVector,
+                vec![Operand::Id(*component), Operand::Literal(*count)],
+            ),
+            Type::Array { element, length } => (
+                Op::TypeArray,
+                vec![Operand::Id(*element), Operand::Id(*length)],
+            ),
+            Type::RuntimeArray { element, stride } => {
+                self.decorate(id, spirv::Decoration::ArrayStride, &[*stride]);
+                (Op::TypeRuntimeArray, vec![Operand::Id(*element)])
+            }
+            Type::Block { member } => {
+                self.decorate(id, spirv::Decoration::Block, &[]);
+                self.annotations.push(Instruction::new(
+                    Op::MemberDecorate,
+                    vec![
+                        Operand::Id(id),
+                        Operand::Literal(0),
+                        Operand::Enumerant("Offset", spirv::Decoration::Offset as u32),
+                        Operand::Literal(0),
+                    ],
+                ));
+                (Op::TypeStruct, vec![Operand::Id(*member)])
+            }
+            Type::Pointer { class, pointee } => (
+                Op::TypePointer,
+                vec![storage_class(*class), Operand::Id(*pointee)],
+            ),
+            Type::Function { output, inputs } => (
+                Op::TypeFunction,
+                [*output]
+                    .iter()
+                    .chain(inputs.iter())
+                    .map(|id| Operand::Id(*id))
+                    .collect(),
+            ),
+            Type::CooperativeMatrix {
+                component,
+                scope,
+                rows,
+                columns,
+                usage,
+            } => (
+                Op::TypeCooperativeMatrixKHR,
+                vec![
+                    Operand::Id(*component),
+                    Operand::Id(*scope),
+                    Operand::Id(*rows),
+                    Operand::Id(*columns),
+                    Operand::Id(*usage),
+                ],
+            ),
+        };
+
+        self.globals.push(Instruction {
+            op,
+            result_type: None,
+            result_id: Some(id),
+            operands,
+        });
+        self.types.insert(ty, id);
+
+        id
+    }
+
+    /// Get the id of a constant, declaring it the first time it is used.
+    pub fn constant(&mut self, constant: Constant) -> Word {
+        if let Some(id) = self.constants.get(&constant) {
+            return *id;
+        }
+
+        let id = self.id();
+        let instruction = match &constant {
+            Constant::Bool(value) => {
+                let ty = self.ty(Type::Bool);
+                let op = match value {
+                    true => Op::ConstantTrue,
+                    false => Op::ConstantFalse,
+                };
+                Instruction {
+                    op,
+                    result_type: Some(ty),
+                    result_id: Some(id),
+                    operands: vec![],
+                }
+            }
+            Constant::Number { ty, words } => Instruction {
+                op: Op::Constant,
+                result_type: Some(*ty),
+                result_id: Some(id),
+                operands: words.iter().map(|word| Operand::Literal(*word)).collect(),
+            },
+            Constant::Null { ty } => Instruction {
+                op: Op::ConstantNull,
+                result_type: Some(*ty),
+                result_id: Some(id),
+                operands: vec![],
+            },
+            Constant::Composite { ty, constituents } => Instruction {
+                op: Op::ConstantComposite,
+                result_type: Some(*ty),
+                result_id: Some(id),
+                operands: constituents.iter().map(|id| Operand::Id(*id)).collect(),
+            },
+        };
+
+        self.globals.push(instruction);
+        self.constants.insert(constant, id);
+
+        id
+    }
+
+    /// Declare a global variable.
+    pub fn global_variable(&mut self, ty: Word, class: StorageClass) -> Word {
+        let id = self.id();
+        self.globals.push(Instruction {
+            op: Op::Variable,
+            result_type: Some(ty),
+            result_id: Some(id),
+            operands: vec![storage_class(class)],
+        });
+        id
+    }
+
+    /// Declare a variable of the current function, with an optional initializer.
+    pub fn function_variable(&mut self, ty: Word, initializer: Option<Word>) -> Word {
+        let id = self.id();
+        let mut operands = vec![storage_class(StorageClass::Function)];
+        operands.extend(initializer.map(Operand::Id));
+
+        self.function.variables.push(Instruction {
+            op: Op::Variable,
+            result_type: Some(ty),
+            result_id: Some(id),
+            operands,
+        });
+        id
+    }
+
+    /// Start a new function, with the given result and parameter types.
+    ///
+    /// Returns the id of the function and the ids of its parameters.
+    pub fn begin_function(&mut self, output: Word, inputs: &[Word]) -> (Word, Vec<Word>) {
+        let ty = self.ty(Type::Function {
+            output,
+            inputs: inputs.to_vec(),
+        });
+        let id = self.id();
+
+        self.function = FunctionState::default();
+        self.function.header.push(Instruction {
+            op: Op::Function,
+            result_type: Some(output),
+            result_id: Some(id),
+            operands: vec![
+                Operand::Enumerant("None", spirv::FunctionControl::NONE.bits()),
+                Operand::Id(ty),
+            ],
+        });
+
+        let params = inputs
+            .iter()
+            .map(|input| {
+                let param = self.id();
+                self.function.header.push(Instruction {
+                    op: Op::FunctionParameter,
+                    result_type: Some(*input),
+                    result_id: Some(param),
+                    operands: vec![],
+                });
+                param
+            })
+            .collect();
+
+        let label = self.id();
+        self.label(label);
+
+        (id, params)
+    }
+
+    /// End the current function, returning from it if the last block isn't terminated.
+    pub fn end_function(&mut self) {
+        if !self.function.terminated {
+            self.terminate(Op::Return, vec![]);
+        }
+
+        let function = core::mem::take(&mut self.function);
+        let mut blocks = function.blocks.into_iter();
+
+        self.functions.extend(function.header);
+        // The first block starts with its label, followed by the variables.
+        self.functions.extend(blocks.next());
+        self.functions.extend(function.variables);
+        self.functions.extend(blocks);
+        self.functions
+            .push(Instruction::new(Op::FunctionEnd, vec![]));
+    }
+
+    /// Start a new block with the given label.
+    pub fn label(&mut self, id: Word) {
+        self.function.blocks.push(Instruction {
+            op: Op::Label,
+            result_type: None,
+            result_id: Some(id),
+            operands: vec![],
+        });
+        self.function.terminated = false;
+    }
+
+    /// Whether the current block ended with a branch or a return.
+    pub fn is_terminated(&self) -> bool {
+        self.function.terminated
+    }
+
+    /// Add an instruction with a result to the current block.
+    pub fn emit(&mut self, op: Op, ty: Word, operands: Vec<Operand>) -> Word {
+        let id = self.id();
+        self.push(Instruction {
+            op,
+            result_type: Some(ty),
+            result_id: Some(id),
+            operands,
+        });
+        id
+    }
+
+    /// Add an instruction without result to the current block.
+    pub fn emit_void(&mut self, op: Op, operands: Vec<Operand>) {
+        self.push(Instruction::new(op, operands));
+    }
+
+    /// Add the instruction ending the current block.
+    pub fn terminate(&mut self, op: Op, operands: Vec<Operand>) {
+        self.push(Instruction::new(op, operands));
+        self.function.terminated = true;
+    }
+
+    /// Add a branch ending the current block, unless it is already terminated.
+    pub fn branch(&mut self, target: Word) {
+        if !self.function.terminated {
+            self.terminate(Op::Branch, vec![Operand::Id(target)]);
+        }
+    }
+
+    fn push(&mut self, instruction: Instruction) {
+        // The instructions following a break or a return are never executed, but they still need
+        // a block.
+        if self.function.terminated {
+            let label = self.id();
+            self.label(label);
+        }
+
+        self.function.blocks.push(instruction);
+    }
+
+    /// Assemble the module.
+    pub fn build(mut self, cube_dim: CubeDim, shared_memory_size: usize) -> SpirvModule {
+        let mut instructions = Vec::new();
+
+        instructions.extend(self.capabilities.iter().map(|capability| {
+            Instruction::new(
+                Op::Capability,
+                vec![Operand::Enumerant(
+                    capability_name(*capability),
+                    *capability as u32,
+                )],
+            )
+        }));
+        instructions.extend(self.extensions.iter().map(|extension| {
+            Instruction::new(Op::Extension, vec![Operand::String(extension.to_string())])
+        }));
+        if let Some(id) = self.glsl {
+            instructions.push(Instruction {
+                op: Op::ExtInstImport,
+                result_type: None,
+                result_id: Some(id),
+                operands: vec![Operand::String("GLSL.std.450".to_string())],
+            });
+        }
+        instructions.push(Instruction::new(
+            Op::MemoryModel,
+            vec![
+                Operand::Enumerant("Logical", spirv::AddressingModel::Logical as u32),
+                Operand::Enumerant("GLSL450", spirv::MemoryModel::GLSL450 as u32),
+            ],
+        ));
+        instructions.append(&mut self.entry_points);
+        instructions.append(&mut self.execution_modes);
+        instructions.append(&mut self.names);
+        instructions.append(&mut self.annotations);
+        instructions.append(&mut self.globals);
+        instructions.append(&mut self.functions);
+
+        SpirvModule {
+            instructions,
+            bound: self.bound + 1,
+            cube_dim,
+            shared_memory_size,
+        }
+    }
+}
+
+/// A storage class operand.
+pub(crate) fn storage_class(class: StorageClass) -> Operand {
+    let name = match class {
+        StorageClass::Function => "Function",
+        StorageClass::Input => "Input",
+        StorageClass::StorageBuffer => "StorageBuffer",
+        StorageClass::Workgroup => "Workgroup",
+        _ => "StorageClass",
+    };
+
+    Operand::Enumerant(name, class as u32)
+}
+
+fn decoration_name(decoration: spirv::Decoration) -> &'static str {
+    match decoration {
+        spirv::Decoration::ArrayStride => "ArrayStride",
+        spirv::Decoration::Block => "Block",
+        spirv::Decoration::BuiltIn => "BuiltIn",
+        spirv::Decoration::Binding => "Binding",
+        spirv::Decoration::DescriptorSet => "DescriptorSet",
+        spirv::Decoration::NonWritable => "NonWritable",
+        _ => "Decoration",
+    }
+}
+
+fn capability_name(capability: Capability) -> &'static str {
+    match capability {
+        Capability::Shader => "Shader",
+        Capability::Int8 => "Int8",
+        Capability::Int16 => "Int16",
+        Capability::Int64 => "Int64",
+        Capability::Float16 => "Float16",
+        Capability::Float64 => "Float64",
+        Capability::Int64Atomics => "Int64Atomics",
+        Capability::StorageBuffer8BitAccess => "StorageBuffer8BitAccess",
+        Capability::StorageBuffer16BitAccess => "StorageBuffer16BitAccess",
+        Capability::GroupNonUniform => "GroupNonUniform",
+        Capability::GroupNonUniformVote => "GroupNonUniformVote",
+        Capability::GroupNonUniformArithmetic => "GroupNonUniformArithmetic",
+        Capability::GroupNonUniformBallot => "GroupNonUniformBallot",
+        Capability::GroupNonUniformShuffle => "GroupNonUniformShuffle",
+        Capability::GroupNonUniformShuffleRelative => "GroupNonUniformShuffleRelative",
+        Capability::CooperativeMatrixKHR => "CooperativeMatrixKHR",
+        _ => "Capability",
+    }
+}
@@ -0,0 +1,1113 @@
+use super::variable::{Array, Length, LocalKey, Var};
+use crate::builder::{Constant, ModuleBuilder, Type};
+use crate::module::{Operand, SpirvModule, Word};
+use cubecl_core::ir::{self as cube, Item};
+use cubecl_runtime::{server::ComputeError, ExecutionMode};
+use hashbrown::HashMap;
+use spirv::{BuiltIn, Capability, Op, StorageClass};
+
+/// SPIR-V Compiler.
+///
+/// Lowers the kernel definition straight to a SPIR-V module instead of going through WGSL, so
+/// that cooperative matrices and subgroup operations can be used on Vulkan.
+#[derive(Clone, Default)]
+pub struct SpirvCompiler {
+    pub(super) builder: ModuleBuilder,
+    pub(super) cube_dim: cube::CubeDim,
+    pub(super) num_inputs: usize,
+    /// The global arrays, the inputs followed by the outputs.
+    pub(super) globals: Vec<Array>,
+    pub(super) named: HashMap<String, Array>,
+    pub(super) shared_memories: HashMap<u16, Array>,
+    pub(super) shared_memory_size: usize,
+    /// The variables of the function being compiled.
+    pub(super) locals: HashMap<LocalKey, Var>,
+    /// The input variables of the builtins used by the kernel.
+    pub(super) builtins: HashMap<BuiltIn, Word>,
+    /// The functions compiled so far, indexed by their id.
+    pub(super) functions: Vec<FunctionInfo>,
+    /// The output of the function being compiled, which is returned by its return statements.
+    pub(super) output: Option<cube::Variable>,
+    /// The merge blocks of the loops enclosing the current block, where a break branches to.
+    pub(super) loops: Vec<Word>,
+    /// The first error encountered during compilation.
+    ///
+    /// The compilation continues with placeholders after an error, but the resulting module is
+    /// discarded.
+    pub(super) error: Option<ComputeError>,
+}
+
+/// The signature of a compiled function.
+#[derive(Debug, Clone)]
+pub(super) struct FunctionInfo {
+    pub id: Word,
+    pub inputs: Vec<cube::Item>,
+    pub output: Option<cube::Item>,
+}
+
+impl core::fmt::Debug for SpirvCompiler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SpirvCompiler")
+    }
+}
+
+impl cubecl_core::Compiler for SpirvCompiler {
+    type Representation = SpirvModule;
+
+    fn compile(kernel: cube::KernelDefinition, mode: ExecutionMode) -> Self::Representation {
+        Self::try_compile(kernel, mode).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    fn try_compile(
+        kernel: cube::KernelDefinition,
+        _mode: ExecutionMode,
+    ) -> Result<Self::Representation, ComputeError> {
+        let mut compiler = Self::default();
+        let module = compiler.compile_kernel(kernel);
+
+        match compiler.error {
+            Some(err) => Err(err),
+            None => Ok(module),
+        }
+    }
+
+    fn elem_size(elem: cube::Elem) -> usize {
+        match elem {
+            // Booleans are stored as 32 bits integers, since they have no size in storage buffers.
+            cube::Elem::Bool => core::mem::size_of::<u32>(),
+            cube::Elem::Float(cube::FloatKind::BF16) => {
+                panic!("bf16 isn't supported by the SPIR-V compiler")
+            }
+            _ => elem.size(),
+        }
+    }
+
+    fn max_shared_memory_size() -> usize {
+        // The minimum guaranteed by Vulkan.
+        16384
+    }
+
+    fn optimizations() -> cube::Optimizations {
+        cube::Optimizations::all()
+    }
+}
+
+impl SpirvCompiler {
+    fn compile_kernel(&mut self, mut value: cube::KernelDefinition) -> SpirvModule {
+        self.cube_dim = value.cube_dim;
+        self.num_inputs = value.inputs.len();
+        self.builder.capability(Capability::Shader);
+
+        let mut binding = 0;
+        for (i, input) in value.inputs.into_iter().enumerate() {
+            let array = self.compile_binding(input, binding, &format!("input_{i}"));
+            self.globals.push(array);
+            binding += 1;
+        }
+        for (i, output) in value.outputs.into_iter().enumerate() {
+            let array = self.compile_binding(output, binding, &format!("output_{i}"));
+            self.globals.push(array);
+            binding += 1;
+        }
+        for (name, named) in value.named.into_iter() {
+            let array = self.compile_binding(named, binding, &name);
+            self.named.insert(name, array);
+            binding += 1;
+        }
+
+        // Functions only call the functions registered before them, so they are compiled in order.
+        for function in value.functions.drain(..) {
+            self.compile_function(function);
+        }
+
+        let void = self.builder.ty(Type::Void);
+        let (main, _) = self.builder.begin_function(void, &[]);
+        self.locals.clear();
+        self.output = None;
+        self.compile_scope(&mut value.body);
+        self.builder.end_function();
+
+        self.builder.name(main, "main");
+        let mut interface = self.builtins.values().copied().collect::<Vec<_>>();
+        interface.sort();
+        self.builder
+            .entry_point(main, "main", interface, value.cube_dim);
+
+        core::mem::take(&mut self.builder).build(value.cube_dim, self.shared_memory_size)
+    }
+
+    fn compile_function(&mut self, mut value: cube::Function) {
+        let inputs = value
+            .inputs
+            .iter()
+            .map(|input| value_item(input.item()))
+            .collect::<Vec<_>>();
+        let output = value.output.map(|output| value_item(output.item()));
+
+        let input_types = inputs
+            .iter()
+            .map(|item| self.item_ty(*item))
+            .collect::<Vec<_>>();
+        let output_type = match output {
+            Some(item) => self.item_ty(item),
+            None => self.builder.ty(Type::Void),
+        };
+
+        let (id, params) = self.builder.begin_function(output_type, &input_types);
+        self.builder
+            .name(id, &format!("{}_{}", value.name, value.id));
+        self.locals.clear();
+        self.output = value.output;
+
+        // The arguments are stored in the inputs, which are regular local variables.
+        for (input, param) in value.inputs.iter().zip(params) {
+            self.write(*input, param);
+        }
+
+        self.compile_scope(&mut value.scope);
+
+        if !self.builder.is_terminated() {
+            self.compile_return();
+        }
+        self.builder.end_function();
+
+        self.functions.push(FunctionInfo { id, inputs, output });
+    }
+
+    fn compile_binding(&mut self, binding: cube::Binding, index: u32, name: &str) -> Array {
+        let stored = stored_item(binding.item);
+        let width = elem_width(stored.elem);
+        match width {
+            8 => {
+                self.builder.capability(Capability::StorageBuffer8BitAccess);
+                self.builder.extension("SPV_KHR_8bit_storage");
+            }
+            16 => {
+                self.builder
+                    .capability(Capability::StorageBuffer16BitAccess);
+                self.builder.extension("SPV_KHR_16bit_storage");
+            }
+            _ => {}
+        }
+
+        let element = self.item_ty(stored);
+        // Vectors of 3 elements are aligned like vectors of 4 elements.
+        let stride = width / 8 * (stored.vectorization as u32).next_power_of_two();
+        let array = self.builder.ty(Type::RuntimeArray { element, stride });
+        let block = self.builder.ty(Type::Block { member: array });
+        let pointer = self.builder.ty(Type::Pointer {
+            class: StorageClass::StorageBuffer,
+            pointee: block,
+        });
+
+        let ptr = self
+            .builder
+            .global_variable(pointer, StorageClass::StorageBuffer);
+        self.builder
+            .decorate(ptr, spirv::Decoration::DescriptorSet, &[0]);
+        self.builder
+            .decorate(ptr, spirv::Decoration::Binding, &[index]);
+        self.builder.name(ptr, name);
+
+        Array {
+            ptr,
+            class: StorageClass::StorageBuffer,
+            item: binding.item,
+            stored,
+            block: true,
+            offset: None,
+            length: Length::Runtime,
+        }
+    }
+
+    pub(super) fn compile_scope(&mut self, scope: &mut cube::Scope) {
+        let processing = scope.process_with(&<Self as cubecl_core::Compiler>::optimizations());
+
+        for variable in processing.variables {
+            // Slices are views created by the slice operator and atomics are pointers created
+            // when indexing an array, they aren't declared.
+            if let cube::Variable::Slice { .. } = variable {
+                continue;
+            }
+            if variable.item().elem.is_atomic() {
+                continue;
+            }
+
+            self.local(variable);
+        }
+
+        for operation in processing.operations {
+            self.compile_operation(operation, scope);
+        }
+    }
+
+    fn compile_operation(&mut self, operation: cube::Operation, scope: &mut cube::Scope) {
+        match operation {
+            cube::Operation::Operator(op) => self.compile_operator(op),
+            cube::Operation::Procedure(proc) => self.compile_procedure(proc, scope),
+            cube::Operation::Metadata(op) => self.compile_metadata(op),
+            cube::Operation::Branch(branch) => self.compile_branch(branch),
+            cube::Operation::Synchronization(sync) => self.compile_synchronization(sync),
+            cube::Operation::Subcube(op) => self.compile_subcube(op),
+            cube::Operation::CoopMma(op) => self.compile_cmma(op),
+            cube::Operation::Call(call) => self.compile_call(call),
+        }
+    }
+
+    fn compile_procedure(&mut self, proc: cube::Procedure, scope: &mut cube::Scope) {
+        match proc {
+            cube::Procedure::ReadGlobalWithLayout(proc) => proc.expand(scope),
+            cube::Procedure::ReadGlobal(proc) => proc.expand(scope),
+            cube::Procedure::WriteGlobal(proc) => proc.expand(scope),
+            cube::Procedure::ConditionalAssign(proc) => proc.expand(scope),
+            cube::Procedure::CheckedIndex(proc) => proc.expand(scope),
+            cube::Procedure::CheckedIndexAssign(proc) => proc.expand(scope),
+            cube::Procedure::IndexOffsetGlobalWithLayout(proc) => proc.expand(scope),
+            cube::Procedure::EarlyReturn(proc) => proc.expand(scope),
+        }
+
+        self.compile_scope(scope);
+    }
+
+    fn compile_call(&mut self, call: cube::Call) {
+        let function = match self.functions.get(call.function as usize) {
+            Some(function) => function.clone(),
+            None => {
+                self.unsupported(format!("Function {} isn't defined", call.function));
+                return;
+            }
+        };
+
+        let mut operands = vec![Operand::Id(function.id)];
+        for (input, item) in call.inputs.into_iter().zip(function.inputs.iter()) {
+            let value = self.read_as(input, *item);
+            operands.push(Operand::Id(value));
+        }
+
+        let ty = match function.output {
+            Some(item) => self.item_ty(item),
+            None => self.builder.ty(Type::Void),
+        };
+        let result = self.builder.emit(Op::FunctionCall, ty, operands);
+
+        if let (Some(output), Some(item)) = (call.output, function.output) {
+            self.write_from(output, result, item);
+        }
+    }
+
+    fn compile_metadata(&mut self, metadata: cube::Metadata) {
+        let uint = uint();
+
+        match metadata {
+            cube::Metadata::Stride { dim, var, out } | cube::Metadata::Shape { dim, var, out } => {
+                let position = match var {
+                    cube::Variable::GlobalInputArray { id, .. } => id as u32,
+                    cube::Variable::GlobalOutputArray { id, .. } => {
+                        self.num_inputs as u32 + id as u32
+                    }
+                    _ => {
+                        self.unsupported(format!(
+                            "Only input and output have metadata, got {var:?}"
+                        ));
+                        return;
+                    }
+                };
+                let is_shape = matches!(metadata, cube::Metadata::Shape { .. });
+
+                // The info buffer starts with the rank, followed by the strides and the shape of
+                // each array.
+                let ty = self.item_ty(uint);
+                let rank = self.rank();
+                let dim = self.read_as(dim, uint);
+                let one = self.const_u32(1);
+                let rank_2 = self.emit(Op::IAdd, ty, &[rank, rank]);
+                let position = self.const_u32(position);
+                let start = self.emit(Op::IMul, ty, &[position, rank_2]);
+                let mut index = self.emit(Op::IAdd, ty, &[start, dim]);
+                if is_shape {
+                    index = self.emit(Op::IAdd, ty, &[index, rank]);
+                }
+                let index = self.emit(Op::IAdd, ty, &[index, one]);
+
+                let value = self.info(index);
+                self.write_from(out, value, uint);
+            }
+            cube::Metadata::Length { var, out } => match self.array(var) {
+                Some(array) => {
+                    let length = self.length(array);
+                    self.write_from(out, length, uint);
+                }
+                None => self.unsupported(format!("Can't get the length of {var:?}")),
+            },
+        }
+    }
+
+    fn compile_synchronization(&mut self, synchronization: cube::Synchronization) {
+        let (memory, semantics) = match synchronization {
+            cube::Synchronization::SyncUnits => (
+                spirv::Scope::Workgroup,
+                spirv::MemorySemantics::ACQUIRE_RELEASE | spirv::MemorySemantics::WORKGROUP_MEMORY,
+            ),
+            cube::Synchronization::SyncStorage => (
+                spirv::Scope::Device,
+                spirv::MemorySemantics::ACQUIRE_RELEASE | spirv::MemorySemantics::UNIFORM_MEMORY,
+            ),
+        };
+
+        let execution = self.const_u32(spirv::Scope::Workgroup as u32);
+        let memory = self.const_u32(memory as u32);
+        let semantics = self.const_u32(semantics.bits());
+        self.builder.emit_void(
+            Op::ControlBarrier,
+            vec![
+                Operand::Id(execution),
+                Operand::Id(memory),
+                Operand::Id(semantics),
+            ],
+        );
+    }
+
+    /// The rank of the arrays, stored at the start of the info buffer.
+    fn rank(&mut self) -> Word {
+        let zero = self.const_u32(0);
+        self.info(zero)
+    }
+
+    fn info(&mut self, index: Word) -> Word {
+        match self.named.get("info").copied() {
+            Some(info) => {
+                let ptr = self.element(info, index);
+                let ty = self.item_ty(info.stored);
+                self.builder.emit(Op::Load, ty, vec![Operand::Id(ptr)])
+            }
+            None => {
+                self.unsupported("The kernel has no info binding for its metadata".to_string());
+                self.undefined(uint())
+            }
+        }
+    }
+
+    /// Read the value of a variable.
+    pub(super) fn read(&mut self, variable: cube::Variable) -> Word {
+        let uint = uint();
+
+        match variable {
+            cube::Variable::ConstantScalar(value) => self.constant(value, value.elem()),
+            cube::Variable::Local { .. } | cube::Variable::LocalScalar { .. } => {
+                match self.local(variable) {
+                    Var::Local { ptr, item } => {
+                        let ty = self.item_ty(item);
+                        self.builder.emit(Op::Load, ty, vec![Operand::Id(ptr)])
+                    }
+                    Var::Atomic { ptr, item } => {
+                        let item = value_item(item);
+                        let ty = self.item_ty(item);
+                        let (scope, semantics) = self.atomic_semantics();
+                        self.emit(Op::AtomicLoad, ty, &[ptr, scope, semantics])
+                    }
+                    _ => {
+                        self.unsupported(format!("Can't read {variable:?} as a value"));
+                        self.undefined(variable.item())
+                    }
+                }
+            }
+            cube::Variable::GlobalScalar { id, elem } => {
+                match self.named.get(&format!("scalars_{elem}")).copied() {
+                    Some(scalars) => {
+                        let index = self.const_u32(id as u32);
+                        let ptr = self.element(scalars, index);
+                        let ty = self.item_ty(scalars.stored);
+                        let value = self.builder.emit(Op::Load, ty, vec![Operand::Id(ptr)]);
+                        self.convert(value, scalars.stored, value_item(Item::new(elem)))
+                    }
+                    None => {
+                        self.unsupported(format!("The kernel has no binding for {elem} scalars"));
+                        self.undefined(Item::new(elem))
+                    }
+                }
+            }
+            cube::Variable::Rank => self.rank(),
+            cube::Variable::UnitPos => self.builtin_scalar(BuiltIn::LocalInvocationIndex),
+            cube::Variable::UnitPosX => self.builtin_component(BuiltIn::LocalInvocationId, 0),
+            cube::Variable::UnitPosY => self.builtin_component(BuiltIn::LocalInvocationId, 1),
+            cube::Variable::UnitPosZ => self.builtin_component(BuiltIn::LocalInvocationId, 2),
+            cube::Variable::CubePosX => self.builtin_component(BuiltIn::WorkgroupId, 0),
+            cube::Variable::CubePosY => self.builtin_component(BuiltIn::WorkgroupId, 1),
+            cube::Variable::CubePosZ => self.builtin_component(BuiltIn::WorkgroupId, 2),
+            cube::Variable::AbsolutePosX => self.builtin_component(BuiltIn::GlobalInvocationId, 0),
+            cube::Variable::AbsolutePosY => self.builtin_component(BuiltIn::GlobalInvocationId, 1),
+            cube::Variable::AbsolutePosZ => self.builtin_component(BuiltIn::GlobalInvocationId, 2),
+            cube::Variable::CubeCountX => self.builtin_component(BuiltIn::NumWorkgroups, 0),
+            cube::Variable::CubeCountY => self.builtin_component(BuiltIn::NumWorkgroups, 1),
+            cube::Variable::CubeCountZ => self.builtin_component(BuiltIn::NumWorkgroups, 2),
+            cube::Variable::CubeDimX => self.const_u32(self.cube_dim.x),
+            cube::Variable::CubeDimY => self.const_u32(self.cube_dim.y),
+            cube::Variable::CubeDimZ => self.const_u32(self.cube_dim.z),
+            cube::Variable::CubeDim => {
+                let cube_dim = self.cube_dim;
+                self.const_u32(cube_dim.x * cube_dim.y * cube_dim.z)
+            }
+            cube::Variable::CubeCount => {
+                let ty = self.item_ty(uint);
+                let x = self.builtin_component(BuiltIn::NumWorkgroups, 0);
+                let y = self.builtin_component(BuiltIn::NumWorkgroups, 1);
+                let z = self.builtin_component(BuiltIn::NumWorkgroups, 2);
+                let xy = self.emit(Op::IMul, ty, &[x, y]);
+                self.emit(Op::IMul, ty, &[xy, z])
+            }
+            cube::Variable::CubePos => {
+                let ty = self.item_ty(uint);
+                let count_x = self.builtin_component(BuiltIn::NumWorkgroups, 0);
+                let count_y = self.builtin_component(BuiltIn::NumWorkgroups, 1);
+                let x = self.builtin_component(BuiltIn::WorkgroupId, 0);
+                let y = self.builtin_component(BuiltIn::WorkgroupId, 1);
+                let z = self.builtin_component(BuiltIn::WorkgroupId, 2);
+
+                let count_xy = self.emit(Op::IMul, ty, &[count_x, count_y]);
+                let z = self.emit(Op::IMul, ty, &[count_xy, z]);
+                let y = self.emit(Op::IMul, ty, &[count_x, y]);
+                let zy = self.emit(Op::IAdd, ty, &[z, y]);
+                self.emit(Op::IAdd, ty, &[zy, x])
+            }
+            cube::Variable::AbsolutePos => {
+                let ty = self.item_ty(uint);
+                let cube_dim = self.cube_dim;
+                let count_x = self.builtin_component(BuiltIn::NumWorkgroups, 0);
+                let count_y = self.builtin_component(BuiltIn::NumWorkgroups, 1);
+                let x = self.builtin_component(BuiltIn::GlobalInvocationId, 0);
+                let y = self.builtin_component(BuiltIn::GlobalInvocationId, 1);
+                let z = self.builtin_component(BuiltIn::GlobalInvocationId, 2);
+                let dim_x = self.const_u32(cube_dim.x);
+                let dim_y = self.const_u32(cube_dim.y);
+
+                // The number of units along each axis of the whole grid.
+                let size_x = self.emit(Op::IMul, ty, &[count_x, dim_x]);
+                let size_y = self.emit(Op::IMul, ty, &[count_y, dim_y]);
+                let size_xy = self.emit(Op::IMul, ty, &[size_x, size_y]);
+
+                let z = self.emit(Op::IMul, ty, &[z, size_xy]);
+                let y = self.emit(Op::IMul, ty, &[y, size_x]);
+                let zy = self.emit(Op::IAdd, ty, &[z, y]);
+                self.emit(Op::IAdd, ty, &[zy, x])
+            }
+            cube::Variable::SubcubeDim => {
+                self.builder.capability(Capability::GroupNonUniform);
+                self.builtin_scalar(BuiltIn::SubgroupSize)
+            }
+            cube::Variable::GlobalInputArray { .. }
+            | cube::Variable::GlobalOutputArray { .. }
+            | cube::Variable::SharedMemory { .. }
+            | cube::Variable::LocalArray { .. }
+            | cube::Variable::Slice { .. }
+            | cube::Variable::Matrix { .. } => {
+                self.unsupported(format!("Can't read {variable:?} as a value"));
+                self.undefined(variable.item())
+            }
+        }
+    }
+
+    /// Read the value of a variable, converted to the given item.
+    pub(super) fn read_as(&mut self, variable: cube::Variable, item: Item) -> Word {
+        let value = self.read(variable);
+        self.convert(value, value_item(variable.item()), item)
+    }
+
+    /// Write a value of the same item as the variable.
+    pub(super) fn write(&mut self, variable: cube::Variable, value: Word) {
+        match self.local(variable) {
+            Var::Local { ptr, .. } => {
+                self.builder
+                    .emit_void(Op::Store, vec![Operand::Id(ptr), Operand::Id(value)]);
+            }
+            Var::Atomic { ptr, .. } => {
+                let (scope, semantics) = self.atomic_semantics();
+                self.builder.emit_void(
+                    Op::AtomicStore,
+                    vec![
+                        Operand::Id(ptr),
+                        Operand::Id(scope),
+                        Operand::Id(semantics),
+                        Operand::Id(value),
+                    ],
+                );
+            }
+            _ => self.unsupported(format!("Can't write a value to {variable:?}")),
+        }
+    }
+
+    /// Write a value of the given item, converting it to the item of the variable.
+    pub(super) fn write_from(&mut self, variable: cube::Variable, value: Word, item: Item) {
+        let value = self.convert(value, item, value_item(variable.item()));
+        self.write(variable, value);
+    }
+
+    fn builtin(&mut self, builtin: BuiltIn) -> Word {
+        if let Some(var) = self.builtins.get(&builtin) {
+            return *var;
+        }
+
+        let uint = self.item_ty(uint());
+        let ty = match builtin {
+            BuiltIn::LocalInvocationIndex | BuiltIn::SubgroupSize => uint,
+            _ => self.builder.ty(Type::Vector {
+                component: uint,
+                count: 3,
+            }),
+        };
+        let pointer = self.builder.ty(Type::Pointer {
+            class: StorageClass::Input,
+            pointee: ty,
+        });
+        let var = self.builder.global_variable(pointer, StorageClass::Input);
+        self.builder
+            .decorate(var, spirv::Decoration::BuiltIn, &[builtin as u32]);
+        self.builtins.insert(builtin, var);
+
+        var
+    }
+
+    fn builtin_scalar(&mut self, builtin: BuiltIn) -> Word {
+        let var = self.builtin(builtin);
+        let ty = self.item_ty(uint());
+        self.builder.emit(Op::Load, ty, vec![Operand::Id(var)])
+    }
+
+    fn builtin_component(&mut self, builtin: BuiltIn, index: u32) -> Word {
+        let var = self.builtin(builtin);
+        let uint = self.item_ty(uint());
+        let ty = self.builder.ty(Type::Vector {
+            component: uint,
+            count: 3,
+        });
+        let vector = self.builder.emit(Op::Load, ty, vec![Operand::Id(var)]);
+        self.builder.emit(
+            Op::CompositeExtract,
+            uint,
+            vec![Operand::Id(vector), Operand::Literal(index)],
+        )
+    }
+
+    /// The variable holding a local, declaring it the first time it is used.
+    pub(super) fn local(&mut self, variable: cube::Variable) -> Var {
+        let key = match LocalKey::of(variable) {
+            Some(key) => key,
+            None => {
+                self.unsupported(format!("{variable:?} isn't a local variable"));
+                return self.placeholder(variable);
+            }
+        };
+        if let Some(var) = self.locals.get(&key) {
+            return *var;
+        }
+
+        let var = match variable {
+            cube::Variable::Local { item, .. } => self.function_variable(value_item(item)),
+            cube::Variable::LocalScalar { elem, .. } => {
+                self.function_variable(value_item(Item::new(elem)))
+            }
+            cube::Variable::LocalArray { item, length, .. } => {
+                let stored = value_item(item);
+                let element = self.item_ty(stored);
+                let ty = self.array_ty(element, length);
+                let null = self.builder.constant(Constant::Null { ty });
+                let pointer = self.builder.ty(Type::Pointer {
+                    class: StorageClass::Function,
+                    pointee: ty,
+                });
+                let ptr = self.builder.function_variable(pointer, Some(null));
+
+                Var::Array(Array {
+                    ptr,
+                    class: StorageClass::Function,
+                    item,
+                    stored,
+                    block: false,
+                    offset: None,
+                    length: Length::Constant(length),
+                })
+            }
+            cube::Variable::Matrix { mat, .. } => {
+                let ty = self.matrix_ty(mat);
+                let pointer = self.builder.ty(Type::Pointer {
+                    class: StorageClass::Function,
+                    pointee: ty,
+                });
+                let ptr = self.builder.function_variable(pointer, None);
+
+                Var::Matrix { ptr, ty, mat }
+            }
+            _ => {
+                self.unsupported(format!("{variable:?} is used before being created"));
+                return self.placeholder(variable);
+            }
+        };
+
+        self.locals.insert(key, var);
+        var
+    }
+
+    fn function_variable(&mut self, item: Item) -> Var {
+        let ty = self.item_ty(item);
+        let null = self.builder.constant(Constant::Null { ty });
+        let pointer = self.builder.ty(Type::Pointer {
+            class: StorageClass::Function,
+            pointee: ty,
+        });
+        let ptr = self.builder.function_variable(pointer, Some(null));
+
+        Var::Local { ptr, item }
+    }
+
+    /// A variable standing for one that failed to compile.
+    fn placeholder(&mut self, variable: cube::Variable) -> Var {
+        match variable.item().elem.is_atomic() {
+            true => Var::Atomic {
+                ptr: self.builder.id(),
+                item: variable.item(),
+            },
+            false => Var::Local {
+                ptr: self.builder.id(),
+                item: value_item(variable.item()),
+            },
+        }
+    }
+
+    /// The array behind a variable, if it is one.
+    pub(super) fn array(&mut self, variable: cube::Variable) -> Option<Array> {
+        match variable {
+            cube::Variable::GlobalInputArray { id, .. } => self.globals.get(id as usize).copied(),
+            cube::Variable::GlobalOutputArray { id, .. } => {
+                self.globals.get(self.num_inputs + id as usize).copied()
+            }
+            cube::Variable::SharedMemory { id, item, length } => {
+                if let Some(array) = self.shared_memories.get(&id) {
+                    return Some(*array);
+                }
+
+                let stored = value_item(item);
+                let element = self.item_ty(stored);
+                let ty = self.array_ty(element, length);
+                let pointer = self.builder.ty(Type::Pointer {
+                    class: StorageClass::Workgroup,
+                    pointee: ty,
+                });
+                let ptr = self
+                    .builder
+                    .global_variable(pointer, StorageClass::Workgroup);
+                self.builder.name(ptr, &format!("shared_memory_{id}"));
+                self.shared_memory_size +=
+                    stored.elem.size() * stored.vectorization as usize * length as usize;
+
+                let array = Array {
+                    ptr,
+                    class: StorageClass::Workgroup,
+                    item,
+                    stored,
+                    block: false,
+                    offset: None,
+                    length: Length::Constant(length),
+                };
+                self.shared_memories.insert(id, array);
+                Some(array)
+            }
+            cube::Variable::LocalArray { .. } | cube::Variable::Slice { .. } => {
+                match self.local(variable) {
+                    Var::Array(array) => Some(array),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// A pointer to the element of an array at the given index.
+    pub(super) fn element(&mut self, array: Array, index: Word) -> Word {
+        let index = match array.offset {
+            Some(offset) => {
+                let ty = self.item_ty(uint());
+                self.emit(Op::IAdd, ty, &[index, offset])
+            }
+            None => index,
+        };
+
+        let element = self.item_ty(array.stored);
+        let pointer = self.builder.ty(Type::Pointer {
+            class: array.class,
+            pointee: element,
+        });
+
+        let mut operands = vec![Operand::Id(array.ptr)];
+        if array.block {
+            operands.push(Operand::Id(self.const_u32(0)));
+        }
+        operands.push(Operand::Id(index));
+
+        self.builder.emit(Op::AccessChain, pointer, operands)
+    }
+
+    /// The number of elements of an array.
+    pub(super) fn length(&mut self, array: Array) -> Word {
+        match array.length {
+            Length::Runtime => {
+                let ty = self.item_ty(uint());
+                self.builder.emit(
+                    Op::ArrayLength,
+                    ty,
+                    vec![Operand::Id(array.ptr), Operand::Literal(0)],
+                )
+            }
+            Length::Constant(length) => self.const_u32(length),
+            Length::Value(length) => length,
+        }
+    }
+
+    fn array_ty(&mut self, element: Word, length: u32) -> Word {
+        let length = self.const_u32(length);
+        self.builder.ty(Type::Array { element, length })
+    }
+
+    pub(super) fn compile_return(&mut self) {
+        match self.output {
+            Some(output) => {
+                let value = self.read(output);
+                self.builder
+                    .terminate(Op::ReturnValue, vec![Operand::Id(value)]);
+            }
+            None => self.builder.terminate(Op::Return, vec![]),
+        }
+    }
+
+    /// The scope and memory semantics of the atomic operations.
+    pub(super) fn atomic_semantics(&mut self) -> (Word, Word) {
+        let scope = self.const_u32(spirv::Scope::Device as u32);
+        let semantics = self.const_u32(spirv::MemorySemantics::RELAXED.bits());
+        (scope, semantics)
+    }
+
+    pub(super) fn item_ty(&mut self, item: Item) -> Word {
+        let component = self.elem_ty(item.elem);
+        match item.vectorization {
+            1 => component,
+            2..=4 => self.builder.ty(Type::Vector {
+                component,
+                count: item.vectorization as u32,
+            }),
+            _ => {
+                self.unsupported(format!(
+                    "Unsupported vectorizations scheme {:?}",
+                    item.vectorization
+                ));
+                component
+            }
+        }
+    }
+
+    pub(super) fn elem_ty(&mut self, elem: cube::Elem) -> Word {
+        let ty = match value_elem(elem) {
+            cube::Elem::Float(kind) => {
+                match kind {
+                    cube::FloatKind::F16 => self.builder.capability(Capability::Float16),
+                    cube::FloatKind::BF16 => {
+                        self.unsupported("bf16 isn't supported by SPIR-V".to_string())
+                    }
+                    cube::FloatKind::F32 => {}
+                    cube::FloatKind::F64 => self.builder.capability(Capability::Float64),
+                }
+                Type::Float {
+                    width: elem_width(elem),
+                }
+            }
+            cube::Elem::Int(_) | cube::Elem::UInt(_) => {
+                let width = elem_width(elem);
+                match width {
+                    8 => self.builder.capability(Capability::Int8),
+                    16 => self.builder.capability(Capability::Int16),
+                    64 => self.builder.capability(Capability::Int64),
+                    _ => {}
+                }
+                if width == 64 && elem.is_atomic() {
+                    self.builder.capability(Capability::Int64Atomics);
+                }
+                Type::Int {
+                    width,
+                    signed: matches!(value_elem(elem), cube::Elem::Int(_)),
+                }
+            }
+            cube::Elem::AtomicFloat(_) => {
+                self.unsupported(format!("{elem} isn't supported by SPIR-V"));
+                Type::Float {
+                    width: elem_width(elem),
+                }
+            }
+            _ => Type::Bool,
+        };
+
+        self.builder.ty(ty)
+    }
+
+    pub(super) fn const_u32(&mut self, value: u32) -> Word {
+        self.constant(
+            cube::ConstantScalarValue::UInt(value as u64, cube::UIntKind::U32),
+            cube::Elem::UInt(cube::UIntKind::U32),
+        )
+    }
+
+    /// A constant of the given element, converted from a scalar of any type.
+    pub(super) fn constant(&mut self, value: cube::ConstantScalarValue, elem: cube::Elem) -> Word {
+        let elem = value_elem(elem);
+        let float = match value {
+            cube::ConstantScalarValue::Int(value, _) => value as f64,
+            cube::ConstantScalarValue::Float(value, _) => value,
+            cube::ConstantScalarValue::UInt(value, _) => value as f64,
+            cube::ConstantScalarValue::Bool(value) => value as u8 as f64,
+        };
+        let int = match value {
+            cube::ConstantScalarValue::Int(value, _) => value,
+            cube::ConstantScalarValue::Float(value, _) => value as i64,
+            cube::ConstantScalarValue::UInt(value, _) => value as i64,
+            cube::ConstantScalarValue::Bool(value) => value as i64,
+        };
+
+        // The literals narrower than 32 bits are sign extended for signed integers, and zero
+        // extended otherwise.
+        let words = match elem {
+            cube::Elem::Float(kind) => match kind {
+                cube::FloatKind::F16 => vec![half::f16::from_f64(float).to_bits() as u32],
+                cube::FloatKind::BF16 => vec![half::bf16::from_f64(float).to_bits() as u32],
+                cube::FloatKind::F32 => vec![(float as f32).to_bits()],
+                cube::FloatKind::F64 => split(float.to_bits()),
+            },
+            cube::Elem::Int(kind) => match kind {
+                cube::IntKind::I8 => vec![int as i8 as i32 as u32],
+                cube::IntKind::I16 => vec![int as i16 as i32 as u32],
+                cube::IntKind::I32 => vec![int as i32 as u32],
+                cube::IntKind::I64 => split(int as u64),
+            },
+            cube::Elem::UInt(kind) => match kind {
+                cube::UIntKind::U8 => vec![int as u8 as u32],
+                cube::UIntKind::U16 => vec![int as u16 as u32],
+                cube::UIntKind::U32 => vec![int as u32],
+                cube::UIntKind::U64 => split(int as u64),
+            },
+            _ => {
+                let value = match value {
+                    cube::ConstantScalarValue::Bool(value) => value,
+                    _ => float != 0.0,
+                };
+                return self.builder.constant(Constant::Bool(value));
+            }
+        };
+
+        let ty = self.elem_ty(elem);
+        self.builder.constant(Constant::Number { ty, words })
+    }
+
+    /// A constant of the given item, with the same value for all its components.
+    pub(super) fn const_item(&mut self, item: Item, value: f64) -> Word {
+        let scalar = self.constant(
+            cube::ConstantScalarValue::Float(value, cube::FloatKind::F64),
+            item.elem,
+        );
+
+        match item.vectorization {
+            1 => scalar,
+            _ => {
+                let ty = self.item_ty(item);
+                self.builder.constant(Constant::Composite {
+                    ty,
+                    constituents: vec![scalar; item.vectorization as usize],
+                })
+            }
+        }
+    }
+
+    /// A value of the given item that is never used, standing for one that failed to compile.
+    pub(super) fn undefined(&mut self, item: Item) -> Word {
+        let ty = self.item_ty(value_item(item));
+        self.builder.emit(Op::Undef, ty, vec![])
+    }
+
+    pub(super) fn emit(&mut self, op: Op, ty: Word, ids: &[Word]) -> Word {
+        self.builder
+            .emit(op, ty, ids.iter().map(|id| Operand::Id(*id)).collect())
+    }
+
+    /// Call an instruction of the `GLSL.std.450` extended instruction set.
+    pub(super) fn glsl(&mut self, instruction: spirv::GLOp, ty: Word, ids: &[Word]) -> Word {
+        let set = self.builder.glsl();
+        let mut operands = vec![Operand::Id(set), Operand::Literal(instruction as u32)];
+        operands.extend(ids.iter().map(|id| Operand::Id(*id)));
+
+        self.builder.emit(Op::ExtInst, ty, operands)
+    }
+
+    /// Convert a value from an item to another, converting the elements and broadcasting scalars
+    /// to vectors.
+    pub(super) fn convert(&mut self, value: Word, from: Item, to: Item) -> Word {
+        let from = value_item(from);
+        let to = value_item(to);
+        if from == to {
+            return value;
+        }
+
+        let converted = Item::vectorized(to.elem, from.vectorization);
+        let value = self.convert_elem(value, from, converted);
+
+        match (from.vectorization, to.vectorization) {
+            (a, b) if a == b => value,
+            (1, _) => {
+                let ty = self.item_ty(to);
+                self.emit(
+                    Op::CompositeConstruct,
+                    ty,
+                    &vec![value; to.vectorization as usize],
+                )
+            }
+            (_, 1) => {
+                let ty = self.item_ty(to);
+                self.builder.emit(
+                    Op::CompositeExtract,
+                    ty,
+                    vec![Operand::Id(value), Operand::Literal(0)],
+                )
+            }
+            _ => {
+                self.unsupported(format!(
+                    "Can't convert a vector of {} elements to {} elements",
+                    from.vectorization, to.vectorization
+                ));
+                value
+            }
+        }
+    }
+
+    fn convert_elem(&mut self, value: Word, from: Item, to: Item) -> Word {
+        if from.elem == to.elem {
+            return value;
+        }
+
+        let ty = self.item_ty(to);
+        let (from_kind, to_kind) = (Kind::of(from.elem), Kind::of(to.elem));
+        let (from_width, to_width) = (elem_width(from.elem), elem_width(to.elem));
+
+        match (from_kind, to_kind) {
+            (Kind::Bool, _) => {
+                let one = self.const_item(to, 1.0);
+                let zero = self.const_item(to, 0.0);
+                self.emit(Op::Select, ty, &[value, one, zero])
+            }
+            (_, Kind::Bool) => {
+                let zero = self.const_item(from, 0.0);
+                let op = match from_kind {
+                    Kind::Float => Op::FUnordNotEqual,
+                    _ => Op::INotEqual,
+                };
+                self.emit(op, ty, &[value, zero])
+            }
+            (Kind::Float, Kind::Float) => self.emit(Op::FConvert, ty, &[value]),
+            (Kind::Float, Kind::Int) => self.emit(Op::ConvertFToS, ty, &[value]),
+            (Kind::Float, Kind::UInt) => self.emit(Op::ConvertFToU, ty, &[value]),
+            (Kind::Int, Kind::Float) => self.emit(Op::ConvertSToF, ty, &[value]),
+            (Kind::UInt, Kind::Float) => self.emit(Op::ConvertUToF, ty, &[value]),
+            (_, _) if from_width == to_width => self.emit(Op::Bitcast, ty, &[value]),
+            (_, _) => {
+                // The width is changed while keeping the signedness of the source, which decides
+                // how the value is extended.
+                let (op, elem) = match from_kind {
+                    Kind::Int => (Op::SConvert, int_elem(to_width, true)),
+                    _ => (Op::UConvert, int_elem(to_width, false)),
+                };
+                let item = Item::vectorized(elem, to.vectorization);
+                let widened_ty = self.item_ty(item);
+                let value = self.emit(op, widened_ty, &[value]);
+
+                match item == to {
+                    true => value,
+                    false => self.emit(Op::Bitcast, ty, &[value]),
+                }
+            }
+        }
+    }
+
+    pub(super) fn unsupported(&mut self, reason: String) {
+        self.fail(ComputeError::UnsupportedFeature(reason));
+    }
+
+    fn fail(&mut self, error: ComputeError) {
+        // Only the first error is kept, the following ones are often a consequence of it.
+        self.error.get_or_insert(error);
+    }
+}
+
+/// The category of an element, which decides the instructions operating on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Kind {
+    Float,
+    Int,
+    UInt,
+    Bool,
+}
+
+impl Kind {
+    pub fn of(elem: cube::Elem) -> Self {
+        match value_elem(elem) {
+            cube::Elem::Float(_) => Kind::Float,
+            cube::Elem::Int(_) => Kind::Int,
+            cube::Elem::UInt(_) => Kind::UInt,
+            _ => Kind::Bool,
+        }
+    }
+}
+
+pub(super) fn uint() -> Item {
+    Item::new(cube::Elem::UInt(cube::UIntKind::U32))
+}
+
+/// The element of the values read from an element, atomics being read as plain integers.
+pub(super) fn value_elem(elem: cube::Elem) -> cube::Elem {
+    match elem {
+        cube::Elem::AtomicInt(kind) => cube::Elem::Int(kind),
+        cube::Elem::AtomicUInt => cube::Elem::UInt(cube::UIntKind::U32),
+        _ => elem,
+    }
+}
+
+pub(super) fn value_item(item: Item) -> Item {
+    Item::vectorized(value_elem(item.elem), item.vectorization)
+}
+
+/// The item stored in a storage buffer, where booleans are stored as integers.
+fn stored_item(item: Item) -> Item {
+    match value_elem(item.elem) {
+        cube::Elem::Bool => {
+            Item::vectorized(cube::Elem::UInt(cube::UIntKind::U32), item.vectorization)
+        }
+        elem => Item::vectorized(elem, item.vectorization),
+    }
+}
+
+/// The number of bits of an element.
+pub(super) fn elem_width(elem: cube::Elem) -> u32 {
+    match value_elem(elem) {
+        cube::Elem::Bool => 32,
+        elem => elem.size() as u32 * 8,
+    }
+}
+
+pub(super) fn int_elem(width: u32, signed: bool) -> cube::Elem {
+    match (width, signed) {
+        (8, true) => cube::Elem::Int(cube::IntKind::I8),
+        (16, true) => cube::Elem::Int(cube::IntKind::I16),
+        (64, true) => cube::Elem::Int(cube::IntKind::I64),
+        (_, true) => cube::Elem::Int(cube::IntKind::I32),
+        (8, false) => cube::Elem::UInt(cube::UIntKind::U8),
+        (16, false) => cube::Elem::UInt(cube::UIntKind::U16),
+        (64, false) => cube::Elem::UInt(cube::UIntKind::U64),
+        (_, false) => cube::Elem::UInt(cube::UIntKind::U32),
+    }
+}
+
+/// Split a 64 bits literal in words, the lowest order word first.
+fn split(value: u64) -> Vec<u32> {
+    vec![value as u32, (value >> 32) as u32]
+}
@@ -0,0 +1,192 @@
+use super::base::{uint, value_item, SpirvCompiler};
+use crate::module::{Operand, Word};
+use cubecl_core::ir::{self as cube, Elem, Item};
+use spirv::Op;
+
+impl SpirvCompiler {
+    pub(super) fn compile_branch(&mut self, branch: cube::Branch) {
+        match branch {
+            cube::Branch::If(mut op) => {
+                let cond = self.read_as(op.cond, Item::new(Elem::Bool));
+                let body = self.builder.id();
+                let merge = self.builder.id();
+
+                self.selection_merge(merge);
+                self.branch_conditional(cond, body, merge);
+
+                self.builder.label(body);
+                self.compile_scope(&mut op.scope);
+                self.builder.branch(merge);
+
+                self.builder.label(merge);
+            }
+            cube::Branch::IfElse(mut op) => {
+                let cond = self.read_as(op.cond, Item::new(Elem::Bool));
+                let body_if = self.builder.id();
+                let body_else = self.builder.id();
+                let merge = self.builder.id();
+
+                self.selection_merge(merge);
+                self.branch_conditional(cond, body_if, body_else);
+
+                self.builder.label(body_if);
+                self.compile_scope(&mut op.scope_if);
+                self.builder.branch(merge);
+
+                self.builder.label(body_else);
+                self.compile_scope(&mut op.scope_else);
+                self.builder.branch(merge);
+
+                self.builder.label(merge);
+            }
+            cube::Branch::Switch(mut op) => {
+                let item = value_item(op.value.item());
+                let value = self.read(op.value);
+                let default = self.builder.id();
+                let merge = self.builder.id();
+                let labels = op
+                    .cases
+                    .iter()
+                    .map(|_| self.builder.id())
+                    .collect::<Vec<_>>();
+
+                let mut operands = vec![Operand::Id(value), Operand::Id(default)];
+                // Every value of a case branches to the same label.
+                for ((values, _), label) in op.cases.iter().zip(labels.iter()) {
+                    for case in values {
+                        let literal = match case {
+                            cube::Variable::ConstantScalar(value) => value.try_as_u64(),
+                            _ => None,
+                        };
+                        let literal = match literal {
+                            Some(literal) => literal,
+                            None => {
+                                self.unsupported(format!(
+                                    "Switch cases must be integers, got {case:?}"
+                                ));
+                                0
+                            }
+                        };
+
+                        operands.push(Operand::Literal(literal as u32));
+                        if item.elem.size() == 8 {
+                            operands.push(Operand::Literal((literal >> 32) as u32));
+                        }
+                        operands.push(Operand::Id(*label));
+                    }
+                }
+
+                self.selection_merge(merge);
+                self.builder.terminate(Op::Switch, operands);
+
+                for ((_, scope), label) in op.cases.iter_mut().zip(labels) {
+                    self.builder.label(label);
+                    self.compile_scope(scope);
+                    self.builder.branch(merge);
+                }
+
+                self.builder.label(default);
+                self.compile_scope(&mut op.scope_default);
+                self.builder.branch(merge);
+
+                self.builder.label(merge);
+            }
+            cube::Branch::RangeLoop(mut op) => {
+                let uint = uint();
+                let ty = self.item_ty(uint);
+                let start = self.read_as(op.start, uint);
+                self.write_from(op.i, start, uint);
+
+                let header = self.builder.id();
+                let body = self.builder.id();
+                let continue_target = self.builder.id();
+                let merge = self.builder.id();
+
+                self.builder.branch(header);
+                self.builder.label(header);
+                let i = self.read_as(op.i, uint);
+                let end = self.read_as(op.end, uint);
+                let bool_ty = self.item_ty(Item::new(Elem::Bool));
+                let cond = self.emit(Op::ULessThan, bool_ty, &[i, end]);
+                self.loop_merge(merge, continue_target);
+                self.branch_conditional(cond, body, merge);
+
+                self.builder.label(body);
+                self.loops.push(merge);
+                self.compile_scope(&mut op.scope);
+                self.loops.pop();
+                self.builder.branch(continue_target);
+
+                self.builder.label(continue_target);
+                let step = match op.step {
+                    Some(step) => self.read_as(step, uint),
+                    None => self.const_u32(1),
+                };
+                let i = self.read_as(op.i, uint);
+                let next = self.emit(Op::IAdd, ty, &[i, step]);
+                self.write_from(op.i, next, uint);
+                self.builder.branch(header);
+
+                self.builder.label(merge);
+            }
+            cube::Branch::Loop(mut op) => {
+                let header = self.builder.id();
+                let body = self.builder.id();
+                let continue_target = self.builder.id();
+                let merge = self.builder.id();
+
+                self.builder.branch(header);
+                self.builder.label(header);
+                self.loop_merge(merge, continue_target);
+                self.builder.branch(body);
+
+                self.builder.label(body);
+                self.loops.push(merge);
+                self.compile_scope(&mut op.scope);
+                self.loops.pop();
+                self.builder.branch(continue_target);
+
+                self.builder.label(continue_target);
+                self.builder.branch(header);
+
+                self.builder.label(merge);
+            }
+            cube::Branch::Return => self.compile_return(),
+            cube::Branch::Break => match self.loops.last() {
+                Some(merge) => {
+                    let merge = *merge;
+                    self.builder.branch(merge);
+                }
+                None => self.unsupported("Break outside of a loop".to_string()),
+            },
+        }
+    }
+
+    fn selection_merge(&mut self, merge: Word) {
+        self.builder.emit_void(
+            Op::SelectionMerge,
+            vec![
+                Operand::Id(merge),
+                Operand::Enumerant("None", spirv::SelectionControl::NONE.bits()),
+            ],
+        );
+    }
+
+    fn loop_merge(&mut self, merge: Word, continue_target: Word) {
+        self.builder.emit_void(
+            Op::LoopMerge,
+            vec![
+                Operand::Id(merge),
+                Operand::Id(continue_target),
+                Operand::Enumerant("None", spirv::LoopControl::NONE.bits()),
+            ],
+        );
+    }
+
+    fn branch_conditional(&mut self, cond: Word, accept: Word, reject: Word) {
+        self.builder.terminate(
+            Op::BranchConditional,
+            vec![Operand::Id(cond), Operand::Id(accept), Operand::Id(reject)],
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use super::base::{uint, SpirvCompiler};
+use super::variable::Var;
+use crate::builder::Type;
+use crate::module::{Operand, Word};
+use cubecl_core::ir::{self as cube, Item, Matrix, MatrixIdent, MatrixLayout};
+use spirv::{Capability, CooperativeMatrixLayout, CooperativeMatrixUse, Op};
+
+impl SpirvCompiler {
+    pub(super) fn compile_cmma(&mut self, cmma: cube::CoopMma) {
+        match cmma {
+            cube::CoopMma::Fill { mat, value } => {
+                let Some((ptr, ty, mat)) = self.matrix(mat) else {
+                    return;
+                };
+                let value = self.read_as(value, Item::new(mat.elem));
+                let matrix = self.emit(Op::CompositeConstruct, ty, &[value]);
+                self.store_matrix(ptr, matrix);
+            }
+            cube::CoopMma::Load { mat, value, stride } => {
+                let Some((ptr, ty, mat)) = self.matrix(mat) else {
+                    return;
+                };
+                let Some(pointer) = self.first_element(value) else {
+                    return;
+                };
+                let layout = self.matrix_layout(mat.layout);
+                let stride = self.read_as(stride, uint());
+                let matrix =
+                    self.emit(Op::CooperativeMatrixLoadKHR, ty, &[pointer, layout, stride]);
+                self.store_matrix(ptr, matrix);
+            }
+            cube::CoopMma::Execute {
+                mat_a,
+                mat_b,
+                mat_c,
+                mat_d,
+            } => {
+                let (Some(a), Some(b), Some(c), Some((ptr, ty, _))) = (
+                    self.load_matrix(mat_a),
+                    self.load_matrix(mat_b),
+                    self.load_matrix(mat_c),
+                    self.matrix(mat_d),
+                ) else {
+                    return;
+                };
+                let matrix = self.emit(Op::CooperativeMatrixMulAddKHR, ty, &[a, b, c]);
+                self.store_matrix(ptr, matrix);
+            }
+            cube::CoopMma::Store {
+                output,
+                mat,
+                stride,
+                layout,
+            } => {
+                let (Some(matrix), Some(pointer)) =
+                    (self.load_matrix(mat), self.first_element(output))
+                else {
+                    return;
+                };
+                let layout = self.matrix_layout(layout);
+                let stride = self.read_as(stride, uint());
+                self.builder.emit_void(
+                    Op::CooperativeMatrixStoreKHR,
+                    vec![
+                        Operand::Id(pointer),
+                        Operand::Id(matrix),
+                        Operand::Id(layout),
+                        Operand::Id(stride),
+                    ],
+                );
+            }
+        }
+    }
+
+    /// The type of a cooperative matrix, whose shape depends on its use in the multiplication.
+    pub(super) fn matrix_ty(&mut self, mat: Matrix) -> Word {
+        self.builder.capability(Capability::CooperativeMatrixKHR);
+        self.builder.extension("SPV_KHR_cooperative_matrix");
+
+        let (rows, columns, usage) = match mat.ident {
+            MatrixIdent::A => (mat.m, mat.k, CooperativeMatrixUse::MatrixAKHR),
+            MatrixIdent::B => (mat.k, mat.n, CooperativeMatrixUse::MatrixBKHR),
+            MatrixIdent::Accumulator => (mat.m, mat.n, CooperativeMatrixUse::MatrixAccumulatorKHR),
+        };
+
+        let component = self.elem_ty(mat.elem);
+        let scope = self.const_u32(spirv::Scope::Subgroup as u32);
+        let rows = self.const_u32(rows as u32);
+        let columns = self.const_u32(columns as u32);
+        let usage = self.const_u32(usage as u32);
+
+        self.builder.ty(Type::CooperativeMatrix {
+            component,
+            scope,
+            rows,
+            columns,
+            usage,
+        })
+    }
+
+    fn matrix(&mut self, variable: cube::Variable) -> Option<(Word, Word, Matrix)> {
+        match self.local(variable) {
+            Var::Matrix { ptr, ty, mat } => Some((ptr, ty, mat)),
+            _ => {
+                self.unsupported(format!("{variable:?} isn't a matrix"));
+                None
+            }
+        }
+    }
+
+    fn load_matrix(&mut self, variable: cube::Variable) -> Option<Word> {
+        let (ptr, ty, _) = self.matrix(variable)?;
+        Some(self.builder.emit(Op::Load, ty, vec![Operand::Id(ptr)]))
+    }
+
+    fn store_matrix(&mut self, ptr: Word, matrix: Word) {
+        self.builder
+            .emit_void(Op::Store, vec![Operand::Id(ptr), Operand::Id(matrix)]);
+    }
+
+    /// A pointer to the first element of an array or slice, where a matrix is loaded or stored.
+    fn first_element(&mut self, variable: cube::Variable) -> Option<Word> {
+        match self.array(variable) {
+            Some(array) => {
+                let zero = self.const_u32(0);
+                Some(self.element(array, zero))
+            }
+            None => {
+                self.unsupported(format!(
+                    "Matrices can only be loaded from arrays, got {variable:?}"
+                ));
+                None
+            }
+        }
+    }
+
+    fn matrix_layout(&mut self, layout: MatrixLayout) -> Word {
+        let layout = match layout {
+            MatrixLayout::ColMajor => CooperativeMatrixLayout::ColumnMajorKHR,
+            // The accumulators have no layout of their own.
+            MatrixLayout::RowMajor | MatrixLayout::Undefined => {
+                CooperativeMatrixLayout::RowMajorKHR
+            }
+        };
+        self.const_u32(layout as u32)
+    }
+}
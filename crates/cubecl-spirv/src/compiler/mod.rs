@@ -0,0 +1,8 @@
+mod base;
+mod branch;
+mod cmma;
+mod operator;
+mod subcube;
+mod variable;
+
+pub use base::*;
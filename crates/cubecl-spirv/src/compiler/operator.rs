@@ -0,0 +1,724 @@
+use super::base::{elem_width, int_elem, uint, value_item, Kind, SpirvCompiler};
+use super::variable::{Array, Length, LocalKey, Var};
+use crate::module::{Operand, Word};
+use cubecl_core::ir::{self as cube, Elem, Item};
+use spirv::{GLOp, Op};
+
+impl SpirvCompiler {
+    pub(super) fn compile_operator(&mut self, op: cube::Operator) {
+        match op {
+            cube::Operator::Add(op) if op.out.item().elem.is_atomic() => {
+                self.atomic_update(op, |_| Op::AtomicIAdd)
+            }
+            cube::Operator::Sub(op) if op.out.item().elem.is_atomic() => {
+                self.atomic_update(op, |_| Op::AtomicISub)
+            }
+            cube::Operator::Max(op) if op.out.item().elem.is_atomic() => {
+                self.atomic_update(op, atomic_max)
+            }
+            cube::Operator::Min(op) if op.out.item().elem.is_atomic() => {
+                self.atomic_update(op, atomic_min)
+            }
+            cube::Operator::And(op) if op.out.item().elem.is_atomic() => {
+                self.atomic_update(op, |_| Op::AtomicAnd)
+            }
+            cube::Operator::Or(op) if op.out.item().elem.is_atomic() => {
+                self.atomic_update(op, |_| Op::AtomicOr)
+            }
+            cube::Operator::Add(op) => self.arithmetic(op, Op::FAdd, Op::IAdd, Op::IAdd),
+            cube::Operator::Sub(op) => self.arithmetic(op, Op::FSub, Op::ISub, Op::ISub),
+            cube::Operator::Mul(op) => self.arithmetic(op, Op::FMul, Op::IMul, Op::IMul),
+            cube::Operator::Div(op) => self.arithmetic(op, Op::FDiv, Op::SDiv, Op::UDiv),
+            // The modulo truncates, like the remainder of Rust.
+            cube::Operator::Modulo(op) => self.arithmetic(op, Op::FRem, Op::SRem, Op::UMod),
+            // The remainder floors, taking the sign of the divisor.
+            cube::Operator::Remainder(op) => self.arithmetic(op, Op::FMod, Op::SMod, Op::UMod),
+            cube::Operator::Fma(op) => {
+                let item = value_item(op.out.item());
+                let ty = self.item_ty(item);
+                let a = self.read_as(op.a, item);
+                let b = self.read_as(op.b, item);
+                let c = self.read_as(op.c, item);
+                let result = match Kind::of(item.elem) {
+                    Kind::Float => self.glsl(GLOp::Fma, ty, &[a, b, c]),
+                    _ => {
+                        let product = self.emit(Op::IMul, ty, &[a, b]);
+                        self.emit(Op::IAdd, ty, &[product, c])
+                    }
+                };
+                self.write(op.out, result);
+            }
+            cube::Operator::Abs(op) => {
+                self.unary(op, |this, ty, input, item| match Kind::of(item.elem) {
+                    Kind::Float => this.glsl(GLOp::FAbs, ty, &[input]),
+                    Kind::Int => this.glsl(GLOp::SAbs, ty, &[input]),
+                    _ => input,
+                })
+            }
+            cube::Operator::Exp(op) => self.float_unary(op, GLOp::Exp),
+            cube::Operator::Log(op) => self.float_unary(op, GLOp::Log),
+            cube::Operator::Log1p(op) => self.unary(op, |this, ty, input, item| {
+                let one = this.const_item(item, 1.0);
+                let input = this.emit(Op::FAdd, ty, &[input, one]);
+                this.glsl(GLOp::Log, ty, &[input])
+            }),
+            cube::Operator::Cos(op) => self.float_unary(op, GLOp::Cos),
+            cube::Operator::Sin(op) => self.float_unary(op, GLOp::Sin),
+            cube::Operator::Tanh(op) => self.float_unary(op, GLOp::Tanh),
+            cube::Operator::Powf(op) => {
+                self.binary(op, |this, ty, lhs, rhs, item| this.powf(ty, lhs, rhs, item))
+            }
+            cube::Operator::Sqrt(op) => self.float_unary(op, GLOp::Sqrt),
+            cube::Operator::Floor(op) => self.float_unary(op, GLOp::Floor),
+            cube::Operator::Ceil(op) => self.float_unary(op, GLOp::Ceil),
+            cube::Operator::Erf(op) => {
+                self.unary(op, |this, ty, input, item| this.erf(ty, input, item))
+            }
+            cube::Operator::Recip(op) => self.unary(op, |this, ty, input, item| {
+                let one = this.const_item(item, 1.0);
+                this.emit(Op::FDiv, ty, &[one, input])
+            }),
+            cube::Operator::Tan(op) => self.float_unary(op, GLOp::Tan),
+            cube::Operator::Asin(op) => self.float_unary(op, GLOp::Asin),
+            cube::Operator::Acos(op) => self.float_unary(op, GLOp::Acos),
+            cube::Operator::Atan(op) => self.float_unary(op, GLOp::Atan),
+            cube::Operator::Atan2(op) => self.binary(op, |this, ty, lhs, rhs, _| {
+                this.glsl(GLOp::Atan2, ty, &[lhs, rhs])
+            }),
+            cube::Operator::Sinh(op) => self.float_unary(op, GLOp::Sinh),
+            cube::Operator::Cosh(op) => self.float_unary(op, GLOp::Cosh),
+            cube::Operator::Exp2(op) => self.float_unary(op, GLOp::Exp2),
+            cube::Operator::Log2(op) => self.float_unary(op, GLOp::Log2),
+            cube::Operator::Round(op) => self.float_unary(op, GLOp::Round),
+            cube::Operator::Trunc(op) => self.float_unary(op, GLOp::Trunc),
+            cube::Operator::Fract(op) => self.unary(op, |this, ty, input, _| {
+                let trunc = this.glsl(GLOp::Trunc, ty, &[input]);
+                this.emit(Op::FSub, ty, &[input, trunc])
+            }),
+            cube::Operator::Sign(op) => {
+                self.unary(op, |this, ty, input, item| match Kind::of(item.elem) {
+                    Kind::Float => this.glsl(GLOp::FSign, ty, &[input]),
+                    Kind::Int => this.glsl(GLOp::SSign, ty, &[input]),
+                    _ => {
+                        let one = this.const_item(item, 1.0);
+                        this.glsl(GLOp::UMin, ty, &[input, one])
+                    }
+                })
+            }
+            cube::Operator::CopySign(op) => self.binary(op, |this, ty, lhs, rhs, item| {
+                this.copy_sign(ty, lhs, rhs, item)
+            }),
+            cube::Operator::Hypot(op) => self.binary(op, |this, ty, lhs, rhs, _| {
+                let lhs = this.emit(Op::FMul, ty, &[lhs, lhs]);
+                let rhs = this.emit(Op::FMul, ty, &[rhs, rhs]);
+                let sum = this.emit(Op::FAdd, ty, &[lhs, rhs]);
+                this.glsl(GLOp::Sqrt, ty, &[sum])
+            }),
+            cube::Operator::Rsqrt(op) => self.float_unary(op, GLOp::InverseSqrt),
+            cube::Operator::IsNan(op) => self.classify(op, Op::IsNan),
+            cube::Operator::IsInf(op) => self.classify(op, Op::IsInf),
+            cube::Operator::Equal(op) => self.comparison(
+                op,
+                [Op::FOrdEqual, Op::IEqual, Op::IEqual, Op::LogicalEqual],
+            ),
+            cube::Operator::NotEqual(op) => self.comparison(
+                op,
+                [
+                    Op::FUnordNotEqual,
+                    Op::INotEqual,
+                    Op::INotEqual,
+                    Op::LogicalNotEqual,
+                ],
+            ),
+            cube::Operator::Lower(op) => self.comparison(
+                op,
+                [Op::FOrdLessThan, Op::SLessThan, Op::ULessThan, Op::Nop],
+            ),
+            cube::Operator::LowerEqual(op) => self.comparison(
+                op,
+                [
+                    Op::FOrdLessThanEqual,
+                    Op::SLessThanEqual,
+                    Op::ULessThanEqual,
+                    Op::Nop,
+                ],
+            ),
+            cube::Operator::Greater(op) => self.comparison(
+                op,
+                [
+                    Op::FOrdGreaterThan,
+                    Op::SGreaterThan,
+                    Op::UGreaterThan,
+                    Op::Nop,
+                ],
+            ),
+            cube::Operator::GreaterEqual(op) => self.comparison(
+                op,
+                [
+                    Op::FOrdGreaterThanEqual,
+                    Op::SGreaterThanEqual,
+                    Op::UGreaterThanEqual,
+                    Op::Nop,
+                ],
+            ),
+            cube::Operator::Clamp(op) => {
+                let item = value_item(op.out.item());
+                let ty = self.item_ty(item);
+                let input = self.read_as(op.input, item);
+                let min = self.read_as(op.min_value, item);
+                let max = self.read_as(op.max_value, item);
+                let instruction = match Kind::of(item.elem) {
+                    Kind::Float => GLOp::FClamp,
+                    Kind::Int => GLOp::SClamp,
+                    _ => GLOp::UClamp,
+                };
+                let result = self.glsl(instruction, ty, &[input, min, max]);
+                self.write(op.out, result);
+            }
+            cube::Operator::Max(op) => self.binary(op, |this, ty, lhs, rhs, item| {
+                let instruction = match Kind::of(item.elem) {
+                    Kind::Float => GLOp::FMax,
+                    Kind::Int => GLOp::SMax,
+                    _ => GLOp::UMax,
+                };
+                this.glsl(instruction, ty, &[lhs, rhs])
+            }),
+            cube::Operator::Min(op) => self.binary(op, |this, ty, lhs, rhs, item| {
+                let instruction = match Kind::of(item.elem) {
+                    Kind::Float => GLOp::FMin,
+                    Kind::Int => GLOp::SMin,
+                    _ => GLOp::UMin,
+                };
+                this.glsl(instruction, ty, &[lhs, rhs])
+            }),
+            cube::Operator::Assign(op) => {
+                if op.out.item().elem.is_atomic() {
+                    // Atomics are pointers to the elements of an array, the assignment only makes
+                    // an alias.
+                    match (LocalKey::of(op.input), LocalKey::of(op.out)) {
+                        (Some(input), Some(out)) if self.locals.contains_key(&input) => {
+                            let var = self.locals[&input];
+                            self.locals.insert(out, var);
+                        }
+                        _ => self.unsupported(format!("Can't assign {:?} to an atomic", op.input)),
+                    }
+                    return;
+                }
+
+                let value = self.read_as(op.input, value_item(op.out.item()));
+                self.write(op.out, value);
+            }
+            cube::Operator::Index(op) | cube::Operator::UncheckedIndex(op) => self.index(op),
+            cube::Operator::IndexAssign(op) | cube::Operator::UncheckedIndexAssign(op) => {
+                self.index_assign(op)
+            }
+            cube::Operator::Slice(op) => self.slice(op),
+            cube::Operator::And(op) => self.logical(op, Op::LogicalAnd),
+            cube::Operator::Or(op) => self.logical(op, Op::LogicalOr),
+            cube::Operator::Not(op) => {
+                let item = Item::vectorized(Elem::Bool, op.out.item().vectorization);
+                let ty = self.item_ty(item);
+                let input = self.read_as(op.input, item);
+                let result = self.emit(Op::LogicalNot, ty, &[input]);
+                self.write_from(op.out, result, item);
+            }
+            cube::Operator::BitwiseAnd(op) => self.integer(op, Op::BitwiseAnd),
+            cube::Operator::BitwiseOr(op) => self.integer(op, Op::BitwiseOr),
+            cube::Operator::BitwiseXor(op) => self.integer(op, Op::BitwiseXor),
+            cube::Operator::ShiftLeft(op) => self.integer(op, Op::ShiftLeftLogical),
+            cube::Operator::ShiftRight(op) => match Kind::of(op.out.item().elem) {
+                Kind::Int => self.integer(op, Op::ShiftRightArithmetic),
+                _ => self.integer(op, Op::ShiftRightLogical),
+            },
+            cube::Operator::BitwiseNot(op) => {
+                self.unary(op, |this, ty, input, _| this.emit(Op::Not, ty, &[input]))
+            }
+            cube::Operator::Neg(op) => {
+                self.unary(op, |this, ty, input, item| match Kind::of(item.elem) {
+                    Kind::Float => this.emit(Op::FNegate, ty, &[input]),
+                    _ => this.emit(Op::SNegate, ty, &[input]),
+                })
+            }
+            cube::Operator::CountOnes(op) => self.bit_count(op, BitCount::CountOnes),
+            cube::Operator::ReverseBits(op) => self.bit_count(op, BitCount::ReverseBits),
+            cube::Operator::LeadingZeros(op) => self.bit_count(op, BitCount::LeadingZeros),
+            cube::Operator::TrailingZeros(op) => self.bit_count(op, BitCount::TrailingZeros),
+            cube::Operator::Bitcast(op) => {
+                let item = value_item(op.out.item());
+                let ty = self.item_ty(item);
+                let input = self.read(op.input);
+                let result = self.emit(Op::Bitcast, ty, &[input]);
+                self.write(op.out, result);
+            }
+            cube::Operator::AtomicLoad(op) => {
+                let value = self.read(op.input);
+                self.write_from(op.out, value, value_item(op.input.item()));
+            }
+            cube::Operator::AtomicStore(op) => {
+                let value = self.read_as(op.input, value_item(op.out.item()));
+                self.write(op.out, value);
+            }
+            cube::Operator::AtomicSwap(op) => self.atomic(op, |_| Op::AtomicExchange),
+            cube::Operator::AtomicAdd(op) => self.atomic(op, |_| Op::AtomicIAdd),
+            cube::Operator::AtomicSub(op) => self.atomic(op, |_| Op::AtomicISub),
+            cube::Operator::AtomicMax(op) => self.atomic(op, atomic_max),
+            cube::Operator::AtomicMin(op) => self.atomic(op, atomic_min),
+            cube::Operator::AtomicAnd(op) => self.atomic(op, |_| Op::AtomicAnd),
+            cube::Operator::AtomicOr(op) => self.atomic(op, |_| Op::AtomicOr),
+            cube::Operator::AtomicXor(op) => self.atomic(op, |_| Op::AtomicXor),
+            cube::Operator::AtomicCompareAndSwap(op) => {
+                let (ptr, item) = self.atomic_pointer(op.input);
+                let ty = self.item_ty(item);
+                let cmp = self.read_as(op.cmp, item);
+                let value = self.read_as(op.val, item);
+                let (scope, semantics) = self.atomic_semantics();
+                // Only the previous value is returned, for compatibility with cuda.
+                let result = self.emit(
+                    Op::AtomicCompareExchange,
+                    ty,
+                    &[ptr, scope, semantics, semantics, value, cmp],
+                );
+                self.write_from(op.out, result, item);
+            }
+        }
+    }
+
+    fn unary(
+        &mut self,
+        op: cube::UnaryOperator,
+        func: impl FnOnce(&mut Self, Word, Word, Item) -> Word,
+    ) {
+        let item = value_item(op.out.item());
+        let ty = self.item_ty(item);
+        let input = self.read_as(op.input, item);
+        let result = func(self, ty, input, item);
+        self.write(op.out, result);
+    }
+
+    fn float_unary(&mut self, op: cube::UnaryOperator, instruction: GLOp) {
+        self.unary(op, |this, ty, input, _| {
+            this.glsl(instruction, ty, &[input])
+        })
+    }
+
+    fn binary(
+        &mut self,
+        op: cube::BinaryOperator,
+        func: impl FnOnce(&mut Self, Word, Word, Word, Item) -> Word,
+    ) {
+        let item = value_item(op.out.item());
+        let ty = self.item_ty(item);
+        let lhs = self.read_as(op.lhs, item);
+        let rhs = self.read_as(op.rhs, item);
+        let result = func(self, ty, lhs, rhs, item);
+        self.write(op.out, result);
+    }
+
+    fn arithmetic(&mut self, op: cube::BinaryOperator, float: Op, int: Op, uint: Op) {
+        match Kind::of(op.out.item().elem) {
+            Kind::Float => self.binary(op, |this, ty, lhs, rhs, _| {
+                this.emit(float, ty, &[lhs, rhs])
+            }),
+            Kind::Int => self.binary(op, |this, ty, lhs, rhs, _| this.emit(int, ty, &[lhs, rhs])),
+            Kind::UInt => self.binary(op, |this, ty, lhs, rhs, _| this.emit(uint, ty, &[lhs, rhs])),
+            Kind::Bool => {
+                self.unsupported(format!("Arithmetic on booleans isn't supported: {op:?}"))
+            }
+        }
+    }
+
+    fn integer(&mut self, op: cube::BinaryOperator, instruction: Op) {
+        match Kind::of(op.out.item().elem) {
+            Kind::Int | Kind::UInt => self.binary(op, |this, ty, lhs, rhs, _| {
+                this.emit(instruction, ty, &[lhs, rhs])
+            }),
+            _ => self.unsupported(format!("{instruction:?} is only supported on integers")),
+        }
+    }
+
+    fn logical(&mut self, op: cube::BinaryOperator, instruction: Op) {
+        let item = Item::vectorized(Elem::Bool, op.out.item().vectorization);
+        let ty = self.item_ty(item);
+        let lhs = self.read_as(op.lhs, item);
+        let rhs = self.read_as(op.rhs, item);
+        let result = self.emit(instruction, ty, &[lhs, rhs]);
+        self.write_from(op.out, result, item);
+    }
+
+    /// Compare two values with the instruction of their kind, being float, signed, unsigned or
+    /// boolean.
+    fn comparison(&mut self, op: cube::BinaryOperator, instructions: [Op; 4]) {
+        let vectorization = op.out.item().vectorization;
+        let item = Item::vectorized(value_item(op.lhs.item()).elem, vectorization);
+        let kind = Kind::of(item.elem);
+        let instruction = match kind {
+            Kind::Float => instructions[0],
+            Kind::Int => instructions[1],
+            Kind::UInt => instructions[2],
+            Kind::Bool => instructions[3],
+        };
+        if instruction == Op::Nop {
+            self.unsupported(format!("Booleans can't be ordered: {op:?}"));
+            return;
+        }
+
+        let out = Item::vectorized(Elem::Bool, vectorization);
+        let ty = self.item_ty(out);
+        let lhs = self.read_as(op.lhs, item);
+        let rhs = self.read_as(op.rhs, item);
+        let result = self.emit(instruction, ty, &[lhs, rhs]);
+        self.write_from(op.out, result, out);
+    }
+
+    fn classify(&mut self, op: cube::UnaryOperator, instruction: Op) {
+        let input_item = value_item(op.input.item());
+        let out = Item::vectorized(Elem::Bool, input_item.vectorization);
+        let ty = self.item_ty(out);
+        let input = self.read(op.input);
+        let result = self.emit(instruction, ty, &[input]);
+        self.write_from(op.out, result, out);
+    }
+
+    /// Raise to a power, keeping the sign of negative numbers raised to odd integers.
+    fn powf(&mut self, ty: Word, lhs: Word, rhs: Word, item: Item) -> Word {
+        let bool_ty = self.item_ty(Item::vectorized(Elem::Bool, item.vectorization));
+        let zero = self.const_item(item, 0.0);
+        let one = self.const_item(item, 1.0);
+        let two = self.const_item(item, 2.0);
+
+        let abs = self.glsl(GLOp::FAbs, ty, &[lhs]);
+        let pow = self.glsl(GLOp::Pow, ty, &[abs, rhs]);
+        let negated = self.emit(Op::FNegate, ty, &[pow]);
+
+        let modulo = self.emit(Op::FRem, ty, &[rhs, two]);
+        let odd = self.emit(Op::FOrdEqual, bool_ty, &[modulo, one]);
+        let negative = self.emit(Op::FOrdLessThan, bool_ty, &[lhs, zero]);
+        // The conjunction is a selection, since naga translates logical operations on boolean
+        // vectors to invalid GLSL.
+        let flip = self.emit(Op::Select, bool_ty, &[odd, negative, odd]);
+
+        self.emit(Op::Select, ty, &[flip, negated, pow])
+    }
+
+    /// An approximation of the error function, with a maximum error of 1.5×10−7.
+    ///
+    /// See <https://en.wikipedia.org/wiki/Error_function#Numerical_approximations>.
+    fn erf(&mut self, ty: Word, x: Word, item: Item) -> Word {
+        let one = self.const_item(item, 1.0);
+        let p = self.const_item(item, 0.3275911);
+        let coefficients = [
+            1.061405429,
+            -1.453152027,
+            1.421413741,
+            -0.284496736,
+            0.254829592,
+        ];
+
+        let abs = self.glsl(GLOp::FAbs, ty, &[x]);
+        let t = self.emit(Op::FMul, ty, &[p, abs]);
+        let t = self.emit(Op::FAdd, ty, &[one, t]);
+        let t = self.emit(Op::FDiv, ty, &[one, t]);
+
+        let mut polynomial = self.const_item(item, coefficients[0]);
+        for coefficient in coefficients.into_iter().skip(1) {
+            let coefficient = self.const_item(item, coefficient);
+            polynomial = self.emit(Op::FMul, ty, &[polynomial, t]);
+            polynomial = self.emit(Op::FAdd, ty, &[polynomial, coefficient]);
+        }
+
+        let square = self.emit(Op::FMul, ty, &[x, x]);
+        let square = self.emit(Op::FNegate, ty, &[square]);
+        let exp = self.glsl(GLOp::Exp, ty, &[square]);
+        let value = self.emit(Op::FMul, ty, &[polynomial, t]);
+        let value = self.emit(Op::FMul, ty, &[value, exp]);
+        let value = self.emit(Op::FSub, ty, &[one, value]);
+
+        // The error function is odd.
+        let sign = self.glsl(GLOp::FSign, ty, &[x]);
+        self.emit(Op::FMul, ty, &[value, sign])
+    }
+
+    fn copy_sign(&mut self, ty: Word, lhs: Word, rhs: Word, item: Item) -> Word {
+        let width = elem_width(item.elem);
+        let bits = Item::vectorized(int_elem(width, false), item.vectorization);
+        let bits_ty = self.item_ty(bits);
+        let sign_mask = self.constant(
+            cube::ConstantScalarValue::UInt(1 << (width - 1), cube::UIntKind::U64),
+            bits.elem,
+        );
+        let sign_mask = self.splat(sign_mask, bits);
+        let magnitude_mask = self.emit(Op::Not, bits_ty, &[sign_mask]);
+
+        let lhs = self.emit(Op::Bitcast, bits_ty, &[lhs]);
+        let rhs = self.emit(Op::Bitcast, bits_ty, &[rhs]);
+        let magnitude = self.emit(Op::BitwiseAnd, bits_ty, &[lhs, magnitude_mask]);
+        let sign = self.emit(Op::BitwiseAnd, bits_ty, &[rhs, sign_mask]);
+        let result = self.emit(Op::BitwiseOr, bits_ty, &[magnitude, sign]);
+
+        self.emit(Op::Bitcast, ty, &[result])
+    }
+
+    /// Count bits on 32 bits integers, since the instructions aren't available on other widths.
+    fn bit_count(&mut self, op: cube::UnaryOperator, count: BitCount) {
+        let input_item = value_item(op.input.item());
+        let width = elem_width(input_item.elem);
+        if width > 32 {
+            self.unsupported(format!("{count:?} isn't supported on {}", input_item.elem));
+            return;
+        }
+
+        let unsigned = Item::vectorized(int_elem(width, false), input_item.vectorization);
+        let wide = Item::vectorized(int_elem(32, false), input_item.vectorization);
+        let ty = self.item_ty(wide);
+
+        // The bits are zero extended, so the upper bits never count.
+        let input = self.read(op.input);
+        let input = self.convert(input, input_item, unsigned);
+        let input = self.convert(input, unsigned, wide);
+
+        let result = match count {
+            BitCount::CountOnes => self.emit(Op::BitCount, ty, &[input]),
+            BitCount::ReverseBits => {
+                let reversed = self.emit(Op::BitReverse, ty, &[input]);
+                let shift = self.const_item(wide, (32 - width) as f64);
+                self.emit(Op::ShiftRightLogical, ty, &[reversed, shift])
+            }
+            BitCount::LeadingZeros => {
+                // The most significant bit of zero is -1, which gives 32 leading zeros.
+                let msb = self.glsl(GLOp::FindUMsb, ty, &[input]);
+                let last = self.const_item(wide, 31.0);
+                let zeros = self.emit(Op::ISub, ty, &[last, msb]);
+                let extension = self.const_item(wide, (32 - width) as f64);
+                self.emit(Op::ISub, ty, &[zeros, extension])
+            }
+            BitCount::TrailingZeros => {
+                // The least significant bit of zero is -1, which is clamped to the width.
+                let lsb = self.glsl(GLOp::FindILsb, ty, &[input]);
+                let width = self.const_item(wide, width as f64);
+                self.glsl(GLOp::UMin, ty, &[lsb, width])
+            }
+        };
+
+        let result = match count {
+            BitCount::ReverseBits => self.convert(result, wide, unsigned),
+            _ => result,
+        };
+        let from = match count {
+            BitCount::ReverseBits => unsigned,
+            _ => wide,
+        };
+        self.write_from(op.out, result, from);
+    }
+
+    fn splat(&mut self, value: Word, item: Item) -> Word {
+        match item.vectorization {
+            1 => value,
+            _ => {
+                let ty = self.item_ty(item);
+                self.emit(
+                    Op::CompositeConstruct,
+                    ty,
+                    &vec![value; item.vectorization as usize],
+                )
+            }
+        }
+    }
+
+    fn index(&mut self, op: cube::BinaryOperator) {
+        let out = op.out.item();
+
+        match self.array(op.lhs) {
+            Some(array) => {
+                let index = self.read_as(op.rhs, uint());
+                let ptr = self.element(array, index);
+
+                if out.elem.is_atomic() {
+                    if let Some(key) = LocalKey::of(op.out) {
+                        self.locals.insert(
+                            key,
+                            Var::Atomic {
+                                ptr,
+                                item: array.item,
+                            },
+                        );
+                    }
+                    return;
+                }
+
+                let ty = self.item_ty(array.stored);
+                let value = self.builder.emit(Op::Load, ty, vec![Operand::Id(ptr)]);
+                self.write_from(op.out, value, array.stored);
+            }
+            None => {
+                let item = value_item(op.lhs.item());
+                let value = self.read(op.lhs);
+                let element = Item::new(item.elem);
+
+                let value = match item.vectorization {
+                    1 => value,
+                    _ => {
+                        let ty = self.item_ty(element);
+                        let index = self.read_as(op.rhs, uint());
+                        self.emit(Op::VectorExtractDynamic, ty, &[value, index])
+                    }
+                };
+                self.write_from(op.out, value, element);
+            }
+        }
+    }
+
+    fn index_assign(&mut self, op: cube::BinaryOperator) {
+        match self.array(op.out) {
+            Some(array) => {
+                let index_item = op.lhs.item();
+                if index_item.vectorization == 1 {
+                    let index = self.read_as(op.lhs, uint());
+                    let value = self.read_as(op.rhs, array.stored);
+                    let ptr = self.element(array, index);
+                    self.store(ptr, value);
+                    return;
+                }
+
+                // Each component of the value is stored at the index of the same component.
+                let uint = uint();
+                let indices = self.read_as(
+                    op.lhs,
+                    Item::vectorized(uint.elem, index_item.vectorization),
+                );
+                let values_item = value_item(op.rhs.item());
+                let values = self.read(op.rhs);
+                let uint_ty = self.item_ty(uint);
+                let element = Item::new(values_item.elem);
+                let element_ty = self.item_ty(element);
+
+                for i in 0..index_item.vectorization as u32 {
+                    let index = self.builder.emit(
+                        Op::CompositeExtract,
+                        uint_ty,
+                        vec![Operand::Id(indices), Operand::Literal(i)],
+                    );
+                    let value = match values_item.vectorization {
+                        1 => values,
+                        _ => self.builder.emit(
+                            Op::CompositeExtract,
+                            element_ty,
+                            vec![Operand::Id(values), Operand::Literal(i)],
+                        ),
+                    };
+                    let value = self.convert(value, element, array.stored);
+                    let ptr = self.element(array, index);
+                    self.store(ptr, value);
+                }
+            }
+            None => {
+                let item = value_item(op.out.item());
+                let element = Item::new(item.elem);
+                let value = self.read_as(op.rhs, element);
+
+                let value = match item.vectorization {
+                    1 => value,
+                    _ => {
+                        let ty = self.item_ty(item);
+                        let vector = self.read(op.out);
+                        let index = self.read_as(op.lhs, uint());
+                        self.emit(Op::VectorInsertDynamic, ty, &[vector, value, index])
+                    }
+                };
+                self.write(op.out, value);
+            }
+        }
+    }
+
+    fn slice(&mut self, op: cube::SliceOperator) {
+        let (array, key) = match (self.array(op.input), LocalKey::of(op.out)) {
+            (Some(array), Some(key)) => (array, key),
+            _ => {
+                self.unsupported(format!("Can't slice {:?}", op.input));
+                return;
+            }
+        };
+
+        let uint = uint();
+        let ty = self.item_ty(uint);
+        let start = self.read_as(op.start, uint);
+        let end = self.read_as(op.end, uint);
+        let length = self.emit(Op::ISub, ty, &[end, start]);
+        let offset = match array.offset {
+            Some(offset) => self.emit(Op::IAdd, ty, &[offset, start]),
+            None => start,
+        };
+
+        self.locals.insert(
+            key,
+            Var::Array(Array {
+                offset: Some(offset),
+                length: Length::Value(length),
+                ..array
+            }),
+        );
+    }
+
+    fn store(&mut self, ptr: Word, value: Word) {
+        self.builder
+            .emit_void(Op::Store, vec![Operand::Id(ptr), Operand::Id(value)]);
+    }
+
+    /// The pointer to an atomic element of an array, with the item of its values.
+    fn atomic_pointer(&mut self, variable: cube::Variable) -> (Word, Item) {
+        match self.local(variable) {
+            Var::Atomic { ptr, item } => (ptr, value_item(item)),
+            _ => {
+                self.unsupported(format!("{variable:?} isn't an atomic"));
+                (self.builder.id(), value_item(variable.item()))
+            }
+        }
+    }
+
+    fn atomic(&mut self, op: cube::BinaryOperator, instruction: impl FnOnce(Kind) -> Op) {
+        let (ptr, item) = self.atomic_pointer(op.lhs);
+        let ty = self.item_ty(item);
+        let value = self.read_as(op.rhs, item);
+        let (scope, semantics) = self.atomic_semantics();
+        let result = self.emit(
+            instruction(Kind::of(item.elem)),
+            ty,
+            &[ptr, scope, semantics, value],
+        );
+        self.write_from(op.out, result, item);
+    }
+
+    /// An atomic operation updating the output in place, discarding its previous value.
+    fn atomic_update(&mut self, op: cube::BinaryOperator, instruction: impl FnOnce(Kind) -> Op) {
+        let (ptr, item) = self.atomic_pointer(op.out);
+        let ty = self.item_ty(item);
+        let value = self.read_as(op.rhs, item);
+        let (scope, semantics) = self.atomic_semantics();
+        self.emit(
+            instruction(Kind::of(item.elem)),
+            ty,
+            &[ptr, scope, semantics, value],
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BitCount {
+    CountOnes,
+    ReverseBits,
+    LeadingZeros,
+    TrailingZeros,
+}
+
+fn atomic_max(kind: Kind) -> Op {
+    match kind {
+        Kind::Int => Op::AtomicSMax,
+        _ => Op::AtomicUMax,
+    }
+}
+
+fn atomic_min(kind: Kind) -> Op {
+    match kind {
+        Kind::Int => Op::AtomicSMin,
+        _ => Op::AtomicUMin,
+    }
+}
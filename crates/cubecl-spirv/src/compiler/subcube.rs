@@ -0,0 +1,223 @@
+use super::base::{uint, value_item, Kind, SpirvCompiler};
+use crate::module::Operand;
+use cubecl_core::ir::{self as cube, Elem, Item};
+use spirv::{Capability, GroupOperation, Op};
+
+impl SpirvCompiler {
+    pub(super) fn compile_subcube(&mut self, subcube: cube::Subcube) {
+        self.builder.capability(Capability::GroupNonUniform);
+        let scope = self.const_u32(spirv::Scope::Subgroup as u32);
+
+        match subcube {
+            cube::Subcube::Elect(op) => {
+                let item = Item::new(Elem::Bool);
+                let ty = self.item_ty(item);
+                let result = self.emit(Op::GroupNonUniformElect, ty, &[scope]);
+                self.write_from(op.out, result, item);
+            }
+            cube::Subcube::All(op) => self.vote(op, Op::GroupNonUniformAll),
+            cube::Subcube::Any(op) => self.vote(op, Op::GroupNonUniformAny),
+            cube::Subcube::Broadcast(op) => {
+                let item = value_item(op.out.item());
+                let ty = self.item_ty(item);
+                let value = self.read_as(op.lhs, item);
+
+                // Broadcasting requires a constant id before SPIR-V 1.5, shuffling works with
+                // any id.
+                let instruction = match op.rhs {
+                    cube::Variable::ConstantScalar(_) => {
+                        self.builder.capability(Capability::GroupNonUniformBallot);
+                        Op::GroupNonUniformBroadcast
+                    }
+                    _ => {
+                        self.builder.capability(Capability::GroupNonUniformShuffle);
+                        Op::GroupNonUniformShuffle
+                    }
+                };
+                let id = self.read_as(op.rhs, uint());
+                let result = self.emit(instruction, ty, &[scope, value, id]);
+                self.write(op.out, result);
+            }
+            cube::Subcube::Shuffle(op) => self.shuffle(
+                op,
+                Op::GroupNonUniformShuffle,
+                Capability::GroupNonUniformShuffle,
+            ),
+            cube::Subcube::ShuffleXor(op) => self.shuffle(
+                op,
+                Op::GroupNonUniformShuffleXor,
+                Capability::GroupNonUniformShuffle,
+            ),
+            cube::Subcube::ShuffleUp(op) => self.shuffle(
+                op,
+                Op::GroupNonUniformShuffleUp,
+                Capability::GroupNonUniformShuffleRelative,
+            ),
+            cube::Subcube::ShuffleDown(op) => self.shuffle(
+                op,
+                Op::GroupNonUniformShuffleDown,
+                Capability::GroupNonUniformShuffleRelative,
+            ),
+            cube::Subcube::Ballot(op) => {
+                self.builder.capability(Capability::GroupNonUniformBallot);
+                let item = Item::vectorized(uint().elem, 4);
+                let ty = self.item_ty(item);
+                let input = self.read_as(op.input, Item::new(Elem::Bool));
+                let result = self.emit(Op::GroupNonUniformBallot, ty, &[scope, input]);
+                self.write_from(op.out, result, item);
+            }
+            cube::Subcube::Sum(op) => self.reduce(
+                op,
+                GroupOperation::Reduce,
+                [Op::GroupNonUniformFAdd, Op::GroupNonUniformIAdd, Op::Nop],
+            ),
+            cube::Subcube::Prod(op) => self.reduce(
+                op,
+                GroupOperation::Reduce,
+                [Op::GroupNonUniformFMul, Op::GroupNonUniformIMul, Op::Nop],
+            ),
+            cube::Subcube::InclusiveSum(op) => self.reduce(
+                op,
+                GroupOperation::InclusiveScan,
+                [Op::GroupNonUniformFAdd, Op::GroupNonUniformIAdd, Op::Nop],
+            ),
+            cube::Subcube::ExclusiveSum(op) => self.reduce(
+                op,
+                GroupOperation::ExclusiveScan,
+                [Op::GroupNonUniformFAdd, Op::GroupNonUniformIAdd, Op::Nop],
+            ),
+            cube::Subcube::InclusiveProd(op) => self.reduce(
+                op,
+                GroupOperation::InclusiveScan,
+                [Op::GroupNonUniformFMul, Op::GroupNonUniformIMul, Op::Nop],
+            ),
+            cube::Subcube::ExclusiveProd(op) => self.reduce(
+                op,
+                GroupOperation::ExclusiveScan,
+                [Op::GroupNonUniformFMul, Op::GroupNonUniformIMul, Op::Nop],
+            ),
+            cube::Subcube::And(op) => self.reduce(
+                op,
+                GroupOperation::Reduce,
+                [
+                    Op::Nop,
+                    Op::GroupNonUniformBitwiseAnd,
+                    Op::GroupNonUniformLogicalAnd,
+                ],
+            ),
+            cube::Subcube::Or(op) => self.reduce(
+                op,
+                GroupOperation::Reduce,
+                [
+                    Op::Nop,
+                    Op::GroupNonUniformBitwiseOr,
+                    Op::GroupNonUniformLogicalOr,
+                ],
+            ),
+            cube::Subcube::Xor(op) => self.reduce(
+                op,
+                GroupOperation::Reduce,
+                [
+                    Op::Nop,
+                    Op::GroupNonUniformBitwiseXor,
+                    Op::GroupNonUniformLogicalXor,
+                ],
+            ),
+            cube::Subcube::Min(op) => {
+                let instruction = match Kind::of(op.out.item().elem) {
+                    Kind::Float => Op::GroupNonUniformFMin,
+                    Kind::Int => Op::GroupNonUniformSMin,
+                    _ => Op::GroupNonUniformUMin,
+                };
+                self.reduce(
+                    op,
+                    GroupOperation::Reduce,
+                    [instruction, instruction, Op::Nop],
+                )
+            }
+            cube::Subcube::Max(op) => {
+                let instruction = match Kind::of(op.out.item().elem) {
+                    Kind::Float => Op::GroupNonUniformFMax,
+                    Kind::Int => Op::GroupNonUniformSMax,
+                    _ => Op::GroupNonUniformUMax,
+                };
+                self.reduce(
+                    op,
+                    GroupOperation::Reduce,
+                    [instruction, instruction, Op::Nop],
+                )
+            }
+        }
+    }
+
+    fn vote(&mut self, op: cube::UnaryOperator, instruction: Op) {
+        self.builder.capability(Capability::GroupNonUniformVote);
+        let scope = self.const_u32(spirv::Scope::Subgroup as u32);
+        let item = Item::new(Elem::Bool);
+        let ty = self.item_ty(item);
+        let input = self.read_as(op.input, item);
+        let result = self.emit(instruction, ty, &[scope, input]);
+        self.write_from(op.out, result, item);
+    }
+
+    /// Read the value of another unit, the id being relative to the current unit for the relative
+    /// shuffles.
+    fn shuffle(&mut self, op: cube::BinaryOperator, instruction: Op, capability: Capability) {
+        self.builder.capability(capability);
+        let scope = self.const_u32(spirv::Scope::Subgroup as u32);
+        let item = value_item(op.out.item());
+        let ty = self.item_ty(item);
+        let value = self.read_as(op.lhs, item);
+        let id = self.read_as(op.rhs, uint());
+        let result = self.emit(instruction, ty, &[scope, value, id]);
+        self.write(op.out, result);
+    }
+
+    /// Reduce or scan a value over the subgroup with the instruction of its kind, being float,
+    /// integer or boolean.
+    fn reduce(
+        &mut self,
+        op: cube::UnaryOperator,
+        operation: GroupOperation,
+        instructions: [Op; 3],
+    ) {
+        let item = value_item(op.out.item());
+        let instruction = match Kind::of(item.elem) {
+            Kind::Float => instructions[0],
+            Kind::Int | Kind::UInt => instructions[1],
+            Kind::Bool => instructions[2],
+        };
+        if instruction == Op::Nop {
+            self.unsupported(format!(
+                "Subcube operation isn't supported on {}",
+                item.elem
+            ));
+            return;
+        }
+
+        self.builder
+            .capability(Capability::GroupNonUniformArithmetic);
+        let scope = self.const_u32(spirv::Scope::Subgroup as u32);
+        let ty = self.item_ty(item);
+        let input = self.read_as(op.input, item);
+        let result = self.builder.emit(
+            instruction,
+            ty,
+            vec![
+                Operand::Id(scope),
+                Operand::Enumerant(group_operation_name(operation), operation as u32),
+                Operand::Id(input),
+            ],
+        );
+        self.write(op.out, result);
+    }
+}
+
+fn group_operation_name(operation: GroupOperation) -> &'static str {
+    match operation {
+        GroupOperation::Reduce => "Reduce",
+        GroupOperation::InclusiveScan => "InclusiveScan",
+        GroupOperation::ExclusiveScan => "ExclusiveScan",
+        _ => unreachable!("Only reductions and scans are compiled"),
+    }
+}
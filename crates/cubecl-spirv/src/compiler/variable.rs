@@ -0,0 +1,64 @@
+use crate::module::Word;
+use cubecl_core::ir::{self as cube, Item, Matrix};
+use spirv::StorageClass;
+
+/// How a variable of the kernel is held in the module.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Var {
+    /// A function variable, loaded and stored at each use.
+    Local { ptr: Word, item: Item },
+    /// An array, only accessed through its elements.
+    Array(Array),
+    /// A pointer to an atomic element of an array, created when indexing it.
+    Atomic { ptr: Word, item: Item },
+    /// A function variable holding a cooperative matrix.
+    Matrix { ptr: Word, ty: Word, mat: Matrix },
+}
+
+/// An array of any storage class, or a slice of one.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Array {
+    pub ptr: Word,
+    pub class: StorageClass,
+    /// The item of the elements as seen by the kernel.
+    pub item: Item,
+    /// The item of the elements as stored in memory.
+    pub stored: Item,
+    /// Whether the array is the member of a block, as the arrays of storage buffers are.
+    pub block: bool,
+    /// The index of the first element of a slice.
+    pub offset: Option<Word>,
+    pub length: Length,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Length {
+    /// The length of a storage buffer, only known when the kernel is launched.
+    Runtime,
+    Constant(u32),
+    /// The length of a slice, computed when the slice is created.
+    Value(Word),
+}
+
+/// Identifies a variable of the function being compiled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum LocalKey {
+    Local(u16, u8),
+    Scalar(u16, u8),
+    Array(u16, u8),
+    Matrix(u16, u8),
+    Slice(u16, u8),
+}
+
+impl LocalKey {
+    pub fn of(variable: cube::Variable) -> Option<Self> {
+        match variable {
+            cube::Variable::Local { id, depth, .. } => Some(Self::Local(id, depth)),
+            cube::Variable::LocalScalar { id, depth, .. } => Some(Self::Scalar(id, depth)),
+            cube::Variable::LocalArray { id, depth, .. } => Some(Self::Array(id, depth)),
+            cube::Variable::Matrix { id, depth, .. } => Some(Self::Matrix(id, depth)),
+            cube::Variable::Slice { id, depth, .. } => Some(Self::Slice(id, depth)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,6 @@
+mod builder;
+mod compiler;
+mod module;
+
+pub use compiler::*;
+pub use module::*;
@@ -0,0 +1,166 @@
+use cubecl_core::{ir::CubeDim, CompilerRepresentation};
+use std::fmt::Display;
+
+/// The id of a SPIR-V result, type or label.
+pub type Word = u32;
+
+/// An operand of a SPIR-V [instruction](Instruction).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Operand {
+    /// A reference to the result of another instruction.
+    Id(Word),
+    /// A literal word, such as an integer or the bits of a float.
+    Literal(u32),
+    /// A literal string, encoded as null terminated UTF-8 padded to a word.
+    String(String),
+    /// A value of a SPIR-V enumeration, with its name for the disassembly.
+    Enumerant(&'static str, u32),
+}
+
+/// A single SPIR-V instruction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Instruction {
+    pub op: spirv::Op,
+    pub result_type: Option<Word>,
+    pub result_id: Option<Word>,
+    pub operands: Vec<Operand>,
+}
+
+impl Instruction {
+    pub fn new(op: spirv::Op, operands: Vec<Operand>) -> Self {
+        Self {
+            op,
+            result_type: None,
+            result_id: None,
+            operands,
+        }
+    }
+
+    /// Append the binary encoding of the instruction.
+    pub fn encode(&self, words: &mut Vec<u32>) {
+        let start = words.len();
+        words.push(0);
+        words.extend(self.result_type);
+        words.extend(self.result_id);
+
+        for operand in self.operands.iter() {
+            match operand {
+                Operand::Id(id) => words.push(*id),
+                Operand::Literal(value) => words.push(*value),
+                Operand::Enumerant(_, value) => words.push(*value),
+                Operand::String(value) => {
+                    let mut bytes = value.as_bytes().to_vec();
+                    // The string is always terminated by at least one null byte.
+                    bytes.resize((bytes.len() / 4 + 1) * 4, 0);
+                    words.extend(
+                        bytes.chunks(4).map(|chunk| {
+                            u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])
+                        }),
+                    );
+                }
+            }
+        }
+
+        let count = (words.len() - start) as u32;
+        words[start] = (count << 16) | self.op as u32;
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(id) = self.result_id {
+            f.write_fmt(format_args!("{:>8} = ", format!("%{id}")))?;
+        } else {
+            f.write_str("           ")?;
+        }
+
+        f.write_fmt(format_args!("Op{:?}", self.op))?;
+
+        if let Some(ty) = self.result_type {
+            f.write_fmt(format_args!(" %{ty}"))?;
+        }
+
+        for operand in self.operands.iter() {
+            match operand {
+                Operand::Id(id) => f.write_fmt(format_args!(" %{id}"))?,
+                Operand::Literal(value) => f.write_fmt(format_args!(" {value}"))?,
+                Operand::String(value) => f.write_fmt(format_args!(" {value:?}"))?,
+                Operand::Enumerant(name, _) => f.write_fmt(format_args!(" {name}"))?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A compiled SPIR-V module.
+///
+/// The [words](SpirvModule::words) can be given to Vulkan as is, or to wgpu with
+/// `wgpu::ShaderSource::SpirV` when the `SPIRV_SHADER_PASSTHROUGH` feature is enabled. The module
+/// is displayed as a disassembly, which is what gets logged.
+#[derive(Debug, Clone)]
+pub struct SpirvModule {
+    /// The instructions of the module, in their logical layout order.
+    pub instructions: Vec<Instruction>,
+    /// The upper bound of all ids used in the module.
+    pub bound: Word,
+    /// The size of a cube for the compiled kernel.
+    pub cube_dim: CubeDim,
+    /// The number of bytes used by the shared memories.
+    pub shared_memory_size: usize,
+}
+
+/// The version of SPIR-V targeted by the compiler, which is the one of Vulkan 1.1.
+pub const SPIRV_VERSION: (u8, u8) = (1, 3);
+
+impl SpirvModule {
+    /// The binary encoding of the module, including its header.
+    pub fn words(&self) -> Vec<u32> {
+        let (major, minor) = SPIRV_VERSION;
+        let mut words = vec![
+            spirv::MAGIC_NUMBER,
+            ((major as u32) << 16) | ((minor as u32) << 8),
+            // No registered generator.
+            0,
+            self.bound,
+            // Reserved schema.
+            0,
+        ];
+
+        for instruction in self.instructions.iter() {
+            instruction.encode(&mut words);
+        }
+
+        words
+    }
+
+    /// The binary encoding of the module as little endian bytes.
+    pub fn bytes(&self) -> Vec<u8> {
+        self.words()
+            .into_iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect()
+    }
+}
+
+impl Display for SpirvModule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (major, minor) = SPIRV_VERSION;
+        f.write_fmt(format_args!(
+            "; SPIR-V\n; Version: {major}.{minor}\n; Bound: {}\n",
+            self.bound
+        ))?;
+
+        for instruction in self.instructions.iter() {
+            f.write_fmt(format_args!("{instruction}\n"))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl CompilerRepresentation for SpirvModule {
+    fn shared_memory_size(&self) -> usize {
+        self.shared_memory_size
+    }
+}
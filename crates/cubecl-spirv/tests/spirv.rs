@@ -0,0 +1,375 @@
+use cubecl_core as cubecl;
+use cubecl_core::{
+    compiler_tests::{self, float, golden_scale},
+    ir::{FloatKind, KernelDefinition},
+    prelude::*,
+    Compiler,
+};
+use cubecl_runtime::ExecutionMode;
+use cubecl_spirv::{SpirvCompiler, SpirvModule};
+use spirv::Op;
+use std::collections::HashSet;
+
+#[cube]
+pub fn spirv_kernel(input: &Array<F32>, output: &mut Array<F32>) {
+    let mut sum = F32::new(0.0);
+
+    for i in range(0u32, input.len(), Comptime::new(false)) {
+        if input[i] > F32::new(1.0) {
+            sum += golden_scale(input[i]);
+        } else {
+            sum -= F32::new(1e-7);
+        }
+    }
+
+    loop {
+        if sum > F32::new(100.0) {
+            break;
+        }
+        sum *= F32::new(-0.5);
+    }
+
+    output[UNIT_POS] = sum;
+}
+
+#[cube]
+pub fn spirv_cmma_kernel(lhs: &Array<F16>, rhs: &Array<F16>, out: &mut Array<F32>) {
+    let a = cmma::Matrix::<F16>::new(
+        cmma::MatrixIdent::A,
+        16,
+        16,
+        16,
+        cmma::MatrixLayout::RowMajor,
+    );
+    let b = cmma::Matrix::<F16>::new(
+        cmma::MatrixIdent::B,
+        16,
+        16,
+        16,
+        cmma::MatrixLayout::ColMajor,
+    );
+    let c = cmma::Matrix::<F32>::new(
+        cmma::MatrixIdent::Accumulator,
+        16,
+        16,
+        16,
+        cmma::MatrixLayout::Undefined,
+    );
+    cmma::fill::<F32>(&c, F32::new(0.0));
+    cmma::load::<F16>(&a, lhs.as_slice(), UInt::new(16));
+    cmma::load::<F16>(&b, rhs.as_slice(), UInt::new(16));
+
+    cmma::execute::<F16, F16, F32, F32>(&a, &b, &c, &c);
+
+    cmma::store::<F32>(
+        out.as_slice_mut(),
+        &c,
+        UInt::new(16),
+        cmma::MatrixLayout::RowMajor,
+    );
+}
+
+fn kernel() -> KernelDefinition {
+    let mut builder = KernelBuilder::default();
+    let input = builder.input_array(float(FloatKind::F32));
+    let output = builder.output_array(float(FloatKind::F32));
+
+    spirv_kernel::__expand(&mut builder.context, input.into(), output.into());
+
+    builder.build(KernelSettings::default())
+}
+
+fn cmma_kernel() -> KernelDefinition {
+    let mut builder = KernelBuilder::default();
+    let lhs = builder.input_array(float(FloatKind::F16));
+    let rhs = builder.input_array(float(FloatKind::F16));
+    let out = builder.output_array(float(FloatKind::F32));
+
+    spirv_cmma_kernel::__expand(&mut builder.context, lhs.into(), rhs.into(), out.into());
+
+    builder.build(KernelSettings::default())
+}
+
+fn compile(kernel: KernelDefinition) -> SpirvModule {
+    let module = SpirvCompiler::compile(kernel, ExecutionMode::Checked);
+    validate(&module);
+    validate_naga(&module);
+    module
+}
+
+/// Parse the module with the SPIR-V frontend of naga and run its validator, which checks the
+/// types, the control flow and the uses of every instruction.
+fn validate_naga(module: &SpirvModule) {
+    let parsed = naga::front::spv::parse_u8_slice(&module.bytes(), &Default::default())
+        .unwrap_or_else(|err| panic!("The module can't be parsed: {err:?}\n{module}"));
+
+    naga::valid::Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::all(),
+    )
+    .validate(&parsed)
+    .unwrap_or_else(|err| panic!("The module is invalid: {err:?}\n{module}"));
+}
+
+/// Parse the binary module and check the structural rules a SPIR-V validator enforces on the
+/// layout of a module: the header, the encoding of each instruction, unique result ids within the
+/// bound, types declared before their use and terminated blocks branching to existing labels.
+fn validate(module: &SpirvModule) {
+    let words = module.words();
+    assert_eq!(words[0], spirv::MAGIC_NUMBER, "Invalid magic number");
+    assert_eq!(words[1], 0x0001_0300, "Invalid version");
+    assert_eq!(words[3], module.bound, "Invalid bound");
+    assert_eq!(words[4], 0, "Invalid schema");
+
+    let mut ops = Vec::new();
+    let mut position = 5;
+    while position < words.len() {
+        let count = (words[position] >> 16) as usize;
+        let opcode = words[position] & 0xffff;
+        assert!(
+            count > 0,
+            "Instruction at word {position} has a zero word count"
+        );
+        assert!(
+            position + count <= words.len(),
+            "Instruction at word {position} overflows the module"
+        );
+        let op = Op::from_u32(opcode)
+            .unwrap_or_else(|| panic!("Unknown opcode {opcode} at word {position}"));
+        ops.push(op);
+        position += count;
+    }
+    let expected = module
+        .instructions
+        .iter()
+        .map(|inst| inst.op)
+        .collect::<Vec<_>>();
+    assert_eq!(ops, expected, "The binary doesn't match the instructions");
+
+    let mut defined = HashSet::new();
+    let mut labels = HashSet::new();
+    for instruction in module.instructions.iter() {
+        if let Some(id) = instruction.result_id {
+            assert!(id > 0 && id < module.bound, "Id %{id} is out of bound");
+            assert!(defined.insert(id), "Id %{id} is defined twice");
+        }
+        if instruction.op == Op::Label {
+            labels.insert(instruction.result_id.unwrap());
+        }
+    }
+
+    let mut declared = HashSet::new();
+    let mut in_function = false;
+    let mut in_block = false;
+    for instruction in module.instructions.iter() {
+        if let Some(ty) = instruction.result_type {
+            assert!(
+                declared.contains(&ty),
+                "{:?} uses type %{ty} before its declaration",
+                instruction.op
+            );
+        }
+        if let Some(id) = instruction.result_id {
+            declared.insert(id);
+        }
+
+        match instruction.op {
+            Op::Function => {
+                assert!(!in_function, "Nested function");
+                in_function = true;
+            }
+            Op::FunctionParameter => assert!(in_function && !in_block),
+            Op::FunctionEnd => {
+                assert!(in_function && !in_block, "Function ends in an open block");
+                in_function = false;
+            }
+            Op::Label => {
+                assert!(in_function && !in_block, "Block isn't terminated");
+                in_block = true;
+            }
+            _ if in_function => {
+                assert!(in_block, "{:?} is outside of a block", instruction.op);
+                if is_terminator(instruction.op) {
+                    for target in branch_targets(instruction) {
+                        assert!(
+                            labels.contains(&target),
+                            "Branch to unknown label %{target}"
+                        );
+                    }
+                    in_block = false;
+                }
+            }
+            _ => {}
+        }
+    }
+    assert!(!in_function, "Function isn't closed");
+    assert!(
+        module
+            .instructions
+            .iter()
+            .any(|inst| inst.op == Op::EntryPoint),
+        "Missing entry point"
+    );
+}
+
+fn is_terminator(op: Op) -> bool {
+    matches!(
+        op,
+        Op::Branch
+            | Op::BranchConditional
+            | Op::Switch
+            | Op::Return
+            | Op::ReturnValue
+            | Op::Kill
+            | Op::Unreachable
+    )
+}
+
+fn branch_targets(instruction: &cubecl_spirv::Instruction) -> Vec<u32> {
+    let ids = instruction
+        .operands
+        .iter()
+        .filter_map(|operand| match operand {
+            cubecl_spirv::Operand::Id(id) => Some(*id),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    match instruction.op {
+        Op::Branch => ids,
+        // The first id is the condition or the selector.
+        Op::BranchConditional | Op::Switch => ids[1..].to_vec(),
+        _ => Vec::new(),
+    }
+}
+
+fn contains(module: &SpirvModule, op: Op) -> bool {
+    module.instructions.iter().any(|inst| inst.op == op)
+}
+
+#[test]
+fn spirv_kernel_test() {
+    let module = compile(kernel());
+
+    assert!(contains(&module, Op::LoopMerge));
+    assert!(contains(&module, Op::SelectionMerge));
+    assert!(contains(&module, Op::FunctionCall));
+    assert!(contains(&module, Op::ArrayLength));
+}
+
+#[test]
+fn spirv_kernel_disassembly_test() {
+    let module = compile(kernel());
+    let text = module.to_string();
+
+    assert!(text.starts_with("; SPIR-V"));
+    assert!(text.contains("OpEntryPoint GLCompute"));
+    assert!(text.contains("OpExecutionMode"));
+}
+
+#[test]
+fn spirv_subcube_test() {
+    let module = compile(compiler_tests::subcube());
+
+    assert!(contains(&module, Op::GroupNonUniformFAdd));
+    assert!(module
+        .to_string()
+        .contains("OpCapability GroupNonUniformArithmetic"));
+}
+
+#[test]
+fn spirv_subcube_shuffle_test() {
+    let module = compile(compiler_tests::subcube_shuffle());
+    let source = module.to_string();
+
+    assert!(contains(&module, Op::GroupNonUniformShuffleXor));
+    assert!(contains(&module, Op::GroupNonUniformShuffleDown));
+    assert!(contains(&module, Op::GroupNonUniformBallot));
+    assert!(source.contains("ExclusiveScan"));
+    assert!(source.contains("OpCapability GroupNonUniformShuffle"));
+    assert!(source.contains("OpCapability GroupNonUniformShuffleRelative"));
+    assert!(source.contains("OpCapability GroupNonUniformBallot"));
+}
+
+#[test]
+fn spirv_cmma_test() {
+    // naga supports neither cooperative matrices nor 16 bits storage, so only the layout of the
+    // module is validated.
+    let module = SpirvCompiler::compile(cmma_kernel(), ExecutionMode::Checked);
+    validate(&module);
+
+    assert!(contains(&module, Op::TypeCooperativeMatrixKHR));
+    assert!(contains(&module, Op::CooperativeMatrixLoadKHR));
+    assert!(contains(&module, Op::CooperativeMatrixMulAddKHR));
+    assert!(contains(&module, Op::CooperativeMatrixStoreKHR));
+    assert!(module
+        .to_string()
+        .contains("OpExtension \"SPV_KHR_cooperative_matrix\""));
+}
+
+#[test]
+fn spirv_unsupported_elem_test() {
+    let mut builder = KernelBuilder::default();
+    let input = builder.input_array(float(FloatKind::BF16));
+    let output = builder.output_array(float(FloatKind::BF16));
+    builder.context.register(cubecl_core::ir::Operator::Assign(
+        cubecl_core::ir::UnaryOperator {
+            input: *input,
+            out: *output,
+        },
+    ));
+    let kernel = builder.build(KernelSettings::default());
+
+    assert!(SpirvCompiler::try_compile(kernel, ExecutionMode::Checked).is_err());
+}
+
+/// Create compute pipelines from the modules with the SPIR-V shader source of wgpu, which checks
+/// that they are accepted by the device.
+#[test]
+fn spirv_wgpu_pipeline_test() {
+    let instance = wgpu::Instance::default();
+    let adapter = pollster::block_on(instance.request_adapter(&Default::default()))
+        .expect("No wgpu adapter is available");
+    let (device, _queue) = pollster::block_on(adapter.request_device(
+        &wgpu::DeviceDescriptor {
+            label: None,
+            required_features: adapter.features(),
+            required_limits: adapter.limits(),
+            memory_hints: wgpu::MemoryHints::MemoryUsage,
+        },
+        None,
+    ))
+    .expect("Unable to request the device");
+
+    let mut kernels = vec![
+        kernel(),
+        compiler_tests::kernel(),
+        compiler_tests::vectorized(),
+    ];
+    if adapter.features().contains(wgpu::Features::SUBGROUP) {
+        kernels.push(compiler_tests::subcube());
+        kernels.push(compiler_tests::subcube_shuffle());
+    }
+
+    for kernel in kernels {
+        let module = compile(kernel);
+
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::SpirV(module.words().into()),
+        });
+        device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: None,
+            layout: None,
+            module: &shader,
+            entry_point: "main",
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        if let Some(err) = pollster::block_on(device.pop_error_scope()) {
+            panic!("The module is rejected by wgpu: {err:?}\n{module}");
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use cubecl_core::ir::{self as cube, ConstantScalarValue, FloatKind, IntKind};
+use cubecl_core::ir::{self as cube, ConstantScalarValue, FloatKind, IntKind, UIntKind};
 use std::fmt::Display;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -60,7 +60,16 @@ pub enum Elem {
     AtomicI32,
     U32,
     AtomicU32,
+    /// WGSL has no float atomics, so they are stored as `atomic<u32>` and converted with bitcasts.
+    AtomicF32,
     Bool,
+    I8,
+    I16,
+    U8,
+    U16,
+    /// WGSL has no 64 bits integers, so they are held in a `vec2<u32>` with the low word first.
+    I64,
+    U64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
@@ -139,6 +148,23 @@ impl Variable {
         }
     }
 
+    /// The number of bits of the elements when they are packed in 32 bits words, which is the case
+    /// for narrow integers stored in global memory.
+    pub fn packed_bits(&self) -> Option<u32> {
+        match self {
+            Variable::GlobalInputArray(_, item) => item.elem().packed_bits(),
+            Variable::GlobalOutputArray(_, item) => item.elem().packed_bits(),
+            Variable::GlobalScalar(_, elem, _) => elem.packed_bits(),
+            Variable::Slice { item, .. } => item.elem().packed_bits(),
+            Variable::Named {
+                item,
+                is_array: true,
+                ..
+            } => item.elem().packed_bits(),
+            _ => None,
+        }
+    }
+
     pub fn item(&self) -> Item {
         match self {
             Self::GlobalInputArray(_, e) => *e,
@@ -198,6 +224,25 @@ impl Item {
             Item::Scalar(_) => 1,
         }
     }
+
+    /// The same vectorization with a different element.
+    pub fn with_elem(&self, elem: Elem) -> Self {
+        match self {
+            Item::Vec4(_) => Item::Vec4(elem),
+            Item::Vec3(_) => Item::Vec3(elem),
+            Item::Vec2(_) => Item::Vec2(elem),
+            Item::Scalar(_) => Item::Scalar(elem),
+        }
+    }
+
+    pub fn unpacked(&self) -> Self {
+        match self {
+            Item::Vec4(e) => Item::Vec4(e.unpacked()),
+            Item::Vec3(e) => Item::Vec3(e.unpacked()),
+            Item::Vec2(e) => Item::Vec2(e.unpacked()),
+            Item::Scalar(e) => Item::Scalar(e.unpacked()),
+        }
+    }
 }
 
 impl Elem {
@@ -208,12 +253,43 @@ impl Elem {
             Self::AtomicI32 => core::mem::size_of::<i32>(),
             Self::U32 => core::mem::size_of::<u32>(),
             Self::AtomicU32 => core::mem::size_of::<u32>(),
+            Self::AtomicF32 => core::mem::size_of::<f32>(),
             Self::Bool => core::mem::size_of::<bool>(),
+            Self::I8 => core::mem::size_of::<i8>(),
+            Self::I16 => core::mem::size_of::<i16>(),
+            Self::U8 => core::mem::size_of::<u8>(),
+            Self::U16 => core::mem::size_of::<u16>(),
+            Self::I64 => core::mem::size_of::<i64>(),
+            Self::U64 => core::mem::size_of::<u64>(),
         }
     }
 
     pub fn is_atomic(&self) -> bool {
-        matches!(self, Self::AtomicI32 | Self::AtomicU32)
+        matches!(self, Self::AtomicI32 | Self::AtomicU32 | Self::AtomicF32)
+    }
+
+    /// WGSL doesn't have integers narrower than 32 bits, so they are widened in registers and
+    /// workgroup memory, and packed in 32 bits words in global memory.
+    pub fn packed_bits(&self) -> Option<u32> {
+        match self {
+            Self::I8 | Self::U8 => Some(8),
+            Self::I16 | Self::U16 => Some(16),
+            _ => None,
+        }
+    }
+
+    /// Whether the element is a 64 bits integer, emulated with two 32 bits words.
+    pub fn is_wide(&self) -> bool {
+        matches!(self, Self::I64 | Self::U64)
+    }
+
+    /// The 32 bits element used to hold the element when it isn't packed.
+    pub fn unpacked(&self) -> Self {
+        match self {
+            Self::I8 | Self::I16 => Self::I32,
+            Self::U8 | Self::U16 => Self::U32,
+            _ => *self,
+        }
     }
 }
 
@@ -224,8 +300,11 @@ impl Display for Elem {
             Self::I32 => f.write_str("i32"),
             Self::AtomicI32 => f.write_str("atomic<i32>"),
             Self::U32 => f.write_str("u32"),
-            Self::AtomicU32 => f.write_str("atomic<u32>"),
+            Self::AtomicU32 | Self::AtomicF32 => f.write_str("atomic<u32>"),
             Self::Bool => f.write_str("bool"),
+            Self::I8 | Self::I16 => f.write_str("i32"),
+            Self::U8 | Self::U16 => f.write_str("u32"),
+            Self::I64 | Self::U64 => f.write_str("vec2<u32>"),
         }
     }
 }
@@ -266,15 +345,25 @@ impl Display for Variable {
             Variable::GlobalOutputArray(number, _) => {
                 f.write_fmt(format_args!("output_{number}_global"))
             }
-            Variable::GlobalScalar(number, _, elem) => {
-                f.write_fmt(format_args!("scalars_{elem}[{number}]"))
-            }
+            Variable::GlobalScalar(number, elem, elem_cube) => match elem.packed_bits() {
+                Some(bits) => {
+                    let per_word = 32 / bits as u16;
+                    f.write_fmt(format_args!(
+                        "extractBits(bitcast<{elem}>(atomicLoad(&scalars_{elem_cube}[{}])), {}u, {bits}u)",
+                        number / per_word,
+                        (number % per_word) as u32 * bits,
+                    ))
+                }
+                None => f.write_fmt(format_args!("scalars_{elem_cube}[{number}]")),
+            },
             // We do the conversion in Rust and then render the number to avoid overflow or other
             // precision related problems.
             Variable::ConstantScalar(number, _elem) => match number {
                 ConstantScalarValue::Int(val, kind) => match kind {
-                    IntKind::I32 => f.write_fmt(format_args!("{}i", *val as i32)),
-                    IntKind::I64 => f.write_fmt(format_args!("{}i", { *val })),
+                    IntKind::I8 | IntKind::I16 | IntKind::I32 => {
+                        f.write_fmt(format_args!("{}i", *val as i32))
+                    }
+                    IntKind::I64 => format_wide(f, *val as u64),
                 },
                 ConstantScalarValue::Float(val, kind) => match kind {
                     FloatKind::F16 => {
@@ -286,7 +375,8 @@ impl Display for Variable {
                     FloatKind::F32 => f.write_fmt(format_args!("{}f", *val as f32)),
                     FloatKind::F64 => f.write_fmt(format_args!("{}f", { *val })),
                 },
-                ConstantScalarValue::UInt(val) => f.write_fmt(format_args!("{}u", *val as u32)),
+                ConstantScalarValue::UInt(val, UIntKind::U64) => format_wide(f, *val),
+                ConstantScalarValue::UInt(val, _) => f.write_fmt(format_args!("{}u", *val as u32)),
                 ConstantScalarValue::Bool(val) => f.write_fmt(format_args!("{}", val)),
             },
             Variable::SharedMemory(number, _, _) => {
@@ -321,6 +411,14 @@ impl Display for Variable {
     }
 }
 
+fn format_wide(f: &mut std::fmt::Formatter<'_>, val: u64) -> std::fmt::Result {
+    f.write_fmt(format_args!(
+        "vec2<u32>({}u, {}u)",
+        val as u32,
+        (val >> 32) as u32
+    ))
+}
+
 impl Display for IndexedVariable {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let should_index = |item: &Item| match item {
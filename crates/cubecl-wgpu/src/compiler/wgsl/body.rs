@@ -1,4 +1,4 @@
-use super::Instruction;
+use super::{Instruction, LocalArray};
 use std::fmt::Display;
 
 /// A body is composed of a list of [instructions](Instruction).
@@ -8,14 +8,37 @@ use std::fmt::Display;
 #[derive(Debug, Clone)]
 pub struct Body {
     pub instructions: Vec<Instruction>,
+    pub local_arrays: Vec<LocalArray>,
     pub rank: bool,
     pub id: bool,
     pub stride: bool,
     pub shape: bool,
+    pub workgroup_id_no_axis: bool,
+    pub workgroup_size_no_axis: bool,
+    pub num_workgroups_no_axis: bool,
 }
 
 impl Display for Body {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for array in self.local_arrays.iter() {
+            f.write_fmt(format_args!(
+                "var a_{}_{}: array<{}, {}>;\n\n",
+                array.name, array.index, array.item, array.size
+            ))?;
+        }
+
+        if self.workgroup_id_no_axis {
+            f.write_str("let workgroup_id_no_axis = (num_workgroups.y * num_workgroups.x * workgroup_id.z) + (num_workgroups.x * workgroup_id.y) + workgroup_id.x;\n")?;
+        }
+
+        if self.workgroup_size_no_axis {
+            f.write_str("let workgroup_size_no_axis = WORKGROUP_SIZE_X * WORKGROUP_SIZE_Y * WORKGROUP_SIZE_Z;\n")?;
+        }
+
+        if self.num_workgroups_no_axis {
+            f.write_str("let num_workgroups_no_axis = num_workgroups.x * num_workgroups.y * num_workgroups.z;\n")?;
+        }
+
         if self.id {
             f.write_str(
                 "let id = (global_id.z * num_workgroups.x * WORKGROUP_SIZE_X * num_workgroups.y * WORKGROUP_SIZE_Y) + (global_id.y * num_workgroups.x * WORKGROUP_SIZE_X) + global_id.x;\n",
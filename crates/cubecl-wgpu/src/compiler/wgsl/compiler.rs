@@ -1,8 +1,8 @@
-use super::{shader::ComputeShader, Item, SharedMemory};
+use super::{shader::ComputeShader, Builtins, Item, SharedMemory};
 use super::{LocalArray, Subgroup};
 use crate::compiler::wgsl;
 use cubecl_core::ir as cube;
-use cubecl_runtime::ExecutionMode;
+use cubecl_runtime::{server::ComputeError, ExecutionMode};
 
 /// Wgsl Compiler.
 #[derive(Clone, Default)]
@@ -23,6 +23,17 @@ pub struct WgslCompiler {
     num_workgroup_no_axis: bool,
     shared_memories: Vec<SharedMemory>,
     local_arrays: Vec<LocalArray>,
+    /// The slices of global arrays, which keep their elements packed.
+    packed_slices: Vec<(u16, u8)>,
+    /// The functions compiled so far, indexed by their id.
+    functions: Vec<wgsl::Function>,
+    /// The builtins used by the called functions.
+    called_builtins: Builtins,
+    /// The first error encountered during compilation.
+    ///
+    /// The compilation continues with placeholders after an error, but the resulting shader is
+    /// discarded.
+    error: Option<ComputeError>,
 }
 
 impl core::fmt::Debug for WgslCompiler {
@@ -34,54 +45,166 @@ impl core::fmt::Debug for WgslCompiler {
 impl cubecl_core::Compiler for WgslCompiler {
     type Representation = ComputeShader;
 
-    fn compile(shader: cube::KernelDefinition, _mode: ExecutionMode) -> Self::Representation {
+    fn compile(shader: cube::KernelDefinition, mode: ExecutionMode) -> Self::Representation {
+        Self::try_compile(shader, mode).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    fn try_compile(
+        shader: cube::KernelDefinition,
+        _mode: ExecutionMode,
+    ) -> Result<Self::Representation, ComputeError> {
         let mut compiler = Self::default();
-        compiler.compile_shader(shader)
+        let shader = compiler.compile_shader(shader);
+
+        match compiler.error {
+            Some(err) => Err(err),
+            None => Ok(shader),
+        }
     }
 
     fn elem_size(elem: cube::Elem) -> usize {
-        Self::compile_elem(elem).size()
+        Self::try_compile_elem(elem)
+            .unwrap_or_else(|err| panic!("{err}"))
+            .size()
     }
 
     fn max_shared_memory_size() -> usize {
         8192
     }
+
+    fn optimizations() -> cube::Optimizations {
+        cube::Optimizations::all()
+    }
 }
 
 impl WgslCompiler {
     fn compile_shader(&mut self, mut value: cube::KernelDefinition) -> wgsl::ComputeShader {
+        if let Err(err) = value.emulate_cmma() {
+            self.fail(ComputeError::Compilation(err.to_string()));
+        }
+
         self.num_inputs = value.inputs.len();
         self.num_outputs = value.outputs.len();
 
+        // Functions only call the functions registered before them, so they are compiled in order.
+        for function in value.functions.drain(..) {
+            let function = self.compile_function(function);
+            self.functions.push(function);
+        }
+
         let instructions = self.compile_scope(&mut value.body);
-        let extensions = register_extensions(&instructions);
-        let body = wgsl::Body {
-            instructions,
-            rank: true,
-            id: self.id,
-            stride: self.stride,
-            shape: self.shape,
-        };
+        let mut extensions = register_extensions(&instructions);
+
+        for function in self.functions.iter() {
+            for extension in register_extensions(&function.body.instructions) {
+                if !extensions.contains(&extension) {
+                    extensions.push(extension);
+                }
+            }
+        }
+
+        let body = self.body(instructions, true);
 
         wgsl::ComputeShader {
             inputs: value
                 .inputs
                 .into_iter()
-                .map(Self::compile_binding)
+                .map(|binding| self.compile_binding(binding))
                 .collect(),
             outputs: value
                 .outputs
                 .into_iter()
-                .map(Self::compile_binding)
+                .map(|binding| self.compile_binding(binding))
                 .collect(),
             named: value
                 .named
                 .into_iter()
-                .map(|(name, binding)| (name, Self::compile_binding(binding)))
+                .map(|(name, binding)| (name, self.compile_binding(binding)))
                 .collect(),
             shared_memories: self.shared_memories.clone(),
-            local_arrays: self.local_arrays.clone(),
             workgroup_size: value.cube_dim,
+            builtins: self.builtins(),
+            functions: core::mem::take(&mut self.functions),
+            body,
+            extensions,
+        }
+    }
+
+    fn compile_function(&mut self, mut value: cube::Function) -> wgsl::Function {
+        let mut compiler = Self {
+            num_inputs: self.num_inputs,
+            num_outputs: self.num_outputs,
+            functions: core::mem::take(&mut self.functions),
+            ..Default::default()
+        };
+
+        let mut instructions = compiler.compile_scope(&mut value.scope);
+        let inputs = value
+            .inputs
+            .into_iter()
+            .map(|input| compiler.compile_variable(input))
+            .collect::<Vec<_>>();
+        let output = value.output.map(|output| compiler.compile_variable(output));
+
+        // The arguments are assigned to the inputs right after their declaration.
+        let position = instructions
+            .iter()
+            .take_while(|instruction| {
+                matches!(instruction, wgsl::Instruction::DeclareVariable { .. })
+            })
+            .count();
+        let assigns = inputs
+            .iter()
+            .enumerate()
+            .map(|(i, input)| wgsl::Instruction::Assign {
+                input: wgsl::Variable::Named {
+                    name: format!("arg_{i}"),
+                    item: input.item(),
+                    is_array: false,
+                },
+                out: input.clone(),
+            })
+            .collect::<Vec<_>>();
+        instructions.splice(position..position, assigns);
+
+        if !compiler.shared_memories.is_empty() {
+            compiler.unsupported(format!(
+                "Shared memories can't be declared in function {}, since it isn't inlined",
+                value.name
+            ));
+        }
+
+        if let Some(err) = compiler.error.take() {
+            self.fail(err);
+        }
+
+        self.functions = core::mem::take(&mut compiler.functions);
+
+        wgsl::Function {
+            name: format!("{}_{}", value.name, value.id),
+            inputs: inputs.iter().map(|input| input.item()).collect(),
+            output,
+            builtins: compiler.builtins(),
+            body: compiler.body(instructions, compiler.rank),
+        }
+    }
+
+    fn body(&self, instructions: Vec<wgsl::Instruction>, rank: bool) -> wgsl::Body {
+        wgsl::Body {
+            instructions,
+            local_arrays: self.local_arrays.clone(),
+            rank,
+            id: self.id,
+            stride: self.stride,
+            shape: self.shape,
+            workgroup_id_no_axis: self.workgroup_id_no_axis,
+            workgroup_size_no_axis: self.workgroup_size_no_axis,
+            num_workgroups_no_axis: self.num_workgroup_no_axis,
+        }
+    }
+
+    fn builtins(&self) -> Builtins {
+        let mut builtins = Builtins {
             global_invocation_id: self.global_invocation_id || self.id,
             local_invocation_index: self.local_invocation_index,
             local_invocation_id: self.local_invocation_id,
@@ -90,78 +213,142 @@ impl WgslCompiler {
                 || self.num_workgroup_no_axis
                 || self.workgroup_id_no_axis,
             workgroup_id: self.workgroup_id || self.workgroup_id_no_axis,
-            body,
-            extensions,
-            num_workgroups_no_axis: self.num_workgroup_no_axis,
-            workgroup_id_no_axis: self.workgroup_id_no_axis,
-            workgroup_size_no_axis: self.workgroup_size_no_axis,
-        }
+        };
+        builtins.merge(&self.called_builtins);
+        builtins
     }
 
-    fn compile_item(item: cube::Item) -> Item {
-        let elem = Self::compile_elem(item.elem);
+    fn compile_call(&mut self, instructions: &mut Vec<wgsl::Instruction>, call: cube::Call) {
+        let (function, builtins) = match self.functions.get(call.function as usize) {
+            Some(function) => (function.name.clone(), function.builtins),
+            None => {
+                self.unsupported(format!("Function {} isn't defined", call.function));
+                return;
+            }
+        };
+        self.called_builtins.merge(&builtins);
+
+        instructions.push(wgsl::Instruction::Call {
+            function,
+            inputs: call
+                .inputs
+                .into_iter()
+                .map(|input| self.compile_variable(input))
+                .collect(),
+            output: call.output.map(|output| self.compile_variable(output)),
+            builtins,
+        });
+    }
+
+    fn compile_item(&mut self, item: cube::Item) -> Item {
+        let elem = self.compile_elem(item.elem);
+
+        if elem.is_wide() && item.vectorization > 1 {
+            self.unsupported(format!("Vectorized {} are not supported", item.elem));
+        }
+
         match item.vectorization {
             1 => wgsl::Item::Scalar(elem),
             2 => wgsl::Item::Vec2(elem),
             3 => wgsl::Item::Vec3(elem),
             4 => wgsl::Item::Vec4(elem),
-            _ => panic!("Unsupported vectorizations scheme {:?}", item.vectorization),
+            _ => {
+                self.unsupported(format!(
+                    "Unsupported vectorizations scheme {:?}",
+                    item.vectorization
+                ));
+                wgsl::Item::Scalar(elem)
+            }
         }
     }
 
-    fn compile_elem(value: cube::Elem) -> wgsl::Elem {
+    fn compile_elem(&mut self, value: cube::Elem) -> wgsl::Elem {
+        Self::try_compile_elem(value).unwrap_or_else(|err| {
+            self.fail(err);
+            wgsl::Elem::F32
+        })
+    }
+
+    fn try_compile_elem(value: cube::Elem) -> Result<wgsl::Elem, ComputeError> {
+        let unsupported = |reason: &str| Err(ComputeError::UnsupportedFeature(reason.into()));
+
         match value {
             cube::Elem::Float(f) => match f {
-                cube::FloatKind::F16 => panic!("f16 is not yet supported"),
-                cube::FloatKind::BF16 => panic!("bf16 is not a valid WgpuElement"),
-                cube::FloatKind::F32 => wgsl::Elem::F32,
-                cube::FloatKind::F64 => panic!("f64 is not a valid WgpuElement"),
+                cube::FloatKind::F16 => unsupported("f16 is not yet supported"),
+                cube::FloatKind::BF16 => unsupported("bf16 is not a valid WgpuElement"),
+                cube::FloatKind::F32 => Ok(wgsl::Elem::F32),
+                cube::FloatKind::F64 => unsupported("f64 is not a valid WgpuElement"),
             },
             cube::Elem::Int(i) => match i {
-                cube::IntKind::I32 => wgsl::Elem::I32,
-                cube::IntKind::I64 => panic!("i64 is not a valid WgpuElement"),
-            },
-            cube::Elem::UInt => wgsl::Elem::U32,
-            cube::Elem::Bool => wgsl::Elem::Bool,
+                cube::IntKind::I8 => Ok(wgsl::Elem::I8),
+                cube::IntKind::I16 => Ok(wgsl::Elem::I16),
+                cube::IntKind::I32 => Ok(wgsl::Elem::I32),
+                cube::IntKind::I64 => Ok(wgsl::Elem::I64),
+            },
+            cube::Elem::UInt(u) => match u {
+                cube::UIntKind::U8 => Ok(wgsl::Elem::U8),
+                cube::UIntKind::U16 => Ok(wgsl::Elem::U16),
+                cube::UIntKind::U32 => Ok(wgsl::Elem::U32),
+                cube::UIntKind::U64 => Ok(wgsl::Elem::U64),
+            },
+            cube::Elem::Bool => Ok(wgsl::Elem::Bool),
             cube::Elem::AtomicInt(i) => match i {
-                cube::IntKind::I32 => wgsl::Elem::AtomicI32,
-                cube::IntKind::I64 => panic!("atomic<i64> is not a valid WgpuElement"),
+                cube::IntKind::I32 => Ok(wgsl::Elem::AtomicI32),
+                cube::IntKind::I64 => unsupported("atomic<i64> is not a valid WgpuElement"),
+                _ => unsupported("atomics narrower than 32 bits are not valid WgpuElements"),
+            },
+            cube::Elem::AtomicUInt => Ok(wgsl::Elem::AtomicU32),
+            cube::Elem::AtomicFloat(f) => match f {
+                cube::FloatKind::F32 => Ok(wgsl::Elem::AtomicF32),
+                _ => unsupported("float atomics other than atomic<f32> are not valid WgpuElements"),
             },
-            cube::Elem::AtomicUInt => wgsl::Elem::AtomicU32,
         }
     }
 
+    fn unsupported(&mut self, reason: String) {
+        self.fail(ComputeError::UnsupportedFeature(reason));
+    }
+
+    fn fail(&mut self, error: ComputeError) {
+        // Only the first error is kept, the following ones are often a consequence of it.
+        self.error.get_or_insert(error);
+    }
+
     fn compile_variable(&mut self, value: cube::Variable) -> wgsl::Variable {
         match value {
             cube::Variable::GlobalInputArray { id, item } => {
-                wgsl::Variable::GlobalInputArray(id, Self::compile_item(item))
+                wgsl::Variable::GlobalInputArray(id, self.compile_item(item))
             }
             cube::Variable::GlobalScalar { id, elem } => {
-                wgsl::Variable::GlobalScalar(id, Self::compile_elem(elem), elem)
+                wgsl::Variable::GlobalScalar(id, self.compile_elem(elem), elem)
             }
             cube::Variable::Local { id, item, depth } => wgsl::Variable::Local {
                 id,
-                item: Self::compile_item(item),
-                depth,
-            },
-            cube::Variable::Slice { id, item, depth } => wgsl::Variable::Slice {
-                id,
-                item: Self::compile_item(item),
+                item: self.compile_item(item),
                 depth,
             },
+            cube::Variable::Slice { id, item, depth } => {
+                let item = self.compile_item(item);
+                let item = match self.packed_slices.contains(&(id, depth)) {
+                    true => item,
+                    false => item.unpacked(),
+                };
+
+                wgsl::Variable::Slice { id, item, depth }
+            }
             cube::Variable::LocalScalar { id, elem, depth } => wgsl::Variable::LocalScalar {
                 id,
-                elem: Self::compile_elem(elem),
+                elem: self.compile_elem(elem),
                 depth,
             },
             cube::Variable::GlobalOutputArray { id, item } => {
-                wgsl::Variable::GlobalOutputArray(id, Self::compile_item(item))
+                wgsl::Variable::GlobalOutputArray(id, self.compile_item(item))
             }
             cube::Variable::ConstantScalar(value) => {
-                wgsl::Variable::ConstantScalar(value, Self::compile_elem(value.elem()))
+                wgsl::Variable::ConstantScalar(value, self.compile_elem(value.elem()))
             }
             cube::Variable::SharedMemory { id, item, length } => {
-                let item = Self::compile_item(item);
+                let item = self.compile_item(item);
                 if !self.shared_memories.iter().any(|s| s.index == id) {
                     self.shared_memories
                         .push(SharedMemory::new(id, item, length));
@@ -174,7 +361,7 @@ impl WgslCompiler {
                 depth,
                 length,
             } => {
-                let item = Self::compile_item(item);
+                let item = self.compile_item(item);
                 if !self.local_arrays.iter().any(|s| s.index == id) {
                     self.local_arrays
                         .push(LocalArray::new(id, item, depth, length));
@@ -258,14 +445,14 @@ impl WgslCompiler {
             }
             cube::Variable::SubcubeDim => wgsl::Variable::SubgroupSize,
             cube::Variable::Matrix { .. } => {
-                panic!("Cooperative matrix-multiply and accumulate not supported.")
+                unreachable!("Cooperative matrices are emulated before the compilation.")
             }
         }
     }
 
     fn compile_scope(&mut self, value: &mut cube::Scope) -> Vec<wgsl::Instruction> {
         let mut instructions = Vec::new();
-        let processing = value.process();
+        let processing = value.process_with(&<Self as cubecl_core::Compiler>::optimizations());
 
         for var in processing.variables {
             // We don't declare slices.
@@ -293,7 +480,39 @@ impl WgslCompiler {
         scope: &mut cube::Scope,
     ) {
         match operation {
-            cube::Operation::Operator(op) => instructions.push(self.compile_instruction(op)),
+            cube::Operation::Operator(
+                cube::Operator::AtomicAnd(op)
+                | cube::Operator::AtomicOr(op)
+                | cube::Operator::AtomicXor(op),
+            ) if matches!(op.lhs.item().elem(), cube::Elem::AtomicFloat(_)) => self.unsupported(
+                "Bitwise operations on float atomics aren't supported on wgpu.".to_string(),
+            ),
+            cube::Operation::Operator(op) if !Self::is_emulated(&op) => self.unsupported(format!(
+                "The operator {op:?} isn't emulated for 64 bits integers on wgpu."
+            )),
+            cube::Operation::Subcube(
+                cube::Subcube::Sum(op)
+                | cube::Subcube::Prod(op)
+                | cube::Subcube::Min(op)
+                | cube::Subcube::Max(op)
+                | cube::Subcube::InclusiveSum(op)
+                | cube::Subcube::ExclusiveSum(op)
+                | cube::Subcube::InclusiveProd(op)
+                | cube::Subcube::ExclusiveProd(op),
+            ) if is_wide(&op.input) => self.unsupported(
+                "Subgroup reductions of 64 bits integers aren't supported on wgpu.".to_string(),
+            ),
+            cube::Operation::Operator(op) => {
+                let truncated = Self::truncated(&op);
+                instructions.push(self.compile_instruction(op));
+
+                if let Some(var) = truncated {
+                    let var = self.compile_variable(var);
+                    if let Some(bits) = var.elem().packed_bits() {
+                        instructions.push(wgsl::Instruction::Truncate { var, bits });
+                    }
+                }
+            }
             cube::Operation::Procedure(proc) => self.compile_procedure(instructions, proc, scope),
             cube::Operation::Metadata(op) => instructions.push(self.compile_metadata(op)),
             cube::Operation::Branch(val) => self.compile_branch(instructions, val),
@@ -302,8 +521,52 @@ impl WgslCompiler {
             }
             cube::Operation::Subcube(op) => self.compile_subgroup(instructions, op),
             cube::Operation::CoopMma(_) => {
-                panic!("Cooperative matrix-multiply and accumulate isn't supported on wgpu.")
+                unreachable!("Cooperative matrix operations are emulated before the compilation.")
+            }
+            cube::Operation::Call(call) => self.compile_call(instructions, call),
+        }
+    }
+
+    /// Whether the operator is emulated for 64 bits integers when it uses them. Only the arithmetic
+    /// with a carry, the comparisons, the bitwise operations and the conversions between integers
+    /// are emulated.
+    fn is_emulated(op: &cube::Operator) -> bool {
+        let float = |var: &cube::Variable| matches!(var.item().elem(), cube::Elem::Float(_));
+
+        match op {
+            cube::Operator::Div(op)
+            | cube::Operator::Modulo(op)
+            | cube::Operator::Remainder(op)
+            | cube::Operator::ShiftLeft(op)
+            | cube::Operator::ShiftRight(op) => !is_wide(&op.lhs),
+            cube::Operator::Abs(op)
+            | cube::Operator::CountOnes(op)
+            | cube::Operator::ReverseBits(op)
+            | cube::Operator::LeadingZeros(op)
+            | cube::Operator::TrailingZeros(op) => !is_wide(&op.input),
+            cube::Operator::Clamp(op) => !is_wide(&op.out),
+            cube::Operator::Assign(op) => {
+                is_wide(&op.input) == is_wide(&op.out) || !float(&op.input) && !float(&op.out)
             }
+            cube::Operator::Bitcast(op) => is_wide(&op.input) == is_wide(&op.out),
+            _ => true,
+        }
+    }
+
+    /// The output of the operators that can overflow a narrow integer, which has to be truncated
+    /// since it is widened to 32 bits.
+    fn truncated(op: &cube::Operator) -> Option<cube::Variable> {
+        match op {
+            cube::Operator::Add(op)
+            | cube::Operator::Sub(op)
+            | cube::Operator::Mul(op)
+            | cube::Operator::Div(op)
+            | cube::Operator::ShiftLeft(op) => Some(op.out),
+            cube::Operator::Assign(op)
+            | cube::Operator::Abs(op)
+            | cube::Operator::Neg(op)
+            | cube::Operator::BitwiseNot(op) => Some(op.out),
+            _ => None,
         }
     }
 
@@ -357,6 +620,46 @@ impl WgslCompiler {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(op.out),
             },
+            cube::Subcube::Shuffle(op) => Subgroup::Shuffle {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::ShuffleXor(op) => Subgroup::ShuffleXor {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::ShuffleUp(op) => Subgroup::ShuffleUp {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::ShuffleDown(op) => Subgroup::ShuffleDown {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::InclusiveSum(op) => Subgroup::InclusiveSum {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::ExclusiveSum(op) => Subgroup::ExclusiveSum {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::InclusiveProd(op) => Subgroup::InclusiveProd {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::ExclusiveProd(op) => Subgroup::ExclusiveProd {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Subcube::Ballot(op) => Subgroup::Ballot {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
         };
 
         instructions.push(wgsl::Instruction::Subgroup(op));
@@ -373,6 +676,21 @@ impl WgslCompiler {
                 instructions_if: self.compile_scope(&mut op.scope_if),
                 instructions_else: self.compile_scope(&mut op.scope_else),
             }),
+            cube::Branch::Switch(mut op) => instructions.push(wgsl::Instruction::Switch {
+                value: self.compile_variable(op.value),
+                instructions_default: self.compile_scope(&mut op.scope_default),
+                cases: op
+                    .cases
+                    .into_iter()
+                    .map(|(values, mut scope)| {
+                        let values = values
+                            .into_iter()
+                            .map(|val| self.compile_variable(val))
+                            .collect();
+                        (values, self.compile_scope(&mut scope))
+                    })
+                    .collect(),
+            }),
             cube::Branch::Return => instructions.push(wgsl::Instruction::Return),
             cube::Branch::Break => instructions.push(wgsl::Instruction::Break),
             cube::Branch::RangeLoop(mut range_loop) => {
@@ -482,6 +800,8 @@ impl WgslCompiler {
             cube::Metadata::Length { var, out } => wgsl::Instruction::Length {
                 out: self.compile_variable(out),
                 var: self.compile_variable(var),
+                num_inputs: self.num_inputs,
+                num_outputs: self.num_outputs,
             },
         }
     }
@@ -592,6 +912,81 @@ impl WgslCompiler {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(op.out),
             },
+            cube::Operator::Tan(op) => wgsl::Instruction::Tan {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Asin(op) => wgsl::Instruction::Asin {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Acos(op) => wgsl::Instruction::Acos {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Atan(op) => wgsl::Instruction::Atan {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Atan2(op) => wgsl::Instruction::Atan2 {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Sinh(op) => wgsl::Instruction::Sinh {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Cosh(op) => wgsl::Instruction::Cosh {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Exp2(op) => wgsl::Instruction::Exp2 {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Log2(op) => wgsl::Instruction::Log2 {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Round(op) => wgsl::Instruction::Round {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Trunc(op) => wgsl::Instruction::Trunc {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Fract(op) => wgsl::Instruction::Fract {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Sign(op) => wgsl::Instruction::Sign {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::CopySign(op) => wgsl::Instruction::CopySign {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Hypot(op) => wgsl::Instruction::Hypot {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Rsqrt(op) => wgsl::Instruction::Rsqrt {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::IsNan(op) => wgsl::Instruction::IsNan {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::IsInf(op) => wgsl::Instruction::IsInf {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
             cube::Operator::Equal(op) => wgsl::Instruction::Equal {
                 lhs: self.compile_variable(op.lhs),
                 rhs: self.compile_variable(op.rhs),
@@ -656,11 +1051,40 @@ impl WgslCompiler {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(op.out),
             },
+            cube::Operator::BitwiseNot(op) => wgsl::Instruction::BitwiseNot {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Neg(op) => wgsl::Instruction::Neg {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::CountOnes(op) => wgsl::Instruction::CountOnes {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::ReverseBits(op) => wgsl::Instruction::ReverseBits {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::LeadingZeros(op) => wgsl::Instruction::LeadingZeros {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::TrailingZeros(op) => wgsl::Instruction::TrailingZeros {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
             cube::Operator::BitwiseAnd(op) => wgsl::Instruction::BitwiseAnd {
                 lhs: self.compile_variable(op.lhs),
                 rhs: self.compile_variable(op.rhs),
                 out: self.compile_variable(op.out),
             },
+            cube::Operator::BitwiseOr(op) => wgsl::Instruction::BitwiseOr {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
             cube::Operator::BitwiseXor(op) => wgsl::Instruction::BitwiseXor {
                 lhs: self.compile_variable(op.lhs),
                 rhs: self.compile_variable(op.rhs),
@@ -681,12 +1105,22 @@ impl WgslCompiler {
                 rhs: self.compile_variable(op.rhs),
                 out: self.compile_variable(op.out),
             },
-            cube::Operator::Slice(op) => wgsl::Instruction::Slice {
-                input: self.compile_variable(op.input),
-                start: self.compile_variable(op.start),
-                end: self.compile_variable(op.end),
-                out: self.compile_variable(op.out),
-            },
+            cube::Operator::Slice(op) => {
+                let input = self.compile_variable(op.input);
+
+                if let (Some(_), cube::Variable::Slice { id, depth, .. }) =
+                    (input.packed_bits(), op.out)
+                {
+                    self.packed_slices.push((id, depth));
+                }
+
+                wgsl::Instruction::Slice {
+                    input,
+                    start: self.compile_variable(op.start),
+                    end: self.compile_variable(op.end),
+                    out: self.compile_variable(op.out),
+                }
+            }
             cube::Operator::AtomicLoad(op) => wgsl::Instruction::AtomicLoad {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(op.out),
@@ -764,16 +1198,33 @@ impl WgslCompiler {
         }
     }
 
-    fn compile_binding(value: cube::Binding) -> wgsl::Binding {
+    fn compile_binding(&mut self, value: cube::Binding) -> wgsl::Binding {
+        let item = self.compile_item(value.item);
+
+        if item.elem().packed_bits().is_some() && item.vectorization_factor() > 1 {
+            self.unsupported(format!(
+                "Vectorized bindings of {} are not supported",
+                value.item.elem
+            ));
+        }
+
         wgsl::Binding {
             visibility: Self::compile_visibility(value.visibility),
             location: Self::compile_location(value.location),
-            item: Self::compile_item(value.item),
+            item,
             size: value.size,
         }
     }
 }
 
+/// Whether the variable is a 64 bits integer, which is emulated with two 32 bits words.
+fn is_wide(var: &cube::Variable) -> bool {
+    matches!(
+        var.item().elem(),
+        cube::Elem::Int(cube::IntKind::I64) | cube::Elem::UInt(cube::UIntKind::U64)
+    )
+}
+
 fn register_extensions(instructions: &[wgsl::Instruction]) -> Vec<wgsl::Extension> {
     let mut extensions = Vec::new();
 
@@ -798,18 +1249,104 @@ fn register_extensions(instructions: &[wgsl::Instruction]) -> Vec<wgsl::Extensio
             wgsl::Instruction::Erf { input, out: _ } => {
                 register_extension(wgsl::Extension::Erf(input.item()));
             }
+            wgsl::Instruction::Round { input, out: _ } => {
+                register_extension(wgsl::Extension::Round(input.item()));
+            }
+            wgsl::Instruction::CopySign {
+                lhs: _,
+                rhs: _,
+                out,
+            } => {
+                register_extension(wgsl::Extension::CopySign(out.item()));
+            }
+            wgsl::Instruction::Hypot {
+                lhs: _,
+                rhs: _,
+                out,
+            } => {
+                register_extension(wgsl::Extension::Hypot(out.item()));
+            }
+            wgsl::Instruction::IsNan { input, out: _ } => {
+                register_extension(wgsl::Extension::IsNan(input.item()));
+            }
+            wgsl::Instruction::IsInf { input, out: _ } => {
+                register_extension(wgsl::Extension::IsInf(input.item()));
+            }
             #[cfg(target_os = "macos")]
             wgsl::Instruction::Tanh { input, out: _ } => {
                 register_extension(wgsl::Extension::SafeTanh(input.item()))
             }
+            wgsl::Instruction::Add {
+                lhs: _,
+                rhs: _,
+                out,
+            } if out.elem().is_wide() => {
+                register_extension(wgsl::Extension::Add64);
+            }
+            wgsl::Instruction::Sub {
+                lhs: _,
+                rhs: _,
+                out,
+            }
+            | wgsl::Instruction::Neg { input: _, out }
+                if out.elem().is_wide() =>
+            {
+                register_extension(wgsl::Extension::Sub64);
+            }
+            wgsl::Instruction::Mul {
+                lhs: _,
+                rhs: _,
+                out,
+            } if out.elem().is_wide() => {
+                register_extension(wgsl::Extension::Mul64);
+            }
+            wgsl::Instruction::Lower { lhs, .. }
+            | wgsl::Instruction::Greater { lhs, .. }
+            | wgsl::Instruction::LowerEqual { lhs, .. }
+            | wgsl::Instruction::GreaterEqual { lhs, .. }
+            | wgsl::Instruction::Max { lhs, .. }
+            | wgsl::Instruction::Min { lhs, .. }
+                if lhs.elem().is_wide() =>
+            {
+                register_extension(wgsl::Extension::Lower64(lhs.elem()));
+            }
             wgsl::Instruction::If {
                 cond: _,
                 instructions,
-            } => {
+            }
+            | wgsl::Instruction::Loop { instructions }
+            | wgsl::Instruction::RangeLoop { instructions, .. } => {
                 for extension in register_extensions(instructions) {
                     register_extension(extension);
                 }
             }
+            wgsl::Instruction::IfElse {
+                cond: _,
+                instructions_if,
+                instructions_else,
+            } => {
+                for instructions in [instructions_if, instructions_else] {
+                    for extension in register_extensions(instructions) {
+                        register_extension(extension);
+                    }
+                }
+            }
+            wgsl::Instruction::Switch {
+                value: _,
+                instructions_default,
+                cases,
+            } => {
+                let blocks = cases
+                    .iter()
+                    .map(|(_, instructions)| instructions)
+                    .chain([instructions_default]);
+
+                for instructions in blocks {
+                    for extension in register_extensions(instructions) {
+                        register_extension(extension);
+                    }
+                }
+            }
             _ => {}
         }
     }
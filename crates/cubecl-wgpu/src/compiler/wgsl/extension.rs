@@ -1,4 +1,4 @@
-use super::base::Item;
+use super::base::{Elem, Item};
 use std::fmt::Display;
 
 /// Not all functions are native to WGSL, so this struct allows to support more functions.
@@ -8,6 +8,15 @@ pub enum Extension {
     PowfPrimitive(Item),
     Powf(Item),
     Erf(Item),
+    Round(Item),
+    CopySign(Item),
+    Hypot(Item),
+    IsNan(Item),
+    IsInf(Item),
+    Add64,
+    Sub64,
+    Mul64,
+    Lower64(Elem),
     #[cfg(target_os = "macos")]
     SafeTanh(Item),
 }
@@ -19,6 +28,15 @@ impl Display for Extension {
             Extension::PowfPrimitive(elem) => format_powf_primitive(f, elem),
             Extension::Powf(elem) => format_powf(f, elem),
             Extension::Erf(elem) => format_erf(f, elem),
+            Extension::Round(item) => format_round(f, item),
+            Extension::CopySign(item) => format_copysign(f, item),
+            Extension::Hypot(item) => format_hypot(f, item),
+            Extension::IsNan(item) => format_is_nan(f, item),
+            Extension::IsInf(item) => format_is_inf(f, item),
+            Extension::Add64 => format_add_64(f),
+            Extension::Sub64 => format_sub_64(f),
+            Extension::Mul64 => format_mul_64(f),
+            Extension::Lower64(elem) => format_lower_64(f, elem),
             #[cfg(target_os = "macos")]
             Extension::SafeTanh(elem) => format_safe_tanh(f, elem),
         }
@@ -219,6 +237,149 @@ fn erf(x: {ty}) -> {ty} {{
     }
 }
 
+fn format_round(f: &mut core::fmt::Formatter<'_>, item: &Item) -> core::fmt::Result {
+    f.write_fmt(format_args!(
+        "
+/// The builtin round function rounds half-way cases to even, not away from zero.
+fn round_away_from_zero(x: {item}) -> {item} {{
+    let t = trunc(x);
+    return select(t, t + sign(x), abs(x - t) >= {item}(0.5));
+}}
+"
+    ))
+}
+
+fn format_copysign(f: &mut core::fmt::Formatter<'_>, item: &Item) -> core::fmt::Result {
+    let bits = item.with_elem(Elem::U32);
+
+    f.write_fmt(format_args!(
+        "
+fn copysign(lhs: {item}, rhs: {item}) -> {item} {{
+    let negative = (bitcast<{bits}>(rhs) & {bits}(0x80000000u)) != {bits}(0u);
+    return select(abs(lhs), -abs(lhs), negative);
+}}
+"
+    ))
+}
+
+fn format_hypot(f: &mut core::fmt::Formatter<'_>, item: &Item) -> core::fmt::Result {
+    f.write_fmt(format_args!(
+        "
+/// Dividing by the largest magnitude avoids overflowing the squares.
+fn hypot(lhs: {item}, rhs: {item}) -> {item} {{
+    let big = max(abs(lhs), abs(rhs));
+    let small = min(abs(lhs), abs(rhs));
+    let ratio = small / select(big, {item}(1.0), big == {item}(0.0));
+    return big * sqrt({item}(1.0) + ratio * ratio);
+}}
+"
+    ))
+}
+
+/// Shaders may assume floats are finite and fold `x != x` away, so NaN and infinity are detected
+/// from the bits.
+fn format_is_nan(f: &mut core::fmt::Formatter<'_>, item: &Item) -> core::fmt::Result {
+    let bits = item.with_elem(Elem::U32);
+    let out = item.with_elem(Elem::Bool);
+
+    f.write_fmt(format_args!(
+        "
+fn is_nan(x: {item}) -> {out} {{
+    return (bitcast<{bits}>(x) & {bits}(0x7fffffffu)) > {bits}(0x7f800000u);
+}}
+"
+    ))
+}
+
+fn format_is_inf(f: &mut core::fmt::Formatter<'_>, item: &Item) -> core::fmt::Result {
+    let bits = item.with_elem(Elem::U32);
+    let out = item.with_elem(Elem::Bool);
+
+    f.write_fmt(format_args!(
+        "
+fn is_inf(x: {item}) -> {out} {{
+    return (bitcast<{bits}>(x) & {bits}(0x7fffffffu)) == {bits}(0x7f800000u);
+}}
+"
+    ))
+}
+
+/// 64 bits integers are held in two words with the low word first, so the carry of the low words
+/// is propagated to the high words.
+fn format_add_64(f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_str(
+        "
+fn add_64(lhs: vec2<u32>, rhs: vec2<u32>) -> vec2<u32> {
+    let low = lhs.x + rhs.x;
+    let carry = select(0u, 1u, low < lhs.x);
+    return vec2<u32>(low, lhs.y + rhs.y + carry);
+}
+",
+    )
+}
+
+fn format_sub_64(f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_str(
+        "
+fn sub_64(lhs: vec2<u32>, rhs: vec2<u32>) -> vec2<u32> {
+    let borrow = select(0u, 1u, lhs.x < rhs.x);
+    return vec2<u32>(lhs.x - rhs.x, lhs.y - rhs.y - borrow);
+}
+",
+    )
+}
+
+/// The product of the low words is computed from their 16 bits halves to keep its high word, the
+/// products involving a high word only contribute to the high word of the result.
+fn format_mul_64(f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_str(
+        "
+fn mul_64(lhs: vec2<u32>, rhs: vec2<u32>) -> vec2<u32> {
+    let lhs_low = lhs.x & 0xffffu;
+    let lhs_high = lhs.x >> 16u;
+    let rhs_low = rhs.x & 0xffffu;
+    let rhs_high = rhs.x >> 16u;
+
+    let low_low = lhs_low * rhs_low;
+    let low_high = lhs_low * rhs_high;
+    let high_low = lhs_high * rhs_low;
+    let middle = (low_low >> 16u) + (low_high & 0xffffu) + (high_low & 0xffffu);
+
+    let low = (middle << 16u) | (low_low & 0xffffu);
+    let high = lhs_high * rhs_high + (low_high >> 16u) + (high_low >> 16u) + (middle >> 16u);
+    return vec2<u32>(low, high + lhs.x * rhs.y + lhs.y * rhs.x);
+}
+",
+    )
+}
+
+/// The name of the function comparing 64 bits integers of the given element.
+pub fn lower_64(elem: &Elem) -> &'static str {
+    match elem {
+        Elem::I64 => "lower_i64",
+        _ => "lower_u64",
+    }
+}
+
+/// The high words hold the sign, so only they are compared as signed integers.
+fn format_lower_64(f: &mut core::fmt::Formatter<'_>, elem: &Elem) -> core::fmt::Result {
+    let name = lower_64(elem);
+    let high = match elem {
+        Elem::I64 => "i32",
+        _ => "u32",
+    };
+
+    f.write_fmt(format_args!(
+        "
+fn {name}(lhs: vec2<u32>, rhs: vec2<u32>) -> bool {{
+    let lhs_high = bitcast<{high}>(lhs.y);
+    let rhs_high = bitcast<{high}>(rhs.y);
+    return lhs_high < rhs_high || (lhs_high == rhs_high && lhs.x < rhs.x);
+}}
+"
+    ))
+}
+
 #[cfg(target_os = "macos")]
 fn format_safe_tanh(f: &mut core::fmt::Formatter<'_>, item: &Item) -> core::fmt::Result {
     let elem = item.elem();
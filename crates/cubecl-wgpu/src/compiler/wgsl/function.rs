@@ -0,0 +1,100 @@
+use super::{Body, Item, Variable};
+use std::fmt::Display;
+
+/// The builtin values used by a shader.
+///
+/// Functions don't have access to the builtins of the entry point, so they are passed as
+/// parameters with the same name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Builtins {
+    pub global_invocation_id: bool,
+    pub local_invocation_index: bool,
+    pub local_invocation_id: bool,
+    pub num_workgroups: bool,
+    pub workgroup_id: bool,
+}
+
+impl Builtins {
+    /// Merge the builtins used by a called function.
+    pub fn merge(&mut self, other: &Builtins) {
+        self.global_invocation_id |= other.global_invocation_id;
+        self.local_invocation_index |= other.local_invocation_index;
+        self.local_invocation_id |= other.local_invocation_id;
+        self.num_workgroups |= other.num_workgroups;
+        self.workgroup_id |= other.workgroup_id;
+    }
+
+    /// The name and type of each builtin used.
+    fn params(&self) -> Vec<(&'static str, &'static str)> {
+        [
+            (self.global_invocation_id, "global_id", "vec3<u32>"),
+            (self.local_invocation_index, "local_idx", "u32"),
+            (self.local_invocation_id, "local_invocation_id", "vec3<u32>"),
+            (self.num_workgroups, "num_workgroups", "vec3<u32>"),
+            (self.workgroup_id, "workgroup_id", "vec3<u32>"),
+        ]
+        .into_iter()
+        .filter(|(used, _, _)| *used)
+        .map(|(_, name, ty)| (name, ty))
+        .collect()
+    }
+}
+
+/// A function called by the shader.
+///
+/// The inputs are named `arg_{i}` and are assigned to the local variables of the body.
+#[derive(Debug, Clone)]
+pub struct Function {
+    pub name: String,
+    pub inputs: Vec<Item>,
+    pub output: Option<Variable>,
+    pub builtins: Builtins,
+    pub body: Body,
+}
+
+impl Display for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("fn {}(\n", self.name))?;
+
+        for (i, item) in self.inputs.iter().enumerate() {
+            f.write_fmt(format_args!("    arg_{i}: {item},\n"))?;
+        }
+
+        for (name, ty) in self.builtins.params() {
+            f.write_fmt(format_args!("    {name}: {ty},\n"))?;
+        }
+
+        match &self.output {
+            Some(output) => f.write_fmt(format_args!(") -> {} {{\n", output.item()))?,
+            None => f.write_str(") {\n")?,
+        }
+
+        f.write_fmt(format_args!("{}", self.body))?;
+
+        if let Some(output) = &self.output {
+            f.write_fmt(format_args!("return {output};\n"))?;
+        }
+
+        f.write_str("}")
+    }
+}
+
+/// Render the arguments of a call, followed by the builtins used by the function.
+pub(crate) fn format_call_args(
+    f: &mut std::fmt::Formatter<'_>,
+    inputs: &[Variable],
+    builtins: &Builtins,
+) -> std::fmt::Result {
+    let args = inputs
+        .iter()
+        .map(|input| input.to_string())
+        .chain(
+            builtins
+                .params()
+                .into_iter()
+                .map(|(name, _)| name.to_string()),
+        )
+        .collect::<Vec<_>>();
+
+    f.write_str(&args.join(", "))
+}
@@ -1,6 +1,6 @@
 use super::{
     base::{Item, Variable},
-    Elem, IndexedVariable, Subgroup,
+    format_call_args, lower_64, Builtins, Elem, IndexedVariable, Subgroup,
 };
 use std::fmt::Display;
 
@@ -41,6 +41,17 @@ pub enum Instruction {
         instructions_if: Vec<Instruction>,
         instructions_else: Vec<Instruction>,
     },
+    Switch {
+        value: Variable,
+        instructions_default: Vec<Instruction>,
+        cases: Vec<(Vec<Variable>, Vec<Instruction>)>,
+    },
+    Call {
+        function: String,
+        inputs: Vec<Variable>,
+        output: Option<Variable>,
+        builtins: Builtins,
+    },
     Return,
     Break,
     WorkgroupBarrier,
@@ -127,6 +138,81 @@ pub enum Instruction {
         input: Variable,
         out: Variable,
     },
+    Tan {
+        input: Variable,
+        out: Variable,
+    },
+    Asin {
+        input: Variable,
+        out: Variable,
+    },
+    Acos {
+        input: Variable,
+        out: Variable,
+    },
+    Atan {
+        input: Variable,
+        out: Variable,
+    },
+    Atan2 {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    Sinh {
+        input: Variable,
+        out: Variable,
+    },
+    Cosh {
+        input: Variable,
+        out: Variable,
+    },
+    Exp2 {
+        input: Variable,
+        out: Variable,
+    },
+    Log2 {
+        input: Variable,
+        out: Variable,
+    },
+    Round {
+        input: Variable,
+        out: Variable,
+    },
+    Trunc {
+        input: Variable,
+        out: Variable,
+    },
+    Fract {
+        input: Variable,
+        out: Variable,
+    },
+    Sign {
+        input: Variable,
+        out: Variable,
+    },
+    CopySign {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    Hypot {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    Rsqrt {
+        input: Variable,
+        out: Variable,
+    },
+    IsNan {
+        input: Variable,
+        out: Variable,
+    },
+    IsInf {
+        input: Variable,
+        out: Variable,
+    },
     Equal {
         lhs: Variable,
         rhs: Variable,
@@ -170,6 +256,8 @@ pub enum Instruction {
     },
     Length {
         var: Variable,
+        num_inputs: usize,
+        num_outputs: usize,
         out: Variable,
     },
     Shape {
@@ -198,6 +286,30 @@ pub enum Instruction {
         input: Variable,
         out: Variable,
     },
+    BitwiseNot {
+        input: Variable,
+        out: Variable,
+    },
+    Neg {
+        input: Variable,
+        out: Variable,
+    },
+    CountOnes {
+        input: Variable,
+        out: Variable,
+    },
+    ReverseBits {
+        input: Variable,
+        out: Variable,
+    },
+    LeadingZeros {
+        input: Variable,
+        out: Variable,
+    },
+    TrailingZeros {
+        input: Variable,
+        out: Variable,
+    },
     Loop {
         instructions: Vec<Instruction>,
     },
@@ -206,6 +318,11 @@ pub enum Instruction {
         rhs: Variable,
         out: Variable,
     },
+    BitwiseOr {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
     BitwiseXor {
         lhs: Variable,
         rhs: Variable,
@@ -299,6 +416,11 @@ pub enum Instruction {
         out: Variable,
     },
     Subgroup(Subgroup),
+    // Wraps a widened narrow integer to its number of bits.
+    Truncate {
+        var: Variable,
+        bits: u32,
+    },
 }
 
 impl Display for Instruction {
@@ -312,6 +434,8 @@ impl Display for Instruction {
                 if out.is_atomic() {
                     assert_eq!(lhs, out, "Can't use regular addition on atomic");
                     f.write_fmt(format_args!("atomicAdd({out}, {rhs});\n"))
+                } else if out.elem().is_wide() {
+                    f.write_fmt(format_args!("{out} = add_64({lhs}, {rhs});\n"))
                 } else {
                     f.write_fmt(format_args!("{out} = {lhs} + {rhs};\n"))
                 }
@@ -333,6 +457,11 @@ impl Display for Instruction {
                 if out.is_atomic() {
                     assert_eq!(lhs, out, "Can't use regular min on atomic");
                     f.write_fmt(format_args!("atomicMin({out}, {rhs});\n"))
+                } else if out.elem().is_wide() {
+                    let lower = lower_64(&out.elem());
+                    f.write_fmt(format_args!(
+                        "{out} = select({lhs}, {rhs}, {lower}({rhs}, {lhs}));\n"
+                    ))
                 } else {
                     f.write_fmt(format_args!("{out} = min({lhs}, {rhs});\n"))
                 }
@@ -341,6 +470,11 @@ impl Display for Instruction {
                 if out.is_atomic() {
                     assert_eq!(lhs, out, "Can't use regular max on atomic");
                     f.write_fmt(format_args!("atomicMax({out}, {rhs});\n"))
+                } else if out.elem().is_wide() {
+                    let lower = lower_64(&out.elem());
+                    f.write_fmt(format_args!(
+                        "{out} = select({lhs}, {rhs}, {lower}({lhs}, {rhs}));\n"
+                    ))
                 } else {
                     f.write_fmt(format_args!("{out} = max({lhs}, {rhs});\n"))
                 }
@@ -362,6 +496,47 @@ impl Display for Instruction {
                 }
             }
             Instruction::Not { input, out } => f.write_fmt(format_args!("{out} = !{input};\n")),
+            Instruction::BitwiseNot { input, out } => {
+                f.write_fmt(format_args!("{out} = ~{input};\n"))
+            }
+            Instruction::Neg { input, out } if out.elem().is_wide() => f.write_fmt(format_args!(
+                "{out} = sub_64(vec2<u32>(0u), {input});\n"
+            )),
+            Instruction::Neg { input, out } => f.write_fmt(format_args!("{out} = -{input};\n")),
+            // Narrow integers are held sign extended in 32 bits, so the bit counting functions
+            // only look at the bits of the element.
+            Instruction::CountOnes { input, out } => match input.elem().packed_bits() {
+                Some(bits) => f.write_fmt(format_args!(
+                    "{out} = countOneBits({input} & {}({}));\n",
+                    input.item(),
+                    (1u32 << bits) - 1
+                )),
+                None => f.write_fmt(format_args!("{out} = countOneBits({input});\n")),
+            },
+            Instruction::ReverseBits { input, out } => match input.elem().packed_bits() {
+                Some(bits) => f.write_fmt(format_args!(
+                    "{out} = reverseBits({input}) >> {}({}u);\n",
+                    input.item().with_elem(Elem::U32),
+                    32 - bits
+                )),
+                None => f.write_fmt(format_args!("{out} = reverseBits({input});\n")),
+            },
+            Instruction::LeadingZeros { input, out } => match input.elem().packed_bits() {
+                Some(bits) => f.write_fmt(format_args!(
+                    "{out} = countLeadingZeros({input} & {item}({})) - {item}({});\n",
+                    (1u32 << bits) - 1,
+                    32 - bits,
+                    item = input.item(),
+                )),
+                None => f.write_fmt(format_args!("{out} = countLeadingZeros({input});\n")),
+            },
+            Instruction::TrailingZeros { input, out } => match input.elem().packed_bits() {
+                Some(bits) => f.write_fmt(format_args!(
+                    "{out} = min(countTrailingZeros({input}), {}({bits}));\n",
+                    input.item(),
+                )),
+                None => f.write_fmt(format_args!("{out} = countTrailingZeros({input});\n")),
+            },
             Instruction::Index { lhs, rhs, out } => match lhs {
                 Variable::Slice { item, .. } => {
                     let offset = Variable::Named {
@@ -388,10 +563,15 @@ impl Display for Instruction {
                 if out.is_atomic() {
                     assert_eq!(lhs, out, "Can't use regular sub on atomic");
                     f.write_fmt(format_args!("atomicSub({out}, {rhs});\n"))
+                } else if out.elem().is_wide() {
+                    f.write_fmt(format_args!("{out} = sub_64({lhs}, {rhs});\n"))
                 } else {
                     f.write_fmt(format_args!("{out} = {lhs} - {rhs};\n"))
                 }
             }
+            Instruction::Mul { lhs, rhs, out } if out.elem().is_wide() => {
+                f.write_fmt(format_args!("{out} = mul_64({lhs}, {rhs});\n"))
+            }
             Instruction::Mul { lhs, rhs, out } => {
                 f.write_fmt(format_args!("{out} = {lhs} * {rhs};\n"))
             }
@@ -450,6 +630,58 @@ impl Display for Instruction {
             Instruction::Recip { input, out } => {
                 f.write_fmt(format_args!("{out} = 1.0 / {input};"))
             }
+            Instruction::Tan { input, out } => f.write_fmt(format_args!("{out} = tan({input});\n")),
+            Instruction::Asin { input, out } => {
+                f.write_fmt(format_args!("{out} = asin({input});\n"))
+            }
+            Instruction::Acos { input, out } => {
+                f.write_fmt(format_args!("{out} = acos({input});\n"))
+            }
+            Instruction::Atan { input, out } => {
+                f.write_fmt(format_args!("{out} = atan({input});\n"))
+            }
+            Instruction::Atan2 { lhs, rhs, out } => {
+                f.write_fmt(format_args!("{out} = atan2({lhs}, {rhs});\n"))
+            }
+            Instruction::Sinh { input, out } => {
+                f.write_fmt(format_args!("{out} = sinh({input});\n"))
+            }
+            Instruction::Cosh { input, out } => {
+                f.write_fmt(format_args!("{out} = cosh({input});\n"))
+            }
+            Instruction::Exp2 { input, out } => {
+                f.write_fmt(format_args!("{out} = exp2({input});\n"))
+            }
+            Instruction::Log2 { input, out } => {
+                f.write_fmt(format_args!("{out} = log2({input});\n"))
+            }
+            Instruction::Round { input, out } => {
+                f.write_fmt(format_args!("{out} = round_away_from_zero({input});\n"))
+            }
+            Instruction::Trunc { input, out } => {
+                f.write_fmt(format_args!("{out} = trunc({input});\n"))
+            }
+            Instruction::Fract { input, out } => {
+                f.write_fmt(format_args!("{out} = {input} - trunc({input});\n"))
+            }
+            Instruction::Sign { input, out } => {
+                f.write_fmt(format_args!("{out} = sign({input});\n"))
+            }
+            Instruction::CopySign { lhs, rhs, out } => {
+                f.write_fmt(format_args!("{out} = copysign({lhs}, {rhs});\n"))
+            }
+            Instruction::Hypot { lhs, rhs, out } => {
+                f.write_fmt(format_args!("{out} = hypot({lhs}, {rhs});\n"))
+            }
+            Instruction::Rsqrt { input, out } => {
+                f.write_fmt(format_args!("{out} = inverseSqrt({input});\n"))
+            }
+            Instruction::IsNan { input, out } => {
+                f.write_fmt(format_args!("{out} = is_nan({input});\n"))
+            }
+            Instruction::IsInf { input, out } => {
+                f.write_fmt(format_args!("{out} = is_inf({input});\n"))
+            }
             Instruction::Equal { lhs, rhs, out } => comparison(lhs, rhs, out, "==", f),
             Instruction::Lower { lhs, rhs, out } => comparison(lhs, rhs, out, "<", f),
             Instruction::Greater { lhs, rhs, out } => comparison(lhs, rhs, out, ">", f),
@@ -503,7 +735,8 @@ impl Display for Instruction {
                     if elem.is_atomic() {
                         f.write_fmt(format_args!("let {out} = &{input};\n"))
                     } else {
-                        f.write_fmt(format_args!("{out} = {elem}({input});\n"))
+                        let value = cast(input, input.elem(), elem);
+                        f.write_fmt(format_args!("{out} = {value};\n"))
                     }
                 }
             },
@@ -576,13 +809,83 @@ for (var {i}: u32 = {start}; {i} < {end}; {increment}) {{
                 }
                 f.write_str("}\n")
             }
+            Instruction::Switch {
+                value,
+                instructions_default,
+                cases,
+            } => {
+                f.write_fmt(format_args!("switch {value} {{\n"))?;
+                for (values, block) in cases {
+                    let values = values
+                        .iter()
+                        .map(|val| val.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    f.write_fmt(format_args!("case {values}: {{\n"))?;
+                    for i in block {
+                        f.write_fmt(format_args!("{i}"))?;
+                    }
+                    f.write_str("}\n")?;
+                }
+                f.write_str("default: {\n")?;
+                for i in instructions_default {
+                    f.write_fmt(format_args!("{i}"))?;
+                }
+                f.write_str("}\n}\n")
+            }
+            Instruction::Call {
+                function,
+                inputs,
+                output,
+                builtins,
+            } => {
+                if let Some(output) = output {
+                    f.write_fmt(format_args!("{output} = "))?;
+                }
+                f.write_fmt(format_args!("{function}("))?;
+                format_call_args(f, inputs, builtins)?;
+                f.write_str(");\n")
+            }
             Instruction::Return => f.write_str("return;\n"),
             Instruction::Break => f.write_str("break;\n"),
             Instruction::WorkgroupBarrier => f.write_str("workgroupBarrier();\n"),
             Instruction::StorageBarrier => f.write_str("storageBarrier();\n"),
-            Instruction::Length { var, out } => match var {
+            Instruction::Length {
+                var,
+                num_inputs,
+                num_outputs,
+                out,
+            } => match var {
                 Variable::Slice { .. } => f.write_fmt(format_args!("{out} = {var}_length;\n")),
-                _ => f.write_fmt(format_args!("{out} = arrayLength(&{var});\n")),
+                _ => match var.packed_bits() {
+                    Some(bits) => {
+                        // The buffer length is rounded up to whole words, so the logical length
+                        // is read from the info buffer instead.
+                        let index = match var {
+                            Variable::GlobalInputArray(id, _) => *id as usize,
+                            Variable::GlobalOutputArray(id, _) => num_inputs + *id as usize,
+                            _ => {
+                                return f.write_fmt(format_args!(
+                                    "{out} = arrayLength(&{var}) * {}u;\n",
+                                    32 / bits
+                                ))
+                            }
+                        } + 1;
+                        let offset = num_inputs + num_outputs;
+                        let factor = var.item().vectorization_factor();
+
+                        if factor == 1 {
+                            return f.write_fmt(format_args!(
+                                "{out} = info[({offset}u * 2u * info[0]) + {index}u];\n"
+                            ));
+                        }
+
+                        f.write_fmt(format_args!(
+                            "{out} = info[({offset}u * 2u * info[0]) + {index}u] / {factor}u;\n"
+                        ))
+                    }
+                    None => f.write_fmt(format_args!("{out} = arrayLength(&{var});\n")),
+                },
             },
             Instruction::Loop { instructions } => {
                 f.write_fmt(format_args!("loop {{\n"))?;
@@ -594,6 +897,9 @@ for (var {i}: u32 = {start}; {i} < {end}; {increment}) {{
             Instruction::BitwiseAnd { lhs, rhs, out } => {
                 f.write_fmt(format_args!("{out} = {lhs} & {rhs};\n"))
             }
+            Instruction::BitwiseOr { lhs, rhs, out } => {
+                f.write_fmt(format_args!("{out} = {lhs} | {rhs};\n"))
+            }
             Instruction::BitwiseXor { lhs, rhs, out } => {
                 f.write_fmt(format_args!("{out} = {lhs} ^ {rhs};\n"))
             }
@@ -609,28 +915,52 @@ for (var {i}: u32 = {start}; {i} < {end}; {increment}) {{
             Instruction::Ceil { input, out } => {
                 f.write_fmt(format_args!("{out} = ceil({input});\n"))
             }
+            Instruction::Truncate { var, bits } => {
+                f.write_fmt(format_args!("{var} = extractBits({var}, 0u, {bits}u);\n"))
+            }
             Instruction::Subgroup(op) => f.write_fmt(format_args!("{op}")),
             Instruction::Bitcast { input, out } => {
                 f.write_fmt(format_args!("{out} = bitcast<{}>({input});\n", out.elem()))
             }
+            Instruction::AtomicLoad { input, out } if is_atomic_float(input) => {
+                f.write_fmt(format_args!("{out} = bitcast<f32>(atomicLoad({input}));\n"))
+            }
             Instruction::AtomicLoad { input, out } => {
                 f.write_fmt(format_args!("{out} = atomicLoad({input});\n"))
             }
+            Instruction::AtomicStore { input, out } if is_atomic_float(out) => {
+                f.write_fmt(format_args!("atomicStore({out},bitcast<u32>({input}));\n"))
+            }
             Instruction::AtomicStore { input, out } => {
                 f.write_fmt(format_args!("atomicStore({out},{input});\n"))
             }
+            Instruction::AtomicSwap { lhs, rhs, out } if is_atomic_float(lhs) => f.write_fmt(
+                format_args!("{out} = bitcast<f32>(atomicExchange({lhs}, bitcast<u32>({rhs})));\n"),
+            ),
             Instruction::AtomicSwap { lhs, rhs, out } => {
                 f.write_fmt(format_args!("{out} = atomicExchange({lhs}, {rhs});"))
             }
+            Instruction::AtomicAdd { lhs, rhs, out } if is_atomic_float(lhs) => {
+                atomic_float_update(f, lhs, out, format_args!("current + {rhs}"))
+            }
             Instruction::AtomicAdd { lhs, rhs, out } => {
                 f.write_fmt(format_args!("{out} = atomicAdd({lhs}, {rhs});"))
             }
+            Instruction::AtomicSub { lhs, rhs, out } if is_atomic_float(lhs) => {
+                atomic_float_update(f, lhs, out, format_args!("current - {rhs}"))
+            }
             Instruction::AtomicSub { lhs, rhs, out } => {
                 f.write_fmt(format_args!("{out} = atomicSub({lhs}, {rhs});"))
             }
+            Instruction::AtomicMax { lhs, rhs, out } if is_atomic_float(lhs) => {
+                atomic_float_update(f, lhs, out, format_args!("max(current, {rhs})"))
+            }
             Instruction::AtomicMax { lhs, rhs, out } => {
                 f.write_fmt(format_args!("{out} = atomicMax({lhs}, {rhs});"))
             }
+            Instruction::AtomicMin { lhs, rhs, out } if is_atomic_float(lhs) => {
+                atomic_float_update(f, lhs, out, format_args!("min(current, {rhs})"))
+            }
             Instruction::AtomicMin { lhs, rhs, out } => {
                 f.write_fmt(format_args!("{out} = atomicMin({lhs}, {rhs});"))
             }
@@ -643,6 +973,14 @@ for (var {i}: u32 = {start}; {i} < {end}; {increment}) {{
             Instruction::AtomicXor { lhs, rhs, out } => {
                 f.write_fmt(format_args!("{out} = atomicXor({lhs}, {rhs});"))
             }
+            Instruction::AtomicCompareExchangeWeak {
+                lhs,
+                cmp,
+                value,
+                out,
+            } if is_atomic_float(lhs) => f.write_fmt(format_args!(
+                "{out} = bitcast<f32>(atomicCompareExchangeWeak({lhs}, bitcast<u32>({cmp}), bitcast<u32>({value})).old_value);\n"
+            )),
             Instruction::AtomicCompareExchangeWeak {
                 lhs,
                 cmp,
@@ -656,6 +994,52 @@ for (var {i}: u32 = {start}; {i} < {end}; {increment}) {{
     }
 }
 
+fn is_atomic_float(var: &Variable) -> bool {
+    var.elem() == Elem::AtomicF32
+}
+
+/// Update a float atomic with a compare exchange loop on its bits, where `value` computes the new
+/// value from the `current` one.
+///
+/// The bits are compared instead of the floats, so that the loop ends even when they are NaN.
+fn atomic_float_update(
+    f: &mut std::fmt::Formatter<'_>,
+    atomic: &Variable,
+    out: &Variable,
+    value: std::fmt::Arguments<'_>,
+) -> std::fmt::Result {
+    f.write_fmt(format_args!(
+        "{{\nvar current_bits = atomicLoad({atomic});\n"
+    ))?;
+    f.write_str("loop {\n")?;
+    f.write_str("let current = bitcast<f32>(current_bits);\n")?;
+    f.write_fmt(format_args!(
+        "let result = atomicCompareExchangeWeak({atomic}, current_bits, bitcast<u32>({value}));\n"
+    ))?;
+    f.write_str("if result.exchanged {\nbreak;\n}\n")?;
+    f.write_str("current_bits = result.old_value;\n}\n")?;
+    f.write_fmt(format_args!("{out} = bitcast<f32>(current_bits);\n}}\n"))
+}
+
+/// Converts a scalar to another element, 64 bits integers are converted word by word.
+fn cast(value: impl Display, from: Elem, to: Elem) -> String {
+    match (from.is_wide(), to.is_wide()) {
+        (true, true) => format!("{value}"),
+        (false, true) => match from {
+            Elem::I8 | Elem::I16 | Elem::I32 => {
+                format!("vec2<u32>(bitcast<u32>({value}), select(0u, 0xffffffffu, {value} < 0i))")
+            }
+            _ => format!("vec2<u32>(u32({value}), 0u)"),
+        },
+        (true, false) => match to {
+            Elem::Bool => format!("any({value} != vec2<u32>(0u))"),
+            Elem::I8 | Elem::I16 | Elem::I32 => format!("bitcast<i32>({value}.x)"),
+            _ => format!("{value}.x"),
+        },
+        (false, false) => format!("{to}({value})"),
+    }
+}
+
 fn comparison(
     lhs: &Variable,
     rhs: &Variable,
@@ -663,6 +1047,20 @@ fn comparison(
     op: &str,
     f: &mut std::fmt::Formatter<'_>,
 ) -> std::fmt::Result {
+    if lhs.elem().is_wide() {
+        let lower = lower_64(&lhs.elem());
+
+        return match op {
+            "==" => f.write_fmt(format_args!("{out} = all({lhs} == {rhs});\n")),
+            "!=" => f.write_fmt(format_args!("{out} = any({lhs} != {rhs});\n")),
+            "<" => f.write_fmt(format_args!("{out} = {lower}({lhs}, {rhs});\n")),
+            ">" => f.write_fmt(format_args!("{out} = {lower}({rhs}, {lhs});\n")),
+            "<=" => f.write_fmt(format_args!("{out} = !{lower}({rhs}, {lhs});\n")),
+            ">=" => f.write_fmt(format_args!("{out} = !{lower}({lhs}, {rhs});\n")),
+            _ => unreachable!("Unknown comparison {op}"),
+        };
+    }
+
     match out.item() {
         Item::Vec4(_) => {
             let lhs0 = lhs.index(0);
@@ -770,16 +1168,32 @@ fn index(
     out: &Variable,
     offset: Option<Variable>,
 ) -> core::fmt::Result {
-    if out.item().elem().is_atomic() {
+    if let Some(bits) = lhs.packed_bits() {
+        let item = out.item();
+        let elem = lhs.elem();
+        let position = IndexOffset::new(rhs, &offset, 0);
+        let per_word = 32 / bits;
+
+        // Extracting the bits of a signed integer extends its sign.
+        f.write_fmt(format_args!(
+            "{out} = {item}(extractBits(bitcast<{elem}>(atomicLoad(&{lhs}[({position}) / {per_word}u])), ({position}) % {per_word}u * {bits}u, {bits}u));\n"
+        ))
+    } else if out.item().elem().is_atomic() {
         match offset {
             Some(offset) => f.write_fmt(format_args!("let {out} = &{lhs}[{rhs} + {offset}];\n")),
             None => f.write_fmt(format_args!("let {out} = &{lhs}[{rhs}];\n")),
         }
     } else if lhs.elem() != out.elem() {
-        let item = out.item();
-        match offset {
-            Some(offset) => f.write_fmt(format_args!("{out} = {item}({lhs}[{rhs} + {offset}]);\n")),
-            None => f.write_fmt(format_args!("{out} = {item}({lhs}[{rhs}]);\n")),
+        let value = match offset {
+            Some(offset) => format!("{lhs}[{rhs} + {offset}]"),
+            None => format!("{lhs}[{rhs}]"),
+        };
+        match out.item() {
+            Item::Scalar(elem) => {
+                let value = cast(value, lhs.elem(), elem);
+                f.write_fmt(format_args!("{out} = {value};\n"))
+            }
+            item => f.write_fmt(format_args!("{out} = {item}({value});\n")),
         }
     } else {
         match offset {
@@ -796,6 +1210,20 @@ fn index_assign(
     out: &Variable,
     offset: Option<Variable>,
 ) -> core::fmt::Result {
+    if let Some(bits) = out.packed_bits() {
+        let position = IndexOffset::new(lhs, &offset, 0);
+        let per_word = 32 / bits;
+        let mask = u32::MAX >> (32 - bits);
+
+        // The other elements of the word are left untouched, since other units can write them.
+        f.write_fmt(format_args!(
+            "atomicAnd(&{out}[({position}) / {per_word}u], ~({mask}u << (({position}) % {per_word}u * {bits}u)));\n"
+        ))?;
+        return f.write_fmt(format_args!(
+            "atomicOr(&{out}[({position}) / {per_word}u], insertBits(0u, u32({rhs}), ({position}) % {per_word}u * {bits}u, {bits}u));\n"
+        ));
+    }
+
     match lhs.item() {
         Item::Vec4(elem) => {
             let lhs0 = IndexOffset::new(lhs, &offset, 0);
@@ -874,6 +1302,9 @@ fn index_assign(
                         }
                     }
                     f.write_str(");\n")
+                } else if let Item::Scalar(elem) = item_out {
+                    let value = cast(rhs, rhs.elem(), elem);
+                    f.write_fmt(format_args!("{out}[{lhs}] = {value};\n"))
                 } else {
                     f.write_fmt(format_args!("{out}[{lhs}] = {item_out}({rhs});\n"))
                 }
@@ -2,6 +2,7 @@ mod base;
 mod body;
 mod compiler;
 mod extension;
+mod function;
 mod instructions;
 mod shader;
 mod subgroup;
@@ -10,6 +11,7 @@ pub(crate) use base::*;
 pub(crate) use body::*;
 pub use compiler::*;
 pub(crate) use extension::*;
+pub(crate) use function::*;
 pub(crate) use instructions::*;
 pub(crate) use shader::*;
 pub(crate) use subgroup::*;
@@ -1,4 +1,4 @@
-use super::{Body, Extension, Item};
+use super::{Body, Builtins, Extension, Function, Item};
 use cubecl_core::{ir::CubeDim, CompilerRepresentation};
 use std::fmt::Display;
 
@@ -44,9 +44,9 @@ impl SharedMemory {
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct LocalArray {
     pub index: u16,
-    item: Item,
-    name: u8,
-    size: u32,
+    pub(crate) item: Item,
+    pub(crate) name: u8,
+    pub(crate) size: u32,
 }
 
 impl LocalArray {
@@ -66,16 +66,9 @@ pub struct ComputeShader {
     pub outputs: Vec<Binding>,
     pub named: Vec<(String, Binding)>,
     pub shared_memories: Vec<SharedMemory>,
-    pub local_arrays: Vec<LocalArray>,
     pub workgroup_size: CubeDim,
-    pub global_invocation_id: bool,
-    pub local_invocation_index: bool,
-    pub local_invocation_id: bool,
-    pub num_workgroups: bool,
-    pub workgroup_id: bool,
-    pub num_workgroups_no_axis: bool,
-    pub workgroup_id_no_axis: bool,
-    pub workgroup_size_no_axis: bool,
+    pub builtins: Builtins,
+    pub functions: Vec<Function>,
     pub body: Body,
     pub extensions: Vec<Extension>,
 }
@@ -108,6 +101,10 @@ const WORKGROUP_SIZE_Z = {}u;\n",
             self.workgroup_size.x, self.workgroup_size.y, self.workgroup_size.z
         ))?;
 
+        for function in self.functions.iter() {
+            f.write_fmt(format_args!("\n{function}\n"))?;
+        }
+
         f.write_fmt(format_args!(
             "
 @compute
@@ -117,50 +114,30 @@ fn main(
             self.workgroup_size.x, self.workgroup_size.y, self.workgroup_size.z
         ))?;
 
-        if self.global_invocation_id {
+        if self.builtins.global_invocation_id {
             f.write_str("    @builtin(global_invocation_id) global_id: vec3<u32>,\n")?;
         }
 
-        if self.local_invocation_index {
+        if self.builtins.local_invocation_index {
             f.write_str("    @builtin(local_invocation_index) local_idx: u32,\n")?;
         }
 
-        if self.local_invocation_id {
+        if self.builtins.local_invocation_id {
             f.write_str("    @builtin(local_invocation_id) local_invocation_id: vec3<u32>,\n")?;
         }
 
-        if self.num_workgroups {
+        if self.builtins.num_workgroups {
             f.write_str("    @builtin(num_workgroups) num_workgroups: vec3<u32>,\n")?;
         }
 
-        if self.workgroup_id {
+        if self.builtins.workgroup_id {
             f.write_str("    @builtin(workgroup_id) workgroup_id: vec3<u32>,\n")?;
         }
 
         // Open body
         f.write_fmt(format_args!(") {{"))?;
 
-        // Local arrays
-        for array in self.local_arrays.iter() {
-            f.write_fmt(format_args!(
-                "var a_{}_{}: array<{}, {}>;\n\n",
-                array.name, array.index, array.item, array.size
-            ))?;
-        }
-
         // Body
-        if self.workgroup_id_no_axis {
-            f.write_str("let workgroup_id_no_axis = (num_workgroups.y * num_workgroups.x * workgroup_id.z) + (num_workgroups.x * workgroup_id.y) + workgroup_id.x;\n")?;
-        }
-
-        if self.workgroup_size_no_axis {
-            f.write_str("let workgroup_size_no_axis = WORKGROUP_SIZE_X * WORKGROUP_SIZE_Y * WORKGROUP_SIZE_Z;\n")?;
-        }
-
-        if self.num_workgroups_no_axis {
-            f.write_str("let num_workgroups_no_axis = num_workgroups.x * num_workgroups.y * num_workgroups.z;\n")?;
-        }
-
         f.write_fmt(format_args!("{}", self.body))?;
 
         // Close body
@@ -199,9 +176,18 @@ impl ComputeShader {
         binding: &Binding,
         num_entry: usize,
     ) -> core::fmt::Result {
-        let ty = match binding.size {
-            Some(size) => format!("array<{}, {}>", binding.item, size),
-            None => format!("array<{}>", binding.item),
+        let ty = match (binding.size, binding.item.elem().packed_bits()) {
+            // Packed elements are written with atomics, since other units can write the other
+            // elements of the same word.
+            (Some(size), Some(bits)) => {
+                format!(
+                    "array<atomic<u32>, {}>",
+                    (size * bits as usize).div_ceil(32)
+                )
+            }
+            (None, Some(_)) => "array<atomic<u32>>".to_string(),
+            (Some(size), None) => format!("array<{}, {}>", binding.item, size),
+            (None, None) => format!("array<{}>", binding.item),
         };
 
         f.write_fmt(format_args!(
@@ -48,6 +48,46 @@ pub enum Subgroup {
         input: Variable,
         out: Variable,
     },
+    Shuffle {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    ShuffleXor {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    ShuffleUp {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    ShuffleDown {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    InclusiveSum {
+        input: Variable,
+        out: Variable,
+    },
+    ExclusiveSum {
+        input: Variable,
+        out: Variable,
+    },
+    InclusiveProd {
+        input: Variable,
+        out: Variable,
+    },
+    ExclusiveProd {
+        input: Variable,
+        out: Variable,
+    },
+    Ballot {
+        input: Variable,
+        out: Variable,
+    },
 }
 
 impl Display for Subgroup {
@@ -84,6 +124,33 @@ impl Display for Subgroup {
             Subgroup::Max { input, out } => {
                 f.write_fmt(format_args!("{out} = subgroupMax({input});\n"))
             }
+            Subgroup::Shuffle { lhs, rhs, out } => {
+                f.write_fmt(format_args!("{out} = subgroupShuffle({lhs}, {rhs});\n"))
+            }
+            Subgroup::ShuffleXor { lhs, rhs, out } => {
+                f.write_fmt(format_args!("{out} = subgroupShuffleXor({lhs}, {rhs});\n"))
+            }
+            Subgroup::ShuffleUp { lhs, rhs, out } => {
+                f.write_fmt(format_args!("{out} = subgroupShuffleUp({lhs}, {rhs});\n"))
+            }
+            Subgroup::ShuffleDown { lhs, rhs, out } => {
+                f.write_fmt(format_args!("{out} = subgroupShuffleDown({lhs}, {rhs});\n"))
+            }
+            Subgroup::InclusiveSum { input, out } => {
+                f.write_fmt(format_args!("{out} = subgroupInclusiveAdd({input});\n"))
+            }
+            Subgroup::ExclusiveSum { input, out } => {
+                f.write_fmt(format_args!("{out} = subgroupExclusiveAdd({input});\n"))
+            }
+            Subgroup::InclusiveProd { input, out } => {
+                f.write_fmt(format_args!("{out} = subgroupInclusiveMul({input});\n"))
+            }
+            Subgroup::ExclusiveProd { input, out } => {
+                f.write_fmt(format_args!("{out} = subgroupExclusiveMul({input});\n"))
+            }
+            Subgroup::Ballot { input, out } => {
+                f.write_fmt(format_args!("{out} = subgroupBallot({input});\n"))
+            }
         }
     }
 }
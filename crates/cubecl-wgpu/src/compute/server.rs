@@ -2,17 +2,31 @@ use std::num::NonZero;
 
 use super::WgpuStorage;
 use alloc::{borrow::Cow, sync::Arc};
-use cubecl_common::{reader::Reader, sync_type::SyncType};
+use core::time::Duration;
+use cubecl_common::{
+    benchmark::{TimestampsError, TimestampsResult},
+    reader::Reader,
+    sync_type::SyncType,
+};
 use cubecl_core::{compute::DebugInformation, prelude::*, server::Handle, FeatureSet, KernelId};
 use cubecl_runtime::{
     debug::DebugLogger,
-    memory_management::MemoryManagement,
-    server::{self, ComputeServer},
+    memory_management::{MemoryManagement, MemoryUsage},
+    profiler::{ProfileEventKind, ProfileTrace, Profiler},
+    server::{self, ComputeError, ComputeServer},
+    staging::StagingBuffer,
     storage::{ComputeStorage, StorageId},
+    stream::{EventId, OrderedStreams, StreamId},
     ExecutionMode,
 };
 use hashbrown::HashMap;
-use wgpu::{CommandEncoder, ComputePass, ComputePipeline, ShaderModuleDescriptor};
+use wgpu::{
+    CommandEncoder, ComputePass, ComputePipeline, QuerySet, QuerySetDescriptor, QueryType,
+    ShaderModuleDescriptor,
+};
+
+/// The maximum number of compute passes with timestamps per command encoder.
+const TIMESTAMPS_MAX_PASSES: u32 = 512;
 
 /// Wgpu compute server.
 #[derive(Debug)]
@@ -27,6 +41,20 @@ pub struct WgpuServer<MM: MemoryManagement<WgpuStorage>> {
     pipelines: HashMap<KernelId, Arc<ComputePipeline>>,
     tasks_max: usize,
     logger: DebugLogger,
+    profiler: Profiler,
+    /// Every stream is submitted to the same queue, which executes the tasks in order.
+    streams: OrderedStreams,
+    timestamps: Option<Timestamps>,
+}
+
+/// Timestamps written at the beginning and at the end of every compute pass.
+#[derive(Debug)]
+struct Timestamps {
+    query_set: QuerySet,
+    /// The number of compute passes with timestamps in the current encoder.
+    num_passes: u32,
+    /// The buffers where the timestamps of the submitted encoders are copied.
+    submitted: Vec<wgpu::Buffer>,
 }
 
 fn create_encoder(device: &wgpu::Device) -> CommandEncoder {
@@ -57,6 +85,9 @@ where
             pipelines: HashMap::new(),
             tasks_max,
             logger: DebugLogger::new(),
+            profiler: Profiler::new(),
+            streams: OrderedStreams::default(),
+            timestamps: None,
         }
     }
 
@@ -64,25 +95,35 @@ where
         &mut self,
         kernel: <Self as ComputeServer>::Kernel,
         mode: ExecutionMode,
-    ) -> Arc<ComputePipeline> {
+    ) -> Result<Arc<ComputePipeline>, ComputeError> {
         let mut kernel_id = kernel.id();
         kernel_id.mode(mode);
 
         if let Some(pipeline) = self.pipelines.get(&kernel_id) {
-            return pipeline.clone();
+            return Ok(pipeline.clone());
         }
 
-        let mut compile = kernel.compile(mode);
+        let started = self.profiler.now();
+        let mut compile = kernel.try_compile(mode)?;
         if self.logger.is_activated() {
             compile.debug_info = Some(DebugInformation::new("wgsl", kernel_id.clone()));
+            compile.ir = kernel.ir();
         }
 
         let compile = self.logger.debug(compile);
         let pipeline = self.compile_source(&compile.source, mode);
 
+        self.profiler.register(
+            started,
+            ProfileEventKind::Compile {
+                name: compile.name.unwrap_or_default().into(),
+                id: kernel_id.to_string(),
+            },
+        );
+
         self.pipelines.insert(kernel_id.clone(), pipeline.clone());
 
-        pipeline
+        Ok(pipeline)
     }
 
     fn compile_source(&self, source: &str, mode: ExecutionMode) -> Arc<ComputePipeline> {
@@ -116,6 +157,41 @@ where
     fn clear_compute_pass(&mut self) {
         self.current_pass = None;
     }
+
+    /// Copy the timestamps of the current encoder to a buffer that can be read once the encoder is
+    /// submitted.
+    fn resolve_timestamps(&mut self) {
+        let Some(timestamps) = self.timestamps.as_mut() else {
+            return;
+        };
+
+        if timestamps.num_passes == 0 {
+            return;
+        }
+
+        let num_queries = timestamps.num_passes * 2;
+        let size = (num_queries * wgpu::QUERY_SIZE) as u64;
+        let resolve_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("CubeCL Timestamps Resolve"),
+            size,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let read_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("CubeCL Timestamps Read"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        self.encoder
+            .resolve_query_set(&timestamps.query_set, 0..num_queries, &resolve_buffer, 0);
+        self.encoder
+            .copy_buffer_to_buffer(&resolve_buffer, 0, &read_buffer, 0, size);
+
+        timestamps.num_passes = 0;
+        timestamps.submitted.push(read_buffer);
+    }
 }
 
 impl<MM> ComputeServer for WgpuServer<MM>
@@ -128,10 +204,13 @@ where
     type MemoryManagement = MM;
     type FeatureSet = FeatureSet;
 
-    fn read(&mut self, binding: server::Binding<Self>) -> Reader {
-        let resource = self.get_resource(binding);
+    fn try_read(&mut self, binding: server::Binding<Self>) -> Result<Reader, ComputeError> {
+        let started = self.profiler.now();
+        let len = binding.size;
+        let resource = self.memory_management.try_get_resource(binding.memory)?;
 
-        let size = resource.size();
+        // Copies must be aligned, so the padding of the reserved memory is read and then trimmed.
+        let size = aligned_size(len) as u64;
         let read_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
             label: None,
             size,
@@ -160,9 +239,16 @@ where
                 .expect("Unable to send buffer slice result to async channel.");
         });
 
+        if started.is_some() {
+            // Wait for the mapping, so the recorded duration includes the transfer.
+            self.device.poll(wgpu::Maintain::Wait);
+            self.profiler
+                .register(started, ProfileEventKind::Read { size: len });
+        }
+
         let device = self.device.clone();
 
-        Box::pin(async move {
+        Ok(Box::pin(async move {
             // Now wait for the GPU to finish.
             device.poll(wgpu::Maintain::Wait);
 
@@ -175,13 +261,13 @@ where
                 .expect("Failed to map buffer");
 
             let data = slice.get_mapped_range();
-            let result = bytemuck::cast_slice(&data).to_vec();
+            let result = data[..len].to_vec();
 
             drop(data);
             read_buffer.unmap();
 
             result
-        })
+        }))
     }
 
     fn get_resource(
@@ -197,53 +283,88 @@ where
     ///
     /// This is important, otherwise the compute passes are going to be too small and we won't be able to
     /// fully utilize the GPU.
-    fn create(&mut self, data: &[u8]) -> server::Handle<Self> {
+    fn try_create(&mut self, data: &[u8]) -> Result<server::Handle<Self>, ComputeError> {
+        let started = self.profiler.now();
+
+        let size = aligned_size(data.len());
+
         // Reserve memory on some storage we haven't yet used this command queue.
         let memory = self
             .memory_management
-            .reserve(data.len(), &self.compute_storage_used);
+            .try_reserve(size, &self.compute_storage_used)?;
 
-        let handle = Handle::new(memory);
+        let handle = Handle::new(memory, data.len());
 
-        if let Some(len) = NonZero::new(data.len() as u64) {
+        if let Some(len) = NonZero::new(size as u64) {
             let resource = self.get_resource(handle.clone().binding());
 
             // Write to the staging buffer. Next queue submission this will copy the data to the GPU.
-            self.queue
+            let mut buffer = self
+                .queue
                 .write_buffer_with(&resource.buffer, resource.offset(), len)
-                .expect("Failed to write to staging buffer.")
-                .copy_from_slice(data);
+                .expect("Failed to write to staging buffer.");
+            buffer[..data.len()].copy_from_slice(data);
+            buffer[data.len()..].fill(0);
         }
 
-        handle
+        self.profiler
+            .register(started, ProfileEventKind::Create { size: data.len() });
+
+        Ok(handle)
+    }
+
+    fn try_empty(&mut self, size: usize) -> Result<server::Handle<Self>, ComputeError> {
+        let memory = self
+            .memory_management
+            .try_reserve(aligned_size(size), &[])?;
+        Ok(server::Handle::new(memory, size))
     }
 
-    fn empty(&mut self, size: usize) -> server::Handle<Self> {
-        server::Handle::new(self.memory_management.reserve(size, &[]))
+    fn try_staging(&mut self, size: usize) -> Result<StagingBuffer, ComputeError> {
+        Ok(vec![0; size].into())
     }
 
-    unsafe fn execute(
+    /// Wgpu doesn't expose page-locked memory, the data is written to a staging buffer of the
+    /// queue and copied to the device with the next submission, like [create](Self::try_create).
+    fn try_upload(
+        &mut self,
+        buffer: StagingBuffer,
+        stream: StreamId,
+    ) -> Result<server::Handle<Self>, ComputeError> {
+        self.streams.validate(stream)?;
+        self.try_create(&buffer)
+    }
+
+    unsafe fn try_execute(
         &mut self,
         kernel: Self::Kernel,
         count: Self::DispatchOptions,
         bindings: Vec<server::Binding<Self>>,
         mode: ExecutionMode,
-    ) {
-        let pipeline = self.pipeline(kernel, mode);
+        stream: StreamId,
+    ) -> Result<(), ComputeError> {
+        self.streams.validate(stream)?;
+
+        let profile = self
+            .profiler
+            .is_activated()
+            .then(|| (kernel.name(), kernel.id().to_string()));
+        let pipeline = self.pipeline(kernel, mode)?;
+        let started = self.profiler.now();
         let group_layout = pipeline.get_bind_group_layout(0);
 
         // Store all the resources we'll be using. This could be eliminated if
         // there was a way to tie the lifetime of the resource to the memory handle.
-        let resources: Vec<_> = bindings
+        let resources = bindings
             .iter()
             .map(|binding| {
-                let resource_handle = self.memory_management.get(binding.memory.clone());
+                let resource_handle = self.memory_management.try_get(binding.memory.clone())?;
                 // Keep track of the storage we've used so far.
                 self.compute_storage_used.push(resource_handle.id);
 
-                self.memory_management.storage().get(&resource_handle)
+                Ok(self.memory_management.storage().get(&resource_handle))
             })
-            .collect();
+            .collect::<Result<Vec<_>, ComputeError>>()?;
 
         let entries = &resources
             .iter()
@@ -263,20 +384,40 @@ where
         // First resolve the dispatch buffer if needed. The weird ordering is because the lifetime of this
         // needs to be longer than the compute pass, so we can't do this just before dispatching.
         let dispatch_resource = match count.clone() {
-            CubeCount::Dynamic(binding) => Some(self.get_resource(binding)),
+            CubeCount::Dynamic(binding) => {
+                Some(self.memory_management.try_get_resource(binding.memory)?)
+            }
             _ => None,
         };
 
+        // The timestamps of the passes in the current encoder must fit in the query set.
+        if self.current_pass.is_none()
+            && matches!(&self.timestamps, Some(timestamps) if timestamps.num_passes >= TIMESTAMPS_MAX_PASSES)
+        {
+            self.sync(SyncType::Flush);
+        }
+
         self.tasks_count += 1;
 
         // Start a new compute pass if needed. The forget_lifetime allows
         // to store this with a 'static lifetime, but the compute pass must
         // be dropped before the encoder. This isn't unsafe - it's still checked at runtime.
         let pass = self.current_pass.get_or_insert_with(|| {
+            let timestamp_writes = self.timestamps.as_mut().map(|timestamps| {
+                let index = timestamps.num_passes * 2;
+                timestamps.num_passes += 1;
+
+                wgpu::ComputePassTimestampWrites {
+                    query_set: &timestamps.query_set,
+                    beginning_of_pass_write_index: Some(index),
+                    end_of_pass_write_index: Some(index + 1),
+                }
+            });
+
             self.encoder
                 .begin_compute_pass(&wgpu::ComputePassDescriptor {
                     label: None,
-                    timestamp_writes: None,
+                    timestamp_writes,
                 })
                 .forget_lifetime()
         });
@@ -284,6 +425,11 @@ where
         pass.set_pipeline(&pipeline);
         pass.set_bind_group(0, &bind_group, &[]);
 
+        let cube_count = match count {
+            CubeCount::Static(x, y, z) => Some([x, y, z]),
+            CubeCount::Dynamic(_) => None,
+        };
+
         match count {
             CubeCount::Static(x, y, z) => {
                 pass.dispatch_workgroups(x, y, z);
@@ -294,14 +440,44 @@ where
             }
         }
 
-        if self.tasks_count >= self.tasks_max {
+        if let Some((name, id)) = profile {
+            // Wait for the kernel to complete to measure its execution time.
+            self.sync(SyncType::Wait);
+            self.profiler.register(
+                started,
+                ProfileEventKind::Execute {
+                    name: name.into(),
+                    id,
+                    cube_count,
+                    binding_sizes: resources
+                        .iter()
+                        .map(|resource| resource.size() as usize)
+                        .collect(),
+                },
+            );
+        } else if self.tasks_count >= self.tasks_max {
             self.sync(SyncType::Flush);
         }
+
+        Ok(())
+    }
+
+    fn try_create_stream(&mut self) -> Result<StreamId, ComputeError> {
+        Ok(self.streams.create())
+    }
+
+    fn try_record_event(&mut self, stream: StreamId) -> Result<EventId, ComputeError> {
+        self.streams.record_event(stream)
+    }
+
+    fn try_wait_event(&mut self, stream: StreamId, event: EventId) -> Result<(), ComputeError> {
+        self.streams.wait_event(stream, event)
     }
 
     fn sync(&mut self, sync_type: SyncType) {
         // End the current compute pass.
         self.clear_compute_pass();
+        self.resolve_timestamps();
         let new_encoder = create_encoder(&self.device);
         let encoder = std::mem::replace(&mut self.encoder, new_encoder);
         self.queue.submit([encoder.finish()]);
@@ -316,4 +492,99 @@ where
         // Cleanup allocations and deallocations.
         self.memory_management.storage().perform_deallocations();
     }
+
+    fn memory_usage(&mut self) -> MemoryUsage {
+        self.memory_management.memory_usage()
+    }
+
+    fn memory_cleanup(&mut self) {
+        self.memory_management.cleanup();
+        // The buffers are destroyed once the pending tasks are submitted.
+        self.sync(SyncType::Flush);
+    }
+
+    fn enable_timestamps(&mut self) {
+        if self.timestamps.is_some()
+            || !self
+                .device
+                .features()
+                .contains(wgpu::Features::TIMESTAMP_QUERY)
+        {
+            return;
+        }
+
+        // The current compute pass doesn't write timestamps.
+        self.clear_compute_pass();
+        self.timestamps = Some(Timestamps {
+            query_set: self.device.create_query_set(&QuerySetDescriptor {
+                label: Some("CubeCL Timestamps"),
+                ty: QueryType::Timestamp,
+                count: TIMESTAMPS_MAX_PASSES * 2,
+            }),
+            num_passes: 0,
+            submitted: Vec::new(),
+        });
+    }
+
+    fn disable_timestamps(&mut self) {
+        // The current compute pass writes to the query set.
+        self.clear_compute_pass();
+        self.timestamps = None;
+    }
+
+    fn timestamps_enabled(&mut self) -> bool {
+        self.timestamps.is_some()
+    }
+
+    fn sync_elapsed(&mut self) -> TimestampsResult {
+        if !self
+            .device
+            .features()
+            .contains(wgpu::Features::TIMESTAMP_QUERY)
+        {
+            return Err(TimestampsError::Unavailable);
+        }
+        if self.timestamps.is_none() {
+            return Err(TimestampsError::Disabled);
+        }
+
+        self.sync(SyncType::Wait);
+
+        let buffers = core::mem::take(&mut self.timestamps.as_mut().unwrap().submitted);
+        for buffer in buffers.iter() {
+            buffer.slice(..).map_async(wgpu::MapMode::Read, |_| ());
+        }
+        self.device.poll(wgpu::Maintain::Wait);
+
+        let period = self.queue.get_timestamp_period() as f64;
+        let mut elapsed = 0.0;
+
+        for buffer in buffers {
+            let data = buffer.slice(..).get_mapped_range();
+            let timestamps: &[u64] = bytemuck::cast_slice(&data);
+
+            for pass in timestamps.chunks_exact(2) {
+                elapsed += pass[1].saturating_sub(pass[0]) as f64 * period;
+            }
+
+            drop(data);
+            buffer.unmap();
+        }
+
+        Ok(Duration::from_nanos(elapsed as u64))
+    }
+
+    fn start_profile(&mut self) {
+        self.profiler.start();
+    }
+
+    fn end_profile(&mut self) -> ProfileTrace {
+        self.profiler.end()
+    }
+}
+
+/// Bindings and copies have to be aligned on 4 bytes, which isn't the case for the data of 8 and
+/// 16 bits elements, so the buffers are padded.
+fn aligned_size(size: usize) -> usize {
+    size.next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT as usize)
 }
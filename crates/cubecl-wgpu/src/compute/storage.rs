@@ -1,4 +1,7 @@
-use cubecl_runtime::storage::{ComputeStorage, StorageHandle, StorageId, StorageUtilization};
+use cubecl_runtime::{
+    server::ComputeError,
+    storage::{ComputeStorage, StorageHandle, StorageId, StorageUtilization},
+};
 use hashbrown::HashMap;
 use std::{num::NonZeroU64, sync::Arc};
 
@@ -102,9 +105,11 @@ impl ComputeStorage for WgpuStorage {
         }
     }
 
-    fn alloc(&mut self, size: usize) -> StorageHandle {
+    fn try_alloc(&mut self, size: usize) -> Result<StorageHandle, ComputeError> {
         let id = StorageId::new();
-        let buffer = Arc::new(self.device.create_buffer(&wgpu::BufferDescriptor {
+
+        self.device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
             label: None,
             size: size as u64,
             usage: wgpu::BufferUsages::COPY_DST
@@ -112,11 +117,20 @@ impl ComputeStorage for WgpuStorage {
                 | wgpu::BufferUsages::COPY_SRC
                 | wgpu::BufferUsages::INDIRECT,
             mapped_at_creation: false,
-        }));
+        });
+
+        if let Some(error) = pollster::block_on(self.device.pop_error_scope()) {
+            buffer.destroy();
+
+            return Err(ComputeError::OutOfMemory {
+                size,
+                reason: error.to_string(),
+            });
+        }
 
-        self.memory.insert(id, buffer);
+        self.memory.insert(id, Arc::new(buffer));
 
-        StorageHandle::new(id, StorageUtilization::Full(size))
+        Ok(StorageHandle::new(id, StorageUtilization::Full(size)))
     }
 
     fn dealloc(&mut self, id: StorageId) {
@@ -4,11 +4,17 @@ use crate::{
     AutoGraphicsApi, GraphicsApi, WgpuDevice,
 };
 use alloc::sync::Arc;
-use cubecl_core::{Feature, FeatureSet, Runtime};
+use cubecl_core::{
+    ir::{Elem, FloatKind},
+    Feature, FeatureSet, Runtime,
+};
 use cubecl_runtime::{
     channel::MutexComputeChannel,
     client::ComputeClient,
-    memory_management::dynamic::{DynamicMemoryManagement, DynamicMemoryManagementOptions},
+    memory_management::{
+        dynamic::{DynamicMemoryManagement, DynamicMemoryManagementOptions},
+        simple::DeallocStrategy,
+    },
     ComputeRuntime,
 };
 use wgpu::DeviceDescriptor;
@@ -43,12 +49,19 @@ impl Runtime for WgpuRuntime {
     fn name() -> &'static str {
         "wgpu"
     }
+
+    fn require_array_lengths() -> bool {
+        // Narrow integers are packed in words, so the buffer length isn't the array length.
+        true
+    }
 }
 
 /// The values that control how a WGPU Runtime will perform its calculations.
 pub struct RuntimeOptions {
     /// Control the amount of compute tasks to be aggregated into a single GPU command.
     pub tasks_max: usize,
+    /// Control when the memory chunks that aren't used anymore are released back to the device.
+    pub dealloc_strategy: DeallocStrategy,
 }
 
 impl Default for RuntimeOptions {
@@ -65,7 +78,10 @@ impl Default for RuntimeOptions {
             Err(_) => DEFAULT_MAX_TASKS,
         };
 
-        Self { tasks_max }
+        Self {
+            tasks_max,
+            dealloc_strategy: DeallocStrategy::Never,
+        }
     }
 }
 
@@ -123,7 +139,8 @@ fn create_client(
         DynamicMemoryManagementOptions::preset(
             limits.max_storage_buffer_binding_size as usize,
             limits.min_storage_buffer_offset_alignment as usize,
-        ),
+        )
+        .with_dealloc_strategy(options.dealloc_strategy),
     );
     let server = WgpuServer::new(memory_management, device_wgpu, queue, options.tasks_max);
     let channel = MutexComputeChannel::new(server);
@@ -135,6 +152,12 @@ fn create_client(
         features_cube.register(Feature::Subcube);
     }
 
+    // Cooperative matrices are emulated, with the only float type WGSL supports.
+    let f32 = Elem::Float(FloatKind::F32);
+    features_cube.register_emulated_cmma(&[(f32, f32, f32)]);
+    // Float atomics are updated with a compare exchange loop.
+    features_cube.register(Feature::AtomicFloat(FloatKind::F32));
+
     ComputeClient::new(channel, Arc::new(features_cube))
 }
 
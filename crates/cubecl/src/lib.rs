@@ -6,5 +6,20 @@ pub use cubecl_wgpu as wgpu;
 #[cfg(feature = "cuda")]
 pub use cubecl_cuda as cuda;
 
+#[cfg(feature = "hip")]
+pub use cubecl_hip as hip;
+
+#[cfg(feature = "opencl")]
+pub use cubecl_opencl as opencl;
+
+#[cfg(feature = "cpu")]
+pub use cubecl_cpu as cpu;
+
+#[cfg(feature = "spirv")]
+pub use cubecl_spirv as spirv;
+
+#[cfg(feature = "msl")]
+pub use cubecl_msl as msl;
+
 #[cfg(feature = "linalg")]
 pub use cubecl_linalg as linalg;